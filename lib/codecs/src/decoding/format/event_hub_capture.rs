@@ -0,0 +1,319 @@
+use bytes::{Buf, Bytes};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use lookup::{event_path, owned_value_path};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{kind::Collection, Kind};
+
+use super::{avro::try_from, Deserializer};
+
+type VrlValue = vrl::value::Value;
+type AvroValue = apache_avro::types::Value;
+
+/// The format of `EnqueuedTimeUtc`, such as `9/26/2018 11:27:33 PM`.
+const ENQUEUED_TIME_FORMAT: &str = "%m/%d/%Y %I:%M:%S %p";
+
+/// Config used to build a `EventHubCaptureDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct EventHubCaptureDeserializerConfig;
+
+impl EventHubCaptureDeserializerConfig {
+    /// Build the `EventHubCaptureDeserializer` from this configuration.
+    pub fn build(&self) -> EventHubCaptureDeserializer {
+        EventHubCaptureDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let definition = match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition = schema::Definition::empty_legacy_namespace();
+
+                if let Some(message_key) = log_schema().message_key() {
+                    definition = definition.with_event_field(
+                        message_key,
+                        Kind::bytes().or_null(),
+                        Some("message"),
+                    );
+                }
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.with_event_field(
+                        timestamp_key,
+                        Kind::timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [log_namespace],
+            )
+            .with_event_field(
+                &owned_value_path!("message"),
+                Kind::bytes().or_null(),
+                Some("message"),
+            )
+            .with_event_field(
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            ),
+        };
+
+        definition
+            .with_event_field(&owned_value_path!("sequence_number"), Kind::integer(), None)
+            .with_event_field(&owned_value_path!("offset"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("properties"),
+                Kind::object(Collection::any()),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("system_properties"),
+                Kind::object(Collection::any()),
+                None,
+            )
+    }
+}
+
+/// Deserializer that builds one `Event` per record of an [Event Hubs Capture][capture] Avro file.
+///
+/// Capture writes the events of an event hub to blob storage as Avro object container files,
+/// whose records wrap the body of each event in an envelope with its properties and the time it
+/// was enqueued at. Each byte frame is expected to hold a complete file.
+///
+/// [capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+#[derive(Debug, Clone, Default)]
+pub struct EventHubCaptureDeserializer;
+
+impl Deserializer for EventHubCaptureDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let reader = apache_avro::Reader::new(bytes.reader())?;
+        let mut events = SmallVec::new();
+        for record in reader {
+            let AvroValue::Record(fields) = record? else {
+                return Err(vector_common::Error::from(
+                    "Expected an Event Hubs Capture record",
+                ));
+            };
+            events.push(Event::Log(record_to_log(fields, log_namespace)?));
+        }
+
+        Ok(events)
+    }
+}
+
+fn record_to_log(
+    fields: Vec<(String, AvroValue)>,
+    log_namespace: LogNamespace,
+) -> vector_common::Result<LogEvent> {
+    let mut log = LogEvent::default();
+    let mut timestamp = None;
+
+    for (name, value) in fields {
+        let value = try_from(value)?;
+        match name.as_str() {
+            "Body" => match log_namespace {
+                LogNamespace::Vector => {
+                    log.insert(event_path!("message"), value);
+                }
+                LogNamespace::Legacy => {
+                    if let Some(message_key) = log_schema().message_key_target_path() {
+                        log.insert(message_key, value);
+                    }
+                }
+            },
+            "EnqueuedTimeUtc" => {
+                timestamp = value.as_str().and_then(|time| parse_enqueued_time(&time));
+            }
+            "SequenceNumber" => {
+                log.insert(event_path!("sequence_number"), value);
+            }
+            "Offset" => {
+                log.insert(event_path!("offset"), value);
+            }
+            "Properties" => {
+                log.insert(event_path!("properties"), value);
+            }
+            "SystemProperties" => {
+                log.insert(event_path!("system_properties"), value);
+            }
+            // Any fields added to the envelope in the future are kept as they are.
+            _ => {
+                log.insert(event_path!(name.as_str()), value);
+            }
+        }
+    }
+
+    let timestamp = timestamp.unwrap_or_else(Utc::now);
+    match log_namespace {
+        LogNamespace::Vector => {
+            log.insert(event_path!("timestamp"), timestamp);
+        }
+        LogNamespace::Legacy => {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                log.insert(timestamp_key, timestamp);
+            }
+        }
+    }
+
+    Ok(log)
+}
+
+/// Parses the time that an event was enqueued at, which Capture writes in the invariant culture
+/// format of .NET, falling back to RFC 3339.
+fn parse_enqueued_time(time: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(time, ENQUEUED_TIME_FORMAT)
+        .map(|time| time.and_utc())
+        .or_else(|_| DateTime::parse_from_rfc3339(time).map(|time| time.with_timezone(&Utc)))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use apache_avro::{types::Record, Schema, Writer};
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const CAPTURE_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "EventData",
+        "namespace": "Microsoft.ServiceBus.Messaging",
+        "fields": [
+            { "name": "SequenceNumber", "type": "long" },
+            { "name": "Offset", "type": "string" },
+            { "name": "EnqueuedTimeUtc", "type": "string" },
+            { "name": "SystemProperties", "type": { "type": "map", "values": ["long", "double", "string", "bytes"] } },
+            { "name": "Properties", "type": { "type": "map", "values": ["long", "double", "string", "bytes", "null"] } },
+            { "name": "Body", "type": ["null", "bytes"] }
+        ]
+    }"#;
+
+    fn write_file(bodies: &[&str]) -> Bytes {
+        let schema = Schema::parse_str(CAPTURE_SCHEMA).unwrap();
+        let mut writer = Writer::new(&schema, Vec::new());
+
+        for (index, body) in bodies.iter().enumerate() {
+            let mut record = Record::new(&schema).unwrap();
+            record.put("SequenceNumber", index as i64);
+            record.put("Offset", (index * 100).to_string());
+            record.put("EnqueuedTimeUtc", "9/26/2018 11:27:33 PM");
+            record.put(
+                "SystemProperties",
+                AvroValue::Map(HashMap::from([(
+                    "x-opt-enqueued-time".to_string(),
+                    AvroValue::Union(0, Box::new(AvroValue::Long(1_538_004_453_000))),
+                )])),
+            );
+            record.put(
+                "Properties",
+                AvroValue::Map(HashMap::from([(
+                    "tenant".to_string(),
+                    AvroValue::Union(2, Box::new(AvroValue::String("acme".to_string()))),
+                )])),
+            );
+            record.put(
+                "Body",
+                AvroValue::Union(1, Box::new(AvroValue::Bytes(body.as_bytes().to_vec()))),
+            );
+            writer.append(record).unwrap();
+        }
+
+        Bytes::from(writer.into_inner().unwrap())
+    }
+
+    #[test]
+    fn deserialize_capture_records() {
+        let events = EventHubCaptureDeserializer
+            .parse(write_file(&["first", "second"]), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(log.get("message").unwrap(), &VrlValue::from("first"));
+        assert_eq!(log.get("sequence_number").unwrap(), &VrlValue::from(0));
+        assert_eq!(log.get("offset").unwrap(), &VrlValue::from("0"));
+        assert_eq!(
+            log.get("properties.tenant").unwrap(),
+            &VrlValue::from("acme")
+        );
+        assert_eq!(
+            log.get("system_properties.\"x-opt-enqueued-time\"")
+                .unwrap(),
+            &VrlValue::from(1_538_004_453_000_i64)
+        );
+        assert_eq!(
+            log.get("timestamp").unwrap(),
+            &VrlValue::from(Utc.with_ymd_and_hms(2018, 9, 26, 23, 27, 33).unwrap())
+        );
+
+        let log = events[1].as_log();
+        assert_eq!(log.get("message").unwrap(), &VrlValue::from("second"));
+        assert_eq!(log.get("sequence_number").unwrap(), &VrlValue::from(1));
+    }
+
+    #[test]
+    fn deserialize_capture_legacy_namespace() {
+        let events = EventHubCaptureDeserializer
+            .parse(write_file(&["first"]), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log.get(log_schema().message_key_target_path().unwrap())
+                .unwrap(),
+            &VrlValue::from("first")
+        );
+        assert_eq!(
+            log.get(log_schema().timestamp_key_target_path().unwrap())
+                .unwrap(),
+            &VrlValue::from(Utc.with_ymd_and_hms(2018, 9, 26, 23, 27, 33).unwrap())
+        );
+    }
+
+    #[test]
+    fn deserialize_capture_empty_frame() {
+        let events = EventHubCaptureDeserializer
+            .parse(Bytes::new(), LogNamespace::Vector)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn deserialize_capture_invalid_file() {
+        assert!(EventHubCaptureDeserializer
+            .parse(Bytes::from("not an avro file"), LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn parse_enqueued_time_formats() {
+        let expected = Utc.with_ymd_and_hms(2018, 9, 6, 8, 7, 3).unwrap();
+        assert_eq!(parse_enqueued_time("9/6/2018 8:07:03 AM"), Some(expected));
+        assert_eq!(parse_enqueued_time("2018-09-06T08:07:03Z"), Some(expected));
+        assert_eq!(parse_enqueued_time("yesterday"), None);
+    }
+}
@@ -5,11 +5,14 @@
 
 mod avro;
 mod bytes;
+mod event_hub_capture;
 mod gelf;
 mod influxdb;
 mod json;
 mod native;
 mod native_json;
+#[cfg(feature = "parquet")]
+mod parquet;
 mod protobuf;
 #[cfg(feature = "syslog")]
 mod syslog;
@@ -18,6 +21,7 @@ mod vrl;
 use ::bytes::Bytes;
 pub use avro::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
 use dyn_clone::DynClone;
+pub use event_hub_capture::{EventHubCaptureDeserializer, EventHubCaptureDeserializerConfig};
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions};
 pub use influxdb::{InfluxdbDeserializer, InfluxdbDeserializerConfig};
 pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
@@ -25,6 +29,8 @@ pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
 };
+#[cfg(feature = "parquet")]
+pub use parquet::{ParquetDeserializer, ParquetDeserializerConfig};
 pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
 use smallvec::SmallVec;
 #[cfg(feature = "syslog")]
@@ -0,0 +1,274 @@
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, Utc};
+use parquet::{
+    file::reader::{FileReader, SerializedFileReader},
+    record::{Field, Row},
+};
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{KeyString, ObjectMap};
+
+use super::Deserializer;
+
+type VrlValue = vrl::value::Value;
+
+/// Config used to build a `ParquetDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ParquetDeserializerConfig;
+
+impl ParquetDeserializerConfig {
+    /// Build the `ParquetDeserializer` from this configuration.
+    pub fn build(&self) -> ParquetDeserializer {
+        ParquetDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition = schema::Definition::empty_legacy_namespace()
+                    .unknown_fields(vrl::value::Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        vrl::value::Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                vrl::value::Kind::any(),
+                [log_namespace],
+            ),
+        }
+    }
+}
+
+/// Deserializer that builds one `Event` per row of an [Apache Parquet][parquet] file.
+///
+/// Parquet is a file format rather than a message format, so each byte frame is expected to
+/// hold a complete file, including its footer.
+///
+/// [parquet]: https://parquet.apache.org/
+#[derive(Debug, Clone, Default)]
+pub struct ParquetDeserializer;
+
+impl Deserializer for ParquetDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let reader = SerializedFileReader::new(bytes)?;
+        let mut events = SmallVec::new();
+        for row in reader.get_row_iter(None)? {
+            let mut log = LogEvent::from(row_to_object(&row?));
+
+            if log_namespace == LogNamespace::Legacy {
+                if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                    if !log.contains(timestamp_key) {
+                        log.insert(timestamp_key, Utc::now());
+                    }
+                }
+            }
+
+            events.push(Event::Log(log));
+        }
+
+        Ok(events)
+    }
+}
+
+fn row_to_object(row: &Row) -> ObjectMap {
+    row.get_column_iter()
+        .map(|(name, field)| (KeyString::from(name.as_str()), field_to_value(field)))
+        .collect()
+}
+
+fn field_to_value(field: &Field) -> VrlValue {
+    match field {
+        Field::Null => VrlValue::Null,
+        Field::Bool(value) => VrlValue::from(*value),
+        Field::Byte(value) => VrlValue::from(*value as i64),
+        Field::Short(value) => VrlValue::from(*value as i64),
+        Field::Int(value) => VrlValue::from(*value as i64),
+        Field::Long(value) => VrlValue::from(*value),
+        Field::UByte(value) => VrlValue::from(*value as i64),
+        Field::UShort(value) => VrlValue::from(*value as i64),
+        Field::UInt(value) => VrlValue::from(*value as i64),
+        // Values above `i64::MAX` don't fit in an integer, so fall back to a float.
+        Field::ULong(value) => i64::try_from(*value)
+            .map(VrlValue::from)
+            .unwrap_or_else(|_| VrlValue::from_f64_or_zero(*value as f64)),
+        Field::Float(value) => VrlValue::from_f64_or_zero(*value as f64),
+        Field::Double(value) => VrlValue::from_f64_or_zero(*value),
+        Field::Str(value) => VrlValue::from(value.as_str()),
+        Field::Bytes(value) => VrlValue::from(Bytes::copy_from_slice(value.data())),
+        Field::Date(days) => NaiveDate::from_num_days_from_ce_opt(UNIX_EPOCH_DAYS_FROM_CE + days)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|datetime| VrlValue::from(datetime.and_utc()))
+            .unwrap_or(VrlValue::Null),
+        Field::TimestampMillis(millis) => DateTime::from_timestamp_millis(*millis)
+            .map(VrlValue::from)
+            .unwrap_or(VrlValue::Null),
+        Field::TimestampMicros(micros) => DateTime::from_timestamp_micros(*micros)
+            .map(VrlValue::from)
+            .unwrap_or(VrlValue::Null),
+        Field::Group(row) => VrlValue::Object(row_to_object(row)),
+        Field::ListInternal(list) => {
+            VrlValue::Array(list.elements().iter().map(field_to_value).collect())
+        }
+        Field::MapInternal(map) => VrlValue::Object(
+            map.entries()
+                .iter()
+                .map(|(key, value)| (map_key(key), field_to_value(value)))
+                .collect(),
+        ),
+        // Decimals and half-precision floats have no lossless equivalent, so they are kept in
+        // their canonical string form.
+        other => VrlValue::from(other.to_string()),
+    }
+}
+
+/// Parquet map keys can be of any primitive type, but event objects are keyed by strings.
+fn map_key(key: &Field) -> KeyString {
+    match key {
+        Field::Str(key) => KeyString::from(key.as_str()),
+        other => KeyString::from(other.to_string()),
+    }
+}
+
+/// Days between 0001-01-01 and 1970-01-01, Parquet `DATE` values count from the latter.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parquet::{
+        data_type::{ByteArray, ByteArrayType, Int64Type},
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::parser::parse_message_type,
+    };
+
+    use super::*;
+
+    fn write_file() -> Bytes {
+        let schema = Arc::new(
+            parse_message_type(
+                "message log {
+                    REQUIRED BINARY message (UTF8);
+                    REQUIRED INT64 count;
+                }",
+            )
+            .unwrap(),
+        );
+
+        let mut buffer = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buffer,
+            schema,
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+
+        let mut row_group = writer.next_row_group().unwrap();
+
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(
+                &[ByteArray::from("first"), ByteArray::from("second")],
+                None,
+                None,
+            )
+            .unwrap();
+        column.close().unwrap();
+
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[1, 2], None, None)
+            .unwrap();
+        column.close().unwrap();
+
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        Bytes::from(buffer)
+    }
+
+    #[test]
+    fn deserialize_parquet_rows() {
+        let events = ParquetDeserializer
+            .parse(write_file(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(log.get("message").unwrap(), &VrlValue::from("first"));
+        assert_eq!(log.get("count").unwrap(), &VrlValue::from(1));
+
+        let log = events[1].as_log();
+        assert_eq!(log.get("message").unwrap(), &VrlValue::from("second"));
+        assert_eq!(log.get("count").unwrap(), &VrlValue::from(2));
+    }
+
+    #[test]
+    fn deserialize_parquet_legacy_namespace_adds_timestamp() {
+        let events = ParquetDeserializer
+            .parse(write_file(), LogNamespace::Legacy)
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        for event in events {
+            let timestamp_key = log_schema().timestamp_key_target_path().unwrap();
+            assert!(event.as_log().get(timestamp_key).is_some());
+        }
+    }
+
+    #[test]
+    fn deserialize_parquet_empty_frame() {
+        let events = ParquetDeserializer
+            .parse(Bytes::new(), LogNamespace::Vector)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn deserialize_parquet_invalid_file() {
+        assert!(ParquetDeserializer
+            .parse(Bytes::from("not a parquet file"), LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn date_is_converted_to_timestamp() {
+        assert_eq!(
+            field_to_value(&Field::Date(1)),
+            VrlValue::from(
+                NaiveDate::from_ymd_opt(1970, 1, 2)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_utc()
+            )
+        );
+    }
+}
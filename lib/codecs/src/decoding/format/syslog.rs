@@ -3,8 +3,8 @@ use chrono::{DateTime, Datelike, Utc};
 use derivative::Derivative;
 use lookup::{event_path, owned_value_path, OwnedTargetPath, OwnedValuePath};
 use smallvec::{smallvec, SmallVec};
-use std::borrow::Cow;
-use syslog_loose::{IncompleteDate, Message, ProcId, Protocol, Variant};
+use std::{borrow::Cow, collections::HashSet};
+use syslog_loose::{IncompleteDate, Message, ProcId, Protocol, StructuredElement, Variant};
 use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 use vector_core::{
@@ -23,6 +23,12 @@ pub struct SyslogDeserializerConfig {
     #[serde(skip)]
     source: Option<&'static str>,
 
+    #[serde(skip)]
+    strict: bool,
+
+    #[serde(skip)]
+    typed_structured_data: bool,
+
     /// Syslog-specific decoding options.
     #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
     pub syslog: SyslogDeserializerOptions,
@@ -32,8 +38,8 @@ impl SyslogDeserializerConfig {
     /// Creates a new `SyslogDeserializerConfig`.
     pub fn new(options: SyslogDeserializerOptions) -> Self {
         Self {
-            source: None,
             syslog: options,
+            ..Default::default()
         }
     }
 
@@ -45,11 +51,39 @@ impl SyslogDeserializerConfig {
         }
     }
 
+    /// Only accept RFC 5424 messages, with well-formed structured data.
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Decode the structured data parameters into typed values.
+    ///
+    /// See [`SyslogDeserializer`] for the decoded parameters.
+    pub const fn with_typed_structured_data(mut self, typed_structured_data: bool) -> Self {
+        self.typed_structured_data = typed_structured_data;
+        self
+    }
+
     /// Build the `SyslogDeserializer` from this configuration.
     pub const fn build(&self) -> SyslogDeserializer {
         SyslogDeserializer {
             source: self.source,
             lossy: self.syslog.lossy,
+            strict: self.strict,
+            typed_structured_data: self.typed_structured_data,
+        }
+    }
+
+    /// The kind of the values of structured data parameters.
+    fn structured_data_param_kind(&self) -> Kind {
+        if self.typed_structured_data {
+            Kind::bytes()
+                .or_integer()
+                .or_boolean()
+                .or_array(Collection::from_unknown(Kind::bytes().or_integer()))
+        } else {
+            Kind::bytes()
         }
     }
 
@@ -100,8 +134,10 @@ impl SyslogDeserializerConfig {
                         Kind::integer().or_bytes(),
                         None,
                     )
-                    // "structured data" is placed at the root. It will always be a map of strings
-                    .unknown_fields(Kind::object(Collection::from_unknown(Kind::bytes())));
+                    // "structured data" is placed at the root.
+                    .unknown_fields(Kind::object(Collection::from_unknown(
+                        self.structured_data_param_kind(),
+                    )));
 
                 if self.source.is_some() {
                     // This field is added by the syslog source. It will not be present if the data
@@ -145,8 +181,10 @@ impl SyslogDeserializerConfig {
                     Kind::integer().or_bytes(),
                     None,
                 )
-                // "structured data" is placed at the root. It will always be a map strings
-                .unknown_fields(Kind::object(Collection::from_unknown(Kind::bytes())))
+                // "structured data" is placed at the root.
+                .unknown_fields(Kind::object(Collection::from_unknown(
+                    self.structured_data_param_kind(),
+                )))
             }
             (LogNamespace::Vector, Some(source)) => {
                 schema::Definition::new_with_default_metadata(Kind::bytes(), [log_namespace])
@@ -219,7 +257,7 @@ impl SyslogDeserializerConfig {
                         None,
                         &owned_value_path!("structured_data"),
                         Kind::object(Collection::from_unknown(Kind::object(
-                            Collection::from_unknown(Kind::bytes()),
+                            Collection::from_unknown(self.structured_data_param_kind()),
                         ))),
                         None,
                     )
@@ -227,8 +265,10 @@ impl SyslogDeserializerConfig {
                         source,
                         None,
                         &owned_value_path!("tls_client_metadata"),
-                        Kind::object(Collection::empty().with_unknown(Kind::bytes()))
-                            .or_undefined(),
+                        Kind::object(Collection::empty().with_unknown(
+                            Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                        ))
+                        .or_undefined(),
                         None,
                     )
             }
@@ -256,6 +296,14 @@ pub struct SyslogDeserializerOptions {
 
 /// Deserializer that builds an `Event` from a byte frame containing a syslog
 /// message.
+///
+/// The structured data elements of RFC 5424 messages are decoded into a map of
+/// parameters per SD-ID. When the structured data is typed, parameters that are
+/// repeated in an element are decoded into arrays, and the parameters of the
+/// SD-IDs registered by RFC 5424 are decoded into their types:
+/// `timeQuality.tzKnown` and `timeQuality.isSynced` into booleans, and
+/// `timeQuality.syncAccuracy`, `meta.sequenceId` and `meta.sysUpTime` into
+/// integers.
 #[derive(Debug, Clone, Derivative)]
 #[derivative(Default)]
 pub struct SyslogDeserializer {
@@ -265,6 +313,10 @@ pub struct SyslogDeserializer {
     pub source: Option<&'static str>,
     #[derivative(Default(value = "default_lossy()"))]
     lossy: bool,
+    /// Only accept RFC 5424 messages, with well-formed structured data.
+    strict: bool,
+    /// Decode the structured data parameters into typed values.
+    typed_structured_data: bool,
 }
 
 impl Deserializer for SyslogDeserializer {
@@ -278,18 +330,36 @@ impl Deserializer for SyslogDeserializer {
             false => Cow::from(std::str::from_utf8(&bytes)?),
         };
         let line = line.trim();
-        let parsed =
-            syslog_loose::parse_message_with_year_exact(line, resolve_year, Variant::Either)?;
+        let variant = if self.strict {
+            Variant::RFC5424
+        } else {
+            Variant::Either
+        };
+        let parsed = syslog_loose::parse_message_with_year_exact(line, resolve_year, variant)?;
+        if self.strict {
+            validate_rfc5424(&parsed)?;
+        }
 
         let log = match (self.source, log_namespace) {
             (Some(source), LogNamespace::Vector) => {
                 let mut log = LogEvent::from(Value::Bytes(Bytes::from(parsed.msg.to_string())));
-                insert_metadata_fields_from_syslog(&mut log, source, parsed, log_namespace);
+                insert_metadata_fields_from_syslog(
+                    &mut log,
+                    source,
+                    parsed,
+                    log_namespace,
+                    self.typed_structured_data,
+                );
                 log
             }
             _ => {
                 let mut log = LogEvent::from(Value::Object(ObjectMap::new()));
-                insert_fields_from_syslog(&mut log, parsed, log_namespace);
+                insert_fields_from_syslog(
+                    &mut log,
+                    parsed,
+                    log_namespace,
+                    self.typed_structured_data,
+                );
                 log
             }
         };
@@ -314,11 +384,81 @@ fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
     }
 }
 
+/// Checks that the message is an RFC 5424 message, with well-formed structured data.
+///
+/// See <https://datatracker.ietf.org/doc/html/rfc5424#section-6.3>.
+fn validate_rfc5424(parsed: &Message<&str>) -> vector_common::Result<()> {
+    if !matches!(parsed.protocol, Protocol::RFC5424(_)) {
+        return Err("message is not an RFC 5424 message".into());
+    }
+
+    let mut ids = HashSet::new();
+    for element in &parsed.structured_data {
+        if !is_sd_name(element.id) {
+            return Err(format!("invalid SD-ID {:?}", element.id).into());
+        }
+        if !ids.insert(element.id) {
+            return Err(format!("SD-ID {:?} exists more than once", element.id).into());
+        }
+        for (name, _) in element.params() {
+            if !is_sd_name(name) {
+                return Err(format!("invalid PARAM-NAME {:?} in {:?}", name, element.id).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An SD-NAME is 1 to 32 printable US-ASCII characters, except `=`, space, `]` and `"`.
+fn is_sd_name(name: &str) -> bool {
+    (1..=32).contains(&name.len())
+        && name
+            .bytes()
+            .all(|b| (33..=126).contains(&b) && !matches!(b, b'=' | b']' | b'"'))
+}
+
+/// Decodes a structured data element into a map of its parameters.
+fn structured_data_element(element: &StructuredElement<&str>, typed: bool) -> ObjectMap {
+    let mut data = ObjectMap::new();
+    for (name, value) in element.params() {
+        if !typed {
+            data.insert(name.to_string().into(), value.into());
+            continue;
+        }
+
+        let value = typed_param_value(element.id, name, &value);
+        match data.get_mut(*name) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.clone(), value]),
+            None => {
+                data.insert(name.to_string().into(), value);
+            }
+        }
+    }
+    data
+}
+
+/// Decodes the value of a parameter of the SD-IDs registered by RFC 5424 into its type.
+///
+/// See <https://datatracker.ietf.org/doc/html/rfc5424#section-7>.
+fn typed_param_value(id: &str, name: &str, value: &str) -> Value {
+    match (id, name, value) {
+        ("timeQuality", "tzKnown" | "isSynced", "0") => Value::Boolean(false),
+        ("timeQuality", "tzKnown" | "isSynced", "1") => Value::Boolean(true),
+        ("timeQuality", "syncAccuracy", _) | ("meta", "sequenceId" | "sysUpTime", _) => value
+            .parse::<i64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| value.into()),
+        _ => value.into(),
+    }
+}
+
 fn insert_metadata_fields_from_syslog(
     log: &mut LogEvent,
     source: &'static str,
     parsed: Message<&str>,
     log_namespace: LogNamespace,
+    typed_structured_data: bool,
 ) {
     if let Some(timestamp) = parsed.timestamp {
         let timestamp = DateTime::<Utc>::from(timestamp);
@@ -400,12 +540,7 @@ fn insert_metadata_fields_from_syslog(
 
     let mut sdata = ObjectMap::new();
     for element in parsed.structured_data.into_iter() {
-        let mut data = ObjectMap::new();
-
-        for (name, value) in element.params() {
-            data.insert(name.to_string().into(), value.into());
-        }
-
+        let data = structured_data_element(&element, typed_structured_data);
         sdata.insert(element.id.into(), data.into());
     }
 
@@ -422,6 +557,7 @@ fn insert_fields_from_syslog(
     log: &mut LogEvent,
     parsed: Message<&str>,
     log_namespace: LogNamespace,
+    typed_structured_data: bool,
 ) {
     match log_namespace {
         LogNamespace::Legacy => {
@@ -470,10 +606,7 @@ fn insert_fields_from_syslog(
     }
 
     for element in parsed.structured_data.into_iter() {
-        let mut sdata = ObjectMap::new();
-        for (name, value) in element.params() {
-            sdata.insert(name.to_string().into(), value.into());
-        }
+        let sdata = structured_data_element(&element, typed_structured_data);
         log.insert(event_path!(element.id), sdata);
     }
 }
@@ -516,6 +649,56 @@ mod tests {
         assert!(events[0].as_log()["timestamp"].is_timestamp());
     }
 
+    #[test]
+    fn deserialize_syslog_typed_structured_data() {
+        init();
+
+        let input = Bytes::from(
+            r#"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 [timeQuality tzKnown="1" isSynced="0" syncAccuracy="60000"][origin ip="192.0.2.1" ip="192.0.2.129"][meta sequenceId="29"] MSG"#,
+        );
+        let deserializer = SyslogDeserializerConfig::default()
+            .with_typed_structured_data(true)
+            .build();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["timeQuality.tzKnown"], true.into());
+        assert_eq!(log["timeQuality.isSynced"], false.into());
+        assert_eq!(log["timeQuality.syncAccuracy"], 60000.into());
+        assert_eq!(
+            log["origin.ip"],
+            Value::Array(vec!["192.0.2.1".into(), "192.0.2.129".into()])
+        );
+        assert_eq!(log["meta.sequenceId"], 29.into());
+    }
+
+    #[test]
+    fn deserialize_syslog_strict() {
+        init();
+
+        let deserializer = SyslogDeserializerConfig::default()
+            .with_strict(true)
+            .build();
+
+        let valid = Bytes::from(
+            r#"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 [exampleSDID@32473 iut="3"] MSG"#,
+        );
+        assert!(deserializer.parse(valid, LogNamespace::Vector).is_ok());
+
+        let rfc3164 = Bytes::from("<34>Oct 11 22:14:15 mymachine su: MSG");
+        assert!(deserializer.parse(rfc3164, LogNamespace::Vector).is_err());
+
+        let duplicate_sd_id = Bytes::from(
+            r#"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 [meta sequenceId="1"][meta sequenceId="2"] MSG"#,
+        );
+        assert!(deserializer
+            .parse(duplicate_sd_id.clone(), LogNamespace::Vector)
+            .is_err());
+
+        let lenient = SyslogDeserializer::default();
+        assert!(lenient.parse(duplicate_sd_id, LogNamespace::Vector).is_ok());
+    }
+
     fn init() {
         let mut schema = LogSchema::default();
         schema.set_message_key(Some(OwnedTargetPath::event(owned_value_path!(
@@ -9,13 +9,15 @@ use crate::decoding::format::{VrlDeserializer, VrlDeserializerConfig};
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
-    GelfDeserializerConfig, GelfDeserializerOptions, InfluxdbDeserializer,
-    InfluxdbDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions,
-    NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
-    NativeJsonDeserializerConfig, NativeJsonDeserializerOptions, ProtobufDeserializer,
-    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, EventHubCaptureDeserializer,
+    EventHubCaptureDeserializerConfig, GelfDeserializer, GelfDeserializerConfig,
+    GelfDeserializerOptions, InfluxdbDeserializer, InfluxdbDeserializerConfig, JsonDeserializer,
+    JsonDeserializerConfig, JsonDeserializerOptions, NativeDeserializer, NativeDeserializerConfig,
+    NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
+    ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions,
 };
+#[cfg(feature = "parquet")]
+pub use format::{ParquetDeserializer, ParquetDeserializerConfig};
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
 pub use framing::{
@@ -266,6 +268,28 @@ pub enum DeserializerConfig {
         avro: AvroDeserializerOptions,
     },
 
+    #[cfg(feature = "parquet")]
+    /// Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+    ///
+    /// Each byte frame must contain a complete Parquet file, so this codec is typically combined
+    /// with `bytes` framing.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet,
+
+    /// Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+    /// event per captured event.
+    ///
+    /// The body of each captured event is decoded as the message, and the time that it was enqueued
+    /// at as the timestamp of the event. Its sequence number, offset, and properties are added as
+    /// fields of the event.
+    ///
+    /// Each byte frame must contain a complete Avro file, so this codec is typically combined with
+    /// `bytes` framing.
+    ///
+    /// [event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+    EventHubCapture,
+
     /// Decodes the raw bytes as a string and passes them as input to a [VRL][vrl] program.
     ///
     /// [vrl]: https://vector.dev/docs/reference/vrl
@@ -315,6 +339,19 @@ impl From<InfluxdbDeserializerConfig> for DeserializerConfig {
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<ParquetDeserializerConfig> for DeserializerConfig {
+    fn from(_: ParquetDeserializerConfig) -> Self {
+        Self::Parquet
+    }
+}
+
+impl From<EventHubCaptureDeserializerConfig> for DeserializerConfig {
+    fn from(_: EventHubCaptureDeserializerConfig) -> Self {
+        Self::EventHubCapture
+    }
+}
+
 impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
     pub fn build(&self) -> vector_common::Result<Deserializer> {
@@ -336,6 +373,13 @@ impl DeserializerConfig {
             DeserializerConfig::NativeJson(config) => Ok(Deserializer::NativeJson(config.build())),
             DeserializerConfig::Gelf(config) => Ok(Deserializer::Gelf(config.build())),
             DeserializerConfig::Influxdb(config) => Ok(Deserializer::Influxdb(config.build())),
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet => {
+                Ok(Deserializer::Parquet(ParquetDeserializerConfig.build()))
+            }
+            DeserializerConfig::EventHubCapture => Ok(Deserializer::EventHubCapture(
+                EventHubCaptureDeserializerConfig.build(),
+            )),
             DeserializerConfig::Vrl(config) => Ok(Deserializer::Vrl(config.build()?)),
         }
     }
@@ -344,6 +388,9 @@ impl DeserializerConfig {
     pub fn default_stream_framing(&self) -> FramingConfig {
         match self {
             DeserializerConfig::Avro { .. } => FramingConfig::Bytes,
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet => FramingConfig::Bytes,
+            DeserializerConfig::EventHubCapture => FramingConfig::Bytes,
             DeserializerConfig::Native => FramingConfig::LengthDelimited(Default::default()),
             DeserializerConfig::Bytes
             | DeserializerConfig::Json(_)
@@ -378,6 +425,9 @@ impl DeserializerConfig {
             DeserializerConfig::Gelf(config) => config.output_type(),
             DeserializerConfig::Vrl(config) => config.output_type(),
             DeserializerConfig::Influxdb(config) => config.output_type(),
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet => ParquetDeserializerConfig.output_type(),
+            DeserializerConfig::EventHubCapture => EventHubCaptureDeserializerConfig.output_type(),
         }
     }
 
@@ -397,6 +447,13 @@ impl DeserializerConfig {
             DeserializerConfig::NativeJson(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Gelf(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Influxdb(config) => config.schema_definition(log_namespace),
+            #[cfg(feature = "parquet")]
+            DeserializerConfig::Parquet => {
+                ParquetDeserializerConfig.schema_definition(log_namespace)
+            }
+            DeserializerConfig::EventHubCapture => {
+                EventHubCaptureDeserializerConfig.schema_definition(log_namespace)
+            }
             DeserializerConfig::Vrl(config) => config.schema_definition(log_namespace),
         }
     }
@@ -420,9 +477,11 @@ impl DeserializerConfig {
                         },
                 }),
             ) => "application/json",
-            (DeserializerConfig::Native, _) | (DeserializerConfig::Avro { .. }, _) => {
-                "application/octet-stream"
-            }
+            (DeserializerConfig::Native, _)
+            | (DeserializerConfig::Avro { .. }, _)
+            | (DeserializerConfig::EventHubCapture, _) => "application/octet-stream",
+            #[cfg(feature = "parquet")]
+            (DeserializerConfig::Parquet, _) => "application/octet-stream",
             (DeserializerConfig::Protobuf(_), _) => "application/octet-stream",
             (
                 DeserializerConfig::Json(_)
@@ -463,6 +522,11 @@ pub enum Deserializer {
     Gelf(GelfDeserializer),
     /// Uses a `InfluxdbDeserializer` for deserialization.
     Influxdb(InfluxdbDeserializer),
+    #[cfg(feature = "parquet")]
+    /// Uses a `ParquetDeserializer` for deserialization.
+    Parquet(ParquetDeserializer),
+    /// Uses a `EventHubCaptureDeserializer` for deserialization.
+    EventHubCapture(EventHubCaptureDeserializer),
     /// Uses a `VrlDeserializer` for deserialization.
     Vrl(VrlDeserializer),
 }
@@ -485,6 +549,9 @@ impl format::Deserializer for Deserializer {
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Influxdb(deserializer) => deserializer.parse(bytes, log_namespace),
+            #[cfg(feature = "parquet")]
+            Deserializer::Parquet(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::EventHubCapture(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Vrl(deserializer) => deserializer.parse(bytes, log_namespace),
         }
     }
@@ -11,13 +11,15 @@ pub mod gelf;
 
 pub use decoding::{
     BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
-    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
-    NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
-    StreamDecodingError,
+    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, EventHubCaptureDeserializer,
+    EventHubCaptureDeserializerConfig, GelfDeserializer, GelfDeserializerConfig, JsonDeserializer,
+    JsonDeserializerConfig, LengthDelimitedDecoder, LengthDelimitedDecoderConfig,
+    NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
+    NativeJsonDeserializerConfig, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    OctetCountingDecoder, OctetCountingDecoderConfig, StreamDecodingError,
 };
+#[cfg(feature = "parquet")]
+pub use decoding::{ParquetDeserializer, ParquetDeserializerConfig};
 #[cfg(feature = "syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use encoding::{
@@ -0,0 +1,160 @@
+use std::{
+    collections::HashSet,
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use notify::{
+    event::ModifyKind, recommended_watcher, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use tokio::sync::Notify;
+use tracing::trace;
+
+use crate::FileSourceInternalEvents;
+
+/// `ChangeNotifier` subscribes to the native change notifications of the
+/// operating system for the directories that files are found in: `inotify` on
+/// Linux, `kqueue` on the BSDs, `FSEvents` on macOS and `ReadDirectoryChangesW`
+/// on Windows.
+///
+/// It only records that files have been created, removed or renamed, so that
+/// `FileServer` can search for files right away instead of waiting for its next
+/// glob. Files are still read by polling.
+pub(crate) struct ChangeNotifier {
+    watcher: RecommendedWatcher,
+    directories: HashSet<PathBuf>,
+    changed: Arc<AtomicBool>,
+    wake: Arc<Notify>,
+}
+
+impl ChangeNotifier {
+    pub(crate) fn new() -> notify::Result<Self> {
+        let changed = Arc::new(AtomicBool::new(false));
+        let wake = Arc::new(Notify::new());
+
+        let watcher = {
+            let changed = Arc::clone(&changed);
+            let wake = Arc::clone(&wake);
+            recommended_watcher(move |event: notify::Result<notify::Event>| {
+                // Errors are ignored, as the next glob finds whatever was missed.
+                let Ok(event) = event else { return };
+                if matches!(
+                    event.kind,
+                    EventKind::Create(_)
+                        | EventKind::Remove(_)
+                        | EventKind::Modify(ModifyKind::Name(_))
+                ) {
+                    trace!(message = "Received file change notification.", paths = ?event.paths);
+                    changed.store(true, Ordering::Relaxed);
+                    wake.notify_one();
+                }
+            })?
+        };
+
+        Ok(Self {
+            watcher,
+            directories: HashSet::new(),
+            changed,
+            wake,
+        })
+    }
+
+    /// Watch exactly the given directories, starting to watch the new ones and
+    /// stopping to watch the ones that are no longer needed.
+    pub(crate) fn sync_directories<E: FileSourceInternalEvents>(
+        &mut self,
+        directories: HashSet<PathBuf>,
+        emitter: &E,
+    ) {
+        // Directories that have been removed must be watched again if they are
+        // created again.
+        self.directories.retain(|directory| directory.exists());
+
+        for directory in self.directories.difference(&directories) {
+            // Fails if the directory has been removed, which is fine.
+            _ = self.watcher.unwatch(directory);
+        }
+        self.directories
+            .retain(|directory| directories.contains(directory));
+
+        for directory in directories {
+            if self.directories.contains(&directory) {
+                continue;
+            }
+            match self.watcher.watch(&directory, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.directories.insert(directory);
+                }
+                Err(error) => emitter.emit_file_watch_error(&directory, into_io_error(error)),
+            }
+        }
+    }
+
+    /// Whether files have been created, removed or renamed since the last call.
+    pub(crate) fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::Relaxed)
+    }
+
+    /// Completes when files are created, removed or renamed, or right away if
+    /// that happened since the last time it completed.
+    pub(crate) async fn notified(&self) {
+        self.wake.notified().await
+    }
+}
+
+fn into_io_error(error: notify::Error) -> io::Error {
+    match error.kind {
+        notify::ErrorKind::Io(error) => error,
+        _ => io::Error::new(io::ErrorKind::Other, error),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path, time::Duration};
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct NoopEmitter;
+
+    impl FileSourceInternalEvents for NoopEmitter {
+        fn emit_file_added(&self, _: &Path) {}
+        fn emit_file_resumed(&self, _: &Path, _: u64) {}
+        fn emit_file_watch_error(&self, _: &Path, _: io::Error) {}
+        fn emit_file_unwatched(&self, _: &Path, _: bool) {}
+        fn emit_file_deleted(&self, _: &Path) {}
+        fn emit_file_delete_error(&self, _: &Path, _: io::Error) {}
+        fn emit_file_fingerprint_read_error(&self, _: &Path, _: io::Error) {}
+        fn emit_file_checkpointed(&self, _: usize, _: Duration) {}
+        fn emit_file_checksum_failed(&self, _: &Path) {}
+        fn emit_file_checkpoint_write_error(&self, _: io::Error) {}
+        fn emit_files_open(&self, _: usize) {}
+        fn emit_path_globbing_failed(&self, _: &Path, _: &io::Error) {}
+    }
+
+    #[tokio::test]
+    async fn notifies_of_rotated_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("file.log");
+        fs::write(&path, "first\n").unwrap();
+
+        let mut notifier = ChangeNotifier::new().unwrap();
+        notifier.sync_directories(HashSet::from([dir.path().to_path_buf()]), &NoopEmitter);
+        assert!(!notifier.take_changed());
+
+        fs::rename(&path, dir.path().join("file.log.1")).unwrap();
+        fs::write(&path, "second\n").unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), notifier.notified())
+            .await
+            .expect("no change notification");
+        assert!(notifier.take_changed());
+    }
+}
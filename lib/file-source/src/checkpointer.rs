@@ -38,6 +38,9 @@ struct Checkpoint {
     fingerprint: FileFingerprint,
     position: FilePosition,
     modified: DateTime<Utc>,
+    /// The decompressed length of a gzipped archive that has been read to its end.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    archive_end: Option<FilePosition>,
 }
 
 pub struct Checkpointer {
@@ -56,6 +59,7 @@ pub struct CheckpointsView {
     checkpoints: DashMap<FileFingerprint, FilePosition>,
     modified_times: DashMap<FileFingerprint, DateTime<Utc>>,
     removed_times: DashMap<FileFingerprint, DateTime<Utc>>,
+    archive_ends: DashMap<FileFingerprint, FilePosition>,
 }
 
 impl CheckpointsView {
@@ -73,6 +77,21 @@ impl CheckpointsView {
         self.removed_times.insert(fng, Utc::now());
     }
 
+    /// Record that the gzipped archive has been read to its end, at the given
+    /// decompressed position.
+    pub fn set_archive_end(&self, fng: FileFingerprint, pos: FilePosition) {
+        self.archive_ends.insert(fng, pos);
+    }
+
+    /// Whether the gzipped archive has been read to its end and everything
+    /// read from it has been checkpointed.
+    pub fn archive_completed(&self, fng: FileFingerprint) -> bool {
+        match (self.get(fng), self.archive_ends.get(&fng)) {
+            (Some(pos), Some(end)) => pos >= *end.value(),
+            _ => false,
+        }
+    }
+
     pub fn update_key(&self, old: FileFingerprint, new: FileFingerprint) {
         if let Some((_, value)) = self.checkpoints.remove(&old) {
             self.checkpoints.insert(new, value);
@@ -85,6 +104,10 @@ impl CheckpointsView {
         if let Some((_, value)) = self.removed_times.remove(&old) {
             self.removed_times.insert(new, value);
         }
+
+        if let Some((_, value)) = self.archive_ends.remove(&old) {
+            self.archive_ends.insert(new, value);
+        }
     }
 
     pub fn contains_bytes_checksums(&self) -> bool {
@@ -114,6 +137,7 @@ impl CheckpointsView {
             self.checkpoints.remove(&fng);
             self.modified_times.remove(&fng);
             self.removed_times.remove(&fng);
+            self.archive_ends.remove(&fng);
         }
     }
 
@@ -122,6 +146,10 @@ impl CheckpointsView {
             .insert(checkpoint.fingerprint, checkpoint.position);
         self.modified_times
             .insert(checkpoint.fingerprint, checkpoint.modified);
+        if let Some(archive_end) = checkpoint.archive_end {
+            self.archive_ends
+                .insert(checkpoint.fingerprint, archive_end);
+        }
     }
 
     fn set_state(&self, state: State, ignore_before: Option<DateTime<Utc>>) {
@@ -155,6 +183,7 @@ impl CheckpointsView {
                             .get(fingerprint)
                             .map(|r| *r.value())
                             .unwrap_or_else(Utc::now),
+                        archive_end: self.archive_ends.get(fingerprint).map(|r| *r.value()),
                     }
                 })
                 .collect(),
@@ -489,6 +518,7 @@ mod test {
                     fingerprint: *fingerprint,
                     position,
                     modified: *modified,
+                    archive_end: None,
                 });
                 assert_eq!(chkptr.get_checkpoint(*fingerprint), Some(position));
                 chkptr.write_checkpoints().unwrap();
@@ -547,6 +577,7 @@ mod test {
             strategy: FingerprintStrategy::DevInode,
             max_line_length: 1000,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let mut buf = Vec::new();
@@ -588,6 +619,7 @@ mod test {
             },
             max_line_length: 102400,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let mut buf = Vec::new();
@@ -629,6 +661,7 @@ mod test {
             },
             max_line_length: 102400,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let mut buf = Vec::new();
@@ -744,6 +777,7 @@ mod test {
             },
             max_line_length: 1024,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let log_path = data_dir.path().join("test.log");
@@ -782,6 +816,27 @@ mod test {
         assert_eq!(None, chkptr.get_checkpoint(old));
     }
 
+    #[test]
+    fn test_checkpointer_archive_completed() {
+        let fingerprint = FileFingerprint::FirstLinesChecksum(78910);
+        let data_dir = tempdir().unwrap();
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(fingerprint, 1000);
+            chkptr.checkpoints.set_archive_end(fingerprint, 1234);
+            assert!(!chkptr.checkpoints.archive_completed(fingerprint));
+            chkptr.update_checkpoint(fingerprint, 1234);
+            assert!(chkptr.checkpoints.archive_completed(fingerprint));
+            chkptr.write_checkpoints().unwrap();
+        }
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            assert!(!chkptr.checkpoints.archive_completed(fingerprint));
+            chkptr.read_checkpoints(None);
+            assert!(chkptr.checkpoints.archive_completed(fingerprint));
+        }
+    }
+
     // guards against accidental changes to the checkpoint serialization
     #[test]
     fn test_checkpointer_serialization() {
@@ -18,18 +18,24 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, trace};
 
 use crate::{
+    change_notifier::ChangeNotifier,
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
     paths_provider::PathsProvider,
-    FileSourceInternalEvents, ReadFrom,
+    DiscoveryMode, FileSourceInternalEvents, ReadFrom,
 };
 
+/// Globs triggered by change notifications are at least this fraction of
+/// `glob_minimum_cooldown` apart.
+const NOTIFIED_GLOB_COOLDOWN_DIVISOR: u32 = 10;
+
 /// `FileServer` is a Source which cooperatively schedules reads over files,
 /// converting the lines of said files into `LogLine` structures. As
 /// `FileServer` is intended to be useful across multiple operating systems with
-/// POSIX filesystem semantics `FileServer` must poll for changes. That is, no
-/// event notification is used by `FileServer`.
+/// POSIX filesystem semantics `FileServer` must poll for changes. Event
+/// notification is only used, when `discovery_mode` is `Native`, to search for
+/// files as soon as they are created, removed or renamed.
 ///
 /// `FileServer` is configured on a path to watch. The files do _not_ need to
 /// exist at startup. `FileServer` will discover new files which match
@@ -47,6 +53,8 @@ where
     pub line_delimiter: Bytes,
     pub data_dir: PathBuf,
     pub glob_minimum_cooldown: Duration,
+    pub discovery_mode: DiscoveryMode,
+    pub read_archives: bool,
     pub fingerprinter: Fingerprinter,
     pub oldest_first: bool,
     pub remove_after: Option<Duration>,
@@ -67,7 +75,7 @@ where
 /// rare occurrence.
 ///
 /// Specific operating systems support evented interfaces that correct this
-/// problem but your intrepid authors know of no generic solution.
+/// problem, which are used when `discovery_mode` is `Native`.
 impl<PP, E> FileServer<PP, E>
 where
     PP: PathsProvider,
@@ -135,6 +143,20 @@ where
         }
         self.emitter.emit_files_open(fp_map.len());
 
+        let mut change_notifier = match self.discovery_mode {
+            DiscoveryMode::Poll => None,
+            DiscoveryMode::Native => match ChangeNotifier::new() {
+                Ok(notifier) => Some(notifier),
+                Err(error) => {
+                    error!(
+                        message = "Failed to subscribe to file change notifications, falling back to polling.",
+                        %error
+                    );
+                    None
+                }
+            },
+        };
+
         let mut stats = TimingStats::default();
 
         // Spawn the checkpoint writer task
@@ -155,11 +177,25 @@ where
         // we do not re-scan for major file changes (new files, moves, deletes),
         // or write new checkpoints, on every iteration.
         let mut next_glob_time = time::Instant::now();
+        let mut last_glob_time = next_glob_time;
         loop {
-            // Glob find files to follow, but not too often.
+            // Glob find files to follow, but not too often. When files have
+            // been created, removed or renamed, glob sooner, but still no more
+            // often than a fraction of the cooldown, so that busy directories
+            // don't trigger a glob on every loop.
             let now_time = time::Instant::now();
+            if change_notifier
+                .as_ref()
+                .is_some_and(ChangeNotifier::take_changed)
+            {
+                let notified_glob_time = last_glob_time
+                    .checked_add(self.glob_minimum_cooldown / NOTIFIED_GLOB_COOLDOWN_DIVISOR)
+                    .unwrap();
+                next_glob_time = next_glob_time.min(notified_glob_time);
+            }
             if next_glob_time <= now_time {
                 // Schedule the next glob time.
+                last_glob_time = now_time;
                 next_glob_time = now_time.checked_add(self.glob_minimum_cooldown).unwrap();
 
                 if stats.started_at.elapsed() > Duration::from_secs(1) {
@@ -227,6 +263,20 @@ where
                         }
                     }
                 }
+                if let Some(change_notifier) = &mut change_notifier {
+                    let directories = self
+                        .paths_provider
+                        .directories()
+                        .into_iter()
+                        .chain(
+                            fp_map
+                                .values()
+                                .filter_map(|watcher| watcher.path.parent().map(PathBuf::from)),
+                        )
+                        .filter(|directory| !directory.as_os_str().is_empty())
+                        .collect();
+                    change_notifier.sync_directories(directories, &self.emitter);
+                }
                 stats.record("discovery", start.elapsed());
             }
 
@@ -266,6 +316,10 @@ where
                 }
                 stats.record("reading", start.elapsed());
 
+                if let Some(archive_end) = watcher.archive_end() {
+                    checkpoints.set_archive_end(file_id, archive_end);
+                }
+
                 if bytes_read > 0 {
                     global_bytes_read = global_bytes_read.saturating_add(bytes_read);
                 } else {
@@ -342,9 +396,17 @@ where
             // call. Also since we are using block_on here and in the above code,
             // this should be run in its own thread. `spawn_blocking` fulfills
             // all of these requirements.
+            let notified = change_notifier.as_ref().map(ChangeNotifier::notified);
             let sleep = async move {
                 if backoff > 0 {
-                    sleep(Duration::from_millis(backoff as u64)).await;
+                    let sleep = sleep(Duration::from_millis(backoff as u64));
+                    match notified {
+                        Some(notified) => {
+                            futures::pin_mut!(sleep, notified);
+                            select(sleep, notified).await;
+                        }
+                        None => sleep.await,
+                    }
                 }
             };
             futures::pin_mut!(sleep);
@@ -402,12 +464,17 @@ where
             fallback
         };
 
+        // Archives that have been read to their end are still watched, so that their checkpoints
+        // expire once they are removed, but they are not decompressed again.
+        let read_archives = self.read_archives && !checkpoints.archive_completed(file_id);
+
         match FileWatcher::new(
             path.clone(),
             read_from,
             self.ignore_before,
             self.max_line_bytes,
             self.line_delimiter.clone(),
+            read_archives,
         ) {
             Ok(mut watcher) => {
                 if let ReadFrom::Checkpoint(file_position) = read_from {
@@ -1,6 +1,6 @@
 use std::{
     fs::{self, File},
-    io::{self, BufRead, Seek},
+    io::{self, BufRead, Read, Seek},
     path::PathBuf,
     time::{Duration, Instant},
 };
@@ -48,6 +48,8 @@ pub struct FileWatcher {
     last_seen: Instant,
     max_line_bytes: usize,
     line_delimiter: Bytes,
+    read_archives: bool,
+    reading_archive: bool,
     buf: BytesMut,
 }
 
@@ -57,12 +59,19 @@ impl FileWatcher {
     /// The input path will be used by `FileWatcher` to prime its state
     /// machine. A `FileWatcher` tracks _only one_ file. This function returns
     /// None if the path does not exist or is not readable by the current process.
+    ///
+    /// When `read_archives` is set, gzipped files are treated as rotated
+    /// archives: they are read from the beginning even if `read_from` is
+    /// `End`, and resumed from their checkpoint instead of being skipped.
+    /// Archives that have already been read to their end should be opened
+    /// without `read_archives`, so that they are not decompressed again.
     pub fn new(
         path: PathBuf,
         read_from: ReadFrom,
         ignore_before: Option<DateTime<Utc>>,
         max_line_bytes: usize,
         line_delimiter: Bytes,
+        read_archives: bool,
     ) -> Result<FileWatcher, io::Error> {
         let f = fs::File::open(&path)?;
         let (devno, ino) = (f.portable_dev()?, f.portable_ino()?);
@@ -79,6 +88,7 @@ impl FileWatcher {
         };
 
         let gzipped = is_gzipped(&mut reader)?;
+        let mut reading_archive = false;
 
         // Determine the actual position at which we should start reading
         let (reader, file_position): (Box<dyn BufRead>, FilePosition) =
@@ -90,6 +100,15 @@ impl FileWatcher {
                     );
                     (Box::new(null_reader()), 0)
                 }
+                (true, _, ReadFrom::Checkpoint(file_position)) if read_archives => {
+                    debug!(
+                        message = "Resuming gzipped file from its stored offset.",
+                        ?path,
+                        %file_position
+                    );
+                    reading_archive = true;
+                    (skip_decompressed(reader, file_position)?, file_position)
+                }
                 (true, _, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
                         message = "Not re-reading gzipped file with existing stored offset.",
@@ -102,14 +121,15 @@ impl FileWatcher {
                 // we were reading before. Should we merge this and the next branch to read
                 // compressed file from the beginning even when `read_from = "end"` (implicitly via
                 // default or explicitly via config)?
-                (true, _, ReadFrom::End) => {
+                (true, _, ReadFrom::End) if !read_archives => {
                     debug!(
                         message = "Can't read from the end of already-compressed file.",
                         ?path,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, false, ReadFrom::Beginning) => {
+                (true, false, ReadFrom::Beginning | ReadFrom::End) => {
+                    reading_archive = read_archives;
                     (Box::new(io::BufReader::new(MultiGzDecoder::new(reader))), 0)
                 }
                 (false, true, _) => {
@@ -151,6 +171,8 @@ impl FileWatcher {
             last_seen: ts,
             max_line_bytes,
             line_delimiter,
+            read_archives,
+            reading_archive,
             buf: BytesMut::new(),
         })
     }
@@ -161,7 +183,11 @@ impl FileWatcher {
             let mut reader = io::BufReader::new(fs::File::open(&path)?);
             let gzipped = is_gzipped(&mut reader)?;
             let new_reader: Box<dyn BufRead> = if gzipped {
-                if self.file_position != 0 {
+                if self.read_archives {
+                    // The file has been compressed after it was rotated.
+                    self.reading_archive = true;
+                    skip_decompressed(reader, self.file_position)?
+                } else if self.file_position != 0 {
                     Box::new(null_reader())
                 } else {
                     Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
@@ -287,6 +313,13 @@ impl FileWatcher {
     pub fn reached_eof(&self) -> bool {
         self.reached_eof
     }
+
+    /// The decompressed position at the end of the gzipped archive, once it
+    /// has been read to its end.
+    #[inline]
+    pub fn archive_end(&self) -> Option<FilePosition> {
+        (self.reading_archive && self.reached_eof).then_some(self.file_position)
+    }
 }
 
 fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
@@ -296,6 +329,17 @@ fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
     Ok(header_bytes.starts_with(&[0x1f, 0x8b]))
 }
 
+/// Decompresses the gzipped file, skipping the given number of decompressed
+/// bytes that have already been read.
+fn skip_decompressed(
+    reader: io::BufReader<fs::File>,
+    file_position: FilePosition,
+) -> io::Result<Box<dyn BufRead>> {
+    let mut reader = io::BufReader::new(MultiGzDecoder::new(reader));
+    io::copy(&mut (&mut reader).take(file_position), &mut io::sink())?;
+    Ok(Box::new(reader))
+}
+
 fn null_reader() -> impl BufRead {
     io::Cursor::new(Vec::new())
 }
@@ -6,6 +6,7 @@ use std::{
 };
 
 use crc::Crc;
+use flate2::read::MultiGzDecoder;
 use serde::{Deserialize, Serialize};
 
 use crate::{metadata_ext::PortableFileExt, FileSourceInternalEvents};
@@ -18,6 +19,10 @@ pub struct Fingerprinter {
     pub strategy: FingerprintStrategy,
     pub max_line_length: usize,
     pub ignore_not_found: bool,
+    /// Whether gzipped files are fingerprinted by their decompressed content,
+    /// so that a file that has been compressed after rotation keeps the same
+    /// fingerprint.
+    pub decompress_gzip: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -95,8 +100,17 @@ impl Fingerprinter {
             } => {
                 buffer.resize(self.max_line_length, 0u8);
                 let mut fp = fs::File::open(path)?;
-                fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
-                let bytes_read = fingerprinter_read_until(fp, b'\n', lines, buffer)?;
+                let bytes_read = if self.decompress_gzip && is_gzipped(&mut fp)? {
+                    let mut decoder = MultiGzDecoder::new(io::BufReader::new(fp));
+                    io::copy(
+                        &mut (&mut decoder).take(ignored_header_bytes as u64),
+                        &mut io::sink(),
+                    )?;
+                    fingerprinter_read_until(decoder, b'\n', lines, buffer)?
+                } else {
+                    fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
+                    fingerprinter_read_until(fp, b'\n', lines, buffer)?
+                };
                 let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..bytes_read]);
                 Ok(FirstLinesChecksum(fingerprint))
             }
@@ -248,6 +262,18 @@ fn fingerprinter_read_until_and_zerofill_buf(
     Ok(())
 }
 
+/// Whether the file starts with the gzip magic bytes, rewinding it afterwards.
+fn is_gzipped(file: &mut File) -> io::Result<bool> {
+    let mut header = [0u8; 2];
+    let gzipped = match file.read_exact(&mut header) {
+        Ok(()) => header == [0x1f, 0x8b],
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => false,
+        Err(error) => return Err(error),
+    };
+    file.seek(SeekFrom::Start(0))?;
+    Ok(gzipped)
+}
+
 fn fingerprinter_read_until(
     mut r: impl Read,
     delim: u8,
@@ -281,7 +307,13 @@ fn fingerprinter_read_until(
 
 #[cfg(test)]
 mod test {
-    use std::{collections::HashSet, fs, io::Error, path::Path, time::Duration};
+    use std::{
+        collections::HashSet,
+        fs,
+        io::{Error, Write},
+        path::Path,
+        time::Duration,
+    };
 
     use tempfile::tempdir;
 
@@ -297,6 +329,7 @@ mod test {
             },
             max_line_length: 1024,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let target_dir = tempdir().unwrap();
@@ -342,6 +375,7 @@ mod test {
             },
             max_line_length,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let target_dir = tempdir().unwrap();
@@ -416,6 +450,7 @@ mod test {
             },
             max_line_length,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let target_dir = tempdir().unwrap();
@@ -460,12 +495,47 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_gzip_fingerprint() {
+        let fingerprinter = |decompress_gzip| Fingerprinter {
+            strategy: FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: 1024,
+            ignore_not_found: false,
+            decompress_gzip,
+        };
+
+        let target_dir = tempdir().unwrap();
+        let data = b"first line\nsecond line\n";
+        let plain_path = target_dir.path().join("file.log.1");
+        let gzip_path = target_dir.path().join("file.log.1.gz");
+        fs::write(&plain_path, data).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&gzip_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut buf = Vec::new();
+        let mut run = |decompress_gzip, path| {
+            fingerprinter(decompress_gzip)
+                .get_fingerprint_of_file(path, &mut buf)
+                .unwrap()
+        };
+        assert_eq!(run(true, &plain_path), run(true, &gzip_path));
+        assert_ne!(run(false, &plain_path), run(false, &gzip_path));
+    }
+
     #[test]
     fn test_inode_fingerprint() {
         let fingerprinter = Fingerprinter {
             strategy: FingerprintStrategy::DevInode,
             max_line_length: 42,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let target_dir = tempdir().unwrap();
@@ -508,6 +578,7 @@ mod test {
             },
             max_line_length: 1024,
             ignore_not_found: false,
+            decompress_gzip: false,
         };
 
         let mut buf = Vec::new();
@@ -5,6 +5,7 @@
 extern crate scan_fmt;
 
 pub mod buffer;
+mod change_notifier;
 mod checkpointer;
 mod file_server;
 mod file_watcher;
@@ -51,3 +52,24 @@ impl From<ReadFromConfig> for ReadFrom {
         }
     }
 }
+
+/// How files are discovered.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryMode {
+    /// Search for files every `glob_minimum_cooldown_ms`.
+    #[default]
+    Poll,
+
+    /// Also subscribe to the change notifications of the operating system, to search for files
+    /// soon after they are created, removed, or renamed in the directories that they are in.
+    ///
+    /// The notifications come from `inotify` on Linux, `kqueue` on the BSDs, `FSEvents` on macOS,
+    /// and `ReadDirectoryChangesW` on Windows. This reduces the chance of missing files that are
+    /// rotated quickly. Searches triggered by notifications are at least a tenth of
+    /// `glob_minimum_cooldown_ms` apart, so files can be searched for up to ten times as often
+    /// as when polling. Files are still searched for every `glob_minimum_cooldown_ms`, in case
+    /// notifications are missed.
+    Native,
+}
@@ -1,6 +1,6 @@
 //! [`Glob`] paths provider.
 
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 pub use glob::MatchOptions;
 use glob::Pattern;
@@ -73,4 +73,31 @@ impl<E: FileSourceInternalEvents> PathsProvider for Glob<E> {
             })
             .collect()
     }
+    fn directories(&self) -> Vec<PathBuf> {
+        self.include_patterns
+            .iter()
+            .map(|include_pattern| {
+                // The longest leading part of the pattern without glob
+                // metacharacters, which is the directory that matches of the
+                // pattern are found in, or one of their ancestors.
+                let mut directory = PathBuf::new();
+                for component in Path::new(include_pattern).components() {
+                    if let Component::Normal(name) = component {
+                        if name
+                            .to_str()
+                            .is_some_and(|name| name.contains(['*', '?', '[']))
+                        {
+                            return directory;
+                        }
+                    }
+                    directory.push(component);
+                }
+                directory
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or(directory)
+            })
+            .filter(|directory| !directory.as_os_str().is_empty())
+            .collect()
+    }
 }
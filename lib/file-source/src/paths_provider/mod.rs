@@ -30,4 +30,13 @@ pub trait PathsProvider {
 
     /// Provides a set of paths.
     fn paths(&self) -> Self::IntoIter;
+
+    /// Provides the directories in which new paths can appear, besides the
+    /// parent directories of the paths that have already been provided.
+    ///
+    /// They are watched for changes when native change notifications are
+    /// enabled.
+    fn directories(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
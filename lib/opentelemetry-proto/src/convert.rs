@@ -5,7 +5,10 @@ use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use vector_core::{
     config::{log_schema, LegacyKey, LogNamespace},
-    event::{Event, LogEvent, TraceEvent},
+    event::{
+        metric::{Bucket, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricTags, MetricValue, TraceEvent,
+    },
 };
 use vrl::value::KeyString;
 use vrl::{
@@ -16,6 +19,11 @@ use vrl::{
 use super::proto::{
     common::v1::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{
+        metric::Data, number_data_point::Value as NumberValue, AggregationTemporality,
+        DataPointFlags, ExponentialHistogramDataPoint, HistogramDataPoint, Metric as OtelMetric,
+        NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+    },
     resource::v1::Resource,
     trace::v1::{
         span::{Event as SpanEvent, Link},
@@ -38,6 +46,11 @@ pub const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
 pub const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
 pub const FLAGS_KEY: &str = "flags";
 
+/// The prefix of the tags holding the resource attributes of a metric.
+pub const RESOURCE_TAG_PREFIX: &str = "resource.";
+/// The prefix of the tags holding the instrumentation scope of a metric.
+pub const SCOPE_TAG_PREFIX: &str = "scope.";
+
 impl ResourceLogs {
     pub fn into_event_iter(self, log_namespace: LogNamespace) -> impl Iterator<Item = Event> {
         let now = Utc::now();
@@ -75,6 +88,33 @@ impl ResourceSpans {
     }
 }
 
+impl ResourceMetrics {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let mut resource_tags = MetricTags::default();
+        if let Some(resource) = self.resource {
+            insert_attribute_tags(&mut resource_tags, RESOURCE_TAG_PREFIX, resource.attributes);
+        }
+
+        self.scope_metrics
+            .into_iter()
+            .flat_map(move |scope_metrics| {
+                let mut tags = resource_tags.clone();
+                if let Some(scope) = scope_metrics.scope {
+                    if !scope.name.is_empty() {
+                        tags.replace(format!("{SCOPE_TAG_PREFIX}{NAME_KEY}"), scope.name);
+                    }
+                    if !scope.version.is_empty() {
+                        tags.replace(format!("{SCOPE_TAG_PREFIX}{VERSION_KEY}"), scope.version);
+                    }
+                }
+                scope_metrics
+                    .metrics
+                    .into_iter()
+                    .flat_map(move |metric| metric_into_events(metric, &tags))
+            })
+    }
+}
+
 impl From<PBValue> for Value {
     fn from(av: PBValue) -> Self {
         match av {
@@ -422,3 +462,240 @@ impl From<SpanStatus> for Value {
         Value::Object(obj)
     }
 }
+
+/// Converts the value of an attribute into a tag value.
+fn any_value_to_tag(value: PBValue) -> String {
+    match value {
+        PBValue::StringValue(v) => v,
+        PBValue::BoolValue(v) => v.to_string(),
+        PBValue::IntValue(v) => v.to_string(),
+        PBValue::DoubleValue(v) => v.to_string(),
+        PBValue::BytesValue(v) => String::from_utf8_lossy(&v).into_owned(),
+        value @ (PBValue::ArrayValue(_) | PBValue::KvlistValue(_)) => {
+            Value::from(value).to_string()
+        }
+    }
+}
+
+fn insert_attribute_tags(tags: &mut MetricTags, prefix: &str, attributes: Vec<KeyValue>) {
+    for kv in attributes {
+        if let Some(value) = kv.value.and_then(|av| av.value) {
+            tags.replace(format!("{prefix}{}", kv.key), any_value_to_tag(value));
+        }
+    }
+}
+
+/// Converts every data point of a metric into a metric event.
+///
+/// Sums are converted into counters when they are monotonic, or into gauges otherwise, and
+/// exponential histograms into aggregated histograms. The metrics whose temporality is delta are
+/// incremental, and the others absolute.
+fn metric_into_events(metric: OtelMetric, tags: &MetricTags) -> Vec<Event> {
+    let name = metric.name;
+    let Some(data) = metric.data else {
+        return Vec::new();
+    };
+    let temporality_kind = |aggregation_temporality: i32| {
+        if aggregation_temporality == AggregationTemporality::Delta as i32 {
+            MetricKind::Incremental
+        } else {
+            MetricKind::Absolute
+        }
+    };
+
+    match data {
+        Data::Gauge(gauge) => gauge
+            .data_points
+            .into_iter()
+            .filter_map(|point| {
+                let value = number_value(&point)?;
+                Some(new_metric(
+                    &name,
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                    point.time_unix_nano,
+                    point.attributes,
+                    tags,
+                ))
+            })
+            .collect(),
+        Data::Sum(sum) => {
+            let kind = temporality_kind(sum.aggregation_temporality);
+            let is_monotonic = sum.is_monotonic;
+            sum.data_points
+                .into_iter()
+                .filter_map(|point| {
+                    let value = number_value(&point)?;
+                    let value = if is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    Some(new_metric(
+                        &name,
+                        kind,
+                        value,
+                        point.time_unix_nano,
+                        point.attributes,
+                        tags,
+                    ))
+                })
+                .collect()
+        }
+        Data::Histogram(histogram) => {
+            let kind = temporality_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| has_recorded_value(point.flags))
+                .map(|mut point| {
+                    let attributes = std::mem::take(&mut point.attributes);
+                    let time_unix_nano = point.time_unix_nano;
+                    let value = histogram_value(point);
+                    new_metric(&name, kind, value, time_unix_nano, attributes, tags)
+                })
+                .collect()
+        }
+        Data::ExponentialHistogram(histogram) => {
+            let kind = temporality_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| has_recorded_value(point.flags))
+                .map(|mut point| {
+                    let attributes = std::mem::take(&mut point.attributes);
+                    let time_unix_nano = point.time_unix_nano;
+                    let value = exponential_histogram_value(point);
+                    new_metric(&name, kind, value, time_unix_nano, attributes, tags)
+                })
+                .collect()
+        }
+        Data::Summary(summary) => summary
+            .data_points
+            .into_iter()
+            .filter(|point| has_recorded_value(point.flags))
+            .map(|mut point| {
+                let attributes = std::mem::take(&mut point.attributes);
+                let time_unix_nano = point.time_unix_nano;
+                let value = summary_value(point);
+                new_metric(
+                    &name,
+                    MetricKind::Absolute,
+                    value,
+                    time_unix_nano,
+                    attributes,
+                    tags,
+                )
+            })
+            .collect(),
+    }
+}
+
+fn new_metric(
+    name: &str,
+    kind: MetricKind,
+    value: MetricValue,
+    time_unix_nano: u64,
+    attributes: Vec<KeyValue>,
+    tags: &MetricTags,
+) -> Event {
+    let mut tags = tags.clone();
+    insert_attribute_tags(&mut tags, "", attributes);
+    let timestamp = (time_unix_nano > 0).then(|| Utc.timestamp_nanos(time_unix_nano as i64));
+
+    Metric::new(name, kind, value)
+        .with_timestamp(timestamp)
+        .with_tags(tags.as_option())
+        .into()
+}
+
+const fn has_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::NoRecordedValueMask as u32 == 0
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    if !has_recorded_value(point.flags) {
+        return None;
+    }
+    match point.value.as_ref()? {
+        NumberValue::AsDouble(value) => Some(*value),
+        NumberValue::AsInt(value) => Some(*value as f64),
+    }
+}
+
+/// Converts an explicit bucket histogram, whose last bucket is unbounded and only part of the count.
+fn histogram_value(point: HistogramDataPoint) -> MetricValue {
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(&point.bucket_counts)
+        .map(|(&upper_limit, &count)| Bucket { upper_limit, count })
+        .collect();
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+/// Converts an exponential histogram into buckets with explicit upper limits, ordered from the
+/// negative buckets to the positive ones.
+///
+/// https://opentelemetry.io/docs/specs/otel/metrics/data-model/#exponentialhistogram
+fn exponential_histogram_value(point: ExponentialHistogramDataPoint) -> MetricValue {
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut buckets = Vec::new();
+    if let Some(negative) = point.negative {
+        // The bucket at index `i` holds the values in `[-base^(i + 1), -base^i)`.
+        buckets.extend(
+            negative
+                .bucket_counts
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, &count)| Bucket {
+                    upper_limit: -base.powi(negative.offset + index as i32),
+                    count,
+                }),
+        );
+    }
+    buckets.push(Bucket {
+        upper_limit: point.zero_threshold,
+        count: point.zero_count,
+    });
+    if let Some(positive) = point.positive {
+        // The bucket at index `i` holds the values in `(base^i, base^(i + 1)]`.
+        buckets.extend(
+            positive
+                .bucket_counts
+                .iter()
+                .enumerate()
+                .map(|(index, &count)| Bucket {
+                    upper_limit: base.powi(positive.offset + index as i32 + 1),
+                    count,
+                }),
+        );
+    }
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+fn summary_value(point: SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: point.count,
+        sum: point.sum,
+    }
+}
@@ -1,18 +1,20 @@
 use ipnet::IpNet;
 use std::{
     collections::HashMap,
+    fmt::Write as _,
     future::Future,
-    net::SocketAddr,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
 };
 
 use futures::{future::BoxFuture, stream, FutureExt, Stream};
-use openssl::ssl::{Ssl, SslAcceptor, SslMethod};
-use openssl::x509::X509;
+use openssl::hash::MessageDigest;
+use openssl::ssl::{Ssl, SslAcceptor, SslContext, SslMethod};
+use openssl::x509::{GeneralNameRef, X509};
 use snafu::ResultExt;
-use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
 use tokio::{
     io::{self, AsyncRead, AsyncWrite, ReadBuf},
     net::{TcpListener, TcpStream},
@@ -24,7 +26,10 @@ use super::{
     CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings, MaybeTlsStream,
     SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
 };
-use crate::tcp::{self, TcpKeepaliveConfig};
+use crate::{
+    event::{ObjectMap, Value},
+    tcp::{self, TcpKeepaliveConfig},
+};
 
 impl TlsSettings {
     pub fn acceptor(&self) -> crate::tls::Result<SslAcceptor> {
@@ -44,14 +49,15 @@ impl MaybeTlsSettings {
     pub async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
 
-        let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
-            Self::Raw(()) => None,
+        let (acceptor, reloaded) = match self {
+            Self::Tls(tls) => (Some(tls.acceptor()?), tls.reloaded.clone()),
+            Self::Raw(()) => (None, None),
         };
 
         Ok(MaybeTlsListener {
             listener,
             acceptor,
+            reloaded,
             origin_filter: None,
         })
     }
@@ -63,14 +69,15 @@ impl MaybeTlsSettings {
     ) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
 
-        let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
-            Self::Raw(()) => None,
+        let (acceptor, reloaded) = match self {
+            Self::Tls(tls) => (Some(tls.acceptor()?), tls.reloaded.clone()),
+            Self::Raw(()) => (None, None),
         };
 
         Ok(MaybeTlsListener {
             listener,
             acceptor,
+            reloaded,
             origin_filter: Some(allow_origin),
         })
     }
@@ -79,6 +86,8 @@ impl MaybeTlsSettings {
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    /// The latest context to accept connections with, if the TLS settings are reloaded.
+    reloaded: Option<watch::Receiver<SslContext>>,
     origin_filter: Option<Vec<IpNet>>,
 }
 
@@ -89,7 +98,7 @@ impl MaybeTlsListener {
             .accept()
             .await
             .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
+                MaybeTlsIncomingStream::new(stream, peer_addr, self.context())
             })
             .context(IncomingListenerSnafu)?;
 
@@ -109,6 +118,16 @@ impl MaybeTlsListener {
         }
     }
 
+    fn context(&self) -> Option<SslContext> {
+        match &self.reloaded {
+            Some(reloaded) => Some(reloaded.borrow().clone()),
+            None => self
+                .acceptor
+                .as_ref()
+                .map(|acceptor| acceptor.context().to_owned()),
+        }
+    }
+
     async fn into_accept(
         mut self,
     ) -> (crate::tls::Result<MaybeTlsIncomingStream<TcpStream>>, Self) {
@@ -181,6 +200,7 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            reloaded: None,
             origin_filter: None,
         }
     }
@@ -248,12 +268,12 @@ impl MaybeTlsIncomingStream<TcpStream> {
     pub(super) fn new(
         stream: TcpStream,
         peer_addr: SocketAddr,
-        acceptor: Option<SslAcceptor>,
+        context: Option<SslContext>,
     ) -> Self {
-        let state = match acceptor {
-            Some(acceptor) => StreamState::Accepting(
+        let state = match context {
+            Some(context) => StreamState::Accepting(
                 async move {
-                    let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
+                    let ssl = Ssl::new(&context).context(SslBuildSnafu)?;
                     let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
                     Pin::new(&mut stream)
                         .accept()
@@ -391,6 +411,10 @@ pub struct CertificateMetadata {
     pub organization_name: Option<String>,
     pub organizational_unit_name: Option<String>,
     pub common_name: Option<String>,
+    /// The DNS names, IP addresses, email addresses and URIs of the subject alternative names.
+    pub subject_alt_names: Vec<String>,
+    /// The SHA-256 fingerprint of the DER-encoded certificate, as lowercase hex.
+    pub fingerprint_sha256: Option<String>,
 }
 
 impl CertificateMetadata {
@@ -416,6 +440,24 @@ impl CertificateMetadata {
         }
         components.join(",")
     }
+
+    /// The metadata inserted into events received over a connection with this client certificate.
+    pub fn to_object_map(&self) -> ObjectMap {
+        let mut metadata = ObjectMap::new();
+        metadata.insert("subject".into(), self.subject().into());
+        if !self.subject_alt_names.is_empty() {
+            let names = self
+                .subject_alt_names
+                .iter()
+                .map(|name| Value::from(name.as_str()))
+                .collect();
+            metadata.insert("subject_alt_names".into(), Value::Array(names));
+        }
+        if let Some(fingerprint) = &self.fingerprint_sha256 {
+            metadata.insert("fingerprint_sha256".into(), fingerprint.as_str().into());
+        }
+        metadata
+    }
 }
 
 impl From<X509> for CertificateMetadata {
@@ -435,10 +477,42 @@ impl From<X509> for CertificateMetadata {
             organization_name: subject_metadata.get("organizationName").cloned(),
             organizational_unit_name: subject_metadata.get("organizationalUnitName").cloned(),
             common_name: subject_metadata.get("commonName").cloned(),
+            subject_alt_names: cert
+                .subject_alt_names()
+                .map(|names| names.iter().filter_map(general_name_to_string).collect())
+                .unwrap_or_default(),
+            fingerprint_sha256: cert
+                .digest(MessageDigest::sha256())
+                .ok()
+                .map(|digest| to_hex(&digest)),
         }
     }
 }
 
+fn general_name_to_string(name: &GeneralNameRef) -> Option<String> {
+    if let Some(dns) = name.dnsname() {
+        return Some(dns.to_owned());
+    }
+    if let Some(ip) = name.ipaddress() {
+        let ip = match ip.len() {
+            4 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(ip).ok()?)),
+            16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(ip).ok()?)),
+            _ => return None,
+        };
+        return Some(ip.to_string());
+    }
+    name.email().or_else(|| name.uri()).map(ToOwned::to_owned)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            _ = write!(s, "{b:02x}");
+            s
+        })
+}
+
 #[derive(Clone)]
 pub struct MaybeTlsConnectInfo {
     pub remote_addr: SocketAddr,
@@ -477,6 +551,8 @@ mod test {
             organization_name: Some("organization".to_owned()),
             organizational_unit_name: Some("org_unit".to_owned()),
             state_or_province_name: Some("state".to_owned()),
+            subject_alt_names: Vec::new(),
+            fingerprint_sha256: None,
         };
 
         let expected = format!(
@@ -500,6 +576,8 @@ mod test {
             organization_name: Some("organization".to_owned()),
             organizational_unit_name: Some("org_unit".to_owned()),
             state_or_province_name: None,
+            subject_alt_names: Vec::new(),
+            fingerprint_sha256: None,
         };
 
         let expected = format!(
@@ -511,4 +589,17 @@ mod test {
         );
         assert_eq!(expected, example_meta.subject());
     }
+
+    #[test]
+    fn certificate_metadata_from_x509() {
+        let cert = X509::from_pem(&std::fs::read(crate::tls::TEST_PEM_CRT_PATH).unwrap()).unwrap();
+        let meta = CertificateMetadata::from(cert);
+
+        assert_eq!(meta.common_name.as_deref(), Some("localhost"));
+        assert!(meta.subject_alt_names.is_empty());
+        assert_eq!(
+            meta.fingerprint_sha256.as_deref(),
+            Some("50c9d18e10be62e8b6bbeaeca3a8b0ec119f5604ed9c033ff648120d168b72d1")
+        );
+    }
 }
@@ -0,0 +1,140 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+use openssl::ssl::SslContext;
+use tokio::sync::watch;
+
+use super::{
+    tls_connector_builder, MaybeTlsSettings, Result, TlsConfig, TlsEnableableConfig, TlsSettings,
+};
+
+impl MaybeTlsSettings {
+    /// Reloads the TLS settings, if TLS is enabled, whenever the files of `config` change. See
+    /// [`TlsSettings::reload_every`].
+    pub fn reload_every(
+        self,
+        config: &Option<TlsEnableableConfig>,
+        for_server: bool,
+        interval: Duration,
+    ) -> Result<Self> {
+        match (self, config) {
+            (Self::Tls(tls), Some(config)) => tls
+                .reload_every(config.options.clone(), for_server, interval)
+                .map(Self::Tls),
+            (settings, _) => Ok(settings),
+        }
+    }
+}
+
+impl TlsSettings {
+    /// Reloads these settings from `config` whenever the certificate, key, or CA files that it
+    /// refers to change, checking them every `interval`.
+    ///
+    /// Connections that are accepted or established after a reload use the reloaded identity and
+    /// authorities, while connections that were already established are kept, so certificates can
+    /// be rotated without restarting the component or dropping its connections. The settings are
+    /// checked until every clone of them has been dropped.
+    pub fn reload_every(
+        mut self,
+        config: TlsConfig,
+        for_server: bool,
+        interval: Duration,
+    ) -> Result<Self> {
+        let (sender, receiver) = watch::channel(self.context(for_server)?);
+        let mut loaded = fingerprint(&config);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {},
+                    () = sender.closed() => break,
+                }
+
+                // The files may be in the middle of being replaced, in which case they are read
+                // again on the next tick.
+                let current = fingerprint(&config);
+                if current.is_none() || current == loaded {
+                    continue;
+                }
+
+                let reloaded = TlsSettings::from_options_base(&Some(config.clone()), for_server)
+                    .and_then(|settings| settings.context(for_server));
+                match reloaded {
+                    Ok(context) => {
+                        loaded = current;
+                        sender.send_replace(context);
+                        info!(message = "Reloaded TLS certificates.");
+                    }
+                    Err(error) => error!(
+                        message = "Failed to reload TLS certificates.",
+                        %error,
+                        internal_log_rate_limit = true
+                    ),
+                }
+            }
+        });
+
+        self.reloaded = Some(receiver);
+        Ok(self)
+    }
+
+    /// Returns the context that connections are accepted with, if `for_server` is set, or
+    /// established with otherwise.
+    fn context(&self, for_server: bool) -> Result<SslContext> {
+        if for_server {
+            Ok(self.acceptor()?.into_context())
+        } else {
+            let settings = MaybeTlsSettings::Tls(self.clone());
+            Ok(tls_connector_builder(&settings)?.build().into_context())
+        }
+    }
+}
+
+/// Hashes the contents of the files of the configuration, or returns `None` if one of them can't be
+/// read.
+fn fingerprint(config: &TlsConfig) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    for path in [&config.ca_file, &config.crt_file, &config.key_file]
+        .into_iter()
+        .flatten()
+    {
+        // Inline PEM data isn't a path, and never changes.
+        match path.to_str() {
+            Some(pem) if pem.starts_with(super::PEM_START_MARKER) => pem.hash(&mut hasher),
+            _ => fs::read(path).ok()?.hash(&mut hasher),
+        }
+    }
+    Some(hasher.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tls::{TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH};
+
+    #[test]
+    fn fingerprints_files() {
+        let config = TlsConfig::test_config();
+        assert!(fingerprint(&config).is_some());
+        assert_eq!(fingerprint(&config), fingerprint(&config));
+
+        let swapped = TlsConfig {
+            crt_file: Some(TEST_PEM_CA_PATH.into()),
+            ca_file: Some(TEST_PEM_CRT_PATH.into()),
+            ..TlsConfig::test_config()
+        };
+        assert_ne!(fingerprint(&config), fingerprint(&swapped));
+
+        let missing = TlsConfig {
+            key_file: Some(format!("{TEST_PEM_KEY_PATH}.missing").into()),
+            ..TlsConfig::test_config()
+        };
+        assert_eq!(fingerprint(&missing), None);
+    }
+}
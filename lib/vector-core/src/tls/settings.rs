@@ -9,11 +9,15 @@ use lookup::lookup_v2::OptionalValuePath;
 use openssl::{
     pkcs12::{ParsedPkcs12_2, Pkcs12},
     pkey::{PKey, Private},
-    ssl::{select_next_proto, AlpnError, ConnectConfiguration, SslContextBuilder, SslVerifyMode},
+    ssl::{
+        select_next_proto, AlpnError, ConnectConfiguration, SslContext, SslContextBuilder,
+        SslVerifyMode,
+    },
     stack::Stack,
     x509::{store::X509StoreBuilder, X509},
 };
 use snafu::ResultExt;
+use tokio::sync::watch;
 use vector_config::configurable_component;
 
 use super::{
@@ -73,6 +77,9 @@ impl TlsEnableableConfig {
 #[derive(Clone, Debug, Default)]
 pub struct TlsSourceConfig {
     /// Event field for client certificate metadata.
+    ///
+    /// The metadata contains the `subject` of the client certificate, and when present, its
+    /// `subject_alt_names` and its SHA-256 fingerprint (`fingerprint_sha256`).
     pub client_metadata_key: Option<OptionalValuePath>,
 
     #[serde(flatten)]
@@ -178,6 +185,8 @@ pub struct TlsSettings {
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
     alpn_protocols: Option<Vec<u8>>,
     server_name: Option<String>,
+    /// The latest context, if the settings are reloaded when their files change.
+    pub(super) reloaded: Option<watch::Receiver<SslContext>>,
 }
 
 #[derive(Clone)]
@@ -213,6 +222,7 @@ impl TlsSettings {
             identity: options.load_identity()?,
             alpn_protocols: options.parse_alpn_protocols()?,
             server_name: options.server_name.clone(),
+            reloaded: None,
         })
     }
 
@@ -347,6 +357,9 @@ impl TlsSettings {
         &self,
         connection: &mut ConnectConfiguration,
     ) -> std::result::Result<(), openssl::error::ErrorStack> {
+        if let Some(reloaded) = &self.reloaded {
+            connection.set_ssl_context(&reloaded.borrow())?;
+        }
         connection.set_verify_hostname(self.verify_hostname);
         if let Some(server_name) = &self.server_name {
             // Prevent native TLS lib from inferring default SNI using domain name from url.
@@ -0,0 +1,116 @@
+//! Parsing of the connection strings of Azure Event Hubs and Service Bus namespaces.
+//!
+//! Both services share the connection string format: `;`-separated `Key=Value` properties, with
+//! the namespace in `Endpoint` and optionally an entity in `EntityPath`.
+use std::collections::HashMap;
+
+use snafu::Snafu;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub(crate) enum ConnectionStringError {
+    #[snafu(display("Connection string is missing the `{}` property", property))]
+    MissingProperty { property: &'static str },
+    #[snafu(display("Invalid `Endpoint` in connection string: {}", endpoint))]
+    InvalidEndpoint { endpoint: String },
+    #[snafu(display(
+        "{} must be set either with `{}` or with `EntityPath` in the connection string",
+        entity,
+        option
+    ))]
+    MissingEntity {
+        entity: &'static str,
+        option: &'static str,
+    },
+}
+
+/// The properties of the connection string of an Event Hubs or Service Bus namespace, or of an
+/// entity in it.
+pub(crate) struct ConnectionString<'a> {
+    properties: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> ConnectionString<'a> {
+    pub(crate) fn parse(connection_string: &'a str) -> Self {
+        let properties = connection_string
+            .split(';')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+        Self { properties }
+    }
+
+    /// The value of a property that must be set.
+    pub(crate) fn property(
+        &self,
+        property: &'static str,
+    ) -> Result<&'a str, ConnectionStringError> {
+        self.properties
+            .get(property)
+            .copied()
+            .ok_or(ConnectionStringError::MissingProperty { property })
+    }
+
+    /// The host of the namespace, from the `Endpoint` property.
+    pub(crate) fn namespace_host(&self) -> Result<&'a str, ConnectionStringError> {
+        let endpoint = self.property("Endpoint")?;
+        endpoint
+            .strip_prefix("sb://")
+            .map(|host| host.trim_end_matches('/'))
+            .filter(|host| !host.is_empty() && !host.contains('/'))
+            .ok_or_else(|| ConnectionStringError::InvalidEndpoint {
+                endpoint: endpoint.to_string(),
+            })
+    }
+
+    /// The entity, which is `entity` if set, and the `EntityPath` property otherwise.
+    ///
+    /// `name` and `option` describe the entity and the option that sets it in errors.
+    pub(crate) fn entity(
+        &self,
+        entity: Option<&'a str>,
+        name: &'static str,
+        option: &'static str,
+    ) -> Result<&'a str, ConnectionStringError> {
+        entity
+            .filter(|entity| !entity.is_empty())
+            .or_else(|| self.properties.get("EntityPath").copied())
+            .ok_or(ConnectionStringError::MissingEntity {
+                entity: name,
+                option,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_connection_string() {
+        let connection_string = ConnectionString::parse(
+            "Endpoint=sb://mynamespace.servicebus.windows.net/; SharedAccessKeyName=vector;SharedAccessKey=a=b;EntityPath=",
+        );
+
+        assert_eq!(
+            connection_string.namespace_host(),
+            Ok("mynamespace.servicebus.windows.net")
+        );
+        assert_eq!(
+            connection_string.property("SharedAccessKeyName"),
+            Ok("vector")
+        );
+        assert_eq!(connection_string.property("SharedAccessKey"), Ok("a=b"));
+        assert_eq!(
+            connection_string.entity(None, "Queue", "queue"),
+            Err(ConnectionStringError::MissingEntity {
+                entity: "Queue",
+                option: "queue"
+            })
+        );
+        assert_eq!(
+            connection_string.entity(Some("orders"), "Queue", "queue"),
+            Ok("orders")
+        );
+    }
+}
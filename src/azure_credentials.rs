@@ -0,0 +1,268 @@
+//! Azure Active Directory credentials shared by the Azure components.
+//!
+//! Components authenticate as a service principal when `client_credentials` are set, and with the
+//! [default credential chain][default_chain] otherwise.
+//!
+//! [default_chain]: https://learn.microsoft.com/en-us/azure/developer/intro/passwordless-overview#use-defaultazurecredential
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    AutoRefreshingTokenCredential, CertificateCredentialOptions, ClientCertificateCredential,
+    ClientSecretCredential, DefaultAzureCredential, TokenCredentialOptions,
+};
+use openssl::{error::ErrorStack, pkcs12::Pkcs12, pkey::PKey, stack::Stack, x509::X509};
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+#[derive(Debug, Snafu)]
+pub(crate) enum ClientCredentialsError {
+    #[snafu(display(
+        "Exactly one of `client_secret` and `client_certificate_path` must be set in `client_credentials`"
+    ))]
+    AmbiguousSecret,
+    #[snafu(display("Could not read client certificate {:?}: {}", path, source))]
+    ReadCertificate {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid client certificate {:?}: {}", path, source))]
+    InvalidCertificate { path: PathBuf, source: ErrorStack },
+    #[snafu(display("Client certificate {:?} contains no certificate", path))]
+    MissingCertificate { path: PathBuf },
+}
+
+/// The credentials of an Azure Active Directory service principal.
+///
+/// The service principal signs in with either a client secret or a client certificate. If unset,
+/// the [default credential chain][default_chain] is used, which tries environment variables,
+/// workload identity, managed identity, and the Azure CLI in turn.
+///
+/// [default_chain]: https://learn.microsoft.com/en-us/azure/developer/intro/passwordless-overview#use-defaultazurecredential
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ClientCredentials {
+    /// The ID of the Azure Active Directory tenant of the service principal.
+    #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+    pub tenant_id: String,
+
+    /// The client ID of the service principal.
+    #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+    pub client_id: String,
+
+    /// A client secret of the service principal.
+    ///
+    /// Either this or `client_certificate_path` must be set.
+    #[configurable(metadata(docs::examples = "${AZURE_CLIENT_SECRET}"))]
+    pub client_secret: Option<SensitiveString>,
+
+    /// The path to a client certificate of the service principal.
+    ///
+    /// The certificate is either a PKCS#12 (PFX) archive, or a PEM file with the certificate, its
+    /// private key, and optionally the rest of its chain.
+    ///
+    /// Either this or `client_secret` must be set.
+    #[configurable(metadata(docs::examples = "/etc/vector/service-principal.pfx"))]
+    #[configurable(metadata(docs::examples = "/etc/vector/service-principal.pem"))]
+    pub client_certificate_path: Option<PathBuf>,
+
+    /// The password of the client certificate.
+    ///
+    /// This is the password of the PKCS#12 archive, or of the encrypted private key in a PEM file.
+    #[configurable(metadata(docs::examples = "${AZURE_CLIENT_CERTIFICATE_PASSWORD}"))]
+    pub client_certificate_password: Option<SensitiveString>,
+}
+
+impl ClientCredentials {
+    fn credential(&self) -> Result<Arc<dyn TokenCredential>, ClientCredentialsError> {
+        match (&self.client_secret, &self.client_certificate_path) {
+            (Some(client_secret), None) => Ok(Arc::new(ClientSecretCredential::new(
+                azure_core::new_http_client(),
+                self.tenant_id.clone(),
+                self.client_id.clone(),
+                client_secret.inner().to_string(),
+                TokenCredentialOptions::default(),
+            ))),
+            (None, Some(path)) => {
+                let password = self
+                    .client_certificate_password
+                    .as_ref()
+                    .map_or("", |password| password.inner());
+                let certificate = load_certificate(path, password)?;
+                Ok(Arc::new(ClientCertificateCredential::new(
+                    self.tenant_id.clone(),
+                    self.client_id.clone(),
+                    openssl::base64::encode_block(&certificate),
+                    password.to_string(),
+                    CertificateCredentialOptions::default(),
+                )))
+            }
+            _ => Err(ClientCredentialsError::AmbiguousSecret),
+        }
+    }
+}
+
+/// The credential that a component authenticates to Azure with, which caches its tokens until
+/// they expire.
+pub(crate) fn token_credential(
+    client_credentials: Option<&ClientCredentials>,
+) -> Result<Arc<dyn TokenCredential>, ClientCredentialsError> {
+    let credential = match client_credentials {
+        Some(client_credentials) => client_credentials.credential()?,
+        None => Arc::new(DefaultAzureCredential::default()),
+    };
+    Ok(Arc::new(AutoRefreshingTokenCredential::new(credential)))
+}
+
+/// Loads a client certificate as a DER encoded PKCS#12 archive, converting it from PEM if needed.
+fn load_certificate(path: &Path, password: &str) -> Result<Vec<u8>, ClientCredentialsError> {
+    let data = fs::read(path).context(ReadCertificateSnafu { path })?;
+    let der = if data.starts_with(b"-----BEGIN") {
+        pem_to_pkcs12(&data, password)
+            .context(InvalidCertificateSnafu { path })?
+            .ok_or_else(|| ClientCredentialsError::MissingCertificate { path: path.into() })?
+    } else {
+        data
+    };
+
+    // Fail on a wrong password or a malformed archive now, rather than on every token request.
+    Pkcs12::from_der(&der)
+        .and_then(|pkcs12| pkcs12.parse2(password))
+        .context(InvalidCertificateSnafu { path })?;
+    Ok(der)
+}
+
+/// Bundles the certificate, private key and chain of a PEM file in a PKCS#12 archive, protected
+/// by the password of the private key.
+fn pem_to_pkcs12(pem: &[u8], password: &str) -> Result<Option<Vec<u8>>, ErrorStack> {
+    let mut certificates = X509::stack_from_pem(pem)?.into_iter();
+    let Some(certificate) = certificates.next() else {
+        return Ok(None);
+    };
+    let private_key = PKey::private_key_from_pem_passphrase(pem, password.as_bytes())?;
+    let mut chain = Stack::new()?;
+    for ca in certificates {
+        chain.push(ca)?;
+    }
+
+    let mut builder = Pkcs12::builder();
+    builder.pkey(&private_key).cert(&certificate);
+    if !chain.is_empty() {
+        builder.ca(chain);
+    }
+    builder.build2(password)?.to_der().map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{
+        asn1::Asn1Time, hash::MessageDigest, rsa::Rsa, symm::Cipher, x509::X509NameBuilder,
+    };
+
+    use super::*;
+
+    fn certificate() -> (PKey<openssl::pkey::Private>, X509) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "vector").unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (key, builder.build())
+    }
+
+    fn parse(der: &[u8], password: &str) -> X509 {
+        Pkcs12::from_der(der)
+            .unwrap()
+            .parse2(password)
+            .unwrap()
+            .cert
+            .unwrap()
+    }
+
+    #[test]
+    fn loads_pem_certificate() {
+        let (key, certificate) = certificate();
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = dir.path().join("plain.pem");
+        let mut pem = certificate.to_pem().unwrap();
+        pem.extend(key.private_key_to_pem_pkcs8().unwrap());
+        fs::write(&path, pem).unwrap();
+        let der = load_certificate(&path, "").unwrap();
+        assert_eq!(parse(&der, ""), certificate);
+
+        let path = dir.path().join("encrypted.pem");
+        let mut pem = certificate.to_pem().unwrap();
+        pem.extend(
+            key.private_key_to_pem_pkcs8_passphrase(Cipher::aes_256_cbc(), b"secret")
+                .unwrap(),
+        );
+        fs::write(&path, pem).unwrap();
+        let der = load_certificate(&path, "secret").unwrap();
+        assert_eq!(parse(&der, "secret"), certificate);
+        assert!(load_certificate(&path, "wrong").is_err());
+    }
+
+    #[test]
+    fn loads_pfx_certificate() {
+        let (key, certificate) = certificate();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("certificate.pfx");
+        let pfx = Pkcs12::builder()
+            .pkey(&key)
+            .cert(&certificate)
+            .build2("secret")
+            .unwrap();
+        fs::write(&path, pfx.to_der().unwrap()).unwrap();
+
+        let der = load_certificate(&path, "secret").unwrap();
+        assert_eq!(parse(&der, "secret"), certificate);
+        assert!(load_certificate(&path, "wrong").is_err());
+    }
+
+    #[test]
+    fn requires_one_secret() {
+        let credentials: ClientCredentials = toml::from_str(
+            r#"
+            tenant_id = "tenant"
+            client_id = "client"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            credentials.credential(),
+            Err(ClientCredentialsError::AmbiguousSecret)
+        ));
+
+        let credentials: ClientCredentials = toml::from_str(
+            r#"
+            tenant_id = "tenant"
+            client_id = "client"
+            client_secret = "secret"
+            client_certificate_path = "/etc/vector/service-principal.pem"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            credentials.credential(),
+            Err(ClientCredentialsError::AmbiguousSecret)
+        ));
+    }
+}
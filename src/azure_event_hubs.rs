@@ -0,0 +1,188 @@
+//! Shared functionality for the Azure Event Hubs source and sink.
+//!
+//! Both components talk to the Kafka-compatible endpoint of an Event Hubs namespace, so this
+//! module only deals with turning an Event Hubs connection string or Azure Active Directory
+//! credentials into Kafka connection settings.
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    azure_connection_string::{ConnectionString, ConnectionStringError},
+    kafka::{KafkaAuthConfig, KafkaSaslConfig},
+    tls::TlsEnableableConfig,
+};
+
+/// Event Hubs exposes its Kafka-compatible endpoint on this port of the namespace host.
+const KAFKA_ENDPOINT_PORT: u16 = 9093;
+
+/// When authenticating to the Kafka endpoint with a connection string, the SASL username is
+/// this fixed literal and the password is the connection string itself.
+const CONNECTION_STRING_SASL_USERNAME: &str = "$ConnectionString";
+
+/// The Kafka connection settings of an Event Hub.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct EventHubsConnection {
+    namespace_host: String,
+    event_hub_name: String,
+}
+
+impl EventHubsConnection {
+    /// The Kafka connection settings of an Event Hub in a namespace, given by its fully qualified
+    /// host name.
+    pub(crate) fn new(namespace: &str, event_hub_name: &str) -> Self {
+        Self {
+            namespace_host: namespace.trim_end_matches('/').to_string(),
+            event_hub_name: event_hub_name.to_string(),
+        }
+    }
+
+    /// Parses an Event Hubs connection string.
+    ///
+    /// The Event Hub is `event_hub_name` if set, and the `EntityPath` of the connection string
+    /// otherwise.
+    pub(crate) fn parse(
+        connection_string: &str,
+        event_hub_name: Option<&str>,
+    ) -> Result<Self, ConnectionStringError> {
+        let connection_string = ConnectionString::parse(connection_string);
+        let namespace_host = connection_string.namespace_host()?;
+        let event_hub_name =
+            connection_string.entity(event_hub_name, "Event Hub name", "event_hub_name")?;
+
+        Ok(Self {
+            namespace_host: namespace_host.to_string(),
+            event_hub_name: event_hub_name.to_string(),
+        })
+    }
+
+    /// The Kafka bootstrap server of the namespace.
+    pub(crate) fn bootstrap_servers(&self) -> String {
+        format!("{}:{}", self.namespace_host, KAFKA_ENDPOINT_PORT)
+    }
+
+    /// The fully qualified host name of the namespace.
+    pub(crate) fn namespace_host(&self) -> &str {
+        &self.namespace_host
+    }
+
+    /// The Event Hub, which the Kafka endpoint exposes as a topic.
+    pub(crate) fn event_hub_name(&self) -> &str {
+        &self.event_hub_name
+    }
+
+    /// The resource that Azure Active Directory tokens for the namespace are requested for.
+    pub(crate) fn token_resource(&self) -> String {
+        format!("https://{}", self.namespace_host)
+    }
+}
+
+/// Kafka authentication settings that sign in to the Event Hubs Kafka endpoint with a
+/// connection string.
+pub(crate) fn kafka_auth(connection_string: &SensitiveString) -> KafkaAuthConfig {
+    KafkaAuthConfig {
+        sasl: Some(KafkaSaslConfig {
+            enabled: Some(true),
+            username: Some(CONNECTION_STRING_SASL_USERNAME.to_string()),
+            password: Some(connection_string.clone()),
+            mechanism: Some("PLAIN".to_string()),
+        }),
+        tls: Some(TlsEnableableConfig::enabled()),
+    }
+}
+
+/// Kafka authentication settings that sign in to the Event Hubs Kafka endpoint with Azure Active
+/// Directory bearer tokens, which the client context provides.
+pub(crate) fn token_kafka_auth() -> KafkaAuthConfig {
+    KafkaAuthConfig {
+        sasl: Some(KafkaSaslConfig {
+            enabled: Some(true),
+            username: None,
+            password: None,
+            mechanism: Some("OAUTHBEARER".to_string()),
+        }),
+        tls: Some(TlsEnableableConfig::enabled()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONNECTION_STRING: &str = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret;EntityPath=logs";
+
+    #[test]
+    fn parse_connection_string_with_entity_path() {
+        let connection = EventHubsConnection::parse(CONNECTION_STRING, None).unwrap();
+        assert_eq!(
+            connection.bootstrap_servers(),
+            "mynamespace.servicebus.windows.net:9093"
+        );
+        assert_eq!(connection.event_hub_name(), "logs");
+    }
+
+    #[test]
+    fn event_hub_name_overrides_entity_path() {
+        assert_eq!(
+            EventHubsConnection::parse(CONNECTION_STRING, Some("metrics"))
+                .unwrap()
+                .event_hub_name(),
+            "metrics"
+        );
+    }
+
+    #[test]
+    fn parse_connection_string_errors() {
+        assert_eq!(
+            EventHubsConnection::parse("SharedAccessKeyName=vector;SharedAccessKey=secret", None),
+            Err(ConnectionStringError::MissingProperty {
+                property: "Endpoint"
+            })
+        );
+        assert_eq!(
+            EventHubsConnection::parse("Endpoint=https://mynamespace/;EntityPath=logs", None),
+            Err(ConnectionStringError::InvalidEndpoint {
+                endpoint: "https://mynamespace/".into()
+            })
+        );
+        assert_eq!(
+            EventHubsConnection::parse(
+                "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKey=secret",
+                None
+            ),
+            Err(ConnectionStringError::MissingEntity {
+                entity: "Event Hub name",
+                option: "event_hub_name"
+            })
+        );
+    }
+
+    #[test]
+    fn kafka_auth_uses_connection_string() {
+        let auth = kafka_auth(&CONNECTION_STRING.to_string().into());
+
+        let sasl = auth.sasl.unwrap();
+        assert_eq!(sasl.enabled, Some(true));
+        assert_eq!(sasl.username.as_deref(), Some("$ConnectionString"));
+        assert_eq!(
+            sasl.password.as_ref().map(SensitiveString::inner),
+            Some(CONNECTION_STRING)
+        );
+        assert_eq!(sasl.mechanism.as_deref(), Some("PLAIN"));
+        assert_eq!(auth.tls.and_then(|tls| tls.enabled), Some(true));
+    }
+
+    #[test]
+    fn token_kafka_auth_uses_oauthbearer() {
+        let connection = EventHubsConnection::new("mynamespace.servicebus.windows.net", "logs");
+        assert_eq!(
+            connection.token_resource(),
+            "https://mynamespace.servicebus.windows.net"
+        );
+
+        let auth = token_kafka_auth();
+        let sasl = auth.sasl.unwrap();
+        assert_eq!(sasl.enabled, Some(true));
+        assert_eq!(sasl.mechanism.as_deref(), Some("OAUTHBEARER"));
+        assert!(sasl.username.is_none() && sasl.password.is_none());
+        assert_eq!(auth.tls.and_then(|tls| tls.enabled), Some(true));
+    }
+}
@@ -100,7 +100,9 @@ fn spawn_input_http_server(
     let outstanding_events = Arc::new(Mutex::new(VecDeque::new()));
 
     // First, we'll build and spawn our HTTP server.
-    let encoder = codec.into_encoder();
+    let encoder = codec
+        .into_encoder()
+        .expect("encoder was already built by the runner");
     let sendable_events = Arc::clone(&outstanding_events);
 
     let (resource_notifier, http_server_shutdown_tx) = spawn_http_server(
@@ -203,7 +205,9 @@ fn spawn_input_http_client(
     // request-per-input-item basis. This runs serially and has no parallelism.
     let started = task_coordinator.track_started();
     let completed = task_coordinator.track_completed();
-    let mut encoder = codec.into_encoder();
+    let mut encoder = codec
+        .into_encoder()
+        .expect("encoder was already built by the runner");
     let runner_metrics = Arc::clone(runner_metrics);
 
     tokio::spawn(async move {
@@ -75,7 +75,9 @@ impl ResourceCodec {
     ///
     /// The encoder is generated as an inverse to the input codec: if a decoding configuration was
     /// given, we generate an encoder that satisfies that decoding configuration, and vice versa.
-    pub fn into_encoder(&self) -> Encoder<encoding::Framer> {
+    ///
+    /// Returns an error if no encoder can be generated for the codec.
+    pub fn into_encoder(&self) -> vector_lib::Result<Encoder<encoding::Framer>> {
         let (framer, serializer) = match self {
             Self::Encoding(config) => (
                 Framer::Bytes(BytesEncoder),
@@ -95,11 +97,11 @@ impl ResourceCodec {
             }
             Self::Decoding(config) => (
                 decoder_framing_to_encoding_framer(config.framing()),
-                deserializer_config_to_serializer(config.config()),
+                deserializer_config_to_serializer(config.config())?,
             ),
         };
 
-        Encoder::<encoding::Framer>::new(framer, serializer)
+        Ok(Encoder::<encoding::Framer>::new(framer, serializer))
     }
 
     /// Gets a decoder for this codec.
@@ -145,7 +147,9 @@ impl From<DecodingConfig> for ResourceCodec {
     }
 }
 
-fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::Serializer {
+fn deserializer_config_to_serializer(
+    config: &DeserializerConfig,
+) -> vector_lib::Result<encoding::Serializer> {
     let serializer_config = match config {
         // TODO: This isn't necessarily a one-to-one conversion, at least not in the future when
         // "bytes" can be a top-level field and we aren't implicitly decoding everything into the
@@ -171,12 +175,23 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         DeserializerConfig::Avro { avro } => SerializerConfig::Avro { avro: avro.into() },
         // TODO: Influxdb has no serializer yet
         DeserializerConfig::Influxdb { .. } => todo!(),
+        // Parquet has no serializer, so sources decoding it can't be validated.
+        #[cfg(feature = "codecs-parquet")]
+        DeserializerConfig::Parquet => {
+            return Err("the `parquet` codec is not supported by component validation".into())
+        }
+        // Event Hubs Capture has no serializer, so sources decoding it can't be validated.
+        DeserializerConfig::EventHubCapture => {
+            return Err(
+                "the `event_hub_capture` codec is not supported by component validation".into(),
+            )
+        }
         DeserializerConfig::Vrl { .. } => unimplemented!(),
     };
 
-    serializer_config
+    Ok(serializer_config
         .build()
-        .expect("building serializer should never fail")
+        .expect("building serializer should never fail"))
 }
 
 fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> encoding::Framer {
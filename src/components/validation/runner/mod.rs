@@ -462,7 +462,10 @@ fn build_external_resource(
         .as_ref()
         .map(|resource| resource.codec.clone());
 
-    let maybe_encoder = resource_codec.as_ref().map(|codec| codec.into_encoder());
+    let maybe_encoder = resource_codec
+        .as_ref()
+        .map(|codec| codec.into_encoder())
+        .transpose()?;
 
     match component_type {
         ComponentType::Source => {
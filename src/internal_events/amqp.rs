@@ -69,6 +69,28 @@ pub mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub struct AmqpNackError {
+        pub error: lapin::Error,
+    }
+
+    impl InternalEvent for AmqpNackError {
+        fn emit(self) {
+            error!(message = "Unable to nack.",
+                   error = ?self.error,
+                   error_type = error_type::ACKNOWLEDGMENT_FAILED,
+                   stage = error_stage::RECEIVING,
+                   internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total",
+                "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+                "stage" => error_stage::RECEIVING,
+            )
+            .increment(1);
+        }
+    }
+
     #[derive(Debug)]
     pub struct AmqpRejectError {
         pub error: lapin::Error,
@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct AzureDataLakeMissingRequiredColumnError<'a> {
+    pub column: &'a str,
+}
+
+impl InternalEvent for AzureDataLakeMissingRequiredColumnError<'_> {
+    fn emit(self) {
+        let reason = "Event has no value of the right type for a required Parquet column.";
+        error!(
+            message = reason,
+            column = self.column,
+            error_code = "missing_required_column",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "missing_required_column",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct AzureMonitorMetricsUnsupportedMetricError<'a> {
+    pub metric_type: &'a str,
+}
+
+impl InternalEvent for AzureMonitorMetricsUnsupportedMetricError<'_> {
+    fn emit(self) {
+        let reason = "Metric type is not supported.";
+        error!(
+            message = reason,
+            metric_type = self.metric_type,
+            error_code = "unsupported_metric_type",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "unsupported_metric_type",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
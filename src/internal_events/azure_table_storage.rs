@@ -0,0 +1,122 @@
+use http::StatusCode;
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct AzureTableStorageInvalidKeyError<'a> {
+    pub field: &'static str,
+    pub key: &'a str,
+}
+
+impl InternalEvent for AzureTableStorageInvalidKeyError<'_> {
+    fn emit(self) {
+        let reason = "Entity key contains characters that are not allowed.";
+        error!(
+            message = reason,
+            field = self.field,
+            key = self.key,
+            error_code = "invalid_entity_key",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "invalid_entity_key",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureTableStorageDuplicateKeyError<'a> {
+    pub partition_key: &'a str,
+    pub row_key: &'a str,
+}
+
+impl InternalEvent for AzureTableStorageDuplicateKeyError<'_> {
+    fn emit(self) {
+        let reason = "Entity with the same keys is already inserted by the batch.";
+        error!(
+            message = reason,
+            partition_key = self.partition_key,
+            row_key = self.row_key,
+            error_code = "duplicate_entity_key",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "duplicate_entity_key",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureTableStorageInvalidPropertyError<'a> {
+    pub name: &'a str,
+}
+
+impl InternalEvent for AzureTableStorageInvalidPropertyError<'_> {
+    fn emit(self) {
+        let reason = "Entity property name is not allowed.";
+        error!(
+            message = reason,
+            name = self.name,
+            error_code = "invalid_property_name",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "invalid_property_name",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureTableStorageEntityRejectedError<'a> {
+    pub partition_key: &'a str,
+    pub row_key: &'a str,
+    pub status: StatusCode,
+    pub response: &'a str,
+}
+
+impl InternalEvent for AzureTableStorageEntityRejectedError<'_> {
+    fn emit(self) {
+        let reason = "Entity was rejected by the service.";
+        error!(
+            message = reason,
+            partition_key = self.partition_key,
+            row_key = self.row_key,
+            status = %self.status,
+            response = self.response,
+            error_code = "entity_rejected",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "entity_rejected",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,31 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct ClickhouseColumnEncodingError<'a> {
+    pub column: &'a str,
+    pub error: String,
+}
+
+impl InternalEvent for ClickhouseColumnEncodingError<'_> {
+    fn emit(self) {
+        let reason = "Failed to encode event field for its column.";
+        error!(
+            message = reason,
+            column = %self.column,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,59 @@
+use std::{io, path::Path};
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::database::PollError;
+
+#[derive(Debug)]
+pub struct DatabasePollError<'a> {
+    pub error: &'a PollError,
+}
+
+impl<'a> InternalEvent for DatabasePollError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to poll the database.",
+            error = %self.error,
+            error_code = "failed_polling",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_polling",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct DatabaseCheckpointWriteError<'a> {
+    pub error: io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for DatabaseCheckpointWriteError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,132 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::gcp_cloud_storage::pubsub::{ProcessingError, PubsubError};
+
+#[derive(Debug)]
+pub struct GcpCloudStoragePullError<'a> {
+    pub error: &'a PubsubError,
+}
+
+impl<'a> InternalEvent for GcpCloudStoragePullError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull bucket notifications.",
+            error = %self.error,
+            error_code = "failed_pulling_notifications",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_pulling_notifications",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpCloudStorageAckDeadlineError<'a> {
+    pub error: &'a PubsubError,
+}
+
+impl<'a> InternalEvent for GcpCloudStorageAckDeadlineError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to extend the acknowledgement deadline of bucket notifications.",
+            error = %self.error,
+            error_code = "failed_modifying_ack_deadline",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_modifying_ack_deadline",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpCloudStorageAcknowledgeError<'a> {
+    pub count: usize,
+    pub error: &'a PubsubError,
+}
+
+impl<'a> InternalEvent for GcpCloudStorageAcknowledgeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge bucket notifications.",
+            count = %self.count,
+            error = %self.error,
+            error_code = "failed_acknowledging_notifications",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_acknowledging_notifications",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpCloudStorageNotificationProcessingError<'a> {
+    pub message_id: &'a str,
+    pub error: &'a ProcessingError,
+}
+
+impl<'a> InternalEvent for GcpCloudStorageNotificationProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process bucket notification.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_processing_notification",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_processing_notification",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpCloudStorageNotificationIgnored<'a> {
+    pub bucket: &'a str,
+    pub object: &'a str,
+    pub event_type: &'a str,
+}
+
+impl<'a> InternalEvent for GcpCloudStorageNotificationIgnored<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Ignored bucket notification for an event that was not OBJECT_FINALIZE.",
+            bucket = %self.bucket,
+            object = %self.object,
+            event_type = %self.event_type,
+        );
+        counter!(
+            "gcp_cloud_storage_notification_ignored_total",
+            "ignore_type" => "invalid_event_type"
+        )
+        .increment(1);
+    }
+}
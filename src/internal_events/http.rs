@@ -186,3 +186,30 @@ impl<'a> InternalEvent for HttpInternalError<'a> {
         .increment(1);
     }
 }
+
+#[cfg(feature = "sources-utils-http-auth")]
+#[derive(Debug)]
+pub struct HttpSourceJwksRefreshError<'a> {
+    pub error: &'a dyn Error,
+}
+
+#[cfg(feature = "sources-utils-http-auth")]
+impl<'a> InternalEvent for HttpSourceJwksRefreshError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed refreshing JSON Web Key Set.",
+            error = %self.error,
+            error_code = "failed_refreshing_jwks",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_refreshing_jwks",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
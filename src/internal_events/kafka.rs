@@ -65,6 +65,30 @@ impl<'a> InternalEvent for KafkaEventsReceived<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaMessageFiltered<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+impl<'a> InternalEvent for KafkaMessageFiltered<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Message skipped by the header filter.",
+            topic = self.topic,
+            partition = %self.partition,
+            offset = %self.offset,
+        );
+        counter!(
+            "kafka_filtered_messages_total",
+            "topic" => self.topic.to_string(),
+            "partition" => self.partition.to_string(),
+        )
+        .increment(1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaOffsetUpdateError {
     pub error: rdkafka::error::KafkaError,
@@ -90,6 +114,31 @@ impl InternalEvent for KafkaOffsetUpdateError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaCheckpointError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for KafkaCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Unable to load or store checkpoints.",
+            error = %self.error,
+            error_code = "kafka_checkpoint",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "kafka_checkpoint",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaReadError {
     pub error: rdkafka::error::KafkaError,
@@ -115,6 +164,33 @@ impl InternalEvent for KafkaReadError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaTransactionError {
+    pub operation: &'static str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for KafkaTransactionError {
+    fn emit(self) {
+        error!(
+            message = "Kafka transaction failed.",
+            operation = self.operation,
+            error = %self.error,
+            error_code = "kafka_transaction",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaStatisticsReceived<'a> {
     pub statistics: &'a rdkafka::Statistics,
@@ -26,10 +26,20 @@ mod aws_kinesis;
 mod aws_kinesis_firehose;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
+#[cfg(feature = "sinks-azure_data_lake")]
+mod azure_data_lake;
+#[cfg(feature = "sinks-azure_monitor_metrics")]
+mod azure_monitor_metrics;
+#[cfg(feature = "sinks-azure_table_storage")]
+mod azure_table_storage;
 mod batch;
+#[cfg(feature = "sinks-clickhouse")]
+mod clickhouse;
 mod codecs;
 mod common;
 mod conditions;
+#[cfg(feature = "sources-database")]
+mod database;
 #[cfg(feature = "sources-datadog_agent")]
 mod datadog_agent;
 #[cfg(feature = "sinks-datadog_metrics")]
@@ -55,9 +65,15 @@ mod file_descriptor;
 mod filter;
 #[cfg(feature = "sources-fluent")]
 mod fluent;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+mod gcp_cloud_storage;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 mod grpc;
 mod heartbeat;
 #[cfg(feature = "sources-host_metrics")]
@@ -87,10 +103,16 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-mqtt")]
+#[cfg(any(feature = "sinks-mqtt", feature = "sources-mqtt"))]
 mod mqtt;
+#[cfg(feature = "sources-nats")]
+mod nats;
+#[cfg(feature = "sources-netflow")]
+mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
+#[cfg(feature = "sources-object_store")]
+mod object_store;
 mod open;
 mod parser;
 #[cfg(feature = "sources-postgresql_metrics")]
@@ -112,6 +134,8 @@ mod remap;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sources-sflow")]
+mod sflow;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -127,6 +151,8 @@ mod udp;
 mod unix;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+mod windows_eventlog;
 
 #[cfg(any(
     feature = "sources-file",
@@ -164,7 +190,17 @@ pub(crate) use self::aws_kinesis::*;
 pub(crate) use self::aws_kinesis_firehose::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-azure_data_lake")]
+pub(crate) use self::azure_data_lake::*;
+#[cfg(feature = "sinks-azure_monitor_metrics")]
+pub(crate) use self::azure_monitor_metrics::*;
+#[cfg(feature = "sinks-azure_table_storage")]
+pub(crate) use self::azure_table_storage::*;
+#[cfg(feature = "sinks-clickhouse")]
+pub(crate) use self::clickhouse::*;
 pub(crate) use self::codecs::*;
+#[cfg(feature = "sources-database")]
+pub(crate) use self::database::*;
 #[cfg(feature = "sources-datadog_agent")]
 pub(crate) use self::datadog_agent::*;
 #[cfg(feature = "sinks-datadog_metrics")]
@@ -195,9 +231,15 @@ pub(crate) use self::file_descriptor::*;
 pub(crate) use self::filter::*;
 #[cfg(feature = "sources-fluent")]
 pub(crate) use self::fluent::*;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+pub(crate) use self::gcp_cloud_storage::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 pub(crate) use self::grpc::*;
 #[cfg(feature = "sources-host_metrics")]
 pub(crate) use self::host_metrics::*;
@@ -223,10 +265,16 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-mqtt")]
+#[cfg(any(feature = "sinks-mqtt", feature = "sources-mqtt"))]
 pub(crate) use self::mqtt::*;
+#[cfg(feature = "sources-nats")]
+pub(crate) use self::nats::*;
+#[cfg(feature = "sources-netflow")]
+pub(crate) use self::netflow::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sources-object_store")]
+pub(crate) use self::object_store::*;
 #[allow(unused_imports)]
 pub(crate) use self::parser::*;
 #[cfg(feature = "sources-postgresql_metrics")]
@@ -249,6 +297,8 @@ pub(crate) use self::remap::*;
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sources-sflow")]
+pub(crate) use self::sflow::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
@@ -263,6 +313,8 @@ pub(crate) use self::unix::*;
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+pub(crate) use self::windows_eventlog::*;
 pub use self::{
     adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
     heartbeat::*, http::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
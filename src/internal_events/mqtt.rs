@@ -33,3 +33,82 @@ impl InternalEvent for MqttConnectionError {
         Some("MqttConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct MqttSourceConnectionError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MqttSourceConnectionError {
+    fn emit(self) {
+        error!(
+            message = "MQTT connection error.",
+            error = %self.error,
+            error_code = "mqtt_connection_error",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "mqtt_connection_error",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttSourceConnectionError")
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttSubscribeError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MqttSubscribeError {
+    fn emit(self) {
+        error!(
+            message = "Unable to subscribe to the MQTT topics.",
+            error = %self.error,
+            error_code = "mqtt_subscribe_error",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "mqtt_subscribe_error",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttAckError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for MqttAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to ack.",
+            error = %self.error,
+            error_code = "mqtt_ack_error",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "mqtt_ack_error",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
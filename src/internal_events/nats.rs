@@ -0,0 +1,49 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NatsJetStreamReceiveError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsJetStreamReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive JetStream message.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsJetStreamAckError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsJetStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to ack JetStream message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,59 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::netflow::DecodeError;
+
+#[derive(Debug)]
+pub struct NetflowDecodeError {
+    pub error: DecodeError,
+    pub exporter: SocketAddr,
+}
+
+impl InternalEvent for NetflowDecodeError {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode flow packet.",
+            error = %self.error,
+            exporter = %self.exporter,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowTemplateMissing {
+    pub template_id: u16,
+    pub exporter: SocketAddr,
+}
+
+impl InternalEvent for NetflowTemplateMissing {
+    fn emit(self) {
+        warn!(
+            message = "Dropping flow records whose template has not been received.",
+            template_id = self.template_id,
+            exporter = %self.exporter,
+            error_code = "template_missing",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "template_missing",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,92 @@
+use std::{io, path::Path};
+
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
+use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::object_store::ProcessingError;
+
+#[derive(Debug)]
+pub struct ObjectStoreListError<'a> {
+    pub bucket: &'a str,
+    pub error: SdkError<ListObjectsV2Error, HttpResponse>,
+}
+
+impl<'a> InternalEvent for ObjectStoreListError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to list objects.",
+            bucket = %self.bucket,
+            error = %self.error,
+            error_code = "failed_listing_objects",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_listing_objects",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ObjectStoreObjectProcessingError<'a> {
+    pub bucket: &'a str,
+    pub key: &'a str,
+    pub error: &'a ProcessingError,
+}
+
+impl<'a> InternalEvent for ObjectStoreObjectProcessingError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process object. It will be read again on the next poll.",
+            bucket = %self.bucket,
+            key = %self.key,
+            error = %self.error,
+            error_code = "failed_processing_object",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_processing_object",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ObjectStoreCheckpointWriteError<'a> {
+    pub error: io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for ObjectStoreCheckpointWriteError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing checkpoints.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "writing_checkpoints",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "writing_checkpoints",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
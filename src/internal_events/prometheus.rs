@@ -41,6 +41,31 @@ impl<'a> InternalEvent for PrometheusParseError<'a> {
     }
 }
 
+#[cfg(feature = "sources-prometheus-scrape")]
+#[derive(Debug)]
+pub struct PrometheusDiscoveryError {
+    pub error: crate::Error,
+}
+
+#[cfg(feature = "sources-prometheus-scrape")]
+impl InternalEvent for PrometheusDiscoveryError {
+    fn emit(self) {
+        error!(
+            message = "Failed to discover scrape targets.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
 #[derive(Debug)]
 pub struct PrometheusRemoteWriteParseError {
     pub error: prost::DecodeError,
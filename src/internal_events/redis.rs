@@ -34,3 +34,30 @@ impl InternalEvent for RedisReceiveEventError {
         .increment(1);
     }
 }
+
+#[derive(Debug)]
+pub struct RedisStreamAckError {
+    pub error: redis::RedisError,
+    pub count: usize,
+}
+
+impl InternalEvent for RedisStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge stream entries. They remain pending and will be read again.",
+            error = %self.error,
+            count = %self.count,
+            error_code = "failed_acknowledging_entries",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "failed_acknowledging_entries",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,32 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::sflow::DecodeError;
+
+#[derive(Debug)]
+pub struct SflowDecodeError {
+    pub error: DecodeError,
+    pub agent: SocketAddr,
+}
+
+impl InternalEvent for SflowDecodeError {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode sFlow datagram.",
+            error = %self.error,
+            agent = %self.agent,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
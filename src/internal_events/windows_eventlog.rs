@@ -0,0 +1,101 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+use crate::sources::windows_eventlog::parser::ParseError;
+
+#[derive(Debug)]
+pub struct WindowsEventLogSubscribeError {
+    pub error: windows::core::Error,
+}
+
+impl InternalEvent for WindowsEventLogSubscribeError {
+    fn emit(self) {
+        error!(
+            message = "Failed to subscribe to the event log.",
+            error = %self.error,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::CONNECTION_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogReadError {
+    pub error: windows::core::Error,
+}
+
+impl InternalEvent for WindowsEventLogReadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read events from the event log.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError {
+    pub error: ParseError,
+}
+
+impl InternalEvent for WindowsEventLogParseError {
+    fn emit(self) {
+        error!(
+            message = "Invalid event from the event log, discarding.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogBookmarkError {
+    pub error: std::io::Error,
+    pub path: String,
+}
+
+impl InternalEvent for WindowsEventLogBookmarkError {
+    fn emit(self) {
+        error!(
+            message = "Failed to persist the event log bookmark.",
+            error = %self.error,
+            path = %self.path,
+            error_code = "writing_bookmark",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "writing_bookmark",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -1,7 +1,16 @@
 #![allow(missing_docs)]
-use std::path::{Path, PathBuf};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use rdkafka::{consumer::ConsumerContext, ClientConfig, ClientContext, Statistics};
+use rdkafka::{
+    client::OAuthToken,
+    consumer::ConsumerContext,
+    producer::{DeliveryResult, ProducerContext},
+    ClientConfig, ClientContext, Statistics,
+};
 use snafu::Snafu;
 use tracing::Span;
 use vector_lib::configurable::configurable_component;
@@ -167,6 +176,7 @@ fn pathbuf_to_string(path: &Path) -> crate::Result<&str> {
         .ok_or_else(|| KafkaError::InvalidPath { path: path.into() }.into())
 }
 
+#[derive(Clone)]
 pub(crate) struct KafkaStatisticsContext {
     pub(crate) expose_lag_metrics: bool,
     pub span: Span,
@@ -185,3 +195,48 @@ impl ClientContext for KafkaStatisticsContext {
 }
 
 impl ConsumerContext for KafkaStatisticsContext {}
+
+/// Provides the bearer tokens of clients that authenticate with the `OAUTHBEARER` SASL mechanism,
+/// in place of the token retrieval built into `librdkafka`.
+pub(crate) trait KafkaTokenProvider: Send + Sync {
+    /// Fetches a new token.
+    ///
+    /// This is called from a `librdkafka` thread, which it may block.
+    fn token(&self) -> Result<OAuthToken, Box<dyn Error>>;
+}
+
+/// A [`KafkaStatisticsContext`] whose client gets its bearer tokens from a [`KafkaTokenProvider`].
+///
+/// Clients created with this context always use the provider, so it must only be used when
+/// authenticating with `OAUTHBEARER` tokens that `librdkafka` can't retrieve itself.
+#[derive(Clone)]
+pub(crate) struct KafkaTokenContext {
+    pub(crate) statistics: KafkaStatisticsContext,
+    pub(crate) token_provider: Arc<dyn KafkaTokenProvider>,
+}
+
+impl ClientContext for KafkaTokenContext {
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
+    fn stats(&self, statistics: Statistics) {
+        self.statistics.stats(statistics);
+    }
+
+    fn generate_oauth_token(
+        &self,
+        _oauthbearer_config: Option<&str>,
+    ) -> Result<OAuthToken, Box<dyn Error>> {
+        self.token_provider.token()
+    }
+}
+
+impl ProducerContext for KafkaTokenContext {
+    type DeliveryOpaque = ();
+
+    fn delivery(
+        &self,
+        _delivery_result: &DeliveryResult<'_>,
+        _delivery_opaque: Self::DeliveryOpaque,
+    ) {
+    }
+}
@@ -67,6 +67,28 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs",
+    feature = "sinks-azure_service_bus"
+))]
+pub(crate) mod azure_connection_string;
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-azure_data_explorer",
+    feature = "sinks-azure_data_lake",
+    feature = "sinks-azure_event_hubs",
+    feature = "sinks-azure_logs_ingestion",
+    feature = "sinks-azure_monitor_metrics",
+    feature = "sinks-azure_storage_queue",
+    feature = "sources-azure_event_hubs"
+))]
+pub(crate) mod azure_credentials;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+pub(crate) mod azure_event_hubs;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub mod common;
@@ -0,0 +1,223 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::DefaultAzureCredential;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use serde::Deserialize;
+use vector_lib::configurable::{component::GenerateConfig, configurable_component};
+
+use crate::config::ProxyConfig;
+use crate::http::HttpClient;
+use crate::tls::{TlsConfig, TlsSettings};
+use crate::{config::SecretBackend, signal};
+
+/// The version of the Key Vault REST API that secrets are retrieved with.
+const API_VERSION: &str = "7.4";
+
+/// The resource that access tokens for Key Vault are requested for.
+///
+/// The credentials append the `/.default` scope to it themselves.
+const TOKEN_RESOURCE: &str = "https://vault.azure.net";
+
+/// Configuration for the `azure_key_vault` secrets backend.
+#[configurable_component(secrets("azure_key_vault"))]
+#[derive(Clone, Debug)]
+pub struct AzureKeyVaultBackend {
+    /// The URL of the key vault to retrieve secrets from.
+    #[configurable(metadata(docs::examples = "https://my-vault.vault.azure.net"))]
+    pub vault_url: String,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub proxy: ProxyConfig,
+}
+
+impl GenerateConfig for AzureKeyVaultBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(AzureKeyVaultBackend {
+            vault_url: String::from("https://my-vault.vault.azure.net"),
+            tls: None,
+            proxy: ProxyConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Deserialize)]
+struct SecretBundle {
+    value: Option<String>,
+}
+
+impl SecretBackend for AzureKeyVaultBackend {
+    async fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        // Secrets are retrieved before the global options are loaded, so the proxy is taken from
+        // the environment and the backend options only.
+        let proxy = ProxyConfig::merge_with_env(&ProxyConfig::default(), &self.proxy);
+        let client = HttpClient::new(tls, &proxy)?;
+
+        // Tokens are loaded from the environment, from a managed identity, or from the Azure CLI,
+        // in that order.
+        let credential: Arc<dyn TokenCredential> = Arc::new(DefaultAzureCredential::default());
+        let token = credential.get_token(TOKEN_RESOURCE).await?;
+        let authorization = format!("Bearer {}", token.token.secret());
+
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            let secret = get_secret(&client, &self.vault_url, &authorization, &k).await?;
+            secrets.insert(k, secret);
+        }
+        Ok(secrets)
+    }
+}
+
+/// Gets the value of the secret that a secret key refers to.
+async fn get_secret(
+    client: &HttpClient,
+    vault_url: &str,
+    authorization: &str,
+    k: &str,
+) -> crate::Result<String> {
+    let vault_url = vault_url.trim_end_matches('/');
+    let name = secret_name(k).ok_or_else(|| {
+        format!(
+            "key '{}' can't be mapped to a Key Vault secret name, which may only contain alphanumeric characters and dashes",
+            k
+        )
+    })?;
+
+    let request = Request::get(format!(
+        "{}/secrets/{}?api-version={}",
+        vault_url, name, API_VERSION
+    ))
+    .header(header::AUTHORIZATION, authorization)
+    .body(Body::empty())?;
+    let response = client.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+
+    match status {
+        StatusCode::OK => {}
+        StatusCode::NOT_FOUND => {
+            return Err(format!(
+                "secret '{}' for key '{}' does not exist in key vault '{}'",
+                name, k, vault_url
+            )
+            .into())
+        }
+        status => {
+            return Err(format!(
+                "secret '{}' for key '{}' could not be retrieved from key vault '{}', server returned {}: {}",
+                name,
+                k,
+                vault_url,
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into())
+        }
+    }
+
+    match serde_json::from_slice::<SecretBundle>(&body)?.value {
+        Some(secret) if !secret.is_empty() => Ok(secret),
+        _ => Err(format!(
+            "value of secret '{}' for key '{}' in key vault '{}' was empty",
+            name, k, vault_url
+        )
+        .into()),
+    }
+}
+
+/// Maps a secret key to the name of a Key Vault secret.
+///
+/// Secret names may only contain alphanumeric characters and dashes, while secret keys are made
+/// of word characters and dots, so underscores are replaced with dashes and keys with dots are
+/// rejected. `SECRET[vault.connection_string]` thus refers to the `connection-string` secret.
+fn secret_name(key: &str) -> Option<String> {
+    let name = key.replace('_', "-");
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        .then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::Response;
+
+    use super::*;
+    use crate::test_util::http::spawn_blackhole_http_server;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureKeyVaultBackend>();
+    }
+
+    #[test]
+    fn secret_names() {
+        assert_eq!(
+            secret_name("connection_string"),
+            Some("connection-string".to_string())
+        );
+        assert_eq!(secret_name("Password-2"), Some("Password-2".to_string()));
+        assert_eq!(secret_name("database.password"), None);
+    }
+
+    async fn vault(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let authorized = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .is_some_and(|value| value == "Bearer token");
+        let response = match (authorized, request.uri().path_and_query().unwrap().as_str()) {
+            (false, _) => Response::builder().status(StatusCode::UNAUTHORIZED),
+            (true, "/secrets/connection-string?api-version=7.4") => {
+                return Ok(Response::new(Body::from(r#"{"value":"secret"}"#)))
+            }
+            (true, "/secrets/empty?api-version=7.4") => {
+                return Ok(Response::new(Body::from(r#"{"value":""}"#)))
+            }
+            (true, _) => Response::builder().status(StatusCode::NOT_FOUND),
+        };
+        Ok(response.body(Body::empty()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn get_secrets() {
+        let vault_url = spawn_blackhole_http_server(vault).await.to_string();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+
+        assert_eq!(
+            get_secret(&client, &vault_url, "Bearer token", "connection_string")
+                .await
+                .unwrap(),
+            "secret"
+        );
+
+        let error = get_secret(&client, &vault_url, "Bearer token", "missing")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("does not exist"), "{error}");
+
+        let error = get_secret(&client, &vault_url, "Bearer token", "empty")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("was empty"), "{error}");
+
+        let error = get_secret(&client, &vault_url, "Bearer expired", "connection_string")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("401"), "{error}");
+    }
+}
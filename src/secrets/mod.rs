@@ -8,6 +8,8 @@ use crate::{config::SecretBackend, signal};
 
 #[cfg(feature = "secrets-aws-secrets-manager")]
 mod aws_secrets_manager;
+#[cfg(feature = "secrets-azure-key-vault")]
+mod azure_key_vault;
 mod exec;
 mod test;
 
@@ -25,6 +27,10 @@ pub enum SecretBackends {
     #[cfg(feature = "secrets-aws-secrets-manager")]
     AwsSecretsManager(aws_secrets_manager::AwsSecretsManagerBackend),
 
+    /// Azure Key Vault.
+    #[cfg(feature = "secrets-azure-key-vault")]
+    AzureKeyVault(azure_key_vault::AzureKeyVaultBackend),
+
     /// Test.
     #[configurable(metadata(docs::hidden))]
     Test(test::TestBackend),
@@ -37,6 +43,8 @@ impl NamedComponent for SecretBackends {
             Self::Exec(config) => config.get_component_name(),
             #[cfg(feature = "secrets-aws-secrets-manager")]
             Self::AwsSecretsManager(config) => config.get_component_name(),
+            #[cfg(feature = "secrets-azure-key-vault")]
+            Self::AzureKeyVault(config) => config.get_component_name(),
             Self::Test(config) => config.get_component_name(),
         }
     }
@@ -9,11 +9,15 @@ use vector_lib::sensitive_string::SensitiveString;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::sinks::util::service::TowerRequestConfigDefaults;
 use crate::{
+    azure_credentials::ClientCredentials,
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
-            self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
+            self,
+            config::{AzureBlobRetryLogic, BlockUploadConfig},
+            service::AzureBlobService,
+            sink::AzureBlobSink,
         },
         util::{
             partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
@@ -52,7 +56,8 @@ pub struct AzureBlobSinkConfig {
 
     /// The Azure Blob Storage Account name.
     ///
-    /// Attempts to load credentials for the account in the following ways, in order:
+    /// Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+    /// for the account in the following ways, in order:
     ///
     /// - read from environment variables ([more information][env_cred_docs])
     /// - looks for a [Managed Identity][managed_ident_docs]
@@ -66,6 +71,9 @@ pub struct AzureBlobSinkConfig {
     #[configurable(metadata(docs::examples = "mylogstorage"))]
     pub storage_account: Option<String>,
 
+    #[configurable(derived)]
+    pub client_credentials: Option<ClientCredentials>,
+
     /// The Azure Blob Storage Endpoint URL.
     ///
     /// This is used to override the default blob storage endpoint URL in cases where you are using
@@ -137,6 +145,10 @@ pub struct AzureBlobSinkConfig {
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub block_upload: BlockUploadConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub request: TowerRequestConfig<AzureBlobTowerRequestConfigDefaults>,
@@ -159,6 +171,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
         toml::Value::try_from(Self {
             connection_string: Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;").into()),
             storage_account: Some(String::from("some-account-name")),
+            client_credentials: None,
             container_name: String::from("logs"),
             endpoint: None,
             blob_prefix: default_blob_prefix(),
@@ -167,6 +180,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::default()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
+            block_upload: BlockUploadConfig::default(),
             request: TowerRequestConfig::default(),
             acknowledgements: Default::default(),
         })
@@ -183,6 +197,7 @@ impl SinkConfig for AzureBlobSinkConfig {
                 .as_ref()
                 .map(|v| v.inner().to_string()),
             self.storage_account.as_ref().map(|v| v.to_string()),
+            self.client_credentials.as_ref(),
             self.container_name.clone(),
             self.endpoint.clone(),
         )?;
@@ -210,10 +225,12 @@ const DEFAULT_FILENAME_APPEND_UUID: bool = true;
 
 impl AzureBlobSinkConfig {
     pub fn build_processor(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
+        self.block_upload.validate()?;
+
         let request_limits = self.request.into_settings();
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(client, self.block_upload));
 
         // Configure our partitioning/batching.
         let batcher_settings = self.batch.into_batcher_settings()?;
@@ -19,7 +19,7 @@ use super::config::AzureBlobSinkConfig;
 use crate::{
     event::{Event, EventArray, LogEvent},
     sinks::{
-        azure_common,
+        azure_common::{self, config::BlockUploadConfig},
         util::{Compression, TowerRequestConfig},
         VectorSink,
     },
@@ -35,6 +35,7 @@ async fn azure_blob_healthcheck_passed() {
     let client = azure_common::config::build_client(
         config.connection_string.map(Into::into),
         None,
+        None,
         config.container_name.clone(),
         None,
     )
@@ -56,6 +57,7 @@ async fn azure_blob_healthcheck_unknown_container() {
     let client = azure_common::config::build_client(
         config.connection_string.map(Into::into),
         config.storage_account.map(Into::into),
+        config.client_credentials.as_ref(),
         config.container_name.clone(),
         config.endpoint.clone(),
     )
@@ -91,6 +93,29 @@ async fn azure_blob_insert_lines_into_blob() {
     assert_eq!(lines, blob_lines);
 }
 
+#[tokio::test]
+async fn azure_blob_insert_lines_into_blob_in_blocks() {
+    let blob_prefix = format!("lines/into/blocks/{}", random_string(10));
+    let config = AzureBlobSinkConfig::new_emulator().await;
+    let config = AzureBlobSinkConfig {
+        blob_prefix: blob_prefix.clone().try_into().unwrap(),
+        block_upload: BlockUploadConfig {
+            block_size: 256,
+            max_concurrent_blocks: 2,
+        },
+        ..config
+    };
+    let (lines, input) = random_lines_with_stream(100, 10, None);
+
+    config.run_assert(input).await;
+
+    let blobs = config.list_blobs(blob_prefix).await;
+    assert_eq!(blobs.len(), 1);
+    let (blob, blob_lines) = config.get_blob(blobs[0].clone()).await;
+    assert_eq!(blob.properties.content_type, String::from("text/plain"));
+    assert_eq!(lines, blob_lines);
+}
+
 #[tokio::test]
 async fn azure_blob_insert_json_into_blob() {
     let blob_prefix = format!("json/into/blob/{}", random_string(10));
@@ -225,6 +250,7 @@ impl AzureBlobSinkConfig {
         let config = AzureBlobSinkConfig {
                 connection_string: Some(format!("UseDevelopmentStorage=true;DefaultEndpointsProtocol=http;AccountName=devstoreaccount1;AccountKey=Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==;BlobEndpoint=http://{}:10000/devstoreaccount1;QueueEndpoint=http://{}:10001/devstoreaccount1;TableEndpoint=http://{}:10002/devstoreaccount1;", address, address, address).into()),
                 storage_account: None,
+                client_credentials: None,
                 container_name: "logs".to_string(),
                 endpoint: None,
                 blob_prefix: Default::default(),
@@ -233,6 +259,7 @@ impl AzureBlobSinkConfig {
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
                 compression: Compression::None,
                 batch: Default::default(),
+                block_upload: Default::default(),
                 request: TowerRequestConfig::default(),
                 acknowledgements: Default::default(),
             };
@@ -246,6 +273,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            self.client_credentials.as_ref(),
             self.container_name.clone(),
             self.endpoint.clone(),
         )
@@ -265,6 +293,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            self.client_credentials.as_ref(),
             self.container_name.clone(),
             self.endpoint.clone(),
         )
@@ -294,6 +323,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            self.client_credentials.as_ref(),
             self.container_name.clone(),
             self.endpoint.clone(),
         )
@@ -331,6 +361,7 @@ impl AzureBlobSinkConfig {
         let client = azure_common::config::build_client(
             self.connection_string.clone().map(Into::into),
             self.storage_account.clone().map(Into::into),
+            self.client_credentials.as_ref(),
             self.container_name.clone(),
             self.endpoint.clone(),
         )
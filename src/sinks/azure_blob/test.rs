@@ -11,13 +11,17 @@ use super::config::AzureBlobSinkConfig;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
-use crate::sinks::util::{request_builder::RequestBuilder, Compression};
+use crate::sinks::{
+    azure_common::{config::BlockUploadConfig, service::split_blocks},
+    util::{request_builder::RequestBuilder, Compression},
+};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
 fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
     AzureBlobSinkConfig {
         connection_string: Default::default(),
         storage_account: Default::default(),
+        client_credentials: Default::default(),
         container_name: Default::default(),
         endpoint: Default::default(),
         blob_prefix: Default::default(),
@@ -26,6 +30,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         encoding,
         compression: Compression::gzip_default(),
         batch: Default::default(),
+        block_upload: Default::default(),
         request: Default::default(),
         acknowledgements: Default::default(),
     }
@@ -230,3 +235,42 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_split_blocks() {
+    let data = Bytes::from_static(b"0123456789");
+
+    let blocks = split_blocks(&data, 4);
+
+    assert_eq!(
+        blocks
+            .iter()
+            .map(|(_, block)| block.as_ref())
+            .collect::<Vec<_>>(),
+        vec![&b"0123"[..], &b"4567"[..], &b"89"[..]]
+    );
+    assert_eq!(
+        blocks
+            .iter()
+            .map(|(id, _)| id.bytes().as_ref())
+            .collect::<Vec<_>>(),
+        vec![&b"00000"[..], &b"00001"[..], &b"00002"[..]]
+    );
+}
+
+#[test]
+fn azure_blob_block_upload_validation() {
+    assert!(BlockUploadConfig::default().validate().is_ok());
+    assert!(BlockUploadConfig {
+        block_size: 0,
+        ..Default::default()
+    }
+    .validate()
+    .is_err());
+    assert!(BlockUploadConfig {
+        max_concurrent_blocks: 0,
+        ..Default::default()
+    }
+    .validate()
+    .is_err());
+}
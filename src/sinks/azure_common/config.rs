@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
 use azure_core::{error::HttpError, RetryOptions};
-use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
 use azure_storage::{prelude::*, CloudLocation, ConnectionString};
-use azure_storage_blobs::{blob::operations::PutBlockBlobResponse, prelude::*};
+use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
 use snafu::Snafu;
+use vector_lib::configurable::configurable_component;
 use vector_lib::stream::DriverResponse;
 use vector_lib::{
     json_size::JsonSize,
@@ -15,6 +15,7 @@ use vector_lib::{
 };
 
 use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
     event::{EventFinalizers, EventStatus, Finalizable},
     sinks::{util::retries::RetryLogic, Healthcheck},
 };
@@ -67,7 +68,6 @@ impl RetryLogic for AzureBlobRetryLogic {
 
 #[derive(Debug)]
 pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
     pub events_byte_size: GroupedCountByteSize,
     pub byte_size: usize,
 }
@@ -86,6 +86,65 @@ impl DriverResponse for AzureBlobResponse {
     }
 }
 
+/// The largest block that can be staged, in bytes.
+const MAX_BLOCK_SIZE: usize = 4000 * 1024 * 1024;
+
+/// Staged block upload options.
+///
+/// Blobs larger than `block_size` are uploaded as [staged blocks][put_block], which are committed
+/// with a [block list][put_block_list] once all of them are uploaded. A block that fails to upload
+/// is retried on its own, so large blobs don't have to be sent again in full.
+///
+/// [put_block]: https://learn.microsoft.com/en-us/rest/api/storageservices/put-block
+/// [put_block_list]: https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-list
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BlockUploadConfig {
+    /// The size of the blocks that blobs are staged in.
+    ///
+    /// Blobs up to this size are uploaded with a single request. Blocks can be at most 4000 MiB.
+    #[serde(default = "default_block_size")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub block_size: usize,
+
+    /// The maximum number of blocks of a blob that are uploaded concurrently.
+    #[serde(default = "default_max_concurrent_blocks")]
+    pub max_concurrent_blocks: usize,
+}
+
+const fn default_block_size() -> usize {
+    4 * 1024 * 1024
+}
+
+const fn default_max_concurrent_blocks() -> usize {
+    4
+}
+
+impl Default for BlockUploadConfig {
+    fn default() -> Self {
+        Self {
+            block_size: default_block_size(),
+            max_concurrent_blocks: default_max_concurrent_blocks(),
+        }
+    }
+}
+
+impl BlockUploadConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.block_size == 0 || self.block_size > MAX_BLOCK_SIZE {
+            return Err(format!(
+                "`block_upload.block_size` must be between 1 and {MAX_BLOCK_SIZE} bytes"
+            )
+            .into());
+        }
+        if self.max_concurrent_blocks == 0 {
+            return Err("`block_upload.max_concurrent_blocks` must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum HealthcheckError {
     #[snafu(display("Invalid connection string specified"))]
@@ -126,12 +185,16 @@ pub fn build_healthcheck(
 pub fn build_client(
     connection_string: Option<String>,
     storage_account: Option<String>,
+    client_credentials: Option<&ClientCredentials>,
     container_name: String,
     endpoint: Option<String>,
 ) -> crate::Result<Arc<ContainerClient>> {
     let client;
     match (connection_string, storage_account) {
         (Some(connection_string_p), None) => {
+            if client_credentials.is_some() {
+                return Err("`client_credentials` can only be used with `storage_account`".into());
+            }
             let connection_string = ConnectionString::new(&connection_string_p)?;
 
             client = match connection_string.blob_endpoint {
@@ -160,9 +223,8 @@ pub fn build_client(
             .container_client(container_name);
         }
         (None, Some(storage_account_p)) => {
-            let creds = std::sync::Arc::new(DefaultAzureCredential::default());
-            let auto_creds = std::sync::Arc::new(AutoRefreshingTokenCredential::new(creds));
-            let storage_credentials = StorageCredentials::token_credential(auto_creds);
+            let storage_credentials =
+                StorageCredentials::token_credential(token_credential(client_credentials)?);
 
             client = match endpoint {
                 // If a blob_endpoint is provided in the configuration, use it with a Custom
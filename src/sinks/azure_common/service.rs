@@ -2,23 +2,43 @@ use std::{
     result::Result as StdResult,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
-use azure_storage_blobs::prelude::*;
-use futures::future::BoxFuture;
+use azure_core::error::ErrorKind;
+use azure_storage_blobs::{blob::BlobBlockType, prelude::*};
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream, StreamExt, TryStreamExt};
+use http::StatusCode;
 use tower::Service;
 use tracing::Instrument;
 
-use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse};
+use crate::sinks::{
+    azure_common::config::{AzureBlobRequest, AzureBlobResponse, BlockUploadConfig},
+    util::retries::ExponentialBackoff,
+};
+
+/// The most blocks that a blob can be committed with.
+const MAX_BLOCKS: usize = 50_000;
+
+/// The number of times that a block is retried before the upload of the blob fails.
+const MAX_BLOCK_RETRIES: usize = 5;
 
 #[derive(Clone)]
 pub struct AzureBlobService {
     client: Arc<ContainerClient>,
+    block_upload: BlockUploadConfig,
 }
 
 impl AzureBlobService {
-    pub const fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+    pub const fn new(
+        client: Arc<ContainerClient>,
+        block_upload: BlockUploadConfig,
+    ) -> AzureBlobService {
+        AzureBlobService {
+            client,
+            block_upload,
+        }
     }
 }
 
@@ -41,22 +61,34 @@ impl Service<AzureBlobRequest> for AzureBlobService {
                 .client
                 .blob_client(request.metadata.partition_key.as_str());
             let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
-            };
 
-            let result = blob
-                .into_future()
+            let result = if byte_size <= this.block_upload.block_size {
+                let blob = client
+                    .put_block_blob(request.blob_data)
+                    .content_type(request.content_type);
+                let blob = match request.content_encoding {
+                    Some(encoding) => blob.content_encoding(encoding),
+                    None => blob,
+                };
+
+                blob.into_future()
+                    .instrument(info_span!("request").or_current())
+                    .await
+                    .map(|_| ())
+                    .map_err(Into::into)
+            } else {
+                put_blocks(
+                    &client,
+                    this.block_upload,
+                    request.blob_data,
+                    request.content_type,
+                    request.content_encoding,
+                )
                 .instrument(info_span!("request").or_current())
                 .await
-                .map_err(|err| err.into());
+            };
 
-            result.map(|inner| AzureBlobResponse {
-                inner,
+            result.map(|()| AzureBlobResponse {
                 events_byte_size: request
                     .request_metadata
                     .into_events_estimated_json_encoded_byte_size(),
@@ -65,3 +97,100 @@ impl Service<AzureBlobRequest> for AzureBlobService {
         })
     }
 }
+
+/// Uploads a blob as staged blocks, and commits them once all of them are uploaded.
+async fn put_blocks(
+    client: &BlobClient,
+    block_upload: BlockUploadConfig,
+    data: Bytes,
+    content_type: &'static str,
+    content_encoding: Option<&'static str>,
+) -> StdResult<(), crate::Error> {
+    let blocks = split_blocks(&data, block_upload.block_size);
+    if blocks.len() > MAX_BLOCKS {
+        return Err(format!(
+            "blob of {} bytes needs {} blocks of {} bytes, but can have at most {MAX_BLOCKS}",
+            data.len(),
+            blocks.len(),
+            block_upload.block_size,
+        )
+        .into());
+    }
+
+    let block_list = BlockList {
+        blocks: blocks
+            .iter()
+            .map(|(id, _)| BlobBlockType::Uncommitted(id.clone()))
+            .collect(),
+    };
+
+    stream::iter(blocks)
+        .map(|(id, block)| put_block(client, id, block))
+        .buffer_unordered(block_upload.max_concurrent_blocks)
+        .try_collect::<()>()
+        .await?;
+
+    let blob = client.put_block_list(block_list).content_type(content_type);
+    let blob = match content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+    blob.into_future().await?;
+
+    Ok(())
+}
+
+/// Stages a single block, retrying it when it fails with a transient error.
+async fn put_block(client: &BlobClient, id: BlockId, block: Bytes) -> StdResult<(), crate::Error> {
+    let mut backoff = ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(30));
+
+    let mut retries = 0;
+    loop {
+        match client
+            .put_block(id.clone(), block.clone())
+            .into_future()
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(error) if retries < MAX_BLOCK_RETRIES && is_retriable_block_error(&error) => {
+                retries += 1;
+                warn!(
+                    message = "Retrying block upload.",
+                    %error,
+                    retries,
+                    internal_log_rate_limit = true,
+                );
+                tokio::time::sleep(backoff.next().unwrap()).await;
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+fn is_retriable_block_error(error: &azure_core::Error) -> bool {
+    match error.kind() {
+        ErrorKind::HttpResponse { status, .. } => {
+            let status: u16 = (*status).into();
+            status >= 500 || status == StatusCode::TOO_MANY_REQUESTS.as_u16()
+        }
+        ErrorKind::Io => true,
+        _ => false,
+    }
+}
+
+/// Splits a blob into blocks of at most `block_size` bytes, with the IDs that they are staged as.
+///
+/// The IDs of the blocks of a blob must all have the same length, so they are zero-padded block
+/// indexes.
+pub fn split_blocks(data: &Bytes, block_size: usize) -> Vec<(BlockId, Bytes)> {
+    (0..data.len())
+        .step_by(block_size)
+        .enumerate()
+        .map(|(index, start)| {
+            let end = data.len().min(start + block_size);
+            (BlockId::new(format!("{index:05}")), data.slice(start..end))
+        })
+        .collect()
+}
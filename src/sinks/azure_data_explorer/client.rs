@@ -0,0 +1,367 @@
+//! A minimal client for [queued ingestion][queued] into Azure Data Explorer.
+//!
+//! Queued ingestion is a three step process: the data is uploaded as a blob to one of the
+//! temporary storage containers of the cluster, and a message pointing at that blob is posted to
+//! one of its ingestion queues. The containers, queues and the authorization context to put in
+//! the message are discovered with management commands, and are cached for an hour.
+//!
+//! [queued]: https://learn.microsoft.com/en-us/azure/data-explorer/ingest-data-overview#queued-ingestion
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{header, Request, StatusCode};
+use hyper::Body;
+use openssl::base64;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::http::{HttpClient, HttpError};
+
+/// How long the discovered ingestion resources are used before being refreshed.
+const RESOURCES_TTL: Duration = Duration::from_secs(3600);
+
+/// The format of the uploaded blobs, newline delimited JSON objects.
+const FORMAT: &str = "multijson";
+
+#[derive(Debug, Snafu)]
+pub(super) enum KustoError {
+    #[snafu(display("Failed to get an access token: {}", source))]
+    Token { source: azure_core::Error },
+    #[snafu(display("Failed to {}: {}", operation, source))]
+    Request {
+        operation: &'static str,
+        source: HttpError,
+    },
+    #[snafu(display("Failed to {}, server returned {}: {}", operation, status, body))]
+    UnexpectedStatus {
+        operation: &'static str,
+        status: StatusCode,
+        body: String,
+    },
+    #[snafu(display("Failed to read the response to {}: {}", operation, source))]
+    ReadResponse {
+        operation: &'static str,
+        source: hyper::Error,
+    },
+    #[snafu(display("Invalid response to {}: {}", command, source))]
+    InvalidResponse {
+        command: &'static str,
+        source: serde_json::Error,
+    },
+    #[snafu(display("The cluster did not return any `{}` resource", resource))]
+    MissingResource { resource: &'static str },
+}
+
+impl KustoError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            KustoError::Token { .. } | KustoError::ReadResponse { .. } => true,
+            KustoError::Request { source, .. } => source.is_retriable(),
+            KustoError::UnexpectedStatus { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            KustoError::InvalidResponse { .. } | KustoError::MissingResource { .. } => false,
+        }
+    }
+}
+
+/// The response to a management command, in the v1 response format.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CommandResponse {
+    tables: Vec<CommandTable>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CommandTable {
+    rows: Vec<Vec<String>>,
+}
+
+/// The resources that data is ingested through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct IngestionResources {
+    /// SAS URIs of the temporary storage containers.
+    pub(super) containers: Vec<String>,
+    /// SAS URIs of the ingestion queues.
+    pub(super) queues: Vec<String>,
+    /// The token that lets the cluster read the uploaded blobs on our behalf.
+    pub(super) authorization_context: String,
+}
+
+impl IngestionResources {
+    pub(super) fn parse(resources: &[u8], identity_token: &[u8]) -> Result<Self, KustoError> {
+        let resources: CommandResponse =
+            serde_json::from_slice(resources).context(InvalidResponseSnafu {
+                command: ".get ingestion resources",
+            })?;
+        let identity_token: CommandResponse =
+            serde_json::from_slice(identity_token).context(InvalidResponseSnafu {
+                command: ".get kusto identity token",
+            })?;
+
+        let rows = resources
+            .tables
+            .into_iter()
+            .next()
+            .map(|table| table.rows)
+            .unwrap_or_default();
+        let storage_roots = |resource_type: &str| -> Vec<String> {
+            rows.iter()
+                .filter(|row| row.first().map(String::as_str) == Some(resource_type))
+                .filter_map(|row| row.get(1).cloned())
+                .collect()
+        };
+
+        let containers = storage_roots("TempStorage");
+        if containers.is_empty() {
+            return Err(KustoError::MissingResource {
+                resource: "TempStorage",
+            });
+        }
+        let queues = storage_roots("SecuredReadyForAggregationQueue");
+        if queues.is_empty() {
+            return Err(KustoError::MissingResource {
+                resource: "SecuredReadyForAggregationQueue",
+            });
+        }
+        let authorization_context = identity_token
+            .tables
+            .into_iter()
+            .next()
+            .and_then(|table| table.rows.into_iter().next())
+            .and_then(|row| row.into_iter().next())
+            .ok_or(KustoError::MissingResource {
+                resource: "AuthorizationContext",
+            })?;
+
+        Ok(Self {
+            containers,
+            queues,
+            authorization_context,
+        })
+    }
+}
+
+/// The message posted to an ingestion queue to ingest a blob.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct IngestionMessage<'a> {
+    id: Uuid,
+    blob_path: &'a str,
+    raw_data_size: usize,
+    database_name: &'a str,
+    table_name: &'a str,
+    retain_blob_on_success: bool,
+    flush_immediately: bool,
+    /// Report ingestion failures only.
+    report_level: u8,
+    /// Report to the failure queue.
+    report_method: u8,
+    additional_properties: serde_json::Value,
+}
+
+/// Inserts `name` into the path of a SAS URI, before its query string.
+pub(super) fn sas_uri_with_path(sas_uri: &str, name: &str) -> String {
+    match sas_uri.split_once('?') {
+        Some((base, sas)) => format!("{}/{}?{}", base.trim_end_matches('/'), name, sas),
+        None => format!("{}/{}", sas_uri.trim_end_matches('/'), name),
+    }
+}
+
+pub(super) struct KustoIngestClient {
+    client: HttpClient,
+    credential: Arc<dyn TokenCredential>,
+    ingestion_endpoint: String,
+    token_resource: String,
+    database: String,
+    table: String,
+    mapping_reference: Option<String>,
+    resources: Mutex<Option<(IngestionResources, Instant)>>,
+    next_resource: AtomicUsize,
+}
+
+impl KustoIngestClient {
+    pub(super) fn new(
+        client: HttpClient,
+        credential: Arc<dyn TokenCredential>,
+        ingestion_endpoint: &str,
+        database: String,
+        table: String,
+        mapping_reference: Option<String>,
+    ) -> Self {
+        let ingestion_endpoint = ingestion_endpoint.trim_end_matches('/').to_string();
+        // Tokens are issued for the engine endpoint of the cluster, which is also accepted by its
+        // data management endpoint.
+        let token_resource = ingestion_endpoint.replacen("://ingest-", "://", 1);
+
+        Self {
+            client,
+            credential,
+            ingestion_endpoint,
+            token_resource,
+            database,
+            table,
+            mapping_reference,
+            resources: Mutex::new(None),
+            next_resource: AtomicUsize::new(0),
+        }
+    }
+
+    /// Runs a management command against the data management endpoint of the cluster.
+    async fn command(&self, command: &'static str) -> Result<Bytes, KustoError> {
+        let token = self
+            .credential
+            .get_token(&self.token_resource)
+            .await
+            .context(TokenSnafu)?;
+        let body = json!({ "db": self.database, "csl": command }).to_string();
+        let request = Request::post(format!("{}/v1/rest/mgmt", self.ingestion_endpoint))
+            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token.token.secret()),
+            )
+            .body(Body::from(body))
+            .context(crate::http::BuildRequestSnafu)
+            .context(RequestSnafu { operation: command })?;
+
+        self.send(command, request).await
+    }
+
+    async fn send(
+        &self,
+        operation: &'static str,
+        request: Request<Body>,
+    ) -> Result<Bytes, KustoError> {
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context(RequestSnafu { operation })?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu { operation })?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(KustoError::UnexpectedStatus {
+                operation,
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            })
+        }
+    }
+
+    /// Returns the ingestion resources, discovering them again once they are too old.
+    pub(super) async fn resources(&self) -> Result<IngestionResources, KustoError> {
+        let mut cached = self.resources.lock().await;
+        if let Some((resources, fetched_at)) = cached.as_ref() {
+            if fetched_at.elapsed() < RESOURCES_TTL {
+                return Ok(resources.clone());
+            }
+        }
+
+        let resources = IngestionResources::parse(
+            &self.command(".get ingestion resources").await?,
+            &self.command(".get kusto identity token").await?,
+        )?;
+        *cached = Some((resources.clone(), Instant::now()));
+        Ok(resources)
+    }
+
+    /// Queues a gzipped blob of newline delimited JSON objects for ingestion.
+    ///
+    /// `raw_data_size` is the uncompressed size of the data, which the cluster uses to plan
+    /// ingestion.
+    pub(super) async fn ingest(&self, data: Bytes, raw_data_size: usize) -> Result<(), KustoError> {
+        let resources = self.resources().await?;
+        // Spread the load across every container and queue of the cluster.
+        let index = self.next_resource.fetch_add(1, Ordering::Relaxed);
+
+        let blob_name = format!(
+            "{}__{}__{}.{}.gz",
+            self.database,
+            self.table,
+            Uuid::new_v4(),
+            FORMAT
+        );
+        let blob_uri = sas_uri_with_path(
+            &resources.containers[index % resources.containers.len()],
+            &blob_name,
+        );
+        let request = Request::put(&blob_uri)
+            .header("x-ms-blob-type", "BlockBlob")
+            .header(header::CONTENT_LENGTH, data.len())
+            .body(Body::from(data))
+            .context(crate::http::BuildRequestSnafu)
+            .context(RequestSnafu {
+                operation: "upload blob",
+            })?;
+        self.send("upload blob", request).await?;
+
+        let message = self.ingestion_message(&blob_uri, raw_data_size, &resources);
+        let message = format!(
+            "<QueueMessage><MessageText>{}</MessageText></QueueMessage>",
+            base64::encode_block(
+                &serde_json::to_vec(&message).expect("serializing a message cannot fail")
+            )
+        );
+        let queue_uri = sas_uri_with_path(
+            &resources.queues[index % resources.queues.len()],
+            "messages",
+        );
+        let request = Request::post(queue_uri)
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(Body::from(message))
+            .context(crate::http::BuildRequestSnafu)
+            .context(RequestSnafu {
+                operation: "post ingestion message",
+            })?;
+        self.send("post ingestion message", request).await?;
+
+        Ok(())
+    }
+
+    pub(super) fn ingestion_message<'a>(
+        &'a self,
+        blob_uri: &'a str,
+        raw_data_size: usize,
+        resources: &IngestionResources,
+    ) -> IngestionMessage<'a> {
+        let mut additional_properties = json!({
+            "authorizationContext": resources.authorization_context,
+            "format": FORMAT,
+        });
+        if let Some(mapping_reference) = &self.mapping_reference {
+            additional_properties["ingestionMappingReference"] = json!(mapping_reference);
+            additional_properties["ingestionMappingType"] = json!("Json");
+        }
+
+        IngestionMessage {
+            id: Uuid::new_v4(),
+            blob_path: blob_uri,
+            raw_data_size,
+            database_name: &self.database,
+            table_name: &self.table,
+            retain_blob_on_success: false,
+            flush_immediately: false,
+            report_level: 0,
+            report_method: 0,
+            additional_properties,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Configuration for the `azure_data_explorer` sink.
+
+use std::sync::Arc;
+
+use vector_lib::codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
+    http::HttpClient,
+    sinks::prelude::*,
+};
+
+use super::{
+    client::KustoIngestClient,
+    request_builder::KustoRequestBuilder,
+    service::{KustoRetryLogic, KustoService},
+    sink::KustoSink,
+};
+
+/// Configuration for the `azure_data_explorer` sink.
+#[configurable_component(sink(
+    "azure_data_explorer",
+    "Ingest log events into Azure Data Explorer tables."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataExplorerConfig {
+    /// The data ingestion URI of the cluster.
+    ///
+    /// This is the URI of the cluster prefixed with `ingest-`, and is shown in the cluster's
+    /// overview page of the Azure portal.
+    #[configurable(metadata(
+        docs::examples = "https://ingest-mycluster.westeurope.kusto.windows.net"
+    ))]
+    #[configurable(validation(format = "uri"))]
+    pub(super) ingestion_endpoint: String,
+
+    /// The database that contains the table that data is ingested into.
+    #[configurable(metadata(docs::examples = "mydatabase"))]
+    pub(super) database: String,
+
+    /// The table that data is ingested into.
+    #[configurable(metadata(docs::examples = "mytable"))]
+    pub(super) table: String,
+
+    /// The name of the JSON [ingestion mapping][mapping] of the table to ingest data with.
+    ///
+    /// If unset, the fields of each event are matched to the columns of the table by name.
+    ///
+    /// [mapping]: https://learn.microsoft.com/en-us/azure/data-explorer/kusto/management/mappings
+    #[configurable(metadata(docs::examples = "vector_mapping"))]
+    pub(super) mapping_reference: Option<String>,
+
+    #[configurable(derived)]
+    pub(super) client_credentials: Option<ClientCredentials>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub(super) encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) batch: BatchConfig<AzureDataExplorerDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct AzureDataExplorerDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureDataExplorerDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 30.0;
+}
+
+impl GenerateConfig for AzureDataExplorerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"ingestion_endpoint = "https://ingest-mycluster.westeurope.kusto.windows.net"
+            database = "mydatabase"
+            table = "mytable""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_data_explorer")]
+impl SinkConfig for AzureDataExplorerConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self.batch.validate()?.into_batcher_settings()?;
+
+        let credential = token_credential(self.client_credentials.as_ref())?;
+        let client = Arc::new(KustoIngestClient::new(
+            HttpClient::new(None, cx.proxy())?,
+            credential,
+            &self.ingestion_endpoint,
+            self.database.clone(),
+            self.table.clone(),
+            self.mapping_reference.clone(),
+        ));
+
+        let healthcheck = healthcheck(Arc::clone(&client)).boxed();
+
+        let request_builder = KustoRequestBuilder {
+            encoder: (
+                self.encoding.clone(),
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoderConfig.build().into(),
+                    JsonSerializerConfig::default().build().into(),
+                ),
+            ),
+        };
+
+        let request_settings = self.request.into_settings();
+        let service = ServiceBuilder::new()
+            .settings(request_settings, KustoRetryLogic)
+            .service(KustoService::new(client));
+
+        let sink = KustoSink::new(batch_settings, request_builder, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the cluster can be reached and that the credentials are allowed to ingest into the
+/// database by discovering its ingestion resources.
+async fn healthcheck(client: Arc<KustoIngestClient>) -> crate::Result<()> {
+    client.resources().await?;
+    Ok(())
+}
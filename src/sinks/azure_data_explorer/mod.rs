@@ -0,0 +1,16 @@
+//! The Azure Data Explorer [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s and ingesting them into an Azure Data
+//! Explorer (Kusto) table through queued ingestion.
+
+mod client;
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AzureDataExplorerConfig;
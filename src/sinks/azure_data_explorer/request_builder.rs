@@ -0,0 +1,54 @@
+//! `RequestBuilder` implementation for the `azure_data_explorer` sink.
+
+use std::io;
+
+use bytes::Bytes;
+use vector_lib::codecs::encoding::Framer;
+
+use crate::sinks::prelude::*;
+
+use super::service::KustoRequest;
+
+pub(super) struct KustoRequestBuilder {
+    pub(super) encoder: (Transformer, Encoder<Framer>),
+}
+
+impl RequestBuilder<Vec<Event>> for KustoRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = KustoRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        // Blobs are always gzipped, which is one of the two compressions the cluster can read.
+        Compression::gzip_default()
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut events: Vec<Event>,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (finalizers, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        KustoRequest {
+            data: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Service implementation for the `azure_data_explorer` sink.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use tracing::Instrument;
+
+use crate::sinks::prelude::*;
+
+use super::client::{KustoError, KustoIngestClient};
+
+#[derive(Clone)]
+pub(super) struct KustoRetryLogic;
+
+impl RetryLogic for KustoRetryLogic {
+    type Error = KustoError;
+    type Response = KustoResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct KustoRequest {
+    pub(super) data: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for KustoRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for KustoRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct KustoResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for KustoResponse {
+    fn event_status(&self) -> EventStatus {
+        // Ingestion itself happens asynchronously on the cluster, so events are delivered once
+        // they are queued for ingestion.
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_wire_size())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct KustoService {
+    client: Arc<KustoIngestClient>,
+}
+
+impl KustoService {
+    pub(super) const fn new(client: Arc<KustoIngestClient>) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<KustoRequest> for KustoService {
+    type Response = KustoResponse;
+    type Error = KustoError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: KustoRequest) -> Self::Future {
+        let client = Arc::clone(&self.client);
+
+        Box::pin(
+            async move {
+                let raw_data_size = request.metadata.request_encoded_size();
+                client.ingest(request.data, raw_data_size).await?;
+                Ok(KustoResponse {
+                    metadata: request.metadata,
+                })
+            }
+            .in_current_span(),
+        )
+    }
+}
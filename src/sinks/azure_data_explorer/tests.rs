@@ -0,0 +1,54 @@
+//! Unit tests for the `azure_data_explorer` sink.
+
+use super::client::{sas_uri_with_path, IngestionResources, KustoError};
+use super::config::AzureDataExplorerConfig;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureDataExplorerConfig>();
+}
+
+#[test]
+fn parse_ingestion_resources() {
+    let resources = br#"{"Tables":[{"TableName":"Table_0","Columns":[{"ColumnName":"ResourceTypeName","DataType":"String"},{"ColumnName":"StorageRoot","DataType":"String"}],"Rows":[["SecuredReadyForAggregationQueue","https://account.queue.core.windows.net/readyforaggregation?sv=1&sig=a"],["TempStorage","https://account.blob.core.windows.net/tempstorage?sv=1&sig=b"],["FailedIngestionsQueue","https://account.queue.core.windows.net/failedingestions?sv=1&sig=c"]]}]}"#;
+    let identity_token = br#"{"Tables":[{"TableName":"Table_0","Columns":[{"ColumnName":"AuthorizationContext","DataType":"String"}],"Rows":[["token"]]}]}"#;
+
+    assert_eq!(
+        IngestionResources::parse(resources, identity_token).unwrap(),
+        IngestionResources {
+            containers: vec!["https://account.blob.core.windows.net/tempstorage?sv=1&sig=b".into()],
+            queues: vec![
+                "https://account.queue.core.windows.net/readyforaggregation?sv=1&sig=a".into()
+            ],
+            authorization_context: "token".into(),
+        }
+    );
+}
+
+#[test]
+fn parse_ingestion_resources_without_queue() {
+    let resources = br#"{"Tables":[{"Rows":[["TempStorage","https://account.blob.core.windows.net/tempstorage?sv=1"]]}]}"#;
+    let identity_token = br#"{"Tables":[{"Rows":[["token"]]}]}"#;
+
+    assert!(matches!(
+        IngestionResources::parse(resources, identity_token),
+        Err(KustoError::MissingResource {
+            resource: "SecuredReadyForAggregationQueue"
+        })
+    ));
+}
+
+#[test]
+fn sas_uri_paths() {
+    assert_eq!(
+        sas_uri_with_path(
+            "https://account.blob.core.windows.net/tempstorage?sv=1&sig=b",
+            "data.multijson.gz"
+        ),
+        "https://account.blob.core.windows.net/tempstorage/data.multijson.gz?sv=1&sig=b"
+    );
+    assert_eq!(
+        sas_uri_with_path("https://account.queue.core.windows.net/queue/", "messages"),
+        "https://account.queue.core.windows.net/queue/messages"
+    );
+}
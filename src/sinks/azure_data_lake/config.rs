@@ -0,0 +1,319 @@
+//! Configuration for the `azure_data_lake` sink.
+
+use std::sync::Arc;
+
+use azure_core::{error::HttpError, RetryOptions};
+use azure_storage::{prelude::*, CloudLocation, ConnectionString};
+use azure_storage_datalake::{clients::DataLakeClientBuilder, prelude::*};
+use http::StatusCode;
+use snafu::Snafu;
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
+    sinks::{
+        azure_common::sink::AzureBlobSink,
+        prelude::*,
+        util::{
+            partitioner::KeyPartitioner, service::TowerRequestConfigDefaults,
+            BulkSizeBasedDefaultBatchSettings,
+        },
+    },
+};
+
+use super::{
+    encoding::DataLakeEncodingConfig,
+    request_builder::DataLakeRequestOptions,
+    service::{DataLakeRetryLogic, DataLakeService},
+};
+
+const DEFAULT_PATH_PREFIX: &str = "year=%Y/month=%m/day=%d/";
+const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
+const DEFAULT_FILENAME_APPEND_UUID: bool = true;
+
+#[derive(Clone, Copy, Debug)]
+pub struct AzureDataLakeTowerRequestConfigDefaults;
+
+impl TowerRequestConfigDefaults for AzureDataLakeTowerRequestConfigDefaults {
+    const RATE_LIMIT_NUM: u64 = 250;
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum HealthcheckError {
+    #[snafu(display("Invalid connection string or credentials specified"))]
+    InvalidCredentials,
+    #[snafu(display("File system: {:?} not found", file_system))]
+    UnknownFileSystem { file_system: String },
+    #[snafu(display("Unknown status code: {}", status))]
+    Unknown { status: StatusCode },
+}
+
+/// Configuration for the `azure_data_lake` sink.
+#[configurable_component(sink(
+    "azure_data_lake",
+    "Store your observability data in Azure Data Lake Storage Gen2."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataLakeConfig {
+    /// The Azure Storage Account connection string.
+    ///
+    /// Authentication with access key is the only supported authentication method.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    #[configurable(metadata(
+        docs::examples = "DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"
+    ))]
+    pub(super) connection_string: Option<SensitiveString>,
+
+    /// The Azure Storage Account name.
+    ///
+    /// The account must have a [hierarchical namespace][hns] enabled.
+    ///
+    /// Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+    /// for the account in the following ways, in order:
+    ///
+    /// - read from environment variables ([more information][env_cred_docs])
+    /// - looks for a [Managed Identity][managed_ident_docs]
+    /// - uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    ///
+    /// [hns]: https://learn.microsoft.com/en-us/azure/storage/blobs/data-lake-storage-namespace
+    /// [env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    /// [az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+    #[configurable(metadata(docs::examples = "mylogstorage"))]
+    pub(super) storage_account: Option<String>,
+
+    #[configurable(derived)]
+    pub(super) client_credentials: Option<ClientCredentials>,
+
+    /// The Data Lake Storage (DFS) endpoint to use instead of the one derived from the account name.
+    ///
+    /// This may only be used with `storage_account` and is ignored when used with
+    /// `connection_string`.
+    #[configurable(metadata(docs::examples = "https://mylogstorage.dfs.core.usgovcloudapi.net/"))]
+    #[configurable(metadata(docs::examples = "https://mylogstorage.dfs.core.windows.net/"))]
+    pub(super) endpoint: Option<String>,
+
+    /// The name of the file system (container) to write files to.
+    #[configurable(metadata(docs::examples = "my-logs"))]
+    pub(super) file_system: String,
+
+    /// A prefix to apply to the path of all files.
+    ///
+    /// The prefix is rendered for each event, so it can partition files into a hierarchy of
+    /// directories built from event fields and from the event timestamp, such as the Hive-style
+    /// partitions read by most query engines. It must end in `/` to act as a directory path. A
+    /// trailing `/` is **not** automatically added.
+    #[configurable(metadata(docs::examples = "year=%Y/month=%m/day=%d/"))]
+    #[configurable(metadata(
+        docs::examples = "application={{ application_name }}/date=%F/hour=%H/"
+    ))]
+    #[serde(default = "default_path_prefix")]
+    pub(super) path_prefix: Template,
+
+    /// The timestamp format for the time component of the file name.
+    ///
+    /// By default, file names are the time at which the file is written to Azure Data Lake
+    /// Storage, in seconds since the Unix epoch.
+    ///
+    /// Supports the common [`strftime`][chrono_strftime_specifiers] specifiers found in most
+    /// languages.
+    ///
+    /// [chrono_strftime_specifiers]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+    #[configurable(metadata(docs::syntax_override = "strftime"))]
+    pub(super) file_time_format: Option<String>,
+
+    /// Whether or not to append a UUID v4 token to the file name.
+    ///
+    /// This ensures there are no name collisions between files written at the same time.
+    pub(super) file_append_uuid: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) encoding: DataLakeEncodingConfig,
+
+    /// Compression to apply to newline-delimited JSON files.
+    ///
+    /// This is ignored for Parquet files, whose pages are always compressed with Snappy.
+    #[serde(default = "Compression::gzip_default")]
+    pub(super) compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig<AzureDataLakeTowerRequestConfigDefaults>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_path_prefix() -> Template {
+    Template::try_from(DEFAULT_PATH_PREFIX).unwrap()
+}
+
+impl GenerateConfig for AzureDataLakeConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;").into()),
+            storage_account: None,
+            client_credentials: None,
+            endpoint: None,
+            file_system: String::from("logs"),
+            path_prefix: default_path_prefix(),
+            file_time_format: Some(String::from(DEFAULT_FILENAME_TIME_FORMAT)),
+            file_append_uuid: Some(DEFAULT_FILENAME_APPEND_UUID),
+            encoding: Default::default(),
+            compression: Compression::gzip_default(),
+            batch: BatchConfig::default(),
+            request: TowerRequestConfig::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_data_lake")]
+impl SinkConfig for AzureDataLakeConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let client = build_client(
+            self.connection_string
+                .as_ref()
+                .map(|v| v.inner().to_string()),
+            self.storage_account.clone(),
+            self.client_credentials.as_ref(),
+            self.file_system.clone(),
+            self.endpoint.clone(),
+        )?;
+
+        let healthcheck = healthcheck(self.file_system.clone(), Arc::clone(&client)).boxed();
+        let sink = self.build_processor(client)?;
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl AzureDataLakeConfig {
+    pub(super) fn build_processor(
+        &self,
+        client: Arc<FileSystemClient>,
+    ) -> crate::Result<VectorSink> {
+        let request_limits = self.request.into_settings();
+        let service = ServiceBuilder::new()
+            .settings(request_limits, DataLakeRetryLogic)
+            .service(DataLakeService::new(client));
+
+        let batcher_settings = self.batch.into_batcher_settings()?;
+
+        let request_options = DataLakeRequestOptions {
+            file_system: self.file_system.clone(),
+            file_time_format: self
+                .file_time_format
+                .clone()
+                .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into()),
+            file_append_uuid: self
+                .file_append_uuid
+                .unwrap_or(DEFAULT_FILENAME_APPEND_UUID),
+            encoder: self.encoding.build()?,
+            compression: self.compression,
+        };
+
+        let sink = AzureBlobSink::new(
+            service,
+            request_options,
+            KeyPartitioner::new(self.path_prefix.clone()),
+            batcher_settings,
+        );
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+}
+
+pub(super) fn build_client(
+    connection_string: Option<String>,
+    storage_account: Option<String>,
+    client_credentials: Option<&ClientCredentials>,
+    file_system: String,
+    endpoint: Option<String>,
+) -> crate::Result<Arc<FileSystemClient>> {
+    let builder = match (connection_string, storage_account) {
+        (Some(connection_string), None) => {
+            if client_credentials.is_some() {
+                return Err("`client_credentials` can only be used with `storage_account`".into());
+            }
+            let connection_string = ConnectionString::new(&connection_string)?;
+
+            // Connection strings have no property for the DFS endpoint, so the account is always
+            // reached at its default endpoint in Azure Commercial.
+            DataLakeClientBuilder::new(
+                connection_string
+                    .account_name
+                    .ok_or("Account name missing in connection string")?,
+                connection_string.storage_credentials()?,
+            )
+        }
+        (None, Some(storage_account)) => {
+            let storage_credentials =
+                StorageCredentials::token_credential(token_credential(client_credentials)?);
+
+            match endpoint {
+                Some(endpoint) => DataLakeClientBuilder::with_location(
+                    CloudLocation::Custom { uri: endpoint },
+                    storage_credentials,
+                ),
+                None => DataLakeClientBuilder::new(storage_account, storage_credentials),
+            }
+        }
+        (None, None) => {
+            return Err("Either `connection_string` or `storage_account` has to be provided".into())
+        }
+        (Some(_), Some(_)) => {
+            return Err(
+                "`connection_string` and `storage_account` can't be provided at the same time"
+                    .into(),
+            )
+        }
+    };
+
+    Ok(Arc::new(
+        builder
+            .retry(RetryOptions::none())
+            .file_system_client(file_system),
+    ))
+}
+
+async fn healthcheck(file_system: String, client: Arc<FileSystemClient>) -> crate::Result<()> {
+    match client.get_properties().into_future().await {
+        Ok(_) => Ok(()),
+        Err(reason) => Err(match reason.downcast_ref::<HttpError>() {
+            Some(err) => match StatusCode::from_u16(err.status().into()) {
+                Ok(StatusCode::FORBIDDEN) => Box::new(HealthcheckError::InvalidCredentials),
+                Ok(StatusCode::NOT_FOUND) => {
+                    Box::new(HealthcheckError::UnknownFileSystem { file_system })
+                }
+                Ok(status) => Box::new(HealthcheckError::Unknown { status }),
+                Err(_) => "unknown status code".into(),
+            },
+            _ => reason.into(),
+        }),
+    }
+}
@@ -0,0 +1,123 @@
+use std::io;
+
+use vector_lib::codecs::{
+    encoding::{Framer, Serializer},
+    JsonSerializerConfig, NewlineDelimitedEncoder,
+};
+use vector_lib::configurable::configurable_component;
+
+use crate::{internal_events::AzureDataLakeMissingRequiredColumnError, sinks::prelude::*};
+
+use super::parquet::{ParquetSerializer, ParquetSerializerConfig};
+
+/// Serializer configuration for Azure Data Lake Storage.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The format of the written files."))]
+pub(super) enum DataLakeSerializerConfig {
+    /// Writes files of [newline-delimited JSON][ndjson], with one event per line.
+    ///
+    /// [ndjson]: https://github.com/ndjson/ndjson-spec
+    #[default]
+    Ndjson,
+
+    /// Writes [Apache Parquet][parquet] files, with one row per event.
+    ///
+    /// [parquet]: https://parquet.apache.org/
+    Parquet(
+        /// Options for the Parquet serializer.
+        ParquetSerializerConfig,
+    ),
+}
+
+/// Encoding configuration for Azure Data Lake Storage.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[configurable(description = "Configures how events are encoded into files.")]
+pub struct DataLakeEncodingConfig {
+    #[serde(flatten)]
+    pub(super) encoding: DataLakeSerializerConfig,
+
+    #[serde(flatten)]
+    pub(super) transformer: Transformer,
+}
+
+impl DataLakeEncodingConfig {
+    pub(super) fn build(&self) -> crate::Result<DataLakeEncoder> {
+        let transformer = self.transformer.clone();
+
+        Ok(match &self.encoding {
+            DataLakeSerializerConfig::Ndjson => DataLakeEncoder::Ndjson((
+                transformer,
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoder::default().into(),
+                    Serializer::Json(JsonSerializerConfig::default().build()),
+                ),
+            )),
+            DataLakeSerializerConfig::Parquet(config) => DataLakeEncoder::Parquet {
+                transformer,
+                serializer: config.build()?,
+            },
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(super) enum DataLakeEncoder {
+    Ndjson((Transformer, Encoder<Framer>)),
+    Parquet {
+        transformer: Transformer,
+        serializer: ParquetSerializer,
+    },
+}
+
+impl DataLakeEncoder {
+    /// The extension of the written files, which for newline-delimited JSON also reflects the
+    /// compression.
+    pub(super) fn extension(&self, compression: Compression) -> String {
+        match self {
+            Self::Ndjson(_) => compression.extension().replacen("log", "ndjson", 1),
+            Self::Parquet { .. } => "parquet".to_string(),
+        }
+    }
+}
+
+impl encoding::Encoder<Vec<Event>> for DataLakeEncoder {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        match self {
+            Self::Ndjson(encoder) => encoder.encode_input(events, writer),
+            Self::Parquet {
+                transformer,
+                serializer,
+            } => {
+                let mut byte_size = telemetry().create_request_count_byte_size();
+                let logs = events
+                    .into_iter()
+                    .filter_map(|mut event| {
+                        transformer.transform(&mut event);
+                        let log = event.as_log();
+                        // A single event without a required column would fail the whole file.
+                        if let Some(column) = serializer.missing_required_column(log) {
+                            emit!(AzureDataLakeMissingRequiredColumnError { column });
+                            return None;
+                        }
+                        byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+                        Some(event.into_log())
+                    })
+                    .collect::<Vec<_>>();
+
+                let file = serializer
+                    .serialize(&logs)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                write_all(writer, logs.len(), &file)?;
+
+                Ok((file.len(), byte_size))
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! The Azure Data Lake Storage Gen2 [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s, partitioning them into directories, and
+//! writing each batch as a newline-delimited JSON or Parquet file through the DFS endpoint.
+
+mod config;
+mod encoding;
+mod parquet;
+mod request_builder;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AzureDataLakeConfig;
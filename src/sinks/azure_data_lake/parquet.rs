@@ -0,0 +1,372 @@
+//! Writing batches of log events as [Apache Parquet][parquet] files.
+//!
+//! [parquet]: https://parquet.apache.org/
+
+use std::sync::Arc;
+
+use parquet::{
+    basic::{
+        Compression as ParquetCompression, ConvertedType, LogicalType, Repetition, TimeUnit,
+        Type as PhysicalType,
+    },
+    column::writer::{ColumnWriter, ColumnWriterImpl},
+    data_type::{ByteArray, DataType},
+    errors::ParquetError,
+    file::{
+        properties::{WriterProperties, WriterPropertiesPtr},
+        writer::SerializedFileWriter,
+    },
+    schema::{parser::parse_message_type, types::TypePtr},
+};
+use vector_lib::configurable::configurable_component;
+use vrl::event_path;
+
+use crate::event::{LogEvent, Value};
+
+/// Parquet serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParquetSerializerConfig {
+    /// The schema of the Parquet files, in the Parquet message type syntax.
+    ///
+    /// Each column is filled from the top-level event field of the same name. Only flat schemas of
+    /// `BOOLEAN`, `INT32`, `INT64`, `FLOAT`, `DOUBLE`, and `BINARY` columns are supported, and
+    /// `INT64` columns annotated as timestamps are filled from timestamp fields.
+    ///
+    /// Events without a value of the right type for a `REQUIRED` column are dropped, so columns for
+    /// fields that are not always present must be `OPTIONAL`.
+    #[configurable(metadata(
+        docs::examples = "message log { REQUIRED INT64 timestamp (TIMESTAMP_MILLIS); OPTIONAL BINARY message (UTF8); }"
+    ))]
+    pub schema: String,
+}
+
+impl ParquetSerializerConfig {
+    /// Parses and validates the schema.
+    pub(super) fn build(&self) -> Result<ParquetSerializer, ParquetError> {
+        let schema = Arc::new(parse_message_type(&self.schema)?);
+
+        for column in schema.get_fields() {
+            let supported = column.is_primitive()
+                && column.get_basic_info().repetition() != Repetition::REPEATED
+                && !matches!(
+                    column.get_physical_type(),
+                    PhysicalType::INT96 | PhysicalType::FIXED_LEN_BYTE_ARRAY
+                );
+            if !supported {
+                return Err(ParquetError::General(format!(
+                    "Unsupported Parquet column `{}`, only non-repeated BOOLEAN, INT32, INT64, FLOAT, DOUBLE, and BINARY columns are supported",
+                    column.name()
+                )));
+            }
+        }
+
+        let properties = WriterProperties::builder()
+            .set_compression(ParquetCompression::SNAPPY)
+            .build();
+
+        Ok(ParquetSerializer {
+            schema,
+            properties: Arc::new(properties),
+        })
+    }
+}
+
+/// Serializes a batch of log events into a single Parquet file, with one row per event.
+#[derive(Clone, Debug)]
+pub(super) struct ParquetSerializer {
+    schema: TypePtr,
+    properties: WriterPropertiesPtr,
+}
+
+impl ParquetSerializer {
+    /// The first `REQUIRED` column that the event has no value of the right type for, if any.
+    ///
+    /// Such events can't be written, so they must be left out of the batch.
+    pub(super) fn missing_required_column(&self, log: &LogEvent) -> Option<&str> {
+        self.schema
+            .get_fields()
+            .iter()
+            .filter(|column| column.get_basic_info().repetition() == Repetition::REQUIRED)
+            .find(|column| {
+                let value = log.get(event_path!(column.name()));
+                !value.is_some_and(|value| has_value_for(column, value))
+            })
+            .map(|column| column.name())
+    }
+
+    pub(super) fn serialize(&self, logs: &[LogEvent]) -> Result<Vec<u8>, ParquetError> {
+        let mut buffer = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buffer,
+            Arc::clone(&self.schema),
+            Arc::clone(&self.properties),
+        )?;
+
+        let mut row_group = writer.next_row_group()?;
+        for column in self.schema.get_fields() {
+            let mut column_writer = row_group
+                .next_column()?
+                .expect("the file has a column writer for every column of the schema");
+            let name = column.name();
+            let optional = column.get_basic_info().repetition() == Repetition::OPTIONAL;
+
+            match column_writer.untyped() {
+                ColumnWriter::BoolColumnWriter(writer) => {
+                    write_column(writer, name, optional, logs, Value::as_boolean)
+                }
+                ColumnWriter::Int32ColumnWriter(writer) => {
+                    write_column(writer, name, optional, logs, to_i32)
+                }
+                ColumnWriter::Int64ColumnWriter(writer) => {
+                    let unit = timestamp_unit(column);
+                    write_column(writer, name, optional, logs, |value| to_i64(value, unit))
+                }
+                ColumnWriter::FloatColumnWriter(writer) => {
+                    write_column(writer, name, optional, logs, to_f32)
+                }
+                ColumnWriter::DoubleColumnWriter(writer) => {
+                    write_column(writer, name, optional, logs, to_f64)
+                }
+                ColumnWriter::ByteArrayColumnWriter(writer) => {
+                    write_column(writer, name, optional, logs, to_byte_array)
+                }
+                // Rejected when the schema is validated.
+                ColumnWriter::Int96ColumnWriter(_)
+                | ColumnWriter::FixedLenByteArrayColumnWriter(_) => {
+                    unreachable!("unsupported columns are rejected when building the serializer")
+                }
+            }?;
+
+            column_writer.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+
+        Ok(buffer)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TimestampUnit {
+    Millis,
+    Micros,
+    Nanos,
+}
+
+fn timestamp_unit(column: &TypePtr) -> Option<TimestampUnit> {
+    let info = column.get_basic_info();
+    match (info.logical_type(), info.converted_type()) {
+        (Some(LogicalType::Timestamp { unit, .. }), _) => Some(match unit {
+            TimeUnit::MILLIS(_) => TimestampUnit::Millis,
+            TimeUnit::MICROS(_) => TimestampUnit::Micros,
+            TimeUnit::NANOS(_) => TimestampUnit::Nanos,
+        }),
+        (_, ConvertedType::TIMESTAMP_MILLIS) => Some(TimestampUnit::Millis),
+        (_, ConvertedType::TIMESTAMP_MICROS) => Some(TimestampUnit::Micros),
+        _ => None,
+    }
+}
+
+/// Whether a value can be written to a column.
+fn has_value_for(column: &TypePtr, value: &Value) -> bool {
+    match column.get_physical_type() {
+        PhysicalType::BOOLEAN => value.as_boolean().is_some(),
+        PhysicalType::INT32 => to_i32(value).is_some(),
+        PhysicalType::INT64 => to_i64(value, timestamp_unit(column)).is_some(),
+        PhysicalType::FLOAT => to_f32(value).is_some(),
+        PhysicalType::DOUBLE => to_f64(value).is_some(),
+        PhysicalType::BYTE_ARRAY => to_byte_array(value).is_some(),
+        // Rejected when the schema is validated.
+        PhysicalType::INT96 | PhysicalType::FIXED_LEN_BYTE_ARRAY => false,
+    }
+}
+
+fn to_i32(value: &Value) -> Option<i32> {
+    value
+        .as_integer()
+        .and_then(|value| i32::try_from(value).ok())
+}
+
+fn to_i64(value: &Value, unit: Option<TimestampUnit>) -> Option<i64> {
+    match value {
+        Value::Timestamp(timestamp) => match unit? {
+            TimestampUnit::Millis => Some(timestamp.timestamp_millis()),
+            TimestampUnit::Micros => Some(timestamp.timestamp_micros()),
+            TimestampUnit::Nanos => timestamp.timestamp_nanos_opt(),
+        },
+        value => value.as_integer(),
+    }
+}
+
+fn to_f32(value: &Value) -> Option<f32> {
+    to_f64(value).map(|value| value as f32)
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(value) => Some(value.into_inner()),
+        Value::Integer(value) => Some(*value as f64),
+        _ => None,
+    }
+}
+
+fn to_byte_array(value: &Value) -> Option<ByteArray> {
+    match value {
+        Value::Bytes(bytes) => Some(ByteArray::from(bytes.clone())),
+        Value::Null => None,
+        value => Some(ByteArray::from(value.to_string_lossy().into_owned())),
+    }
+}
+
+/// Writes the values of one column, which are taken from the field of the same name of each event.
+///
+/// Missing fields, and fields that can't be converted to the column type, are written as nulls to
+/// `OPTIONAL` columns, and fail the whole file for `REQUIRED` columns, so events without them must
+/// be left out beforehand with [`ParquetSerializer::missing_required_column`].
+fn write_column<T: DataType>(
+    writer: &mut ColumnWriterImpl<'_, T>,
+    name: &str,
+    optional: bool,
+    logs: &[LogEvent],
+    convert: impl Fn(&Value) -> Option<T::T>,
+) -> Result<(), ParquetError> {
+    let mut values = Vec::with_capacity(logs.len());
+    let mut definition_levels = Vec::with_capacity(logs.len());
+
+    for log in logs {
+        match log.get(event_path!(name)).and_then(&convert) {
+            Some(value) => {
+                values.push(value);
+                definition_levels.push(1);
+            }
+            None if optional => definition_levels.push(0),
+            None => {
+                return Err(ParquetError::General(format!(
+                    "Event has no value of the right type for the required column `{name}`"
+                )))
+            }
+        }
+    }
+
+    writer.write_batch(&values, optional.then_some(&definition_levels[..]), None)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+    };
+
+    use super::*;
+
+    fn serializer(schema: &str) -> Result<ParquetSerializer, ParquetError> {
+        ParquetSerializerConfig {
+            schema: schema.to_string(),
+        }
+        .build()
+    }
+
+    #[test]
+    fn serialize_events_as_rows() {
+        let serializer = serializer(
+            "message log {
+                REQUIRED INT64 timestamp (TIMESTAMP_MILLIS);
+                REQUIRED BINARY message (UTF8);
+                OPTIONAL INT64 status;
+                OPTIONAL DOUBLE duration;
+                OPTIONAL BOOLEAN success;
+            }",
+        )
+        .unwrap();
+
+        let timestamp = Utc.timestamp_millis_opt(1_700_000_000_123).unwrap();
+        let mut first = LogEvent::from("first");
+        first.insert("timestamp", timestamp);
+        first.insert("status", 200);
+        first.insert("duration", Value::from_f64_or_zero(1.5));
+        first.insert("success", true);
+        let mut second = LogEvent::from("second");
+        second.insert("timestamp", timestamp);
+        second.insert("status", "not a number");
+
+        let file = serializer.serialize(&[first, second]).unwrap();
+
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+
+        let columns = rows[0].get_column_iter().collect::<Vec<_>>();
+        assert_eq!(
+            columns[0],
+            (
+                &"timestamp".to_string(),
+                &Field::TimestampMillis(1_700_000_000_123)
+            )
+        );
+        assert_eq!(
+            columns[1],
+            (&"message".to_string(), &Field::Str("first".into()))
+        );
+        assert_eq!(columns[2], (&"status".to_string(), &Field::Long(200)));
+        assert_eq!(columns[3], (&"duration".to_string(), &Field::Double(1.5)));
+        assert_eq!(columns[4], (&"success".to_string(), &Field::Bool(true)));
+
+        let columns = rows[1].get_column_iter().collect::<Vec<_>>();
+        assert_eq!(
+            columns[1],
+            (&"message".to_string(), &Field::Str("second".into()))
+        );
+        assert_eq!(columns[2], (&"status".to_string(), &Field::Null));
+        assert_eq!(columns[4], (&"success".to_string(), &Field::Null));
+    }
+
+    #[test]
+    fn missing_required_field_fails() {
+        let serializer = serializer("message log { REQUIRED BINARY host (UTF8); }").unwrap();
+        assert!(serializer.serialize(&[LogEvent::from("message")]).is_err());
+    }
+
+    #[test]
+    fn finds_missing_required_columns() {
+        let serializer = serializer(
+            "message log {
+                OPTIONAL BINARY host (UTF8);
+                REQUIRED INT32 status;
+                REQUIRED INT64 timestamp (TIMESTAMP_MILLIS);
+            }",
+        )
+        .unwrap();
+
+        let mut log = LogEvent::from("message");
+        log.insert("status", 200);
+        log.insert("timestamp", Utc::now());
+        assert_eq!(serializer.missing_required_column(&log), None);
+
+        log.insert("status", i64::from(i32::MAX) + 1);
+        assert_eq!(serializer.missing_required_column(&log), Some("status"));
+
+        log.insert("status", 200);
+        log.insert("timestamp", "yesterday");
+        assert_eq!(serializer.missing_required_column(&log), Some("timestamp"));
+    }
+
+    #[test]
+    fn unsupported_schemas_are_rejected() {
+        assert!(serializer("not a schema").is_err());
+        assert!(serializer("message log { REPEATED BINARY tags (UTF8); }").is_err());
+        assert!(serializer(
+            "message log { OPTIONAL group kubernetes { OPTIONAL BINARY pod (UTF8); } }"
+        )
+        .is_err());
+        assert!(serializer("message log { OPTIONAL INT96 timestamp; }").is_err());
+    }
+}
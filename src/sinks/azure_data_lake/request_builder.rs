@@ -0,0 +1,112 @@
+use bytes::Bytes;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::sinks::{azure_common::config::AzureBlobMetadata, prelude::*};
+
+use super::encoding::DataLakeEncoder;
+
+#[derive(Debug, Clone)]
+pub(super) struct DataLakeRequest {
+    pub(super) data: Bytes,
+    pub(super) metadata: AzureBlobMetadata,
+    pub(super) request_metadata: RequestMetadata,
+}
+
+impl Finalizable for DataLakeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.metadata.finalizers)
+    }
+}
+
+impl MetaDescriptive for DataLakeRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct DataLakeRequestOptions {
+    pub(super) file_system: String,
+    pub(super) file_time_format: String,
+    pub(super) file_append_uuid: bool,
+    pub(super) encoder: DataLakeEncoder,
+    pub(super) compression: Compression,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for DataLakeRequestOptions {
+    type Metadata = AzureBlobMetadata;
+    type Events = Vec<Event>;
+    type Encoder = DataLakeEncoder;
+    type Payload = Bytes;
+    type Request = DataLakeRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        match self.encoder {
+            // Parquet compresses the pages of the file itself.
+            DataLakeEncoder::Parquet { .. } => Compression::None,
+            DataLakeEncoder::Ndjson(_) => self.compression,
+        }
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (partition_key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let metadata = AzureBlobMetadata {
+            partition_key,
+            count: events.len(),
+            byte_size: events.estimated_json_encoded_size_of(),
+            finalizers,
+        };
+
+        let builder = RequestMetadataBuilder::from_events(&events);
+
+        (metadata, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        mut metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let file_name = {
+            let formatted_ts = Utc::now().format(self.file_time_format.as_str());
+
+            self.file_append_uuid
+                .then(|| format!("{}-{}", formatted_ts, Uuid::new_v4().hyphenated()))
+                .unwrap_or_else(|| formatted_ts.to_string())
+        };
+
+        let extension = self.encoder.extension(self.compression());
+        metadata.partition_key = format!("{}{}.{}", metadata.partition_key, file_name, extension);
+
+        let data = payload.into_payload();
+
+        debug!(
+            message = "Sending events.",
+            bytes = ?data.len(),
+            events_len = ?metadata.count,
+            path = ?metadata.partition_key,
+            file_system = ?self.file_system,
+        );
+
+        DataLakeRequest {
+            data,
+            metadata,
+            request_metadata,
+        }
+    }
+}
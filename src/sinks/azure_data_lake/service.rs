@@ -0,0 +1,98 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use azure_core::error::HttpError;
+use azure_storage_datalake::prelude::*;
+use http::StatusCode;
+use tracing::Instrument;
+
+use crate::sinks::prelude::*;
+
+use super::request_builder::DataLakeRequest;
+
+#[derive(Clone)]
+pub(super) struct DataLakeService {
+    client: Arc<FileSystemClient>,
+}
+
+impl DataLakeService {
+    pub(super) const fn new(client: Arc<FileSystemClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct DataLakeResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for DataLakeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+impl Service<DataLakeRequest> for DataLakeService {
+    type Response = DataLakeResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: DataLakeRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let byte_size = request.data.len();
+
+            // Files are written in three steps: the file is created, which also creates any
+            // missing directories of its path, its contents are appended, and the appended
+            // contents are flushed to commit them.
+            let file = this
+                .client
+                .get_file_client(request.metadata.partition_key.as_str());
+            async {
+                file.create().into_future().await?;
+                file.append(0, request.data).into_future().await?;
+                file.flush(byte_size as i64).close(true).into_future().await
+            }
+            .instrument(info_span!("request").or_current())
+            .await?;
+
+            Ok(DataLakeResponse {
+                events_byte_size: request
+                    .request_metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct DataLakeRetryLogic;
+
+impl RetryLogic for DataLakeRetryLogic {
+    type Error = HttpError;
+    type Response = DataLakeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.status().is_server_error()
+            || StatusCode::TOO_MANY_REQUESTS.as_u16() == Into::<u16>::into(error.status())
+    }
+}
@@ -0,0 +1,139 @@
+//! Unit tests for the `azure_data_lake` sink.
+
+use bytes::Bytes;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use vector_lib::partition::Partitioner;
+
+use super::{
+    config::AzureDataLakeConfig,
+    encoding::{DataLakeEncoder, DataLakeSerializerConfig},
+    request_builder::DataLakeRequestOptions,
+};
+use crate::{
+    event::{Event, LogEvent},
+    sinks::{
+        prelude::*,
+        util::{
+            encoding::Encoder as _, partitioner::KeyPartitioner, request_builder::RequestBuilder,
+        },
+    },
+};
+
+fn config(options: &str) -> AzureDataLakeConfig {
+    toml::from_str(&format!(
+        r#"
+        connection_string = "DefaultEndpointsProtocol=https;AccountName=account;AccountKey=key"
+        file_system = "logs"
+        {options}
+        "#
+    ))
+    .unwrap()
+}
+
+fn build_request(config: &AzureDataLakeConfig, event: Event) -> String {
+    let key = KeyPartitioner::new(config.path_prefix.clone())
+        .partition(&event)
+        .expect("key wasn't provided");
+    let request_options = DataLakeRequestOptions {
+        file_system: config.file_system.clone(),
+        file_time_format: String::from("%s"),
+        file_append_uuid: false,
+        encoder: config.encoding.build().unwrap(),
+        compression: config.compression,
+    };
+
+    let (metadata, request_metadata_builder, events) =
+        request_options.split_input((key, vec![event]));
+    let mut byte_size = GroupedCountByteSize::new_untagged();
+    for event in &events {
+        byte_size.add_event(event, event.estimated_json_encoded_size_of());
+    }
+    let payload = EncodeResult::uncompressed(Bytes::new(), byte_size);
+    let request_metadata = request_metadata_builder.build(&payload);
+
+    request_options
+        .build_request(metadata, request_metadata, payload)
+        .metadata
+        .partition_key
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureDataLakeConfig>();
+}
+
+#[test]
+fn ndjson_files_are_partitioned_by_date() {
+    let config = config("");
+    assert!(matches!(
+        config.encoding.encoding,
+        DataLakeSerializerConfig::Ndjson
+    ));
+
+    let mut log = LogEvent::from("message");
+    log.insert(
+        "timestamp",
+        chrono::DateTime::parse_from_rfc3339("2024-03-05T10:00:00Z")
+            .unwrap()
+            .to_utc(),
+    );
+    let path = build_request(&config, Event::Log(log));
+
+    assert!(path.starts_with("year=2024/month=03/day=05/1"));
+    assert!(path.ends_with(".ndjson.gz"));
+}
+
+#[test]
+fn parquet_files_are_not_compressed_again() {
+    let config = config(
+        r#"
+        path_prefix = "application={{ application }}/"
+        compression = "gzip"
+        encoding.codec = "parquet"
+        encoding.schema = "message log { OPTIONAL BINARY message (UTF8); }"
+        "#,
+    );
+    assert!(matches!(
+        config.encoding.build().unwrap(),
+        DataLakeEncoder::Parquet { .. }
+    ));
+
+    let mut log = LogEvent::from("message");
+    log.insert("application", "checkout");
+    let path = build_request(&config, Event::Log(log));
+
+    assert!(path.starts_with("application=checkout/"));
+    assert!(path.ends_with(".parquet"));
+}
+
+#[test]
+fn events_without_required_columns_are_dropped() {
+    let config = config(
+        r#"
+        encoding.codec = "parquet"
+        encoding.schema = "message log { REQUIRED BINARY host (UTF8); }"
+        "#,
+    );
+    let encoder = config.encoding.build().unwrap();
+
+    let mut log = LogEvent::from("message");
+    log.insert("host", "example.com");
+    let events = vec![Event::Log(log), Event::Log(LogEvent::from("no host"))];
+    let mut file = Vec::new();
+    let (_, byte_size) = encoder.encode_input(events, &mut file).unwrap();
+
+    assert_eq!(byte_size.size().unwrap().0, 1);
+    let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 1);
+}
+
+#[test]
+fn invalid_parquet_schema_is_rejected() {
+    let config = config(
+        r#"
+        encoding.codec = "parquet"
+        encoding.schema = "message log { REPEATED BINARY tags (UTF8); }"
+        "#,
+    );
+    assert!(config.encoding.build().is_err());
+}
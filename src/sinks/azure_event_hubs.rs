@@ -0,0 +1,399 @@
+use std::{collections::HashMap, error::Error, sync::Arc, thread, time::Duration};
+
+use azure_core::auth::TokenCredential;
+use rdkafka::client::OAuthToken;
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio::runtime::Handle;
+use tracing::Span;
+use vector_lib::codecs::JsonSerializerConfig;
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+use vrl::value::Kind;
+
+use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
+    azure_event_hubs::{kafka_auth, token_kafka_auth, EventHubsConnection},
+    kafka::{KafkaCompression, KafkaStatisticsContext, KafkaTokenContext, KafkaTokenProvider},
+    sinks::{
+        kafka::{
+            sink::{healthcheck_with_context, KafkaSink},
+            KafkaSinkConfig,
+        },
+        prelude::*,
+    },
+};
+
+/// `librdkafka` requires a principal name for each token, but Event Hubs identifies clients by
+/// the token itself.
+const TOKEN_PRINCIPAL_NAME: &str = "vector";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`client_credentials` can't be used with `connection_string`"))]
+    AmbiguousAuthentication,
+    #[snafu(display("`namespace` can't be used with `connection_string`"))]
+    AmbiguousNamespace,
+    #[snafu(display(
+        "`namespace` and `event_hub_name` must be set when not authenticating with `connection_string`"
+    ))]
+    MissingNamespace,
+}
+
+/// Configuration for the `azure_event_hubs` sink.
+#[serde_as]
+#[configurable_component(sink(
+    "azure_event_hubs",
+    "Publish observability events to Azure Event Hubs."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsSinkConfig {
+    /// The connection string of the Event Hubs namespace or of the Event Hub itself.
+    ///
+    /// The namespace host is taken from its `Endpoint` property, and the Event Hub name from its
+    /// `EntityPath` property when present.
+    ///
+    /// If unset, the sink authenticates with Azure Active Directory instead, as the service
+    /// principal in `client_credentials` or with the default credential chain.
+    #[configurable(metadata(
+        docs::examples = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=logs"
+    ))]
+    pub connection_string: Option<SensitiveString>,
+
+    /// The service principal to authenticate with Azure Active Directory as.
+    ///
+    /// The identity must be granted a role such as `Azure Event Hubs Data Sender` on the Event
+    /// Hub. If neither this nor `connection_string` is set, credentials are loaded from the
+    /// environment, a [managed identity][managed_ident_docs], or the `az` CLI, in that order.
+    ///
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    #[configurable(derived)]
+    pub client_credentials: Option<ClientCredentials>,
+
+    /// The fully qualified host name of the Event Hubs namespace.
+    ///
+    /// Required when authenticating with Azure Active Directory. Can't be used with
+    /// `connection_string`, which contains the namespace.
+    #[configurable(metadata(docs::examples = "mynamespace.servicebus.windows.net"))]
+    pub namespace: Option<String>,
+
+    /// The name of the Event Hub to write events to.
+    ///
+    /// Required when authenticating with Azure Active Directory, or if the connection string does
+    /// not contain an `EntityPath`.
+    #[configurable(metadata(docs::examples = "logs"))]
+    pub event_hub_name: Option<String>,
+
+    /// The partition key of events.
+    ///
+    /// Events with the same partition key are written to the same partition. If unspecified, or
+    /// if the key can't be rendered for an event, events are spread across partitions.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(docs::examples = "{{ user_id }}"))]
+    #[configurable(metadata(docs::examples = "{{ tenant }}-{{ host }}"))]
+    pub partition_key: Option<Template>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub batch: BatchConfig<NoDefaultsBatchSettings>,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub compression: KafkaCompression,
+
+    /// Local message timeout, in milliseconds.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::examples = 150000, docs::examples = 450000))]
+    #[serde(default = "default_message_timeout_ms")]
+    #[configurable(metadata(docs::human_name = "Message Timeout"))]
+    #[configurable(metadata(docs::advanced))]
+    pub message_timeout_ms: Duration,
+
+    /// A map of advanced options to pass directly to the underlying `librdkafka` client.
+    ///
+    /// For more information on configuration options, see [Configuration properties][config_props_docs].
+    ///
+    /// [config_props_docs]: https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(
+        docs::additional_props_description = "A librdkafka configuration option."
+    ))]
+    pub librdkafka_options: HashMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_socket_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
+const fn default_message_timeout_ms() -> Duration {
+    Duration::from_millis(300000) // default in librdkafka
+}
+
+impl GenerateConfig for AzureEventHubsSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: Some("Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=logs".to_string().into()),
+            client_credentials: None,
+            namespace: None,
+            event_hub_name: None,
+            partition_key: None,
+            encoding: JsonSerializerConfig::default().into(),
+            batch: Default::default(),
+            compression: KafkaCompression::None,
+            message_timeout_ms: default_message_timeout_ms(),
+            librdkafka_options: Default::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+impl AzureEventHubsSinkConfig {
+    /// The Event Hub that events are written to.
+    fn connection(&self) -> crate::Result<EventHubsConnection> {
+        match &self.connection_string {
+            Some(connection_string) => {
+                if self.client_credentials.is_some() {
+                    return Err(BuildError::AmbiguousAuthentication.into());
+                }
+                if self.namespace.is_some() {
+                    return Err(BuildError::AmbiguousNamespace.into());
+                }
+                Ok(EventHubsConnection::parse(
+                    connection_string.inner(),
+                    self.event_hub_name.as_deref(),
+                )?)
+            }
+            None => match (&self.namespace, &self.event_hub_name) {
+                (Some(namespace), Some(event_hub_name)) => {
+                    Ok(EventHubsConnection::new(namespace, event_hub_name))
+                }
+                _ => Err(BuildError::MissingNamespace.into()),
+            },
+        }
+    }
+
+    /// Event Hubs speaks the Kafka protocol, so events are produced by the `kafka` sink pointed
+    /// at the namespace's Kafka endpoint, with the Event Hub as the topic.
+    fn kafka_config(&self) -> crate::Result<KafkaSinkConfig> {
+        let connection = self.connection()?;
+        let auth = match &self.connection_string {
+            Some(connection_string) => kafka_auth(connection_string),
+            None => token_kafka_auth(),
+        };
+
+        Ok(KafkaSinkConfig {
+            bootstrap_servers: connection.bootstrap_servers(),
+            topic: Template::try_from(connection.event_hub_name())?,
+            healthcheck_topic: None,
+            key_field: None,
+            encoding: self.encoding.clone(),
+            batch: self.batch,
+            compression: self.compression,
+            auth,
+            socket_timeout_ms: default_socket_timeout_ms(),
+            message_timeout_ms: self.message_timeout_ms,
+            librdkafka_options: self.librdkafka_options.clone(),
+            delivery: Default::default(),
+            headers_key: None,
+            acknowledgements: self.acknowledgements,
+            key_template: self.partition_key.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SinkConfig for AzureEventHubsSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let kafka_config = self.kafka_config()?;
+        if self.connection_string.is_some() {
+            return kafka_config.build(cx).await;
+        }
+
+        let context = KafkaTokenContext {
+            statistics: KafkaStatisticsContext {
+                expose_lag_metrics: false,
+                span: Span::current(),
+            },
+            token_provider: Arc::new(AzureAdTokenProvider {
+                credential: token_credential(self.client_credentials.as_ref())?,
+                resource: self.connection()?.token_resource(),
+                runtime: Handle::current(),
+            }),
+        };
+        let sink = KafkaSink::with_context(kafka_config.clone(), context.clone())?;
+        let healthcheck = healthcheck_with_context(kafka_config, context).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        let requirements = Requirement::empty().optional_meaning("timestamp", Kind::timestamp());
+
+        Input::new(self.encoding.config().input_type() & (DataType::Log | DataType::Metric))
+            .with_schema_requirement(requirements)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Fetches the bearer tokens of the Kafka endpoint of a namespace from Azure Active Directory.
+struct AzureAdTokenProvider {
+    credential: Arc<dyn TokenCredential>,
+    resource: String,
+    /// The runtime that token requests are run on.
+    runtime: Handle,
+}
+
+impl KafkaTokenProvider for AzureAdTokenProvider {
+    fn token(&self) -> Result<OAuthToken, Box<dyn Error>> {
+        // The request runs on a thread of its own, as `librdkafka` may also ask for a token from a
+        // thread of the runtime, such as when a producer is dropped.
+        let response = thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    self.runtime
+                        .block_on(self.credential.get_token(&self.resource))
+                })
+                .join()
+        })
+        .map_err(|_| "Azure Active Directory token request panicked")??;
+        Ok(OAuthToken {
+            token: response.token.secret().to_string(),
+            principal_name: TOKEN_PRINCIPAL_NAME.to_string(),
+            lifetime_ms: (response.expires_on.unix_timestamp_nanos() / 1_000_000) as i64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSinkConfig>();
+    }
+
+    #[test]
+    fn kafka_config_targets_event_hubs_endpoint() {
+        let config: AzureEventHubsSinkConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret"
+            event_hub_name = "logs"
+            partition_key = "{{ user_id }}"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        let kafka_config = config.kafka_config().unwrap();
+
+        assert_eq!(
+            kafka_config.bootstrap_servers,
+            "mynamespace.servicebus.windows.net:9093"
+        );
+        assert_eq!(kafka_config.topic.to_string(), "logs");
+        assert_eq!(
+            kafka_config.key_template,
+            Some(Template::try_from("{{ user_id }}").unwrap())
+        );
+        assert_eq!(
+            kafka_config.auth.sasl.unwrap().mechanism.as_deref(),
+            Some("PLAIN")
+        );
+    }
+
+    #[test]
+    fn kafka_config_with_azure_ad() {
+        for credentials in [
+            "",
+            r#"
+            client_credentials.tenant_id = "tenant"
+            client_credentials.client_id = "client"
+            client_credentials.client_secret = "secret"
+            "#,
+        ] {
+            let config: AzureEventHubsSinkConfig = toml::from_str(&format!(
+                r#"
+                namespace = "mynamespace.servicebus.windows.net"
+                event_hub_name = "logs"
+                encoding.codec = "json"
+                {credentials}
+                "#
+            ))
+            .unwrap();
+            let kafka_config = config.kafka_config().unwrap();
+
+            assert_eq!(
+                kafka_config.bootstrap_servers,
+                "mynamespace.servicebus.windows.net:9093"
+            );
+            assert_eq!(kafka_config.topic.to_string(), "logs");
+            assert_eq!(
+                kafka_config.auth.sasl.unwrap().mechanism.as_deref(),
+                Some("OAUTHBEARER")
+            );
+        }
+    }
+
+    #[test]
+    fn kafka_config_rejects_ambiguous_settings() {
+        let config: AzureEventHubsSinkConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret;EntityPath=logs"
+            namespace = "othernamespace.servicebus.windows.net"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.kafka_config().unwrap_err().to_string(),
+            BuildError::AmbiguousNamespace.to_string()
+        );
+
+        let config: AzureEventHubsSinkConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret;EntityPath=logs"
+            client_credentials.tenant_id = "tenant"
+            client_credentials.client_id = "client"
+            client_credentials.client_secret = "secret"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.kafka_config().unwrap_err().to_string(),
+            BuildError::AmbiguousAuthentication.to_string()
+        );
+
+        let config: AzureEventHubsSinkConfig = toml::from_str(
+            r#"
+            event_hub_name = "logs"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.kafka_config().unwrap_err().to_string(),
+            BuildError::MissingNamespace.to_string()
+        );
+    }
+}
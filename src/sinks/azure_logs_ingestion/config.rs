@@ -0,0 +1,197 @@
+use http::Uri;
+use vector_lib::configurable::configurable_component;
+use vector_lib::schema;
+use vrl::value::Kind;
+
+use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
+    http::{get_http_scheme_from_uri, HttpClient},
+    sinks::{prelude::*, util::http::HttpStatusRetryLogic},
+};
+
+use super::{
+    service::{AzureLogsIngestionResponse, AzureLogsIngestionService},
+    sink::AzureLogsIngestionSink,
+};
+
+/// Max number of bytes in request body
+///
+/// The Logs Ingestion API rejects calls larger than 1 MB, whether compressed or not, so batches
+/// are capped at that size before compression.
+const MAX_BATCH_SIZE: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureLogsIngestionDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureLogsIngestionDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// API version
+const API_VERSION: &str = "2023-01-01";
+
+pub(super) fn default_token_scope() -> String {
+    "https://monitor.azure.com".into()
+}
+
+pub(super) fn default_timestamp_field() -> String {
+    "TimeGenerated".into()
+}
+
+/// Configuration for the `azure_logs_ingestion` sink.
+#[configurable_component(sink(
+    "azure_logs_ingestion",
+    "Publish log events to the Azure Monitor Logs Ingestion API."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureLogsIngestionConfig {
+    /// The [logs ingestion endpoint][dce] of the data collection endpoint, or of the data collection
+    /// rule itself.
+    ///
+    /// [dce]: https://learn.microsoft.com/en-us/azure/azure-monitor/logs/logs-ingestion-api-overview#endpoint
+    #[configurable(metadata(
+        docs::examples = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+    ))]
+    #[configurable(validation(format = "uri"))]
+    pub endpoint: String,
+
+    /// The immutable ID of the data collection rule that processes the logs.
+    #[configurable(metadata(docs::examples = "dcr-000a00a000a00000a000000aa000a0aa"))]
+    pub dcr_immutable_id: String,
+
+    /// The name of the stream of the data collection rule that logs are sent to.
+    ///
+    /// Custom tables use streams prefixed with `Custom-`.
+    #[configurable(metadata(docs::examples = "Custom-MyTable_CL"))]
+    pub stream_name: String,
+
+    /// The resource that access tokens are requested for.
+    ///
+    /// This only needs to be changed for sovereign clouds.
+    #[configurable(metadata(docs::examples = "https://monitor.azure.us"))]
+    #[serde(default = "default_token_scope")]
+    pub token_scope: String,
+
+    #[configurable(derived)]
+    pub client_credentials: Option<ClientCredentials>,
+
+    /// The field that the timestamp of each event is written to.
+    ///
+    /// The timestamp is formatted as an RFC 3339 string with millisecond precision. Events without
+    /// a timestamp are stamped with the time they are encoded at.
+    #[configurable(metadata(docs::examples = "TimeGenerated"))]
+    #[serde(default = "default_timestamp_field")]
+    pub timestamp_field: String,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureLogsIngestionDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl Default for AzureLogsIngestionConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com".to_string(),
+            dcr_immutable_id: "dcr-000a00a000a00000a000000aa000a0aa".to_string(),
+            stream_name: "Custom-MyTable_CL".to_string(),
+            token_scope: default_token_scope(),
+            client_credentials: None,
+            timestamp_field: default_timestamp_field(),
+            encoding: Default::default(),
+            batch: Default::default(),
+            request: Default::default(),
+            tls: None,
+            acknowledgements: Default::default(),
+        }
+    }
+}
+
+impl AzureLogsIngestionConfig {
+    /// The URI that the logs of the stream are posted to.
+    pub(super) fn stream_uri(&self) -> crate::Result<Uri> {
+        format!(
+            "{}/dataCollectionRules/{}/streams/{}?api-version={API_VERSION}",
+            self.endpoint.trim_end_matches('/'),
+            self.dcr_immutable_id,
+            self.stream_name
+        )
+        .parse::<Uri>()
+        .map_err(Into::into)
+    }
+}
+
+impl_generate_config_from_default!(AzureLogsIngestionConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_logs_ingestion")]
+impl SinkConfig for AzureLogsIngestionConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.stream_uri()?;
+        let protocol = get_http_scheme_from_uri(&endpoint).to_string();
+
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_SIZE)?
+            .into_batcher_settings()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(Some(tls_settings), &cx.proxy)?;
+
+        let credential = token_credential(self.client_credentials.as_ref())?;
+
+        let service =
+            AzureLogsIngestionService::new(client, endpoint, credential, self.token_scope.clone());
+        let healthcheck = service.healthcheck();
+
+        let retry_logic =
+            HttpStatusRetryLogic::new(|res: &AzureLogsIngestionResponse| res.http_status);
+        let request_settings = self.request.into_settings();
+        let service = ServiceBuilder::new()
+            .settings(request_settings, retry_logic)
+            .service(service);
+
+        let sink = AzureLogsIngestionSink::new(
+            batch_settings,
+            self.encoding.clone(),
+            service,
+            self.timestamp_field.clone(),
+            protocol,
+        );
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        let requirements =
+            schema::Requirement::empty().optional_meaning("timestamp", Kind::timestamp());
+
+        Input::log().with_schema_requirement(requirements)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
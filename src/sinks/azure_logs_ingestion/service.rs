@@ -0,0 +1,165 @@
+use std::sync::{Arc, LazyLock};
+use std::task::{Context, Poll};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{
+    header::{self, HeaderMap},
+    HeaderValue, Request, StatusCode, Uri,
+};
+use hyper::Body;
+use tracing::Instrument;
+
+use crate::{http::HttpClient, sinks::prelude::*};
+
+static CONTENT_TYPE_VALUE: LazyLock<HeaderValue> =
+    LazyLock::new(|| HeaderValue::from_static("application/json"));
+static CONTENT_ENCODING_VALUE: LazyLock<HeaderValue> =
+    LazyLock::new(|| HeaderValue::from_static("gzip"));
+
+#[derive(Debug, Clone)]
+pub struct AzureLogsIngestionRequest {
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl MetaDescriptive for AzureLogsIngestionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+impl Finalizable for AzureLogsIngestionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct AzureLogsIngestionResponse {
+    pub http_status: StatusCode,
+    pub events_byte_size: GroupedCountByteSize,
+    pub raw_byte_size: usize,
+}
+
+impl DriverResponse for AzureLogsIngestionResponse {
+    fn event_status(&self) -> EventStatus {
+        match self.http_status.is_success() {
+            true => EventStatus::Delivered,
+            false => EventStatus::Rejected,
+        }
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.raw_byte_size)
+    }
+}
+
+/// `AzureLogsIngestionService` is a `Tower` service used to send logs to a data collection rule.
+#[derive(Clone)]
+pub struct AzureLogsIngestionService {
+    client: HttpClient,
+    endpoint: Uri,
+    credential: Arc<dyn TokenCredential>,
+    token_scope: String,
+    default_headers: HeaderMap,
+}
+
+impl AzureLogsIngestionService {
+    /// Creates a new `AzureLogsIngestionService`.
+    pub fn new(
+        client: HttpClient,
+        endpoint: Uri,
+        credential: Arc<dyn TokenCredential>,
+        token_scope: String,
+    ) -> Self {
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert(header::CONTENT_TYPE, CONTENT_TYPE_VALUE.clone());
+
+        Self {
+            client,
+            endpoint,
+            credential,
+            token_scope,
+            default_headers,
+        }
+    }
+
+    async fn build_request(&self, body: Bytes, gzipped: bool) -> crate::Result<Request<Body>> {
+        let token = self.credential.get_token(&self.token_scope).await?;
+
+        let mut request = Request::post(&self.endpoint).body(Body::from(body))?;
+
+        *request.headers_mut() = self.default_headers.clone();
+        if gzipped {
+            request
+                .headers_mut()
+                .insert(header::CONTENT_ENCODING, CONTENT_ENCODING_VALUE.clone());
+        }
+        request.headers_mut().insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token.token.secret()).parse()?,
+        );
+
+        Ok(request)
+    }
+
+    pub fn healthcheck(&self) -> Healthcheck {
+        let service = self.clone();
+        Box::pin(async move {
+            let request = service.build_request(Bytes::from("[]"), false).await?;
+            let res = service.client.send(request).in_current_span().await?;
+
+            if res.status().is_server_error() {
+                return Err("Server returned a server error".into());
+            }
+
+            if res.status() == StatusCode::UNAUTHORIZED || res.status() == StatusCode::FORBIDDEN {
+                return Err("The service failed to authorize the request. Verify that the identity has the Monitoring Metrics Publisher role on the data collection rule".into());
+            }
+
+            if res.status() == StatusCode::NOT_FOUND {
+                return Err(
+                    "Either the endpoint is incorrect, or the data collection rule or stream does not exist".into(),
+                );
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl Service<AzureLogsIngestionRequest> for AzureLogsIngestionService {
+    type Response = AzureLogsIngestionResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of Error internal event is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of Error internal event is handled upstream by the caller.
+    fn call(&mut self, request: AzureLogsIngestionRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let http_request = service.build_request(request.body, true).await?;
+            let response = service.client.send(http_request).in_current_span().await?;
+            Ok(AzureLogsIngestionResponse {
+                http_status: response.status(),
+                raw_byte_size: request.metadata.request_encoded_size(),
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use vector_lib::config::log_schema;
+
+use super::{config::AzureLogsIngestionConfig, sink::JsonEncoding};
+use crate::{
+    event::LogEvent,
+    sinks::{prelude::*, util::encoding::Encoder},
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureLogsIngestionConfig>();
+}
+
+#[test]
+fn correct_stream_uri() {
+    let config = toml::from_str::<AzureLogsIngestionConfig>(
+        r#"
+            endpoint = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com/"
+            dcr_immutable_id = "dcr-000a00a000a00000a000000aa000a0aa"
+            stream_name = "Custom-MyTable_CL"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.stream_uri().unwrap().to_string(),
+        "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com/dataCollectionRules/dcr-000a00a000a00000a000000aa000a0aa/streams/Custom-MyTable_CL?api-version=2023-01-01"
+    );
+    assert_eq!(config.token_scope, "https://monitor.azure.com");
+    assert_eq!(config.timestamp_field, "TimeGenerated");
+}
+
+#[tokio::test]
+async fn builds_with_default_batch() {
+    let config = toml::from_str::<AzureLogsIngestionConfig>(
+        r#"
+            endpoint = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+            dcr_immutable_id = "dcr-000a00a000a00000a000000aa000a0aa"
+            stream_name = "Custom-MyTable_CL"
+        "#,
+    )
+    .unwrap();
+
+    config
+        .build(SinkContext::default())
+        .await
+        .expect("Config with default batch settings failed to build");
+}
+
+#[test]
+fn fails_config_missing_fields() {
+    toml::from_str::<AzureLogsIngestionConfig>(
+        r#"
+            endpoint = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+            stream_name = "Custom-MyTable_CL"
+        "#,
+    )
+    .expect_err("Config parsing failed to error with missing dcr_immutable_id");
+
+    toml::from_str::<AzureLogsIngestionConfig>(
+        r#"
+            endpoint = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+            dcr_immutable_id = "dcr-000a00a000a00000a000000aa000a0aa"
+        "#,
+    )
+    .expect_err("Config parsing failed to error with missing stream_name");
+}
+
+#[test]
+fn encode_valid() {
+    let now = chrono::Utc::now();
+    let mut log = [("message", "hello world")]
+        .iter()
+        .copied()
+        .collect::<LogEvent>();
+    log.insert(log_schema().timestamp_key_target_path().unwrap(), now);
+
+    let encoder = JsonEncoding::new(Default::default(), "TimeGenerated".to_string());
+    let mut encoded = vec![];
+    encoder
+        .encode_input(vec![Event::from(log)], &mut encoded)
+        .unwrap();
+
+    let expected_json = serde_json::json!([{
+        "TimeGenerated": now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "message": "hello world"
+    }]);
+    let json: serde_json::Value = serde_json::from_slice(&encoded).unwrap();
+    assert_eq!(json, expected_json);
+}
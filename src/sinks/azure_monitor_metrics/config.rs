@@ -0,0 +1,177 @@
+//! Configuration for the `azure_monitor_metrics` sink.
+use http::Uri;
+
+use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
+    http::HttpClient,
+    sinks::{prelude::*, util::http::HttpStatusRetryLogic},
+};
+
+use super::{
+    request_builder::{AzureMonitorMetricsEncoder, AzureMonitorMetricsRequestBuilder},
+    service::{AzureMonitorMetricsResponse, AzureMonitorMetricsService},
+    sink::AzureMonitorMetricsSink,
+};
+
+pub(super) fn default_token_scope() -> String {
+    "https://monitoring.azure.com".into()
+}
+
+pub(super) fn default_namespace() -> String {
+    "vector".into()
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureMonitorMetricsDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureMonitorMetricsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1_000);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `azure_monitor_metrics` sink.
+#[configurable_component(sink(
+    "azure_monitor_metrics",
+    "Publish metric events as Azure Monitor custom metrics."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureMonitorMetricsConfig {
+    /// The Azure region of the resource that metrics are published for.
+    ///
+    /// Custom metrics are published to the regional endpoint of the resource, and must be
+    /// published to a region that supports them.
+    #[configurable(metadata(docs::examples = "eastus"))]
+    #[configurable(metadata(docs::examples = "westeurope"))]
+    pub region: String,
+
+    /// The ID of the Azure resource that metrics are published for.
+    ///
+    /// Custom metrics are stored next to the platform metrics of this resource.
+    #[configurable(metadata(
+        docs::examples = "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/my-group/providers/Microsoft.Compute/virtualMachines/my-vm"
+    ))]
+    pub resource_id: String,
+
+    /// The endpoint to publish metrics to, instead of the regional endpoint derived from `region`.
+    ///
+    /// This only needs to be set for sovereign clouds.
+    #[configurable(metadata(docs::examples = "https://usgovvirginia.monitoring.azure.us"))]
+    pub endpoint: Option<String>,
+
+    /// The resource that access tokens are requested for.
+    ///
+    /// This only needs to be changed for sovereign clouds.
+    #[configurable(metadata(docs::examples = "https://monitoring.azure.us"))]
+    #[serde(default = "default_token_scope")]
+    pub token_scope: String,
+
+    #[configurable(derived)]
+    pub client_credentials: Option<ClientCredentials>,
+
+    /// The default namespace to use for metrics that do not have one.
+    ///
+    /// Metrics with the same name can only be differentiated by their namespace, and not all
+    /// metrics have their own namespace.
+    #[configurable(metadata(docs::examples = "service"))]
+    #[serde(default = "default_namespace")]
+    pub default_namespace: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureMonitorMetricsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl Default for AzureMonitorMetricsConfig {
+    fn default() -> Self {
+        Self {
+            region: "eastus".to_string(),
+            resource_id: "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/my-group/providers/Microsoft.Compute/virtualMachines/my-vm".to_string(),
+            endpoint: None,
+            token_scope: default_token_scope(),
+            client_credentials: None,
+            default_namespace: default_namespace(),
+            batch: Default::default(),
+            request: Default::default(),
+            tls: None,
+            acknowledgements: Default::default(),
+        }
+    }
+}
+
+impl AzureMonitorMetricsConfig {
+    /// The URI that the custom metrics of the resource are posted to.
+    pub(super) fn metrics_uri(&self) -> crate::Result<Uri> {
+        let endpoint = match &self.endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://{}.monitoring.azure.com", self.region),
+        };
+
+        format!("{endpoint}/{}/metrics", self.resource_id.trim_matches('/'))
+            .parse::<Uri>()
+            .map_err(Into::into)
+    }
+}
+
+impl_generate_config_from_default!(AzureMonitorMetricsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_monitor_metrics")]
+impl SinkConfig for AzureMonitorMetricsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.metrics_uri()?;
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(Some(tls_settings), &cx.proxy)?;
+
+        let credential = token_credential(self.client_credentials.as_ref())?;
+
+        let service =
+            AzureMonitorMetricsService::new(client, endpoint, credential, self.token_scope.clone());
+        let healthcheck = service.healthcheck();
+
+        let retry_logic =
+            HttpStatusRetryLogic::new(|res: &AzureMonitorMetricsResponse| res.http_status);
+        let request_settings = self.request.into_settings();
+        let service = ServiceBuilder::new()
+            .settings(request_settings, retry_logic)
+            .service(service);
+
+        let request_builder = AzureMonitorMetricsRequestBuilder {
+            encoder: AzureMonitorMetricsEncoder,
+        };
+        let sink = AzureMonitorMetricsSink::new(
+            service,
+            batch_settings,
+            request_builder,
+            self.default_namespace.clone(),
+        );
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
@@ -0,0 +1,15 @@
+//! The Azure Monitor metrics [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s and publishing the metrics among them as
+//! custom metrics of an Azure resource.
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AzureMonitorMetricsConfig;
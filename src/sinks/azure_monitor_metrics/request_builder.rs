@@ -0,0 +1,216 @@
+use std::{collections::BTreeMap, io};
+
+use bytes::Bytes;
+use chrono::{DateTime, DurationRound, SecondsFormat, TimeDelta, Utc};
+use serde_json::json;
+use vector_lib::event::{Metric, MetricValue};
+
+use crate::sinks::prelude::*;
+
+use super::service::AzureMonitorMetricsRequest;
+
+/// The most dimensions a custom metric can have. Further tags are left out, in the order of their
+/// names.
+pub(super) const MAX_DIMENSIONS: usize = 10;
+
+/// Identifies the custom metric document that a metric is published in.
+///
+/// A document holds the series of a single metric for a single minute, all of which must have the
+/// same dimensions.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct MetricDocumentKey {
+    pub(super) namespace: String,
+    pub(super) name: String,
+    pub(super) time: DateTime<Utc>,
+    pub(super) dim_names: Vec<String>,
+}
+
+impl MetricDocumentKey {
+    pub(super) fn new(metric: &Metric, default_namespace: &str) -> Self {
+        let timestamp = metric.timestamp().unwrap_or_else(Utc::now);
+
+        Self {
+            namespace: metric.namespace().unwrap_or(default_namespace).to_string(),
+            name: metric.name().to_string(),
+            // Custom metrics are aggregated per minute.
+            time: timestamp
+                .duration_trunc(TimeDelta::minutes(1))
+                .unwrap_or(timestamp),
+            dim_names: dimensions(metric)
+                .map(|(name, _)| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+fn dimensions(metric: &Metric) -> impl Iterator<Item = (&str, &str)> {
+    metric
+        .tags()
+        .into_iter()
+        .flat_map(|tags| tags.iter_single())
+        .take(MAX_DIMENSIONS)
+}
+
+/// The minimum, maximum, sum and count of the values of a series within a minute.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct SeriesAggregate {
+    pub(super) min: f64,
+    pub(super) max: f64,
+    pub(super) sum: f64,
+    pub(super) count: u64,
+}
+
+impl SeriesAggregate {
+    fn from_value(value: &MetricValue) -> Option<Self> {
+        match value {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => Some(Self {
+                min: *value,
+                max: *value,
+                sum: *value,
+                count: 1,
+            }),
+            MetricValue::Set { values } => {
+                let value = values.len() as f64;
+                Some(Self {
+                    min: value,
+                    max: value,
+                    sum: value,
+                    count: 1,
+                })
+            }
+            MetricValue::Distribution { samples, .. } => samples
+                .iter()
+                .filter(|sample| sample.rate > 0)
+                .map(|sample| Self {
+                    min: sample.value,
+                    max: sample.value,
+                    sum: sample.value * f64::from(sample.rate),
+                    count: u64::from(sample.rate),
+                })
+                .reduce(Self::merge),
+            _ => None,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+            sum: self.sum + other.sum,
+            count: self.count + other.count,
+        }
+    }
+}
+
+/// Aggregates the metrics of a document into one series per combination of dimension values.
+pub(super) fn aggregate_series(metrics: &[Metric]) -> BTreeMap<Vec<String>, SeriesAggregate> {
+    let mut series = BTreeMap::<Vec<String>, SeriesAggregate>::new();
+    for metric in metrics {
+        let Some(aggregate) = SeriesAggregate::from_value(metric.value()) else {
+            continue;
+        };
+        let dim_values = dimensions(metric)
+            .map(|(_, value)| value.to_string())
+            .collect();
+        series
+            .entry(dim_values)
+            .and_modify(|existing| *existing = existing.merge(aggregate))
+            .or_insert(aggregate);
+    }
+    series
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureMonitorMetricsEncoder;
+
+impl encoding::Encoder<(MetricDocumentKey, Vec<Metric>)> for AzureMonitorMetricsEncoder {
+    /// Create the custom metric document defined [here][api_docs].
+    ///
+    /// [api_docs]: https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/metrics-store-custom-rest-api
+    fn encode_input(
+        &self,
+        input: (MetricDocumentKey, Vec<Metric>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let (key, metrics) = input;
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for metric in &metrics {
+            byte_size.add_event(metric, metric.estimated_json_encoded_size_of());
+        }
+
+        let series = aggregate_series(&metrics)
+            .into_iter()
+            .map(|(dim_values, aggregate)| {
+                json!({
+                    "dimValues": dim_values,
+                    "min": aggregate.min,
+                    "max": aggregate.max,
+                    "sum": aggregate.sum,
+                    "count": aggregate.count,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let document = json!({
+            "time": key.time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "data": {
+                "baseData": {
+                    "metric": key.name,
+                    "namespace": key.namespace,
+                    "dimNames": key.dim_names,
+                    "series": series,
+                }
+            }
+        });
+
+        let body = serde_json::to_vec(&document)?;
+        write_all(writer, metrics.len(), &body)?;
+        Ok((body.len(), byte_size))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AzureMonitorMetricsRequestBuilder {
+    pub(super) encoder: AzureMonitorMetricsEncoder,
+}
+
+impl RequestBuilder<(MetricDocumentKey, Vec<Metric>)> for AzureMonitorMetricsRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = (MetricDocumentKey, Vec<Metric>);
+    type Encoder = AzureMonitorMetricsEncoder;
+    type Payload = Bytes;
+    type Request = AzureMonitorMetricsRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (MetricDocumentKey, Vec<Metric>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut metrics) = input;
+        let finalizers = metrics.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&metrics);
+        (finalizers, builder, (key, metrics))
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        AzureMonitorMetricsRequest {
+            body: payload.into_payload(),
+            finalizers,
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::{Arc, LazyLock};
+use std::task::{Context, Poll};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{
+    header::{self, HeaderMap},
+    HeaderValue, Request, StatusCode, Uri,
+};
+use hyper::Body;
+use tracing::Instrument;
+
+use crate::{http::HttpClient, sinks::prelude::*};
+
+static CONTENT_TYPE_VALUE: LazyLock<HeaderValue> =
+    LazyLock::new(|| HeaderValue::from_static("application/json"));
+
+#[derive(Debug, Clone)]
+pub struct AzureMonitorMetricsRequest {
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl MetaDescriptive for AzureMonitorMetricsRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+impl Finalizable for AzureMonitorMetricsRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct AzureMonitorMetricsResponse {
+    pub http_status: StatusCode,
+    pub events_byte_size: GroupedCountByteSize,
+    pub raw_byte_size: usize,
+}
+
+impl DriverResponse for AzureMonitorMetricsResponse {
+    fn event_status(&self) -> EventStatus {
+        match self.http_status.is_success() {
+            true => EventStatus::Delivered,
+            false => EventStatus::Rejected,
+        }
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.raw_byte_size)
+    }
+}
+
+/// `AzureMonitorMetricsService` is a `Tower` service used to publish custom metrics of a resource.
+#[derive(Clone)]
+pub struct AzureMonitorMetricsService {
+    client: HttpClient,
+    endpoint: Uri,
+    credential: Arc<dyn TokenCredential>,
+    token_scope: String,
+}
+
+impl AzureMonitorMetricsService {
+    /// Creates a new `AzureMonitorMetricsService`.
+    pub fn new(
+        client: HttpClient,
+        endpoint: Uri,
+        credential: Arc<dyn TokenCredential>,
+        token_scope: String,
+    ) -> Self {
+        Self {
+            client,
+            endpoint,
+            credential,
+            token_scope,
+        }
+    }
+
+    async fn build_request(&self, body: Bytes) -> crate::Result<Request<Body>> {
+        let token = self.credential.get_token(&self.token_scope).await?;
+
+        let mut request = Request::post(&self.endpoint).body(Body::from(body))?;
+        request
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, CONTENT_TYPE_VALUE.clone());
+        request.headers_mut().insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token.token.secret()).parse()?,
+        );
+
+        Ok(request)
+    }
+
+    pub fn healthcheck(&self) -> Healthcheck {
+        let service = self.clone();
+        Box::pin(async move {
+            // An empty document is rejected as invalid, but only once the request is authorized
+            // for the resource.
+            let request = service.build_request(Bytes::from("{}")).await?;
+            let res = service.client.send(request).in_current_span().await?;
+
+            if res.status().is_server_error() {
+                return Err("Server returned a server error".into());
+            }
+
+            if res.status() == StatusCode::UNAUTHORIZED || res.status() == StatusCode::FORBIDDEN {
+                return Err("The service failed to authorize the request. Verify that the identity has the Monitoring Metrics Publisher role on the resource".into());
+            }
+
+            if res.status() == StatusCode::NOT_FOUND {
+                return Err(
+                    "Either the region is incorrect, or the resource does not exist".into(),
+                );
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl Service<AzureMonitorMetricsRequest> for AzureMonitorMetricsService {
+    type Response = AzureMonitorMetricsResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of Error internal event is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of Error internal event is handled upstream by the caller.
+    fn call(&mut self, request: AzureMonitorMetricsRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let http_request = service.build_request(request.body).await?;
+            let response = service.client.send(http_request).in_current_span().await?;
+            Ok(AzureMonitorMetricsResponse {
+                http_status: response.status(),
+                raw_byte_size: request.metadata.request_encoded_size(),
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use vector_lib::event::{Metric, MetricValue};
+
+use crate::{
+    internal_events::AzureMonitorMetricsUnsupportedMetricError,
+    sinks::{
+        prelude::*,
+        util::buffer::metrics::{MetricNormalize, MetricSet},
+    },
+};
+
+use super::{
+    request_builder::{AzureMonitorMetricsRequestBuilder, MetricDocumentKey},
+    service::AzureMonitorMetricsRequest,
+};
+
+#[derive(Clone, Debug, Default)]
+struct AzureMonitorMetricsNormalize;
+
+impl MetricNormalize for AzureMonitorMetricsNormalize {
+    fn normalize(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
+        // Custom metrics are aggregated per minute, so counters and distributions are published
+        // as the changes since they were last seen.
+        match metric.value() {
+            MetricValue::Gauge { .. } => state.make_absolute(metric),
+            _ => state.make_incremental(metric),
+        }
+    }
+}
+
+/// Partitions metrics by the custom metric document that they are published in.
+struct MetricDocumentPartitioner {
+    default_namespace: String,
+}
+
+impl Partitioner for MetricDocumentPartitioner {
+    type Item = Metric;
+    type Key = MetricDocumentKey;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        MetricDocumentKey::new(item, &self.default_namespace)
+    }
+}
+
+pub(super) struct AzureMonitorMetricsSink<S> {
+    service: S,
+    batch_settings: BatcherSettings,
+    request_builder: AzureMonitorMetricsRequestBuilder,
+    default_namespace: String,
+}
+
+impl<S> AzureMonitorMetricsSink<S>
+where
+    S: Service<AzureMonitorMetricsRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    /// Creates a new `AzureMonitorMetricsSink`.
+    pub(super) const fn new(
+        service: S,
+        batch_settings: BatcherSettings,
+        request_builder: AzureMonitorMetricsRequestBuilder,
+        default_namespace: String,
+    ) -> Self {
+        Self {
+            service,
+            batch_settings,
+            request_builder,
+            default_namespace,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let partitioner = MetricDocumentPartitioner {
+            default_namespace: self.default_namespace,
+        };
+
+        input
+            .filter_map(|event| {
+                // Filter out anything that can't be summarized as a minimum, maximum, sum and
+                // count.
+                let metric = event.into_metric();
+
+                future::ready(match metric.value() {
+                    &MetricValue::Counter { .. }
+                    | &MetricValue::Gauge { .. }
+                    | &MetricValue::Set { .. }
+                    | &MetricValue::Distribution { .. } => Some(metric),
+                    not_supported => {
+                        emit!(AzureMonitorMetricsUnsupportedMetricError {
+                            metric_type: not_supported.as_name(),
+                        });
+                        None
+                    }
+                })
+            })
+            .normalized_with_default::<AzureMonitorMetricsNormalize>()
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for AzureMonitorMetricsSink<S>
+where
+    S: Service<AzureMonitorMetricsRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(
+        self: Box<Self>,
+        input: futures_util::stream::BoxStream<'_, Event>,
+    ) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
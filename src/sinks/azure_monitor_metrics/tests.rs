@@ -0,0 +1,124 @@
+use chrono::{offset::TimeZone, Utc};
+use similar_asserts::assert_eq;
+use vector_lib::metric_tags;
+
+use super::{
+    config::AzureMonitorMetricsConfig,
+    request_builder::{aggregate_series, AzureMonitorMetricsEncoder, MetricDocumentKey},
+};
+use crate::{
+    event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
+    sinks::util::encoding::Encoder,
+};
+
+fn config(options: &str) -> AzureMonitorMetricsConfig {
+    toml::from_str::<AzureMonitorMetricsConfig>(&format!(
+        r#"
+            region = "eastus"
+            resource_id = "/subscriptions/sub/resourceGroups/group/providers/Microsoft.Compute/virtualMachines/vm"
+            {options}
+        "#
+    ))
+    .unwrap()
+}
+
+fn counter(value: f64, host: &str) -> Metric {
+    Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value },
+    )
+    .with_tags(Some(metric_tags!("host" => host)))
+    .with_timestamp(Some(Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 15).unwrap()))
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureMonitorMetricsConfig>();
+}
+
+#[test]
+fn correct_metrics_uri() {
+    let config = config("");
+    assert_eq!(
+        config.metrics_uri().unwrap().to_string(),
+        "https://eastus.monitoring.azure.com/subscriptions/sub/resourceGroups/group/providers/Microsoft.Compute/virtualMachines/vm/metrics"
+    );
+    assert_eq!(config.token_scope, "https://monitoring.azure.com");
+    assert_eq!(config.default_namespace, "vector");
+
+    let config = config(r#"endpoint = "https://usgovvirginia.monitoring.azure.us/""#);
+    assert_eq!(
+        config.metrics_uri().unwrap().to_string(),
+        "https://usgovvirginia.monitoring.azure.us/subscriptions/sub/resourceGroups/group/providers/Microsoft.Compute/virtualMachines/vm/metrics"
+    );
+}
+
+#[test]
+fn document_key_truncates_time_to_the_minute() {
+    let key = MetricDocumentKey::new(&counter(1.0, "a").with_namespace(Some("app")), "vector");
+
+    assert_eq!(key.namespace, "app");
+    assert_eq!(key.name, "requests");
+    assert_eq!(
+        key.time,
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 0).unwrap()
+    );
+    assert_eq!(key.dim_names, vec!["host".to_string()]);
+
+    let key = MetricDocumentKey::new(&counter(1.0, "a"), "vector");
+    assert_eq!(key.namespace, "vector");
+}
+
+#[test]
+fn series_are_aggregated_per_dimension_values() {
+    let distribution = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Distribution {
+            samples: vector_lib::samples![0.5 => 2, 4.0 => 1],
+            statistic: StatisticKind::Histogram,
+        },
+    )
+    .with_tags(Some(metric_tags!("host" => "a")));
+
+    let series = aggregate_series(&[counter(3.0, "a"), counter(2.0, "b"), distribution]);
+
+    assert_eq!(series.len(), 2);
+    let a = series[&vec!["a".to_string()]];
+    assert_eq!((a.min, a.max, a.sum, a.count), (0.5, 4.0, 8.0, 4));
+    let b = series[&vec!["b".to_string()]];
+    assert_eq!((b.min, b.max, b.sum, b.count), (2.0, 2.0, 2.0, 1));
+}
+
+#[test]
+fn encode_document() {
+    let metrics = vec![counter(3.0, "a"), counter(2.0, "a")];
+    let key = MetricDocumentKey::new(&metrics[0], "vector");
+
+    let mut body = Vec::new();
+    AzureMonitorMetricsEncoder
+        .encode_input((key, metrics), &mut body)
+        .unwrap();
+
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        serde_json::json!({
+            "time": "2024-01-01T12:30:00Z",
+            "data": {
+                "baseData": {
+                    "metric": "requests",
+                    "namespace": "vector",
+                    "dimNames": ["host"],
+                    "series": [{
+                        "dimValues": ["a"],
+                        "min": 2.0,
+                        "max": 3.0,
+                        "sum": 5.0,
+                        "count": 2,
+                    }],
+                }
+            }
+        })
+    );
+}
@@ -0,0 +1,69 @@
+//! Batch settings for the `azure_service_bus` sink.
+
+use vector_lib::event::Event;
+use vector_lib::stream::batcher::limiter::ItemBatchSize;
+
+use super::encoder::ServiceBusEncoder;
+
+/// Sizes events as the messages they are sent as, envelope included, so that batches stay below
+/// the size limit of the service.
+pub(super) struct ServiceBusBatchSizer {
+    pub(super) encoder: ServiceBusEncoder,
+}
+
+impl ItemBatchSize<Event> for ServiceBusBatchSizer {
+    fn size(&self, item: &Event) -> usize {
+        // Events that are dropped, or that fail to encode, are reported when the request is built,
+        // and take no room in the batch.
+        let Ok(session_id) = self.encoder.session_id(item) else {
+            return 0;
+        };
+        self.encoder
+            .build_message(item.clone(), session_id)
+            .ok()
+            .and_then(|message| serde_json::to_vec(&message).ok())
+            // Each message is followed by a comma, or by the closing bracket of the batch.
+            .map_or(0, |message| message.len() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::codecs::{NativeSerializerConfig, TextSerializerConfig};
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+    use crate::codecs::Encoder;
+
+    fn sizer(encoder: Encoder<()>) -> ServiceBusBatchSizer {
+        ServiceBusBatchSizer {
+            encoder: ServiceBusEncoder {
+                transformer: Default::default(),
+                encoder,
+                session_id: None,
+                scheduled_enqueue_time_key: None,
+            },
+        }
+    }
+
+    #[test]
+    fn sizes_encoded_messages() {
+        // Quotes are escaped in the JSON body of the message.
+        let event = Event::Log(LogEvent::from("\"\"\"\""));
+
+        let text = sizer(Encoder::<()>::new(
+            TextSerializerConfig::default().build().into(),
+        ));
+        assert_eq!(
+            text.size(&event),
+            r#"{"Body":"\"\"\"\"","BrokerProperties":{}},"#.len()
+        );
+
+        let native = sizer(Encoder::<()>::new(NativeSerializerConfig.build().into()));
+        let message = native.encoder.build_message(event.clone(), None).unwrap();
+        assert_eq!(
+            native.size(&event),
+            serde_json::to_vec(&message).unwrap().len() + 1
+        );
+    }
+}
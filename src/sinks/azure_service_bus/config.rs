@@ -0,0 +1,236 @@
+//! Configuration for the `azure_service_bus` sink.
+
+use futures::FutureExt;
+use http::{StatusCode, Uri};
+use vector_lib::codecs::TextSerializerConfig;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    azure_connection_string::{ConnectionString, ConnectionStringError},
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::http::{http_response_retry_logic, HttpService},
+    },
+};
+
+use super::{
+    encoder::ServiceBusEncoder, request_builder::ServiceBusRequestBuilder,
+    service::ServiceBusSvcRequestBuilder, sink::ServiceBusSink,
+};
+
+/// The largest batch that the Standard tier accepts in a single send.
+const MAX_BATCH_SIZE: usize = 256 * 1024;
+
+/// Configuration for the `azure_service_bus` sink.
+#[configurable_component(sink(
+    "azure_service_bus",
+    "Send events as messages to Azure Service Bus queues and topics."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureServiceBusConfig {
+    /// The connection string of the Service Bus namespace or of the queue or topic itself.
+    ///
+    /// It must contain the `Endpoint`, `SharedAccessKeyName`, and `SharedAccessKey` properties,
+    /// which are used to sign requests with a [shared access signature][sas].
+    ///
+    /// [sas]: https://learn.microsoft.com/en-us/azure/service-bus-messaging/service-bus-sas
+    #[configurable(metadata(
+        docs::examples = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${SERVICE_BUS_KEY};EntityPath=orders"
+    ))]
+    pub(super) connection_string: SensitiveString,
+
+    /// The name of the queue or topic to send messages to.
+    ///
+    /// Required if the connection string does not contain an `EntityPath`.
+    #[configurable(metadata(docs::examples = "orders"))]
+    pub(super) queue_or_topic: Option<String>,
+
+    /// The session ID to set on each message.
+    ///
+    /// Messages that share a session ID are delivered in order to a single session receiver. This
+    /// must be set when sending to a queue or topic that requires sessions.
+    #[configurable(metadata(docs::examples = "{{ customer_id }}"))]
+    pub(super) session_id: Option<Template>,
+
+    /// The log field that holds the time at which each message is enqueued.
+    ///
+    /// Messages whose field holds a timestamp in the future are [scheduled][scheduled] and only
+    /// become visible to receivers at that time. Messages without the field are enqueued
+    /// immediately.
+    ///
+    /// [scheduled]: https://learn.microsoft.com/en-us/azure/service-bus-messaging/message-sequencing#scheduled-messages
+    #[configurable(metadata(docs::examples = "deliver_at"))]
+    pub(super) scheduled_enqueue_time_key: Option<ConfigValuePath>,
+
+    #[configurable(derived)]
+    pub(super) encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) batch: BatchConfig<ServiceBusDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct ServiceBusDefaultBatchSettings;
+
+impl SinkBatchSettings for ServiceBusDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+impl GenerateConfig for AzureServiceBusConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${SERVICE_BUS_KEY};EntityPath=orders".to_string().into(),
+            queue_or_topic: None,
+            session_id: None,
+            scheduled_enqueue_time_key: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: Default::default(),
+            request: Default::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_service_bus")]
+impl SinkConfig for AzureServiceBusConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connection = ServiceBusConnection::parse(
+            self.connection_string.inner(),
+            self.queue_or_topic.as_deref(),
+        )?;
+
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_SIZE)?
+            .into_batcher_settings()?;
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let request_builder = ServiceBusRequestBuilder {
+            encoder: ServiceBusEncoder {
+                transformer,
+                encoder: Encoder::<()>::new(serializer),
+                session_id: self.session_id.clone(),
+                scheduled_enqueue_time_key: self.scheduled_enqueue_time_key.clone(),
+            },
+        };
+
+        let client = HttpClient::new(None, cx.proxy())?;
+
+        let service_request_builder = ServiceBusSvcRequestBuilder {
+            connection: connection.clone(),
+        };
+        let service = HttpService::new(client.clone(), service_request_builder.clone());
+
+        let request_limits = self.request.into_settings();
+        let service = ServiceBuilder::new()
+            .settings(request_limits, http_response_retry_logic())
+            .service(service);
+
+        let sink = ServiceBusSink::new(service, batch_settings, request_builder);
+
+        let healthcheck = healthcheck(service_request_builder, client).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// The parts of a Service Bus connection string needed to send messages to a queue or topic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct ServiceBusConnection {
+    pub(super) namespace_host: String,
+    pub(super) key_name: String,
+    pub(super) key: SensitiveString,
+    pub(super) entity: String,
+}
+
+impl ServiceBusConnection {
+    /// Parses a Service Bus connection string.
+    ///
+    /// The queue or topic is `queue_or_topic` if set, and the `EntityPath` of the connection
+    /// string otherwise.
+    pub(super) fn parse(
+        connection_string: &str,
+        queue_or_topic: Option<&str>,
+    ) -> Result<Self, ConnectionStringError> {
+        let connection_string = ConnectionString::parse(connection_string);
+        let namespace_host = connection_string.namespace_host()?;
+        let key_name = connection_string.property("SharedAccessKeyName")?;
+        let key = connection_string.property("SharedAccessKey")?;
+        let entity =
+            connection_string.entity(queue_or_topic, "Queue or topic", "queue_or_topic")?;
+
+        Ok(Self {
+            namespace_host: namespace_host.to_string(),
+            key_name: key_name.to_string(),
+            key: key.to_string().into(),
+            entity: entity.to_string(),
+        })
+    }
+
+    /// The URI of the queue or topic, which is also the resource that requests are signed for.
+    pub(super) fn resource_uri(&self) -> String {
+        format!("https://{}/{}", self.namespace_host, self.entity)
+    }
+
+    /// The URI that messages are sent to.
+    pub(super) fn messages_uri(&self) -> crate::Result<Uri> {
+        format!("{}/messages", self.resource_uri())
+            .parse::<Uri>()
+            .map_err(Into::into)
+    }
+}
+
+/// Checks that the queue or topic exists and that the credentials are allowed to send to it by
+/// sending an empty batch. The service rejects the empty batch itself with a `400` only after the
+/// request has been authorized, so that response is healthy too.
+async fn healthcheck(
+    request_builder: ServiceBusSvcRequestBuilder,
+    client: HttpClient,
+) -> crate::Result<()> {
+    let request = request_builder.build_request(bytes::Bytes::from_static(b"[]"))?;
+    let response = client.send(request.map(hyper::Body::from)).await?;
+
+    match response.status() {
+        status if status.is_success() || status == StatusCode::BAD_REQUEST => Ok(()),
+        StatusCode::UNAUTHORIZED => {
+            Err("The shared access signature was rejected, verify the connection string".into())
+        }
+        StatusCode::NOT_FOUND => Err(format!(
+            "The queue or topic {:?} does not exist",
+            request_builder.connection.entity
+        )
+        .into()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
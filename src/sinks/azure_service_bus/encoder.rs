@@ -0,0 +1,291 @@
+//! Encoding for the `azure_service_bus` sink.
+
+use std::{collections::BTreeMap, io};
+
+use bytes::BytesMut;
+use openssl::base64;
+use serde::Serialize;
+use serde_json::to_vec;
+use tokio_util::codec::Encoder as _;
+use vector_lib::codecs::encoding::Serializer;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use crate::sinks::{prelude::*, util::encoding::Encoder as SinkEncoder};
+
+/// The format of the RFC 2616 dates that Service Bus expects in broker properties.
+const BROKER_PROPERTY_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// The user property that marks message bodies sent base64 encoded.
+const CONTENT_ENCODING_PROPERTY: &str = "ContentEncoding";
+
+/// Whether the serializer produces binary message bodies.
+///
+/// The batch format only carries text bodies, so binary bodies are sent base64 encoded.
+const fn is_binary(serializer: &Serializer) -> bool {
+    matches!(
+        serializer,
+        Serializer::Avro(_) | Serializer::Native(_) | Serializer::Protobuf(_)
+    )
+}
+
+/// A message in the [batch format][batch] of the Service Bus REST API.
+///
+/// [batch]: https://learn.microsoft.com/en-us/rest/api/servicebus/send-message-batch
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct ServiceBusMessage {
+    body: String,
+    broker_properties: BrokerProperties,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    user_properties: BTreeMap<&'static str, &'static str>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct BrokerProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_enqueue_time_utc: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct ServiceBusEncoder {
+    pub(super) transformer: Transformer,
+    pub(super) encoder: Encoder<()>,
+    pub(super) session_id: Option<Template>,
+    pub(super) scheduled_enqueue_time_key: Option<ConfigValuePath>,
+}
+
+impl ServiceBusEncoder {
+    /// Renders the session ID of an event, if one is configured.
+    pub(super) fn session_id(
+        &self,
+        event: &Event,
+    ) -> Result<Option<String>, crate::template::TemplateRenderingError> {
+        self.session_id
+            .as_ref()
+            .map(|template| template.render_string(event))
+            .transpose()
+    }
+
+    /// Builds the message for an event, with the session ID rendered for it.
+    pub(super) fn build_message(
+        &self,
+        mut event: Event,
+        session_id: Option<String>,
+    ) -> io::Result<ServiceBusMessage> {
+        // The broker properties are read before transforming the event, so that they can be
+        // taken from fields that are excluded from the message body.
+        let scheduled_enqueue_time_utc = self
+            .scheduled_enqueue_time_key
+            .as_ref()
+            .and_then(|key| event.as_log().get((PathPrefix::Event, &key.0)))
+            .and_then(Value::as_timestamp)
+            .map(|timestamp| timestamp.format(BROKER_PROPERTY_DATE_FORMAT).to_string());
+
+        self.transformer.transform(&mut event);
+
+        let mut body = BytesMut::new();
+        self.encoder
+            .clone()
+            .serialize(event, &mut body)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        // Text codecs can still produce invalid UTF-8, for example `raw_message` with a binary
+        // message, which is sent base64 encoded rather than mangled.
+        let body = if is_binary(self.encoder.serializer()) {
+            Err(body.to_vec())
+        } else {
+            String::from_utf8(body.to_vec()).map_err(|error| error.into_bytes())
+        };
+        let mut user_properties = BTreeMap::new();
+        let body = body.unwrap_or_else(|body| {
+            user_properties.insert(CONTENT_ENCODING_PROPERTY, "base64");
+            base64::encode_block(&body)
+        });
+
+        Ok(ServiceBusMessage {
+            body,
+            broker_properties: BrokerProperties {
+                session_id,
+                scheduled_enqueue_time_utc,
+            },
+            user_properties,
+        })
+    }
+
+    /// Builds the message for an event, or `None` if the event is dropped because its session ID
+    /// could not be rendered.
+    fn encode_event(&self, event: Event) -> io::Result<Option<ServiceBusMessage>> {
+        match self.session_id(&event) {
+            Ok(session_id) => self.build_message(event, session_id).map(Some),
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("session_id"),
+                    drop_event: true,
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl SinkEncoder<Vec<Event>> for ServiceBusEncoder {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut n_events = events.len();
+
+        let mut messages = Vec::with_capacity(n_events);
+        for event in &events {
+            let size = event.estimated_json_encoded_size_of();
+            if let Some(message) = self.encode_event(event.clone())? {
+                byte_size.add_event(event, size);
+                messages.push(message);
+            } else {
+                // encode_event() emits the `TemplateRenderingError` internal event,
+                // which emits an `EventsDropped`, so no need to here.
+                n_events -= 1;
+            }
+        }
+
+        let body = to_vec(&messages)?;
+
+        write_all(writer, n_events, &body).map(|()| (body.len(), byte_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_lib::codecs::{
+        NativeSerializerConfig, RawMessageSerializerConfig, TextSerializerConfig,
+    };
+
+    use super::*;
+
+    fn encoder(
+        session_id: Option<&str>,
+        scheduled_enqueue_time_key: Option<&str>,
+    ) -> ServiceBusEncoder {
+        ServiceBusEncoder {
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+            session_id: session_id.map(|template| Template::try_from(template).unwrap()),
+            scheduled_enqueue_time_key: scheduled_enqueue_time_key
+                .map(|key| ConfigValuePath::try_from(key.to_owned()).unwrap()),
+        }
+    }
+
+    fn encode(encoder: &ServiceBusEncoder, events: Vec<Event>) -> serde_json::Value {
+        let mut body = Vec::new();
+        encoder.encode_input(events, &mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[test]
+    fn encodes_batch_of_messages() {
+        let body = encode(
+            &encoder(None, None),
+            vec![
+                Event::Log(LogEvent::from("first")),
+                Event::Log(LogEvent::from("second")),
+            ],
+        );
+
+        assert_eq!(
+            body,
+            serde_json::json!([
+                { "Body": "first", "BrokerProperties": {} },
+                { "Body": "second", "BrokerProperties": {} },
+            ])
+        );
+    }
+
+    #[test]
+    fn sets_session_id_and_scheduled_enqueue_time() {
+        let mut log = LogEvent::from("hello");
+        log.insert("customer", "acme");
+        log.insert(
+            "deliver_at",
+            Utc.with_ymd_and_hms(2024, 11, 6, 8, 49, 37).unwrap(),
+        );
+
+        let body = encode(
+            &encoder(Some("{{ customer }}"), Some("deliver_at")),
+            vec![Event::Log(log)],
+        );
+
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "Body": "hello",
+                "BrokerProperties": {
+                    "SessionId": "acme",
+                    "ScheduledEnqueueTimeUtc": "Wed, 06 Nov 2024 08:49:37 GMT",
+                },
+            }])
+        );
+    }
+
+    #[test]
+    fn drops_events_without_session_id() {
+        let body = encode(
+            &encoder(Some("{{ customer }}"), None),
+            vec![Event::Log(LogEvent::from("hello"))],
+        );
+
+        assert_eq!(body, serde_json::json!([]));
+    }
+
+    #[test]
+    fn encodes_binary_bodies_as_base64() {
+        let serializer: Serializer = NativeSerializerConfig.build().into();
+        let encoder = ServiceBusEncoder {
+            encoder: Encoder::<()>::new(serializer.clone()),
+            ..encoder(None, None)
+        };
+        let event = Event::Log(LogEvent::from("hello"));
+        let mut native = BytesMut::new();
+        Encoder::<()>::new(serializer)
+            .serialize(event.clone(), &mut native)
+            .unwrap();
+
+        let body = encode(&encoder, vec![event]);
+
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "Body": base64::encode_block(&native),
+                "BrokerProperties": {},
+                "UserProperties": { "ContentEncoding": "base64" },
+            }])
+        );
+    }
+
+    #[test]
+    fn encodes_invalid_utf8_bodies_as_base64() {
+        let encoder = ServiceBusEncoder {
+            encoder: Encoder::<()>::new(RawMessageSerializerConfig.build().into()),
+            ..encoder(None, None)
+        };
+        let log = LogEvent::from(bytes::Bytes::from_static(&[0xff, 0xfe]));
+
+        let body = encode(&encoder, vec![Event::Log(log)]);
+
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "Body": "//4=",
+                "BrokerProperties": {},
+                "UserProperties": { "ContentEncoding": "base64" },
+            }])
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! The Azure Service Bus [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s and sending them as messages to an Azure
+//! Service Bus queue or topic.
+
+mod batch;
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AzureServiceBusConfig;
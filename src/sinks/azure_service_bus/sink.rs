@@ -0,0 +1,80 @@
+//! Implementation of the `azure_service_bus` sink.
+
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+use super::{batch::ServiceBusBatchSizer, request_builder::ServiceBusRequestBuilder};
+
+pub(super) struct ServiceBusSink<S> {
+    service: S,
+    batch_settings: BatcherSettings,
+    request_builder: ServiceBusRequestBuilder,
+}
+
+impl<S> ServiceBusSink<S>
+where
+    S: Service<HttpRequest<()>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    /// Creates a new `ServiceBusSink`.
+    pub(super) const fn new(
+        service: S,
+        batch_settings: BatcherSettings,
+        request_builder: ServiceBusRequestBuilder,
+    ) -> Self {
+        Self {
+            service,
+            batch_settings,
+            request_builder,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            // Batch the input stream with size calculation based on the size of the encoded
+            // messages, envelope included
+            .batched(
+                self.batch_settings
+                    .as_item_size_config(ServiceBusBatchSizer {
+                        encoder: self.request_builder.encoder.clone(),
+                    }),
+            )
+            // Build requests with default concurrency limit.
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            // Filter out any errors that occurred in the request building.
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            // Generate the driver that will send requests and handle retries,
+            // event finalization, and logging/internal metric reporting.
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for ServiceBusSink<S>
+where
+    S: Service<HttpRequest<()>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(
+        self: Box<Self>,
+        input: futures_util::stream::BoxStream<'_, Event>,
+    ) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,71 @@
+//! Unit tests for the `azure_service_bus` sink.
+
+use super::config::{AzureServiceBusConfig, ServiceBusConnection};
+use super::service::ServiceBusSvcRequestBuilder;
+use crate::azure_connection_string::ConnectionStringError;
+
+const CONNECTION_STRING: &str = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret;EntityPath=orders";
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureServiceBusConfig>();
+}
+
+#[test]
+fn parse_connection_string() {
+    let connection = ServiceBusConnection::parse(CONNECTION_STRING, None).unwrap();
+
+    assert_eq!(
+        connection.namespace_host,
+        "mynamespace.servicebus.windows.net"
+    );
+    assert_eq!(connection.key_name, "vector");
+    assert_eq!(connection.key.inner(), "secret");
+    assert_eq!(connection.entity, "orders");
+    assert_eq!(
+        connection.messages_uri().unwrap().to_string(),
+        "https://mynamespace.servicebus.windows.net/orders/messages"
+    );
+
+    let connection = ServiceBusConnection::parse(CONNECTION_STRING, Some("invoices")).unwrap();
+    assert_eq!(connection.entity, "invoices");
+}
+
+#[test]
+fn parse_connection_string_errors() {
+    assert_eq!(
+        ServiceBusConnection::parse(
+            "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector",
+            Some("orders")
+        ),
+        Err(ConnectionStringError::MissingProperty {
+            property: "SharedAccessKey"
+        })
+    );
+    assert_eq!(
+        ServiceBusConnection::parse(
+            "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret",
+            None
+        ),
+        Err(ConnectionStringError::MissingEntity {
+            entity: "Queue or topic",
+            option: "queue_or_topic"
+        })
+    );
+}
+
+#[test]
+fn shared_access_signature() {
+    let request_builder = ServiceBusSvcRequestBuilder {
+        connection: ServiceBusConnection::parse(CONNECTION_STRING, None).unwrap(),
+    };
+
+    let signature = request_builder
+        .shared_access_signature(1_700_000_000)
+        .unwrap();
+
+    assert!(signature.starts_with(
+        "SharedAccessSignature sr=https%3A%2F%2Fmynamespace%2Eservicebus%2Ewindows%2Enet%2Forders&sig="
+    ));
+    assert!(signature.ends_with("&se=1700003600&skn=vector"));
+}
@@ -0,0 +1,326 @@
+//! Configuration for the `azure_storage_queue` sink.
+
+use std::{sync::Arc, time::Duration};
+
+use azure_core::{error::HttpError, RetryOptions};
+use azure_storage::{prelude::*, CloudLocation, ConnectionString};
+use azure_storage_queues::{QueueClient, QueueServiceClientBuilder};
+use futures::FutureExt;
+use http::StatusCode;
+use snafu::Snafu;
+use vector_lib::codecs::TextSerializerConfig;
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    azure_credentials::{token_credential, ClientCredentials},
+    sinks::prelude::*,
+};
+
+use super::{
+    request_builder::{QueueMessageEncoder, QueueRequestBuilder},
+    service::{QueueRetryLogic, QueueService},
+    sink::QueueSink,
+};
+
+/// The longest time that a message can be kept on a queue before it expires, or be kept
+/// invisible after it's put on the queue.
+const MAX_MESSAGE_DURATION_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub(super) enum ConfigError {
+    #[snafu(display("`{}` must be at most {} seconds", option, MAX_MESSAGE_DURATION_SECS))]
+    DurationTooLong { option: &'static str },
+    #[snafu(display("`visibility_timeout_secs` must be shorter than `message_ttl_secs`"))]
+    VisibilityTimeoutExceedsTtl,
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum HealthcheckError {
+    #[snafu(display("Invalid connection string or credentials specified"))]
+    InvalidCredentials,
+    #[snafu(display("Queue: {:?} not found", queue))]
+    UnknownQueue { queue: String },
+    #[snafu(display("Unknown status code: {}", status))]
+    Unknown { status: StatusCode },
+}
+
+/// Configuration for the `azure_storage_queue` sink.
+#[configurable_component(sink(
+    "azure_storage_queue",
+    "Put events as messages on Azure Queue Storage queues."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureStorageQueueConfig {
+    /// The Azure Storage Account connection string.
+    ///
+    /// Authentication with access key is the only supported authentication method.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    #[configurable(metadata(
+        docs::examples = "DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"
+    ))]
+    #[configurable(metadata(
+        docs::examples = "QueueEndpoint=https://test.queue.core.usgovcloudapi.net/;SharedAccessSignature=sv=2020-08-04&ss=q&srt=so&sp=a&se=2021-01-01T00:00:00Z&st=2020-01-01T00:00:00Z&spr=https&sig=XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX"
+    ))]
+    pub(super) connection_string: Option<SensitiveString>,
+
+    /// The Azure Storage Account name.
+    ///
+    /// Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+    /// for the account in the following ways, in order:
+    ///
+    /// - read from environment variables ([more information][env_cred_docs])
+    /// - looks for a [Managed Identity][managed_ident_docs]
+    /// - uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    ///
+    /// [env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    /// [az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+    #[configurable(metadata(docs::examples = "mylogstorage"))]
+    pub(super) storage_account: Option<String>,
+
+    #[configurable(derived)]
+    pub(super) client_credentials: Option<ClientCredentials>,
+
+    /// The Queue Storage endpoint to use instead of the one derived from `storage_account`.
+    ///
+    /// This is only used together with `storage_account`. When using `connection_string`, the
+    /// endpoint is taken from its `QueueEndpoint` property instead.
+    #[configurable(metadata(docs::examples = "https://test.queue.core.usgovcloudapi.net/"))]
+    #[configurable(metadata(docs::examples = "https://test.queue.core.windows.net/"))]
+    pub(super) endpoint: Option<String>,
+
+    /// The name of the queue to put messages on.
+    #[configurable(metadata(docs::examples = "function-triggers"))]
+    pub(super) queue_name: String,
+
+    /// How long, in seconds, messages are kept on the queue before they expire.
+    ///
+    /// Defaults to the service default of seven days, which is also the maximum.
+    #[configurable(metadata(docs::examples = 3600))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) message_ttl_secs: Option<u64>,
+
+    /// How long, in seconds, messages stay invisible to consumers after being put on the queue.
+    ///
+    /// This delays the processing of messages, for example by the Azure Functions that are
+    /// triggered from the queue. Must be shorter than `message_ttl_secs`.
+    #[configurable(metadata(docs::examples = 30))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) visibility_timeout_secs: Option<u64>,
+
+    /// Whether to Base64-encode message contents.
+    ///
+    /// Queue messages must be valid UTF-8 text, and [Azure Functions queue triggers][functions]
+    /// expect Base64-encoded messages by default. Disable this only if consumers of the queue
+    /// read messages as plain text, in which case encoded events that aren't valid UTF-8 have
+    /// their invalid bytes replaced.
+    ///
+    /// [functions]: https://learn.microsoft.com/en-us/azure/azure-functions/functions-bindings-storage-queue#host-json
+    #[serde(default = "crate::serde::default_true")]
+    pub(super) base64_encode: bool,
+
+    #[configurable(derived)]
+    pub(super) encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub(super) acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureStorageQueueConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: Some(String::from("DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;").into()),
+            storage_account: None,
+            client_credentials: None,
+            endpoint: None,
+            queue_name: String::from("function-triggers"),
+            message_ttl_secs: None,
+            visibility_timeout_secs: None,
+            base64_encode: true,
+            encoding: TextSerializerConfig::default().into(),
+            request: Default::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_storage_queue")]
+impl SinkConfig for AzureStorageQueueConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let (message_ttl, visibility_timeout) = self.message_durations()?;
+
+        let client = build_client(
+            self.connection_string
+                .as_ref()
+                .map(|v| v.inner().to_string()),
+            self.storage_account.as_ref().map(|v| v.to_string()),
+            self.client_credentials.as_ref(),
+            self.queue_name.clone(),
+            self.endpoint.clone(),
+        )?;
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let request_builder = QueueRequestBuilder {
+            encoder: QueueMessageEncoder {
+                transformer,
+                encoder: Encoder::<()>::new(serializer),
+            },
+            base64_encode: self.base64_encode,
+        };
+
+        let request_settings = self.request.into_settings();
+        let service = ServiceBuilder::new()
+            .settings(request_settings, QueueRetryLogic)
+            .service(QueueService::new(
+                Arc::clone(&client),
+                message_ttl,
+                visibility_timeout,
+            ));
+
+        let sink = QueueSink::new(service, request_builder);
+        let healthcheck = healthcheck(self.queue_name.clone(), client).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl AzureStorageQueueConfig {
+    /// The time to live and visibility timeout set on each message, if configured.
+    pub(super) fn message_durations(
+        &self,
+    ) -> Result<(Option<Duration>, Option<Duration>), ConfigError> {
+        if self
+            .message_ttl_secs
+            .is_some_and(|ttl| ttl > MAX_MESSAGE_DURATION_SECS)
+        {
+            return Err(ConfigError::DurationTooLong {
+                option: "message_ttl_secs",
+            });
+        }
+        if self
+            .visibility_timeout_secs
+            .is_some_and(|timeout| timeout > MAX_MESSAGE_DURATION_SECS)
+        {
+            return Err(ConfigError::DurationTooLong {
+                option: "visibility_timeout_secs",
+            });
+        }
+
+        let ttl = self.message_ttl_secs.unwrap_or(MAX_MESSAGE_DURATION_SECS);
+        if self
+            .visibility_timeout_secs
+            .is_some_and(|timeout| timeout >= ttl)
+        {
+            return Err(ConfigError::VisibilityTimeoutExceedsTtl);
+        }
+
+        Ok((
+            self.message_ttl_secs.map(Duration::from_secs),
+            self.visibility_timeout_secs.map(Duration::from_secs),
+        ))
+    }
+}
+
+pub(super) fn build_client(
+    connection_string: Option<String>,
+    storage_account: Option<String>,
+    client_credentials: Option<&ClientCredentials>,
+    queue_name: String,
+    endpoint: Option<String>,
+) -> crate::Result<Arc<QueueClient>> {
+    let builder = match (connection_string, storage_account) {
+        (Some(connection_string), None) => {
+            if client_credentials.is_some() {
+                return Err("`client_credentials` can only be used with `storage_account`".into());
+            }
+            let connection_string = ConnectionString::new(&connection_string)?;
+
+            match connection_string.queue_endpoint {
+                // As with the `azure_blob` sink, an explicit endpoint in the connection string
+                // takes precedence, which also makes the account name optional.
+                Some(uri) => QueueServiceClientBuilder::with_location(
+                    CloudLocation::Custom {
+                        uri: uri.to_string(),
+                    },
+                    connection_string.storage_credentials()?,
+                ),
+                None => QueueServiceClientBuilder::new(
+                    connection_string
+                        .account_name
+                        .ok_or("Account name missing in connection string")?,
+                    connection_string.storage_credentials()?,
+                ),
+            }
+        }
+        (None, Some(storage_account)) => {
+            let storage_credentials =
+                StorageCredentials::token_credential(token_credential(client_credentials)?);
+
+            match endpoint {
+                Some(endpoint) => QueueServiceClientBuilder::with_location(
+                    CloudLocation::Custom { uri: endpoint },
+                    storage_credentials,
+                ),
+                None => QueueServiceClientBuilder::new(storage_account, storage_credentials),
+            }
+        }
+        (None, None) => {
+            return Err("Either `connection_string` or `storage_account` has to be provided".into())
+        }
+        (Some(_), Some(_)) => {
+            return Err(
+                "`connection_string` and `storage_account` can't be provided at the same time"
+                    .into(),
+            )
+        }
+    };
+
+    Ok(Arc::new(
+        builder
+            .retry(RetryOptions::none())
+            .build()
+            .queue_client(queue_name),
+    ))
+}
+
+async fn healthcheck(queue_name: String, client: Arc<QueueClient>) -> crate::Result<()> {
+    match client.get_metadata().into_future().await {
+        Ok(_) => Ok(()),
+        Err(reason) => Err(match reason.downcast_ref::<HttpError>() {
+            Some(err) => match StatusCode::from_u16(err.status().into()) {
+                Ok(StatusCode::FORBIDDEN) => Box::new(HealthcheckError::InvalidCredentials),
+                Ok(StatusCode::NOT_FOUND) => {
+                    Box::new(HealthcheckError::UnknownQueue { queue: queue_name })
+                }
+                Ok(status) => Box::new(HealthcheckError::Unknown { status }),
+                Err(_) => "unknown status code".into(),
+            },
+            _ => reason.into(),
+        }),
+    }
+}
@@ -0,0 +1,15 @@
+//! The Azure Queue Storage [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s and putting each of them as a message on an
+//! Azure Storage queue.
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AzureStorageQueueConfig;
@@ -0,0 +1,114 @@
+use std::io;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::{Bytes, BytesMut};
+use tokio_util::codec::Encoder as _;
+use vector_lib::config::telemetry;
+
+use crate::sinks::prelude::*;
+
+pub(super) struct QueueMessageEncoder {
+    pub(super) transformer: Transformer,
+    pub(super) encoder: Encoder<()>,
+}
+
+impl encoding::Encoder<Event> for QueueMessageEncoder {
+    fn encode_input(
+        &self,
+        mut input: Event,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut body = BytesMut::new();
+        self.transformer.transform(&mut input);
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        byte_size.add_event(&input, input.estimated_json_encoded_size_of());
+
+        let mut encoder = self.encoder.clone();
+        encoder
+            .encode(input, &mut body)
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "unable to encode"))?;
+
+        let body = body.freeze();
+        write_all(writer, 1, body.as_ref())?;
+
+        Ok((body.len(), byte_size))
+    }
+}
+
+pub(super) struct QueueRequestBuilder {
+    pub(super) encoder: QueueMessageEncoder,
+    pub(super) base64_encode: bool,
+}
+
+#[derive(Clone)]
+pub(super) struct QueueRequest {
+    pub(super) message: String,
+    finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for QueueRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for QueueRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+impl RequestBuilder<Event> for QueueRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = Event;
+    type Encoder = QueueMessageEncoder;
+    type Payload = Bytes;
+    type Request = QueueRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut input: Event,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let builder = RequestMetadataBuilder::from_event(&input);
+        let finalizers = input.take_finalizers();
+
+        (finalizers, builder, input)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        QueueRequest {
+            message: message_text(&payload.into_payload(), self.base64_encode),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+/// The text of the queue message holding an encoded event.
+pub(super) fn message_text(payload: &[u8], base64_encode: bool) -> String {
+    if base64_encode {
+        BASE64_STANDARD.encode(payload)
+    } else {
+        String::from_utf8_lossy(payload).into_owned()
+    }
+}
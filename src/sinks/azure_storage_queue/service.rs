@@ -0,0 +1,101 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use azure_core::error::HttpError;
+use azure_storage_queues::{prelude::*, QueueClient};
+use http::StatusCode;
+use tracing::Instrument;
+
+use crate::sinks::prelude::*;
+
+use super::request_builder::QueueRequest;
+
+#[derive(Clone)]
+pub(super) struct QueueService {
+    client: Arc<QueueClient>,
+    message_ttl: Option<Duration>,
+    visibility_timeout: Option<Duration>,
+}
+
+impl QueueService {
+    pub(super) const fn new(
+        client: Arc<QueueClient>,
+        message_ttl: Option<Duration>,
+        visibility_timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            client,
+            message_ttl,
+            visibility_timeout,
+        }
+    }
+}
+
+pub(super) struct QueueResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for QueueResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+impl Service<QueueRequest> for QueueService {
+    type Response = QueueResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: QueueRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let mut message = this.client.put_message(request.message);
+            if let Some(ttl) = this.message_ttl {
+                message = message.ttl(MessageTTL::new(ttl));
+            }
+            if let Some(timeout) = this.visibility_timeout {
+                message = message.visibility_timeout(VisibilityTimeout::new(timeout));
+            }
+
+            message
+                .into_future()
+                .instrument(info_span!("request").or_current())
+                .await
+                .map_err(Into::into)
+                .map(|_| QueueResponse {
+                    metadata: request.metadata,
+                })
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct QueueRetryLogic;
+
+impl RetryLogic for QueueRetryLogic {
+    type Error = HttpError;
+    type Response = QueueResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.status().is_server_error()
+            || StatusCode::TOO_MANY_REQUESTS.as_u16() == Into::<u16>::into(error.status())
+    }
+}
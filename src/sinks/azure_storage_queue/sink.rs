@@ -0,0 +1,60 @@
+use std::fmt;
+
+use crate::sinks::prelude::*;
+
+use super::request_builder::{QueueRequest, QueueRequestBuilder};
+
+pub(super) struct QueueSink<S> {
+    service: S,
+    request_builder: QueueRequestBuilder,
+}
+
+impl<S> QueueSink<S>
+where
+    S: Service<QueueRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(service: S, request_builder: QueueRequestBuilder) -> Self {
+        Self {
+            service,
+            request_builder,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            // Every event is put on the queue as a message of its own.
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for QueueSink<S>
+where
+    S: Service<QueueRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,76 @@
+//! Unit tests for the `azure_storage_queue` sink.
+
+use std::time::Duration;
+
+use super::{
+    config::{AzureStorageQueueConfig, ConfigError},
+    request_builder::message_text,
+};
+
+fn config(options: &str) -> AzureStorageQueueConfig {
+    toml::from_str(&format!(
+        r#"
+        connection_string = "DefaultEndpointsProtocol=https;AccountName=account;AccountKey=key"
+        queue_name = "function-triggers"
+        encoding.codec = "json"
+        {options}
+        "#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureStorageQueueConfig>();
+}
+
+#[test]
+fn base64_encode_is_enabled_by_default() {
+    assert!(config("").base64_encode);
+    assert!(!config("base64_encode = false").base64_encode);
+}
+
+#[test]
+fn message_text_encoding() {
+    assert_eq!(
+        message_text(b"{\"message\":\"hello\"}", true),
+        "eyJtZXNzYWdlIjoiaGVsbG8ifQ=="
+    );
+    assert_eq!(
+        message_text(b"{\"message\":\"hello\"}", false),
+        "{\"message\":\"hello\"}"
+    );
+    assert_eq!(message_text(b"hello \xff", false), "hello \u{fffd}");
+}
+
+#[test]
+fn message_durations() {
+    assert_eq!(config("").message_durations(), Ok((None, None)));
+    assert_eq!(
+        config("message_ttl_secs = 3600\nvisibility_timeout_secs = 30").message_durations(),
+        Ok((
+            Some(Duration::from_secs(3600)),
+            Some(Duration::from_secs(30))
+        ))
+    );
+}
+
+#[test]
+fn message_durations_errors() {
+    assert_eq!(
+        config("message_ttl_secs = 604801").message_durations(),
+        Err(ConfigError::DurationTooLong {
+            option: "message_ttl_secs"
+        })
+    );
+    assert_eq!(
+        config("visibility_timeout_secs = 604801").message_durations(),
+        Err(ConfigError::DurationTooLong {
+            option: "visibility_timeout_secs"
+        })
+    );
+    assert_eq!(
+        config("message_ttl_secs = 60\nvisibility_timeout_secs = 60").message_durations(),
+        Err(ConfigError::VisibilityTimeoutExceedsTtl)
+    );
+}
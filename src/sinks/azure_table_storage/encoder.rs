@@ -0,0 +1,386 @@
+//! Mapping events to table entities, and batches of entities to batch transactions.
+
+use std::io::{self, Write};
+
+use bytes::Bytes;
+use chrono::SecondsFormat;
+use serde_json::{json, Map};
+use uuid::Uuid;
+
+use crate::{
+    internal_events::{AzureTableStorageInvalidKeyError, AzureTableStorageInvalidPropertyError},
+    sinks::prelude::*,
+};
+
+use super::config::WriteMode;
+
+/// The longest `PartitionKey` or `RowKey` accepted by the service, in bytes.
+const MAX_KEY_BYTES: usize = 1024;
+
+/// The longest property name accepted by the service, in characters.
+const MAX_PROPERTY_NAME_CHARS: usize = 255;
+
+/// The suffix of the annotation that holds the type of a property.
+const ODATA_TYPE_SUFFIX: &str = "@odata.type";
+
+/// Properties that are set by the service, or from the configured templates, and so are never
+/// taken from event fields.
+const SYSTEM_PROPERTIES: [&str; 3] = ["PartitionKey", "RowKey", "Timestamp"];
+
+/// An event mapped to a table entity, ready to be written as one operation of a batch transaction.
+#[derive(Clone)]
+pub(super) struct TableEntity {
+    pub(super) partition_key: String,
+    pub(super) row_key: String,
+    pub(super) body: Bytes,
+    pub(super) json_byte_size: JsonSize,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) event_count_tags: TaggedEventsSent,
+}
+
+impl TableEntity {
+    /// Replaces the entity with a later one with the same keys, as writing it with the
+    /// `insert_or_replace` write mode would.
+    pub(super) fn replace(&mut self, mut later: TableEntity) {
+        later.finalizers.merge(self.take_finalizers());
+        later.json_byte_size += self.json_byte_size;
+        *self = later;
+    }
+
+    /// Merges the properties of a later entity with the same keys into the entity, as writing it
+    /// with the `insert_or_merge` write mode would.
+    pub(super) fn merge(&mut self, later: TableEntity) {
+        let mut properties = entity_body_properties(&self.body);
+        let later_properties = entity_body_properties(&later.body);
+
+        // A property that is set again is written with its new type, or without one.
+        for name in later_properties.keys() {
+            if !name.ends_with(ODATA_TYPE_SUFFIX) {
+                properties.remove(&format!("{name}{ODATA_TYPE_SUFFIX}"));
+            }
+        }
+        properties.extend(later_properties);
+
+        self.body = serde_json::to_vec(&properties)
+            .expect("entity properties are always serializable")
+            .into();
+        self.json_byte_size += later.json_byte_size;
+        self.finalizers.merge(later.finalizers);
+    }
+}
+
+/// Reads back the properties of an entity from its body.
+fn entity_body_properties(body: &[u8]) -> Map<String, serde_json::Value> {
+    serde_json::from_slice(body).expect("entity bodies are always JSON objects")
+}
+
+impl ByteSizeOf for TableEntity {
+    fn allocated_bytes(&self) -> usize {
+        self.partition_key.allocated_bytes() + self.row_key.allocated_bytes() + self.body.len()
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for TableEntity {
+    fn estimated_json_encoded_size_of(&self) -> JsonSize {
+        self.json_byte_size
+    }
+}
+
+impl EventCount for TableEntity {
+    fn event_count(&self) -> usize {
+        // An entity is mapped one-to-one with an event.
+        1
+    }
+}
+
+impl Finalizable for TableEntity {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl GetEventCountTags for TableEntity {
+    fn get_tags(&self) -> TaggedEventsSent {
+        self.event_count_tags.clone()
+    }
+}
+
+/// Partitions entities by their `PartitionKey`, as a batch transaction may only hold entities of a
+/// single partition.
+#[derive(Default)]
+pub(super) struct EntityPartitioner;
+
+impl Partitioner for EntityPartitioner {
+    type Item = TableEntity;
+    type Key = String;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        item.partition_key.clone()
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct TableEntityEncoder {
+    pub(super) partition_key: Template,
+    pub(super) row_key: Template,
+    pub(super) transformer: Transformer,
+}
+
+impl TableEntityEncoder {
+    /// Maps an event to an entity, or drops it if its keys can't be rendered or are not valid.
+    ///
+    /// Events with a field whose name is not a valid property name are rejected, as the service
+    /// would fail the whole batch transaction that holds them.
+    pub(super) fn encode_event(&self, mut event: Event) -> Option<TableEntity> {
+        let partition_key = self.render_key(&self.partition_key, "partition_key", &event)?;
+        let row_key = self.render_key(&self.row_key, "row_key", &event)?;
+
+        self.transformer.transform(&mut event);
+        let json_byte_size = event.estimated_json_encoded_size_of();
+        let event_count_tags = event.get_tags();
+        let finalizers = event.take_finalizers();
+
+        let properties = entity_properties(&event.into_log(), &partition_key, &row_key);
+        if let Some(name) = properties.keys().find(|name| {
+            !is_valid_property_name(
+                name.strip_suffix(ODATA_TYPE_SUFFIX)
+                    .unwrap_or(name.as_str()),
+            )
+        }) {
+            emit!(AzureTableStorageInvalidPropertyError { name });
+            finalizers.update_status(EventStatus::Rejected);
+            return None;
+        }
+        let body = serde_json::to_vec(&properties)
+            .expect("entity properties are always serializable")
+            .into();
+
+        Some(TableEntity {
+            partition_key,
+            row_key,
+            body,
+            json_byte_size,
+            finalizers,
+            event_count_tags,
+        })
+    }
+
+    fn render_key(
+        &self,
+        template: &Template,
+        field: &'static str,
+        event: &Event,
+    ) -> Option<String> {
+        let key = template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                })
+            })
+            .ok()?;
+
+        if is_valid_key(&key) {
+            Some(key)
+        } else {
+            emit!(AzureTableStorageInvalidKeyError { field, key: &key });
+            None
+        }
+    }
+}
+
+/// Whether a `PartitionKey` or `RowKey` is accepted by the service.
+///
+/// See the [documentation][keys] of the keys for the characters that are not allowed.
+///
+/// [keys]: https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-the-table-service-data-model#characters-disallowed-in-key-fields
+pub(super) fn is_valid_key(key: &str) -> bool {
+    key.len() <= MAX_KEY_BYTES
+        && !key
+            .chars()
+            .any(|c| matches!(c, '/' | '\\' | '#' | '?') || c.is_control())
+}
+
+/// Whether a property name is accepted by the service, which requires it to be a C# identifier.
+///
+/// See the [documentation][properties] of the properties.
+///
+/// [properties]: https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-the-table-service-data-model#property-names
+pub(super) fn is_valid_property_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    name.chars().count() <= MAX_PROPERTY_NAME_CHARS
+        && chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Maps the top-level fields of the event to entity properties.
+///
+/// Integers and timestamps are annotated with their [property type][types], as JSON has no way
+/// to tell them apart from doubles and strings. Objects and arrays are stored as JSON strings, and
+/// null fields are left out.
+///
+/// [types]: https://learn.microsoft.com/en-us/rest/api/storageservices/payload-format-for-table-service-operations#property-types-in-a-json-feed
+pub(super) fn entity_properties(
+    log: &LogEvent,
+    partition_key: &str,
+    row_key: &str,
+) -> Map<String, serde_json::Value> {
+    let mut properties = Map::new();
+    properties.insert("PartitionKey".to_string(), json!(partition_key));
+    properties.insert("RowKey".to_string(), json!(row_key));
+
+    let fields = match log.value() {
+        Value::Object(fields) => fields,
+        // Events whose root is not an object are stored as a single `message` property.
+        value => {
+            insert_property(&mut properties, "message", value);
+            return properties;
+        }
+    };
+
+    for (name, value) in fields {
+        if !SYSTEM_PROPERTIES.contains(&name.as_str()) {
+            insert_property(&mut properties, name.as_str(), value);
+        }
+    }
+
+    properties
+}
+
+fn insert_property(properties: &mut Map<String, serde_json::Value>, name: &str, value: &Value) {
+    let (value, edm_type) = match value {
+        Value::Null => return,
+        Value::Boolean(value) => (json!(value), None),
+        Value::Integer(value) => (json!(value.to_string()), Some("Edm.Int64")),
+        Value::Float(value) => (json!(value.into_inner()), Some("Edm.Double")),
+        Value::Timestamp(timestamp) => (
+            json!(timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)),
+            Some("Edm.DateTime"),
+        ),
+        Value::Bytes(_) | Value::Regex(_) => (json!(value.to_string_lossy()), None),
+        Value::Object(_) | Value::Array(_) => (
+            json!(serde_json::to_string(value).expect("values are always serializable")),
+            None,
+        ),
+    };
+
+    properties.insert(name.to_string(), value);
+    if let Some(edm_type) = edm_type {
+        properties.insert(format!("{name}{ODATA_TYPE_SUFFIX}"), json!(edm_type));
+    }
+}
+
+/// Writes a batch of entities of a single partition as the [multipart body][batch] of a batch
+/// transaction, with one change set that holds an operation for each entity.
+///
+/// [batch]: https://learn.microsoft.com/en-us/rest/api/storageservices/performing-entity-group-transactions
+#[derive(Clone)]
+pub(super) struct TableBatchEncoder {
+    table_uri: String,
+    write_mode: WriteMode,
+    pub(super) boundary: String,
+    changeset: String,
+}
+
+impl TableBatchEncoder {
+    pub(super) fn new(table_uri: String, write_mode: WriteMode) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            table_uri,
+            write_mode,
+            boundary: format!("batch_{id}"),
+            changeset: format!("changeset_{id}"),
+        }
+    }
+
+    fn write_operation(
+        &self,
+        writer: &mut Vec<u8>,
+        content_id: usize,
+        entity: &TableEntity,
+    ) -> io::Result<()> {
+        let (method, uri) = match self.write_mode {
+            WriteMode::Insert => ("POST", self.table_uri.clone()),
+            WriteMode::InsertOrReplace => ("PUT", self.entity_uri(entity)),
+            WriteMode::InsertOrMerge => ("MERGE", self.entity_uri(entity)),
+        };
+
+        write!(writer, "--{}\r\n", self.changeset)?;
+        write!(writer, "Content-Type: application/http\r\n")?;
+        write!(writer, "Content-Transfer-Encoding: binary\r\n\r\n")?;
+        write!(writer, "{method} {uri} HTTP/1.1\r\n")?;
+        write!(writer, "Content-ID: {content_id}\r\n")?;
+        write!(writer, "Content-Type: application/json\r\n")?;
+        write!(writer, "Accept: application/json;odata=minimalmetadata\r\n")?;
+        if self.write_mode == WriteMode::Insert {
+            write!(writer, "Prefer: return-no-content\r\n")?;
+        }
+        write!(writer, "DataServiceVersion: 3.0\r\n\r\n")?;
+        writer.write_all(&entity.body)?;
+        write!(writer, "\r\n")
+    }
+
+    /// The URI that addresses a single entity by its keys.
+    fn entity_uri(&self, entity: &TableEntity) -> String {
+        format!(
+            "{}(PartitionKey='{}',RowKey='{}')",
+            self.table_uri,
+            escape_key(&entity.partition_key),
+            escape_key(&entity.row_key),
+        )
+    }
+}
+
+/// Escapes a key for use in the URI of an entity, where it is quoted as an OData string literal.
+fn escape_key(key: &str) -> String {
+    percent_encoding::utf8_percent_encode(&key.replace('\'', "''"), KEY_ENCODE_SET).to_string()
+}
+
+/// Everything but unreserved characters is percent-encoded, except the quotes that are already
+/// escaped for OData.
+const KEY_ENCODE_SET: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b'\'');
+
+impl TableBatchEncoder {
+    /// Writes the body of a batch transaction that holds the entities.
+    pub(super) fn batch_body(&self, entities: &[TableEntity]) -> io::Result<Vec<u8>> {
+        let mut body = Vec::new();
+
+        write!(body, "--{}\r\n", self.boundary)?;
+        write!(
+            body,
+            "Content-Type: multipart/mixed; boundary={}\r\n\r\n",
+            self.changeset
+        )?;
+        for (index, entity) in entities.iter().enumerate() {
+            self.write_operation(&mut body, index + 1, entity)?;
+        }
+        write!(body, "--{}--\r\n", self.changeset)?;
+        write!(body, "--{}--\r\n", self.boundary)?;
+
+        Ok(body)
+    }
+}
+
+impl encoding::Encoder<Vec<TableEntity>> for TableBatchEncoder {
+    fn encode_input(
+        &self,
+        input: Vec<TableEntity>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for entity in &input {
+            byte_size.add_event(entity, entity.estimated_json_encoded_size_of());
+        }
+        let body = self.batch_body(&input)?;
+
+        write_all(writer, input.len(), &body)?;
+        Ok((body.len(), byte_size))
+    }
+}
@@ -0,0 +1,16 @@
+//! The Azure Table Storage [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s, mapping each of them to a table entity, and
+//! writing the entities of each partition in batch transactions.
+
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AzureTableStorageConfig;
@@ -0,0 +1,66 @@
+//! Building batch transaction requests from batches of entities.
+
+use std::io;
+
+use bytes::Bytes;
+
+use crate::sinks::prelude::*;
+
+use super::{
+    encoder::{TableBatchEncoder, TableEntity},
+    service::TableRequest,
+};
+
+#[derive(Clone)]
+pub(super) struct TableRequestBuilder {
+    pub(super) encoder: TableBatchEncoder,
+}
+
+impl RequestBuilder<(String, Vec<TableEntity>)> for TableRequestBuilder {
+    type Metadata = (EventFinalizers, Vec<TableEntity>);
+    type Events = Vec<TableEntity>;
+    type Encoder = TableBatchEncoder;
+    type Payload = Bytes;
+    type Request = TableRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<TableEntity>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (_partition_key, entities) = input;
+
+        let metadata_builder = RequestMetadataBuilder::from_events(&entities);
+        // The entities keep their finalizers, so that the service can reject one of them while
+        // the driver finalizes the others.
+        let finalizers = entities
+            .iter()
+            .map(|entity| entity.finalizers.clone())
+            .collect();
+
+        ((finalizers, entities.clone()), metadata_builder, entities)
+    }
+
+    fn build_request(
+        &self,
+        (finalizers, entities): Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        TableRequest {
+            body: payload.into_payload(),
+            encoder: self.encoder.clone(),
+            entities,
+            finalizers,
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Service implementation for the `azure_table_storage` sink.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http::{header, Method, Request, StatusCode};
+use hyper::Body;
+use openssl::{base64, error::ErrorStack, hash, pkey, sign};
+use snafu::{ResultExt, Snafu};
+use tracing::Instrument;
+
+use crate::{
+    http::{HttpClient, HttpError},
+    internal_events::AzureTableStorageEntityRejectedError,
+    sinks::prelude::*,
+};
+
+use super::{
+    config::{TableConnection, TableCredentials},
+    encoder::{TableBatchEncoder, TableEntity},
+};
+
+/// The version of the Table service REST API that requests are made with.
+const API_VERSION: &str = "2019-02-02";
+
+#[derive(Debug, Snafu)]
+pub(super) enum TableStorageError {
+    #[snafu(display("Failed to sign the request: {}", source))]
+    Sign { source: ErrorStack },
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Failed to encode the batch transaction: {}", source))]
+    Encode { source: std::io::Error },
+    #[snafu(display("Batch transaction failed, server returned {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display(
+        "Operation {} of the batch transaction failed, server returned {}: {}",
+        index,
+        status,
+        body
+    ))]
+    OperationFailed {
+        index: usize,
+        status: StatusCode,
+        body: String,
+    },
+}
+
+impl TableStorageError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            TableStorageError::Sign { .. } | TableStorageError::Encode { .. } => false,
+            TableStorageError::ReadResponse { .. } => true,
+            TableStorageError::Request { source } => source.is_retriable(),
+            TableStorageError::UnexpectedStatus { status, .. }
+            | TableStorageError::OperationFailed { status, .. } => is_retriable_status(*status),
+        }
+    }
+}
+
+fn is_retriable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[derive(Clone)]
+pub(super) struct TableStorageRetryLogic;
+
+impl RetryLogic for TableStorageRetryLogic {
+    type Error = TableStorageError;
+    type Response = TableStorageResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct TableRequest {
+    pub(super) body: Bytes,
+    pub(super) encoder: TableBatchEncoder,
+    /// The entities that the body holds, which keep their own finalizers so that an entity can be
+    /// rejected without the others.
+    pub(super) entities: Vec<TableEntity>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for TableRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for TableRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct TableStorageResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for TableStorageResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_wire_size())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct TableStorageService {
+    client: HttpClient,
+    connection: Arc<TableConnection>,
+}
+
+impl TableStorageService {
+    pub(super) const fn new(client: HttpClient, connection: Arc<TableConnection>) -> Self {
+        Self { client, connection }
+    }
+
+    /// Gets the table, returning the status of the response.
+    pub(super) async fn get_table(
+        &self,
+        table_name: &str,
+    ) -> Result<StatusCode, TableStorageError> {
+        let request = self
+            .request(Method::GET, &format!("Tables('{table_name}')"), Utc::now())?
+            .header(header::ACCEPT, "application/json;odata=nometadata")
+            .body(Body::empty())
+            .context(crate::http::BuildRequestSnafu)
+            .context(RequestSnafu)?;
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        Ok(response.status())
+    }
+
+    /// Sends a [batch transaction][batch].
+    ///
+    /// The service answers a batch with `202 Accepted` even when it fails, with the response to
+    /// the operation that failed the transaction in the multipart response body.
+    ///
+    /// [batch]: https://learn.microsoft.com/en-us/rest/api/storageservices/performing-entity-group-transactions
+    async fn send_batch(&self, boundary: &str, body: Bytes) -> Result<(), TableStorageError> {
+        let request = self
+            .request(Method::POST, "$batch", Utc::now())?
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/mixed; boundary={boundary}"),
+            )
+            .header("DataServiceVersion", "3.0")
+            .body(Body::from(body))
+            .context(crate::http::BuildRequestSnafu)
+            .context(RequestSnafu)?;
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+        let body = String::from_utf8_lossy(&body);
+
+        if status != StatusCode::ACCEPTED {
+            return if status.is_success() {
+                Ok(())
+            } else {
+                Err(TableStorageError::UnexpectedStatus {
+                    status,
+                    body: body.into_owned(),
+                })
+            };
+        }
+
+        match operation_status(&body) {
+            Some(status) if !status.is_success() => match failed_operation_index(&body) {
+                Some(index) => Err(TableStorageError::OperationFailed {
+                    index,
+                    status,
+                    body: body.into_owned(),
+                }),
+                None => Err(TableStorageError::UnexpectedStatus {
+                    status,
+                    body: body.into_owned(),
+                }),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts a request to a resource of the Table service, authorized with the credentials of
+    /// the connection string.
+    fn request(
+        &self,
+        method: Method,
+        path: &str,
+        now: DateTime<Utc>,
+    ) -> Result<http::request::Builder, TableStorageError> {
+        let date = now.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let builder = Request::builder()
+            .method(method)
+            .header("x-ms-date", &date)
+            .header("x-ms-version", API_VERSION);
+
+        Ok(match &self.connection.credentials {
+            TableCredentials::AccountKey { account_name, key } => {
+                let uri = format!("{}/{path}", self.connection.endpoint);
+                let signature = shared_key_lite_signature(key, account_name, &uri, &date)?;
+                builder.uri(uri).header(
+                    header::AUTHORIZATION,
+                    format!("SharedKeyLite {account_name}:{signature}"),
+                )
+            }
+            TableCredentials::SharedAccessSignature(signature) => builder.uri(format!(
+                "{}/{path}?{}",
+                self.connection.endpoint,
+                signature.inner()
+            )),
+        })
+    }
+}
+
+/// Signs a request with the [Shared Key Lite][shared_key] scheme of the Table service, which
+/// covers the date of the request and the resource it addresses.
+///
+/// [shared_key]: https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key#shared-key-lite-and-table-service-format-for-2009-09-19-and-later
+pub(super) fn shared_key_lite_signature(
+    key: &[u8],
+    account_name: &str,
+    uri: &str,
+    date: &str,
+) -> Result<String, TableStorageError> {
+    // The endpoint is validated when the connection string is parsed, so the path is only
+    // missing for a malformed resource, which is then signed as the root of the account.
+    let path = uri
+        .parse::<http::Uri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|_| "/".to_string());
+
+    let sign = || {
+        let key = pkey::PKey::hmac(key)?;
+        let mut signer = sign::Signer::new(hash::MessageDigest::sha256(), &key)?;
+        signer.update(format!("{date}\n/{account_name}{path}").as_bytes())?;
+        signer.sign_to_vec()
+    };
+
+    Ok(base64::encode_block(&sign().context(SignSnafu)?))
+}
+
+/// The status of the first operation response in the multipart body of a batch response.
+///
+/// A successful transaction has a response for every operation, while a failed one only has the
+/// response of the operation that failed it.
+pub(super) fn operation_status(body: &str) -> Option<StatusCode> {
+    body.lines()
+        .find_map(|line| line.strip_prefix("HTTP/1.1 "))
+        .and_then(|status| status.split_whitespace().next())
+        .and_then(|code| code.parse::<u16>().ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+}
+
+/// The index of the operation that failed a batch transaction, which the service puts at the start
+/// of the error message in the response to the operation.
+pub(super) fn failed_operation_index(body: &str) -> Option<usize> {
+    body.lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find_map(|error| {
+            let message = error["odata.error"]["message"]["value"].as_str()?;
+            message.split_once(':')?.0.parse().ok()
+        })
+}
+
+impl Service<TableRequest> for TableStorageService {
+    type Response = TableStorageResponse;
+    type Error = TableStorageError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: TableRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(
+            async move {
+                let TableRequest {
+                    mut body,
+                    encoder,
+                    mut entities,
+                    metadata,
+                    ..
+                } = request;
+
+                loop {
+                    match service.send_batch(&encoder.boundary, body).await {
+                        // The operation of a single entity failed the transaction, so that entity
+                        // is rejected, and the others are written again without it.
+                        Err(TableStorageError::OperationFailed {
+                            index,
+                            status,
+                            body: response,
+                        }) if !is_retriable_status(status) && index < entities.len() => {
+                            let entity = entities.remove(index);
+                            emit!(AzureTableStorageEntityRejectedError {
+                                partition_key: &entity.partition_key,
+                                row_key: &entity.row_key,
+                                status,
+                                response: &response,
+                            });
+                            entity.finalizers.update_status(EventStatus::Rejected);
+
+                            if entities.is_empty() {
+                                break;
+                            }
+                            body = encoder.batch_body(&entities).context(EncodeSnafu)?.into();
+                        }
+                        result => {
+                            result?;
+                            break;
+                        }
+                    }
+                }
+
+                Ok(TableStorageResponse { metadata })
+            }
+            .in_current_span(),
+        )
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+};
+
+use crate::{internal_events::AzureTableStorageDuplicateKeyError, sinks::prelude::*};
+
+use super::{
+    config::WriteMode,
+    encoder::{EntityPartitioner, TableEntity, TableEntityEncoder},
+    request_builder::TableRequestBuilder,
+    service::TableRequest,
+};
+
+pub(super) struct TableStorageSink<S> {
+    service: S,
+    encoder: TableEntityEncoder,
+    write_mode: WriteMode,
+    batch_settings: BatcherSettings,
+    request_builder: TableRequestBuilder,
+}
+
+impl<S> TableStorageSink<S>
+where
+    S: Service<TableRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        service: S,
+        encoder: TableEntityEncoder,
+        write_mode: WriteMode,
+        batch_settings: BatcherSettings,
+        request_builder: TableRequestBuilder,
+    ) -> Self {
+        Self {
+            service,
+            encoder,
+            write_mode,
+            batch_settings,
+            request_builder,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoder = self.encoder;
+        let write_mode = self.write_mode;
+        let batch_settings = self.batch_settings;
+
+        input
+            .filter_map(|event| future::ready(encoder.encode_event(event)))
+            // A batch transaction may only hold entities of a single partition.
+            .batched_partitioned(EntityPartitioner, || batch_settings.as_byte_size_config())
+            .map(|batch| fold_duplicate_keys(batch, write_mode))
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for TableStorageSink<S>
+where
+    S: Service<TableRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Folds the entities of a batch that have the same `RowKey` into one, as a batch transaction
+/// can't hold more than one operation on the same entity.
+///
+/// The entities of a batch share their `PartitionKey`, and are folded into the entity that writing
+/// them one after the other would leave in the table: with `insert_or_replace`, the last one
+/// replaces the earlier ones, and with `insert_or_merge`, the properties of the later ones are
+/// merged into the earlier ones. With `insert`, the later ones would fail to be inserted, so they
+/// are rejected.
+pub(super) fn fold_duplicate_keys(
+    (partition_key, entities): (String, Vec<TableEntity>),
+    write_mode: WriteMode,
+) -> (String, Vec<TableEntity>) {
+    let mut folded: Vec<TableEntity> = Vec::with_capacity(entities.len());
+    let mut positions = HashMap::new();
+
+    for entity in entities {
+        match positions.entry(entity.row_key.clone()) {
+            Entry::Vacant(entry) => {
+                entry.insert(folded.len());
+                folded.push(entity);
+            }
+            Entry::Occupied(entry) => {
+                let earlier = &mut folded[*entry.get()];
+                match write_mode {
+                    WriteMode::Insert => {
+                        emit!(AzureTableStorageDuplicateKeyError {
+                            partition_key: &entity.partition_key,
+                            row_key: &entity.row_key,
+                        });
+                        entity.finalizers.update_status(EventStatus::Rejected);
+                    }
+                    WriteMode::InsertOrReplace => earlier.replace(entity),
+                    WriteMode::InsertOrMerge => earlier.merge(entity),
+                }
+            }
+        }
+    }
+
+    (partition_key, folded)
+}
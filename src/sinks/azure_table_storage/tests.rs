@@ -0,0 +1,363 @@
+//! Unit tests for the `azure_table_storage` sink.
+
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+
+use crate::{
+    event::{Event, LogEvent, Value},
+    sinks::{prelude::*, util::encoding::Encoder as _},
+};
+
+use super::{
+    config::{
+        AzureTableStorageConfig, ConnectionStringError, TableConnection, TableCredentials,
+        WriteMode,
+    },
+    encoder::{
+        entity_properties, is_valid_key, is_valid_property_name, TableBatchEncoder, TableEntity,
+        TableEntityEncoder,
+    },
+    service::{failed_operation_index, operation_status, shared_key_lite_signature},
+    sink::fold_duplicate_keys,
+};
+
+fn entity_encoder() -> TableEntityEncoder {
+    TableEntityEncoder {
+        partition_key: Template::try_from("{{ host }}").unwrap(),
+        row_key: Template::try_from("{{ id }}").unwrap(),
+        transformer: Default::default(),
+    }
+}
+
+fn entity(row_key: &str, message: &str) -> TableEntity {
+    let mut log = LogEvent::from(message);
+    log.insert("host", "web-1");
+    log.insert("id", row_key);
+    entity_encoder().encode_event(Event::from(log)).unwrap()
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AzureTableStorageConfig>();
+}
+
+#[test]
+fn write_mode_defaults_to_insert_or_replace() {
+    let config: AzureTableStorageConfig = toml::from_str(
+        r#"
+        connection_string = "AccountName=account;AccountKey=a2V5"
+        table_name = "logs"
+        partition_key = "{{ host }}"
+        row_key = "{{ id }}"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.write_mode, WriteMode::InsertOrReplace);
+}
+
+#[test]
+fn parse_connection_string_with_account_key() {
+    let connection = TableConnection::parse(
+        "DefaultEndpointsProtocol=https;AccountName=account;AccountKey=a2V5;EndpointSuffix=core.usgovcloudapi.net",
+    )
+    .unwrap();
+
+    assert_eq!(
+        connection.endpoint,
+        "https://account.table.core.usgovcloudapi.net"
+    );
+    assert_eq!(
+        connection.table_uri("logs"),
+        "https://account.table.core.usgovcloudapi.net/logs"
+    );
+    match connection.credentials {
+        TableCredentials::AccountKey { account_name, key } => {
+            assert_eq!(account_name, "account");
+            assert_eq!(key, b"key");
+        }
+        TableCredentials::SharedAccessSignature(_) => panic!("expected an account key"),
+    }
+}
+
+#[test]
+fn parse_connection_string_with_shared_access_signature() {
+    let connection = TableConnection::parse(
+        "TableEndpoint=https://account.table.core.windows.net/;SharedAccessSignature=sv=2019-02-02&sig=abc%3D",
+    )
+    .unwrap();
+
+    assert_eq!(
+        connection.endpoint,
+        "https://account.table.core.windows.net"
+    );
+    match connection.credentials {
+        TableCredentials::SharedAccessSignature(signature) => {
+            assert_eq!(signature.inner(), "sv=2019-02-02&sig=abc%3D")
+        }
+        TableCredentials::AccountKey { .. } => panic!("expected a shared access signature"),
+    }
+}
+
+#[test]
+fn parse_connection_string_errors() {
+    assert!(matches!(
+        TableConnection::parse("AccountKey=a2V5"),
+        Err(ConnectionStringError::MissingProperty {
+            property: "AccountName"
+        })
+    ));
+    assert!(matches!(
+        TableConnection::parse("AccountName=account"),
+        Err(ConnectionStringError::MissingCredentials)
+    ));
+    assert!(matches!(
+        TableConnection::parse("AccountName=account;AccountKey=not base64!"),
+        Err(ConnectionStringError::InvalidAccountKey)
+    ));
+    assert!(matches!(
+        TableConnection::parse("AccountName=account;AccountKey"),
+        Err(ConnectionStringError::InvalidConnectionString { .. })
+    ));
+}
+
+#[test]
+fn shared_key_lite_signature_covers_date_and_resource() {
+    // HMAC-SHA256 of "Mon, 01 Jan 2024 00:00:00 GMT\n/account/$batch" with the key "key".
+    let signature = shared_key_lite_signature(
+        b"key",
+        "account",
+        "https://account.table.core.windows.net/$batch",
+        "Mon, 01 Jan 2024 00:00:00 GMT",
+    )
+    .unwrap();
+    let other_resource = shared_key_lite_signature(
+        b"key",
+        "account",
+        "https://account.table.core.windows.net/Tables('logs')",
+        "Mon, 01 Jan 2024 00:00:00 GMT",
+    )
+    .unwrap();
+
+    assert_eq!(signature, "AOBuk51KRpBpimndyneXKhBipCV6JNlG8Q0jFwmbs1U=");
+    assert_ne!(signature, other_resource);
+}
+
+#[test]
+fn keys_with_disallowed_characters_are_invalid() {
+    assert!(is_valid_key("web-1"));
+    assert!(is_valid_key("2024-01-01T00:00:00Z"));
+    assert!(!is_valid_key("a/b"));
+    assert!(!is_valid_key("a\\b"));
+    assert!(!is_valid_key("a#b"));
+    assert!(!is_valid_key("a?b"));
+    assert!(!is_valid_key("a\tb"));
+    assert!(!is_valid_key(&"a".repeat(1025)));
+}
+
+#[test]
+fn events_with_invalid_keys_are_dropped() {
+    let mut log = LogEvent::from("message");
+    log.insert("host", "web/1");
+    log.insert("id", "1");
+    assert!(entity_encoder().encode_event(Event::from(log)).is_none());
+
+    let mut log = LogEvent::from("message");
+    log.insert("host", "web-1");
+    assert!(entity_encoder().encode_event(Event::from(log)).is_none());
+}
+
+#[test]
+fn property_names_must_be_identifiers() {
+    assert!(is_valid_property_name("message"));
+    assert!(is_valid_property_name("_source_type2"));
+    assert!(!is_valid_property_name("@timestamp"));
+    assert!(!is_valid_property_name("2xx"));
+    assert!(!is_valid_property_name("host.name"));
+    assert!(!is_valid_property_name(""));
+    assert!(!is_valid_property_name(&"a".repeat(256)));
+}
+
+#[test]
+fn events_with_invalid_property_names_are_rejected() {
+    let mut log = LogEvent::from("message");
+    log.insert("host", "web-1");
+    log.insert("id", "1");
+    log.insert("\"@timestamp\"", "now");
+    assert!(entity_encoder().encode_event(Event::from(log)).is_none());
+}
+
+#[test]
+fn fields_are_mapped_to_typed_properties() {
+    let mut log = LogEvent::default();
+    log.insert("message", "hello");
+    log.insert("status", 200);
+    log.insert("duration", Value::from_f64_or_zero(1.5));
+    log.insert("success", true);
+    log.insert(
+        "timestamp",
+        Utc.timestamp_millis_opt(1_700_000_000_123).unwrap(),
+    );
+    log.insert("tags", vec!["a", "b"]);
+    log.insert("missing", Value::Null);
+    log.insert("RowKey", "ignored");
+
+    let properties = entity_properties(&log, "web-1", "1");
+
+    assert_eq!(
+        serde_json::Value::Object(properties),
+        json!({
+            "PartitionKey": "web-1",
+            "RowKey": "1",
+            "message": "hello",
+            "status": "200",
+            "status@odata.type": "Edm.Int64",
+            "duration": 1.5,
+            "duration@odata.type": "Edm.Double",
+            "success": true,
+            "timestamp": "2023-11-14T22:13:20.123000Z",
+            "timestamp@odata.type": "Edm.DateTime",
+            "tags": "[\"a\",\"b\"]",
+        })
+    );
+}
+
+fn fold_batch(write_mode: WriteMode, entities: Vec<TableEntity>) -> Vec<serde_json::Value> {
+    let (partition_key, entities) =
+        fold_duplicate_keys(("web-1".to_string(), entities), write_mode);
+    assert_eq!(partition_key, "web-1");
+    entities
+        .iter()
+        .map(|entity| {
+            let mut properties: serde_json::Value = serde_json::from_slice(&entity.body).unwrap();
+            // The timestamps of the events differ, and aren't what is being tested.
+            let properties_map = properties.as_object_mut().unwrap();
+            properties_map.remove("timestamp");
+            properties_map.remove("timestamp@odata.type");
+            properties
+        })
+        .collect()
+}
+
+fn duplicate_entities() -> Vec<TableEntity> {
+    let mut log = LogEvent::from("third");
+    log.insert("host", "web-1");
+    log.insert("id", "1");
+    log.insert("status", 200);
+    vec![
+        entity("1", "first"),
+        entity("2", "second"),
+        entity_encoder().encode_event(Event::from(log)).unwrap(),
+        entity("1", "fourth"),
+    ]
+}
+
+#[test]
+fn duplicate_keys_are_replaced_by_the_last_entity() {
+    let entities = fold_batch(WriteMode::InsertOrReplace, duplicate_entities());
+
+    assert_eq!(
+        entities,
+        vec![
+            json!({ "PartitionKey": "web-1", "RowKey": "1", "host": "web-1", "id": "1", "message": "fourth" }),
+            json!({ "PartitionKey": "web-1", "RowKey": "2", "host": "web-1", "id": "2", "message": "second" }),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_keys_are_merged_in_order() {
+    let mut log = LogEvent::from("fifth");
+    log.insert("host", "web-1");
+    log.insert("id", "1");
+    log.insert("status", "ok");
+    let mut entities = duplicate_entities();
+    entities.push(entity_encoder().encode_event(Event::from(log)).unwrap());
+
+    let entities = fold_batch(WriteMode::InsertOrMerge, entities);
+
+    assert_eq!(
+        entities,
+        vec![
+            json!({ "PartitionKey": "web-1", "RowKey": "1", "host": "web-1", "id": "1", "message": "fifth", "status": "ok" }),
+            json!({ "PartitionKey": "web-1", "RowKey": "2", "host": "web-1", "id": "2", "message": "second" }),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_keys_are_inserted_once() {
+    let entities = fold_batch(WriteMode::Insert, duplicate_entities());
+
+    assert_eq!(
+        entities,
+        vec![
+            json!({ "PartitionKey": "web-1", "RowKey": "1", "host": "web-1", "id": "1", "message": "first" }),
+            json!({ "PartitionKey": "web-1", "RowKey": "2", "host": "web-1", "id": "2", "message": "second" }),
+        ]
+    );
+}
+
+fn encode_batch(write_mode: WriteMode) -> String {
+    let encoder = TableBatchEncoder::new(
+        "https://account.table.core.windows.net/logs".to_string(),
+        write_mode,
+    );
+    let mut body = Vec::new();
+    encoder
+        .encode_input(vec![entity("it's", "hello")], &mut body)
+        .unwrap();
+    String::from_utf8(body).unwrap()
+}
+
+#[test]
+fn batch_body_is_a_single_changeset() {
+    let body = encode_batch(WriteMode::Insert);
+    let lines = body.split("\r\n").collect::<Vec<_>>();
+
+    assert!(lines[0].starts_with("--batch_"));
+    assert!(lines[1].starts_with("Content-Type: multipart/mixed; boundary=changeset_"));
+    assert!(lines.contains(&"POST https://account.table.core.windows.net/logs HTTP/1.1"));
+    assert!(lines.contains(&"Prefer: return-no-content"));
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|line| line.starts_with("--changeset_"))
+            .count(),
+        2
+    );
+    assert!(body.ends_with("--\r\n"));
+}
+
+#[test]
+fn batch_operations_address_entities_by_key() {
+    let body = encode_batch(WriteMode::InsertOrReplace);
+    assert!(body.contains(
+        "PUT https://account.table.core.windows.net/logs(PartitionKey='web-1',RowKey='it''s') HTTP/1.1\r\n"
+    ));
+
+    let body = encode_batch(WriteMode::InsertOrMerge);
+    assert!(body.contains(
+        "MERGE https://account.table.core.windows.net/logs(PartitionKey='web-1',RowKey='it''s') HTTP/1.1\r\n"
+    ));
+}
+
+#[test]
+fn batch_response_status() {
+    let success = "--batchresponse_1\r\nContent-Type: multipart/mixed; boundary=changesetresponse_1\r\n\r\n--changesetresponse_1\r\nContent-Type: application/http\r\n\r\nHTTP/1.1 204 No Content\r\n";
+    let failure = "--batchresponse_1\r\nContent-Type: multipart/mixed; boundary=changesetresponse_1\r\n\r\n--changesetresponse_1\r\nContent-Type: application/http\r\n\r\nHTTP/1.1 409 Conflict\r\n";
+
+    assert_eq!(
+        operation_status(success),
+        Some(http::StatusCode::NO_CONTENT)
+    );
+    assert_eq!(operation_status(failure), Some(http::StatusCode::CONFLICT));
+    assert_eq!(operation_status(""), None);
+}
+
+#[test]
+fn batch_response_failed_operation() {
+    let failure = "--batchresponse_1\r\nContent-Type: multipart/mixed; boundary=changesetresponse_1\r\n\r\n--changesetresponse_1\r\nContent-Type: application/http\r\n\r\nHTTP/1.1 409 Conflict\r\nContent-ID: 3\r\nContent-Type: application/json;odata=minimalmetadata;charset=utf-8\r\n\r\n{\"odata.error\":{\"code\":\"EntityAlreadyExists\",\"message\":{\"lang\":\"en-US\",\"value\":\"2:The specified entity already exists.\\nRequestId:1\"}}}\r\n--changesetresponse_1--\r\n";
+
+    assert_eq!(failed_operation_index(failure), Some(2));
+    assert_eq!(failed_operation_index(""), None);
+}
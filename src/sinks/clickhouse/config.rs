@@ -1,6 +1,7 @@
 //! Configuration for the `Clickhouse` sink.
 
 use super::{
+    native::{Connector, NativeRetryLogic, NativeService, NativeSink},
     request_builder::ClickhouseRequestBuilder,
     service::{ClickhouseRetryLogic, ClickhouseServiceRequestBuilder},
     sink::{ClickhouseSink, PartitionKey},
@@ -49,6 +50,35 @@ impl fmt::Display for Format {
     }
 }
 
+/// The interface of ClickHouse that events are inserted through.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Protocol {
+    /// The [HTTP interface][http], which inserts events as rows of JSON in the configured `format`.
+    ///
+    /// [http]: https://clickhouse.com/docs/en/interfaces/http
+    #[derivative(Default)]
+    Http,
+
+    /// The [native TCP interface][native], which inserts events as blocks of columns.
+    ///
+    /// The endpoint is that of the TCP interface, such as `tcp://localhost:9000`, which is
+    /// connected to over TLS when `tls` is set. As the schema of the table is fetched before the
+    /// first insert, and again whenever it changes, `database` and `table` can't be templated.
+    ///
+    /// Each column of the table is filled from the field of the same name, which may be a path to a
+    /// nested field such as `host.name`. Columns that no event of a batch has a field for are left
+    /// out of its insert, so that ClickHouse fills them with their `DEFAULT` expression. Other
+    /// missing fields are written as the default value of their column, and events with fields that
+    /// can't be converted to the type of their column are dropped. The `compression`, `format`,
+    /// `skip_unknown_fields`, and `date_time_best_effort` options only apply to the HTTP interface.
+    ///
+    /// [native]: https://clickhouse.com/docs/en/interfaces/tcp
+    Native,
+}
+
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse", "Deliver log data to a ClickHouse database."))]
 #[derive(Clone, Debug, Default)]
@@ -67,6 +97,10 @@ pub struct ClickhouseConfig {
     #[configurable(metadata(docs::examples = "mydatabase"))]
     pub database: Option<Template>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: Protocol,
+
     /// The format to parse input data.
     #[serde(default)]
     pub format: Format,
@@ -120,6 +154,10 @@ impl_generate_config_from_default!(ClickhouseConfig);
 #[typetag::serde(name = "clickhouse")]
 impl SinkConfig for ClickhouseConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.protocol == Protocol::Native {
+            return self.build_native();
+        }
+
         let endpoint = self.endpoint.with_default_parts().uri;
 
         let auth = self.auth.choose_one(&self.endpoint.auth)?;
@@ -148,11 +186,7 @@ impl SinkConfig for ClickhouseConfig {
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
-        let database = self.database.clone().unwrap_or_else(|| {
-            "default"
-                .try_into()
-                .expect("'default' should be a valid template")
-        });
+        let database = self.database();
 
         let request_builder = ClickhouseRequestBuilder {
             compression: self.compression,
@@ -188,6 +222,52 @@ impl SinkConfig for ClickhouseConfig {
     }
 }
 
+impl ClickhouseConfig {
+    fn database(&self) -> Template {
+        self.database.clone().unwrap_or_else(|| {
+            "default"
+                .try_into()
+                .expect("'default' should be a valid template")
+        })
+    }
+
+    fn build_native(&self) -> crate::Result<(VectorSink, Healthcheck)> {
+        let database = self.database();
+        if database.is_dynamic() || self.table.is_dynamic() {
+            return Err("The native protocol requires a static database and table.".into());
+        }
+
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+        let connector = Connector::new(
+            &self.endpoint,
+            auth,
+            &self.tls,
+            database.get_ref().to_owned(),
+            self.insert_random_shard,
+        )?;
+
+        // The schema of the table is fetched by the healthcheck, or else before the first insert.
+        let service = NativeService::new(
+            connector,
+            database.get_ref().to_owned(),
+            self.table.get_ref().to_owned(),
+        );
+        let healthcheck = Box::pin(service.clone().healthcheck());
+
+        let request_limits = self.request.into_settings();
+
+        let service = ServiceBuilder::new()
+            .settings(request_limits, NativeRetryLogic)
+            .service(service);
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let sink = NativeSink::new(batch_settings, service, self.encoding.clone());
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+}
+
 fn get_healthcheck_uri(endpoint: &Uri) -> String {
     let mut uri = endpoint.to_string();
     if !uri.ends_with('/') {
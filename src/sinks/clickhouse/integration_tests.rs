@@ -19,7 +19,7 @@ use vector_lib::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event,
 use vector_lib::lookup::PathPrefix;
 use warp::Filter;
 
-use super::*;
+use super::{config::Protocol, *};
 use crate::{
     codecs::{TimestampFormat, Transformer},
     config::{log_schema, SinkConfig, SinkContext},
@@ -34,6 +34,10 @@ fn clickhouse_address() -> String {
     std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
 }
 
+fn clickhouse_native_address() -> String {
+    std::env::var("CLICKHOUSE_NATIVE_ADDRESS").unwrap_or_else(|_| "tcp://localhost:9000".into())
+}
+
 #[tokio::test]
 async fn insert_events() {
     trace_init();
@@ -391,6 +395,111 @@ async fn templated_table() {
     }
 }
 
+#[tokio::test]
+async fn insert_events_native() {
+    trace_init();
+
+    let table = gen_table();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(2);
+
+    let config = ClickhouseConfig {
+        endpoint: clickhouse_native_address().parse().unwrap(),
+        protocol: Protocol::Native,
+        table: table.clone().try_into().unwrap(),
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(clickhouse_address());
+    client
+        .create_table(
+            &table,
+            "host String, timestamp String, message String, status Nullable(UInt16), \
+            level String DEFAULT 'info'",
+        )
+        .await;
+
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let (mut first, mut receiver) = make_event();
+    first.as_mut_log().insert("status", 200);
+    let (second, _) = make_event();
+
+    run_and_assert_sink_compliance(sink, stream::iter(vec![first, second]), &SINK_TAGS).await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(2, output.rows);
+    let mut statuses = output
+        .data
+        .iter()
+        .map(|row| row["status"].clone())
+        .collect::<Vec<_>>();
+    statuses.sort_by_key(Value::is_null);
+    assert_eq!(statuses, vec![Value::from(200), Value::Null]);
+    for row in &output.data {
+        assert_eq!(row["message"], "raw log line");
+        // No event has a `level` field, so the column is filled by its `DEFAULT` expression.
+        assert_eq!(row["level"], "info");
+    }
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn native_fetches_changed_schema() {
+    trace_init();
+
+    let table = gen_table();
+
+    let config = ClickhouseConfig {
+        endpoint: clickhouse_native_address().parse().unwrap(),
+        protocol: Protocol::Native,
+        table: table.clone().try_into().unwrap(),
+        request: TowerRequestConfig {
+            retry_attempts: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(clickhouse_address());
+    client
+        .create_table(
+            &table,
+            "host String, timestamp String, message String, status UInt16",
+        )
+        .await;
+
+    // The healthcheck fetches the schema that the first block is built with.
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    client
+        .execute(&format!(
+            "ALTER TABLE {} MODIFY COLUMN status String",
+            table
+        ))
+        .await;
+
+    let (mut input_event, mut receiver) = make_event();
+    input_event.as_mut_log().insert("status", 200);
+
+    run_and_assert_sink_compliance(sink, stream::once(ready(input_event)), &SINK_TAGS).await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+    assert_eq!(output.data[0]["status"], "200");
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
 fn make_event() -> (Event, BatchStatusReceiver) {
     let (batch, receiver) = BatchNotifier::new_with_receiver();
     let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
@@ -431,6 +540,20 @@ impl ClickhouseClient {
         }
     }
 
+    async fn execute(&self, query: &str) {
+        let response = self
+            .client
+            .post(&self.host)
+            .body(query.to_owned())
+            .send()
+            .await
+            .unwrap();
+
+        if !response.status().is_success() {
+            panic!("query failed: {}", response.text().await.unwrap())
+        }
+    }
+
     async fn select_all(&self, table: &str) -> QueryResponse {
         let response = self
             .client
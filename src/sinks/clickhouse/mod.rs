@@ -7,11 +7,15 @@
 //! `INSERT INTO my_db.my_table FORMAT JSONEachRow`. The event payload is encoded as new-line
 //! delimited JSON.
 //!
+//! Events can also be sent using the native TCP interface, which inserts them as blocks of columns
+//! in the binary layout of the table that they're inserted into. See the [`native`] module.
+//!
 //! This sink only supports logs for now but could support metrics and traces as well in the future.
 
 mod config;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
+mod native;
 mod request_builder;
 mod service;
 mod sink;
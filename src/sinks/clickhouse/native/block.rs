@@ -0,0 +1,731 @@
+//! Encoding of batches of events as the columnar blocks of the native protocol.
+
+use std::{
+    borrow::Cow,
+    fmt,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+use vector_lib::{
+    config::telemetry, request_metadata::GroupedCountByteSize, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    protocol::{put_block_header, put_string},
+    schema::{ColumnType, TableSchema},
+};
+use crate::{
+    event::{LogEvent, Value},
+    internal_events::ClickhouseColumnEncodingError,
+};
+
+/// A block with one row per event, whose columns are those of the table that the events are
+/// inserted into.
+///
+/// Each column is filled from the event field of the same name, or else from the nested field that
+/// its name is the path of, such as `kubernetes.pod_name`. Columns that none of the events have a
+/// field for are left out of the block, so that ClickHouse fills them with their `DEFAULT`
+/// expression. Other missing fields are written as the default value of their column, or as null
+/// for `Nullable` columns, and events with a field that can't be converted to the type of its
+/// column are dropped.
+pub struct Block {
+    /// The columns of the block, in the order of the columns of the table.
+    pub schema: TableSchema,
+    pub rows: usize,
+    pub data: Vec<u8>,
+    /// The size of the events that were written to the block, which excludes dropped events.
+    pub byte_size: GroupedCountByteSize,
+}
+
+impl Block {
+    /// Encodes events as a block of the columns of `schema` that they fill.
+    pub fn encode(schema: &TableSchema, events: &[LogEvent]) -> Self {
+        let mut columns = schema
+            .columns
+            .iter()
+            .filter(|column| events.iter().any(|log| field(log, &column.name).is_some()))
+            .cloned()
+            .collect::<Vec<_>>();
+        // Inserts need at least one column, which is every column if none of them are filled.
+        if columns.is_empty() {
+            columns = schema.columns.clone();
+        }
+        let schema = TableSchema { columns };
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut buffers = schema
+            .columns
+            .iter()
+            .map(|column| ColumnBuffer::new(&column.column_type))
+            .collect::<Vec<_>>();
+        let mut rows = 0;
+
+        for log in events {
+            match append_row(&schema, &mut buffers, rows, log) {
+                Ok(()) => {
+                    byte_size.add_event(log, log.estimated_json_encoded_size_of());
+                    rows += 1;
+                }
+                Err((column, error)) => emit!(ClickhouseColumnEncodingError {
+                    column,
+                    error: error.to_string(),
+                }),
+            }
+        }
+
+        // An empty block ends an insert, so no block is sent when every event was dropped.
+        let mut data = Vec::new();
+        if rows > 0 {
+            put_block_header(&mut data, buffers.len(), rows);
+            for (column, buffer) in schema.columns.iter().zip(buffers) {
+                put_string(&mut data, column.name.as_bytes());
+                put_string(&mut data, column.column_type.to_string().as_bytes());
+                buffer.write(&mut data);
+            }
+        }
+
+        Self {
+            schema,
+            rows,
+            data,
+            byte_size,
+        }
+    }
+}
+
+/// Appends the fields of an event to the columns, or leaves the columns as they were if one of the
+/// fields can't be converted to the type of its column.
+fn append_row<'a>(
+    schema: &'a TableSchema,
+    buffers: &mut [ColumnBuffer],
+    rows: usize,
+    log: &LogEvent,
+) -> Result<(), (&'a str, ValueError)> {
+    for (position, column) in schema.columns.iter().enumerate() {
+        let value = field(log, &column.name);
+        if let Err(error) = buffers[position].append(&column.column_type, value) {
+            for buffer in &mut buffers[..position] {
+                buffer.truncate(rows);
+            }
+            return Err((&column.name, error));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the field of an event that fills the column of `name`.
+fn field<'a>(log: &'a LogEvent, name: &str) -> Option<&'a Value> {
+    let fields = log.as_map()?;
+    fields.get(name).or_else(|| {
+        let mut path = name.split('.');
+        let mut value = fields.get(path.next()?)?;
+        for segment in path {
+            value = value.as_object()?.get(segment)?;
+        }
+        Some(value)
+    })
+}
+
+/// A field that can't be converted to the type of its column.
+#[derive(Debug)]
+struct ValueError {
+    kind: String,
+    column_type: String,
+}
+
+impl ValueError {
+    fn new(column_type: &ColumnType, value: &Value) -> Self {
+        Self {
+            kind: value.kind_str().to_owned(),
+            column_type: column_type.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Can't convert {} to {}.", self.kind, self.column_type)
+    }
+}
+
+/// The values of a column, in the layout that the native protocol sends them in.
+enum ColumnBuffer {
+    /// Values of the same size, such as numbers.
+    Fixed { width: usize, data: Vec<u8> },
+    /// Strings, each prefixed with its size, along with the end of each of them in the data.
+    String { data: Vec<u8>, ends: Vec<usize> },
+    /// Whether each value is null, followed by the values, which are the default ones for nulls.
+    Nullable {
+        nulls: Vec<u8>,
+        values: Box<ColumnBuffer>,
+    },
+    /// The end of each array in the values, followed by the values of every array.
+    Array {
+        offsets: Vec<u64>,
+        values: Box<ColumnBuffer>,
+    },
+    /// The end of each map in the entries, followed by the keys, and the values, of every map.
+    Map {
+        offsets: Vec<u64>,
+        keys: Box<ColumnBuffer>,
+        values: Box<ColumnBuffer>,
+    },
+}
+
+impl ColumnBuffer {
+    fn new(column_type: &ColumnType) -> Self {
+        match column_type {
+            ColumnType::String => Self::String {
+                data: Vec::new(),
+                ends: Vec::new(),
+            },
+            ColumnType::Nullable(inner) => Self::Nullable {
+                nulls: Vec::new(),
+                values: Box::new(Self::new(inner)),
+            },
+            ColumnType::Array(inner) => Self::Array {
+                offsets: Vec::new(),
+                values: Box::new(Self::new(inner)),
+            },
+            ColumnType::Map(key, value) => Self::Map {
+                offsets: Vec::new(),
+                keys: Box::new(Self::new(key)),
+                values: Box::new(Self::new(value)),
+            },
+            column_type => Self::Fixed {
+                width: fixed_width(column_type),
+                data: Vec::new(),
+            },
+        }
+    }
+
+    fn rows(&self) -> usize {
+        match self {
+            Self::Fixed { width, data } => data.len() / width,
+            Self::String { ends, .. } => ends.len(),
+            Self::Nullable { nulls, .. } => nulls.len(),
+            Self::Array { offsets, .. } | Self::Map { offsets, .. } => offsets.len(),
+        }
+    }
+
+    /// Removes the values after the first `rows` ones.
+    fn truncate(&mut self, rows: usize) {
+        if rows >= self.rows() {
+            return;
+        }
+        match self {
+            Self::Fixed { width, data } => data.truncate(rows * *width),
+            Self::String { data, ends } => {
+                data.truncate(rows.checked_sub(1).map_or(0, |last| ends[last]));
+                ends.truncate(rows);
+            }
+            Self::Nullable { nulls, values } => {
+                nulls.truncate(rows);
+                values.truncate(rows);
+            }
+            Self::Array { offsets, values } => {
+                values.truncate(end_offset(offsets, rows));
+                offsets.truncate(rows);
+            }
+            Self::Map {
+                offsets,
+                keys,
+                values,
+            } => {
+                let end = end_offset(offsets, rows);
+                keys.truncate(end);
+                values.truncate(end);
+                offsets.truncate(rows);
+            }
+        }
+    }
+
+    /// Appends a value, or the default value of the column if there's none, or leaves the column as
+    /// it was if the value can't be converted to the type of the column.
+    fn append(
+        &mut self,
+        column_type: &ColumnType,
+        value: Option<&Value>,
+    ) -> Result<(), ValueError> {
+        let value = value.filter(|value| !matches!(value, Value::Null));
+        match (self, column_type) {
+            (Self::Nullable { nulls, values }, ColumnType::Nullable(inner)) => {
+                values.append(inner, value)?;
+                nulls.push(u8::from(value.is_none()));
+            }
+            (Self::Array { offsets, values }, ColumnType::Array(inner)) => {
+                let items = match value {
+                    None => &[][..],
+                    Some(Value::Array(items)) => items.as_slice(),
+                    Some(value) => return Err(ValueError::new(column_type, value)),
+                };
+                let rows = values.rows();
+                for item in items {
+                    if let Err(error) = values.append(inner, Some(item)) {
+                        values.truncate(rows);
+                        return Err(error);
+                    }
+                }
+                offsets.push((rows + items.len()) as u64);
+            }
+            (
+                Self::Map {
+                    offsets,
+                    keys,
+                    values,
+                },
+                ColumnType::Map(key_type, value_type),
+            ) => {
+                let entries = match value {
+                    None => None,
+                    Some(Value::Object(entries)) => Some(entries),
+                    Some(value) => return Err(ValueError::new(column_type, value)),
+                };
+                let rows = keys.rows();
+                for (key, value) in entries.into_iter().flatten() {
+                    let key = Value::from(key.as_str());
+                    let appended = keys
+                        .append(key_type, Some(&key))
+                        .and_then(|()| values.append(value_type, Some(value)));
+                    if let Err(error) = appended {
+                        keys.truncate(rows);
+                        values.truncate(rows);
+                        return Err(error);
+                    }
+                }
+                offsets.push((rows + entries.map_or(0, |entries| entries.len())) as u64);
+            }
+            (Self::String { data, ends }, _) => {
+                put_string(data, &value.map(to_bytes).unwrap_or_default());
+                ends.push(data.len());
+            }
+            (Self::Fixed { data, .. }, column_type) => append_fixed(data, column_type, value)?,
+            _ => unreachable!("columns are built for their type"),
+        }
+        Ok(())
+    }
+
+    fn write(&self, block: &mut Vec<u8>) {
+        match self {
+            Self::Fixed { data, .. } | Self::String { data, .. } => block.extend_from_slice(data),
+            Self::Nullable { nulls, values } => {
+                block.extend_from_slice(nulls);
+                values.write(block);
+            }
+            Self::Array { offsets, values } => {
+                for offset in offsets {
+                    block.extend_from_slice(&offset.to_le_bytes());
+                }
+                values.write(block);
+            }
+            Self::Map {
+                offsets,
+                keys,
+                values,
+            } => {
+                for offset in offsets {
+                    block.extend_from_slice(&offset.to_le_bytes());
+                }
+                keys.write(block);
+                values.write(block);
+            }
+        }
+    }
+}
+
+/// Returns the number of nested values of the first `rows` arrays or maps.
+fn end_offset(offsets: &[u64], rows: usize) -> usize {
+    rows.checked_sub(1).map_or(0, |last| offsets[last] as usize)
+}
+
+const fn fixed_width(column_type: &ColumnType) -> usize {
+    match column_type {
+        ColumnType::Bool | ColumnType::UInt8 | ColumnType::Int8 | ColumnType::Enum8(_) => 1,
+        ColumnType::UInt16 | ColumnType::Int16 | ColumnType::Date | ColumnType::Enum16(_) => 2,
+        ColumnType::UInt32
+        | ColumnType::Int32
+        | ColumnType::Float32
+        | ColumnType::Date32
+        | ColumnType::DateTime
+        | ColumnType::Ipv4 => 4,
+        ColumnType::UInt64
+        | ColumnType::Int64
+        | ColumnType::Float64
+        | ColumnType::DateTime64(_) => 8,
+        ColumnType::Uuid | ColumnType::Ipv6 => 16,
+        ColumnType::FixedString(length) => *length,
+        ColumnType::String
+        | ColumnType::Nullable(_)
+        | ColumnType::Array(_)
+        | ColumnType::Map(_, _) => 0,
+    }
+}
+
+fn append_fixed(
+    data: &mut Vec<u8>,
+    column_type: &ColumnType,
+    value: Option<&Value>,
+) -> Result<(), ValueError> {
+    let invalid = || ValueError::new(column_type, value.unwrap_or(&Value::Null));
+    match column_type {
+        ColumnType::Bool => data.push(u8::from(convert(column_type, value, to_bool)?)),
+        ColumnType::UInt8 => data.push(convert(column_type, value, to_integer::<u8>)?),
+        ColumnType::UInt16 => put(data, convert(column_type, value, to_integer::<u16>)?),
+        ColumnType::UInt32 => put(data, convert(column_type, value, to_integer::<u32>)?),
+        ColumnType::UInt64 => put(data, convert(column_type, value, to_integer::<u64>)?),
+        ColumnType::Int8 => put(data, convert(column_type, value, to_integer::<i8>)?),
+        ColumnType::Int16 => put(data, convert(column_type, value, to_integer::<i16>)?),
+        ColumnType::Int32 => put(data, convert(column_type, value, to_integer::<i32>)?),
+        ColumnType::Int64 => put(data, convert(column_type, value, to_integer::<i64>)?),
+        ColumnType::Float32 => put(
+            data,
+            convert(column_type, value, |value| {
+                to_float(value).map(|float| float as f32)
+            })?,
+        ),
+        ColumnType::Float64 => put(data, convert(column_type, value, to_float)?),
+        ColumnType::FixedString(length) => {
+            let bytes = value.map(to_bytes).unwrap_or_default();
+            if bytes.len() > *length {
+                return Err(invalid());
+            }
+            data.extend_from_slice(&bytes);
+            data.resize(data.len() + length - bytes.len(), 0);
+        }
+        ColumnType::Date => put(
+            data,
+            convert(column_type, value, |value| {
+                u16::try_from(to_days(value)?).ok()
+            })?,
+        ),
+        ColumnType::Date32 => put(
+            data,
+            convert(column_type, value, |value| {
+                i32::try_from(to_days(value)?).ok()
+            })?,
+        ),
+        ColumnType::DateTime => put(
+            data,
+            convert(column_type, value, |value| {
+                u32::try_from(to_timestamp(value)?.timestamp()).ok()
+            })?,
+        ),
+        ColumnType::DateTime64(precision) => put(
+            data,
+            convert(column_type, value, |value| {
+                let timestamp = to_timestamp(value)?;
+                let subseconds = timestamp.timestamp_subsec_nanos() / 10_u32.pow(9 - precision);
+                timestamp
+                    .timestamp()
+                    .checked_mul(10_i64.pow(*precision))?
+                    .checked_add(i64::from(subseconds))
+            })?,
+        ),
+        ColumnType::Uuid => {
+            let uuid = convert(column_type, value, to_uuid)?.as_u128();
+            put(data, (uuid >> 64) as u64);
+            put(data, uuid as u64);
+        }
+        ColumnType::Ipv4 => put(
+            data,
+            convert(column_type, value, |value| {
+                to_str(value)?.parse::<Ipv4Addr>().ok().map(u32::from)
+            })?,
+        ),
+        ColumnType::Ipv6 => {
+            let address = match value {
+                None => [0; 16],
+                Some(value) => match to_str(value).and_then(|value| value.parse().ok()) {
+                    Some(IpAddr::V4(address)) => address.to_ipv6_mapped().octets(),
+                    Some(IpAddr::V6(address)) => address.octets(),
+                    None => return Err(invalid()),
+                },
+            };
+            data.extend_from_slice(&address);
+        }
+        ColumnType::Enum8(values) => put(data, to_enum(values, value).ok_or_else(invalid)?),
+        ColumnType::Enum16(values) => put(data, to_enum(values, value).ok_or_else(invalid)?),
+        ColumnType::String
+        | ColumnType::Nullable(_)
+        | ColumnType::Array(_)
+        | ColumnType::Map(_, _) => unreachable!("only fixed-size values are appended"),
+    }
+    Ok(())
+}
+
+/// Converts a value to a column type with `conversion`, or returns the default value of the type if
+/// there's none.
+fn convert<T: Default>(
+    column_type: &ColumnType,
+    value: Option<&Value>,
+    conversion: impl FnOnce(&Value) -> Option<T>,
+) -> Result<T, ValueError> {
+    match value {
+        None => Ok(T::default()),
+        Some(value) => conversion(value).ok_or_else(|| ValueError::new(column_type, value)),
+    }
+}
+
+/// Appends a number in little-endian order.
+fn put<T: LittleEndian>(data: &mut Vec<u8>, value: T) {
+    value.put_le(data);
+}
+
+trait LittleEndian {
+    fn put_le(self, data: &mut Vec<u8>);
+}
+
+macro_rules! impl_little_endian {
+    ($($t:ty),*) => {
+        $(
+            impl LittleEndian for $t {
+                fn put_le(self, data: &mut Vec<u8>) {
+                    data.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_little_endian!(u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+fn to_str(value: &Value) -> Option<&str> {
+    match value {
+        Value::Bytes(bytes) => std::str::from_utf8(bytes).ok().map(str::trim),
+        _ => None,
+    }
+}
+
+/// Converts strings as they are, arrays and objects to JSON, and other values to their string
+/// representation.
+fn to_bytes(value: &Value) -> Cow<'_, [u8]> {
+    match value {
+        Value::Bytes(bytes) => Cow::Borrowed(bytes),
+        Value::Array(_) | Value::Object(_) => {
+            Cow::Owned(serde_json::to_vec(value).expect("values are serializable to JSON"))
+        }
+        value => Cow::Owned(value.to_string_lossy().into_owned().into_bytes()),
+    }
+}
+
+fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(boolean) => Some(*boolean),
+        value => to_str(value)?.parse().ok(),
+    }
+}
+
+fn to_integer<T: TryFrom<i64> + std::str::FromStr>(value: &Value) -> Option<T> {
+    match value {
+        Value::Integer(integer) => T::try_from(*integer).ok(),
+        Value::Boolean(boolean) => T::try_from(i64::from(*boolean)).ok(),
+        value => to_str(value)?.parse().ok(),
+    }
+}
+
+fn to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Integer(integer) => Some(*integer as f64),
+        value => to_str(value)?.parse().ok(),
+    }
+}
+
+/// Converts timestamps, RFC 3339 strings, and integers of seconds since the Unix epoch.
+fn to_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => DateTime::from_timestamp(*seconds, 0),
+        value => DateTime::parse_from_rfc3339(to_str(value)?)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+    }
+}
+
+/// Converts dates to the number of days since the Unix epoch, from timestamps, `YYYY-MM-DD` and
+/// RFC 3339 strings, and integers of days since the Unix epoch.
+fn to_days(value: &Value) -> Option<i64> {
+    let date = match value {
+        Value::Integer(days) => return Some(*days),
+        value => match to_str(value).map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d")) {
+            Some(Ok(date)) => date,
+            _ => to_timestamp(value)?.date_naive(),
+        },
+    };
+    Some(
+        date.signed_duration_since(DateTime::UNIX_EPOCH.date_naive())
+            .num_days(),
+    )
+}
+
+fn to_uuid(value: &Value) -> Option<Uuid> {
+    Uuid::parse_str(to_str(value)?).ok()
+}
+
+/// Converts the names and values of an enum, or returns its default value, which is the lowest
+/// one, if there's no value.
+fn to_enum<T: Copy + Ord + TryFrom<i64>>(
+    values: &[(String, T)],
+    value: Option<&Value>,
+) -> Option<T> {
+    let mut values = values.iter();
+    match value {
+        None => values.map(|(_, value)| *value).min(),
+        Some(Value::Integer(integer)) => {
+            let integer = T::try_from(*integer).ok()?;
+            values.find(|(_, value)| *value == integer).map(|_| integer)
+        }
+        Some(value) => {
+            let name = to_str(value)?;
+            values
+                .find(|(candidate, _)| candidate == name)
+                .map(|(_, value)| *value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::clickhouse::native::schema::Column;
+
+    fn schema(columns: &[(&str, &str)]) -> TableSchema {
+        let columns = columns
+            .iter()
+            .map(|(name, column_type)| Column {
+                name: name.to_string(),
+                column_type: ColumnType::parse(column_type).unwrap(),
+            })
+            .collect();
+        TableSchema { columns }
+    }
+
+    fn encode(schema: &TableSchema, events: Vec<LogEvent>) -> Vec<u8> {
+        Block::encode(schema, &events).data
+    }
+
+    fn header(columns: usize, rows: usize) -> Vec<u8> {
+        let mut header = Vec::new();
+        put_block_header(&mut header, columns, rows);
+        header
+    }
+
+    #[test]
+    fn encodes_columns() {
+        let schema = schema(&[
+            ("message", "LowCardinality(String)"),
+            ("status", "Nullable(UInt16)"),
+            ("tags", "Array(String)"),
+        ]);
+        let mut event = LogEvent::from("hello");
+        event.insert("status", 200);
+        event.insert("tags", vec!["a", "bc"]);
+        let events = vec![event, LogEvent::from("hi")];
+
+        let mut expected = header(3, 2);
+        expected.extend_from_slice(b"\x07message\x06String\x05hello\x02hi");
+        expected.extend_from_slice(b"\x06status\x10Nullable(UInt16)\x00\x01\xc8\x00\x00\x00");
+        expected.extend_from_slice(b"\x04tags\x0dArray(String)");
+        expected.extend_from_slice(&2_u64.to_le_bytes());
+        expected.extend_from_slice(&2_u64.to_le_bytes());
+        expected.extend_from_slice(b"\x01a\x02bc");
+
+        assert_eq!(encode(&schema, events), expected);
+    }
+
+    #[test]
+    fn encodes_nested_fields_and_maps() {
+        let schema = schema(&[
+            ("kubernetes.pod_name", "String"),
+            ("labels", "Map(String, UInt8)"),
+        ]);
+        let mut event = LogEvent::default();
+        event.insert("kubernetes.pod_name", "pod");
+        event.insert("labels.a", 1);
+        event.insert("labels.b", 2);
+
+        let mut expected = header(2, 1);
+        expected.extend_from_slice(b"\x13kubernetes.pod_name\x06String\x03pod");
+        expected.extend_from_slice(b"\x06labels\x12Map(String, UInt8)");
+        expected.extend_from_slice(&2_u64.to_le_bytes());
+        expected.extend_from_slice(b"\x01a\x01b\x01\x02");
+
+        assert_eq!(encode(&schema, vec![event]), expected);
+    }
+
+    #[test]
+    fn encodes_times() {
+        let schema = schema(&[
+            ("date", "Date"),
+            ("time", "DateTime('UTC')"),
+            ("precise", "DateTime64(3)"),
+        ]);
+        let timestamp = DateTime::parse_from_rfc3339("2024-01-02T03:04:05.678Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut event = LogEvent::default();
+        event.insert("date", "2024-01-02");
+        event.insert("time", timestamp);
+        event.insert("precise", "2024-01-02T03:04:05.678Z");
+
+        let mut expected = header(3, 1);
+        expected.extend_from_slice(b"\x04date\x04Date");
+        expected.extend_from_slice(&19724_u16.to_le_bytes());
+        expected.extend_from_slice(b"\x04time\x08DateTime");
+        expected.extend_from_slice(&1704164645_u32.to_le_bytes());
+        expected.extend_from_slice(b"\x07precise\x0dDateTime64(3)");
+        expected.extend_from_slice(&1704164645678_i64.to_le_bytes());
+
+        assert_eq!(encode(&schema, vec![event]), expected);
+    }
+
+    #[test]
+    fn drops_events_with_invalid_fields() {
+        let schema = schema(&[
+            ("message", "String"),
+            ("tags", "Array(UInt8)"),
+            ("level", "Enum8('info' = 1, 'error' = 2)"),
+        ]);
+        let mut invalid_tag = LogEvent::from("invalid tag");
+        invalid_tag.insert("tags", vec![Value::from(1), Value::from(1000)]);
+        let mut invalid_level = LogEvent::from("invalid level");
+        invalid_level.insert("level", "debug");
+        let mut valid = LogEvent::from("valid");
+        valid.insert("level", "error");
+        let events = vec![invalid_tag, valid, invalid_level];
+
+        let mut expected = header(3, 1);
+        expected.extend_from_slice(b"\x07message\x06String\x05valid");
+        expected.extend_from_slice(b"\x04tags\x0cArray(UInt8)");
+        expected.extend_from_slice(&0_u64.to_le_bytes());
+        expected.extend_from_slice(b"\x05level\x1eEnum8('info' = 1, 'error' = 2)\x02");
+
+        assert_eq!(encode(&schema, events), expected);
+
+        let events = vec![LogEvent::from("only invalid")];
+        let schema = self::schema(&[("message", "UInt8")]);
+        assert!(encode(&schema, events).is_empty());
+    }
+
+    #[test]
+    fn leaves_out_unfilled_columns() {
+        let schema = schema(&[("id", "UUID"), ("message", "String"), ("status", "UInt16")]);
+        let mut event = LogEvent::from("hello");
+        event.insert("status", 200);
+        let block = Block::encode(&schema, &[event, LogEvent::from("hi")]);
+
+        let mut expected = header(2, 2);
+        expected.extend_from_slice(b"\x07message\x06String\x05hello\x02hi");
+        expected.extend_from_slice(b"\x06status\x06UInt16\xc8\x00\x00\x00");
+        assert_eq!(block.data, expected);
+        assert_eq!(
+            block.schema,
+            self::schema(&[("message", "String"), ("status", "UInt16")])
+        );
+
+        let block = Block::encode(&schema, &[LogEvent::default()]);
+        assert_eq!(block.schema, schema);
+    }
+}
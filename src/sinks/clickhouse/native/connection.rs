@@ -0,0 +1,343 @@
+//! Connections to the native TCP interface of ClickHouse.
+
+use std::net::SocketAddr;
+
+use bytes::{BufMut, BytesMut};
+use snafu::ResultExt;
+use tokio::{
+    io::{AsyncWriteExt, BufStream},
+    net::TcpStream,
+};
+
+use super::{
+    protocol::{
+        client, put_block_header, put_string, put_varint, read_block_header, read_string,
+        read_varint, server, skip_progress, REVISION,
+    },
+    Block, ConnectSnafu, DnsSnafu, NativeError, ServerException, TableSchema,
+};
+use crate::{
+    dns,
+    http::Auth,
+    sinks::util::UriSerde,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsConfig},
+};
+
+/// The port of the native TCP interface of ClickHouse.
+const DEFAULT_PORT: u16 = 9000;
+
+/// The port of the native TCP interface of ClickHouse, over TLS.
+const DEFAULT_TLS_PORT: u16 = 9440;
+
+/// The settings of every query, which make ClickHouse send and accept `LowCardinality` columns as
+/// columns of the type that they wrap.
+const QUERY_SETTINGS: [(&str, &str); 1] = [("low_cardinality_allow_in_native_format", "0")];
+
+/// Establishes authenticated connections to ClickHouse.
+#[derive(Clone, Debug)]
+pub struct Connector {
+    host: String,
+    port: u16,
+    tls: MaybeTlsSettings,
+    database: String,
+    user: String,
+    password: String,
+    insert_random_shard: bool,
+}
+
+impl Connector {
+    /// Creates a connector to the host of `endpoint`, over TLS if `tls` is set.
+    pub fn new(
+        endpoint: &UriSerde,
+        auth: Option<Auth>,
+        tls: &Option<TlsConfig>,
+        database: String,
+        insert_random_shard: bool,
+    ) -> crate::Result<Self> {
+        let host = endpoint
+            .uri
+            .host()
+            .ok_or("The endpoint of the native protocol must have a host.")?
+            .to_owned();
+        let (tls, default_port) = match tls {
+            Some(_) => (MaybeTlsSettings::tls_client(tls)?, DEFAULT_TLS_PORT),
+            None => (MaybeTlsSettings::Raw(()), DEFAULT_PORT),
+        };
+        let (user, password) = match auth {
+            Some(Auth::Basic { user, password }) => (user, password.inner().to_owned()),
+            Some(Auth::Bearer { .. }) => {
+                return Err("Bearer authentication isn't supported by the native protocol.".into());
+            }
+            None => ("default".to_owned(), String::new()),
+        };
+
+        Ok(Self {
+            host,
+            port: endpoint.uri.port_u16().unwrap_or(default_port),
+            tls,
+            database,
+            user,
+            password,
+            insert_random_shard,
+        })
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub async fn connect(&self) -> Result<Connection, NativeError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(NativeError::NoAddresses)?;
+        let addr = SocketAddr::new(ip, self.port);
+        let stream = self
+            .tls
+            .connect(&self.host, &addr)
+            .await
+            .context(ConnectSnafu)?;
+
+        let mut connection = Connection {
+            stream: BufStream::new(stream),
+            insert_random_shard: self.insert_random_shard,
+        };
+        connection.hello(self).await?;
+        Ok(connection)
+    }
+}
+
+/// A connection that ClickHouse has authenticated, which runs one query at a time.
+pub struct Connection {
+    stream: BufStream<MaybeTlsStream<TcpStream>>,
+    insert_random_shard: bool,
+}
+
+impl Connection {
+    async fn hello(&mut self, connector: &Connector) -> Result<(), NativeError> {
+        let mut packet = BytesMut::new();
+        put_varint(&mut packet, client::HELLO);
+        put_client_version(&mut packet);
+        put_string(&mut packet, connector.database.as_bytes());
+        put_string(&mut packet, connector.user.as_bytes());
+        put_string(&mut packet, connector.password.as_bytes());
+        self.send(&packet).await?;
+
+        match read_varint(&mut self.stream).await? {
+            server::HELLO => {
+                let _name = read_string(&mut self.stream).await?;
+                let _major = read_varint(&mut self.stream).await?;
+                let _minor = read_varint(&mut self.stream).await?;
+                let revision = read_varint(&mut self.stream).await?;
+                if revision < REVISION {
+                    return Err(NativeError::UnsupportedRevision { revision });
+                }
+                let _time_zone = read_string(&mut self.stream).await?;
+                let _display_name = read_string(&mut self.stream).await?;
+                let _patch = read_varint(&mut self.stream).await?;
+                Ok(())
+            }
+            packet => Err(self.unexpected(packet).await),
+        }
+    }
+
+    /// Checks that the connection is still usable.
+    pub async fn ping(&mut self) -> Result<(), NativeError> {
+        let mut packet = BytesMut::new();
+        put_varint(&mut packet, client::PING);
+        self.send(&packet).await?;
+
+        match read_varint(&mut self.stream).await? {
+            server::PONG => Ok(()),
+            packet => Err(self.unexpected(packet).await),
+        }
+    }
+
+    /// Fetches the schema of a table, which is that of the blocks that are inserted into it.
+    pub async fn fetch_schema(
+        &mut self,
+        database: &str,
+        table: &str,
+    ) -> Result<TableSchema, NativeError> {
+        let header = self.begin_insert(database, table, None).await?;
+        self.finish_insert().await?;
+        TableSchema::parse(header)
+    }
+
+    /// Inserts a block of rows into its columns of a table, if their types are still the ones that
+    /// the block was built with.
+    pub async fn insert(
+        &mut self,
+        database: &str,
+        table: &str,
+        block: &Block,
+    ) -> Result<(), NativeError> {
+        let header = self
+            .begin_insert(database, table, Some(&block.schema))
+            .await?;
+        if TableSchema::parse(header).ok().as_ref() != Some(&block.schema) {
+            // Nothing is inserted, and the connection can be used again.
+            self.finish_insert().await?;
+            return Err(NativeError::SchemaChanged {
+                table: table.to_owned(),
+            });
+        }
+
+        let mut packet = BytesMut::new();
+        put_varint(&mut packet, client::DATA);
+        put_string(&mut packet, b"");
+        packet.put_slice(&block.data);
+        self.send(&packet).await?;
+
+        self.finish_insert().await
+    }
+
+    /// Starts inserting into the columns of a table, or into all of them if they're not given, and
+    /// returns the names and types of the columns of the blocks that ClickHouse expects.
+    async fn begin_insert(
+        &mut self,
+        database: &str,
+        table: &str,
+        columns: Option<&TableSchema>,
+    ) -> Result<Vec<(String, String)>, NativeError> {
+        let mut query = format!("INSERT INTO {}.{}", quote(database), quote(table));
+        if let Some(columns) = columns {
+            let names = columns
+                .columns
+                .iter()
+                .map(|column| quote(&column.name))
+                .collect::<Vec<_>>();
+            query.push_str(&format!(" ({})", names.join(", ")));
+        }
+        query.push_str(" VALUES");
+
+        let mut packet = BytesMut::new();
+        put_varint(&mut packet, client::QUERY);
+        // The ID of the query, which ClickHouse generates.
+        put_string(&mut packet, b"");
+        // The client info: an initial query, without an initial user, query, or address, sent over
+        // TCP from this host.
+        packet.put_u8(1);
+        put_string(&mut packet, b"");
+        put_string(&mut packet, b"");
+        put_string(&mut packet, b"0.0.0.0:0");
+        packet.put_u8(1);
+        put_string(&mut packet, b"");
+        put_string(
+            &mut packet,
+            crate::get_hostname().unwrap_or_default().as_bytes(),
+        );
+        put_client_version(&mut packet);
+        // The quota key, and the patch version of the client.
+        put_string(&mut packet, b"");
+        put_varint(&mut packet, client_version(2));
+
+        let mut settings = QUERY_SETTINGS.to_vec();
+        if self.insert_random_shard {
+            settings.push(("insert_distributed_one_random_shard", "1"));
+        }
+        for (name, value) in settings {
+            put_string(&mut packet, name.as_bytes());
+            // The flags of the setting, which isn't important enough to fail the query on servers
+            // that don't know it.
+            put_varint(&mut packet, 0);
+            put_string(&mut packet, value.as_bytes());
+        }
+        put_string(&mut packet, b"");
+
+        // The query is processed to completion, and the data isn't compressed.
+        put_varint(&mut packet, 2);
+        put_varint(&mut packet, 0);
+        put_string(&mut packet, query.as_bytes());
+
+        // The end of the external tables of the query, of which there are none.
+        put_empty_block(&mut packet);
+        self.send(&packet).await?;
+
+        loop {
+            match read_varint(&mut self.stream).await? {
+                server::DATA => {
+                    let _table = read_string(&mut self.stream).await?;
+                    return read_block_header(&mut self.stream)
+                        .await?
+                        .ok_or(NativeError::UnexpectedRows);
+                }
+                server::TABLE_COLUMNS => {
+                    let _table = read_string(&mut self.stream).await?;
+                    let _columns = read_string(&mut self.stream).await?;
+                }
+                packet @ server::PROGRESS => skip_progress(&mut self.stream, packet).await?,
+                packet => return Err(self.unexpected(packet).await),
+            }
+        }
+    }
+
+    /// Ends an insert, once ClickHouse has inserted its blocks.
+    async fn finish_insert(&mut self) -> Result<(), NativeError> {
+        let mut packet = BytesMut::new();
+        put_empty_block(&mut packet);
+        self.send(&packet).await?;
+
+        loop {
+            match read_varint(&mut self.stream).await? {
+                server::END_OF_STREAM => return Ok(()),
+                server::TABLE_COLUMNS => {
+                    let _table = read_string(&mut self.stream).await?;
+                    let _columns = read_string(&mut self.stream).await?;
+                }
+                packet @ (server::PROGRESS | server::PROFILE_INFO) => {
+                    skip_progress(&mut self.stream, packet).await?
+                }
+                packet => return Err(self.unexpected(packet).await),
+            }
+        }
+    }
+
+    async fn send(&mut self, packet: &[u8]) -> Result<(), NativeError> {
+        self.stream.write_all(packet).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
+    /// Returns the error of a packet that isn't expected, which is the exception that it carries
+    /// if it's one.
+    async fn unexpected(&mut self, packet: u64) -> NativeError {
+        if packet != server::EXCEPTION {
+            return NativeError::UnexpectedPacket { packet };
+        }
+        match ServerException::read(&mut self.stream).await {
+            Ok(exception) => NativeError::Server { exception },
+            Err(source) => NativeError::Io { source },
+        }
+    }
+}
+
+/// Writes the name of the client, its version, and the revision of the protocol that it speaks.
+fn put_client_version(packet: &mut BytesMut) {
+    put_string(packet, b"Vector");
+    put_varint(packet, client_version(0));
+    put_varint(packet, client_version(1));
+    put_varint(packet, REVISION);
+}
+
+/// Returns the major, minor, or patch version of Vector.
+fn client_version(position: usize) -> u64 {
+    crate::built_info::PKG_VERSION
+        .split(['.', '-'])
+        .nth(position)
+        .and_then(|version| version.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Quotes the name of a database, table, or column.
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn put_empty_block(packet: &mut BytesMut) {
+    put_varint(packet, client::DATA);
+    put_string(packet, b"");
+    put_block_header(packet, 0, 0);
+}
@@ -0,0 +1,66 @@
+//! Inserting events through the [native TCP interface][native] of ClickHouse.
+//!
+//! Rather than encoding every event as a row of JSON text that ClickHouse parses, events are
+//! written column by column into the blocks of the native protocol, in the binary layout that
+//! ClickHouse stores them in. The columns of the blocks are those of the table that events are
+//! inserted into, whose schema is fetched before the first insert. Blocks are built when their
+//! events are inserted, so that an insert which finds that the schema changed is retried with a
+//! block of the new schema.
+//!
+//! [native]: https://clickhouse.com/docs/en/interfaces/tcp
+
+mod block;
+mod connection;
+mod protocol;
+mod schema;
+mod service;
+mod sink;
+
+use std::io;
+
+use snafu::Snafu;
+
+pub(super) use self::{
+    block::Block,
+    connection::Connector,
+    protocol::ServerException,
+    schema::TableSchema,
+    service::{NativeRequest, NativeRetryLogic, NativeService},
+    sink::NativeSink,
+};
+use crate::{dns, tls::TlsError};
+
+/// An error raised while talking to ClickHouse through the native protocol.
+#[derive(Debug, Snafu)]
+pub(super) enum NativeError {
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    Dns { source: dns::DnsError },
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+    #[snafu(display("Connect error: {}", source))]
+    Connect { source: TlsError },
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("{}", exception))]
+    Server { exception: ServerException },
+    #[snafu(display("Unexpected packet {} received from ClickHouse.", packet))]
+    UnexpectedPacket { packet: u64 },
+    #[snafu(display("Unexpected rows received from ClickHouse in a block header."))]
+    UnexpectedRows,
+    #[snafu(display(
+        "ClickHouse speaks revision {} of the native protocol, revision {} is required.",
+        revision,
+        protocol::REVISION
+    ))]
+    UnsupportedRevision { revision: u64 },
+    #[snafu(display("Unsupported type {:?} of column {:?}.", column_type, column))]
+    UnsupportedType { column: String, column_type: String },
+    #[snafu(display("The schema of table {:?} changed since the block was built.", table))]
+    SchemaChanged { table: String },
+}
+
+impl From<io::Error> for NativeError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+}
@@ -0,0 +1,230 @@
+//! Encoding of the values that make up the packets of the native protocol.
+//!
+//! Integers are either little-endian, or variable-length like in Protocol Buffers, and strings are
+//! prefixed with their variable-length size.
+
+use std::{fmt, io};
+
+use bytes::BufMut;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// The revision of the native protocol that the sink speaks.
+///
+/// Servers agree on the oldest revision that they and their client support, so the fields of the
+/// packets are those of this revision with any server that supports it.
+pub const REVISION: u64 = 54429;
+
+/// The longest string that is read from ClickHouse, which guards against allocating huge buffers
+/// for corrupted packets.
+const MAX_STRING_LENGTH: u64 = 16 * 1024 * 1024;
+
+/// The packets sent by the client.
+pub mod client {
+    pub const HELLO: u64 = 0;
+    pub const QUERY: u64 = 1;
+    pub const DATA: u64 = 2;
+    pub const PING: u64 = 4;
+}
+
+/// The packets sent by the server.
+pub mod server {
+    pub const HELLO: u64 = 0;
+    pub const DATA: u64 = 1;
+    pub const EXCEPTION: u64 = 2;
+    pub const PROGRESS: u64 = 3;
+    pub const PONG: u64 = 4;
+    pub const END_OF_STREAM: u64 = 5;
+    pub const PROFILE_INFO: u64 = 6;
+    pub const TABLE_COLUMNS: u64 = 11;
+}
+
+pub fn put_varint(buf: &mut impl BufMut, mut value: u64) {
+    while value >= 0x80 {
+        buf.put_u8(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.put_u8(value as u8);
+}
+
+pub fn put_string(buf: &mut impl BufMut, value: &[u8]) {
+    put_varint(buf, value.len() as u64);
+    buf.put_slice(value);
+}
+
+/// Writes the header of a block of `columns` columns of `rows` rows, which is followed by the name,
+/// type, and values of each column.
+pub fn put_block_header(buf: &mut impl BufMut, columns: usize, rows: usize) {
+    // The block info, which is made of numbered fields: the block isn't the overflow of a
+    // `GROUP BY`, and isn't a bucket of a two-level aggregation.
+    put_varint(buf, 1);
+    buf.put_u8(0);
+    put_varint(buf, 2);
+    buf.put_i32_le(-1);
+    put_varint(buf, 0);
+
+    put_varint(buf, columns as u64);
+    put_varint(buf, rows as u64);
+}
+
+pub async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = reader.read_u8().await?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_data("variable-length integer is too long"))
+}
+
+pub async fn read_string<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<String> {
+    let length = read_varint(reader).await?;
+    if length > MAX_STRING_LENGTH {
+        return Err(invalid_data("string is too long"));
+    }
+    let mut buf = vec![0; length as usize];
+    reader.read_exact(&mut buf).await?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Reads the names and types of the columns of a block without rows, such as the header of the
+/// blocks that an insert expects, or returns `None` if the block has rows.
+pub async fn read_block_header<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> io::Result<Option<Vec<(String, String)>>> {
+    loop {
+        match read_varint(reader).await? {
+            0 => break,
+            1 => {
+                reader.read_u8().await?;
+            }
+            2 => {
+                reader.read_i32_le().await?;
+            }
+            _ => return Err(invalid_data("unknown block info field")),
+        }
+    }
+
+    let columns = read_varint(reader).await?;
+    let rows = read_varint(reader).await?;
+    if rows > 0 {
+        return Ok(None);
+    }
+    let mut header = Vec::new();
+    for _ in 0..columns {
+        let name = read_string(reader).await?;
+        let column_type = read_string(reader).await?;
+        header.push((name, column_type));
+    }
+    Ok(Some(header))
+}
+
+/// Skips the progress of a query, or the profile of its execution.
+pub async fn skip_progress<R: AsyncRead + Unpin>(reader: &mut R, packet: u64) -> io::Result<()> {
+    if packet == server::PROFILE_INFO {
+        // Rows, blocks, and bytes, whether a limit was applied, the rows before the limit, and
+        // whether they were calculated.
+        for _ in 0..3 {
+            read_varint(reader).await?;
+        }
+        reader.read_u8().await?;
+        read_varint(reader).await?;
+        reader.read_u8().await?;
+    } else {
+        // Rows and bytes that were read, total rows to read, and rows and bytes that were written.
+        for _ in 0..5 {
+            read_varint(reader).await?;
+        }
+    }
+    Ok(())
+}
+
+/// An exception raised by ClickHouse, along with the exceptions that caused it.
+#[derive(Debug)]
+pub struct ServerException {
+    /// The [error code][codes] of the exception.
+    ///
+    /// [codes]: https://github.com/ClickHouse/ClickHouse/blob/master/src/Common/ErrorCodes.cpp
+    pub code: i32,
+    pub name: String,
+    pub message: String,
+}
+
+impl ServerException {
+    pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Self> {
+        // The message of the outermost exception already describes the nested ones.
+        let (exception, mut nested) = Self::read_one(reader).await?;
+        while nested {
+            nested = Self::read_one(reader).await?.1;
+        }
+        Ok(exception)
+    }
+
+    /// Reads an exception, and whether it's followed by a nested one.
+    async fn read_one<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<(Self, bool)> {
+        let code = reader.read_i32_le().await?;
+        let name = read_string(reader).await?;
+        let message = read_string(reader).await?;
+        let _stack_trace = read_string(reader).await?;
+        let nested = reader.read_u8().await? != 0;
+        Ok((
+            Self {
+                code,
+                name,
+                message,
+            },
+            nested,
+        ))
+    }
+}
+
+impl fmt::Display for ServerException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Code: {}. {}: {}", self.code, self.name, self.message)
+    }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn varints_roundtrip() {
+        for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+            let mut buf = Vec::new();
+            put_varint(&mut buf, value);
+            assert_eq!(read_varint(&mut buf.as_slice()).await.unwrap(), value);
+        }
+
+        let mut buf = Vec::new();
+        put_varint(&mut buf, 300);
+        assert_eq!(buf, [0xac, 0x02]);
+    }
+
+    #[tokio::test]
+    async fn reads_block_header() {
+        let mut buf = Vec::new();
+        put_block_header(&mut buf, 2, 0);
+        put_string(&mut buf, b"timestamp");
+        put_string(&mut buf, b"DateTime64(3)");
+        put_string(&mut buf, b"message");
+        put_string(&mut buf, b"String");
+
+        assert_eq!(
+            read_block_header(&mut buf.as_slice()).await.unwrap(),
+            Some(vec![
+                ("timestamp".to_owned(), "DateTime64(3)".to_owned()),
+                ("message".to_owned(), "String".to_owned()),
+            ])
+        );
+
+        let mut buf = Vec::new();
+        put_block_header(&mut buf, 1, 1);
+        assert_eq!(read_block_header(&mut buf.as_slice()).await.unwrap(), None);
+    }
+}
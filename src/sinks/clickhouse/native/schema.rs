@@ -0,0 +1,297 @@
+//! The schema of the table that events are inserted into.
+
+use std::{fmt, str::FromStr};
+
+use super::NativeError;
+
+/// The columns of a table, in the order that they're written to blocks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableSchema {
+    pub columns: Vec<Column>,
+}
+
+impl TableSchema {
+    /// Parses the names and types of the columns of the header of a block.
+    pub fn parse(header: Vec<(String, String)>) -> Result<Self, NativeError> {
+        let columns = header
+            .into_iter()
+            .map(
+                |(name, column_type)| match ColumnType::parse(&column_type) {
+                    Some(column_type) => Ok(Column { name, column_type }),
+                    None => Err(NativeError::UnsupportedType {
+                        column: name,
+                        column_type,
+                    }),
+                },
+            )
+            .collect::<Result<_, _>>()?;
+        Ok(Self { columns })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub column_type: ColumnType,
+}
+
+/// The types of columns that events can be written to.
+///
+/// `LowCardinality` columns are written as columns of the type that they wrap, which ClickHouse
+/// converts them back from, so they are parsed as that type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnType {
+    Bool,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    String,
+    FixedString(usize),
+    Date,
+    Date32,
+    DateTime,
+    /// The precision of the timestamps, in decimal digits of a second.
+    DateTime64(u32),
+    Uuid,
+    Ipv4,
+    Ipv6,
+    Enum8(Vec<(String, i8)>),
+    Enum16(Vec<(String, i16)>),
+    Nullable(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+}
+
+impl ColumnType {
+    /// Parses the name of a type, or returns `None` if it's not supported.
+    ///
+    /// The time zones of date and time types are ignored, as their values are written as Unix
+    /// timestamps.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        let (base, arguments) = match name.find('(') {
+            Some(start) if name.ends_with(')') => {
+                (&name[..start], Some(&name[start + 1..name.len() - 1]))
+            }
+            Some(_) => return None,
+            None => (name, None),
+        };
+
+        Some(match (base, arguments) {
+            ("Bool", None) => Self::Bool,
+            ("UInt8", None) => Self::UInt8,
+            ("UInt16", None) => Self::UInt16,
+            ("UInt32", None) => Self::UInt32,
+            ("UInt64", None) => Self::UInt64,
+            ("Int8", None) => Self::Int8,
+            ("Int16", None) => Self::Int16,
+            ("Int32", None) => Self::Int32,
+            ("Int64", None) => Self::Int64,
+            ("Float32", None) => Self::Float32,
+            ("Float64", None) => Self::Float64,
+            ("String", None) => Self::String,
+            ("FixedString", Some(length)) => {
+                Self::FixedString(length.trim().parse().ok().filter(|length| *length > 0)?)
+            }
+            ("Date", None) => Self::Date,
+            ("Date32", None) => Self::Date32,
+            ("DateTime", _) => Self::DateTime,
+            ("DateTime64", Some(arguments)) => {
+                let precision = split_arguments(arguments)?.first()?.parse().ok()?;
+                (precision <= 9).then_some(Self::DateTime64(precision))?
+            }
+            ("UUID", None) => Self::Uuid,
+            ("IPv4", None) => Self::Ipv4,
+            ("IPv6", None) => Self::Ipv6,
+            ("Enum8", Some(arguments)) => Self::Enum8(parse_enum(arguments)?),
+            ("Enum16", Some(arguments)) => Self::Enum16(parse_enum(arguments)?),
+            ("LowCardinality", Some(inner)) => Self::parse(inner)?,
+            ("Nullable", Some(inner)) => Self::Nullable(Box::new(Self::parse(inner)?)),
+            ("Array", Some(inner)) => Self::Array(Box::new(Self::parse(inner)?)),
+            ("Map", Some(arguments)) => match split_arguments(arguments)?.as_slice() {
+                [key, value] => {
+                    Self::Map(Box::new(Self::parse(key)?), Box::new(Self::parse(value)?))
+                }
+                _ => return None,
+            },
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => write!(f, "Bool"),
+            Self::UInt8 => write!(f, "UInt8"),
+            Self::UInt16 => write!(f, "UInt16"),
+            Self::UInt32 => write!(f, "UInt32"),
+            Self::UInt64 => write!(f, "UInt64"),
+            Self::Int8 => write!(f, "Int8"),
+            Self::Int16 => write!(f, "Int16"),
+            Self::Int32 => write!(f, "Int32"),
+            Self::Int64 => write!(f, "Int64"),
+            Self::Float32 => write!(f, "Float32"),
+            Self::Float64 => write!(f, "Float64"),
+            Self::String => write!(f, "String"),
+            Self::FixedString(length) => write!(f, "FixedString({})", length),
+            Self::Date => write!(f, "Date"),
+            Self::Date32 => write!(f, "Date32"),
+            Self::DateTime => write!(f, "DateTime"),
+            Self::DateTime64(precision) => write!(f, "DateTime64({})", precision),
+            Self::Uuid => write!(f, "UUID"),
+            Self::Ipv4 => write!(f, "IPv4"),
+            Self::Ipv6 => write!(f, "IPv6"),
+            Self::Enum8(values) => write!(f, "Enum8({})", EnumValues(values)),
+            Self::Enum16(values) => write!(f, "Enum16({})", EnumValues(values)),
+            Self::Nullable(inner) => write!(f, "Nullable({})", inner),
+            Self::Array(inner) => write!(f, "Array({})", inner),
+            Self::Map(key, value) => write!(f, "Map({}, {})", key, value),
+        }
+    }
+}
+
+/// Formats the values of an enum, such as `'info' = 1, 'error' = 2`.
+struct EnumValues<'a, T>(&'a [(String, T)]);
+
+impl<T: fmt::Display> fmt::Display for EnumValues<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, (name, value)) in self.0.iter().enumerate() {
+            if position > 0 {
+                write!(f, ", ")?;
+            }
+            let name = name.replace('\\', "\\\\").replace('\'', "\\'");
+            write!(f, "'{}' = {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits the arguments of a type at the commas that aren't nested in other types or in quotes.
+fn split_arguments(arguments: &str) -> Option<Vec<&str>> {
+    let mut split = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (position, c) in arguments.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '\'' => quoted = !quoted,
+            _ if quoted => {}
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                split.push(arguments[start..position].trim());
+                start = position + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(arguments[start..].trim());
+    Some(split)
+}
+
+/// Parses the values of an enum, such as `'info' = 1, 'error' = 2`.
+fn parse_enum<T: FromStr>(arguments: &str) -> Option<Vec<(String, T)>> {
+    split_arguments(arguments)?
+        .into_iter()
+        .map(|argument| {
+            let quoted = argument.strip_prefix('\'')?;
+            let mut chars = quoted.char_indices();
+            let mut name = String::new();
+            let end = loop {
+                match chars.next()? {
+                    (_, '\\') => name.push(chars.next()?.1),
+                    (position, '\'') => break position,
+                    (_, c) => name.push(c),
+                }
+            };
+            let value = quoted[end + 1..].trim_start().strip_prefix('=')?;
+            Some((name, value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_types() {
+        for (name, column_type) in [
+            ("UInt64", ColumnType::UInt64),
+            ("LowCardinality(String)", ColumnType::String),
+            ("FixedString(16)", ColumnType::FixedString(16)),
+            ("DateTime('Europe/Berlin')", ColumnType::DateTime),
+            ("DateTime64(6, 'UTC')", ColumnType::DateTime64(6)),
+            (
+                "Nullable(IPv6)",
+                ColumnType::Nullable(Box::new(ColumnType::Ipv6)),
+            ),
+            (
+                "Array(LowCardinality(Nullable(String)))",
+                ColumnType::Array(Box::new(ColumnType::Nullable(Box::new(ColumnType::String)))),
+            ),
+            (
+                "Map(LowCardinality(String), Array(Int32))",
+                ColumnType::Map(
+                    Box::new(ColumnType::String),
+                    Box::new(ColumnType::Array(Box::new(ColumnType::Int32))),
+                ),
+            ),
+            (
+                "Enum8('info' = 1, 'it\\'s, (bad)' = -2)",
+                ColumnType::Enum8(vec![("info".to_owned(), 1), ("it's, (bad)".to_owned(), -2)]),
+            ),
+        ] {
+            assert_eq!(ColumnType::parse(name), Some(column_type), "{}", name);
+        }
+
+        for name in [
+            "Decimal(10, 2)",
+            "Tuple(String, UInt8)",
+            "DateTime64(12)",
+            "Array(UInt8",
+        ] {
+            assert_eq!(ColumnType::parse(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn formats_types() {
+        for (name, formatted) in [
+            ("LowCardinality(Nullable(String))", "Nullable(String)"),
+            ("DateTime64(3, 'UTC')", "DateTime64(3)"),
+            (
+                "Map(String, Enum16('it\\'s' = 1, 'a\\\\b' = 2))",
+                "Map(String, Enum16('it\\'s' = 1, 'a\\\\b' = 2))",
+            ),
+        ] {
+            let column_type = ColumnType::parse(name).unwrap();
+            assert_eq!(column_type.to_string(), formatted);
+            assert_eq!(ColumnType::parse(formatted), Some(column_type));
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_columns() {
+        let header = vec![
+            ("message".to_owned(), "String".to_owned()),
+            ("amount".to_owned(), "Decimal(10, 2)".to_owned()),
+        ];
+        assert!(matches!(
+            TableSchema::parse(header),
+            Err(NativeError::UnsupportedType { column, .. }) if column == "amount"
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+//! Service implementation for the native protocol of the `Clickhouse` sink.
+
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use super::{connection::Connection, Block, Connector, NativeError, TableSchema};
+use crate::{internal_events::EndpointBytesSent, sinks::prelude::*};
+
+/// [Error codes][codes] of the exceptions raised by ClickHouse for blocks that it can't insert,
+/// which fail again when retried.
+///
+/// [codes]: https://github.com/ClickHouse/ClickHouse/blob/master/src/Common/ErrorCodes.cpp
+const NON_RETRIABLE_CODES: [i32; 2] = [
+    // TYPE_MISMATCH
+    53, // INCORRECT_DATA
+    117,
+];
+
+#[derive(Debug, Default, Clone)]
+pub struct NativeRetryLogic;
+
+impl RetryLogic for NativeRetryLogic {
+    type Error = NativeError;
+    type Response = NativeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            NativeError::Server { exception } => !NON_RETRIABLE_CODES.contains(&exception.code),
+            NativeError::UnsupportedType { .. } => false,
+            // The schema is fetched again when the request is retried, and its block rebuilt.
+            _ => true,
+        }
+    }
+}
+
+/// The events of a batch, which are encoded as a block when they're inserted, so that the block is
+/// built with the current schema of the table on every attempt.
+#[derive(Clone)]
+pub struct NativeRequest {
+    pub events: Arc<Vec<LogEvent>>,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for NativeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for NativeRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+/// Inserts the events of requests through connections that are kept open between requests.
+#[derive(Clone)]
+pub struct NativeService {
+    connector: Arc<Connector>,
+    database: String,
+    table: String,
+    /// The schema of the table, until an insert finds that it changed.
+    schema: Arc<Mutex<Option<Arc<TableSchema>>>>,
+    idle: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl NativeService {
+    pub fn new(connector: Connector, database: String, table: String) -> Self {
+        Self {
+            connector: Arc::new(connector),
+            database,
+            table,
+            schema: Default::default(),
+            idle: Default::default(),
+        }
+    }
+
+    /// Checks that ClickHouse can be reached, and that the table has columns that events can be
+    /// inserted into.
+    pub async fn healthcheck(self) -> crate::Result<()> {
+        let mut connection = self.connection().await?;
+        connection.ping().await?;
+        self.schema(&mut connection).await?;
+        self.release(connection);
+        Ok(())
+    }
+
+    /// Returns an idle connection, or establishes a new one if they're all in use.
+    async fn connection(&self) -> Result<Connection, NativeError> {
+        let idle = self.idle.lock().expect("mutex poisoned").pop();
+        match idle {
+            Some(connection) => Ok(connection),
+            None => self.connector.connect().await,
+        }
+    }
+
+    fn release(&self, connection: Connection) {
+        self.idle.lock().expect("mutex poisoned").push(connection);
+    }
+
+    /// Returns the schema of the table, which is fetched through `connection` if it isn't known.
+    async fn schema(&self, connection: &mut Connection) -> Result<Arc<TableSchema>, NativeError> {
+        let schema = self.schema.lock().expect("mutex poisoned").clone();
+        match schema {
+            Some(schema) => Ok(schema),
+            None => {
+                let schema = connection.fetch_schema(&self.database, &self.table).await?;
+                let schema = Arc::new(schema);
+                *self.schema.lock().expect("mutex poisoned") = Some(Arc::clone(&schema));
+                Ok(schema)
+            }
+        }
+    }
+
+    async fn insert(&self, events: &[LogEvent]) -> Result<NativeResponse, NativeError> {
+        // Connections are only reused after a successful query, as they may be in the middle of
+        // one otherwise.
+        let mut connection = self.connection().await?;
+        let schema = self.schema(&mut connection).await?;
+        let block = Block::encode(&schema, events);
+
+        // Requests whose events were all dropped while encoding have no block to insert.
+        if block.rows > 0 {
+            match connection.insert(&self.database, &self.table, &block).await {
+                Ok(()) => {}
+                Err(error @ NativeError::SchemaChanged { .. }) => {
+                    *self.schema.lock().expect("mutex poisoned") = None;
+                    self.release(connection);
+                    return Err(error);
+                }
+                Err(error) => return Err(error),
+            }
+
+            emit!(EndpointBytesSent {
+                byte_size: block.data.len(),
+                protocol: "tcp",
+                endpoint: &self.connector.endpoint(),
+            });
+        }
+        self.release(connection);
+
+        Ok(NativeResponse {
+            events_byte_size: block.byte_size,
+            byte_size: block.data.len(),
+        })
+    }
+}
+
+impl Service<NativeRequest> for NativeService {
+    type Response = NativeResponse;
+    type Error = NativeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: NativeRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move { service.insert(&request.events).await })
+    }
+}
+
+pub struct NativeResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for NativeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::clickhouse::native::ServerException;
+
+    #[test]
+    fn retries_transient_errors() {
+        let logic = NativeRetryLogic;
+        let exception = |code| NativeError::Server {
+            exception: ServerException {
+                code,
+                name: "DB::Exception".to_owned(),
+                message: String::new(),
+            },
+        };
+
+        assert!(logic.is_retriable_error(&exception(202)));
+        assert!(logic.is_retriable_error(&NativeError::NoAddresses));
+        assert!(!logic.is_retriable_error(&exception(53)));
+        assert!(!logic.is_retriable_error(&exception(117)));
+        assert!(logic.is_retriable_error(&NativeError::SchemaChanged {
+            table: "logs".to_owned(),
+        }));
+        assert!(!logic.is_retriable_error(&NativeError::UnsupportedType {
+            column: "point".to_owned(),
+            column_type: "Point".to_owned(),
+        }));
+    }
+}
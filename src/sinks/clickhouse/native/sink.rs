@@ -0,0 +1,79 @@
+//! Implementation of the native protocol of the `Clickhouse` sink.
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use super::NativeRequest;
+use crate::sinks::prelude::*;
+
+/// Batches events into requests, whose blocks are built by the service when they're inserted.
+pub struct NativeSink<S> {
+    batch_settings: BatcherSettings,
+    service: S,
+    transformer: Transformer,
+}
+
+impl<S> NativeSink<S>
+where
+    S: Service<NativeRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    pub const fn new(
+        batch_settings: BatcherSettings,
+        service: S,
+        transformer: Transformer,
+    ) -> Self {
+        Self {
+            batch_settings,
+            service,
+            transformer,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let transformer = self.transformer;
+
+        input
+            .map(|mut event| {
+                transformer.transform(&mut event);
+                event
+            })
+            .batched(self.batch_settings.as_byte_size_config())
+            .map(|mut events| {
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+                // The block is only encoded once the schema of the table is known, so the size of
+                // the request is estimated from that of its events.
+                let request_size = events.estimated_json_encoded_size_of().get();
+                let metadata = builder.with_request_size(
+                    NonZeroUsize::new(request_size).unwrap_or(NonZeroUsize::MIN),
+                );
+
+                NativeRequest {
+                    events: Arc::new(events.into_iter().map(Event::into_log).collect()),
+                    finalizers,
+                    metadata,
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for NativeSink<S>
+where
+    S: Service<NativeRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(
+        self: Box<Self>,
+        input: futures_util::stream::BoxStream<'_, Event>,
+    ) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -100,9 +100,13 @@ impl ElasticsearchCommon {
             && (config.bulk.version_type == VersionType::External
                 || config.bulk.version_type == VersionType::ExternalGte)
             && config.id_key.is_none()
+            && config.document_id.is_none()
         {
             return Err(ParseError::ExternalVersioningWithoutDocumentID.into());
         }
+        if config.id_key.is_some() && config.document_id.is_some() {
+            return Err(ParseError::IdKeyAndDocumentIdExclusive.into());
+        }
         if config.bulk.version.is_none()
             && (config.bulk.version_type == VersionType::External
                 || config.bulk.version_type == VersionType::ExternalGte)
@@ -116,9 +120,10 @@ impl ElasticsearchCommon {
             format!("{}s", tower_request.timeout.as_secs()),
         );
 
-        if let Some(pipeline) = &config.pipeline {
+        // A pipeline that is rendered from the event is set for each document instead.
+        if let Some(pipeline) = config.pipeline.as_ref().filter(|p| !p.is_dynamic()) {
             if !pipeline.is_empty() {
-                query_params.insert("pipeline".into(), pipeline.into());
+                query_params.insert("pipeline".into(), pipeline.get_ref().into());
             }
         }
 
@@ -198,6 +203,9 @@ impl ElasticsearchCommon {
                 doc_type,
                 suppress_type_name,
             },
+            retain_events: mode
+                .as_data_stream_config()
+                .is_some_and(|data_stream| data_stream.retry_attempts() > 0),
         };
 
         Ok(Self {
@@ -320,12 +328,34 @@ async fn get(
     client: HttpClient,
     path: &str,
 ) -> crate::Result<Response<Body>> {
-    let mut builder = Request::get(format!("{}{}", base_url, path));
+    let builder = Request::get(format!("{}{}", base_url, path));
+    send(builder, Bytes::new(), auth, request, client).await
+}
+
+pub(super) async fn put(
+    base_url: &str,
+    auth: &Option<Auth>,
+    request: &RequestConfig,
+    client: HttpClient,
+    path: &str,
+    body: Bytes,
+) -> crate::Result<Response<Body>> {
+    let builder =
+        Request::put(format!("{}{}", base_url, path)).header("Content-Type", "application/json");
+    send(builder, body, auth, request, client).await
+}
 
+async fn send(
+    mut builder: http::request::Builder,
+    body: Bytes,
+    auth: &Option<Auth>,
+    request: &RequestConfig,
+    client: HttpClient,
+) -> crate::Result<Response<Body>> {
     for (header, value) in &request.headers {
         builder = builder.header(&header[..], &value[..]);
     }
-    let mut request = builder.body(Bytes::new())?;
+    let mut request = builder.body(body)?;
 
     if let Some(auth) = auth {
         match auth {
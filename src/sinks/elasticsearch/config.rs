@@ -14,12 +14,13 @@ use crate::{
     internal_events::TemplateRenderingError,
     sinks::{
         elasticsearch::{
+            data_stream::DataStreamManager,
             health::ElasticsearchHealthLogic,
             retry::ElasticsearchRetryLogic,
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
             ElasticsearchApiVersion, ElasticsearchAuthConfig, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode, VersionType,
+            ElasticsearchCommonMode, ElasticsearchMode, ParseError, VersionType,
         },
         util::{
             http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
@@ -110,11 +111,40 @@ pub struct ElasticsearchConfig {
     #[configurable(metadata(docs::examples = "_id"))]
     pub id_key: Option<ConfigValuePath>,
 
+    /// The [`_id`][es_id] of each document, rendered from its event.
+    ///
+    /// Unlike `id_key`, the fields that the ID is rendered from are kept in the document. This
+    /// option can't be used along with `id_key`. If the ID can't be rendered, or is empty, it's not
+    /// set, which allows Elasticsearch to set it automatically.
+    ///
+    /// [es_id]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-id-field.html
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ tenant }}-{{ request_id }}"))]
+    pub document_id: Option<Template>,
+
+    /// The [routing][es_routing] value of each document, rendered from its event.
+    ///
+    /// The routing value determines the shard that a document is written to, such as to write the
+    /// documents of each tenant to the same shard. If it can't be rendered, or is empty, it's not
+    /// set, which routes the document by its ID.
+    ///
+    /// [es_routing]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-routing-field.html
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ tenant }}"))]
+    pub routing: Option<Template>,
+
     /// The name of the pipeline to apply.
+    ///
+    /// If the name is rendered from the event, the pipeline is set for each document, so that
+    /// events can be dispatched to different ingest pipelines. If it can't be rendered, or is
+    /// empty, it's not set, which applies the default pipeline of the index.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
     #[configurable(metadata(docs::examples = "pipeline-name"))]
-    pub pipeline: Option<String>,
+    #[configurable(metadata(docs::examples = "{{ service }}-pipeline"))]
+    pub pipeline: Option<Template>,
 
     #[serde(default)]
     #[configurable(derived)]
@@ -204,6 +234,8 @@ impl Default for ElasticsearchConfig {
             suppress_type_name: false,
             request_retry_partial: false,
             id_key: None,
+            document_id: None,
+            routing: None,
             pipeline: None,
             mode: Default::default(),
             compression: Default::default(),
@@ -233,9 +265,17 @@ impl ElasticsearchConfig {
                 version: self.bulk.version.clone(),
                 version_type: self.bulk.version_type,
             }),
-            ElasticsearchMode::DataStream => Ok(ElasticsearchCommonMode::DataStream(
-                self.data_stream.clone().unwrap_or_default(),
-            )),
+            ElasticsearchMode::DataStream => {
+                let data_stream = self.data_stream.clone().unwrap_or_default();
+                if data_stream
+                    .index_template
+                    .as_ref()
+                    .is_some_and(|template| template.index_patterns.is_empty())
+                {
+                    return Err(ParseError::IndexTemplateWithoutPatterns.into());
+                }
+                Ok(ElasticsearchCommonMode::DataStream(data_stream))
+            }
         }
     }
 }
@@ -340,6 +380,94 @@ pub struct DataStreamConfig {
     /// This ensures that fields match the name of the data stream that is receiving events.
     #[serde(default = "DataStreamConfig::default_sync_fields")]
     pub sync_fields: bool,
+
+    /// Whether or not to create the data streams that events are routed to if they don't exist.
+    ///
+    /// Elasticsearch creates a data stream when events are first written to it, unless the creation
+    /// of indices is restricted with the `action.auto_create_index` cluster setting, in which case
+    /// the events are rejected. If enabled, the data streams that events are rejected for are
+    /// created, and the rejected events are written to them again, at least once even if
+    /// `index_retry_attempts` is `0`.
+    ///
+    /// A data stream can only be created if an index template with a data stream matches its name,
+    /// such as the `index_template` of this configuration.
+    #[serde(default)]
+    pub auto_create: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub index_template: Option<DataStreamIndexTemplate>,
+
+    /// The number of times that documents are written again when the data stream that they're
+    /// written to is unavailable.
+    ///
+    /// Documents that are rejected because the write index of their data stream is closed, or is
+    /// blocked for writes while [index lifecycle management][ilm] rolls it over, are written again
+    /// on their own, without the documents of the request that were accepted, so that they aren't
+    /// duplicated.
+    ///
+    /// Documents are not written again by default, as the documents of each request are then kept
+    /// in memory until it completes.
+    ///
+    /// [ilm]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index-lifecycle-management.html
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub index_retry_attempts: usize,
+}
+
+/// The index template that data streams are created with.
+///
+/// The template is installed, or updated, before events are first written, so that the data streams
+/// that match its patterns are created with its settings. If it can't be installed, it's installed
+/// again before the next request.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DataStreamIndexTemplate {
+    /// The name of the index template.
+    #[configurable(metadata(docs::examples = "vector-logs"))]
+    pub name: String,
+
+    /// The patterns of the names of the data streams that the index template applies to.
+    #[configurable(metadata(docs::examples = "logs-vector-*"))]
+    pub index_patterns: Vec<String>,
+
+    /// The priority of the index template.
+    ///
+    /// The index template with the highest priority applies to a data stream that several index
+    /// templates match. The built-in index templates of Elasticsearch have a priority of `100`.
+    #[serde(default = "DataStreamIndexTemplate::default_priority")]
+    #[configurable(metadata(docs::advanced))]
+    pub priority: u64,
+
+    /// The component templates that the index template is composed of, such as the mappings of the
+    /// data streams.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "logs-mappings"))]
+    pub composed_of: Vec<String>,
+
+    /// The [index lifecycle management][ilm] policy of the backing indices of the data streams.
+    ///
+    /// [ilm]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index-lifecycle-management.html
+    #[configurable(metadata(docs::examples = "logs"))]
+    pub ilm_policy: Option<String>,
+
+    /// The index settings of the backing indices of the data streams.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::additional_props_description = "An index setting."))]
+    #[configurable(metadata(docs::examples = "index_settings_examples()"))]
+    pub settings: HashMap<String, String>,
+}
+
+impl DataStreamIndexTemplate {
+    const fn default_priority() -> u64 {
+        200
+    }
+}
+
+fn index_settings_examples() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([("index.number_of_shards".to_owned(), "1".to_owned())])
 }
 
 impl Default for DataStreamConfig {
@@ -350,6 +478,9 @@ impl Default for DataStreamConfig {
             namespace: Self::default_namespace(),
             auto_routing: Self::default_auto_routing(),
             sync_fields: Self::default_sync_fields(),
+            auto_create: false,
+            index_template: None,
+            index_retry_attempts: 0,
         }
     }
 }
@@ -375,6 +506,12 @@ impl DataStreamConfig {
         true
     }
 
+    /// The number of times that rejected documents are written again, which is at least once if
+    /// missing data streams are created.
+    pub fn retry_attempts(&self) -> usize {
+        self.index_retry_attempts.max(usize::from(self.auto_create))
+    }
+
     /// If there is a `timestamp` field, rename it to the expected `@timestamp` for Elastic Common Schema.
     pub fn remap_timestamp(&self, log: &mut LogEvent) {
         if let Some(timestamp_key) = log.timestamp_path().cloned() {
@@ -513,7 +650,9 @@ impl SinkConfig for ElasticsearchConfig {
                 let endpoint = common.base_url.clone();
 
                 let http_request_builder = HttpRequestBuilder::new(&common, self);
-                let service = ElasticsearchService::new(client.clone(), http_request_builder);
+                let data_streams = DataStreamManager::new(&common, client.clone());
+                let service =
+                    ElasticsearchService::new(client.clone(), http_request_builder, data_streams);
 
                 (endpoint, service)
             })
@@ -597,6 +736,41 @@ mod tests {
         assert!(config.data_stream.is_some());
     }
 
+    #[test]
+    fn parse_data_stream_index_template() {
+        let config = toml::from_str::<ElasticsearchConfig>(
+            r#"
+            endpoints = [""]
+            mode = "data_stream"
+            data_stream.auto_create = true
+            data_stream.index_template.name = "vector-logs"
+            data_stream.index_template.index_patterns = ["logs-vector-*"]
+            data_stream.index_template.ilm_policy = "logs"
+        "#,
+        )
+        .unwrap();
+        assert!(config.common_mode().is_ok());
+
+        let data_stream = config.data_stream.unwrap();
+        assert!(data_stream.auto_create);
+        assert_eq!(data_stream.index_retry_attempts, 0);
+        assert_eq!(data_stream.retry_attempts(), 1);
+        let index_template = data_stream.index_template.unwrap();
+        assert_eq!(index_template.priority, 200);
+        assert_eq!(index_template.ilm_policy.as_deref(), Some("logs"));
+
+        let config = toml::from_str::<ElasticsearchConfig>(
+            r#"
+            endpoints = [""]
+            mode = "data_stream"
+            data_stream.index_template.name = "vector-logs"
+            data_stream.index_template.index_patterns = []
+        "#,
+        )
+        .unwrap();
+        assert!(config.common_mode().is_err());
+    }
+
     #[test]
     fn parse_distribution() {
         toml::from_str::<ElasticsearchConfig>(
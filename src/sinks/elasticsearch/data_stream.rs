@@ -0,0 +1,268 @@
+use std::{
+    collections::BTreeSet,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::Response;
+use hyper::body;
+use serde_json::json;
+use tower::{Service, ServiceExt};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use super::{
+    common::put,
+    encoder::ProcessedEvent,
+    retry::{data_stream_errors, DataStreamError},
+    service::ElasticsearchRequest,
+    DataStreamConfig, DataStreamIndexTemplate, ElasticsearchCommon,
+};
+use crate::{http::HttpClient, sinks::util::RequestBuilder};
+
+/// The longest time to wait for a data stream to become available again.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Installs the index template of the data streams that events are written to, and writes the
+/// documents that are rejected because of their data stream again.
+pub struct DataStreamManager {
+    common: ElasticsearchCommon,
+    client: HttpClient,
+    config: DataStreamConfig,
+    index_template_installed: AtomicBool,
+}
+
+impl DataStreamManager {
+    /// Creates the manager of the data streams of an endpoint, unless events aren't written to
+    /// data streams.
+    pub fn new(common: &ElasticsearchCommon, client: HttpClient) -> Option<Self> {
+        let config = common.mode.as_data_stream_config()?.clone();
+        Some(Self {
+            common: common.clone(),
+            client,
+            index_template_installed: AtomicBool::new(config.index_template.is_none()),
+            config,
+        })
+    }
+
+    /// Installs the index template, unless it's already installed.
+    pub async fn install_index_template(&self) {
+        let Some(template) = &self.config.index_template else {
+            return;
+        };
+        if self.index_template_installed.load(Ordering::Acquire) {
+            return;
+        }
+
+        let path = format!("/_index_template/{}", template.name);
+        let body = Bytes::from(index_template_body(template).to_string());
+        match self.create(&path, body).await {
+            Ok(()) => {
+                self.index_template_installed.store(true, Ordering::Release);
+                info!(message = "Installed data stream index template.", name = %template.name);
+            }
+            Err(error) => error!(
+                message = "Failed to install data stream index template.",
+                name = %template.name,
+                %error,
+                internal_log_rate_limit = true
+            ),
+        }
+    }
+
+    /// Writes the documents of a request that were rejected because of their data stream again,
+    /// after creating the data streams that are missing, and returns the response to the last
+    /// request.
+    ///
+    /// If a request can't be sent, the response to the previous one is returned, so that the
+    /// documents it rejected are reported as such.
+    pub async fn retry_documents<S>(
+        &self,
+        service: &mut S,
+        events: &[ProcessedEvent],
+        mut response: Response<Bytes>,
+    ) -> Response<Bytes>
+    where
+        S: Service<ElasticsearchRequest, Response = Response<Bytes>, Error = crate::Error>,
+    {
+        let mut events = events.to_vec();
+        for attempt in 1..=self.config.retry_attempts() {
+            let Some(errors) = rejected_documents(&response) else {
+                break;
+            };
+
+            let missing = errors
+                .iter()
+                .filter(|(_, error)| *error == DataStreamError::Missing)
+                .map(|(position, _)| events[*position].index.as_str())
+                .collect::<BTreeSet<_>>();
+            if !missing.is_empty() && !self.config.auto_create {
+                break;
+            }
+            for name in missing {
+                let path = format!("/_data_stream/{}", name);
+                if let Err(error) = self.create(&path, Bytes::new()).await {
+                    error!(
+                        message = "Failed to create data stream.",
+                        data_stream = %name,
+                        %error,
+                        internal_log_rate_limit = true
+                    );
+                    return response;
+                }
+                info!(message = "Created data stream.", data_stream = %name);
+            }
+
+            // The write index of a data stream becomes available again once it's rolled over.
+            if errors
+                .iter()
+                .any(|(_, error)| *error == DataStreamError::Unavailable)
+            {
+                let backoff = Duration::from_secs(1 << (attempt - 1).min(5));
+                tokio::time::sleep(backoff.min(MAX_RETRY_BACKOFF)).await;
+            }
+
+            warn!(
+                message = "Writing documents rejected because of their data stream again.",
+                count = errors.len(),
+                attempt,
+                internal_log_rate_limit = true
+            );
+            events = errors
+                .into_iter()
+                .map(|(position, _)| events[position].clone())
+                .collect();
+            let payload = match self.common.request_builder.encode_events(events.clone()) {
+                Ok(payload) => payload.into_payload(),
+                Err(error) => {
+                    error!(
+                        message = "Failed to encode documents to write again.",
+                        %error,
+                        internal_log_rate_limit = true
+                    );
+                    break;
+                }
+            };
+            let request = ElasticsearchRequest {
+                payload,
+                finalizers: Default::default(),
+                batch_size: events.len(),
+                events_byte_size: events
+                    .iter()
+                    .map(|event| event.log.estimated_json_encoded_size_of())
+                    .sum(),
+                metadata: Default::default(),
+                original_events: None,
+            };
+            match send(service, request).await {
+                Ok(next_response) => response = next_response,
+                Err(error) => {
+                    error!(
+                        message = "Failed to write documents again.",
+                        %error,
+                        attempt,
+                        internal_log_rate_limit = true
+                    );
+                    break;
+                }
+            }
+        }
+
+        response
+    }
+
+    /// Creates, or updates, the resource at `path`.
+    async fn create(&self, path: &str, body: Bytes) -> crate::Result<()> {
+        let response = put(
+            &self.common.base_url,
+            &self.common.auth,
+            &self.common.request,
+            self.client.clone(),
+            path,
+            body,
+        )
+        .await?;
+        let status = response.status();
+        let body = body::to_bytes(response.into_body()).await?;
+        let body = String::from_utf8_lossy(&body);
+
+        // The data stream may have been created for another request in the meantime.
+        if status.is_success() || body.contains("resource_already_exists_exception") {
+            Ok(())
+        } else {
+            Err(format!("{}: {}", status, body).into())
+        }
+    }
+}
+
+async fn send<S>(service: &mut S, request: ElasticsearchRequest) -> crate::Result<Response<Bytes>>
+where
+    S: Service<ElasticsearchRequest, Response = Response<Bytes>, Error = crate::Error>,
+{
+    service.ready().await?.call(request).await
+}
+
+/// Returns the positions of the documents that were rejected because of their data stream, unless
+/// the request succeeded, or failed for another reason.
+fn rejected_documents(response: &Response<Bytes>) -> Option<Vec<(usize, DataStreamError)>> {
+    if !response.status().is_success()
+        || !String::from_utf8_lossy(response.body()).contains("\"errors\":true")
+    {
+        return None;
+    }
+    data_stream_errors(response.body()).filter(|errors| !errors.is_empty())
+}
+
+fn index_template_body(template: &DataStreamIndexTemplate) -> serde_json::Value {
+    let mut settings = template
+        .settings
+        .iter()
+        .map(|(name, value)| (name.clone(), json!(value)))
+        .collect::<serde_json::Map<_, _>>();
+    if let Some(ilm_policy) = &template.ilm_policy {
+        settings.insert("index.lifecycle.name".to_owned(), json!(ilm_policy));
+    }
+
+    json!({
+        "index_patterns": template.index_patterns,
+        "data_stream": {},
+        "priority": template.priority,
+        "composed_of": template.composed_of,
+        "template": { "settings": settings },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn builds_index_template() {
+        let template = DataStreamIndexTemplate {
+            name: "vector-logs".to_owned(),
+            index_patterns: vec!["logs-vector-*".to_owned()],
+            priority: 200,
+            composed_of: vec!["logs-mappings".to_owned()],
+            ilm_policy: Some("logs".to_owned()),
+            settings: HashMap::from([("index.number_of_shards".to_owned(), "1".to_owned())]),
+        };
+
+        assert_eq!(
+            index_template_body(&template),
+            json!({
+                "index_patterns": ["logs-vector-*"],
+                "data_stream": {},
+                "priority": 200,
+                "composed_of": ["logs-mappings"],
+                "template": {
+                    "settings": {
+                        "index.number_of_shards": "1",
+                        "index.lifecycle.name": "logs",
+                    },
+                },
+            })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::{io, io::Write};
+use std::{fmt, io, io::Write};
 
 use serde::Serialize;
 use vector_lib::buffers::EventCount;
@@ -18,7 +18,7 @@ use crate::{
     },
 };
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub enum DocumentVersionType {
     External,
     ExternalGte,
@@ -33,25 +33,55 @@ impl DocumentVersionType {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct DocumentVersion {
     pub kind: DocumentVersionType,
     pub value: u64,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub enum DocumentMetadata {
     WithoutId,
     Id(String),
     IdAndVersion(String, DocumentVersion),
 }
 
-#[derive(Serialize)]
+/// The parameters of a document that are rendered from its event, besides its ID.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DocumentParameters {
+    pub routing: Option<String>,
+    pub pipeline: Option<String>,
+}
+
+/// Formats the parameters as the fields that they add to a bulk action, such as
+/// `,"routing":"user-1"`.
+impl fmt::Display for DocumentParameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(routing) = &self.routing {
+            write!(
+                f,
+                r#","routing":{}"#,
+                serde_json::Value::from(routing.as_str())
+            )?;
+        }
+        if let Some(pipeline) = &self.pipeline {
+            write!(
+                f,
+                r#","pipeline":{}"#,
+                serde_json::Value::from(pipeline.as_str())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
     pub log: LogEvent,
     pub document_metadata: DocumentMetadata,
+    pub document_parameters: DocumentParameters,
 }
 
 impl Finalizable for ProcessedEvent {
@@ -122,6 +152,7 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
                 &self.doc_type,
                 self.suppress_type_name,
                 &event.document_metadata,
+                &event.document_parameters,
             )?;
             written_bytes +=
                 as_tracked_write::<_, _, io::Error>(writer, &log, |mut writer, log| {
@@ -146,6 +177,7 @@ fn write_bulk_action(
     doc_type: &str,
     suppress_type: bool,
     document: &DocumentMetadata,
+    parameters: &DocumentParameters,
 ) -> std::io::Result<usize> {
     as_tracked_write(
         writer,
@@ -157,48 +189,61 @@ fn write_bulk_action(
             (true, DocumentMetadata::Id(id)) => {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, id
+                    r#"{{"{}":{{"_index":"{}","_id":{}{}}}}}"#,
+                    bulk_action,
+                    index,
+                    serde_json::Value::from(id.as_str()),
+                    parameters
                 )
             }
             (false, DocumentMetadata::Id(id)) => {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, doc_type, id
+                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":{}{}}}}}"#,
+                    bulk_action,
+                    index,
+                    doc_type,
+                    serde_json::Value::from(id.as_str()),
+                    parameters
                 )
             }
             (true, DocumentMetadata::WithoutId) => {
-                write!(writer, r#"{{"{}":{{"_index":"{}"}}}}"#, bulk_action, index)
+                write!(
+                    writer,
+                    r#"{{"{}":{{"_index":"{}"{}}}}}"#,
+                    bulk_action, index, parameters
+                )
             }
             (false, DocumentMetadata::WithoutId) => {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}"}}}}"#,
-                    bulk_action, index, doc_type
+                    r#"{{"{}":{{"_index":"{}","_type":"{}"{}}}}}"#,
+                    bulk_action, index, doc_type, parameters
                 )
             }
             (true, DocumentMetadata::IdAndVersion(id, version)) => {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_id":"{}","version_type":"{}","version":{}}}}}"#,
+                    r#"{{"{}":{{"_index":"{}","_id":{},"version_type":"{}","version":{}{}}}}}"#,
                     bulk_action,
                     index,
-                    id,
+                    serde_json::Value::from(id.as_str()),
                     version.kind.as_str(),
-                    version.value
+                    version.value,
+                    parameters
                 )
             }
             (false, DocumentMetadata::IdAndVersion(id, version)) => {
                 write!(
                     writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":"{}","version_type":"{}","version":{}}}}}"#,
+                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":{},"version_type":"{}","version":{}{}}}}}"#,
                     bulk_action,
                     index,
                     doc_type,
-                    id,
+                    serde_json::Value::from(id.as_str()),
                     version.kind.as_str(),
-                    version.value
+                    version.value,
+                    parameters
                 )
             }
         },
@@ -220,6 +265,7 @@ mod tests {
             "TYPE",
             true,
             &DocumentMetadata::Id("ID".to_string()),
+            &DocumentParameters::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -248,6 +294,7 @@ mod tests {
             "TYPE",
             true,
             &DocumentMetadata::WithoutId,
+            &DocumentParameters::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -275,6 +322,7 @@ mod tests {
             "TYPE",
             false,
             &DocumentMetadata::Id("ID".to_string()),
+            &DocumentParameters::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -304,6 +352,7 @@ mod tests {
             "TYPE",
             false,
             &DocumentMetadata::WithoutId,
+            &DocumentParameters::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -320,4 +369,66 @@ mod tests {
         assert!(nested.contains_key("_type"));
         assert_eq!(nested.get("_type").unwrap().as_str(), Some("TYPE"));
     }
+
+    #[test]
+    fn with_routing_and_pipeline() {
+        let mut writer = Vec::new();
+
+        _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &DocumentMetadata::Id("ID".to_string()),
+            &DocumentParameters {
+                routing: Some("user \"1\"".to_string()),
+                pipeline: Some("PIPELINE".to_string()),
+            },
+        );
+
+        let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "ACTION": {
+                    "_index": "INDEX",
+                    "_id": "ID",
+                    "routing": "user \"1\"",
+                    "pipeline": "PIPELINE",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn escapes_id() {
+        for document in [
+            DocumentMetadata::Id("user \"1\"\\".to_string()),
+            DocumentMetadata::IdAndVersion(
+                "user \"1\"\\".to_string(),
+                DocumentVersion {
+                    kind: DocumentVersionType::External,
+                    value: 3,
+                },
+            ),
+        ] {
+            for suppress_type in [true, false] {
+                let mut writer = Vec::new();
+
+                _ = write_bulk_action(
+                    &mut writer,
+                    "ACTION",
+                    "INDEX",
+                    "TYPE",
+                    suppress_type,
+                    &document,
+                    &DocumentParameters::default(),
+                );
+
+                let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
+                assert_eq!(value["ACTION"]["_id"], "user \"1\"\\");
+            }
+        }
+    }
 }
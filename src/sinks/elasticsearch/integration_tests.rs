@@ -1,4 +1,4 @@
-use std::{fs::File, io::Read};
+use std::{collections::HashMap, fs::File, io::Read};
 
 use aws_smithy_types::body::SdkBody;
 use bytes::Bytes;
@@ -117,7 +117,7 @@ async fn ensure_pipeline_in_params() {
             index,
             ..Default::default()
         },
-        pipeline: Some(pipeline.clone()),
+        pipeline: Some(Template::try_from(pipeline.clone()).unwrap()),
         batch: batch_settings(),
         ..Default::default()
     };
@@ -139,7 +139,7 @@ async fn ensure_empty_pipeline_not_in_params() {
             index,
             ..Default::default()
         },
-        pipeline: Some(pipeline.clone()),
+        pipeline: Some(Template::try_from(pipeline.clone()).unwrap()),
         batch: batch_settings(),
         ..Default::default()
     };
@@ -504,6 +504,59 @@ async fn insert_events_in_data_stream() {
     run_insert_tests_with_config(&cfg, TestType::Normal, BatchStatus::Delivered).await;
 }
 
+#[tokio::test]
+async fn insert_events_in_data_stream_with_index_template() {
+    trace_init();
+    let dataset = random_string(10).to_lowercase();
+    let stream_index = format!("vector-{}-default", dataset);
+
+    let cfg = ElasticsearchConfig {
+        endpoints: vec![http_server()],
+        mode: ElasticsearchMode::DataStream,
+        data_stream: Some(DataStreamConfig {
+            dtype: Template::try_from("vector").expect("unable to parse template"),
+            dataset: Template::try_from(dataset.clone()).expect("unable to parse template"),
+            index_template: Some(DataStreamIndexTemplate {
+                name: format!("vector-{}", dataset),
+                index_patterns: vec![format!("vector-{}-*", dataset)],
+                priority: 200,
+                composed_of: Vec::new(),
+                ilm_policy: None,
+                settings: HashMap::from([("index.number_of_replicas".to_owned(), "0".to_owned())]),
+            }),
+            ..Default::default()
+        }),
+        batch: batch_settings(),
+        ..Default::default()
+    };
+    let common = ElasticsearchCommon::parse_single(&cfg)
+        .await
+        .expect("Config error");
+
+    let (sink, _healthcheck) = cfg
+        .build(SinkContext::default())
+        .await
+        .expect("Building config failed");
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_input, events) = random_events_with_stream(100, 10, Some(batch));
+    run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    // The data stream is created with the index template of the sink.
+    let response = create_http_client()
+        .get(format!("{}/_data_stream/{}", common.base_url, stream_index))
+        .send()
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+    assert_eq!(
+        response["data_streams"][0]["template"],
+        json!(format!("vector-{}", dataset))
+    );
+}
+
 #[tokio::test]
 async fn distributed_insert_events() {
     trace_init();
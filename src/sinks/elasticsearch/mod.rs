@@ -1,5 +1,6 @@
 mod common;
 mod config;
+mod data_stream;
 pub mod encoder;
 pub mod health;
 pub mod request_builder;
@@ -319,4 +320,8 @@ pub enum ParseError {
     ExternalVersioningWithoutDocumentID,
     #[snafu(display("Your version field will be ignored because you use internal versioning"))]
     ExternalVersionIgnoredWithInternalVersioning,
+    #[snafu(display("`id_key` and `document_id` options are mutually exclusive"))]
+    IdKeyAndDocumentIdExclusive,
+    #[snafu(display("The data stream index template must have at least one index pattern"))]
+    IndexTemplateWithoutPatterns,
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
 use vector_lib::EstimatedJsonEncodedSizeOf;
 use vector_lib::{json_size::JsonSize, request_metadata::RequestMetadata};
@@ -20,12 +22,16 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    /// Whether the events are kept in the requests, to write them again on their own if they're
+    /// rejected because their data stream is unavailable.
+    pub retain_events: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: JsonSize,
+    original_events: Option<Arc<[ProcessedEvent]>>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -60,6 +66,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             finalizers: events.take_finalizers(),
             batch_size: events.len(),
             events_byte_size,
+            original_events: self.retain_events.then(|| events.clone().into()),
         };
         (es_metadata, metadata_builder, events)
     }
@@ -76,6 +83,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
+            original_events: es_metadata.original_events,
         }
     }
 }
@@ -81,6 +81,48 @@ struct EsErrorDetails {
     err_type: String,
 }
 
+impl EsErrorDetails {
+    fn data_stream_error(&self) -> Option<DataStreamError> {
+        match self.err_type.as_str() {
+            "index_not_found_exception" => Some(DataStreamError::Missing),
+            "index_closed_exception" => Some(DataStreamError::Unavailable),
+            // Index lifecycle management blocks writes to indices while it rolls them over or
+            // shrinks them.
+            "cluster_block_exception" if self.reason.contains("index write") => {
+                Some(DataStreamError::Unavailable)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The errors of documents that are caused by the data stream that they're written to, rather than
+/// by the documents themselves.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum DataStreamError {
+    /// The data stream doesn't exist, and Elasticsearch isn't allowed to create it.
+    Missing,
+
+    /// The write index of the data stream is closed, or blocked for writes.
+    Unavailable,
+}
+
+/// Returns the positions of the documents of a bulk response that were rejected because of their
+/// data stream, along with the error, or `None` if a document was rejected for another reason, in
+/// which case writing the others again doesn't save the request.
+pub(super) fn data_stream_errors(body: &[u8]) -> Option<Vec<(usize, DataStreamError)>> {
+    let response = serde_json::from_slice::<EsResultResponse>(body).ok()?;
+    response
+        .items
+        .iter()
+        .enumerate()
+        .filter_map(|(position, item)| {
+            let error = item.result().error.as_ref()?;
+            Some(error.data_stream_error().map(|error| (position, error)))
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct ElasticsearchRetryLogic {
     pub retry_partial: bool,
@@ -224,4 +266,19 @@ mod tests {
         };
         assert_eq!(reason, "error type: mapper_parsing_exception, reason: object mapping for [host] tried to parse field [host] as object, but found a concrete value");
     }
+
+    #[test]
+    fn get_data_stream_errors() {
+        let json = r#"{"took":3,"errors":true,"items":[{"create":{"_index":"logs-nginx-default","status":201}},{"create":{"_index":"logs-nginx-default","status":404,"error":{"type":"index_not_found_exception","reason":"no such index [logs-nginx-default]"}}},{"create":{"_index":".ds-logs-app-default-2024.05.01-000001","status":403,"error":{"type":"cluster_block_exception","reason":"index [.ds-logs-app-default-2024.05.01-000001] blocked by: [FORBIDDEN/8/index write (api)];"}}}]}"#;
+        assert_eq!(
+            data_stream_errors(json.as_bytes()),
+            Some(vec![
+                (1, DataStreamError::Missing),
+                (2, DataStreamError::Unavailable),
+            ])
+        );
+
+        let json = r#"{"took":3,"errors":true,"items":[{"create":{"_index":"logs-nginx-default","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}},{"create":{"_index":"logs-nginx-default","status":404,"error":{"type":"index_not_found_exception","reason":"no such index [logs-nginx-default]"}}}]}"#;
+        assert_eq!(data_stream_errors(json.as_bytes()), None);
+    }
 }
@@ -15,7 +15,10 @@ use vector_lib::{
     request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata},
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{
+    data_stream::DataStreamManager, encoder::ProcessedEvent, ElasticsearchCommon,
+    ElasticsearchConfig,
+};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::HttpClient,
@@ -33,6 +36,9 @@ pub struct ElasticsearchRequest {
     pub batch_size: usize,
     pub events_byte_size: JsonSize,
     pub metadata: RequestMetadata,
+    /// The events of the request, which are kept to write them again on their own if they're
+    /// rejected because of their data stream.
+    pub original_events: Option<Arc<[ProcessedEvent]>>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
@@ -72,12 +78,14 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    data_streams: Option<Arc<DataStreamManager>>,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        data_streams: Option<DataStreamManager>,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -86,7 +94,10 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            data_streams: data_streams.map(Arc::new),
+        }
     }
 }
 
@@ -184,11 +195,22 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, mut req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let data_streams = self.data_streams.clone();
         Box::pin(async move {
+            if let Some(data_streams) = &data_streams {
+                data_streams.install_index_template().await;
+            }
+
             http_service.ready().await?;
             let events_byte_size =
                 std::mem::take(req.metadata_mut()).into_events_estimated_json_encoded_byte_size();
-            let http_response = http_service.call(req).await?;
+            let original_events = req.original_events.take();
+            let mut http_response = http_service.call(req).await?;
+            if let (Some(data_streams), Some(events)) = (data_streams, original_events) {
+                http_response = data_streams
+                    .retry_documents(&mut http_service, &events, http_response)
+                    .await;
+            }
 
             let event_status = get_event_status(&http_response);
             Ok(ElasticsearchResponse {
@@ -15,7 +15,7 @@ use crate::{
 };
 
 use super::{
-    encoder::{DocumentMetadata, DocumentVersion, DocumentVersionType},
+    encoder::{DocumentMetadata, DocumentParameters, DocumentVersion, DocumentVersionType},
     ElasticsearchCommon, ElasticsearchConfig, VersionType,
 };
 
@@ -25,6 +25,26 @@ pub struct PartitionKey {
     pub bulk_action: BulkAction,
 }
 
+/// The templates of the parameters of each document that are rendered from its event.
+#[derive(Clone, Debug, Default)]
+pub struct DocumentTemplates {
+    pub id: Option<Template>,
+    pub routing: Option<Template>,
+    /// The pipeline is only rendered for each document if it depends on the event, as it's
+    /// otherwise a parameter of the requests.
+    pub pipeline: Option<Template>,
+}
+
+impl DocumentTemplates {
+    pub fn new(config: &ElasticsearchConfig) -> Self {
+        Self {
+            id: config.document_id.clone(),
+            routing: config.routing.clone(),
+            pipeline: config.pipeline.clone().filter(Template::is_dynamic),
+        }
+    }
+}
+
 pub struct ElasticsearchSink<S> {
     pub batch_settings: BatcherSettings,
     pub request_builder: ElasticsearchRequestBuilder,
@@ -33,6 +53,7 @@ pub struct ElasticsearchSink<S> {
     pub metric_to_log: MetricToLog,
     pub mode: ElasticsearchCommonMode,
     pub id_key_field: Option<ConfigValuePath>,
+    pub document_templates: DocumentTemplates,
 }
 
 impl<S> ElasticsearchSink<S> {
@@ -51,6 +72,7 @@ impl<S> ElasticsearchSink<S> {
             metric_to_log: common.metric_to_log.clone(),
             mode: common.mode.clone(),
             id_key_field: config.id_key.clone(),
+            document_templates: DocumentTemplates::new(config),
         })
     }
 }
@@ -65,6 +87,7 @@ where
     pub async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mode = self.mode;
         let id_key_field = self.id_key_field.as_ref();
+        let document_templates = &self.document_templates;
         let transformer = self.transformer.clone();
 
         input
@@ -82,7 +105,13 @@ where
             })
             .filter_map(|x| async move { x })
             .filter_map(move |log| {
-                future::ready(process_log(log, &mode, id_key_field, &transformer))
+                future::ready(process_log(
+                    log,
+                    &mode,
+                    id_key_field,
+                    document_templates,
+                    &transformer,
+                ))
             })
             .batched(self.batch_settings.as_byte_size_config())
             .request_builder(
@@ -110,6 +139,7 @@ pub(super) fn process_log(
     mut log: LogEvent,
     mode: &ElasticsearchCommonMode,
     id_key_field: Option<&ConfigValuePath>,
+    document_templates: &DocumentTemplates,
     transformer: &Transformer,
 ) -> Option<ProcessedEvent> {
     let index = mode.index(&log)?;
@@ -124,7 +154,11 @@ pub(super) fn process_log(
     {
         Some(String::from_utf8_lossy(&key).into_owned())
     } else {
-        None
+        render_parameter(document_templates.id.as_ref(), &log, "document_id")
+    };
+    let document_parameters = DocumentParameters {
+        routing: render_parameter(document_templates.routing.as_ref(), &log, "routing"),
+        pipeline: render_parameter(document_templates.pipeline.as_ref(), &log, "pipeline"),
     };
     let document_metadata = match (id.clone(), mode.version_type(), mode.version(&log)) {
         (None, _, _) => DocumentMetadata::WithoutId,
@@ -159,9 +193,29 @@ pub(super) fn process_log(
         bulk_action,
         log,
         document_metadata,
+        document_parameters,
     })
 }
 
+/// Renders a parameter of a document, which isn't set if it can't be rendered, or is empty.
+fn render_parameter(
+    template: Option<&Template>,
+    log: &LogEvent,
+    field: &'static str,
+) -> Option<String> {
+    template?
+        .render_string(log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: false,
+            });
+        })
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
 #[async_trait]
 impl<S> StreamSink<Event> for ElasticsearchSink<S>
 where
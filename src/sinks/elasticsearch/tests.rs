@@ -7,8 +7,9 @@ use crate::{
     event::{LogEvent, Metric, MetricKind, MetricValue, ObjectMap, Value},
     sinks::{
         elasticsearch::{
-            sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion,
-            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode, VersionType,
+            sink::{process_log, DocumentTemplates},
+            BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion, ElasticsearchCommon,
+            ElasticsearchConfig, ElasticsearchMode, VersionType,
         },
         util::encoding::Encoder,
     },
@@ -53,7 +54,7 @@ async fn sets_create_action_when_configured() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -65,6 +66,61 @@ async fn sets_create_action_when_configured() {
     assert_eq!(encoded.len(), encoded_size);
 }
 
+#[tokio::test]
+async fn encodes_document_parameters_from_templates() {
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            index: parse_template("vector"),
+            ..Default::default()
+        },
+        document_id: Some(parse_template("{{ tenant }}-{{ request_id }}")),
+        routing: Some(parse_template("{{ tenant }}")),
+        pipeline: Some(parse_template("{{ service }}-pipeline")),
+        endpoints: vec![String::from("https://example.com")],
+        api_version: ElasticsearchApiVersion::V8,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+    // The pipeline is set for each document instead of the requests.
+    assert_eq!(es.query_params.get("pipeline"), None);
+
+    let mut log = LogEvent::from("hello there");
+    log.insert("tenant", "acme");
+    log.insert("request_id", "42");
+    log.insert("service", "nginx");
+
+    let templates = DocumentTemplates::new(&config);
+    let events = [log, LogEvent::from("hello there")]
+        .into_iter()
+        .map(|log| process_log(log, &es.mode, None, &templates, &config.encoding))
+        .collect::<Option<Vec<_>>>()
+        .unwrap();
+    let mut encoded = vec![];
+    es.request_builder
+        .encoder
+        .encode_input(events, &mut encoded)
+        .unwrap();
+
+    let expected = r#"{"index":{"_index":"vector","_id":"acme-42","routing":"acme","pipeline":"nginx-pipeline"}}
+{"message":"hello there","request_id":"42","service":"nginx","tenant":"acme"}
+{"index":{"_index":"vector"}}
+{"message":"hello there"}
+"#;
+    assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
+}
+
+#[tokio::test]
+async fn document_id_and_id_key_are_exclusive() {
+    let config = ElasticsearchConfig {
+        id_key: Some("my_id".into()),
+        document_id: Some(parse_template("{{ my_id }}")),
+        endpoints: vec![String::from("https://example.com")],
+        api_version: ElasticsearchApiVersion::V8,
+        ..Default::default()
+    };
+    assert!(ElasticsearchCommon::parse_single(&config).await.is_err());
+}
+
 #[tokio::test]
 async fn encoding_with_external_versioning_without_version_set_does_not_include_version() {
     let config = ElasticsearchConfig {
@@ -119,7 +175,14 @@ async fn encoding_with_external_versioning_with_version_set_includes_version() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, config.id_key.as_ref(), &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                config.id_key.as_ref(),
+                &Default::default(),
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -167,7 +230,14 @@ async fn encoding_with_external_gte_versioning_with_version_set_includes_version
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, config.id_key.as_ref(), &config.encoding).unwrap()],
+            vec![process_log(
+                log,
+                &es.mode,
+                config.id_key.as_ref(),
+                &Default::default(),
+                &config.encoding,
+            )
+            .unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -240,7 +310,7 @@ async fn encode_datastream_mode() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -295,7 +365,7 @@ async fn encode_datastream_mode_no_routing() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -332,7 +402,7 @@ async fn handle_metrics() {
     es.request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -447,7 +517,7 @@ async fn encode_datastream_mode_no_sync() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -483,7 +553,7 @@ async fn allows_using_except_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -518,7 +588,7 @@ async fn allows_using_only_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -644,7 +714,8 @@ async fn datastream_index_name() {
             ),
         );
 
-        let processed_event = process_log(log, &es.mode, None, &config.encoding).unwrap();
+        let processed_event =
+            process_log(log, &es.mode, None, &Default::default(), &config.encoding).unwrap();
         assert_eq!(processed_event.index, test_case.want, "{test_case:?}");
     }
 }
@@ -109,6 +109,11 @@ pub struct KafkaSinkConfig {
     ))]
     pub librdkafka_options: HashMap<String, String>,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub delivery: KafkaDeliveryConfig,
+
     /// The log field name to use for the Kafka headers.
     ///
     /// If omitted, no headers are written.
@@ -124,6 +129,68 @@ pub struct KafkaSinkConfig {
         skip_serializing_if = "crate::serde::is_default"
     )]
     pub acknowledgements: AcknowledgementsConfig,
+
+    // This setting is relevant only for the `azure_event_hubs` sink, whose partition key is a
+    // template, and takes precedence over `key_field`. It should be left as `None` everywhere else.
+    #[serde(skip)]
+    pub key_template: Option<Template>,
+}
+
+/// How events written to Kafka are protected against duplication.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The delivery mode of the producer."))]
+pub enum KafkaDeliveryConfig {
+    /// Events are written at least once.
+    ///
+    /// Records that the producer retries, such as after losing the connection to a broker, may be
+    /// written more than once.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// Events are written by an [idempotent producer][idempotence].
+    ///
+    /// Kafka deduplicates the records that the producer retries, so that each record is written
+    /// once while the sink runs. Events that are sent again after Vector restarts can still be
+    /// written twice.
+    ///
+    /// [idempotence]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#idempotent-producer
+    Idempotent,
+
+    /// Events are written in [transactions][transactions], one per batch of events.
+    ///
+    /// A transaction is committed once all of its events are written, and only then are its events
+    /// acknowledged. If any of its events can't be written, the transaction is aborted and none of
+    /// its events are acknowledged, so that they are sent again by sources with end-to-end
+    /// acknowledgements or from disk buffers. Consumers that read with `isolation.level` set to
+    /// `read_committed` never see the events of aborted transactions, so restarting Vector
+    /// doesn't duplicate events in downstream topics.
+    ///
+    /// Batches are bounded by the `batch` options, and transactions are written one at a time.
+    ///
+    /// [transactions]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#transactional-producer
+    Transactional {
+        /// The transactional ID of the producer.
+        ///
+        /// It must be unique to this sink, and must stay the same across restarts of Vector, so
+        /// that the transactions left open by a previous run are aborted when the sink starts
+        /// writing.
+        #[configurable(metadata(docs::examples = "vector-aggregator-0"))]
+        transactional_id: String,
+
+        /// The maximum time that a transaction can stay open, in milliseconds, before the brokers
+        /// abort it.
+        ///
+        /// It can't be lower than `message_timeout_ms`, and can't be higher than the
+        /// `transaction.max.timeout.ms` of the brokers.
+        #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+        #[serde(default = "default_transaction_timeout_ms")]
+        #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+        transaction_timeout_ms: Duration,
+    },
 }
 
 const fn default_socket_timeout_ms() -> Duration {
@@ -134,6 +201,10 @@ const fn default_message_timeout_ms() -> Duration {
     Duration::from_millis(300000) // default in librdkafka
 }
 
+const fn default_transaction_timeout_ms() -> Duration {
+    default_message_timeout_ms()
+}
+
 fn example_librdkafka_options() -> HashMap<String, String> {
     HashMap::<_, _>::from_iter([
         ("client.id".to_string(), "${ENV_VAR}".to_string()),
@@ -223,6 +294,29 @@ impl KafkaSinkConfig {
             client_config.set(key, value.to_string());
         }
 
+        match &self.delivery {
+            KafkaDeliveryConfig::AtLeastOnce => {}
+            KafkaDeliveryConfig::Idempotent => {
+                client_config.set("enable.idempotence", "true");
+            }
+            KafkaDeliveryConfig::Transactional {
+                transactional_id,
+                transaction_timeout_ms,
+            } => {
+                // Records that aren't delivered within a transaction would fail it anyway, so
+                // librdkafka refuses to time them out later than the transaction.
+                if self.message_timeout_ms > *transaction_timeout_ms {
+                    return Err("`message_timeout_ms` can't be higher than \
+                                `delivery.transaction_timeout_ms`."
+                        .into());
+                }
+                client_config.set("transactional.id", transactional_id).set(
+                    "transaction.timeout.ms",
+                    transaction_timeout_ms.as_millis().to_string(),
+                );
+            }
+        }
+
         for (key, value) in self.librdkafka_options.iter() {
             debug!(option = %key, value = %value, "Setting librdkafka option.");
             client_config.set(key.as_str(), value.as_str());
@@ -246,8 +340,10 @@ impl GenerateConfig for KafkaSinkConfig {
             socket_timeout_ms: default_socket_timeout_ms(),
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
+            delivery: Default::default(),
             headers_key: None,
             acknowledgements: Default::default(),
+            key_template: None,
         })
         .unwrap()
     }
@@ -282,4 +378,51 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn parse_config(config: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            {config}
+            "#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn delivery_sets_librdkafka_options() {
+        let client_config = parse_config("").to_rdkafka().unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), None);
+        assert_eq!(client_config.get("transactional.id"), None);
+
+        let client_config = parse_config(r#"delivery.mode = "idempotent""#)
+            .to_rdkafka()
+            .unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+
+        let client_config = parse_config(
+            r#"
+            delivery.mode = "transactional"
+            delivery.transactional_id = "vector-0"
+            "#,
+        )
+        .to_rdkafka()
+        .unwrap();
+        assert_eq!(client_config.get("transactional.id"), Some("vector-0"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("300000"));
+    }
+
+    #[test]
+    fn transaction_timeout_covers_message_timeout() {
+        let config = parse_config(
+            r#"
+            message_timeout_ms = 450000
+            delivery.mode = "transactional"
+            delivery.transactional_id = "vector-0"
+            "#,
+        );
+        assert!(config.to_rdkafka().is_err());
+    }
 }
@@ -12,6 +12,7 @@ use crate::{
 
 pub struct KafkaRequestBuilder {
     pub key_field: Option<OwnedTargetPath>,
+    pub key_template: Option<Template>,
     pub headers_key: Option<OwnedTargetPath>,
     pub encoder: (Transformer, Encoder<()>),
 }
@@ -41,7 +42,10 @@ impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
 
         let metadata = KafkaRequestMetadata {
             finalizers: event.take_finalizers(),
-            key: get_key(&event, self.key_field.as_ref()),
+            key: match &self.key_template {
+                Some(key_template) => render_key(&event, key_template),
+                None => get_key(&event, self.key_field.as_ref()),
+            },
             timestamp_millis: get_timestamp_millis(&event),
             headers: get_headers(&event, self.headers_key.as_ref()),
             topic,
@@ -75,6 +79,19 @@ fn get_key(event: &Event, key_field: Option<&OwnedTargetPath>) -> Option<Bytes>
     })
 }
 
+fn render_key(event: &Event, key_template: &Template) -> Option<Bytes> {
+    key_template
+        .render(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                field: Some("key"),
+                drop_event: false,
+                error,
+            });
+        })
+        .ok()
+}
+
 fn get_timestamp_millis(event: &Event) -> Option<i64> {
     match &event {
         Event::Log(log) => log.get_timestamp().and_then(|v| v.as_timestamp()).copied(),
@@ -141,4 +158,21 @@ mod tests {
         assert_eq!(headers.get(1).key, "b-key");
         assert_eq!(headers.get(1).value.unwrap(), "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_render_key() {
+        let key_template = Template::try_from("{{ tenant }}-{{ user_id }}").unwrap();
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("tenant", "acme");
+        event.as_mut_log().insert("user_id", 42);
+
+        assert_eq!(
+            render_key(&event, &key_template),
+            Some(Bytes::from("acme-42"))
+        );
+        assert_eq!(
+            render_key(&Event::Log(LogEvent::from("hello")), &key_template),
+            None
+        );
+    }
 }
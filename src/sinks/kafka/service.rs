@@ -1,22 +1,27 @@
 use std::{
     sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
     },
     task::{Context, Poll},
     time::Duration,
 };
 
 use bytes::Bytes;
+use futures::future::join_all;
 use rdkafka::{
     error::KafkaError,
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     types::RDKafkaErrorCode,
+    ClientConfig, ClientContext,
 };
 use vector_lib::config;
 
-use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
+use super::sink::create_producer;
+use crate::{
+    internal_events::KafkaTransactionError, kafka::KafkaStatisticsContext, sinks::prelude::*,
+};
 
 pub struct KafkaRequest {
     pub body: Bytes,
@@ -52,6 +57,12 @@ impl DriverResponse for KafkaResponse {
     }
 }
 
+impl ByteSizeOf for KafkaRequest {
+    fn allocated_bytes(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map_or(0, |key| key.len())
+    }
+}
+
 impl Finalizable for KafkaRequest {
     fn take_finalizers(&mut self) -> EventFinalizers {
         std::mem::take(&mut self.metadata.finalizers)
@@ -87,15 +98,15 @@ impl Drop for BlockedRecordState {
 }
 
 #[derive(Clone)]
-pub struct KafkaService {
-    kafka_producer: FutureProducer<KafkaStatisticsContext>,
+pub struct KafkaService<C: ClientContext + 'static = KafkaStatisticsContext> {
+    kafka_producer: FutureProducer<C>,
 
     /// The number of records blocked from being enqueued on the producer.
     records_blocked: Arc<AtomicUsize>,
 }
 
-impl KafkaService {
-    pub(crate) fn new(kafka_producer: FutureProducer<KafkaStatisticsContext>) -> KafkaService {
+impl<C: ClientContext + 'static> KafkaService<C> {
+    pub(crate) fn new(kafka_producer: FutureProducer<C>) -> Self {
         KafkaService {
             kafka_producer,
             records_blocked: Arc::new(AtomicUsize::new(0)),
@@ -103,7 +114,7 @@ impl KafkaService {
     }
 }
 
-impl Service<KafkaRequest> for KafkaService {
+impl<C: ClientContext + Clone + 'static> Service<KafkaRequest> for KafkaService<C> {
     type Response = KafkaResponse;
     type Error = KafkaError;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
@@ -200,3 +211,276 @@ impl Service<KafkaRequest> for KafkaService {
         })
     }
 }
+
+/// A batch of records that are written in one transaction.
+pub struct KafkaTransactionRequest {
+    pub requests: Vec<KafkaRequest>,
+    pub finalizers: EventFinalizers,
+    pub request_metadata: RequestMetadata,
+}
+
+impl From<Vec<KafkaRequest>> for KafkaTransactionRequest {
+    fn from(mut requests: Vec<KafkaRequest>) -> Self {
+        let finalizers = requests.take_finalizers();
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter_mut()
+                .map(|request| std::mem::take(request.metadata_mut())),
+        );
+
+        Self {
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+/// Writes the records of each request in a transaction, which is only committed if all of them
+/// are delivered.
+///
+/// A producer runs one transaction at a time, so requests must be sent one at a time.
+#[derive(Clone)]
+pub struct KafkaTransactionService<C: ClientContext + 'static = KafkaStatisticsContext> {
+    /// The service of the current producer, which is replaced when an error leaves it unusable.
+    inner: Arc<Mutex<KafkaService<C>>>,
+    client_config: ClientConfig,
+    context: C,
+    transaction_timeout: Duration,
+
+    /// Whether the transactions of the producer were initialized, which aborts the transactions
+    /// left open by previous producers with the same transactional ID.
+    transactions_initialized: Arc<AtomicBool>,
+}
+
+impl<C: ClientContext + Clone + 'static> KafkaTransactionService<C> {
+    pub(crate) fn new(
+        inner: KafkaService<C>,
+        client_config: ClientConfig,
+        context: C,
+        transaction_timeout: Duration,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+            client_config,
+            context,
+            transaction_timeout,
+            transactions_initialized: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn inner(&self) -> KafkaService<C> {
+        self.inner.lock().expect("mutex poisoned").clone()
+    }
+
+    /// Runs an operation on the transactions of the producer, which blocks until the brokers
+    /// respond.
+    async fn transaction_operation<F>(
+        &self,
+        inner: &KafkaService<C>,
+        operation: F,
+    ) -> Result<(), KafkaError>
+    where
+        F: FnOnce(&FutureProducer<C>, Duration) -> Result<(), KafkaError> + Send + 'static,
+    {
+        let producer = inner.kafka_producer.clone();
+        let timeout = self.transaction_timeout;
+        tokio::task::spawn_blocking(move || operation(&producer, timeout))
+            .await
+            .expect("Kafka transaction operation panicked")
+    }
+
+    /// Initializes the transactions of the producer, unless they already are.
+    async fn init_transactions(&self, inner: &KafkaService<C>) -> Result<(), KafkaError> {
+        if !self.transactions_initialized.load(Ordering::Relaxed) {
+            self.transaction_operation(inner, |producer, timeout| {
+                producer.init_transactions(timeout)
+            })
+            .await?;
+            self.transactions_initialized.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Aborts the ongoing transaction, so that consumers reading committed records never see its
+    /// records.
+    ///
+    /// A producer that was fenced by a newer one with the same transactional ID, or that failed
+    /// otherwise beyond recovery, can't abort it, and is replaced instead. The new producer aborts
+    /// it when it initializes its transactions.
+    async fn abort_transaction(&self, inner: &KafkaService<C>, error: Option<&KafkaError>) {
+        if !is_unusable(inner, error) {
+            match self
+                .transaction_operation(inner, |producer, timeout| {
+                    producer.abort_transaction(timeout)
+                })
+                .await
+            {
+                Ok(()) => return,
+                Err(error) => {
+                    warn!(message = "Failed to abort Kafka transaction.", %error);
+                    if !is_unusable(inner, Some(&error)) {
+                        return;
+                    }
+                }
+            }
+        }
+        self.replace_producer();
+    }
+
+    /// Replaces the producer with a new one, whose transactions are initialized before its first
+    /// transaction.
+    fn replace_producer(&self) {
+        match create_producer(self.client_config.clone(), self.context.clone()) {
+            Ok(producer) => {
+                *self.inner.lock().expect("mutex poisoned") = KafkaService::new(producer);
+                self.transactions_initialized
+                    .store(false, Ordering::Relaxed);
+            }
+            Err(error) => emit!(KafkaTransactionError {
+                operation: "create_producer",
+                error,
+            }),
+        }
+    }
+
+    /// Aborts the transaction after one of its operations failed, and returns the response of its
+    /// events, which aren't delivered.
+    async fn fail(
+        &self,
+        inner: &KafkaService<C>,
+        operation: &'static str,
+        error: KafkaError,
+    ) -> KafkaResponse {
+        self.abort_transaction(inner, Some(&error)).await;
+        emit!(KafkaTransactionError {
+            operation,
+            error: error.into(),
+        });
+        errored_response()
+    }
+}
+
+/// Whether an error, or an earlier one, left the producer unusable, such as when a newer producer
+/// with the same transactional ID fenced it.
+fn is_unusable<C: ClientContext + 'static>(
+    inner: &KafkaService<C>,
+    error: Option<&KafkaError>,
+) -> bool {
+    let fatal = error.is_some_and(|error| match error {
+        KafkaError::Transaction(error) => error.is_fatal(),
+        error => matches!(
+            error.rdkafka_error_code(),
+            Some(
+                RDKafkaErrorCode::Fatal
+                    | RDKafkaErrorCode::Fenced
+                    | RDKafkaErrorCode::ProducerFenced
+                    | RDKafkaErrorCode::InvalidProducerEpoch
+            )
+        ),
+    });
+    fatal || inner.kafka_producer.client().fatal_error().is_some()
+}
+
+/// The response of events that weren't delivered, which sources with acknowledgements retry.
+fn errored_response() -> KafkaResponse {
+    KafkaResponse {
+        event_byte_size: config::telemetry().create_request_count_byte_size(),
+        raw_byte_size: 0,
+        event_status: EventStatus::Errored,
+    }
+}
+
+impl<C: ClientContext + Clone + 'static> Service<KafkaTransactionRequest>
+    for KafkaTransactionService<C>
+{
+    type Response = KafkaResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.lock().expect("mutex poisoned").poll_ready(cx)
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let inner = this.inner();
+            let event_byte_size = request
+                .request_metadata
+                .into_events_estimated_json_encoded_byte_size();
+
+            if let Err(error) = this.init_transactions(&inner).await {
+                // No transaction was begun, so there's none to abort.
+                if is_unusable(&inner, Some(&error)) {
+                    this.replace_producer();
+                }
+                emit!(KafkaTransactionError {
+                    operation: "init_transactions",
+                    error: error.into(),
+                });
+                return Ok(errored_response());
+            }
+            if let Err(error) = inner.kafka_producer.begin_transaction() {
+                return Ok(this.fail(&inner, "begin_transaction", error).await);
+            }
+
+            let sends = request
+                .requests
+                .into_iter()
+                .map(|request| inner.clone().call(request));
+            match join_all(sends)
+                .await
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(responses)
+                    if responses
+                        .iter()
+                        .all(|response| response.event_status == EventStatus::Delivered) =>
+                {
+                    let raw_byte_size = responses
+                        .iter()
+                        .map(|response| response.raw_byte_size)
+                        .sum();
+                    let committed = this
+                        .transaction_operation(&inner, |producer, timeout| {
+                            producer.commit_transaction(timeout)
+                        })
+                        .await;
+                    match committed {
+                        Ok(()) => Ok(KafkaResponse {
+                            event_byte_size,
+                            raw_byte_size,
+                            event_status: EventStatus::Delivered,
+                        }),
+                        Err(error) => Ok(this.fail(&inner, "commit_transaction", error).await),
+                    }
+                }
+                // Some of the records weren't delivered, so none of them are.
+                Ok(_) => {
+                    this.abort_transaction(&inner, None).await;
+                    Ok(errored_response())
+                }
+                Err(error) => Ok(this.fail(&inner, "send", error).await),
+            }
+        })
+    }
+}
@@ -1,17 +1,20 @@
 use rdkafka::{
     error::KafkaError,
-    producer::{BaseProducer, FutureProducer, Producer},
-    ClientConfig,
+    producer::{BaseProducer, DefaultProducerContext, FutureProducer, Producer, ProducerContext},
+    ClientConfig, ClientContext,
 };
 use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use tracing::Span;
 use vrl::path::OwnedTargetPath;
 
-use super::config::KafkaSinkConfig;
+use super::config::{KafkaDeliveryConfig, KafkaSinkConfig};
 use crate::{
     kafka::KafkaStatisticsContext,
-    sinks::kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
+    sinks::kafka::{
+        request_builder::KafkaRequestBuilder,
+        service::{KafkaService, KafkaTransactionRequest, KafkaTransactionService},
+    },
     sinks::prelude::*,
 };
 
@@ -22,34 +25,69 @@ pub(super) enum BuildError {
     KafkaCreateFailed { source: KafkaError },
 }
 
-pub struct KafkaSink {
+pub struct KafkaSink<C: ClientContext + 'static = KafkaStatisticsContext> {
     transformer: Transformer,
     encoder: Encoder<()>,
-    service: KafkaService,
+    service: KafkaService<C>,
     topic: Template,
     key_field: Option<OwnedTargetPath>,
+    key_template: Option<Template>,
     headers_key: Option<OwnedTargetPath>,
+    transactions: Option<Transactions<C>>,
 }
 
-pub(crate) fn create_producer(
+/// The settings of the transactions that batches of events are written in.
+struct Transactions<C> {
+    batch_settings: BatcherSettings,
+    timeout: Duration,
+    /// The configuration and context of the producer, which is recreated when it can't be used
+    /// anymore.
     client_config: ClientConfig,
-) -> crate::Result<FutureProducer<KafkaStatisticsContext>> {
+    context: C,
+}
+
+pub(crate) fn create_producer<C: ClientContext + 'static>(
+    client_config: ClientConfig,
+    context: C,
+) -> crate::Result<FutureProducer<C>> {
     let producer = client_config
-        .create_with_context(KafkaStatisticsContext {
-            expose_lag_metrics: false,
-            span: Span::current(),
-        })
+        .create_with_context(context)
         .context(KafkaCreateFailedSnafu)?;
     Ok(producer)
 }
 
 impl KafkaSink {
     pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+        Self::with_context(
+            config,
+            KafkaStatisticsContext {
+                expose_lag_metrics: false,
+                span: Span::current(),
+            },
+        )
+    }
+}
+
+impl<C: ClientContext + Clone + 'static> KafkaSink<C> {
+    /// Creates a sink whose producers are created with the given context.
+    pub(crate) fn with_context(config: KafkaSinkConfig, context: C) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka()?;
-        let producer = create_producer(producer_config)?;
+        let producer = create_producer(producer_config.clone(), context.clone())?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let transactions = match config.delivery {
+            KafkaDeliveryConfig::AtLeastOnce | KafkaDeliveryConfig::Idempotent => None,
+            KafkaDeliveryConfig::Transactional {
+                transaction_timeout_ms,
+                ..
+            } => Some(Transactions {
+                batch_settings: config.batch.into_batcher_settings()?,
+                timeout: transaction_timeout_ms,
+                client_config: producer_config,
+                context,
+            }),
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
@@ -58,17 +96,20 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
+            key_template: config.key_template,
+            transactions,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
+            key_template: self.key_template,
             headers_key: self.headers_key,
             encoder: (self.transformer, self.encoder),
         };
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -94,15 +135,49 @@ impl KafkaSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
-            .into_driver(self.service)
-            .protocol("kafka")
-            .run()
-            .await
+            });
+
+        match self.transactions {
+            None => {
+                requests
+                    .into_driver(self.service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+            // Transactions are written one at a time, and the events of each are only
+            // acknowledged once it's committed.
+            Some(transactions) => {
+                let service = ServiceBuilder::new().concurrency_limit(1).service(
+                    KafkaTransactionService::new(
+                        self.service,
+                        transactions.client_config,
+                        transactions.context,
+                        transactions.timeout,
+                    ),
+                );
+
+                requests
+                    .batched(transactions.batch_settings.as_byte_size_config())
+                    .map(KafkaTransactionRequest::from)
+                    .into_driver(service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
 pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
+    healthcheck_with_context(config, DefaultProducerContext).await
+}
+
+/// Checks that the brokers can be reached by a producer created with the given context.
+pub(crate) async fn healthcheck_with_context<C: ProducerContext + 'static>(
+    config: KafkaSinkConfig,
+    context: C,
+) -> crate::Result<()> {
     trace!("Healthcheck started.");
     let client_config = config.to_rdkafka().unwrap();
     let topic: Option<String> = match config.healthcheck_topic {
@@ -120,9 +195,13 @@ pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
     };
 
     tokio::task::spawn_blocking(move || {
-        let producer: BaseProducer = client_config.create().unwrap();
+        let producer: BaseProducer<C> = client_config.create_with_context(context).unwrap();
         let topic = topic.as_ref().map(|topic| &topic[..]);
 
+        // Serve the token refresh queued by producers that provide their own `OAUTHBEARER`
+        // tokens, as connecting to the brokers waits for it.
+        producer.poll(Duration::ZERO);
+
         producer
             .client()
             .fetch_metadata(topic, Duration::from_secs(3))
@@ -134,7 +213,7 @@ pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
 }
 
 #[async_trait]
-impl StreamSink<Event> for KafkaSink {
+impl<C: ClientContext + Clone + 'static> StreamSink<Event> for KafkaSink<C> {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
     }
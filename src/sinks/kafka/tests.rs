@@ -10,6 +10,7 @@ mod integration_test {
     use rdkafka::{
         consumer::{BaseConsumer, Consumer},
         message::Headers,
+        producer::{BaseProducer, BaseRecord, Producer},
         Message, Offset, TopicPartitionList,
     };
     use vector_lib::codecs::TextSerializerConfig;
@@ -19,7 +20,11 @@ mod integration_test {
         event::{BatchNotifier, BatchStatus},
     };
 
-    use super::super::{config::KafkaSinkConfig, sink::KafkaSink, *};
+    use super::super::{
+        config::{KafkaDeliveryConfig, KafkaSinkConfig},
+        sink::KafkaSink,
+        *,
+    };
     use crate::{
         event::{ObjectMap, Value},
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
@@ -60,8 +65,10 @@ mod integration_test {
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
+            delivery: Default::default(),
             headers_key: None,
             acknowledgements: Default::default(),
+            key_template: None,
         };
         self::sink::healthcheck(config).await.unwrap();
     }
@@ -84,8 +91,10 @@ mod integration_test {
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
+            delivery: Default::default(),
             headers_key: None,
             acknowledgements: Default::default(),
+            key_template: None,
         };
         self::sink::healthcheck(config).await.unwrap();
     }
@@ -183,8 +192,10 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             batch,
             librdkafka_options,
+            delivery: Default::default(),
             headers_key: None,
             acknowledgements: Default::default(),
+            key_template: None,
         };
         config.clone().to_rdkafka()?;
         self::sink::healthcheck(config.clone()).await?;
@@ -295,6 +306,99 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_transactions_read_committed() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let transactional_id = format!("vector-{}", random_string(10));
+
+        // A transaction left open by a previous producer with the same transactional ID, which the
+        // sink aborts when it initializes its transactions.
+        let mut producer_config = rdkafka::ClientConfig::new();
+        producer_config
+            .set("bootstrap.servers", server.as_str())
+            .set("transactional.id", transactional_id.as_str());
+        let previous_producer: BaseProducer = producer_config.create().unwrap();
+        previous_producer
+            .init_transactions(Duration::from_secs(30))
+            .unwrap();
+        previous_producer.begin_transaction().unwrap();
+        previous_producer
+            .send(BaseRecord::<(), str>::to(&topic).payload("left open"))
+            .unwrap();
+        previous_producer.flush(Duration::from_secs(30)).unwrap();
+
+        let mut batch = BatchConfig::default();
+        batch.max_events = Some(100);
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.as_str()).unwrap(),
+            healthcheck_topic: None,
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch,
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            delivery: KafkaDeliveryConfig::Transactional {
+                transactional_id,
+                transaction_timeout_ms: Duration::from_millis(300000),
+            },
+            headers_key: None,
+            acknowledgements: Default::default(),
+            key_template: None,
+        };
+
+        let num_events = 250;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // Only the records of committed transactions are read, without the record left open.
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                None if out.len() >= input.len() => break,
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -331,8 +435,10 @@ mod integration_test {
             socket_timeout_ms: Duration::from_millis(60000),
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
+            delivery: Default::default(),
             headers_key: Some(headers_key.clone()),
             acknowledgements: Default::default(),
+            key_template: None,
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
         println!("Topic name generated in test: {:?}", topic);
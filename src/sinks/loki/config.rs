@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    num::{NonZeroU32, NonZeroU64},
+};
 
 use vrl::value::Kind;
 
@@ -98,6 +101,10 @@ pub struct LokiConfig {
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tenant_limits: TenantLimitsConfig,
+
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
@@ -190,6 +197,93 @@ pub enum OutOfOrderAction {
     Drop,
 }
 
+/// Per-tenant limits of the pushes to Loki.
+///
+/// When events are pushed on behalf of several tenants, these mirror the [limits][limits] that Loki
+/// enforces for each tenant, so that the events of one tenant don't get the pushes of the others
+/// throttled or rejected.
+///
+/// [limits]: https://grafana.com/docs/loki/latest/configure/#limits_config
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TenantLimitsConfig {
+    /// The limits of the tenants that aren't listed in `tenants`, including events without a
+    /// tenant ID.
+    #[serde(default)]
+    pub default: TenantLimits,
+
+    /// The limits of specific tenants, keyed by tenant ID.
+    #[configurable(metadata(docs::additional_props_description = "The limits of a tenant."))]
+    #[serde(default)]
+    pub tenants: HashMap<String, TenantLimits>,
+}
+
+impl TenantLimitsConfig {
+    /// Returns the limits of a tenant.
+    pub fn get(&self, tenant_id: Option<&str>) -> &TenantLimits {
+        tenant_id
+            .and_then(|tenant_id| self.tenants.get(tenant_id))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// The limits of the pushes of a tenant.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TenantLimits {
+    /// The maximum number of requests pushing events of the tenant allowed within the
+    /// `rate_limit_duration_secs` time window.
+    ///
+    /// Requests beyond this rate are delayed until the tenant is allowed to push again, before they
+    /// are sent, so the delay doesn't count towards the request timeout. The requests of other
+    /// tenants are sent as usual while up to 64 requests are delayed across tenants. Beyond that,
+    /// no more requests are built until delayed requests are sent.
+    ///
+    /// By default, the requests of the tenant are only limited by the `request` options.
+    #[configurable(metadata(docs::type_unit = "requests"))]
+    pub rate_limit_num: Option<NonZeroU32>,
+
+    /// The time window used for the `rate_limit_num` option.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[serde(default = "default_rate_limit_duration_secs")]
+    pub rate_limit_duration_secs: NonZeroU64,
+
+    /// How far behind the latest event of the tenant an event can be while still being sent
+    /// unmodified, regardless of `out_of_order_action`.
+    ///
+    /// Loki 2.4.0 and newer accept out-of-order events within a window of half of their
+    /// `max_chunk_age`, one hour by default. Setting this to that window sends the events that Loki
+    /// accepts as they are, and only applies `out_of_order_action` to the others.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[serde(default)]
+    pub out_of_order_window_secs: u64,
+
+    /// Whether or not the tenant accepts structured metadata.
+    ///
+    /// Loki rejects pushes with structured metadata for tenants whose `allow_structured_metadata`
+    /// limit is disabled. If set to false, the structured metadata of the events of the tenant is
+    /// not sent, and the fields used in it are kept in the events.
+    #[serde(default = "crate::serde::default_true")]
+    pub structured_metadata: bool,
+}
+
+const fn default_rate_limit_duration_secs() -> NonZeroU64 {
+    NonZeroU64::MIN
+}
+
+impl Default for TenantLimits {
+    fn default() -> Self {
+        Self {
+            rate_limit_num: None,
+            rate_limit_duration_secs: default_rate_limit_duration_secs(),
+            out_of_order_window_secs: 0,
+            structured_metadata: true,
+        }
+    }
+}
+
 impl GenerateConfig for LokiConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -1,13 +1,23 @@
-use std::{collections::HashMap, num::NonZeroUsize, sync::LazyLock};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    num::NonZeroUsize,
+    pin::pin,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
 
+use async_stream::stream;
 use bytes::{Bytes, BytesMut};
+use futures::stream::FuturesUnordered;
+use governor::{DefaultDirectRateLimiter, DefaultKeyedRateLimiter, Quota, RateLimiter};
 use regex::Regex;
 use snafu::Snafu;
 use tokio_util::codec::Encoder as _;
 use vrl::path::parse_target_path;
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
+    config::{LokiConfig, OutOfOrderAction, TenantLimits, TenantLimitsConfig},
     event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
     service::{LokiRequest, LokiRetryLogic, LokiService},
 };
@@ -136,6 +146,7 @@ pub(super) struct EventEncoder {
     structured_metadata: HashMap<Template, Template>,
     remove_structured_metadata_fields: bool,
     remove_timestamp: bool,
+    tenant_limits: TenantLimitsConfig,
 }
 
 impl EventEncoder {
@@ -359,8 +370,17 @@ impl EventEncoder {
         let json_byte_size = event.estimated_json_encoded_size_of();
         let mut labels: Vec<(String, String)> = self.build_labels(&event);
         self.remove_label_fields(&mut event);
-        let structured_metadata: Vec<(String, String)> = self.build_structured_metadata(&event);
-        self.remove_structured_metadata_fields(&mut event);
+        let structured_metadata: Vec<(String, String)> = if self
+            .tenant_limits
+            .get(tenant_id.as_deref())
+            .structured_metadata
+        {
+            let structured_metadata = self.build_structured_metadata(&event);
+            self.remove_structured_metadata_fields(&mut event);
+            structured_metadata
+        } else {
+            Vec::new()
+        };
 
         let timestamp = match event.as_log().get_timestamp() {
             Some(Value::Timestamp(ts)) => match ts.timestamp_nanos_opt() {
@@ -449,13 +469,15 @@ impl ByteSizeOf for FilteredRecord {
 struct RecordFilter {
     timestamps: HashMap<PartitionKey, i64>,
     out_of_order_action: OutOfOrderAction,
+    tenant_limits: TenantLimitsConfig,
 }
 
 impl RecordFilter {
-    fn new(out_of_order_action: OutOfOrderAction) -> Self {
+    fn new(out_of_order_action: OutOfOrderAction, tenant_limits: TenantLimitsConfig) -> Self {
         Self {
             timestamps: HashMap::new(),
             out_of_order_action,
+            tenant_limits,
         }
     }
 }
@@ -464,6 +486,17 @@ impl RecordFilter {
     pub fn filter_record(&mut self, mut record: LokiRecord) -> Option<FilteredRecord> {
         if let Some(latest) = self.timestamps.get_mut(&record.partition) {
             if record.event.timestamp < *latest {
+                let window_secs = self
+                    .tenant_limits
+                    .get(record.partition.tenant_id.as_deref())
+                    .out_of_order_window_secs;
+                // Events within the window of the tenant are accepted by Loki as they are.
+                if record.event.timestamp.abs_diff(*latest)
+                    <= window_secs.saturating_mul(1_000_000_000)
+                {
+                    return Some(FilteredRecord::valid(record));
+                }
+
                 match self.out_of_order_action {
                     OutOfOrderAction::Drop => None,
                     OutOfOrderAction::RewriteTimestamp => {
@@ -484,11 +517,179 @@ impl RecordFilter {
     }
 }
 
+/// The maximum number of requests that are delayed by the rate limits of their tenants, across
+/// tenants, before no more requests are built.
+const MAX_DELAYED_REQUESTS: usize = 64;
+
+/// Delays the requests of the tenants that exceed their rate limit.
+pub struct TenantRateLimiter {
+    /// The limiter of the tenants without limits of their own, keyed by tenant ID.
+    default: Option<DefaultKeyedRateLimiter<Option<String>>>,
+    /// The limiters of the tenants with limits of their own, which are unlimited if they're `None`.
+    tenants: HashMap<String, Option<DefaultDirectRateLimiter>>,
+    /// How often the state of the tenants that are back to their full quota is removed from the
+    /// `default` limiter, which is the time window of its limit.
+    retain_interval: Duration,
+    /// When that state was last removed.
+    retained_at: Mutex<Instant>,
+}
+
+impl TenantRateLimiter {
+    pub fn new(limits: &TenantLimitsConfig) -> Self {
+        Self {
+            default: quota(&limits.default).map(RateLimiter::dashmap),
+            tenants: limits
+                .tenants
+                .iter()
+                .map(|(tenant_id, limits)| {
+                    (tenant_id.clone(), quota(limits).map(RateLimiter::direct))
+                })
+                .collect(),
+            retain_interval: Duration::from_secs(limits.default.rate_limit_duration_secs.get()),
+            retained_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Delays the requests of the tenants that exceed their rate limit, while the requests of the
+    /// other tenants are passed through as they come.
+    ///
+    /// The delayed requests of a tenant are kept in order. Once `MAX_DELAYED_REQUESTS` requests
+    /// are delayed, no more requests are taken until one of them is released.
+    pub fn delay<'a>(
+        &'a self,
+        requests: impl Stream<Item = LokiRequest> + 'a,
+    ) -> impl Stream<Item = LokiRequest> + 'a {
+        stream! {
+            let mut requests = pin!(requests);
+            let mut done = false;
+            let mut delayed: HashMap<Option<String>, VecDeque<LokiRequest>> = HashMap::new();
+            let mut delayed_count: usize = 0;
+            // Only the oldest delayed request of each tenant waits for the rate limit, so that
+            // they are released in order.
+            let wait = |tenant_id: Option<String>| async move {
+                self.until_ready(&tenant_id).await;
+                tenant_id
+            };
+            let mut waiting = FuturesUnordered::new();
+
+            while !done || delayed_count > 0 {
+                let request = tokio::select! {
+                    request = requests.next(), if !done && delayed_count < MAX_DELAYED_REQUESTS => {
+                        match request {
+                            None => {
+                                done = true;
+                                None
+                            }
+                            Some(request) => match delayed.get_mut(&request.tenant_id) {
+                                Some(queue) => {
+                                    queue.push_back(request);
+                                    delayed_count += 1;
+                                    None
+                                }
+                                None if self.check(&request.tenant_id) => Some(request),
+                                None => {
+                                    waiting.push(wait(request.tenant_id.clone()));
+                                    let tenant_id = request.tenant_id.clone();
+                                    delayed.insert(tenant_id, VecDeque::from([request]));
+                                    delayed_count += 1;
+                                    None
+                                }
+                            },
+                        }
+                    }
+                    Some(tenant_id) = waiting.next(), if !waiting.is_empty() => {
+                        let queue = delayed
+                            .get_mut(&tenant_id)
+                            .expect("tenant has delayed requests");
+                        let request = queue.pop_front();
+                        delayed_count -= 1;
+                        if queue.is_empty() {
+                            delayed.remove(&tenant_id);
+                        } else {
+                            waiting.push(wait(tenant_id));
+                        }
+                        request
+                    }
+                };
+                if let Some(request) = request {
+                    yield request;
+                }
+            }
+        }
+    }
+
+    /// Returns whether a request of the tenant can be sent now, counting it towards the limit of
+    /// the tenant if so.
+    fn check(&self, tenant_id: &Option<String>) -> bool {
+        match tenant_id
+            .as_ref()
+            .and_then(|tenant_id| self.tenants.get(tenant_id))
+        {
+            Some(Some(limiter)) => limiter.check().is_ok(),
+            Some(None) => true,
+            None => match &self.default {
+                Some(limiter) => {
+                    let ready = limiter.check_key(tenant_id).is_ok();
+                    self.retain_recent(limiter);
+                    ready
+                }
+                None => true,
+            },
+        }
+    }
+
+    /// Waits until a request of the tenant can be sent.
+    async fn until_ready(&self, tenant_id: &Option<String>) {
+        match tenant_id
+            .as_ref()
+            .and_then(|tenant_id| self.tenants.get(tenant_id))
+        {
+            Some(Some(limiter)) => limiter.until_ready().await,
+            Some(None) => {}
+            None => {
+                if let Some(limiter) = &self.default {
+                    limiter.until_key_ready(tenant_id).await;
+                    self.retain_recent(limiter);
+                }
+            }
+        }
+    }
+
+    /// Removes the state of the tenants that are back to their full quota, so that the `default`
+    /// limiter doesn't grow with every tenant that it has seen.
+    fn retain_recent(&self, limiter: &DefaultKeyedRateLimiter<Option<String>>) {
+        let mut retained_at = self.retained_at.lock().expect("mutex poisoned");
+        if retained_at.elapsed() >= self.retain_interval {
+            limiter.retain_recent();
+            limiter.shrink_to_fit();
+            *retained_at = Instant::now();
+        }
+    }
+}
+
+impl fmt::Debug for TenantRateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantRateLimiter")
+            .field("default", &self.default.is_some())
+            .field("tenants", &self.tenants.keys())
+            .finish()
+    }
+}
+
+/// Returns the quota of the requests of a tenant, which allows bursts of the whole limit.
+fn quota(limits: &TenantLimits) -> Option<Quota> {
+    let num = limits.rate_limit_num?;
+    let period = Duration::from_secs(limits.rate_limit_duration_secs.get()) / num.get();
+    Quota::with_period(period).map(|quota| quota.allow_burst(num))
+}
+
 pub struct LokiSink {
     request_builder: LokiRequestBuilder,
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
+    tenant_limits: TenantLimitsConfig,
+    rate_limiter: TenantRateLimiter,
     service: Svc<LokiService, LokiRetryLogic>,
     protocol: &'static str,
 }
@@ -547,9 +748,12 @@ impl LokiSink {
                 remove_label_fields: config.remove_label_fields,
                 remove_structured_metadata_fields: config.remove_structured_metadata_fields,
                 remove_timestamp: config.remove_timestamp,
+                tenant_limits: config.tenant_limits.clone(),
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
+            rate_limiter: TenantRateLimiter::new(&config.tenant_limits),
+            tenant_limits: config.tenant_limits,
             service,
             protocol,
         })
@@ -557,7 +761,7 @@ impl LokiSink {
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut encoder = self.encoder.clone();
-        let mut filter = RecordFilter::new(self.out_of_order_action);
+        let mut filter = RecordFilter::new(self.out_of_order_action, self.tenant_limits);
 
         // out_of_order_action's that require a complete ordering are limited to building 1 request
         // at a time
@@ -568,8 +772,9 @@ impl LokiSink {
             }
         };
         let batch_settings = self.batch_settings;
+        let rate_limiter = self.rate_limiter;
 
-        input
+        let requests = input
             .map(|event| encoder.encode_event(event))
             .filter_map(|event| async { event })
             .map(|record| filter.filter_record(record))
@@ -605,7 +810,12 @@ impl LokiSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
+            });
+
+        // Requests wait for the rate limit of their tenant before they're dispatched, so that they
+        // don't count towards the request timeout, nor hold back the requests of other tenants.
+        rate_limiter
+            .delay(requests)
             .into_driver(self.service)
             .protocol(self.protocol)
             .run()
@@ -629,17 +839,27 @@ fn slugify_text(input: String) -> String {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, convert::TryFrom};
+    use std::{
+        collections::HashMap,
+        convert::TryFrom,
+        num::{NonZeroU32, NonZeroU64},
+    };
 
     use futures::stream::StreamExt;
     use vector_lib::codecs::JsonSerializerConfig;
     use vector_lib::event::{Event, LogEvent, ObjectMap, Value};
     use vector_lib::lookup::PathPrefix;
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use super::{EventEncoder, KeyPartitioner, RecordFilter, TenantRateLimiter};
     use crate::{
-        codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
-        template::Template, test_util::random_lines,
+        codecs::Encoder,
+        config::log_schema,
+        sinks::loki::{
+            config::{OutOfOrderAction, TenantLimits, TenantLimitsConfig},
+            service::LokiRequest,
+        },
+        template::Template,
+        test_util::random_lines,
     };
 
     #[test]
@@ -653,6 +873,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -698,6 +919,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -750,6 +972,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
 
         let message = r#"
@@ -802,6 +1025,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
 
         let message = r#"
@@ -842,6 +1066,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
 
         let msg: ObjectMap = serde_json::from_str("{}")?;
@@ -865,6 +1090,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: true,
+            tenant_limits: Default::default(),
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -897,6 +1123,7 @@ mod tests {
             remove_label_fields: true,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -935,6 +1162,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
 
         let message = r#"
@@ -990,6 +1218,7 @@ mod tests {
             remove_label_fields: false,
             remove_structured_metadata_fields: false,
             remove_timestamp: false,
+            tenant_limits: Default::default(),
         };
         let base = chrono::Utc::now();
         let events = random_lines(100)
@@ -1010,7 +1239,7 @@ mod tests {
                 event
             })
             .collect::<Vec<_>>();
-        let mut filter = RecordFilter::new(OutOfOrderAction::Drop);
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, Default::default());
         let stream = futures::stream::iter(events)
             .map(|event| encoder.encode_event(event))
             .filter_map(|event| async { event })
@@ -1025,4 +1254,133 @@ mod tests {
         }
         assert_eq!(result.len(), 17);
     }
+
+    #[tokio::test]
+    async fn rate_limiter_delays_only_limited_tenants() {
+        let limits = TenantLimitsConfig {
+            default: Default::default(),
+            tenants: HashMap::from([(
+                "limited".to_string(),
+                TenantLimits {
+                    rate_limit_num: NonZeroU32::new(1),
+                    rate_limit_duration_secs: NonZeroU64::new(3600).unwrap(),
+                    ..Default::default()
+                },
+            )]),
+        };
+        let rate_limiter = TenantRateLimiter::new(&limits);
+        let requests = [("limited", 1), ("limited", 2), ("other", 3), ("other", 4)]
+            .into_iter()
+            .map(|(tenant_id, id)| LokiRequest {
+                compression: Default::default(),
+                finalizers: Default::default(),
+                payload: id.to_string().into(),
+                tenant_id: Some(tenant_id.to_string()),
+                metadata: Default::default(),
+            });
+
+        // The second request of the limited tenant is delayed for an hour, without holding back
+        // the requests of the other tenant.
+        let sent = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            rate_limiter
+                .delay(futures::stream::iter(requests))
+                .take(3)
+                .map(|request| request.payload)
+                .collect::<Vec<_>>(),
+        )
+        .await
+        .expect("requests of other tenants are sent");
+        assert_eq!(sent, ["1", "3", "4"]);
+    }
+
+    #[test]
+    fn encoder_without_tenant_structured_metadata() {
+        let mut structured_metadata = HashMap::default();
+        structured_metadata.insert(
+            Template::try_from("pod").unwrap(),
+            Template::try_from("{{ pod }}").unwrap(),
+        );
+        let mut tenant_limits = TenantLimitsConfig::default();
+        tenant_limits.tenants.insert(
+            "legacy".to_string(),
+            TenantLimits {
+                structured_metadata: false,
+                ..Default::default()
+            },
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(Some(Template::try_from("{{ tenant }}").unwrap())),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels: HashMap::default(),
+            structured_metadata,
+            remove_label_fields: false,
+            remove_structured_metadata_fields: true,
+            remove_timestamp: false,
+            tenant_limits,
+        };
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("tenant", "legacy");
+        event.as_mut_log().insert("pod", "web-server");
+        let record = encoder.encode_event(event).unwrap();
+        assert!(record.event.structured_metadata.is_empty());
+        assert!(String::from_utf8_lossy(&record.event.event).contains("web-server"));
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("tenant", "current");
+        event.as_mut_log().insert("pod", "web-server");
+        let record = encoder.encode_event(event).unwrap();
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![("pod".to_string(), "web-server".to_string())]
+        );
+        assert!(!String::from_utf8_lossy(&record.event.event).contains("web-server"));
+    }
+
+    #[test]
+    fn filter_encoder_drop_outside_window() {
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            remove_label_fields: false,
+            remove_structured_metadata_fields: false,
+            remove_timestamp: false,
+            tenant_limits: Default::default(),
+        };
+        let base = chrono::Utc::now();
+        let events = random_lines(100)
+            .take(20)
+            .map(|e| Event::Log(LogEvent::from(e)))
+            .enumerate()
+            .map(|(i, mut event)| {
+                let log = event.as_mut_log();
+                let ts = if i % 5 == 1 {
+                    base
+                } else {
+                    base + chrono::Duration::seconds(i as i64)
+                };
+                log.insert(
+                    (PathPrefix::Event, log_schema().timestamp_key().unwrap()),
+                    ts,
+                );
+                event
+            })
+            .collect::<Vec<_>>();
+        let mut tenant_limits = TenantLimitsConfig::default();
+        tenant_limits.default.out_of_order_window_secs = 10;
+        let mut filter = RecordFilter::new(OutOfOrderAction::Drop, tenant_limits);
+        let result = events
+            .into_iter()
+            .filter_map(|event| encoder.encode_event(event))
+            .filter_map(|record| filter.filter_record(record))
+            .collect::<Vec<_>>();
+        // Only the event that is 15 seconds behind the latest one is dropped.
+        assert_eq!(result.len(), 19);
+        assert!(result.iter().all(|record| !record.rewritten));
+    }
 }
@@ -26,10 +26,30 @@ pub mod aws_s_s;
 pub mod axiom;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
-#[cfg(feature = "sinks-azure_blob")]
+#[cfg(any(
+    feature = "sinks-azure_blob",
+    feature = "sinks-azure_data_lake",
+    feature = "sources-azure_event_hubs"
+))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_data_explorer")]
+pub mod azure_data_explorer;
+#[cfg(feature = "sinks-azure_data_lake")]
+pub mod azure_data_lake;
+#[cfg(feature = "sinks-azure_event_hubs")]
+pub mod azure_event_hubs;
+#[cfg(feature = "sinks-azure_logs_ingestion")]
+pub mod azure_logs_ingestion;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
+#[cfg(feature = "sinks-azure_monitor_metrics")]
+pub mod azure_monitor_metrics;
+#[cfg(feature = "sinks-azure_service_bus")]
+pub mod azure_service_bus;
+#[cfg(feature = "sinks-azure_storage_queue")]
+pub mod azure_storage_queue;
+#[cfg(feature = "sinks-azure_table_storage")]
+pub mod azure_table_storage;
 #[cfg(feature = "sinks-blackhole")]
 pub mod blackhole;
 #[cfg(feature = "sinks-clickhouse")]
@@ -1,8 +1,12 @@
+use std::time::Duration;
+
 use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
-use tonic::body::BoxBody;
+use serde::{Deserialize, Deserializer};
+use serde_with::serde_as;
+use tonic::{body::BoxBody, codec::CompressionEncoding, Code, Status};
 use tower::ServiceBuilder;
 use vector_lib::configurable::configurable_component;
 
@@ -29,6 +33,7 @@ use crate::{
 };
 
 /// Configuration for the `vector` sink.
+#[serde_as]
 #[configurable_component(sink("vector", "Relay observability data to a Vector instance."))]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
@@ -49,14 +54,15 @@ pub struct VectorConfig {
     #[configurable(metadata(docs::examples = "https://somehost:6000"))]
     address: String,
 
-    /// Whether or not to compress requests.
+    /// The compression of requests.
     ///
-    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    /// If the downstream Vector doesn't accept this compression, such as a version of Vector that
+    /// predates `zstd` support, requests fall back to a compression that it accepts.
     ///
-    /// [gzip_docs]: https://www.gzip.org/
+    /// For backward compatibility, `true` is accepted as `gzip`, and `false` as `none`.
     #[configurable(metadata(docs::advanced))]
-    #[serde(default)]
-    compression: bool,
+    #[serde(default, deserialize_with = "compression_or_bool")]
+    pub(in crate::sinks::vector) compression: VectorCompression,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -70,6 +76,20 @@ pub struct VectorConfig {
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
 
+    /// The interval at which the TLS certificate, key, and CA files are checked for changes, in
+    /// seconds.
+    ///
+    /// When the files change, they are reloaded, so that the client certificate can be rotated
+    /// without restarting Vector. The connection to the downstream Vector is kept, and new
+    /// connections are established with the reloaded certificates.
+    ///
+    /// If not set, the files are only read when the sink is built.
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = 60))]
+    #[configurable(metadata(docs::human_name = "TLS Reload Interval"))]
+    tls_reload_interval_secs: Option<Duration>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -79,6 +99,85 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// The compression of the requests sent to the downstream Vector.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorCompression {
+    /// No compression.
+    #[default]
+    None,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl VectorCompression {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "identity",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    pub(super) const fn encoding(self) -> Option<CompressionEncoding> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some(CompressionEncoding::Gzip),
+            Self::Zstd => Some(CompressionEncoding::Zstd),
+        }
+    }
+
+    /// Returns the compression to fall back to, if the downstream Vector rejected a request
+    /// because it doesn't accept this compression.
+    pub(super) fn fallback(self, status: &Status) -> Option<Self> {
+        if self == Self::None || status.code() != Code::Unimplemented {
+            return None;
+        }
+        let accepted = status
+            .metadata()
+            .get("grpc-accept-encoding")?
+            .to_str()
+            .ok()?
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        if accepted.contains(&self.as_str()) {
+            None
+        } else if self == Self::Zstd && accepted.contains(&Self::Gzip.as_str()) {
+            Some(Self::Gzip)
+        } else {
+            Some(Self::None)
+        }
+    }
+}
+
+fn compression_or_bool<'de, D>(deserializer: D) -> Result<VectorCompression, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CompressionOrBool {
+        Bool(bool),
+        Compression(VectorCompression),
+    }
+
+    Ok(match CompressionOrBool::deserialize(deserializer)? {
+        CompressionOrBool::Bool(true) => VectorCompression::Gzip,
+        CompressionOrBool::Bool(false) => VectorCompression::None,
+        CompressionOrBool::Compression(compression) => compression,
+    })
+}
+
 impl VectorConfig {
     /// Creates a `VectorConfig` with the given address.
     pub fn from_address(addr: Uri) -> Self {
@@ -97,10 +196,11 @@ fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         version: None,
         address: address.to_owned(),
-        compression: false,
+        compression: VectorCompression::None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
+        tls_reload_interval_secs: None,
         acknowledgements: Default::default(),
     }
 }
@@ -109,7 +209,10 @@ fn default_config(address: &str) -> VectorConfig {
 #[typetag::serde(name = "vector")]
 impl SinkConfig for VectorConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSinkType, Healthcheck)> {
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let mut tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        if let Some(interval) = self.tls_reload_interval_secs {
+            tls = tls.reload_every(&self.tls, false, interval)?;
+        }
         let uri = with_default_scheme(&self.address, tls.is_tls())?;
 
         let client = new_client(&tls, cx.proxy())?;
@@ -120,7 +223,8 @@ impl SinkConfig for VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let healthcheck_client =
+            VectorService::new(client.clone(), healthcheck_uri, VectorCompression::None);
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
         let service = VectorService::new(client, uri, self.compression);
         let request_settings = self.request.into_settings();
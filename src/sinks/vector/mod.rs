@@ -50,7 +50,7 @@ mod tests {
         event::{BatchNotifier, BatchStatus},
     };
 
-    use super::config::with_default_scheme;
+    use super::config::{with_default_scheme, VectorCompression};
     use super::*;
     use crate::{
         config::{SinkConfig as _, SinkContext},
@@ -193,6 +193,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compression_falls_back_to_accepted_compression() {
+        let rejected = |accepted: &'static str| {
+            let mut status = tonic::Status::unimplemented("compression scheme is not supported");
+            status
+                .metadata_mut()
+                .insert("grpc-accept-encoding", accepted.parse().unwrap());
+            status
+        };
+
+        assert_eq!(
+            VectorCompression::Zstd.fallback(&rejected("gzip,identity")),
+            Some(VectorCompression::Gzip)
+        );
+        assert_eq!(
+            VectorCompression::Zstd.fallback(&rejected("identity")),
+            Some(VectorCompression::None)
+        );
+        assert_eq!(
+            VectorCompression::Zstd.fallback(&rejected("zstd,gzip,identity")),
+            None
+        );
+        assert_eq!(
+            VectorCompression::Gzip.fallback(&tonic::Status::unavailable("unavailable")),
+            None
+        );
+    }
+
+    #[test]
+    fn compression_accepts_booleans() {
+        let compression = |value: &str| {
+            let config = format!("address = \"127.0.0.1:6000\"\ncompression = {}", value);
+            toml::from_str::<VectorConfig>(&config).unwrap().compression
+        };
+
+        assert_eq!(compression("true"), VectorCompression::Gzip);
+        assert_eq!(compression("false"), VectorCompression::None);
+        assert_eq!(compression("\"zstd\""), VectorCompression::Zstd);
+    }
+
     async fn get_received(
         rx: mpsc::Receiver<(Parts, Bytes)>,
         assert_parts: impl Fn(Parts),
@@ -1,17 +1,21 @@
-use std::task::{Context, Poll};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
 
-use futures::{future::BoxFuture, TryFutureExt};
+use futures::future::BoxFuture;
 use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use prost::Message;
-use tonic::{body::BoxBody, IntoRequest};
+use tonic::{body::BoxBody, IntoRequest, Status};
 use tower::Service;
 use vector_lib::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
 use vector_lib::stream::DriverResponse;
 
-use super::VectorSinkError;
+use super::{config::VectorCompression, VectorSinkError};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
@@ -22,9 +26,53 @@ use crate::{
 
 #[derive(Clone, Debug)]
 pub struct VectorService {
+    /// The client, which sends uncompressed requests.
     pub client: proto_vector::Client<HyperSvc>,
     pub protocol: String,
     pub endpoint: String,
+    compression: NegotiatedCompression,
+}
+
+/// The compression of the requests, as negotiated with the downstream Vector.
+///
+/// The configured compression is used until the downstream Vector rejects it, as older versions
+/// of Vector don't accept `zstd`, in which case the requests fall back to a compression that the
+/// downstream Vector accepts.
+#[derive(Clone, Debug)]
+struct NegotiatedCompression(Arc<Mutex<Negotiation>>);
+
+#[derive(Clone, Copy, Debug)]
+struct Negotiation {
+    compression: VectorCompression,
+    /// Whether the downstream Vector has accepted a request with this compression, after which
+    /// requests no longer need to be kept to be sent again with another compression.
+    accepted: bool,
+}
+
+impl NegotiatedCompression {
+    fn new(compression: VectorCompression) -> Self {
+        Self(Arc::new(Mutex::new(Negotiation {
+            compression,
+            accepted: compression == VectorCompression::None,
+        })))
+    }
+
+    fn get(&self) -> Negotiation {
+        *self.0.lock().expect("mutex poisoned")
+    }
+
+    fn set(&self, compression: VectorCompression, accepted: bool) {
+        let mut negotiation = self.0.lock().expect("mutex poisoned");
+        // Another request may have fallen back to another compression in the meantime.
+        if negotiation.compression == compression {
+            negotiation.accepted |= accepted;
+        } else if !accepted {
+            *negotiation = Negotiation {
+                compression,
+                accepted: compression == VectorCompression::None,
+            };
+        }
+    }
 }
 
 pub struct VectorResponse {
@@ -68,23 +116,33 @@ impl VectorService {
     pub fn new(
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
         uri: Uri,
-        compression: bool,
+        compression: VectorCompression,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
-        let mut proto_client = proto_vector::Client::new(HyperSvc {
+        let proto_client = proto_vector::Client::new(HyperSvc {
             uri,
             client: hyper_client,
         });
 
-        if compression {
-            proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
-        }
         Self {
             client: proto_client,
             protocol,
             endpoint,
+            compression: NegotiatedCompression::new(compression),
         }
     }
+
+    fn push_events(
+        &self,
+        compression: VectorCompression,
+        request: proto_vector::PushEventsRequest,
+    ) -> impl Future<Output = Result<(), Status>> {
+        let mut client = self.client.clone();
+        if let Some(encoding) = compression.encoding() {
+            client = client.send_compressed(encoding);
+        }
+        async move { client.push_events(request.into_request()).await.map(drop) }
+    }
 }
 
 impl Service<VectorRequest> for VectorService {
@@ -104,26 +162,50 @@ impl Service<VectorRequest> for VectorService {
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, mut list: VectorRequest) -> Self::Future {
-        let mut service = self.clone();
+        let service = self.clone();
         let byte_size = list.request.encoded_len();
         let metadata = std::mem::take(list.metadata_mut());
         let events_byte_size = metadata.into_events_estimated_json_encoded_byte_size();
 
+        // The request is kept until the compression is accepted, to send it again with another
+        // compression if the downstream Vector rejects this one.
+        let Negotiation {
+            mut compression,
+            accepted,
+        } = service.compression.get();
+        let fallback_request = (!accepted).then(|| list.request.clone());
+
         let future = async move {
-            service
-                .client
-                .push_events(list.request.into_request())
-                .map_ok(|_response| {
+            let mut result = service.push_events(compression, list.request).await;
+            let fallback = match &result {
+                Err(status) if fallback_request.is_some() => compression.fallback(status),
+                _ => None,
+            };
+            if let (Some(fallback), Some(request)) = (fallback, fallback_request) {
+                warn!(
+                    message = "Compression rejected by downstream Vector, falling back.",
+                    ?compression,
+                    ?fallback,
+                    internal_log_rate_limit = true
+                );
+                service.compression.set(fallback, false);
+                compression = fallback;
+                result = service.push_events(compression, request).await;
+            }
+
+            match result {
+                Ok(()) => {
+                    service.compression.set(compression, true);
                     emit!(EndpointBytesSent {
                         byte_size,
                         protocol: &service.protocol,
                         endpoint: &service.endpoint,
                     });
 
-                    VectorResponse { events_byte_size }
-                })
-                .map_err(|source| VectorSinkError::Request { source }.into())
-                .await
+                    Ok(VectorResponse { events_byte_size })
+                }
+                Err(source) => Err(VectorSinkError::Request { source }.into()),
+            }
         };
 
         Box::pin(future)
@@ -6,7 +6,7 @@ use crate::{
     config::{SourceConfig, SourceContext, SourceOutput},
     event::{BatchNotifier, BatchStatus},
     internal_events::{
-        source::{AmqpAckError, AmqpBytesReceived, AmqpEventError, AmqpRejectError},
+        source::{AmqpAckError, AmqpBytesReceived, AmqpEventError, AmqpNackError, AmqpRejectError},
         StreamClosedError,
     },
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -18,7 +18,7 @@ use bytes::Bytes;
 use chrono::{TimeZone, Utc};
 use futures::{FutureExt, StreamExt};
 use futures_util::Stream;
-use lapin::{acker::Acker, message::Delivery, Channel};
+use lapin::{acker::Acker, message::Delivery, types::AMQPValue, BasicProperties, Channel};
 use snafu::Snafu;
 use std::{io::Cursor, pin::Pin};
 use tokio_util::codec::FramedRead;
@@ -100,6 +100,24 @@ pub struct AmqpSourceConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     pub(crate) acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The maximum number of unacknowledged messages the broker delivers to the consumer.
+    ///
+    /// By default, the number of unacknowledged messages is not limited by the consumer.
+    #[configurable(metadata(docs::examples = 100))]
+    pub(crate) prefetch_count: Option<u16>,
+
+    /// The maximum number of times a message is requeued when its events fail to be delivered.
+    ///
+    /// Once this limit is reached, or when its events are rejected by the sinks, the message is
+    /// rejected without being requeued, so that the broker dead-letters it when the queue has a
+    /// dead letter exchange.
+    ///
+    /// The number of previous deliveries of a message is read from the `x-delivery-count` header
+    /// set by quorum queues. For other queues, a redelivered message counts as delivered once
+    /// before.
+    #[serde(default)]
+    pub(crate) max_requeues: u32,
 }
 
 fn default_queue() -> String {
@@ -192,16 +210,40 @@ impl SourceConfig for AmqpSourceConfig {
 #[derive(Debug)]
 struct FinalizerEntry {
     acker: Acker,
+    delivery_count: u32,
 }
 
 impl From<Delivery> for FinalizerEntry {
     fn from(delivery: Delivery) -> Self {
         Self {
+            delivery_count: delivery_count(&delivery.properties, delivery.redelivered),
             acker: delivery.acker,
         }
     }
 }
 
+/// The number of times the message was delivered before.
+fn delivery_count(properties: &BasicProperties, redelivered: bool) -> u32 {
+    let header = properties.headers().as_ref().and_then(|headers| {
+        headers
+            .inner()
+            .iter()
+            .find(|(key, _)| key.as_str() == "x-delivery-count")
+            .map(|(_, value)| value)
+    });
+    let count = match header {
+        Some(AMQPValue::ShortShortUInt(count)) => Some(u32::from(*count)),
+        Some(AMQPValue::ShortUInt(count)) => Some(u32::from(*count)),
+        Some(AMQPValue::LongUInt(count)) => Some(*count),
+        Some(AMQPValue::ShortShortInt(count)) => u32::try_from(*count).ok(),
+        Some(AMQPValue::ShortInt(count)) => u32::try_from(*count).ok(),
+        Some(AMQPValue::LongInt(count)) => u32::try_from(*count).ok(),
+        Some(AMQPValue::LongLongInt(count)) => u32::try_from(*count).ok(),
+        _ => None,
+    };
+    count.unwrap_or(u32::from(redelivered))
+}
+
 pub(crate) async fn amqp_source(
     config: &AmqpSourceConfig,
     shutdown: ShutdownSignal,
@@ -422,6 +464,15 @@ async fn run_amqp_source(
     let (finalizer, mut ack_stream) =
         UnorderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, Some(shutdown.clone()));
 
+    if let Some(prefetch_count) = config.prefetch_count {
+        channel
+            .basic_qos(prefetch_count, lapin::options::BasicQosOptions::default())
+            .await
+            .map_err(|error| {
+                error!(message = "Failed to set prefetch count.", error = ?error, internal_log_rate_limit = true);
+            })?;
+    }
+
     debug!("Starting amqp source, listening to queue {}.", config.queue);
     let mut consumer = channel
         .basic_consume(
@@ -441,7 +492,7 @@ async fn run_amqp_source(
             _ = &mut shutdown => break,
             entry = ack_stream.next() => {
                 if let Some((status, entry)) = entry {
-                    handle_ack(status, entry).await;
+                    handle_ack(status, entry, config.max_requeues).await;
                 }
             },
             opt_m = consumer.next() => {
@@ -465,7 +516,7 @@ async fn run_amqp_source(
     Ok(())
 }
 
-async fn handle_ack(status: BatchStatus, entry: FinalizerEntry) {
+async fn handle_ack(status: BatchStatus, entry: FinalizerEntry, max_requeues: u32) {
     match status {
         BatchStatus::Delivered => {
             let ack_options = lapin::options::BasicAckOptions::default();
@@ -473,13 +524,16 @@ async fn handle_ack(status: BatchStatus, entry: FinalizerEntry) {
                 emit!(AmqpAckError { error });
             }
         }
-        BatchStatus::Errored => {
-            let ack_options = lapin::options::BasicRejectOptions::default();
-            if let Err(error) = entry.acker.reject(ack_options).await {
-                emit!(AmqpRejectError { error });
+        BatchStatus::Errored if entry.delivery_count < max_requeues => {
+            let nack_options = lapin::options::BasicNackOptions {
+                requeue: true,
+                ..Default::default()
+            };
+            if let Err(error) = entry.acker.nack(nack_options).await {
+                emit!(AmqpNackError { error });
             }
         }
-        BatchStatus::Rejected => {
+        BatchStatus::Errored | BatchStatus::Rejected => {
             let ack_options = lapin::options::BasicRejectOptions::default();
             if let Err(error) = entry.acker.reject(ack_options).await {
                 emit!(AmqpRejectError { error });
@@ -502,6 +556,23 @@ pub mod test {
         crate::test_util::test_generate_config::<AmqpSourceConfig>();
     }
 
+    #[test]
+    fn delivery_count_from_headers() {
+        use lapin::types::{FieldTable, ShortString};
+
+        let properties = BasicProperties::default();
+        assert_eq!(delivery_count(&properties, false), 0);
+        assert_eq!(delivery_count(&properties, true), 1);
+
+        let mut headers = FieldTable::default();
+        headers.insert(
+            ShortString::from("x-delivery-count"),
+            AMQPValue::LongLongInt(3),
+        );
+        let properties = BasicProperties::default().with_headers(headers);
+        assert_eq!(delivery_count(&properties, true), 3);
+    }
+
     pub fn make_config() -> AmqpSourceConfig {
         let mut config = AmqpSourceConfig {
             queue: "it".to_string(),
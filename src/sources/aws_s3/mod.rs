@@ -1,10 +1,7 @@
-use std::{convert::TryInto, io::ErrorKind};
+use std::convert::TryInto;
 
-use async_compression::tokio::bufread;
 use aws_smithy_types::byte_stream::ByteStream;
-use futures::{stream, stream::StreamExt, TryStreamExt};
 use snafu::Snafu;
-use tokio_util::io::StreamReader;
 use vector_lib::codecs::decoding::{
     DeserializerConfig, FramingConfig, NewlineDelimitedDecoderOptions,
 };
@@ -14,7 +11,10 @@ use vector_lib::configurable::configurable_component;
 use vector_lib::lookup::owned_value_path;
 use vrl::value::{kind::Collection, Kind};
 
-use super::util::MultilineConfig;
+use super::util::{
+    object_decoding::{object_decoder, Compression},
+    MultilineConfig,
+};
 use crate::codecs::DecodingConfig;
 use crate::{
     aws::{auth::AwsAuthentication, create_client, create_client_and_region, RegionOrEndpoint},
@@ -29,32 +29,6 @@ use crate::{
 
 pub mod sqs;
 
-/// Compression scheme for objects retrieved from S3.
-#[configurable_component]
-#[configurable(metadata(docs::advanced))]
-#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-#[derivative(Default)]
-pub enum Compression {
-    /// Automatically attempt to determine the compression scheme.
-    ///
-    /// The compression scheme of the object is determined from its `Content-Encoding` and
-    /// `Content-Type` metadata, as well as the key suffix (for example, `.gz`).
-    ///
-    /// It is set to `none` if the compression scheme cannot be determined.
-    #[derivative(Default)]
-    Auto,
-
-    /// Uncompressed.
-    None,
-
-    /// GZIP.
-    Gzip,
-
-    /// ZSTD.
-    Zstd,
-}
-
 /// Strategies for consuming objects from AWS S3.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative)]
@@ -282,95 +256,19 @@ enum CreateSqsIngestorError {
 }
 
 /// None if body is empty
-async fn s3_object_decoder(
+pub(crate) async fn s3_object_decoder(
     compression: Compression,
     key: &str,
     content_encoding: Option<&str>,
     content_type: Option<&str>,
     mut body: ByteStream,
 ) -> Box<dyn tokio::io::AsyncRead + Send + Unpin> {
-    let first = if let Some(first) = body.next().await {
-        first
-    } else {
-        return Box::new(tokio::io::empty());
-    };
-
-    let r = tokio::io::BufReader::new(StreamReader::new(
-        stream::iter(Some(first))
-            .chain(Box::pin(async_stream::stream! {
-                while let Some(next) = body.next().await {
-                    yield next;
-                }
-            }))
-            .map_err(|e| std::io::Error::new(ErrorKind::Other, e)),
-    ));
-
-    let compression = match compression {
-        Auto => determine_compression(content_encoding, content_type, key).unwrap_or(None),
-        _ => compression,
-    };
-
-    use Compression::*;
-    match compression {
-        Auto => unreachable!(), // is mapped above
-        None => Box::new(r),
-        Gzip => Box::new({
-            let mut decoder = bufread::GzipDecoder::new(r);
-            decoder.multiple_members(true);
-            decoder
-        }),
-        Zstd => Box::new({
-            let mut decoder = bufread::ZstdDecoder::new(r);
-            decoder.multiple_members(true);
-            decoder
-        }),
-    }
-}
-
-// try to determine the compression given the:
-// * content-encoding
-// * content-type
-// * key name (for file extension)
-//
-// It will use this information in this order
-fn determine_compression(
-    content_encoding: Option<&str>,
-    content_type: Option<&str>,
-    key: &str,
-) -> Option<Compression> {
-    content_encoding
-        .and_then(content_encoding_to_compression)
-        .or_else(|| content_type.and_then(content_type_to_compression))
-        .or_else(|| object_key_to_compression(key))
-}
-
-fn content_encoding_to_compression(content_encoding: &str) -> Option<Compression> {
-    match content_encoding {
-        "gzip" => Some(Compression::Gzip),
-        "zstd" => Some(Compression::Zstd),
-        _ => None,
-    }
-}
-
-fn content_type_to_compression(content_type: &str) -> Option<Compression> {
-    match content_type {
-        "application/gzip" | "application/x-gzip" => Some(Compression::Gzip),
-        "application/zstd" => Some(Compression::Zstd),
-        _ => None,
-    }
-}
-
-fn object_key_to_compression(key: &str) -> Option<Compression> {
-    let extension = std::path::Path::new(key)
-        .extension()
-        .and_then(std::ffi::OsStr::to_str);
-
-    use Compression::*;
-    extension.and_then(|extension| match extension {
-        "gz" => Some(Gzip),
-        "zst" => Some(Zstd),
-        _ => Option::None,
-    })
+    let body = Box::pin(async_stream::stream! {
+        while let Some(next) = body.next().await {
+            yield next;
+        }
+    });
+    object_decoder(compression, key, content_encoding, content_type, body).await
 }
 
 #[cfg(test)]
@@ -379,34 +277,6 @@ mod test {
 
     use super::*;
 
-    #[test]
-    fn determine_compression() {
-        use super::Compression;
-
-        let cases = vec![
-            ("out.log", Some("gzip"), None, Some(Compression::Gzip)),
-            (
-                "out.log",
-                None,
-                Some("application/gzip"),
-                Some(Compression::Gzip),
-            ),
-            ("out.log.gz", None, None, Some(Compression::Gzip)),
-            ("out.txt", None, None, None),
-        ];
-        for case in cases {
-            let (key, content_encoding, content_type, expected) = case;
-            assert_eq!(
-                super::determine_compression(content_encoding, content_type, key),
-                expected,
-                "key={:?} content_encoding={:?} content_type={:?}",
-                key,
-                content_encoding,
-                content_type,
-            );
-        }
-    }
-
     #[tokio::test]
     async fn decode_empty_message_gzip() {
         let key = uuid::Uuid::new_v4().to_string();
@@ -1,5 +1,4 @@
-use std::collections::HashMap;
-use std::{future::ready, num::NonZeroUsize, panic, sync::Arc, sync::LazyLock};
+use std::{num::NonZeroUsize, panic, sync::Arc, sync::LazyLock};
 
 use aws_sdk_s3::operation::get_object::GetObjectError;
 use aws_sdk_s3::Client as S3Client;
@@ -12,43 +11,37 @@ use aws_sdk_sqs::Client as SqsClient;
 use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_types::region::Region;
-use bytes::Bytes;
-use chrono::{DateTime, TimeZone, Utc};
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use chrono::{TimeZone, Utc};
+use futures::{FutureExt, TryFutureExt};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
-use smallvec::SmallVec;
 use snafu::{ResultExt, Snafu};
 use tokio::{pin, select};
-use tokio_util::codec::FramedRead;
 use tracing::Instrument;
 use vector_lib::codecs::decoding::FramingError;
 use vector_lib::configurable::configurable_component;
-use vector_lib::internal_event::{
-    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
-};
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
 
 use crate::codecs::Decoder;
-use crate::event::{Event, LogEvent};
 use crate::{
     aws::AwsTimeout,
     config::{SourceAcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, EstimatedJsonEncodedSizeOf},
+    event::{BatchNotifier, BatchStatus},
     internal_events::{
         EventsReceived, SqsMessageDeleteBatchError, SqsMessageDeletePartialError,
         SqsMessageDeleteSucceeded, SqsMessageProcessingError, SqsMessageProcessingSucceeded,
         SqsMessageReceiveError, SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
-        StreamClosedError,
     },
-    line_agg::{self, LineAgg},
+    line_agg,
     shutdown::ShutdownSignal,
-    sources::aws_s3::AwsS3Config,
+    sources::{
+        aws_s3::AwsS3Config,
+        util::object_decoding::{send_object_events, ObjectEventsError, ObjectMetadata},
+    },
     tls::TlsConfig,
     SourceSender,
 };
-use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
-use vector_lib::event::MaybeAsLogMut;
-use vector_lib::lookup::{metadata_path, path, PathPrefix};
+use vector_lib::config::LogNamespace;
 
 static SUPPORTED_S3_EVENT_VERSION: LazyLock<semver::VersionReq> =
     LazyLock::new(|| semver::VersionReq::parse("~2").unwrap());
@@ -535,108 +528,37 @@ impl IngestorProcess {
         )
         .await;
 
-        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
-        // message
-        //
-        // String is used as we cannot clone std::io::Error to take ownership in closure
-        //
-        // FramedRead likely stops when it gets an i/o error but I found it more clear to
-        // show that we `take_while` there hasn't been an error
-        //
-        // This can result in objects being partially processed before an error, but we
-        // prefer duplicate lines over message loss. Future work could include recording
-        // the offset of the object that has been read, but this would only be relevant in
-        // the case that the same vector instance processes the same message.
-        let mut read_error = None;
-        let bytes_received = self.bytes_received.clone();
-        let events_received = self.events_received.clone();
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, self.state.decoder.framer.clone())
-                .map(|res| {
-                    res.inspect(|bytes| {
-                        bytes_received.emit(ByteSize(bytes.len()));
-                    })
-                    .map_err(|err| {
-                        read_error = Some(err);
-                    })
-                    .ok()
-                })
-                .take_while(|res| ready(res.is_some()))
-                .map(|r| r.expect("validated by take_while")),
-        );
-
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.state.multiline {
-            Some(config) => Box::new(
-                LineAgg::new(
-                    lines.map(|line| ((), line, ())),
-                    line_agg::Logic::new(config.clone()),
-                )
-                .map(|(_src, line, _context, _lastline_context)| line),
-            ),
-            None => lines,
+        let object_metadata = ObjectMetadata {
+            bucket: &s3_event.s3.bucket.name,
+            key: &s3_event.s3.object.key,
+            attributes: &[("region", s3_event.aws_region.as_str())],
+            metadata: metadata.as_ref(),
+            timestamp,
         };
+        let result = send_object_events(
+            object_reader,
+            &self.state.decoder,
+            self.state.multiline.as_ref(),
+            batch,
+            &self.bytes_received,
+            &self.events_received,
+            &mut self.out,
+            |log| object_metadata.insert(AwsS3Config::NAME, log, log_namespace),
+        )
+        .await;
 
-        let mut stream = lines.flat_map(|line| {
-            let events = match self.state.decoder.deserializer_parse(line) {
-                Ok((events, _events_size)) => events,
-                Err(_error) => {
-                    // Error is handled by `codecs::Decoder`, no further handling
-                    // is needed here.
-                    SmallVec::new()
-                }
-            };
-
-            let events = events
-                .into_iter()
-                .map(|mut event: Event| {
-                    event = event.with_batch_notifier_option(&batch);
-                    if let Some(log_event) = event.maybe_as_log_mut() {
-                        handle_single_log(
-                            log_event,
-                            log_namespace,
-                            &s3_event,
-                            &metadata,
-                            timestamp,
-                        );
-                    }
-                    events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
-                    event
-                })
-                .collect::<Vec<Event>>();
-            futures::stream::iter(events)
-        });
-
-        let send_error = match self.out.send_event_stream(&mut stream).await {
-            Ok(_) => None,
-            Err(_) => {
-                let (count, _) = stream.size_hint();
-                emit!(StreamClosedError { count });
-                Some(crate::source_sender::ClosedError)
-            }
-        };
-
-        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
-        // so we explicitly drop it so that we can again utilize `read_error` below.
-        drop(stream);
-
-        // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
-        // reference must be dropped before the status of the batch is sent to the channel.
-        drop(batch);
-
-        if let Some(error) = read_error {
-            Err(ProcessingError::ReadObject {
+        match result {
+            Err(ObjectEventsError::Read(error)) => Err(ProcessingError::ReadObject {
                 source: error,
                 bucket: s3_event.s3.bucket.name.clone(),
                 key: s3_event.s3.object.key.clone(),
-            })
-        } else if let Some(error) = send_error {
-            Err(ProcessingError::PipelineSend {
+            }),
+            Err(ObjectEventsError::Send(error)) => Err(ProcessingError::PipelineSend {
                 source: error,
                 bucket: s3_event.s3.bucket.name.clone(),
                 key: s3_event.s3.object.key.clone(),
-            })
-        } else {
-            match receiver {
+            }),
+            Ok(()) => match receiver {
                 None => Ok(()),
                 Some(receiver) => {
                     let result = receiver.await;
@@ -652,7 +574,7 @@ impl IngestorProcess {
                         }
                     }
                 }
-            }
+            },
         }
     }
 
@@ -685,77 +607,6 @@ impl IngestorProcess {
     }
 }
 
-fn handle_single_log(
-    log: &mut LogEvent,
-    log_namespace: LogNamespace,
-    s3_event: &S3EventRecord,
-    metadata: &Option<HashMap<String, String>>,
-    timestamp: Option<DateTime<Utc>>,
-) {
-    log_namespace.insert_source_metadata(
-        AwsS3Config::NAME,
-        log,
-        Some(LegacyKey::Overwrite(path!("bucket"))),
-        path!("bucket"),
-        Bytes::from(s3_event.s3.bucket.name.as_bytes().to_vec()),
-    );
-
-    log_namespace.insert_source_metadata(
-        AwsS3Config::NAME,
-        log,
-        Some(LegacyKey::Overwrite(path!("object"))),
-        path!("object"),
-        Bytes::from(s3_event.s3.object.key.as_bytes().to_vec()),
-    );
-    log_namespace.insert_source_metadata(
-        AwsS3Config::NAME,
-        log,
-        Some(LegacyKey::Overwrite(path!("region"))),
-        path!("region"),
-        Bytes::from(s3_event.aws_region.as_bytes().to_vec()),
-    );
-
-    if let Some(metadata) = metadata {
-        for (key, value) in metadata {
-            log_namespace.insert_source_metadata(
-                AwsS3Config::NAME,
-                log,
-                Some(LegacyKey::Overwrite(path!(key))),
-                path!("metadata", key.as_str()),
-                value.clone(),
-            );
-        }
-    }
-
-    log_namespace.insert_vector_metadata(
-        log,
-        log_schema().source_type_key(),
-        path!("source_type"),
-        Bytes::from_static(AwsS3Config::NAME.as_bytes()),
-    );
-
-    // This handles the transition from the original timestamp logic. Originally the
-    // `timestamp_key` was populated by the `last_modified` time on the object, falling
-    // back to calling `now()`.
-    match log_namespace {
-        LogNamespace::Vector => {
-            if let Some(timestamp) = timestamp {
-                log.insert(metadata_path!(AwsS3Config::NAME, "timestamp"), timestamp);
-            }
-
-            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
-        }
-        LogNamespace::Legacy => {
-            if let Some(timestamp_key) = log_schema().timestamp_key() {
-                log.try_insert(
-                    (PathPrefix::Event, timestamp_key),
-                    timestamp.unwrap_or_else(Utc::now),
-                );
-            }
-        }
-    };
-}
-
 // https://docs.aws.amazon.com/sns/latest/dg/sns-sqs-as-subscriber.html
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
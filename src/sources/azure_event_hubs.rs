@@ -0,0 +1,430 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use azure_core::error::ErrorKind;
+use azure_storage_blobs::prelude::ContainerClient;
+use futures::future::try_join_all;
+use http::StatusCode;
+use serde_with::serde_as;
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    azure_credentials::ClientCredentials,
+    azure_event_hubs::{kafka_auth, EventHubsConnection},
+    config::{
+        GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sinks::azure_common::config::build_client,
+    sources::kafka::{CheckpointStore, KafkaSourceConfig, TopicPartition},
+};
+
+/// Where to start reading a partition when the consumer group has no committed offset for it.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartPosition {
+    /// Start from the oldest event still retained by the Event Hub.
+    Earliest,
+
+    /// Start from events enqueued after the consumer joined.
+    #[default]
+    Latest,
+}
+
+impl StartPosition {
+    const fn auto_offset_reset(self) -> &'static str {
+        match self {
+            StartPosition::Earliest => "earliest",
+            StartPosition::Latest => "latest",
+        }
+    }
+}
+
+/// Configuration for the `azure_event_hubs` source.
+#[serde_as]
+#[configurable_component(source("azure_event_hubs", "Collect events from Azure Event Hubs."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsSourceConfig {
+    /// The connection string of the Event Hubs namespace or of the Event Hub itself.
+    ///
+    /// The namespace host is taken from its `Endpoint` property, and the Event Hub name from its
+    /// `EntityPath` property when present.
+    #[configurable(metadata(
+        docs::examples = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=logs"
+    ))]
+    connection_string: SensitiveString,
+
+    /// The name of the Event Hub to read events from.
+    ///
+    /// Required if the connection string does not contain an `EntityPath`.
+    #[configurable(metadata(docs::examples = "logs"))]
+    event_hub_name: Option<String>,
+
+    /// The consumer group to read events as.
+    ///
+    /// Partitions of the Event Hub are balanced across all Vector instances that share a consumer
+    /// group, so that each partition is read by only one of them at a time. The offsets of
+    /// processed events are checkpointed against that group.
+    #[serde(default = "default_consumer_group")]
+    #[derivative(Default(value = "default_consumer_group()"))]
+    #[configurable(metadata(docs::examples = "$Default", docs::examples = "vector"))]
+    consumer_group: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    start_position: StartPosition,
+
+    #[configurable(derived)]
+    checkpoint_store: Option<BlobCheckpointStoreConfig>,
+
+    /// The frequency that the offsets of processed events are checkpointed.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_checkpoint_interval_ms")]
+    #[derivative(Default(value = "default_checkpoint_interval_ms()"))]
+    #[configurable(metadata(docs::examples = 5000, docs::examples = 10000))]
+    #[configurable(metadata(docs::human_name = "Checkpoint Interval"))]
+    checkpoint_interval_ms: Duration,
+
+    /// Advanced options set directly on the underlying `librdkafka` client.
+    ///
+    /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(
+        docs::additional_props_description = "A librdkafka configuration option."
+    ))]
+    librdkafka_options: Option<HashMap<String, String>>,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// Azure Blob Storage settings for storing checkpoints.
+///
+/// Each partition is checkpointed to a blob named
+/// `<namespace host>/<event hub>/<consumer group>/<partition>`, which holds the offset of the last
+/// processed event of the partition. When a partition is assigned to a Vector instance, it resumes
+/// after its checkpoint, and from the offset committed to the consumer group when it has none.
+///
+/// Offsets are still committed to the consumer group as well.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BlobCheckpointStoreConfig {
+    /// The Azure Blob Storage Account connection string.
+    ///
+    /// Authentication with access key is the only supported authentication method.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    #[configurable(metadata(
+        docs::examples = "DefaultEndpointsProtocol=https;AccountName=mycheckpoints;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"
+    ))]
+    connection_string: Option<SensitiveString>,
+
+    /// The Azure Blob Storage Account name.
+    ///
+    /// Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+    /// for the account in the following ways, in order:
+    ///
+    /// - read from environment variables ([more information][env_cred_docs])
+    /// - looks for a [Managed Identity][managed_ident_docs]
+    /// - uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    ///
+    /// [env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    /// [az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+    #[configurable(metadata(docs::examples = "mycheckpoints"))]
+    storage_account: Option<String>,
+
+    #[configurable(derived)]
+    client_credentials: Option<ClientCredentials>,
+
+    /// The Azure Blob Storage Endpoint URL.
+    ///
+    /// This is used to override the default blob storage endpoint URL when authenticating with
+    /// `storage_account`.
+    ///
+    /// This may only be used with `storage_account` and is ignored when used with
+    /// `connection_string`.
+    #[configurable(metadata(docs::examples = "https://test.blob.core.usgovcloudapi.net/"))]
+    #[configurable(metadata(docs::examples = "https://test.blob.core.windows.net/"))]
+    endpoint: Option<String>,
+
+    /// The name of the container that checkpoints are stored in.
+    ///
+    /// The container must already exist.
+    #[configurable(metadata(docs::examples = "event-hubs-checkpoints"))]
+    container_name: String,
+}
+
+impl BlobCheckpointStoreConfig {
+    fn build(
+        &self,
+        connection: &EventHubsConnection,
+        consumer_group: &str,
+    ) -> crate::Result<BlobCheckpointStore> {
+        let client = build_client(
+            self.connection_string
+                .as_ref()
+                .map(|connection_string| connection_string.inner().to_string()),
+            self.storage_account.clone(),
+            self.client_credentials.as_ref(),
+            self.container_name.clone(),
+            self.endpoint.clone(),
+        )?;
+        Ok(BlobCheckpointStore {
+            client,
+            prefix: format!(
+                "{}/{}/{}",
+                connection.namespace_host(),
+                connection.event_hub_name(),
+                consumer_group
+            )
+            .to_lowercase(),
+        })
+    }
+}
+
+/// Checkpoints stored in Azure Blob Storage, in a blob per partition.
+#[derive(Debug)]
+struct BlobCheckpointStore {
+    client: Arc<ContainerClient>,
+
+    /// The blob name prefix of the checkpoints of the Event Hub and consumer group.
+    prefix: String,
+}
+
+impl BlobCheckpointStore {
+    fn blob_name(&self, partition: i32) -> String {
+        format!("{}/{}", self.prefix, partition)
+    }
+
+    async fn load_partition(&self, partition: i32) -> crate::Result<Option<i64>> {
+        let blob_name = self.blob_name(partition);
+        match self.client.blob_client(&blob_name).get_content().await {
+            Ok(content) => {
+                let offset = String::from_utf8(content)
+                    .ok()
+                    .and_then(|content| content.trim().parse().ok())
+                    .ok_or_else(|| format!("Invalid checkpoint {:?}.", blob_name))?;
+                Ok(Some(offset))
+            }
+            Err(error) => match error.kind() {
+                ErrorKind::HttpResponse { status, .. }
+                    if u16::from(*status) == StatusCode::NOT_FOUND.as_u16() =>
+                {
+                    Ok(None)
+                }
+                _ => Err(error.into()),
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckpointStore for BlobCheckpointStore {
+    async fn load(
+        &self,
+        partitions: &[TopicPartition],
+    ) -> crate::Result<HashMap<TopicPartition, i64>> {
+        let offsets = try_join_all(
+            partitions
+                .iter()
+                .map(|(_, partition)| self.load_partition(*partition)),
+        )
+        .await?;
+        Ok(partitions
+            .iter()
+            .cloned()
+            .zip(offsets)
+            .filter_map(|(tp, offset)| offset.map(|offset| (tp, offset)))
+            .collect())
+    }
+
+    async fn store(&self, offsets: &HashMap<TopicPartition, i64>) -> crate::Result<()> {
+        try_join_all(offsets.iter().map(|((_, partition), offset)| {
+            self.client
+                .blob_client(self.blob_name(*partition))
+                .put_block_blob(offset.to_string())
+                .content_type("text/plain")
+                .into_future()
+        }))
+        .await?;
+        Ok(())
+    }
+}
+
+fn default_consumer_group() -> String {
+    "$Default".into()
+}
+
+const fn default_checkpoint_interval_ms() -> Duration {
+    Duration::from_millis(5000)
+}
+
+impl GenerateConfig for AzureEventHubsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"connection_string = "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=logs""#,
+        )
+        .unwrap()
+    }
+}
+
+impl AzureEventHubsSourceConfig {
+    /// Event Hubs speaks the Kafka protocol, so the source is driven by the `kafka` source
+    /// pointed at the namespace's Kafka endpoint. Consumer group membership takes care of
+    /// balancing partition ownership across instances, and offsets are committed to the Event
+    /// Hubs service itself, as well as checkpointed to the checkpoint store if one is set.
+    fn kafka_config(&self) -> crate::Result<KafkaSourceConfig> {
+        let connection = EventHubsConnection::parse(
+            self.connection_string.inner(),
+            self.event_hub_name.as_deref(),
+        )?;
+        let checkpoint_store = match &self.checkpoint_store {
+            Some(config) => Some(Arc::new(config.build(&connection, &self.consumer_group)?) as _),
+            None => None,
+        };
+
+        Ok(KafkaSourceConfig {
+            bootstrap_servers: connection.bootstrap_servers(),
+            topics: vec![connection.event_hub_name().to_string()],
+            group_id: self.consumer_group.clone(),
+            auto_offset_reset: self.start_position.auto_offset_reset().to_string(),
+            commit_interval_ms: self.checkpoint_interval_ms,
+            librdkafka_options: self.librdkafka_options.clone(),
+            auth: kafka_auth(&self.connection_string),
+            framing: self.framing.clone(),
+            decoding: self.decoding.clone(),
+            acknowledgements: self.acknowledgements,
+            log_namespace: self.log_namespace,
+            source_type: Self::NAME,
+            checkpoint_store,
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SourceConfig for AzureEventHubsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        self.kafka_config()?.build(cx).await
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // The outputs don't depend on the connection details, so the schema can be produced even
+        // when the connection string is invalid. That error is reported when building.
+        let kafka_config = KafkaSourceConfig {
+            framing: self.framing.clone(),
+            decoding: self.decoding.clone(),
+            log_namespace: self.log_namespace,
+            source_type: Self::NAME,
+            ..Default::default()
+        };
+        kafka_config.outputs(global_log_namespace)
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSourceConfig>();
+    }
+
+    #[test]
+    fn kafka_config_targets_event_hubs_endpoint() {
+        let config = AzureEventHubsSourceConfig {
+            connection_string: "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret;EntityPath=logs".to_string().into(),
+            start_position: StartPosition::Earliest,
+            ..Default::default()
+        };
+        let kafka_config = config.kafka_config().unwrap();
+
+        assert_eq!(
+            kafka_config.bootstrap_servers,
+            "mynamespace.servicebus.windows.net:9093"
+        );
+        assert_eq!(kafka_config.topics, vec!["logs".to_string()]);
+        assert_eq!(kafka_config.group_id, "$Default");
+        assert_eq!(kafka_config.auto_offset_reset, "earliest");
+        assert_eq!(kafka_config.source_type, "azure_event_hubs");
+        assert!(kafka_config.auth.sasl.is_some());
+    }
+
+    #[test]
+    fn checkpoint_store_is_keyed_by_event_hub_and_consumer_group() {
+        let config: AzureEventHubsSourceConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://MyNamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret;EntityPath=Logs"
+            consumer_group = "Vector"
+
+            [checkpoint_store]
+            connection_string = "DefaultEndpointsProtocol=https;AccountName=mycheckpoints;AccountKey=c2VjcmV0;EndpointSuffix=core.windows.net"
+            container_name = "checkpoints"
+            "#,
+        )
+        .unwrap();
+        assert!(config.kafka_config().unwrap().checkpoint_store.is_some());
+
+        let connection =
+            EventHubsConnection::parse(config.connection_string.inner(), None).unwrap();
+        let store = config
+            .checkpoint_store
+            .unwrap()
+            .build(&connection, &config.consumer_group)
+            .unwrap();
+        assert_eq!(
+            store.blob_name(3),
+            "mynamespace.servicebus.windows.net/logs/vector/3"
+        );
+    }
+
+    #[test]
+    fn kafka_config_requires_event_hub_name() {
+        let config = AzureEventHubsSourceConfig {
+            connection_string: "Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=secret".to_string().into(),
+            ..Default::default()
+        };
+        assert!(config.kafka_config().is_err());
+
+        let config = AzureEventHubsSourceConfig {
+            event_hub_name: Some("logs".into()),
+            ..config
+        };
+        assert_eq!(
+            config.kafka_config().unwrap().topics,
+            vec!["logs".to_string()]
+        );
+    }
+}
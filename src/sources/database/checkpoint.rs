@@ -0,0 +1,231 @@
+use std::{
+    fs,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::TrackingColumnType;
+use crate::event::Value;
+
+const TMP_FILE_NAME: &str = "checkpoint.new.json";
+const CHECKPOINT_FILE_NAME: &str = "checkpoint.json";
+
+/// The greatest value of the tracking column among the rows that have been read.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Checkpoint {
+    Integer(i64),
+    Timestamp(DateTime<Utc>),
+}
+
+impl Checkpoint {
+    /// The checkpoint before any row has been read.
+    const fn initial(tracking_column_type: TrackingColumnType) -> Self {
+        match tracking_column_type {
+            TrackingColumnType::Integer => Self::Integer(0),
+            TrackingColumnType::Timestamp => Self::Timestamp(DateTime::UNIX_EPOCH),
+        }
+    }
+
+    /// The checkpoint of a value of the tracking column, if it has the type of the column.
+    ///
+    /// Integers that don't fit the column type of the database, such as `NUMERIC` or unsigned
+    /// `BIGINT`, are read as strings, and parsed back.
+    pub(super) fn from_value(
+        tracking_column_type: TrackingColumnType,
+        value: &Value,
+    ) -> Option<Self> {
+        match (tracking_column_type, value) {
+            (TrackingColumnType::Integer, Value::Integer(value)) => Some(Self::Integer(*value)),
+            (TrackingColumnType::Integer, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()?
+                .parse()
+                .ok()
+                .map(Self::Integer),
+            (TrackingColumnType::Timestamp, Value::Timestamp(value)) => {
+                Some(Self::Timestamp(*value))
+            }
+            _ => None,
+        }
+    }
+
+    const fn has_type(&self, tracking_column_type: TrackingColumnType) -> bool {
+        matches!(
+            (self, tracking_column_type),
+            (Self::Integer(_), TrackingColumnType::Integer)
+                | (Self::Timestamp(_), TrackingColumnType::Timestamp)
+        )
+    }
+}
+
+/// This enum represents the file format of checkpoints persisted to disk. Right
+/// now there is only one variant, but any incompatible changes will require an
+/// additional variant to be added here and handled anywhere that we transit
+/// this format.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "version", rename_all = "snake_case")]
+enum State {
+    #[serde(rename = "1")]
+    V1 {
+        tracking_column: String,
+        checkpoint: Checkpoint,
+    },
+}
+
+/// The checkpoint of the tracking column, persisted so that rows are not read again after a
+/// restart.
+pub(super) struct Checkpointer {
+    tmp_file_path: PathBuf,
+    stable_file_path: PathBuf,
+    tracking_column: String,
+    checkpoint: Checkpoint,
+}
+
+impl Checkpointer {
+    /// Loads the checkpoint persisted in `data_dir`, if there is one.
+    ///
+    /// The checkpoint of another tracking column, or of another type, is discarded, as the rows
+    /// that it tracks have changed.
+    pub(super) fn new(
+        data_dir: &Path,
+        tracking_column: &str,
+        tracking_column_type: TrackingColumnType,
+    ) -> Result<Self, io::Error> {
+        let tmp_file_path = data_dir.join(TMP_FILE_NAME);
+        let stable_file_path = data_dir.join(CHECKPOINT_FILE_NAME);
+
+        let checkpoint = match fs::read(&stable_file_path) {
+            Ok(contents) => {
+                let State::V1 {
+                    tracking_column: checkpointed_column,
+                    checkpoint,
+                } = serde_json::from_slice(&contents)?;
+                if checkpointed_column == tracking_column
+                    && checkpoint.has_type(tracking_column_type)
+                {
+                    Some(checkpoint)
+                } else {
+                    info!(
+                        message = "Discarding the checkpoint of another tracking column.",
+                        column = %checkpointed_column,
+                    );
+                    None
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            tmp_file_path,
+            stable_file_path,
+            tracking_column: tracking_column.to_owned(),
+            checkpoint: checkpoint.unwrap_or(Checkpoint::initial(tracking_column_type)),
+        })
+    }
+
+    pub(super) const fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    pub(super) fn set(&mut self, checkpoint: Checkpoint) {
+        self.checkpoint = checkpoint;
+    }
+
+    /// Persist the checkpoint to disk.
+    ///
+    /// The checkpoint is written to a tmp file that is then renamed over the previous file, so
+    /// that there is always one complete file to recover from.
+    pub(super) fn write(&self) -> Result<(), io::Error> {
+        let state = State::V1 {
+            tracking_column: self.tracking_column.clone(),
+            checkpoint: self.checkpoint.clone(),
+        };
+        let mut file = BufWriter::new(fs::File::create(&self.tmp_file_path)?);
+        serde_json::to_writer(&mut file, &state)?;
+        file.into_inner()?.sync_all()?;
+        fs::rename(&self.tmp_file_path, &self.stable_file_path)
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.stable_file_path
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let data_dir = tempdir().unwrap();
+
+        let mut checkpointer =
+            Checkpointer::new(data_dir.path(), "id", TrackingColumnType::Integer).unwrap();
+        assert_eq!(checkpointer.checkpoint(), &Checkpoint::Integer(0));
+
+        checkpointer.set(Checkpoint::Integer(42));
+        checkpointer.write().unwrap();
+
+        let checkpointer =
+            Checkpointer::new(data_dir.path(), "id", TrackingColumnType::Integer).unwrap();
+        assert_eq!(checkpointer.checkpoint(), &Checkpoint::Integer(42));
+        assert!(!data_dir.path().join(TMP_FILE_NAME).exists());
+        assert_eq!(
+            fs::read_to_string(checkpointer.path()).unwrap(),
+            r#"{"version":"1","tracking_column":"id","checkpoint":{"integer":42}}"#
+        );
+    }
+
+    #[test]
+    fn discards_checkpoint_of_other_column() {
+        let data_dir = tempdir().unwrap();
+
+        let mut checkpointer =
+            Checkpointer::new(data_dir.path(), "id", TrackingColumnType::Integer).unwrap();
+        checkpointer.set(Checkpoint::Integer(42));
+        checkpointer.write().unwrap();
+
+        let checkpointer =
+            Checkpointer::new(data_dir.path(), "event_id", TrackingColumnType::Integer).unwrap();
+        assert_eq!(checkpointer.checkpoint(), &Checkpoint::Integer(0));
+
+        let checkpointer =
+            Checkpointer::new(data_dir.path(), "id", TrackingColumnType::Timestamp).unwrap();
+        assert_eq!(
+            checkpointer.checkpoint(),
+            &Checkpoint::Timestamp(DateTime::UNIX_EPOCH)
+        );
+    }
+
+    #[test]
+    fn checkpoint_from_value() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            Checkpoint::from_value(TrackingColumnType::Integer, &Value::from(7)),
+            Some(Checkpoint::Integer(7))
+        );
+        assert_eq!(
+            Checkpoint::from_value(TrackingColumnType::Integer, &Value::from("18446744")),
+            Some(Checkpoint::Integer(18446744))
+        );
+        assert_eq!(
+            Checkpoint::from_value(TrackingColumnType::Timestamp, &Value::from(timestamp)),
+            Some(Checkpoint::Timestamp(timestamp))
+        );
+        assert_eq!(
+            Checkpoint::from_value(TrackingColumnType::Timestamp, &Value::from(7)),
+            None
+        );
+        assert_eq!(
+            Checkpoint::from_value(TrackingColumnType::Integer, &Value::Null),
+            None
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use chrono::{Datelike, NaiveDate, Timelike};
+use mysql_async::{
+    consts::ColumnType, prelude::Queryable, Opts, OptsBuilder, Params, Pool, Row, SslOpts,
+    Value as MySqlValue,
+};
+use snafu::ResultExt;
+use vrl::value::ObjectMap;
+
+use super::{
+    checkpoint::Checkpoint, InvalidMySqlEndpointSnafu, MySqlClientCertificateSnafu, MySqlSnafu,
+    PollError,
+};
+use crate::{event::Value, tls::TlsConfig};
+
+pub(super) struct MySqlClient {
+    pool: Pool,
+    query: String,
+    placeholders: usize,
+}
+
+impl MySqlClient {
+    pub(super) fn new(
+        endpoint: &str,
+        query: String,
+        placeholders: usize,
+        tls: &Option<TlsConfig>,
+    ) -> crate::Result<Self> {
+        let opts = Opts::from_url(endpoint).context(InvalidMySqlEndpointSnafu)?;
+        let opts = OptsBuilder::from_opts(opts).ssl_opts(ssl_opts(tls)?);
+
+        Ok(Self {
+            pool: Pool::new(opts),
+            query,
+            placeholders,
+        })
+    }
+
+    pub(super) async fn query(
+        &mut self,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Result<Vec<ObjectMap>, PollError> {
+        // Each placeholder is a positional parameter of its own.
+        let params = match checkpoint {
+            Some(checkpoint) => {
+                Params::Positional(vec![checkpoint_parameter(checkpoint); self.placeholders])
+            }
+            None => Params::Empty,
+        };
+
+        let mut conn = self.pool.get_conn().await.context(MySqlSnafu)?;
+        let rows: Vec<Row> = conn
+            .exec(self.query.as_str(), params)
+            .await
+            .context(MySqlSnafu)?;
+        Ok(rows.into_iter().map(row_to_object).collect())
+    }
+}
+
+fn ssl_opts(tls: &Option<TlsConfig>) -> crate::Result<Option<SslOpts>> {
+    let Some(tls) = tls else {
+        return Ok(None);
+    };
+    if tls.crt_file.is_some() || tls.key_file.is_some() {
+        return Err(MySqlClientCertificateSnafu.build().into());
+    }
+
+    let mut ssl_opts = SslOpts::default()
+        .with_danger_accept_invalid_certs(tls.verify_certificate == Some(false))
+        .with_danger_skip_domain_validation(tls.verify_hostname == Some(false));
+    if let Some(ca_file) = &tls.ca_file {
+        ssl_opts = ssl_opts.with_root_certs(vec![ca_file.clone().into()]);
+    }
+    Ok(Some(ssl_opts))
+}
+
+fn checkpoint_parameter(checkpoint: &Checkpoint) -> MySqlValue {
+    match checkpoint {
+        Checkpoint::Integer(value) => MySqlValue::Int(*value),
+        Checkpoint::Timestamp(value) => MySqlValue::Date(
+            value.year() as u16,
+            value.month() as u8,
+            value.day() as u8,
+            value.hour() as u8,
+            value.minute() as u8,
+            value.second() as u8,
+            value.timestamp_subsec_micros(),
+        ),
+    }
+}
+
+fn row_to_object(row: Row) -> ObjectMap {
+    let columns = row.columns();
+    columns
+        .iter()
+        .zip(row.unwrap())
+        .map(|(column, value)| {
+            let value = match (column.column_type(), value) {
+                (ColumnType::MYSQL_TYPE_JSON, MySqlValue::Bytes(bytes)) => {
+                    serde_json::from_slice::<serde_json::Value>(&bytes)
+                        .map_or_else(|_| Value::Bytes(bytes.into()), Value::from)
+                }
+                (_, value) => convert_value(value),
+            };
+            (column.name_str().as_ref().into(), value)
+        })
+        .collect()
+}
+
+fn convert_value(value: MySqlValue) -> Value {
+    match value {
+        MySqlValue::NULL => Value::Null,
+        MySqlValue::Bytes(bytes) => Value::Bytes(bytes.into()),
+        MySqlValue::Int(value) => Value::Integer(value),
+        MySqlValue::UInt(value) => i64::try_from(value)
+            .map_or_else(|_| Value::from_f64_or_zero(value as f64), Value::Integer),
+        MySqlValue::Float(value) => Value::from_f64_or_zero(value.into()),
+        MySqlValue::Double(value) => Value::from_f64_or_zero(value),
+        MySqlValue::Date(year, month, day, hour, minute, second, micros) => {
+            // Zero dates, such as `0000-00-00`, are not valid timestamps.
+            NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+                .and_then(|date| {
+                    date.and_hms_micro_opt(hour.into(), minute.into(), second.into(), micros)
+                })
+                .map_or(Value::Null, |timestamp| {
+                    Value::Timestamp(timestamp.and_utc())
+                })
+        }
+        MySqlValue::Time(negative, days, hours, minutes, seconds, micros) => Value::from(format!(
+            "{}{:02}:{:02}:{:02}.{:06}",
+            if negative { "-" } else { "" },
+            days * 24 + u32::from(hours),
+            minutes,
+            seconds,
+            micros
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(convert_value(MySqlValue::NULL), Value::Null);
+        assert_eq!(convert_value(MySqlValue::Int(-7)), Value::from(-7));
+        assert_eq!(convert_value(MySqlValue::UInt(7)), Value::from(7));
+        assert_eq!(
+            convert_value(MySqlValue::Bytes(b"login".to_vec())),
+            Value::from("login")
+        );
+        assert_eq!(
+            convert_value(MySqlValue::Date(2024, 5, 1, 12, 30, 0, 0)),
+            Value::from(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap())
+        );
+        assert_eq!(
+            convert_value(MySqlValue::Date(0, 0, 0, 0, 0, 0, 0)),
+            Value::Null
+        );
+        assert_eq!(
+            convert_value(MySqlValue::Time(true, 1, 2, 3, 4, 5)),
+            Value::from("-26:03:04.000005")
+        );
+    }
+
+    #[test]
+    fn binds_timestamp_checkpoint() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 15).unwrap();
+        assert_eq!(
+            checkpoint_parameter(&Checkpoint::Timestamp(timestamp)),
+            MySqlValue::Date(2024, 5, 1, 12, 30, 15, 0)
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use postgres_openssl::MakeTlsConnector;
+use snafu::ResultExt;
+use tokio_postgres::{
+    types::{FromSql, ToSql, Type},
+    Client, Config, NoTls, Row,
+};
+use vrl::value::ObjectMap;
+
+use super::{checkpoint::Checkpoint, InvalidPostgresEndpointSnafu, PollError, PostgresSnafu};
+use crate::{
+    event::Value,
+    tls::{tls_connector_builder, MaybeTlsSettings, TlsConfig},
+};
+
+type Parameter = Box<dyn ToSql + Sync + Send>;
+
+pub(super) struct PostgresClient {
+    config: Config,
+    query: String,
+    tls: Option<MakeTlsConnector>,
+    client: Option<Client>,
+}
+
+impl PostgresClient {
+    pub(super) fn new(
+        endpoint: &str,
+        query: String,
+        tls: &Option<TlsConfig>,
+    ) -> crate::Result<Self> {
+        let config = endpoint
+            .parse::<Config>()
+            .context(InvalidPostgresEndpointSnafu)?;
+        let tls = match tls {
+            Some(_) => {
+                let settings = MaybeTlsSettings::tls_client(tls)?;
+                Some(MakeTlsConnector::new(
+                    tls_connector_builder(&settings)?.build(),
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            config,
+            query,
+            tls,
+            client: None,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, tokio_postgres::Error> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+
+    pub(super) async fn query(
+        &mut self,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Result<Vec<ObjectMap>, PollError> {
+        // The connection is made again once it is closed, such as after an error.
+        let client = match self.client.take() {
+            Some(client) if !client.is_closed() => client,
+            _ => self.connect().await.context(PostgresSnafu)?,
+        };
+        let client = self.client.insert(client);
+
+        let statement = client.prepare(&self.query).await.context(PostgresSnafu)?;
+        let parameters = statement
+            .params()
+            .iter()
+            .map(|ty| checkpoint_parameter(checkpoint, ty))
+            .collect::<Result<Vec<_>, _>>()?;
+        let parameters = parameters
+            .iter()
+            .map(|parameter| parameter.as_ref() as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+
+        let rows = client
+            .query(&statement, &parameters)
+            .await
+            .context(PostgresSnafu)?;
+        Ok(rows.iter().map(row_to_object).collect())
+    }
+}
+
+/// Binds the checkpoint to a parameter of the query, converted to the type of the parameter.
+///
+/// The type of the parameter is inferred by PostgreSQL from the query, and can be set with a
+/// cast, such as `{{ checkpoint }}::bigint`.
+fn checkpoint_parameter(
+    checkpoint: Option<&Checkpoint>,
+    ty: &Type,
+) -> Result<Parameter, PollError> {
+    let unsupported = || PollError::CheckpointParameter { ty: ty.to_string() };
+    let parameter: Parameter = match checkpoint {
+        Some(Checkpoint::Integer(value)) if *ty == Type::INT8 => Box::new(*value),
+        Some(Checkpoint::Integer(value)) if *ty == Type::INT4 => {
+            Box::new(i32::try_from(*value).map_err(|_| unsupported())?)
+        }
+        Some(Checkpoint::Integer(value)) if *ty == Type::INT2 => {
+            Box::new(i16::try_from(*value).map_err(|_| unsupported())?)
+        }
+        Some(Checkpoint::Timestamp(value)) if *ty == Type::TIMESTAMPTZ => Box::new(*value),
+        Some(Checkpoint::Timestamp(value)) if *ty == Type::TIMESTAMP => Box::new(value.naive_utc()),
+        _ => return Err(unsupported()),
+    };
+    Ok(parameter)
+}
+
+fn row_to_object(row: &Row) -> ObjectMap {
+    row.columns()
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            (
+                column.name().into(),
+                column_value(row, index, column.type_()),
+            )
+        })
+        .collect()
+}
+
+fn column_value(row: &Row, index: usize, ty: &Type) -> Value {
+    match ty.name() {
+        "bool" => get(row, index, Value::Boolean),
+        "int2" => get(row, index, |value: i16| Value::Integer(value.into())),
+        "int4" => get(row, index, |value: i32| Value::Integer(value.into())),
+        "int8" => get(row, index, Value::Integer),
+        "oid" => get(row, index, |value: u32| Value::Integer(value.into())),
+        "float4" => get(row, index, |value: f32| {
+            Value::from_f64_or_zero(value.into())
+        }),
+        "float8" => get(row, index, Value::from_f64_or_zero),
+        "timestamptz" => get::<DateTime<Utc>>(row, index, Value::Timestamp),
+        "timestamp" => get(row, index, |value: NaiveDateTime| {
+            Value::Timestamp(value.and_utc())
+        }),
+        "date" => get(row, index, |value: NaiveDate| {
+            Value::from(value.to_string())
+        }),
+        "json" | "jsonb" => get::<serde_json::Value>(row, index, Value::from),
+        "bytea" => get(row, index, |value: Vec<u8>| Value::Bytes(value.into())),
+        // The text types, and the other types that can be read as text.
+        _ => get::<String>(row, index, Value::from),
+    }
+}
+
+fn get<'a, T: FromSql<'a>>(row: &'a Row, index: usize, convert: impl FnOnce(T) -> Value) -> Value {
+    match row.try_get::<_, Option<T>>(index) {
+        Ok(value) => value.map_or(Value::Null, convert),
+        Err(error) => {
+            let column = &row.columns()[index];
+            warn!(
+                message = "Unsupported column type, cast the column to text in the query.",
+                column = %column.name(),
+                r#type = %column.type_(),
+                %error,
+                internal_log_rate_limit = true,
+            );
+            Value::Null
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn binds_checkpoint_to_parameter_type() {
+        let checkpoint = Checkpoint::Integer(42);
+        assert!(checkpoint_parameter(Some(&checkpoint), &Type::INT8).is_ok());
+        assert!(checkpoint_parameter(Some(&checkpoint), &Type::INT4).is_ok());
+        assert!(checkpoint_parameter(Some(&checkpoint), &Type::TIMESTAMPTZ).is_err());
+        let overflow = Checkpoint::Integer(1 << 40);
+        assert!(checkpoint_parameter(Some(&overflow), &Type::INT4).is_err());
+        assert!(checkpoint_parameter(None, &Type::INT8).is_err());
+
+        let timestamp = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let checkpoint = Checkpoint::Timestamp(timestamp);
+        assert!(checkpoint_parameter(Some(&checkpoint), &Type::TIMESTAMP).is_ok());
+        assert!(checkpoint_parameter(Some(&checkpoint), &Type::TIMESTAMPTZ).is_ok());
+        assert!(checkpoint_parameter(Some(&checkpoint), &Type::TEXT).is_err());
+    }
+}
@@ -262,11 +262,7 @@ impl<T: FrameHandler + Clone> TcpFrameHandler for DnstapFrameHandler<T> {
     }
 
     fn insert_tls_client_metadata(&mut self, metadata: Option<CertificateMetadata>) {
-        self.tls_client_metadata = metadata.map(|c| {
-            let mut metadata = ObjectMap::new();
-            metadata.insert("subject".into(), c.subject().into());
-            metadata
-        });
+        self.tls_client_metadata = metadata.map(|c| c.to_object_map());
     }
 
     fn allowed_origins(&self) -> Option<&[IpNet]> {
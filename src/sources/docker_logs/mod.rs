@@ -11,6 +11,7 @@ use bollard::{
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, FixedOffset, Local, ParseError, Utc};
 use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
 use serde_with::serde_as;
 use tokio::sync::mpsc;
 use tracing_futures::Instrument;
@@ -134,6 +135,22 @@ pub struct DockerLogsConfig {
     #[configurable(metadata(docs::examples = "httpd", docs::examples = "redis",))]
     include_images: Option<Vec<String>>,
 
+    /// A table of output names to container labels, used to route the logs of containers to named
+    /// outputs.
+    ///
+    /// The logs of a container are sent to the first output whose label the container has, and to
+    /// the default output if the container has none of them. Labels follow the same syntax as
+    /// `include_labels`, either `key=value` to match the value of the label, or `key` to match any
+    /// value.
+    ///
+    /// Each output can then be referenced as an input by other components with the name
+    /// `<source_id>.<output>`.
+    #[configurable(metadata(
+        docs::additional_props_description = "The container label of the output."
+    ))]
+    #[configurable(metadata(docs::examples = "example_routes()"))]
+    routes: IndexMap<String, String>,
+
     /// Overrides the name of the log field used to mark an event as partial.
     ///
     /// If `auto_partial_merge` is disabled, partial events are emitted with a log field, set by this
@@ -175,6 +192,7 @@ impl Default for DockerLogsConfig {
             include_containers: None,
             include_labels: None,
             include_images: None,
+            routes: IndexMap::new(),
             partial_event_marker_field: default_partial_event_marker_field(),
             auto_partial_merge: true,
             multiline: None,
@@ -184,6 +202,13 @@ impl Default for DockerLogsConfig {
     }
 }
 
+fn example_routes() -> IndexMap<String, String> {
+    IndexMap::from([
+        ("nginx".to_owned(), "vector.pipeline=nginx".to_owned()),
+        ("redis".to_owned(), "vector.pipeline=redis".to_owned()),
+    ])
+}
+
 fn default_partial_event_marker_field() -> Option<String> {
     Some(event::PARTIAL.to_string())
 }
@@ -218,6 +243,18 @@ impl DockerLogsConfig {
                 .any(|name| items.iter().any(|item| name.starts_with(item)))
     }
 
+    /// The named output that the logs of a container with these labels are routed to, or `None`
+    /// for the default output.
+    fn route_output(&self, labels: &HashMap<String, String>) -> Option<&str> {
+        self.routes
+            .iter()
+            .find(|(_, label)| match label.split_once('=') {
+                Some((key, value)) => labels.get(key).is_some_and(|v| v == value),
+                None => labels.contains_key(label.as_str()),
+            })
+            .map(|(output, _)| output.as_str())
+    }
+
     fn with_empty_partial_event_marker_field_as_none(mut self) -> Self {
         if let Some(val) = &self.partial_event_marker_field {
             if val.is_empty() {
@@ -347,10 +384,15 @@ impl SourceConfig for DockerLogsConfig {
                 None,
             );
 
-        vec![SourceOutput::new_maybe_logs(
+        let mut outputs = vec![SourceOutput::new_maybe_logs(
             DataType::Log,
-            schema_definition,
-        )]
+            schema_definition.clone(),
+        )];
+        outputs.extend(self.routes.keys().map(|output| {
+            SourceOutput::new_maybe_logs(DataType::Log, schema_definition.clone())
+                .with_port(output.clone())
+        }));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -812,10 +854,15 @@ impl EventStreamBuilder {
 
         let host_key = self.host_key.clone().path;
         let hostname = self.hostname.clone();
+        let output = core.config.route_output(&info.metadata.labels);
         let result = {
             let mut stream = events_stream
                 .map(move |event| add_hostname(event, &host_key, &hostname, self.log_namespace));
-            self.out.send_event_stream(&mut stream).await.map_err(|_| {
+            let result = match output {
+                Some(output) => self.out.send_event_stream_named(output, &mut stream).await,
+                None => self.out.send_event_stream(&mut stream).await,
+            };
+            result.map_err(|_| {
                 let (count, _) = stream.size_hint();
                 emit!(StreamClosedError { count });
             })
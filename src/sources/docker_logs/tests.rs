@@ -23,6 +23,53 @@ fn exclude_self() {
     assert!(!source.exclude_self("a29d569bd46c"));
 }
 
+#[test]
+fn route_output() {
+    let config = DockerLogsConfig {
+        routes: IndexMap::from([
+            ("nginx".to_owned(), "vector.pipeline=nginx".to_owned()),
+            ("debug".to_owned(), "vector.debug".to_owned()),
+        ]),
+        ..Default::default()
+    };
+    let labels = |labels: &[(&str, &str)]| {
+        labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>()
+    };
+
+    assert_eq!(
+        config.route_output(&labels(&[("vector.pipeline", "nginx")])),
+        Some("nginx")
+    );
+    assert_eq!(
+        config.route_output(&labels(&[
+            ("vector.pipeline", "redis"),
+            ("vector.debug", "")
+        ])),
+        Some("debug")
+    );
+    assert_eq!(
+        config.route_output(&labels(&[
+            ("vector.pipeline", "nginx"),
+            ("vector.debug", "1")
+        ])),
+        Some("nginx")
+    );
+    assert_eq!(
+        config.route_output(&labels(&[("vector.pipeline", "redis")])),
+        None
+    );
+
+    let outputs = config.outputs(LogNamespace::Legacy);
+    let ports = outputs
+        .iter()
+        .map(|o| o.port.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(ports, vec![None, Some("nginx"), Some("debug")]);
+}
+
 #[cfg(all(test, feature = "docker-logs-integration-tests"))]
 mod integration_tests {
     use bollard::{
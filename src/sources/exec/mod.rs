@@ -65,6 +65,11 @@ pub struct ExecConfig {
 
     /// Custom environment variables to set or update when running the command.
     /// If a variable name already exists in the environment, its value is replaced.
+    ///
+    /// Values can contain placeholders, rendered each time the command is run:
+    ///
+    /// - `{{ hostname }}`: the hostname of the host running Vector.
+    /// - `{{ restart_count }}`: the number of times a streaming command has been restarted.
     #[serde(default)]
     #[configurable(metadata(docs::additional_props_description = "An environment variable."))]
     #[configurable(metadata(docs::examples = "environment_examples()"))]
@@ -81,6 +86,19 @@ pub struct ExecConfig {
     #[serde(default = "default_include_stderr")]
     pub include_stderr: bool,
 
+    /// Whether to send the events generated from stderr to a separate output.
+    ///
+    /// If `true`, these events can be referenced as an input by other components with the name
+    /// `<source_id>.stderr`, and are not sent to the default output.
+    #[serde(default)]
+    pub stderr_output: bool,
+
+    /// The amount of time, in seconds, that the command has to exit once it is sent a `SIGTERM`
+    /// on shutdown, before it is killed.
+    #[serde(default = "default_termination_grace_period_secs")]
+    #[configurable(metadata(docs::human_name = "Termination Grace Period"))]
+    pub termination_grace_period_secs: u64,
+
     /// The maximum buffer size allowed before a log event is generated.
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
@@ -128,6 +146,8 @@ pub struct ScheduledConfig {
 #[serde(deny_unknown_fields)]
 pub struct StreamingConfig {
     /// Whether or not the command should be rerun if the command exits.
+    ///
+    /// Ignored if `restart_policy` is set.
     #[serde(default = "default_respawn_on_exit")]
     respawn_on_exit: bool,
 
@@ -135,6 +155,46 @@ pub struct StreamingConfig {
     #[serde(default = "default_respawn_interval_secs")]
     #[configurable(metadata(docs::human_name = "Respawn Interval"))]
     respawn_interval_secs: u64,
+
+    #[configurable(derived)]
+    restart_policy: Option<RestartPolicy>,
+
+    /// The maximum amount of time, in seconds, before rerunning a streaming command that keeps
+    /// exiting.
+    ///
+    /// If set, the time before rerunning the command doubles each time it exits, starting from
+    /// `respawn_interval_secs`, up to this value. It is reset once the command has run for longer
+    /// than this value.
+    #[configurable(metadata(docs::human_name = "Maximum Respawn Interval"))]
+    max_respawn_interval_secs: Option<u64>,
+}
+
+/// The policy for rerunning a streaming command that exited.
+///
+/// Defaults to `always` if `respawn_on_exit` is `true`, and to `never` otherwise.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// The command is always rerun.
+    Always,
+
+    /// The command is rerun only if it failed, exiting with a non-zero status or killed by a
+    /// signal.
+    OnFailure,
+
+    /// The command is never rerun.
+    Never,
+}
+
+impl RestartPolicy {
+    const fn should_restart(self, succeeded: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnFailure => !succeeded,
+            Self::Never => false,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Snafu)]
@@ -143,6 +203,15 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display(
+        "Unknown placeholder `{}` in the value of environment variable {}",
+        placeholder,
+        variable
+    ))]
+    UnknownPlaceholder {
+        variable: String,
+        placeholder: String,
+    },
 }
 
 impl Default for ExecConfig {
@@ -158,6 +227,8 @@ impl Default for ExecConfig {
             clear_environment: default_clear_environment(),
             working_directory: None,
             include_stderr: default_include_stderr(),
+            stderr_output: false,
+            termination_grace_period_secs: default_termination_grace_period_secs(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
             framing: None,
             decoding: default_decoding(),
@@ -183,6 +254,10 @@ const fn default_respawn_on_exit() -> bool {
     true
 }
 
+const fn default_termination_grace_period_secs() -> u64 {
+    10
+}
+
 const fn default_clear_environment() -> bool {
     false
 }
@@ -208,6 +283,8 @@ const STDERR: &str = "stderr";
 const STREAM_KEY: &str = "stream";
 const PID_KEY: &str = "pid";
 const COMMAND_KEY: &str = "command";
+const HOSTNAME_PLACEHOLDER: &str = "hostname";
+const RESTART_COUNT_PLACEHOLDER: &str = "restart_count";
 
 impl_generate_config_from_default!(ExecConfig);
 
@@ -218,10 +295,25 @@ impl ExecConfig {
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
         } else {
-            Ok(())
+            self.validate_environment()
         }
     }
 
+    fn validate_environment(&self) -> Result<(), ExecConfigError> {
+        for (variable, value) in self.environment.iter().flatten() {
+            render_template(value, |name| {
+                [HOSTNAME_PLACEHOLDER, RESTART_COUNT_PLACEHOLDER]
+                    .contains(&name)
+                    .then(String::new)
+            })
+            .map_err(|placeholder| ExecConfigError::UnknownPlaceholder {
+                variable: variable.clone(),
+                placeholder,
+            })?;
+        }
+        Ok(())
+    }
+
     fn command_line(&self) -> String {
         self.command.join(" ")
     }
@@ -233,10 +325,15 @@ impl ExecConfig {
         }
     }
 
-    const fn respawn_on_exit_or_default(&self) -> bool {
-        match &self.streaming {
-            None => default_respawn_on_exit(),
-            Some(config) => config.respawn_on_exit,
+    const fn restart_policy_or_default(&self) -> RestartPolicy {
+        let (restart_policy, respawn_on_exit) = match &self.streaming {
+            None => (None, default_respawn_on_exit()),
+            Some(config) => (config.restart_policy, config.respawn_on_exit),
+        };
+        match (restart_policy, respawn_on_exit) {
+            (Some(restart_policy), _) => restart_policy,
+            (None, true) => RestartPolicy::Always,
+            (None, false) => RestartPolicy::Never,
         }
     }
 
@@ -246,6 +343,13 @@ impl ExecConfig {
             Some(config) => config.respawn_interval_secs,
         }
     }
+
+    const fn max_respawn_interval_secs(&self) -> Option<u64> {
+        match &self.streaming {
+            None => None,
+            Some(config) => config.max_respawn_interval_secs,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -278,14 +382,16 @@ impl SourceConfig for ExecConfig {
                 )))
             }
             Mode::Streaming => {
-                let respawn_on_exit = self.respawn_on_exit_or_default();
+                let restart_policy = self.restart_policy_or_default();
                 let respawn_interval_secs = self.respawn_interval_secs_or_default();
+                let max_respawn_interval_secs = self.max_respawn_interval_secs();
 
                 Ok(Box::pin(run_streaming(
                     self.clone(),
                     hostname,
-                    respawn_on_exit,
+                    restart_policy,
                     respawn_interval_secs,
+                    max_respawn_interval_secs,
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -335,10 +441,17 @@ impl SourceConfig for ExecConfig {
                 None,
             );
 
-        vec![SourceOutput::new_maybe_logs(
+        let mut outputs = vec![SourceOutput::new_maybe_logs(
             self.decoding.output_type(),
-            schema_definition,
-        )]
+            schema_definition.clone(),
+        )];
+        if self.include_stderr && self.stderr_output {
+            outputs.push(
+                SourceOutput::new_maybe_logs(self.decoding.output_type(), schema_definition)
+                    .with_port(STDERR),
+            );
+        }
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -367,6 +480,7 @@ async fn run_scheduled(
             run_command(
                 config.clone(),
                 hostname.clone(),
+                0,
                 decoder.clone(),
                 shutdown.clone(),
                 out.clone(),
@@ -402,58 +516,67 @@ async fn run_scheduled(
 async fn run_streaming(
     config: ExecConfig,
     hostname: Option<String>,
-    respawn_on_exit: bool,
+    restart_policy: RestartPolicy,
     respawn_interval_secs: u64,
+    max_respawn_interval_secs: Option<u64>,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<(), ()> {
-    if respawn_on_exit {
-        let duration = Duration::from_secs(respawn_interval_secs);
-
-        // Continue to loop while not shutdown
-        loop {
-            let output = run_command(
-                config.clone(),
-                hostname.clone(),
-                decoder.clone(),
-                shutdown.clone(),
-                out.clone(),
-                log_namespace,
-            )
-            .await;
+    let respawn_interval = Duration::from_secs(respawn_interval_secs);
+    let max_respawn_interval = max_respawn_interval_secs.map(Duration::from_secs);
+    let mut delay = respawn_interval;
+    let mut restart_count = 0;
+
+    // Continue to loop while not shutdown
+    loop {
+        let start = Instant::now();
+        let output = run_command(
+            config.clone(),
+            hostname.clone(),
+            restart_count,
+            decoder.clone(),
+            shutdown.clone(),
+            out.clone(),
+            log_namespace,
+        )
+        .await;
 
-            // handle command finished
-            if let Err(command_error) = output {
+        // handle command finished
+        let succeeded = match output {
+            Ok(exit_status) => exit_status.is_some_and(|exit_status| exit_status.success()),
+            Err(command_error) => {
                 emit!(ExecFailedError {
                     command: config.command_line().as_str(),
                     error: command_error,
                 });
+                false
             }
+        };
+        if !restart_policy.should_restart(succeeded) {
+            debug!(
+                message = "Not restarting streaming process.",
+                ?restart_policy,
+                succeeded
+            );
+            break;
+        }
 
-            tokio::select! {
-                _ = &mut shutdown => break, // will break early if a shutdown is started
-                _ = sleep(duration) => debug!("Restarting streaming process."),
+        if let Some(max_respawn_interval) = max_respawn_interval {
+            // A command that ran for long enough is considered healthy again.
+            if start.elapsed() >= max_respawn_interval {
+                delay = respawn_interval;
             }
         }
-    } else {
-        let output = run_command(
-            config.clone(),
-            hostname,
-            decoder,
-            shutdown,
-            out,
-            log_namespace,
-        )
-        .await;
-
-        if let Err(command_error) = output {
-            emit!(ExecFailedError {
-                command: config.command_line().as_str(),
-                error: command_error,
-            });
+        tokio::select! {
+            _ = &mut shutdown => break, // will break early if a shutdown is started
+            _ = sleep(delay) => debug!(message = "Restarting streaming process.", restart_count),
         }
+        if let Some(max_respawn_interval) = max_respawn_interval {
+            delay = (delay * 2).min(max_respawn_interval);
+        }
+        restart_count += 1;
     }
 
     Ok(())
@@ -462,13 +585,14 @@ async fn run_streaming(
 async fn run_command(
     config: ExecConfig,
     hostname: Option<String>,
+    restart_count: u64,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<Option<ExitStatus>, Error> {
     debug!("Starting command run.");
-    let mut command = build_command(&config);
+    let mut command = build_command(&config, &hostname, restart_count);
 
     // Mark the start time just before spawning the process as
     // this seems to be the best approximation of exec duration
@@ -505,13 +629,21 @@ async fn run_command(
 
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
 
+    let grace_period = Duration::from_secs(config.termination_grace_period_secs);
+    let termination_deadline = sleep(grace_period);
+    tokio::pin!(termination_deadline);
+    let mut terminating = false;
+
     'outer: loop {
         tokio::select! {
-            _ = &mut shutdown => {
+            _ = &mut shutdown, if !terminating => {
                 if !shutdown_child(&mut child, &command).await {
                         break 'outer; // couldn't signal, exit early
                 }
+                termination_deadline.as_mut().reset(Instant::now() + grace_period);
+                terminating = true;
             }
+            _ = &mut termination_deadline, if terminating => break 'outer,
             v = receiver.recv() => {
                 match v {
                     None => break 'outer,
@@ -528,7 +660,12 @@ async fn run_command(
                         for event in &mut events {
                             handle_event(&config, &hostname, &Some(stream.to_string()), pid, event, log_namespace);
                         }
-                        if (out.send_batch(events).await).is_err() {
+                        let sent = if stream == STDERR && config.stderr_output {
+                            out.send_batch_named(STDERR, events).await
+                        } else {
+                            out.send_batch(events).await
+                        };
+                        if sent.is_err() {
                             emit!(StreamClosedError { count });
                             break;
                         }
@@ -538,9 +675,20 @@ async fn run_command(
         }
     }
 
+    let exit_status = if terminating {
+        // The command is waited for until the end of its grace period, and killed if it is still
+        // running by then.
+        tokio::select! {
+            exit_status = child.wait() => exit_status.map(Some),
+            _ = &mut termination_deadline => kill_child(&config, &mut child).await,
+        }
+    } else {
+        child.try_wait()
+    };
+
     let elapsed = start.elapsed();
 
-    let result = match child.try_wait() {
+    let result = match exit_status {
         Ok(Some(exit_status)) => {
             handle_exit_status(&config, exit_status.code(), elapsed);
             Ok(Some(exit_status))
@@ -570,6 +718,19 @@ fn handle_exit_status(config: &ExecConfig, exit_status: Option<i32>, exec_durati
     });
 }
 
+async fn kill_child(
+    config: &ExecConfig,
+    child: &mut tokio::process::Child,
+) -> Result<Option<ExitStatus>, Error> {
+    warn!(
+        message = "Command did not exit within its termination grace period, killing it.",
+        command = %config.command_line(),
+        grace_period_secs = config.termination_grace_period_secs,
+    );
+    child.kill().await?;
+    child.try_wait()
+}
+
 #[cfg(unix)]
 async fn shutdown_child(
     child: &mut tokio::process::Child,
@@ -626,7 +787,7 @@ async fn shutdown_child(
     }
 }
 
-fn build_command(config: &ExecConfig) -> Command {
+fn build_command(config: &ExecConfig, hostname: &Option<String>, restart_count: u64) -> Command {
     let command = &config.command[0];
 
     let mut command = Command::new(command);
@@ -644,7 +805,16 @@ fn build_command(config: &ExecConfig) -> Command {
 
     // Configure environment variables if needed
     if let Some(envs) = &config.environment {
-        command.envs(envs);
+        let lookup = |name: &str| match name {
+            HOSTNAME_PLACEHOLDER => Some(hostname.clone().unwrap_or_default()),
+            RESTART_COUNT_PLACEHOLDER => Some(restart_count.to_string()),
+            _ => None,
+        };
+        // The placeholders are validated when the source is built.
+        command.envs(envs.iter().map(|(name, value)| {
+            let value = render_template(value, lookup).unwrap_or_else(|_| value.clone());
+            (name, value)
+        }));
     }
 
     // Explicitly set the current dir if needed
@@ -668,6 +838,27 @@ fn build_command(config: &ExecConfig) -> Command {
     command
 }
 
+/// Renders the `{{ name }}` placeholders of a template, or returns the name of the first
+/// placeholder that has no value.
+fn render_template(
+    template: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + length].trim();
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&lookup(name).ok_or_else(|| name.to_owned())?);
+        rest = &rest[start + length + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
 fn handle_event(
     config: &ExecConfig,
     hostname: &Option<String>,
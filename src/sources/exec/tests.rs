@@ -3,7 +3,7 @@ use crate::{event::LogEvent, test_util::trace_init};
 use bytes::Bytes;
 use std::ffi::OsStr;
 use std::io::Cursor;
-use vector_lib::event::EventMetadata;
+use vector_lib::event::{into_event_stream, EventMetadata, EventStatus};
 use vrl::value;
 
 #[cfg(unix)]
@@ -174,19 +174,23 @@ fn test_build_command() {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            restart_policy: None,
+            max_respawn_interval_secs: None,
         }),
         command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
         environment: None,
         clear_environment: default_clear_environment(),
         working_directory: Some(PathBuf::from("/tmp")),
         include_stderr: default_include_stderr(),
+        stderr_output: false,
+        termination_grace_period_secs: default_termination_grace_period_secs(),
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
     };
 
-    let command = build_command(&config);
+    let command = build_command(&config, &None, 0);
 
     let mut expected_command = Command::new("./runner");
     expected_command.kill_on_drop(true);
@@ -208,19 +212,23 @@ fn test_build_command_custom_environment() {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            restart_policy: None,
+            max_respawn_interval_secs: None,
         }),
         command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
         environment: Some(HashMap::from([("FOO".to_owned(), "foo".to_owned())])),
         clear_environment: default_clear_environment(),
         working_directory: Some(PathBuf::from("/tmp")),
         include_stderr: default_include_stderr(),
+        stderr_output: false,
+        termination_grace_period_secs: default_termination_grace_period_secs(),
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
     };
 
-    let command = build_command(&config);
+    let command = build_command(&config, &None, 0);
     let cmd = command.as_std();
 
     let idx = cmd
@@ -238,19 +246,23 @@ fn test_build_command_clear_environment() {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            restart_policy: None,
+            max_respawn_interval_secs: None,
         }),
         command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
         environment: Some(HashMap::from([("FOO".to_owned(), "foo".to_owned())])),
         clear_environment: true,
         working_directory: Some(PathBuf::from("/tmp")),
         include_stderr: default_include_stderr(),
+        stderr_output: false,
+        termination_grace_period_secs: default_termination_grace_period_secs(),
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
     };
 
-    let command = build_command(&config);
+    let command = build_command(&config, &None, 0);
     let cmd = command.as_std();
 
     let envs: Vec<_> = cmd.get_envs().collect();
@@ -311,6 +323,7 @@ async fn test_drop_receiver() {
         run_command(
             config.clone(),
             hostname,
+            0,
             decoder,
             shutdown,
             tx,
@@ -346,6 +359,7 @@ async fn test_run_command_linux() {
                 run_command(
                     config.clone(),
                     hostname,
+                    0,
                     decoder,
                     shutdown,
                     tx,
@@ -399,6 +413,7 @@ async fn test_graceful_shutdown() {
     let task = tokio::spawn(run_command(
         config.clone(),
         hostname,
+        0,
         decoder,
         shutdown,
         tx,
@@ -435,6 +450,173 @@ async fn test_graceful_shutdown() {
     }
 }
 
+#[test]
+fn test_build_command_environment_placeholders() {
+    let mut config = standard_streaming_test_config();
+    config.environment = Some(HashMap::from([
+        ("HOST".to_owned(), "{{ hostname }}".to_owned()),
+        (
+            "RESTARTS".to_owned(),
+            "restarts-{{restart_count}}".to_owned(),
+        ),
+    ]));
+
+    let command = build_command(&config, &Some("Some.Machine".to_string()), 3);
+    let envs: HashMap<_, _> = command.as_std().get_envs().collect();
+
+    assert_eq!(envs[OsStr::new("HOST")], Some(OsStr::new("Some.Machine")));
+    assert_eq!(envs[OsStr::new("RESTARTS")], Some(OsStr::new("restarts-3")));
+}
+
+#[test]
+fn test_validate_unknown_environment_placeholder() {
+    let mut config = standard_streaming_test_config();
+    config.environment = Some(HashMap::from([(
+        "FOO".to_owned(),
+        "{{ hostname }}-{{ foo }}".to_owned(),
+    )]));
+
+    assert_eq!(
+        config.validate(),
+        Err(ExecConfigError::UnknownPlaceholder {
+            variable: "FOO".to_owned(),
+            placeholder: "foo".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn test_restart_policy() {
+    assert!(RestartPolicy::Always.should_restart(true));
+    assert!(RestartPolicy::Always.should_restart(false));
+    assert!(!RestartPolicy::OnFailure.should_restart(true));
+    assert!(RestartPolicy::OnFailure.should_restart(false));
+    assert!(!RestartPolicy::Never.should_restart(false));
+
+    let mut config = standard_streaming_test_config();
+    assert_eq!(config.restart_policy_or_default(), RestartPolicy::Always);
+    config.streaming.as_mut().unwrap().respawn_on_exit = false;
+    assert_eq!(config.restart_policy_or_default(), RestartPolicy::Never);
+    config.streaming.as_mut().unwrap().restart_policy = Some(RestartPolicy::OnFailure);
+    assert_eq!(config.restart_policy_or_default(), RestartPolicy::OnFailure);
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn test_streaming_restart_on_failure() {
+    trace_init();
+    let mut config = standard_streaming_test_config();
+    config.command = vec![
+        String::from("sh"),
+        String::from("-c"),
+        String::from(r#"echo "$RESTARTS"; test "$RESTARTS" -ge 2"#),
+    ];
+    config.environment = Some(HashMap::from([(
+        "RESTARTS".to_owned(),
+        "{{ restart_count }}".to_owned(),
+    )]));
+    let (tx, rx) = SourceSender::new_test();
+
+    tokio::time::timeout(
+        time::Duration::from_secs(5),
+        run_streaming(
+            config,
+            None,
+            RestartPolicy::OnFailure,
+            0,
+            Some(1),
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ),
+    )
+    .await
+    .expect("command was restarted after succeeding")
+    .unwrap();
+
+    let events: Vec<_> = rx.collect().await;
+    let messages: Vec<_> = events
+        .iter()
+        .map(|event| event.as_log().get_message().unwrap().clone())
+        .collect();
+    assert_eq!(messages, vec![value!("0"), value!("1"), value!("2")]);
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn test_stderr_output() {
+    let mut config = standard_scheduled_test_config();
+    config.command = vec![
+        String::from("sh"),
+        String::from("-c"),
+        String::from("echo out; echo err >&2"),
+    ];
+    config.stderr_output = true;
+    let (mut tx, rx) = SourceSender::new_test();
+    let stderr_rx = tx
+        .add_outputs(EventStatus::Delivered, STDERR.to_owned())
+        .flat_map(into_event_stream);
+
+    run_command(
+        config,
+        None,
+        0,
+        Default::default(),
+        ShutdownSignal::noop(),
+        tx,
+        LogNamespace::Legacy,
+    )
+    .await
+    .expect("command error");
+
+    let stdout: Vec<_> = rx.collect().await;
+    let stderr: Vec<_> = stderr_rx.collect().await;
+    assert_eq!(stdout.len(), 1);
+    assert_eq!(*stdout[0].as_log().get_message().unwrap(), "out".into());
+    assert_eq!(stderr.len(), 1);
+    assert_eq!(*stderr[0].as_log().get_message().unwrap(), "err".into());
+    assert_eq!(stderr[0].as_log()[STREAM_KEY], STDERR.into());
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn test_kill_after_termination_grace_period() {
+    trace_init();
+    let mut config = standard_streaming_test_config();
+    config.command = vec![
+        String::from("bash"),
+        String::from("-c"),
+        String::from(r#"trap '' SIGTERM; while true ; do sleep 1 ; done"#),
+    ];
+    config.termination_grace_period_secs = 1;
+    let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+    let (tx, _rx) = SourceSender::new_test();
+
+    let task = tokio::spawn(run_command(
+        config,
+        None,
+        0,
+        Default::default(),
+        shutdown,
+        tx,
+        LogNamespace::Legacy,
+    ));
+
+    tokio::time::sleep(Duration::from_secs(1)).await; // let the source start the command
+
+    drop(trigger); // start shutdown
+
+    let exit_status = tokio::time::timeout(time::Duration::from_secs(10), task)
+        .await
+        .expect("command was not killed")
+        .expect("join failed")
+        .expect("command error");
+
+    // The command was killed by a signal, so it has no exit code.
+    assert_eq!(None, exit_status.expect("missing exit status").code());
+}
+
 fn standard_scheduled_test_config() -> ExecConfig {
     Default::default()
 }
@@ -446,12 +628,16 @@ fn standard_streaming_test_config() -> ExecConfig {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            restart_policy: None,
+            max_respawn_interval_secs: None,
         }),
         command: vec!["yes".to_owned()],
         environment: None,
         clear_environment: default_clear_environment(),
         working_directory: None,
         include_stderr: default_include_stderr(),
+        stderr_output: false,
+        termination_grace_period_secs: default_termination_grace_period_secs(),
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
         framing: None,
         decoding: default_decoding(),
@@ -13,8 +13,8 @@ use vector_lib::configurable::configurable_component;
 use vector_lib::file_source::{
     calculate_ignore_before,
     paths_provider::glob::{Glob, MatchOptions},
-    Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
-    ReadFromConfig,
+    Checkpointer, DiscoveryMode, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter,
+    Line, ReadFrom, ReadFromConfig,
 };
 use vector_lib::finalizer::OrderedFinalizer;
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
@@ -161,10 +161,27 @@ pub struct FileConfig {
     #[configurable(metadata(docs::human_name = "Glob Minimum Cooldown"))]
     pub glob_minimum_cooldown_ms: Duration,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub discovery_mode: DiscoveryMode,
+
     #[configurable(derived)]
     #[serde(alias = "fingerprinting", default)]
     fingerprint: FingerprintConfig,
 
+    /// Whether to read gzipped files that are matched by `include` as rotated archives.
+    ///
+    /// When enabled, gzipped files, such as `*.log.1.gz`, are read from the beginning once, even
+    /// if `read_from` is `end`, and their position is checkpointed. Archives that have been read
+    /// to their end are marked as completed in the checkpoints, so that they aren't decompressed
+    /// again after a restart. They are fingerprinted by their decompressed content, so a file that
+    /// is compressed after it has been rotated is resumed where it was left off rather than read
+    /// again.
+    ///
+    /// This allows catching up on the rotated history after Vector has been stopped.
+    #[serde(default)]
+    pub read_archives: bool,
+
     /// Ignore missing files when fingerprinting.
     ///
     /// This may be useful when used with source directories containing dangling symlinks.
@@ -384,10 +401,12 @@ impl Default for FileConfig {
             max_line_bytes: default_max_line_bytes(),
             fingerprint: FingerprintConfig::default(),
             ignore_not_found: false,
+            read_archives: false,
             host_key: None,
             offset_key: None,
             data_dir: None,
             glob_minimum_cooldown_ms: default_glob_minimum_cooldown_ms(),
+            discovery_mode: DiscoveryMode::default(),
             message_start_indicator: None,
             multi_line_timeout: default_multi_line_timeout(), // millis
             multiline: None,
@@ -556,10 +575,13 @@ pub fn file_source(
         line_delimiter: line_delimiter_as_bytes,
         data_dir,
         glob_minimum_cooldown,
+        discovery_mode: config.discovery_mode,
+        read_archives: config.read_archives,
         fingerprinter: Fingerprinter {
             strategy: config.fingerprint.clone().into(),
             max_line_length: config.max_line_bytes,
             ignore_not_found: config.ignore_not_found,
+            decompress_gzip: config.read_archives,
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after_secs.map(Duration::from_secs),
@@ -1361,6 +1383,38 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_rotate_native_discovery() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*.log")],
+            // Files are only discovered through notifications within the test.
+            glob_minimum_cooldown_ms: Duration::from_secs(60),
+            discovery_mode: DiscoveryMode::Native,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file.log");
+        let received = run_file_source(&config, false, NoAcks, LogNamespace::Legacy, async {
+            sleep_500_millis().await;
+
+            let mut file = File::create(&path).unwrap();
+            writeln!(&mut file, "prerot").unwrap();
+
+            sleep_500_millis().await;
+
+            fs::rename(&path, dir.path().join("file.1.log")).unwrap();
+            let mut file = File::create(&path).unwrap();
+            writeln!(&mut file, "postrot").unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let lines = extract_messages_string(received);
+        assert_eq!(lines, vec!["prerot", "postrot"]);
+    }
+
     #[tokio::test]
     async fn file_multiple_paths() {
         let n = 5;
@@ -1636,6 +1690,45 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_read_archives_resumes_compressed_file() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("file*")],
+            read_archives: true,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        fs::write(&path, "first line\nsecond line\n").unwrap();
+
+        let received = run_file_source(&config, true, NoAcks, LogNamespace::Legacy, async {
+            sleep_500_millis().await;
+        })
+        .await;
+        assert_eq!(
+            extract_messages_string(received),
+            vec!["first line", "second line"]
+        );
+
+        // While Vector is stopped, the file gets another line, is rotated and compressed.
+        let mut encoder = flate2::write::GzEncoder::new(
+            File::create(dir.path().join("file.1.gz")).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder
+            .write_all(b"first line\nsecond line\nthird line\n")
+            .unwrap();
+        encoder.finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let received = run_file_source(&config, true, NoAcks, LogNamespace::Legacy, async {
+            sleep_500_millis().await;
+        })
+        .await;
+        assert_eq!(extract_messages_string(received), vec!["third line"]);
+    }
+
     #[tokio::test]
     async fn file_start_position_server_restart_unfinalized() {
         let dir = tempdir().unwrap();
@@ -160,45 +160,49 @@ impl FluentConfig {
 
         // There is a global and per-source `log_namespace` config.
         // The source config overrides the global setting and is merged here.
-        let mut schema_definition = BytesDeserializerConfig
-            .schema_definition(log_namespace)
-            .with_standard_vector_source_metadata()
-            .with_source_metadata(
-                FluentConfig::NAME,
-                host_key,
-                &owned_value_path!("host"),
-                Kind::bytes(),
-                Some("host"),
-            )
-            .with_source_metadata(
-                FluentConfig::NAME,
-                tag_key,
-                &owned_value_path!("tag"),
-                Kind::bytes(),
-                None,
-            )
-            .with_source_metadata(
-                FluentConfig::NAME,
-                None,
-                &owned_value_path!("timestamp"),
-                Kind::timestamp(),
-                Some("timestamp"),
-            )
-            // for metadata that is added to the events dynamically from the FluentRecord
-            .with_source_metadata(
-                FluentConfig::NAME,
-                None,
-                &owned_value_path!("record"),
-                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
-                None,
-            )
-            .with_source_metadata(
-                Self::NAME,
-                tls_client_metadata_path,
-                &owned_value_path!("tls_client_metadata"),
-                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
-                None,
-            );
+        let mut schema_definition =
+            BytesDeserializerConfig
+                .schema_definition(log_namespace)
+                .with_standard_vector_source_metadata()
+                .with_source_metadata(
+                    FluentConfig::NAME,
+                    host_key,
+                    &owned_value_path!("host"),
+                    Kind::bytes(),
+                    Some("host"),
+                )
+                .with_source_metadata(
+                    FluentConfig::NAME,
+                    tag_key,
+                    &owned_value_path!("tag"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_source_metadata(
+                    FluentConfig::NAME,
+                    None,
+                    &owned_value_path!("timestamp"),
+                    Kind::timestamp(),
+                    Some("timestamp"),
+                )
+                // for metadata that is added to the events dynamically from the FluentRecord
+                .with_source_metadata(
+                    FluentConfig::NAME,
+                    None,
+                    &owned_value_path!("record"),
+                    Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                    None,
+                )
+                .with_source_metadata(
+                    Self::NAME,
+                    tls_client_metadata_path,
+                    &owned_value_path!("tls_client_metadata"),
+                    Kind::object(Collection::empty().with_unknown(
+                        Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                    ))
+                    .or_undefined(),
+                    None,
+                );
 
         // for metadata that is added to the events dynamically
         if log_namespace == LogNamespace::Legacy {
@@ -1010,7 +1014,10 @@ mod tests {
                 )
                 .with_metadata_field(
                     &owned_value_path!("fluent", "tls_client_metadata"),
-                    Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                    Kind::object(Collection::empty().with_unknown(
+                        Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                    ))
+                    .or_undefined(),
                     None,
                 );
 
@@ -0,0 +1,240 @@
+use snafu::Snafu;
+use vector_lib::codecs::decoding::{
+    DeserializerConfig, FramingConfig, NewlineDelimitedDecoderOptions,
+};
+use vector_lib::codecs::NewlineDelimitedDecoderConfig;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::owned_value_path;
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    codecs::DecodingConfig,
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput},
+    gcp::{GcpAuthConfig, Scope},
+    http::HttpClient,
+    serde::{bool_or_struct, default_decoding},
+    sources::util::object_decoding::Compression,
+    tls::{TlsConfig, TlsSettings},
+};
+
+pub mod pubsub;
+
+/// Strategies for consuming objects from GCS.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+enum Strategy {
+    /// Consumes objects by processing bucket notifications sent to a [GCP Pub/Sub][gcp_pubsub]
+    /// subscription.
+    ///
+    /// [gcp_pubsub]: https://cloud.google.com/storage/docs/pubsub-notifications
+    #[derivative(Default)]
+    Pubsub,
+}
+
+/// Configuration for the `gcp_cloud_storage` source.
+#[configurable_component(source("gcp_cloud_storage", "Collect logs from GCP Cloud Storage."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct GcsSourceConfig {
+    /// The endpoint from which to fetch objects.
+    #[configurable(metadata(docs::examples = "https://storage.googleapis.com"))]
+    #[serde(default = "default_endpoint")]
+    #[derivative(Default(value = "default_endpoint()"))]
+    endpoint: String,
+
+    /// The compression scheme used for decompressing objects retrieved from GCS.
+    compression: Compression,
+
+    /// The strategy to use to consume objects from GCS.
+    #[configurable(metadata(docs::hidden))]
+    strategy: Strategy,
+
+    /// Configuration options for Pub/Sub.
+    pubsub: Option<pubsub::Config>,
+
+    #[serde(flatten)]
+    auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing")]
+    #[derivative(Default(value = "default_framing()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+}
+
+fn default_endpoint() -> String {
+    "https://storage.googleapis.com".to_string()
+}
+
+const fn default_framing() -> FramingConfig {
+    FramingConfig::NewlineDelimited(NewlineDelimitedDecoderConfig {
+        newline_delimited: NewlineDelimitedDecoderOptions { max_length: None },
+    })
+}
+
+impl_generate_config_from_default!(GcsSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_cloud_storage")]
+impl SourceConfig for GcsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        match self.strategy {
+            Strategy::Pubsub => Ok(Box::pin(
+                self.create_pubsub_ingestor(&cx, log_namespace).await?.run(
+                    cx,
+                    self.acknowledgements,
+                    log_namespace,
+                ),
+            )),
+        }
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("bucket"))),
+                &owned_value_path!("bucket"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("object"))),
+                &owned_value_path!("object"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("generation"))),
+                &owned_value_path!("generation"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+            // for metadata that is added to the events dynamically from the object metadata
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+
+        // for metadata that is added to the events dynamically from the object metadata
+        if log_namespace == LogNamespace::Legacy {
+            schema_definition = schema_definition.unknown_fields(Kind::bytes());
+        }
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl GcsSourceConfig {
+    async fn create_pubsub_ingestor(
+        &self,
+        cx: &SourceContext,
+        log_namespace: LogNamespace,
+    ) -> crate::Result<pubsub::Ingestor> {
+        // Both the Pub/Sub subscription and the bucket are accessed with the same token.
+        let auth = self.auth.build(Scope::CloudPlatform).await?;
+        auth.spawn_regenerate_token();
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        match self.pubsub {
+            Some(ref pubsub) => Ok(pubsub::Ingestor::new(
+                client,
+                auth,
+                self.endpoint.clone(),
+                pubsub.clone(),
+                self.compression,
+                decoder,
+            )?),
+            None => Err(CreatePubsubIngestorError::ConfigMissing {}.into()),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum CreatePubsubIngestorError {
+    #[snafu(display("Configuration for `pubsub` required when strategy=pubsub"))]
+    ConfigMissing,
+}
+
+#[cfg(test)]
+mod test {
+    use hyper::Body;
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+    use crate::sources::util::object_decoding::object_decoder;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GcsSourceConfig>();
+    }
+
+    #[tokio::test]
+    async fn decode_empty_object_gzip() {
+        let mut data = Vec::new();
+        object_decoder(
+            Compression::Auto,
+            "empty.log",
+            Some("gzip"),
+            None,
+            Body::empty(),
+        )
+        .await
+        .read_to_end(&mut data)
+        .await
+        .unwrap();
+
+        assert!(data.is_empty());
+    }
+}
@@ -0,0 +1,795 @@
+use std::collections::HashMap;
+use std::{num::NonZeroUsize, panic, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use http::{
+    header::{self, AsHeaderName},
+    uri::InvalidUri,
+    HeaderMap, Request, StatusCode, Uri,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use serde_json::json;
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use tokio::{pin, select};
+use tracing::Instrument;
+use vector_lib::codecs::decoding::FramingError;
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
+
+use crate::codecs::Decoder;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus},
+    gcp::{GcpAuthenticator, PUBSUB_URL},
+    http::{HttpClient, HttpError},
+    internal_events::{
+        EventsReceived, GcpCloudStorageAckDeadlineError, GcpCloudStorageAcknowledgeError,
+        GcpCloudStorageNotificationIgnored, GcpCloudStorageNotificationProcessingError,
+        GcpCloudStoragePullError,
+    },
+    shutdown::ShutdownSignal,
+    sources::{
+        gcp_cloud_storage::GcsSourceConfig,
+        util::object_decoding::{
+            object_decoder, send_object_events, Compression, ObjectEventsError, ObjectMetadata,
+        },
+    },
+    SourceSender,
+};
+
+const MIN_ACK_DEADLINE_SECS: u32 = 10;
+const MAX_ACK_DEADLINE_SECS: u32 = 600;
+
+/// The event type of the notifications sent when an object is created or overwritten.
+const OBJECT_FINALIZE: &str = "OBJECT_FINALIZE";
+
+/// The prefix of the response headers that hold the custom metadata of an object.
+const METADATA_HEADER_PREFIX: &str = "x-goog-meta-";
+
+/// Object names are sent in the path of the request, keeping the `/` between their segments.
+const OBJECT_NAME_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Pub/Sub configuration options.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The project name of the subscription.
+    #[configurable(metadata(docs::examples = "my-log-source-project"))]
+    pub(super) project: String,
+
+    /// The subscription that the notifications of the bucket are delivered to.
+    ///
+    /// The notifications must be sent with the `JSON_API_V1` or `NONE` payload format, as only
+    /// their attributes are used.
+    #[configurable(metadata(docs::examples = "my-bucket-notifications"))]
+    pub(super) subscription: String,
+
+    /// The endpoint from which to pull notifications.
+    #[configurable(metadata(docs::examples = "https://us-central1-pubsub.googleapis.com"))]
+    #[serde(default = "default_endpoint")]
+    #[derivative(Default(value = "default_endpoint()"))]
+    pub(super) endpoint: String,
+
+    /// The acknowledgement deadline, in seconds, to use for notifications.
+    ///
+    /// The deadline of each notification is extended to this value once it is received. If an
+    /// object takes longer than `ack_deadline_secs` to process, its notification is made available
+    /// again for another consumer.
+    // NOTE: This value isn't used as a `Duration` downstream, so we don't bother using `serde_with`
+    #[serde(default = "default_ack_deadline_secs")]
+    #[derivative(Default(value = "default_ack_deadline_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Acknowledgement Deadline"))]
+    pub(super) ack_deadline_secs: u32,
+
+    /// Whether to acknowledge non-retryable notifications.
+    ///
+    /// If the events of an object are rejected by the sink and not retryable, its notification is
+    /// acknowledged.
+    #[serde(default = "default_true")]
+    #[derivative(Default(value = "default_true()"))]
+    pub(super) ack_failed_message: bool,
+
+    /// Number of concurrent tasks to create for pulling notifications from the subscription.
+    ///
+    /// Defaults to the number of available CPUs on the system.
+    ///
+    /// Should not typically need to be changed, but it can sometimes be beneficial to raise this
+    /// value when there is a high rate of notifications and the objects being fetched are small.
+    #[configurable(metadata(docs::type_unit = "tasks"))]
+    #[configurable(metadata(docs::examples = 5))]
+    pub(super) client_concurrency: Option<NonZeroUsize>,
+
+    /// Maximum number of notifications to pull from the subscription at once.
+    ///
+    /// Should be set to a smaller value when the objects are large to help prevent the ingestion
+    /// of one object from causing the other notifications to exceed the `ack_deadline_secs`. Valid
+    /// values are 1 - 1000.
+    #[serde(default = "default_max_number_of_messages")]
+    #[derivative(Default(value = "default_max_number_of_messages()"))]
+    #[configurable(metadata(docs::human_name = "Max Messages"))]
+    #[configurable(metadata(docs::examples = 1))]
+    pub(super) max_number_of_messages: u32,
+
+    /// The amount of time, in seconds, to wait between retry attempts after failing to pull
+    /// notifications.
+    #[serde(default = "default_retry_delay")]
+    #[derivative(Default(value = "default_retry_delay()"))]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Retry Delay"))]
+    pub(super) retry_delay_secs: Duration,
+}
+
+fn default_endpoint() -> String {
+    PUBSUB_URL.to_string()
+}
+
+const fn default_ack_deadline_secs() -> u32 {
+    300
+}
+
+const fn default_max_number_of_messages() -> u32 {
+    10
+}
+
+const fn default_retry_delay() -> Duration {
+    Duration::from_secs(1)
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Invalid value for max_number_of_messages {}", messages))]
+    InvalidNumberOfMessages { messages: u32 },
+    #[snafu(display(
+        "`ack_deadline_secs` is outside the valid range of {} to {}",
+        MIN_ACK_DEADLINE_SECS,
+        MAX_ACK_DEADLINE_SECS
+    ))]
+    InvalidAckDeadline,
+    #[snafu(display("Invalid subscription URI: {}", source))]
+    SubscriptionUri { source: InvalidUri },
+}
+
+/// Errors from the requests made to the Pub/Sub subscription.
+#[derive(Debug, Snafu)]
+pub enum PubsubError {
+    #[snafu(display("Failed to send request: {}", source))]
+    Send { source: HttpError },
+    #[snafu(display("Unexpected status: {}", status))]
+    Status { status: StatusCode },
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadBody { source: hyper::Error },
+    #[snafu(display("Failed to parse response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+}
+
+#[derive(Debug, Snafu)]
+pub enum ProcessingError {
+    #[snafu(display(
+        "Pub/Sub message with id {} is not a bucket notification, it has no {} attribute",
+        message_id,
+        attribute
+    ))]
+    InvalidNotification {
+        message_id: String,
+        attribute: &'static str,
+    },
+    #[snafu(display("Invalid URI for gs://{}/{}: {}", bucket, object, source))]
+    ObjectUri {
+        source: InvalidUri,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to fetch gs://{}/{}: {}", bucket, object, source))]
+    GetObject {
+        source: HttpError,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display(
+        "Failed to fetch gs://{}/{}: unexpected status {}",
+        bucket,
+        object,
+        status
+    ))]
+    GetObjectStatus {
+        status: StatusCode,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to read all of gs://{}/{}: {}", bucket, object, source))]
+    ReadObject {
+        source: Box<dyn FramingError>,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to flush all of gs://{}/{}: {}", bucket, object, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Sink reported an error sending events"))]
+    ErrorAcknowledgement,
+}
+
+pub struct State {
+    client: HttpClient,
+    auth: GcpAuthenticator,
+
+    compression: Compression,
+
+    storage_endpoint: String,
+    pull_uri: Uri,
+    acknowledge_uri: Uri,
+    modify_ack_deadline_uri: Uri,
+    max_number_of_messages: u32,
+    client_concurrency: usize,
+    ack_deadline_secs: u32,
+    ack_failed_message: bool,
+    retry_delay: Duration,
+    decoder: Decoder,
+}
+
+pub(super) struct Ingestor {
+    state: Arc<State>,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        client: HttpClient,
+        auth: GcpAuthenticator,
+        storage_endpoint: String,
+        config: Config,
+        compression: Compression,
+        decoder: Decoder,
+    ) -> Result<Ingestor, IngestorNewError> {
+        if config.max_number_of_messages < 1 || config.max_number_of_messages > 1000 {
+            return Err(IngestorNewError::InvalidNumberOfMessages {
+                messages: config.max_number_of_messages,
+            });
+        }
+        if !(MIN_ACK_DEADLINE_SECS..=MAX_ACK_DEADLINE_SECS).contains(&config.ack_deadline_secs) {
+            return Err(IngestorNewError::InvalidAckDeadline);
+        }
+
+        let subscription = format!(
+            "{}/v1/projects/{}/subscriptions/{}",
+            config.endpoint.trim_end_matches('/'),
+            config.project,
+            config.subscription
+        );
+        let subscription_uri = |action: &str| {
+            format!("{subscription}:{action}")
+                .parse::<Uri>()
+                .context(SubscriptionUriSnafu)
+        };
+
+        let state = Arc::new(State {
+            client,
+            auth,
+
+            compression,
+
+            storage_endpoint: storage_endpoint.trim_end_matches('/').to_string(),
+            pull_uri: subscription_uri("pull")?,
+            acknowledge_uri: subscription_uri("acknowledge")?,
+            modify_ack_deadline_uri: subscription_uri("modifyAckDeadline")?,
+            max_number_of_messages: config.max_number_of_messages,
+            client_concurrency: config
+                .client_concurrency
+                .map(|n| n.get())
+                .unwrap_or_else(crate::num_threads),
+            ack_deadline_secs: config.ack_deadline_secs,
+            ack_failed_message: config.ack_failed_message,
+            retry_delay: config.retry_delay_secs,
+            decoder,
+        });
+
+        Ok(Ingestor { state })
+    }
+
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let mut handles = Vec::new();
+        for _ in 0..self.state.client_concurrency {
+            let process = IngestorProcess::new(
+                Arc::clone(&self.state),
+                cx.out.clone(),
+                cx.shutdown.clone(),
+                log_namespace,
+                acknowledgements,
+            );
+            let fut = process.run();
+            let handle = tokio::spawn(fut.in_current_span());
+            handles.push(handle);
+        }
+
+        // Wait for all of the processes to finish.  If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        for handle in handles.drain(..) {
+            if let Err(e) = handle.await {
+                if e.is_panic() {
+                    panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct IngestorProcess {
+    state: Arc<State>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl IngestorProcess {
+    pub fn new(
+        state: Arc<State>,
+        out: SourceSender,
+        shutdown: ShutdownSignal,
+        log_namespace: LogNamespace,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            state,
+            out,
+            shutdown,
+            acknowledgements,
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    async fn run(mut self) {
+        let shutdown = self.shutdown.clone().fuse();
+        pin!(shutdown);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once() => {},
+            }
+        }
+    }
+
+    async fn run_once(&mut self) {
+        let messages = match self.pull_messages().await {
+            Ok(messages) => messages,
+            Err(error) => {
+                emit!(GcpCloudStoragePullError { error: &error });
+                tokio::time::sleep(self.state.retry_delay).await;
+                return;
+            }
+        };
+        if messages.is_empty() {
+            return;
+        }
+
+        // The deadline of the subscription is usually far shorter than the time it takes to
+        // process an object, so it is extended before any of them are fetched.
+        let ack_ids = messages
+            .iter()
+            .map(|received| received.ack_id.clone())
+            .collect();
+        if let Err(error) = self.modify_ack_deadline(ack_ids).await {
+            emit!(GcpCloudStorageAckDeadlineError { error: &error });
+        }
+
+        let mut ack_ids = Vec::new();
+        for received in messages {
+            let message_id = received.message.message_id.clone();
+            match self.handle_pubsub_message(received.message).await {
+                Ok(()) => ack_ids.push(received.ack_id),
+                Err(error) => {
+                    emit!(GcpCloudStorageNotificationProcessingError {
+                        message_id: &message_id,
+                        error: &error,
+                    });
+                }
+            }
+        }
+
+        if !ack_ids.is_empty() {
+            let count = ack_ids.len();
+            if let Err(error) = self.acknowledge(ack_ids).await {
+                emit!(GcpCloudStorageAcknowledgeError {
+                    count,
+                    error: &error
+                });
+            }
+        }
+    }
+
+    async fn handle_pubsub_message(
+        &mut self,
+        message: PubsubMessage,
+    ) -> Result<(), ProcessingError> {
+        let notification = Notification::try_from(&message)?;
+
+        if notification.event_type != OBJECT_FINALIZE {
+            emit!(GcpCloudStorageNotificationIgnored {
+                bucket: &notification.bucket,
+                object: &notification.object,
+                event_type: &notification.event_type,
+            });
+            return Ok(());
+        }
+
+        self.handle_object(notification, self.log_namespace).await
+    }
+
+    async fn handle_object(
+        &mut self,
+        notification: Notification,
+        log_namespace: LogNamespace,
+    ) -> Result<(), ProcessingError> {
+        let uri = notification
+            .object_uri(&self.state.storage_endpoint)
+            .context(ObjectUriSnafu {
+                bucket: notification.bucket.clone(),
+                object: notification.object.clone(),
+            })?;
+
+        let mut request = Request::new(Body::empty());
+        *request.uri_mut() = uri;
+        self.state.auth.apply(&mut request);
+
+        let response = self
+            .state
+            .client
+            .send(request)
+            .await
+            .context(GetObjectSnafu {
+                bucket: notification.bucket.clone(),
+                object: notification.object.clone(),
+            })?;
+
+        if response.status() != StatusCode::OK {
+            return Err(ProcessingError::GetObjectStatus {
+                status: response.status(),
+                bucket: notification.bucket.clone(),
+                object: notification.object.clone(),
+            });
+        }
+
+        let (parts, body) = response.into_parts();
+
+        let metadata = parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let key = name.as_str().strip_prefix(METADATA_HEADER_PREFIX)?;
+                Some((key.to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let timestamp = header_str(&parts.headers, header::LAST_MODIFIED)
+            .and_then(|last_modified| DateTime::parse_from_rfc2822(last_modified).ok())
+            .map(|last_modified| last_modified.with_timezone(&Utc));
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let object_reader = object_decoder(
+            self.state.compression,
+            &notification.object,
+            header_str(&parts.headers, header::CONTENT_ENCODING),
+            header_str(&parts.headers, header::CONTENT_TYPE),
+            body,
+        )
+        .await;
+
+        let generation = notification
+            .generation
+            .as_deref()
+            .map(|generation| ("generation", generation));
+        let object_metadata = ObjectMetadata {
+            bucket: &notification.bucket,
+            key: &notification.object,
+            attributes: generation.as_slice(),
+            metadata: Some(&metadata),
+            timestamp,
+        };
+        let result = send_object_events(
+            object_reader,
+            &self.state.decoder,
+            None,
+            batch,
+            &self.bytes_received,
+            &self.events_received,
+            &mut self.out,
+            |log| object_metadata.insert(GcsSourceConfig::NAME, log, log_namespace),
+        )
+        .await;
+
+        match result {
+            Err(ObjectEventsError::Read(error)) => Err(ProcessingError::ReadObject {
+                source: error,
+                bucket: notification.bucket.clone(),
+                object: notification.object.clone(),
+            }),
+            Err(ObjectEventsError::Send(error)) => Err(ProcessingError::PipelineSend {
+                source: error,
+                bucket: notification.bucket.clone(),
+                object: notification.object.clone(),
+            }),
+            Ok(()) => match receiver {
+                None => Ok(()),
+                Some(receiver) => {
+                    let result = receiver.await;
+                    match result {
+                        BatchStatus::Delivered => Ok(()),
+                        BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                        BatchStatus::Rejected => {
+                            if self.state.ack_failed_message {
+                                Ok(())
+                            } else {
+                                Err(ProcessingError::ErrorAcknowledgement)
+                            }
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    async fn pull_messages(&mut self) -> Result<Vec<ReceivedMessage>, PubsubError> {
+        let body = self
+            .subscription_request(
+                self.state.pull_uri.clone(),
+                json!({ "maxMessages": self.state.max_number_of_messages }),
+            )
+            .await?;
+
+        serde_json::from_slice::<PullResponse>(&body)
+            .map(|response| response.received_messages)
+            .context(ParseResponseSnafu)
+    }
+
+    async fn modify_ack_deadline(&mut self, ack_ids: Vec<String>) -> Result<(), PubsubError> {
+        self.subscription_request(
+            self.state.modify_ack_deadline_uri.clone(),
+            json!({
+                "ackIds": ack_ids,
+                "ackDeadlineSeconds": self.state.ack_deadline_secs,
+            }),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn acknowledge(&mut self, ack_ids: Vec<String>) -> Result<(), PubsubError> {
+        self.subscription_request(
+            self.state.acknowledge_uri.clone(),
+            json!({ "ackIds": ack_ids }),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn subscription_request(
+        &self,
+        uri: Uri,
+        body: serde_json::Value,
+    ) -> Result<Bytes, PubsubError> {
+        let mut request = Request::new(Body::from(body.to_string()));
+        *request.method_mut() = http::Method::POST;
+        *request.uri_mut() = uri;
+        request.headers_mut().insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        self.state.auth.apply(&mut request);
+
+        let response = self.state.client.send(request).await.context(SendSnafu)?;
+        if !response.status().is_success() {
+            return Err(PubsubError::Status {
+                status: response.status(),
+            });
+        }
+
+        hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadBodySnafu)
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: impl AsHeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+// https://cloud.google.com/pubsub/docs/reference/rest/v1/projects.subscriptions/pull
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullResponse {
+    #[serde(default)]
+    received_messages: Vec<ReceivedMessage>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceivedMessage {
+    ack_id: String,
+    message: PubsubMessage,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PubsubMessage {
+    #[serde(default)]
+    message_id: String,
+    #[serde(default)]
+    attributes: HashMap<String, String>,
+}
+
+// https://cloud.google.com/storage/docs/pubsub-notifications#attributes
+/// A bucket notification, read from the attributes of a Pub/Sub message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Notification {
+    event_type: String,
+    bucket: String,
+    object: String,
+    generation: Option<String>,
+}
+
+impl TryFrom<&PubsubMessage> for Notification {
+    type Error = ProcessingError;
+
+    fn try_from(message: &PubsubMessage) -> Result<Self, Self::Error> {
+        let attribute = |attribute: &'static str| {
+            message.attributes.get(attribute).cloned().ok_or_else(|| {
+                ProcessingError::InvalidNotification {
+                    message_id: message.message_id.clone(),
+                    attribute,
+                }
+            })
+        };
+
+        Ok(Self {
+            event_type: attribute("eventType")?,
+            bucket: attribute("bucketId")?,
+            object: attribute("objectId")?,
+            generation: message.attributes.get("objectGeneration").cloned(),
+        })
+    }
+}
+
+impl Notification {
+    /// The URI of the generation of the object that the notification was sent for, so that
+    /// objects overwritten in the meantime are not read twice.
+    fn object_uri(&self, endpoint: &str) -> Result<Uri, InvalidUri> {
+        let object = utf8_percent_encode(&self.object, OBJECT_NAME_ENCODE_SET);
+        match &self.generation {
+            Some(generation) => format!(
+                "{endpoint}/{}/{object}?generation={generation}",
+                self.bucket
+            ),
+            None => format!("{endpoint}/{}/{object}", self.bucket),
+        }
+        .parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_pull_response() {
+        let response: PullResponse = serde_json::from_str(
+            r#"{
+            "receivedMessages": [{
+                "ackId": "ack-1",
+                "message": {
+                    "attributes": {
+                        "bucketId": "my-bucket",
+                        "eventTime": "2024-05-01T12:00:00.000000Z",
+                        "eventType": "OBJECT_FINALIZE",
+                        "notificationConfig": "projects/_/buckets/my-bucket/notificationConfigs/1",
+                        "objectGeneration": "1714564800000000",
+                        "objectId": "logs/2024/05/01/app.log.gz",
+                        "payloadFormat": "JSON_API_V1"
+                    },
+                    "data": "e30=",
+                    "messageId": "1234",
+                    "publishTime": "2024-05-01T12:00:00.123Z"
+                }
+            }]
+        }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.received_messages.len(), 1);
+        let received = &response.received_messages[0];
+        assert_eq!(received.ack_id, "ack-1");
+
+        let notification = Notification::try_from(&received.message).unwrap();
+        assert_eq!(
+            notification,
+            Notification {
+                event_type: OBJECT_FINALIZE.to_string(),
+                bucket: "my-bucket".to_string(),
+                object: "logs/2024/05/01/app.log.gz".to_string(),
+                generation: Some("1714564800000000".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_empty_pull_response() {
+        let response: PullResponse = serde_json::from_str("{}").unwrap();
+        assert!(response.received_messages.is_empty());
+    }
+
+    #[test]
+    fn notification_missing_attribute() {
+        let message = PubsubMessage {
+            message_id: "1234".to_string(),
+            attributes: HashMap::from([("eventType".to_string(), OBJECT_FINALIZE.to_string())]),
+        };
+
+        assert!(matches!(
+            Notification::try_from(&message),
+            Err(ProcessingError::InvalidNotification {
+                attribute: "bucketId",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn notification_object_uri() {
+        let mut notification = Notification {
+            event_type: OBJECT_FINALIZE.to_string(),
+            bucket: "my-bucket".to_string(),
+            object: "logs/app log+1.json".to_string(),
+            generation: Some("42".to_string()),
+        };
+
+        assert_eq!(
+            notification
+                .object_uri("https://storage.googleapis.com")
+                .unwrap()
+                .to_string(),
+            "https://storage.googleapis.com/my-bucket/logs/app%20log%2B1.json?generation=42"
+        );
+
+        notification.generation = None;
+        assert_eq!(
+            notification
+                .object_uri("https://storage.googleapis.com")
+                .unwrap()
+                .to_string(),
+            "https://storage.googleapis.com/my-bucket/logs/app%20log%2B1.json"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! The `grpc` source. See [GrpcConfig].
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, sync::Arc};
+
+use futures::TryFutureExt;
+use prost_reflect::{DescriptorPool, MethodDescriptor};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tonic::transport::server::Routes;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::owned_value_path;
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
+    },
+    internal_events::EventsReceived,
+    serde::bool_or_struct,
+    sources::{util::grpc::run_grpc_server_with_routes, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+mod service;
+
+use self::service::{GrpcService, Handler};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read descriptor set {:?}: {}", path, source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not decode descriptor set {:?}: {}", path, source))]
+    DecodeDescriptorSet {
+        path: PathBuf,
+        source: prost_reflect::DescriptorError,
+    },
+    #[snafu(display("Service {:?} is not defined in the descriptor set", service))]
+    UnknownService { service: String },
+    #[snafu(display("Method {:?} is not defined by service {:?}", method, service))]
+    UnknownMethod { service: String, method: String },
+}
+
+/// Configuration for the `grpc` source.
+#[configurable_component(source(
+    "grpc",
+    "Collect messages from gRPC clients, decoded with a Protobuf descriptor set."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcConfig {
+    /// The socket address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    #[configurable(metadata(docs::examples = "0.0.0.0:50051"))]
+    address: SocketAddr,
+
+    /// The path to the compiled Protobuf descriptor set that defines the service.
+    ///
+    /// The descriptor set can be generated with `protoc --include_imports --descriptor_set_out`.
+    #[configurable(metadata(docs::examples = "/etc/vector/ingest.desc"))]
+    desc_file: PathBuf,
+
+    /// The fully qualified name of the service to serve.
+    #[configurable(metadata(docs::examples = "mycompany.ingest.v1.IngestService"))]
+    service: String,
+
+    /// The names of the methods of the service to serve.
+    ///
+    /// All the methods of the service are served if this is empty. Each request message is
+    /// decoded into an event, for unary as well as streaming methods.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "Push"))]
+    methods: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for GrpcConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:50051".parse().unwrap(),
+            desc_file: PathBuf::from("/etc/vector/ingest.desc"),
+            service: "mycompany.ingest.v1.IngestService".to_owned(),
+            methods: Vec::new(),
+            tls: None,
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+impl GrpcConfig {
+    /// Loads the descriptors of the methods to serve, keyed by method name.
+    fn load_methods(&self) -> Result<HashMap<String, MethodDescriptor>, BuildError> {
+        let bytes = std::fs::read(&self.desc_file).context(ReadDescriptorSetSnafu {
+            path: &self.desc_file,
+        })?;
+        let pool = DescriptorPool::decode(bytes.as_slice()).context(DecodeDescriptorSetSnafu {
+            path: &self.desc_file,
+        })?;
+        let service = pool
+            .get_service_by_name(&self.service)
+            .context(UnknownServiceSnafu {
+                service: &self.service,
+            })?;
+
+        let methods = service
+            .methods()
+            .map(|method| (method.name().to_owned(), method))
+            .collect::<HashMap<_, _>>();
+        if self.methods.is_empty() {
+            return Ok(methods);
+        }
+
+        self.methods
+            .iter()
+            .map(|name| {
+                let method = methods.get(name).context(UnknownMethodSnafu {
+                    service: &self.service,
+                    method: name,
+                })?;
+                Ok((name.clone(), method.clone()))
+            })
+            .collect()
+    }
+
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(
+            Kind::object(Collection::empty().with_unknown(Kind::any())),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            Some(LegacyKey::InsertIfEmpty(owned_value_path!("method"))),
+            &owned_value_path!("method"),
+            Kind::bytes(),
+            None,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "grpc")]
+impl SourceConfig for GrpcConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let methods = self.load_methods()?;
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let service = GrpcService::new(
+            Arc::new(methods),
+            Handler {
+                pipeline: cx.out,
+                acknowledgements,
+                events_received: register!(EventsReceived),
+                log_namespace,
+            },
+        );
+        // The service is only known at runtime, so it can't be routed with `NamedService`.
+        let router =
+            axum::Router::new().route_service(&format!("/{}/*method", self.service), service);
+
+        let source = run_grpc_server_with_routes(
+            self.address,
+            tls_settings,
+            Routes::from(router),
+            cx.shutdown,
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
+
+        Ok(Box::pin(source))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message as _;
+    use prost_reflect::DynamicMessage;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+    use tonic::{codegen::http::uri::PathAndQuery, transport::Channel};
+    use vector_lib::config::log_schema;
+
+    use super::{service::DynamicCodec, *};
+    use crate::{
+        test_util::{
+            self,
+            components::{assert_source_compliance, SOURCE_TAGS},
+        },
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        test_util::test_generate_config::<GrpcConfig>();
+    }
+
+    /// A descriptor set with an `test.Ingest` service, whose `Push` method takes an `Entry`.
+    fn descriptor_set() -> Vec<u8> {
+        let field = |name: &str, number, r#type: Type| FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional.into()),
+            r#type: Some(r#type.into()),
+            ..Default::default()
+        };
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("test.proto".to_owned()),
+                package: Some("test".to_owned()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("Entry".to_owned()),
+                        field: vec![
+                            field("message", 1, Type::String),
+                            field("level", 2, Type::Int32),
+                        ],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("Ack".to_owned()),
+                        ..Default::default()
+                    },
+                ],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("Ingest".to_owned()),
+                    method: vec![MethodDescriptorProto {
+                        name: Some("Push".to_owned()),
+                        input_type: Some(".test.Entry".to_owned()),
+                        output_type: Some(".test.Ack".to_owned()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                syntax: Some("proto3".to_owned()),
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    fn config(address: SocketAddr, desc_file: PathBuf, methods: &[&str]) -> GrpcConfig {
+        GrpcConfig {
+            address,
+            desc_file,
+            service: "test.Ingest".to_owned(),
+            methods: methods.iter().map(|method| method.to_string()).collect(),
+            tls: None,
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_methods() {
+        let dir = test_util::temp_dir();
+        let desc_file = dir.join("test.desc");
+        std::fs::write(&desc_file, descriptor_set()).unwrap();
+
+        let config = config(test_util::next_addr(), desc_file, &["Pull"]);
+        let error = config.load_methods().unwrap_err();
+        assert!(matches!(error, BuildError::UnknownMethod { .. }));
+    }
+
+    #[tokio::test]
+    async fn receives_unary_requests() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let dir = test_util::temp_dir();
+            let desc_file = dir.join("test.desc");
+            std::fs::write(&desc_file, descriptor_set()).unwrap();
+
+            let address = test_util::next_addr();
+            let config = config(address, desc_file, &["Push"]);
+            let method = config.load_methods().unwrap().remove("Push").unwrap();
+
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            test_util::wait_for_tcp(address).await;
+
+            let channel = Channel::from_shared(format!("http://{}", address))
+                .unwrap()
+                .connect()
+                .await
+                .unwrap();
+            let mut client = tonic::client::Grpc::new(channel);
+            client.ready().await.unwrap();
+
+            let mut entry = DynamicMessage::new(method.input());
+            entry.set_field_by_name("message", prost_reflect::Value::String("hello".into()));
+            entry.set_field_by_name("level", prost_reflect::Value::I32(3));
+            client
+                .unary(
+                    tonic::Request::new(entry),
+                    PathAndQuery::from_static("/test.Ingest/Push"),
+                    DynamicCodec::new(method.output()),
+                )
+                .await
+                .unwrap();
+
+            let events = test_util::collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "hello".into());
+            assert_eq!(log["level"], 3.into());
+            assert_eq!(log["method"], "Push".into());
+            assert_eq!(
+                log[log_schema().source_type_key().unwrap().to_string()],
+                "grpc".into()
+            );
+        })
+        .await;
+    }
+}
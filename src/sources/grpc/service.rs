@@ -0,0 +1,271 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use chrono::Utc;
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+use http::{Request, Response};
+use hyper::Body;
+use prost::Message as _;
+use prost_reflect::{DynamicMessage, MessageDescriptor, MethodDescriptor};
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    server::{ClientStreamingService, Grpc, StreamingService},
+    Status, Streaming,
+};
+use tower::Service;
+use vector_lib::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
+use vector_lib::lookup::path;
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::GrpcConfig;
+use crate::{
+    internal_events::{EventsReceived, GrpcError, StreamClosedError},
+    SourceSender,
+};
+
+/// A codec for messages whose type is only known at runtime.
+#[derive(Clone, Debug)]
+pub(super) struct DynamicCodec {
+    /// The descriptor of the decoded messages.
+    descriptor: MessageDescriptor,
+}
+
+impl DynamicCodec {
+    pub(super) const fn new(descriptor: MessageDescriptor) -> Self {
+        Self { descriptor }
+    }
+}
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.descriptor.clone())
+    }
+}
+
+pub(super) struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .expect("Message only errors if not enough space");
+        Ok(())
+    }
+}
+
+pub(super) struct DynamicDecoder(MessageDescriptor);
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|error| Status::internal(error.to_string()))
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Handler {
+    pub pipeline: SourceSender,
+    pub acknowledgements: bool,
+    pub events_received: Registered<EventsReceived>,
+    pub log_namespace: LogNamespace,
+}
+
+impl Handler {
+    /// Sends the event of a request message, and returns the receiver of its delivery status.
+    async fn handle_message(
+        &self,
+        method: &MethodDescriptor,
+        message: DynamicMessage,
+    ) -> Result<Option<BatchStatusReceiver>, Status> {
+        let value = vrl::protobuf::proto_to_value(&prost_reflect::Value::Message(message), None)
+            .map_err(|error| {
+                emit!(GrpcError { error: &error });
+                Status::invalid_argument(error.to_string())
+            })?;
+
+        let mut log = LogEvent::from(value);
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            GrpcConfig::NAME,
+            Utc::now(),
+        );
+        self.log_namespace.insert_source_metadata(
+            GrpcConfig::NAME,
+            &mut log,
+            Some(LegacyKey::InsertIfEmpty(path!("method"))),
+            path!("method"),
+            method.name().to_owned(),
+        );
+
+        let mut events = vec![Event::from(log)];
+        self.events_received
+            .emit(CountByteSize(1, events.estimated_json_encoded_size_of()));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        self.pipeline
+            .clone()
+            .send_batch(events)
+            .await
+            .map_err(|error| {
+                emit!(StreamClosedError { count: 1 });
+                Status::unavailable(error.to_string())
+            })?;
+
+        Ok(receiver)
+    }
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
+
+/// Serves a method with a single response, sent once all the request messages are delivered.
+///
+/// Unary requests are handled as a stream of a single message.
+#[derive(Clone)]
+struct SingleResponse {
+    method: MethodDescriptor,
+    handler: Handler,
+}
+
+impl ClientStreamingService<DynamicMessage> for SingleResponse {
+    type Response = DynamicMessage;
+    type Future = BoxFuture<'static, Result<tonic::Response<Self::Response>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<DynamicMessage>>) -> Self::Future {
+        let Self { method, handler } = self.clone();
+        Box::pin(async move {
+            let mut messages = request.into_inner();
+            let mut receivers = Vec::new();
+            while let Some(message) = messages.message().await? {
+                receivers.push(handler.handle_message(&method, message).await?);
+            }
+            for receiver in receivers {
+                handle_batch_status(receiver).await?;
+            }
+
+            Ok(tonic::Response::new(DynamicMessage::new(method.output())))
+        })
+    }
+}
+
+/// Serves a method with a stream of responses, one per delivered request message.
+#[derive(Clone)]
+struct StreamingResponse {
+    method: MethodDescriptor,
+    handler: Handler,
+}
+
+impl StreamingService<DynamicMessage> for StreamingResponse {
+    type Response = DynamicMessage;
+    type ResponseStream = BoxStream<'static, Result<Self::Response, Status>>;
+    type Future = BoxFuture<'static, Result<tonic::Response<Self::ResponseStream>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<DynamicMessage>>) -> Self::Future {
+        let this = self.clone();
+        let responses = request
+            .into_inner()
+            .then(move |message| {
+                let Self { method, handler } = this.clone();
+                async move {
+                    let receiver = handler.handle_message(&method, message?).await?;
+                    handle_batch_status(receiver).await?;
+                    Ok(DynamicMessage::new(method.output()))
+                }
+            })
+            .boxed();
+
+        Box::pin(async move { Ok(tonic::Response::new(responses)) })
+    }
+}
+
+/// Routes the requests to the served methods of the service.
+#[derive(Clone)]
+pub(super) struct GrpcService {
+    methods: Arc<HashMap<String, MethodDescriptor>>,
+    handler: Handler,
+}
+
+impl GrpcService {
+    pub(super) const fn new(
+        methods: Arc<HashMap<String, MethodDescriptor>>,
+        handler: Handler,
+    ) -> Self {
+        Self { methods, handler }
+    }
+}
+
+impl Service<Request<Body>> for GrpcService {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // The path is defined as “/” {service name} “/” {method name}.
+        let method = request
+            .uri()
+            .path()
+            .rsplit('/')
+            .next()
+            .and_then(|name| self.methods.get(name))
+            .cloned();
+        let Some(method) = method else {
+            let status = Status::unimplemented("Method is not served.");
+            return Box::pin(async move { Ok(status.to_http()) });
+        };
+
+        let handler = self.handler.clone();
+        Box::pin(async move {
+            let mut grpc = Grpc::new(DynamicCodec::new(method.input()))
+                .accept_compressed(CompressionEncoding::Gzip)
+                // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
+                .max_decoding_message_size(usize::MAX);
+
+            let response = if method.is_server_streaming() {
+                grpc.streaming(StreamingResponse { method, handler }, request)
+                    .await
+            } else {
+                grpc.client_streaming(SingleResponse { method, handler }, request)
+                    .await
+            };
+            Ok(response)
+        })
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     io::{BufRead, BufReader},
     net::SocketAddr,
     str::FromStr,
+    sync::Arc,
 };
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
@@ -21,6 +22,7 @@ use vector_lib::configurable::configurable_component;
 use vector_lib::{
     config::{LegacyKey, LogNamespace},
     schema::Definition,
+    sensitive_string::SensitiveString,
 };
 
 use crate::{
@@ -66,6 +68,16 @@ pub struct LogplexConfig {
     #[configurable(metadata(docs::examples = "*"))]
     query_parameters: Vec<String>,
 
+    /// The Logplex drains that send logs to this source.
+    ///
+    /// When drains are configured, requests whose drain token isn't the token of one of them are
+    /// rejected, and the logs of each drain are sent to the output named after the drain, so that
+    /// the logs of many Heroku apps can be received on the same address and routed separately.
+    ///
+    /// When no drains are configured, the logs of every drain are sent to the default output.
+    #[serde(default)]
+    drains: Vec<DrainConfig>,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -94,6 +106,23 @@ pub struct LogplexConfig {
     keepalive: KeepaliveConfig,
 }
 
+/// A Logplex drain that sends logs to the `heroku_logs` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DrainConfig {
+    /// The token of the drain, which Logplex sends in the `Logplex-Drain-Token` header.
+    #[configurable(metadata(docs::examples = "d.fc6b856b-3332-4546-93de-7d0ee272c3bd"))]
+    token: SensitiveString,
+
+    /// The name of the drain, such as the name of the Heroku app that it drains.
+    ///
+    /// The logs of the drain are sent to the output of this name. Several drains can share a
+    /// name, to send their logs to the same output.
+    #[configurable(metadata(docs::examples = "my-app"))]
+    name: String,
+}
+
 impl LogplexConfig {
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
     fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
@@ -139,7 +168,24 @@ impl LogplexConfig {
                 &owned_value_path!("query_parameters"),
                 Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
                 None,
+            )
+            .with_source_metadata(
+                LogplexConfig::NAME,
+                None,
+                &owned_value_path!("drain_token"),
+                Kind::bytes(),
+                None,
+            );
+
+        if !self.drains.is_empty() {
+            schema_definition = schema_definition.with_source_metadata(
+                LogplexConfig::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("drain_name"))),
+                &owned_value_path!("drain_name"),
+                Kind::bytes(),
+                None,
             );
+        }
 
         // for metadata that is added to the events dynamically from config options
         if log_namespace == LogNamespace::Legacy {
@@ -155,6 +201,7 @@ impl Default for LogplexConfig {
         Self {
             address: "0.0.0.0:80".parse().unwrap(),
             query_parameters: Vec::new(),
+            drains: Vec::new(),
             tls: None,
             auth: None,
             framing: default_framing_message_based(),
@@ -182,11 +229,22 @@ impl SourceConfig for LogplexConfig {
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
+        let mut drains = HashMap::with_capacity(self.drains.len());
+        for drain in &self.drains {
+            if drains
+                .insert(drain.token.inner().to_owned(), drain.name.clone())
+                .is_some()
+            {
+                return Err(format!("Duplicate token for drain {:?}.", drain.name).into());
+            }
+        }
+
         let source = LogplexSource {
             query_parameters: build_param_matcher(&remove_duplicates(
                 self.query_parameters.clone(),
                 "query_parameters",
             ))?,
+            drains: Arc::new(drains),
             decoder,
             log_namespace,
         };
@@ -209,10 +267,27 @@ impl SourceConfig for LogplexConfig {
         // There is a global and per-source `log_namespace` config.
         // The source config overrides the global setting and is merged here.
         let schema_def = self.schema_definition(global_log_namespace.merge(self.log_namespace));
-        vec![SourceOutput::new_maybe_logs(
-            self.decoding.output_type(),
-            schema_def,
-        )]
+        if self.drains.is_empty() {
+            return vec![SourceOutput::new_maybe_logs(
+                self.decoding.output_type(),
+                schema_def,
+            )];
+        }
+
+        let mut names = self
+            .drains
+            .iter()
+            .map(|drain| drain.name.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+            .into_iter()
+            .map(|name| {
+                SourceOutput::new_maybe_logs(self.decoding.output_type(), schema_def.clone())
+                    .with_port(name)
+            })
+            .collect()
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -227,6 +302,8 @@ impl SourceConfig for LogplexConfig {
 #[derive(Clone, Default)]
 struct LogplexSource {
     query_parameters: Vec<HttpConfigParamKind>,
+    /// The names of the configured drains, by drain token.
+    drains: Arc<HashMap<String, String>>,
     decoder: Decoder,
     log_namespace: LogNamespace,
 }
@@ -251,6 +328,13 @@ impl LogplexSource {
             drain_token
         });
 
+        if !self.drains.is_empty() && !self.drains.contains_key(drain_token) {
+            return Err(ErrorMessage::new(
+                StatusCode::UNAUTHORIZED,
+                "Unknown drain token".to_string(),
+            ));
+        }
+
         // Deal with body
         let events = self.body_to_events(body);
 
@@ -298,7 +382,7 @@ impl HttpSource for LogplexSource {
         &self,
         events: &mut [Event],
         _request_path: &str,
-        _headers_config: &HeaderMap,
+        headers_config: &HeaderMap,
         query_parameters: &HashMap<String, String>,
         _source_ip: Option<&SocketAddr>,
     ) {
@@ -309,6 +393,39 @@ impl HttpSource for LogplexSource {
             self.log_namespace,
             LogplexConfig::NAME,
         );
+
+        // The drain token header was validated when the events were built.
+        let Ok(drain_token) = get_header(headers_config, "Logplex-Drain-Token") else {
+            return;
+        };
+        let drain_name = self.drains.get(drain_token);
+        let legacy_drain_name_key = parse_value_path("drain_name").ok();
+        for event in events.iter_mut() {
+            if let Event::Log(log) = event {
+                self.log_namespace.insert_source_metadata(
+                    LogplexConfig::NAME,
+                    log,
+                    None::<LegacyKey<&str>>,
+                    path!("drain_token"),
+                    drain_token.to_owned(),
+                );
+
+                if let Some(drain_name) = drain_name {
+                    self.log_namespace.insert_source_metadata(
+                        LogplexConfig::NAME,
+                        log,
+                        legacy_drain_name_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                        path!("drain_name"),
+                        drain_name.clone(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn request_output(&self, header_map: &HeaderMap) -> Option<String> {
+        let drain_token = get_header(header_map, "Logplex-Drain-Token").ok()?;
+        self.drains.get(drain_token).cloned()
     }
 }
 
@@ -428,21 +545,23 @@ mod tests {
     use std::net::SocketAddr;
 
     use chrono::{DateTime, Utc};
-    use futures::Stream;
+    use futures::{Stream, StreamExt};
     use similar_asserts::assert_eq;
     use vector_lib::lookup::{owned_value_path, OwnedTargetPath};
     use vector_lib::{
         config::LogNamespace,
-        event::{Event, EventStatus, Value},
+        event::{into_event_stream, Event, EventStatus, Value},
         schema::Definition,
     };
     use vrl::value::{kind::Collection, Kind};
 
-    use super::{HttpSourceAuthConfig, LogplexConfig};
+    use super::{DrainConfig, HttpSourceAuthConfig, LogplexConfig};
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
         serde::{default_decoding, default_framing_message_based},
+        sources::util::http::BasicAuthConfig,
         test_util::{
+            collect_ready,
             components::{assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
             next_addr, random_string, spawn_collect_n, wait_for_tcp,
         },
@@ -467,6 +586,7 @@ mod tests {
             LogplexConfig {
                 address,
                 query_parameters,
+                drains: Vec::new(),
                 tls: None,
                 auth,
                 framing: default_framing_message_based(),
@@ -490,6 +610,16 @@ mod tests {
         body: &str,
         auth: Option<HttpSourceAuthConfig>,
         query: &str,
+    ) -> u16 {
+        send_drain(address, body, auth, query, "drain-bar").await
+    }
+
+    async fn send_drain(
+        address: SocketAddr,
+        body: &str,
+        auth: Option<HttpSourceAuthConfig>,
+        query: &str,
+        drain_token: &str,
     ) -> u16 {
         let len = body.lines().count();
         let mut req = reqwest::Client::new().post(format!("http://{}/events?{}", address, query));
@@ -498,7 +628,7 @@ mod tests {
         }
         req.header("Logplex-Msg-Count", len)
             .header("Logplex-Frame-Id", "frame-foo")
-            .header("Logplex-Drain-Token", "drain-bar")
+            .header("Logplex-Drain-Token", drain_token)
             .body(body.to_owned())
             .send()
             .await
@@ -508,10 +638,10 @@ mod tests {
     }
 
     fn make_auth() -> HttpSourceAuthConfig {
-        HttpSourceAuthConfig {
+        HttpSourceAuthConfig::Basic(BasicAuthConfig {
             username: random_string(16),
             password: random_string(16).into(),
-        }
+        })
     }
 
     const SAMPLE_BODY: &str = r#"267 <158>1 2020-01-08T22:33:57.353034+00:00 host heroku router - at=info method=GET path="/cart_link" host=lumberjack-store.timber.io request_id=05726858-c44e-4f94-9a20-37df73be9006 fwd="73.75.38.87" dyno=web.1 connect=1ms service=22ms status=304 bytes=656 protocol=http"#;
@@ -668,6 +798,78 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn logplex_routes_drains_to_their_outputs() {
+        let (mut sender, mut default_rx) = SourceSender::new_test();
+        let mut store_rx = sender
+            .add_outputs(EventStatus::Delivered, "lumberjack-store".to_owned())
+            .flat_map(into_event_stream);
+        let mut api_rx = sender
+            .add_outputs(EventStatus::Delivered, "lumberjack-api".to_owned())
+            .flat_map(into_event_stream);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            LogplexConfig {
+                address,
+                drains: vec![
+                    DrainConfig {
+                        token: "d.store".to_owned().into(),
+                        name: "lumberjack-store".to_owned(),
+                    },
+                    DrainConfig {
+                        token: "d.api".to_owned().into(),
+                        name: "lumberjack-api".to_owned(),
+                    },
+                ],
+                ..Default::default()
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+
+        let status = send_drain(address, SAMPLE_BODY, None, "", "d.store").await;
+        assert_eq!(status, 200);
+        let status = send_drain(address, SAMPLE_BODY, None, "", "d.unknown").await;
+        assert_eq!(status, 401);
+
+        let events = collect_ready(&mut store_rx).await;
+        assert_eq!(events.len(), SAMPLE_BODY.lines().count());
+        assert_eq!(events[0].as_log()["drain_name"], "lumberjack-store".into());
+        assert!(collect_ready(&mut api_rx).await.is_empty());
+        assert!(collect_ready(&mut default_rx).await.is_empty());
+    }
+
+    #[test]
+    fn logplex_drains_have_named_outputs() {
+        let drain = |token: &str, name: &str| DrainConfig {
+            token: token.to_owned().into(),
+            name: name.to_owned(),
+        };
+        let config = LogplexConfig {
+            drains: vec![
+                drain("d.1", "app-b"),
+                drain("d.2", "app-a"),
+                drain("d.3", "app-b"),
+            ],
+            ..Default::default()
+        };
+
+        let ports = config
+            .outputs(LogNamespace::Legacy)
+            .into_iter()
+            .map(|output| output.port)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ports,
+            vec![Some("app-a".to_owned()), Some("app-b".to_owned())]
+        );
+    }
+
     #[test]
     fn logplex_handles_normal_lines() {
         let log_namespace = LogNamespace::Legacy;
@@ -767,6 +969,11 @@ mod tests {
                     &owned_value_path!(LogplexConfig::NAME, "query_parameters"),
                     Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
                     None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!(LogplexConfig::NAME, "drain_token"),
+                    Kind::bytes(),
+                    None,
                 );
 
         assert_eq!(definitions, Some(expected_definition))
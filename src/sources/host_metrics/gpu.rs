@@ -0,0 +1,138 @@
+#[cfg(feature = "sources-host_metrics-gpu")]
+use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, Device, Nvml};
+use vector_lib::configurable::configurable_component;
+#[cfg(feature = "sources-host_metrics-gpu")]
+use vector_lib::metric_tags;
+
+#[cfg(feature = "sources-host_metrics-gpu")]
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+use super::{default_all_devices, FilterList};
+#[cfg(feature = "sources-host_metrics-gpu")]
+use super::{filter_result_sync, HostMetrics, MetricsBuffer};
+
+#[cfg(feature = "sources-host_metrics-gpu")]
+const MILLIWATTS: f64 = 1.0 / 1_000.0;
+
+/// Options for the GPU metrics collector.
+///
+/// This collector reports the metrics of NVIDIA GPUs, through the NVIDIA Management Library (NVML)
+/// installed with their drivers.
+///
+/// This collector is only available if Vector is built with the `sources-host_metrics-gpu` feature.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct GpuConfig {
+    /// Lists of GPU index patterns, such as `0`, to include or exclude in gathering
+    /// GPU metrics.
+    #[serde(default = "default_all_devices")]
+    #[configurable(metadata(docs::examples = "example_gpus()"))]
+    devices: FilterList,
+}
+
+fn example_gpus() -> FilterList {
+    FilterList {
+        includes: Some(vec!["0".try_into().unwrap()]),
+        excludes: Some(vec!["7".try_into().unwrap()]),
+    }
+}
+
+#[cfg(feature = "sources-host_metrics-gpu")]
+/// Loads the NVIDIA Management Library, if it is installed.
+pub(super) fn init_nvml() -> Option<Nvml> {
+    Nvml::init()
+        .map_err(|error| {
+            emit!(HostMetricsScrapeDetailError {
+                message: "Failed to load the NVIDIA Management Library.",
+                error,
+            })
+        })
+        .ok()
+}
+
+#[cfg(feature = "sources-host_metrics-gpu")]
+impl HostMetrics {
+    pub async fn gpu_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "gpu";
+        let Some(nvml) = &self.nvml else {
+            return;
+        };
+        let Some(count) = filter_result_sync(nvml.device_count(), "Failed to load GPU count.")
+        else {
+            return;
+        };
+
+        for index in 0..count {
+            let index_str = index.to_string();
+            if !self.config.gpu.devices.contains_str(Some(&index_str)) {
+                continue;
+            }
+            let Some(device) =
+                filter_result_sync(nvml.device_by_index(index), "Failed to load GPU info.")
+            else {
+                continue;
+            };
+            device_metrics(&device, index_str, output);
+        }
+    }
+}
+
+#[cfg(feature = "sources-host_metrics-gpu")]
+fn device_metrics(device: &Device<'_>, index: String, output: &mut MetricsBuffer) {
+    let mut tags = metric_tags!("device" => index);
+    if let Ok(name) = device.name() {
+        tags.replace("model".into(), name);
+    }
+    if let Ok(uuid) = device.uuid() {
+        tags.replace("uuid".into(), uuid);
+    }
+
+    // The metrics that are not supported by a device are skipped.
+    if let Ok(utilization) = device.utilization_rates() {
+        output.gauge(
+            "gpu_utilization_ratio",
+            utilization.gpu as f64 / 100.0,
+            tags.clone(),
+        );
+        output.gauge(
+            "gpu_memory_utilization_ratio",
+            utilization.memory as f64 / 100.0,
+            tags.clone(),
+        );
+    }
+    if let Ok(memory) = device.memory_info() {
+        output.gauge("gpu_memory_total_bytes", memory.total as f64, tags.clone());
+        output.gauge("gpu_memory_used_bytes", memory.used as f64, tags.clone());
+        output.gauge("gpu_memory_free_bytes", memory.free as f64, tags.clone());
+    }
+    if let Ok(temperature) = device.temperature(TemperatureSensor::Gpu) {
+        output.gauge("gpu_temperature_celsius", temperature as f64, tags.clone());
+    }
+    if let Ok(power) = device.power_usage() {
+        output.gauge(
+            "gpu_power_usage_watts",
+            power as f64 * MILLIWATTS,
+            tags.clone(),
+        );
+    }
+    if let Ok(limit) = device.enforced_power_limit() {
+        output.gauge("gpu_power_limit_watts", limit as f64 * MILLIWATTS, tags);
+    }
+}
+
+#[cfg(all(test, feature = "sources-host_metrics-gpu"))]
+mod tests {
+    use super::super::{Collector, HostMetrics, HostMetricsConfig, MetricsBuffer};
+
+    #[tokio::test]
+    async fn skips_gpu_metrics_without_nvml() {
+        let mut buffer = MetricsBuffer::new(None);
+        let host = HostMetrics::new(HostMetricsConfig {
+            collectors: Some(vec![Collector::Cpu]),
+            ..Default::default()
+        });
+        assert!(host.nvml.is_none());
+        host.gpu_metrics(&mut buffer).await;
+        assert!(buffer.metrics.is_empty());
+    }
+}
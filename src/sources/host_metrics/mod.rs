@@ -32,8 +32,10 @@ mod cgroups;
 mod cpu;
 mod disk;
 mod filesystem;
+mod gpu;
 mod memory;
 mod network;
+mod process;
 
 /// Collector types.
 #[serde_as]
@@ -55,6 +57,11 @@ pub enum Collector {
     /// Metrics related to filesystem space utilization.
     Filesystem,
 
+    /// Metrics related to NVIDIA GPU utilization.
+    ///
+    /// Not collected by default, as it requires the NVIDIA Management Library.
+    Gpu,
+
     /// Metrics related to the system load average.
     Load,
 
@@ -66,6 +73,11 @@ pub enum Collector {
 
     /// Metrics related to network utilization.
     Network,
+
+    /// Metrics related to the resource usage of individual processes.
+    ///
+    /// Not collected by default, as the number of processes can be large.
+    Process,
 }
 
 /// Filtering configuration.
@@ -98,7 +110,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors, except `gpu` and `process`.
     #[configurable(metadata(docs::examples = "example_collectors()"))]
     #[derivative(Default(value = "default_collectors()"))]
     #[serde(default = "default_collectors")]
@@ -122,9 +134,17 @@ pub struct HostMetricsConfig {
     #[serde(default)]
     pub filesystem: filesystem::FilesystemConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub gpu: gpu::GpuConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub process: process::ProcessConfig,
 }
 
 /// Options for the cgroups (controller groups) metrics collector.
@@ -170,16 +190,18 @@ pub fn default_namespace() -> Option<String> {
     Some(String::from("host"))
 }
 
-const fn example_collectors() -> [&'static str; 8] {
+const fn example_collectors() -> [&'static str; 10] {
     [
         "cgroups",
         "cpu",
         "disk",
         "filesystem",
+        "gpu",
         "load",
         "host",
         "memory",
         "network",
+        "process",
     ]
 }
 
@@ -263,6 +285,15 @@ impl SourceConfig for HostMetricsConfig {
             }
         }
 
+        #[cfg(not(feature = "sources-host_metrics-gpu"))]
+        if self
+            .collectors
+            .as_ref()
+            .is_some_and(|collectors| collectors.contains(&Collector::Gpu))
+        {
+            return Err("GPU collector requires the `sources-host_metrics-gpu` feature".into());
+        }
+
         let mut config = self.clone();
         config.namespace = config.namespace.filter(|namespace| !namespace.is_empty());
 
@@ -317,14 +348,23 @@ pub struct HostMetrics {
     config: HostMetricsConfig,
     #[cfg(target_os = "linux")]
     root_cgroup: Option<cgroups::CGroupRoot>,
+    #[cfg(feature = "sources-host_metrics-gpu")]
+    nvml: Option<nvml_wrapper::Nvml>,
     events_received: Registered<EventsReceived>,
 }
 
 impl HostMetrics {
     #[cfg(not(target_os = "linux"))]
     pub fn new(config: HostMetricsConfig) -> Self {
+        #[cfg(feature = "sources-host_metrics-gpu")]
+        let nvml = config
+            .has_collector(Collector::Gpu)
+            .then(gpu::init_nvml)
+            .flatten();
         Self {
             config,
+            #[cfg(feature = "sources-host_metrics-gpu")]
+            nvml,
             events_received: register!(EventsReceived),
         }
     }
@@ -333,9 +373,16 @@ impl HostMetrics {
     pub fn new(config: HostMetricsConfig) -> Self {
         let cgroups = config.cgroups.clone().unwrap_or_default();
         let root_cgroup = cgroups::CGroupRoot::new(&cgroups);
+        #[cfg(feature = "sources-host_metrics-gpu")]
+        let nvml = config
+            .has_collector(Collector::Gpu)
+            .then(gpu::init_nvml)
+            .flatten();
         Self {
             config,
             root_cgroup,
+            #[cfg(feature = "sources-host_metrics-gpu")]
+            nvml,
             events_received: register!(EventsReceived),
         }
     }
@@ -360,6 +407,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Filesystem) {
             self.filesystem_metrics(&mut buffer).await;
         }
+        #[cfg(feature = "sources-host_metrics-gpu")]
+        if self.config.has_collector(Collector::Gpu) {
+            self.gpu_metrics(&mut buffer).await;
+        }
         if self.config.has_collector(Collector::Load) {
             self.loadavg_metrics(&mut buffer).await;
         }
@@ -373,6 +424,9 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        if self.config.has_collector(Collector::Process) {
+            self.process_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         self.events_received.emit(CountByteSize(
@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+
+use futures::StreamExt;
+#[cfg(target_os = "linux")]
+use heim::process::os::linux::ProcessExt;
+use heim::{
+    process::{Pid, Process},
+    units::{information::byte, time::second},
+};
+use vector_lib::configurable::configurable_component;
+use vector_lib::{event::MetricTags, metric_tags};
+
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+use super::{default_all_devices, FilterList, HostMetrics, MetricsBuffer};
+
+/// Options for the process metrics collector.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct ProcessConfig {
+    /// Lists of process name patterns to include or exclude in gathering
+    /// process metrics.
+    #[derivative(Default(value = "default_all_devices()"))]
+    #[serde(default = "default_all_devices")]
+    #[configurable(metadata(docs::examples = "example_names()"))]
+    names: FilterList,
+
+    /// Lists of command line patterns to include or exclude in gathering
+    /// process metrics.
+    ///
+    /// The command line of a process is its arguments separated by spaces.
+    #[derivative(Default(value = "default_all_devices()"))]
+    #[serde(default = "default_all_devices")]
+    #[configurable(metadata(docs::examples = "example_cmdlines()"))]
+    cmdlines: FilterList,
+
+    /// The maximum number of processes to report metrics for.
+    ///
+    /// If more processes match the filters, the ones that used the most CPU time are reported.
+    #[derivative(Default(value = "default_max_processes()"))]
+    #[serde(default = "default_max_processes")]
+    max_processes: usize,
+
+    /// Whether to aggregate the metrics of the processes with the same name.
+    ///
+    /// If `true`, the metrics are not tagged with the `pid` of the processes, which keeps their
+    /// cardinality bounded when processes are restarted, and the number of processes is reported
+    /// as `process_count`.
+    ///
+    /// The CPU time and I/O totals of a group drop when one of its processes exits, so they are
+    /// reported as gauges rather than counters.
+    #[serde(default)]
+    group_by_name: bool,
+}
+
+const fn default_max_processes() -> usize {
+    100
+}
+
+fn example_names() -> FilterList {
+    FilterList {
+        includes: Some(vec!["nginx".try_into().unwrap()]),
+        excludes: None,
+    }
+}
+
+fn example_cmdlines() -> FilterList {
+    FilterList {
+        includes: Some(vec!["*--config /etc/app/*".try_into().unwrap()]),
+        excludes: None,
+    }
+}
+
+/// The usage of a process, or of a group of processes with the same name.
+#[derive(Debug, Default)]
+struct ProcessUsage {
+    name: String,
+    pid: Option<Pid>,
+    count: usize,
+    cpu_user_seconds: f64,
+    cpu_system_seconds: f64,
+    rss_bytes: f64,
+    virtual_bytes: f64,
+    open_fds: Option<f64>,
+    read_bytes: Option<f64>,
+    written_bytes: Option<f64>,
+}
+
+impl ProcessUsage {
+    fn cpu_seconds(&self) -> f64 {
+        self.cpu_user_seconds + self.cpu_system_seconds
+    }
+
+    fn merge(&mut self, other: Self) {
+        fn add(total: &mut Option<f64>, value: Option<f64>) {
+            if let Some(value) = value {
+                *total = Some(total.unwrap_or_default() + value);
+            }
+        }
+
+        self.count += other.count;
+        self.cpu_user_seconds += other.cpu_user_seconds;
+        self.cpu_system_seconds += other.cpu_system_seconds;
+        self.rss_bytes += other.rss_bytes;
+        self.virtual_bytes += other.virtual_bytes;
+        add(&mut self.open_fds, other.open_fds);
+        add(&mut self.read_bytes, other.read_bytes);
+        add(&mut self.written_bytes, other.written_bytes);
+    }
+}
+
+impl HostMetrics {
+    pub async fn process_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "process";
+        let config = &self.config.process;
+        let processes = match heim::process::processes().await {
+            Ok(processes) => processes,
+            Err(error) => {
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to load processes info.",
+                    error,
+                });
+                return;
+            }
+        };
+
+        // Processes can exit, or deny access to their details, while they are listed, so
+        // they are skipped rather than reported as errors.
+        let usages = processes
+            .filter_map(|result| async { result.ok() })
+            .filter_map(|process| process_usage(config, process))
+            .collect::<Vec<_>>()
+            .await;
+
+        for usage in limit_usages(config, usages) {
+            let mut tags = metric_tags!("process_name" => usage.name.as_str());
+            // The totals of a group of processes go backwards when one of them exits, which
+            // counters must not do.
+            let total: fn(&mut MetricsBuffer, &str, f64, MetricTags) = match usage.pid {
+                Some(pid) => {
+                    tags.replace("pid".into(), pid.to_string());
+                    MetricsBuffer::counter
+                }
+                None => {
+                    output.gauge("process_count", usage.count as f64, tags.clone());
+                    MetricsBuffer::gauge
+                }
+            };
+
+            let mut user_tags = tags.clone();
+            user_tags.replace("mode".into(), "user".to_string());
+            total(
+                output,
+                "process_cpu_seconds_total",
+                usage.cpu_user_seconds,
+                user_tags,
+            );
+            let mut system_tags = tags.clone();
+            system_tags.replace("mode".into(), "system".to_string());
+            total(
+                output,
+                "process_cpu_seconds_total",
+                usage.cpu_system_seconds,
+                system_tags,
+            );
+            output.gauge("process_memory_rss_bytes", usage.rss_bytes, tags.clone());
+            output.gauge(
+                "process_memory_virtual_bytes",
+                usage.virtual_bytes,
+                tags.clone(),
+            );
+            if let Some(open_fds) = usage.open_fds {
+                output.gauge("process_open_fds", open_fds, tags.clone());
+            }
+            if let Some(read_bytes) = usage.read_bytes {
+                total(
+                    output,
+                    "process_io_read_bytes_total",
+                    read_bytes,
+                    tags.clone(),
+                );
+            }
+            if let Some(written_bytes) = usage.written_bytes {
+                total(
+                    output,
+                    "process_io_written_bytes_total",
+                    written_bytes,
+                    tags,
+                );
+            }
+        }
+    }
+}
+
+/// Loads the usage of a process, if it matches the filters.
+async fn process_usage(config: &ProcessConfig, process: Process) -> Option<ProcessUsage> {
+    let name = process.name().await.ok()?;
+    if !config.names.contains_str(Some(&name)) {
+        return None;
+    }
+    let command = process.command().await.ok()?;
+    if !config
+        .cmdlines
+        .contains_str(Some(&command.to_os_string().to_string_lossy()))
+    {
+        return None;
+    }
+
+    let cpu_time = process.cpu_time().await.ok()?;
+    let memory = process.memory().await.ok()?;
+    #[cfg(target_os = "linux")]
+    let (open_fds, read_bytes, written_bytes) = {
+        let io_counters = process.io_counters().await.ok();
+        (
+            open_fds(process.pid()).await,
+            io_counters
+                .as_ref()
+                .map(|counters| counters.bytes_read().get::<byte>() as f64),
+            io_counters
+                .as_ref()
+                .map(|counters| counters.bytes_written().get::<byte>() as f64),
+        )
+    };
+    #[cfg(not(target_os = "linux"))]
+    let (open_fds, read_bytes, written_bytes) = (None, None, None);
+
+    Some(ProcessUsage {
+        name,
+        pid: Some(process.pid()),
+        count: 1,
+        cpu_user_seconds: cpu_time.user().get::<second>(),
+        cpu_system_seconds: cpu_time.system().get::<second>(),
+        rss_bytes: memory.rss().get::<byte>() as f64,
+        virtual_bytes: memory.vms().get::<byte>() as f64,
+        open_fds,
+        read_bytes,
+        written_bytes,
+    })
+}
+
+/// Counts the open file descriptors of a process.
+#[cfg(target_os = "linux")]
+async fn open_fds(pid: Pid) -> Option<f64> {
+    let procfs_root = std::env::var_os("PROCFS_ROOT").unwrap_or_else(|| "/proc".into());
+    let path = std::path::Path::new(&procfs_root)
+        .join(pid.to_string())
+        .join("fd");
+    let mut entries = tokio::fs::read_dir(path).await.ok()?;
+    let mut count = 0;
+    while let Ok(Some(_)) = entries.next_entry().await {
+        count += 1;
+    }
+    Some(count as f64)
+}
+
+/// Groups the usages by name if configured, and keeps the ones that used the most CPU time.
+fn limit_usages(config: &ProcessConfig, usages: Vec<ProcessUsage>) -> Vec<ProcessUsage> {
+    let mut usages = if config.group_by_name {
+        let mut groups = HashMap::<String, ProcessUsage>::new();
+        for usage in usages {
+            match groups.get_mut(&usage.name) {
+                Some(group) => group.merge(usage),
+                None => {
+                    let name = usage.name.clone();
+                    groups.insert(name, ProcessUsage { pid: None, ..usage });
+                }
+            }
+        }
+        groups.into_values().collect()
+    } else {
+        usages
+    };
+
+    if usages.len() > config.max_processes {
+        debug!(
+            message = "Reporting the metrics of the processes that used the most CPU time only.",
+            matched = usages.len(),
+            max_processes = config.max_processes,
+            internal_log_rate_limit = true,
+        );
+        usages.sort_by(|a, b| b.cpu_seconds().total_cmp(&a.cpu_seconds()));
+        usages.truncate(config.max_processes);
+    }
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            tests::{count_name, count_tag},
+            HostMetricsConfig,
+        },
+        *,
+    };
+    use crate::event::MetricValue;
+
+    fn usage(name: &str, pid: Pid, cpu_user_seconds: f64) -> ProcessUsage {
+        ProcessUsage {
+            name: name.to_owned(),
+            pid: Some(pid),
+            count: 1,
+            cpu_user_seconds,
+            rss_bytes: 1024.0,
+            open_fds: Some(4.0),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig::default())
+            .process_metrics(&mut buffer)
+            .await;
+        let metrics = buffer.metrics;
+        assert!(!metrics.is_empty());
+
+        // All metrics are named process_*
+        assert!(!metrics
+            .iter()
+            .any(|metric| !metric.name().starts_with("process_")));
+
+        // They should all have "process_name" and "pid" tags
+        assert_eq!(count_tag(&metrics, "process_name"), metrics.len());
+        assert_eq!(count_tag(&metrics, "pid"), metrics.len());
+        assert_eq!(count_name(&metrics, "process_count"), 0);
+    }
+
+    #[tokio::test]
+    async fn reports_grouped_totals_as_gauges() {
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(HostMetricsConfig {
+            process: ProcessConfig {
+                group_by_name: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .process_metrics(&mut buffer)
+        .await;
+        let metrics = buffer.metrics;
+        assert!(count_name(&metrics, "process_cpu_seconds_total") > 0);
+        assert_eq!(count_tag(&metrics, "pid"), 0);
+
+        // The totals of a group go backwards when one of its processes exits.
+        assert!(metrics
+            .iter()
+            .filter(|metric| metric.name().ends_with("_total"))
+            .all(|metric| matches!(metric.value(), MetricValue::Gauge { .. })));
+    }
+
+    #[test]
+    fn limits_to_the_busiest_processes() {
+        let config = ProcessConfig {
+            max_processes: 2,
+            ..Default::default()
+        };
+        let usages = limit_usages(
+            &config,
+            vec![usage("a", 1, 1.0), usage("b", 2, 3.0), usage("c", 3, 2.0)],
+        );
+        let pids = usages.iter().map(|usage| usage.pid).collect::<Vec<_>>();
+        assert_eq!(pids, vec![Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn groups_processes_by_name() {
+        let config = ProcessConfig {
+            group_by_name: true,
+            ..Default::default()
+        };
+        let mut usages = limit_usages(
+            &config,
+            vec![usage("a", 1, 1.0), usage("a", 2, 3.0), usage("b", 3, 2.0)],
+        );
+        usages.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].pid, None);
+        assert_eq!(usages[0].count, 2);
+        assert_eq!(usages[0].cpu_user_seconds, 4.0);
+        assert_eq!(usages[0].rss_bytes, 2048.0);
+        assert_eq!(usages[0].open_fds, Some(8.0));
+        assert_eq!(usages[0].read_bytes, None);
+        assert_eq!(usages[1].count, 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -30,7 +30,7 @@ use crate::{
     http::KeepaliveConfig,
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        http::{add_query_parameters, HttpMethod},
+        http::{add_query_parameters, run_routes, HttpMethod, HttpRoute},
         Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
@@ -174,14 +174,54 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     #[serde(default)]
     keepalive: KeepaliveConfig,
+
+    /// A list of routes to serve on the same address, each with its own path, method, decoding
+    /// and output.
+    ///
+    /// When routes are set, requests are only accepted on the routes, and not on `path`. The
+    /// `method`, `framing`, and `decoding` options are used for the routes that don't set them.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    routes: Vec<RouteConfig>,
+}
+
+/// A route served by the `http_server` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RouteConfig {
+    /// The URL path on which requests for this route are sent.
+    #[configurable(metadata(docs::examples = "/logs"))]
+    #[configurable(metadata(docs::examples = "/metrics"))]
+    path: String,
+
+    /// The HTTP method of requests for this route.
+    ///
+    /// The `method` of the source is used if this is not set.
+    method: Option<HttpMethod>,
+
+    /// The name of the output that the events received on this route are sent to.
+    ///
+    /// Events are sent to the default output of the source if this is not set. Otherwise, they can
+    /// be consumed by using `<source_id>.<output>` as an input.
+    #[configurable(metadata(docs::examples = "logs"))]
+    output: Option<String>,
+
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    decoding: Option<DeserializerConfig>,
 }
 
 impl SimpleHttpConfig {
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
-    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
-        let mut schema_definition = self
-            .decoding
-            .as_ref()
+    fn schema_definition(
+        &self,
+        decoding: Option<&DeserializerConfig>,
+        log_namespace: LogNamespace,
+    ) -> Definition {
+        let mut schema_definition = decoding
             .unwrap_or(&default_decoding())
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -224,6 +264,17 @@ impl SimpleHttpConfig {
         schema_definition
     }
 
+    fn output(
+        &self,
+        decoding: Option<&DeserializerConfig>,
+        log_namespace: LogNamespace,
+    ) -> SourceOutput {
+        SourceOutput::new_maybe_logs(
+            decoding.map(|d| d.output_type()).unwrap_or(DataType::Log),
+            self.schema_definition(decoding, log_namespace),
+        )
+    }
+
     fn get_decoding_config(&self) -> crate::Result<DecodingConfig> {
         if self.encoding.is_some() && (self.framing.is_some() || self.decoding.is_some()) {
             return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
@@ -263,6 +314,34 @@ impl SimpleHttpConfig {
             self.log_namespace.unwrap_or(false).into(),
         ))
     }
+
+    /// The decoding of a route, which falls back to the decoding of the source.
+    fn get_route_decoding_config(&self, route: &RouteConfig) -> crate::Result<DecodingConfig> {
+        if route.framing.is_none() && route.decoding.is_none() {
+            return self.get_decoding_config();
+        }
+
+        let decoding = route
+            .decoding
+            .clone()
+            .or_else(|| self.decoding.clone())
+            .unwrap_or_else(default_decoding);
+        let framing = route
+            .framing
+            .clone()
+            .or_else(|| self.framing.clone())
+            .unwrap_or_else(|| decoding.default_stream_framing());
+
+        Ok(DecodingConfig::new(
+            framing,
+            decoding,
+            self.log_namespace.unwrap_or(false).into(),
+        ))
+    }
+
+    fn route_decoding<'a>(&'a self, route: &'a RouteConfig) -> Option<&'a DeserializerConfig> {
+        route.decoding.as_ref().or(self.decoding.as_ref())
+    }
 }
 
 impl Default for SimpleHttpConfig {
@@ -285,6 +364,7 @@ impl Default for SimpleHttpConfig {
             acknowledgements: SourceAcknowledgementsConfig::default(),
             log_namespace: None,
             keepalive: KeepaliveConfig::default(),
+            routes: Vec::new(),
         }
     }
 }
@@ -370,10 +450,41 @@ impl SourceConfig for SimpleHttpConfig {
             decoder,
             log_namespace,
         };
-        source.run(
+
+        if self.routes.is_empty() {
+            return source.run(
+                self.address,
+                self.path.as_str(),
+                self.method,
+                self.response_code,
+                self.strict_path,
+                &self.tls,
+                &self.auth,
+                cx,
+                self.acknowledgements,
+                self.keepalive.clone(),
+            );
+        }
+
+        let routes = self
+            .routes
+            .iter()
+            .map(|route| {
+                Ok(HttpRoute {
+                    path: route.path.clone(),
+                    method: route.method.unwrap_or(self.method),
+                    output: route.output.clone(),
+                    source: SimpleHttpSource {
+                        decoder: self.get_route_decoding_config(route)?.build()?,
+                        ..source.clone()
+                    },
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        run_routes(
+            routes,
             self.address,
-            self.path.as_str(),
-            self.method,
             self.response_code,
             self.strict_path,
             &self.tls,
@@ -389,15 +500,34 @@ impl SourceConfig for SimpleHttpConfig {
         // The source config overrides the global setting and is merged here.
         let log_namespace = global_log_namespace.merge(self.log_namespace);
 
-        let schema_definition = self.schema_definition(log_namespace);
+        if self.routes.is_empty() {
+            return vec![self.output(self.decoding.as_ref(), log_namespace)];
+        }
 
-        vec![SourceOutput::new_maybe_logs(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-            schema_definition,
-        )]
+        // Routes that share an output share its schema.
+        let mut outputs: Vec<SourceOutput> = Vec::new();
+        for route in &self.routes {
+            let output = self.output(self.route_decoding(route), log_namespace);
+            let output = match &route.output {
+                Some(name) => output.with_port(name.clone()),
+                None => output,
+            };
+            match outputs.iter_mut().find(|o| o.port == output.port) {
+                Some(existing) => {
+                    existing.ty |= output.ty;
+                    existing.schema_definition =
+                        match (existing.schema_definition.take(), output.schema_definition) {
+                            (Some(existing), Some(definition)) => Some(Arc::new(
+                                Arc::unwrap_or_clone(existing)
+                                    .merge(Arc::unwrap_or_clone(definition)),
+                            )),
+                            (existing, definition) => existing.or(definition),
+                        };
+                }
+                None => outputs.push(output),
+            }
+        }
+        outputs
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -571,7 +701,7 @@ mod tests {
         decoding::{DeserializerConfig, FramingConfig},
         BytesDecoderConfig, JsonDeserializerConfig,
     };
-    use vector_lib::config::LogNamespace;
+    use vector_lib::config::{DataType, LogNamespace};
     use vector_lib::event::LogEvent;
     use vector_lib::lookup::lookup_v2::OptionalValuePath;
     use vector_lib::lookup::{event_path, owned_value_path, OwnedTargetPath, PathPrefix};
@@ -643,6 +773,7 @@ mod tests {
                 acknowledgements: acknowledgements.into(),
                 log_namespace: None,
                 keepalive: Default::default(),
+                routes: Vec::new(),
             }
             .build(context)
             .await
@@ -1319,6 +1450,91 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_routes() {
+        let (sender, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let config: SimpleHttpConfig = toml::from_str(&format!(
+            r#"
+            address = "{address}"
+
+            [[routes]]
+            path = "/logs"
+
+            [[routes]]
+            path = "/json"
+            decoding.codec = "json"
+            "#
+        ))
+        .unwrap();
+        tokio::spawn(async move {
+            config
+                .build(SourceContext::new_test(sender, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        assert_eq!(404, send_with_path(address, "test body", "/").await);
+        let mut events = spawn_collect_n(
+            async move {
+                assert_eq!(200, send_with_path(address, "test body", "/logs").await);
+                assert_eq!(
+                    200,
+                    send_with_path(address, "{\"key1\":\"value1\"}", "/json").await
+                );
+            },
+            rx,
+            2,
+        )
+        .await;
+
+        let log = events.remove(0).into_log();
+        assert_eq!(log["message"], "test body".into());
+        assert_eq!(log["path"], "/logs".into());
+        let log = events.remove(0).into_log();
+        assert_eq!(log["key1"], "value1".into());
+        assert_eq!(log["path"], "/json".into());
+    }
+
+    #[test]
+    fn routes_outputs() {
+        let config: SimpleHttpConfig = toml::from_str(
+            r#"
+            address = "0.0.0.0:8080"
+
+            [[routes]]
+            path = "/logs"
+            output = "logs"
+
+            [[routes]]
+            path = "/v1/logs"
+            output = "logs"
+
+            [[routes]]
+            path = "/metrics"
+            output = "metrics"
+            decoding.codec = "native_json"
+            "#,
+        )
+        .unwrap();
+
+        let outputs = config.outputs(LogNamespace::Legacy);
+        let ports = outputs
+            .iter()
+            .map(|output| (output.port.as_deref(), output.ty))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ports,
+            vec![
+                (Some("logs"), DataType::Log),
+                (Some("metrics"), DataType::all_bits())
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn http_path_no_restriction() {
         let mut events = assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
@@ -1,5 +1,9 @@
+use std::{str::FromStr, sync::Arc};
+
 use chrono::Utc;
 use futures::{stream, StreamExt};
+use glob::Pattern;
+use tracing::{level_filters::LevelFilter, Level};
 use vector_lib::codecs::BytesDeserializerConfig;
 use vector_lib::config::log_schema;
 use vector_lib::configurable::configurable_component;
@@ -13,10 +17,10 @@ use vrl::value::Kind;
 
 use crate::{
     config::{DataType, SourceConfig, SourceContext, SourceOutput},
-    event::{EstimatedJsonEncodedSizeOf, Event},
+    event::{EstimatedJsonEncodedSizeOf, Event, LogEvent},
     internal_events::{InternalLogsBytesReceived, InternalLogsEventsReceived, StreamClosedError},
     shutdown::ShutdownSignal,
-    trace::TraceSubscription,
+    trace::{self, ElevationGuard, TraceSubscription},
     SourceSender,
 };
 
@@ -45,6 +49,16 @@ pub struct InternalLogsConfig {
     #[serde(default = "default_pid_key")]
     pid_key: OptionalValuePath,
 
+    /// Overrides of the severity of the internal log events captured from some components or
+    /// modules.
+    ///
+    /// The first override that matches an event decides whether it is captured: events of a
+    /// matching component or module are captured if they are at least as severe as the level of
+    /// the override, even if they are more verbose than the log level of Vector. Events that no
+    /// override matches are captured as per the log level of Vector.
+    #[serde(default)]
+    severity_overrides: Vec<SeverityOverride>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -55,6 +69,161 @@ fn default_pid_key() -> OptionalValuePath {
     OptionalValuePath::from(owned_value_path!("pid"))
 }
 
+/// An override of the severity of the internal log events captured from some components or
+/// modules.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SeverityOverride {
+    /// A pattern matching the IDs of the components whose events are overridden.
+    ///
+    /// Wildcards (`*`) are supported. If not set, events are matched regardless of their
+    /// component.
+    #[configurable(metadata(docs::examples = "azure_blob"))]
+    #[configurable(metadata(docs::examples = "kafka_*"))]
+    component_id: Option<String>,
+
+    /// A pattern matching the module paths whose events are overridden.
+    ///
+    /// The events of submodules are matched as well, and module paths are matched with or without
+    /// the name of their crate, so `sinks` matches the events of `vector::sinks::http`. Wildcards
+    /// (`*`) are supported. If not set, events are matched regardless of their module.
+    #[configurable(metadata(docs::examples = "sinks"))]
+    #[configurable(metadata(docs::examples = "sources::azure_blob"))]
+    module_path: Option<String>,
+
+    /// The least severe level at which the matching events are captured.
+    level: Severity,
+}
+
+/// The severity of internal log events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The `TRACE` level.
+    Trace,
+
+    /// The `DEBUG` level.
+    Debug,
+
+    /// The `INFO` level.
+    Info,
+
+    /// The `WARN` level.
+    Warn,
+
+    /// The `ERROR` level.
+    Error,
+}
+
+impl From<Severity> for Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Trace => Level::TRACE,
+            Severity::Debug => Level::DEBUG,
+            Severity::Info => Level::INFO,
+            Severity::Warn => Level::WARN,
+            Severity::Error => Level::ERROR,
+        }
+    }
+}
+
+/// The severity overrides of a source, with their patterns compiled.
+struct SeverityOverrides(Vec<CompiledOverride>);
+
+struct CompiledOverride {
+    component_id: Option<Pattern>,
+    module_path: Option<Pattern>,
+    level: Level,
+}
+
+impl SeverityOverrides {
+    fn new(overrides: &[SeverityOverride]) -> crate::Result<Self> {
+        let compile = |pattern: &Option<String>| pattern.as_deref().map(Pattern::new).transpose();
+        overrides
+            .iter()
+            .map(|severity_override| {
+                Ok(CompiledOverride {
+                    component_id: compile(&severity_override.component_id)?,
+                    module_path: compile(&severity_override.module_path)?,
+                    level: severity_override.level.into(),
+                })
+            })
+            .collect::<crate::Result<_>>()
+            .map(Self)
+    }
+
+    /// Returns the level of the first override matching the component and module of an event.
+    fn level(&self, component_id: Option<&str>, module_path: Option<&str>) -> Option<Level> {
+        self.0
+            .iter()
+            .find(|severity_override| {
+                matches(
+                    &severity_override.component_id,
+                    component_id,
+                    Pattern::matches,
+                ) && matches(&severity_override.module_path, module_path, matches_module)
+            })
+            .map(|severity_override| severity_override.level)
+    }
+
+    /// The most verbose level of the overrides.
+    fn max_level(&self) -> LevelFilter {
+        self.0
+            .iter()
+            .map(|severity_override| LevelFilter::from_level(severity_override.level))
+            .max()
+            .unwrap_or(LevelFilter::OFF)
+    }
+
+    /// Returns whether an internal log event is captured.
+    fn captures(&self, log: &LogEvent) -> bool {
+        let field = |path: &str| log.get(path).map(|value| value.to_string_lossy());
+        let level = field("metadata.level").and_then(|level| Level::from_str(&level).ok());
+        let Some(level) = level else {
+            return true;
+        };
+
+        let component_id = field("vector.component_id");
+        let module_path = field("metadata.module_path");
+        match self.level(component_id.as_deref(), module_path.as_deref()) {
+            Some(max_level) => level <= max_level,
+            // The event may only be broadcast because of the overrides of another source.
+            None => trace::is_enabled_by_log_levels(
+                field("metadata.target").as_deref().unwrap_or_default(),
+                &level,
+            ),
+        }
+    }
+}
+
+fn matches(
+    pattern: &Option<Pattern>,
+    value: Option<&str>,
+    matcher: fn(&Pattern, &str) -> bool,
+) -> bool {
+    match (pattern, value) {
+        (None, _) => true,
+        (Some(pattern), Some(value)) => matcher(pattern, value),
+        (Some(_), None) => false,
+    }
+}
+
+/// Matches a module path, or one of its parent modules, with or without the name of its crate.
+fn matches_module(pattern: &Pattern, module_path: &str) -> bool {
+    let without_crate = module_path.split_once("::").map(|(_, path)| path);
+    [Some(module_path), without_crate]
+        .into_iter()
+        .flatten()
+        .any(|path| {
+            path.match_indices("::")
+                .map(|(index, _)| &path[..index])
+                .chain([path])
+                .any(|module| pattern.matches(module))
+        })
+}
+
 impl_generate_config_from_default!(InternalLogsConfig);
 
 impl Default for InternalLogsConfig {
@@ -62,6 +231,7 @@ impl Default for InternalLogsConfig {
         InternalLogsConfig {
             host_key: None,
             pid_key: default_pid_key(),
+            severity_overrides: Vec::new(),
             log_namespace: None,
         }
     }
@@ -111,6 +281,23 @@ impl SourceConfig for InternalLogsConfig {
             .path;
         let pid_key = self.pid_key.clone().path;
 
+        let overrides = Arc::new(SeverityOverrides::new(&self.severity_overrides)?);
+        // The events of the overridden components and modules must be broadcast even if they are
+        // more verbose than the log level of Vector.
+        let elevation = (!overrides.0.is_empty()).then(|| {
+            let overrides = Arc::clone(&overrides);
+            trace::elevate(
+                overrides.max_level(),
+                Arc::new(
+                    move |meta: &tracing::Metadata<'_>, component_id: Option<&str>| {
+                        overrides
+                            .level(component_id, meta.module_path())
+                            .is_some_and(|level| *meta.level() <= level)
+                    },
+                ),
+            )
+        });
+
         let subscription = TraceSubscription::subscribe();
 
         let log_namespace = cx.log_namespace(self.log_namespace);
@@ -118,6 +305,8 @@ impl SourceConfig for InternalLogsConfig {
         Ok(Box::pin(run(
             host_key,
             pid_key,
+            overrides,
+            elevation,
             subscription,
             cx.out,
             cx.shutdown,
@@ -143,6 +332,9 @@ impl SourceConfig for InternalLogsConfig {
 async fn run(
     host_key: Option<OwnedValuePath>,
     pid_key: Option<OwnedValuePath>,
+    overrides: Arc<SeverityOverrides>,
+    // The elevation is removed once the source stops.
+    _elevation: Option<ElevationGuard>,
     mut subscription: TraceSubscription,
     mut out: SourceSender,
     shutdown: ShutdownSignal,
@@ -162,6 +354,10 @@ async fn run(
     // any logs that don't break the loop, as that could cause an
     // infinite loop since it receives all such logs.
     while let Some(mut log) = rx.next().await {
+        if !overrides.captures(&log) {
+            continue;
+        }
+
         // TODO: Should this actually be in memory size?
         let byte_size = log.estimated_json_encoded_size_of().get();
         let json_byte_size = log.estimated_json_encoded_size_of();
@@ -344,6 +540,49 @@ mod tests {
         rx
     }
 
+    #[test]
+    fn severity_overrides_match_components_and_modules() {
+        let overrides: Vec<SeverityOverride> = serde_json::from_value(serde_json::json!([
+            {"module_path": "sinks", "level": "warn"},
+            {"component_id": "azure_*", "module_path": "sources::azure_blob", "level": "debug"},
+        ]))
+        .unwrap();
+        let overrides = SeverityOverrides::new(&overrides).unwrap();
+
+        assert_eq!(overrides.max_level(), LevelFilter::DEBUG);
+        assert_eq!(
+            overrides.level(Some("out"), Some("vector::sinks::http::sink")),
+            Some(Level::WARN)
+        );
+        assert_eq!(
+            overrides.level(Some("out"), Some("vector::sinks_extra")),
+            None
+        );
+        assert_eq!(
+            overrides.level(Some("azure_in"), Some("vector::sources::azure_blob::queue")),
+            Some(Level::DEBUG)
+        );
+        assert_eq!(
+            overrides.level(Some("other"), Some("vector::sources::azure_blob")),
+            None
+        );
+        assert_eq!(overrides.level(None, None), None);
+
+        let mut log = LogEvent::from("message");
+        log.insert("metadata.level", "INFO");
+        log.insert("metadata.module_path", "vector::sinks::http");
+        assert!(!overrides.captures(&log));
+        log.insert("metadata.level", "ERROR");
+        assert!(overrides.captures(&log));
+
+        log.insert("metadata.level", "DEBUG");
+        log.insert("metadata.module_path", "vector::sources::azure_blob");
+        log.insert("vector.component_id", "azure_in");
+        assert!(overrides.captures(&log));
+        log.insert("metadata.level", "TRACE");
+        assert!(!overrides.captures(&log));
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = InternalLogsConfig::default();
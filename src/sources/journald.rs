@@ -64,10 +64,23 @@ const CHECKPOINT_FILENAME: &str = "checkpoint.txt";
 const CURSOR: &str = "__CURSOR";
 const HOSTNAME: &str = "_HOSTNAME";
 const MESSAGE: &str = "MESSAGE";
+const PRIORITY: &str = "PRIORITY";
 const SYSTEMD_UNIT: &str = "_SYSTEMD_UNIT";
 const SOURCE_TIMESTAMP: &str = "_SOURCE_REALTIME_TIMESTAMP";
 const RECEIVED_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
 
+/// The trusted fields, added by journald itself, that hold integers.
+///
+/// See <https://www.freedesktop.org/software/systemd/man/latest/systemd.journal-fields.html#Trusted%20Journal%20Fields>.
+const TRUSTED_INTEGER_FIELDS: [&str; 6] = [
+    "_PID",
+    "_UID",
+    "_GID",
+    "_AUDIT_SESSION",
+    "_AUDIT_LOGINUID",
+    "_SYSTEMD_OWNER_UID",
+];
+
 const BACKOFF_DURATION: Duration = Duration::from_secs(1);
 
 static JOURNALCTL: LazyLock<PathBuf> = LazyLock::new(|| "journalctl".into());
@@ -104,6 +117,15 @@ pub struct JournaldConfig {
     #[serde(default = "crate::serde::default_true")]
     pub current_boot_only: bool,
 
+    /// Resume from the checkpoint even if it is from a previous boot of the system.
+    ///
+    /// By default, with `current_boot_only`, the entries that are appended to the journal between
+    /// the checkpoint and a reboot are never read. When enabled, the source resumes from the
+    /// checkpoint, replaying the rest of the previous boots before following the current boot, and
+    /// `current_boot_only` only applies when there is no checkpoint yet.
+    #[serde(default)]
+    pub resume_across_boots: bool,
+
     /// A list of unit names to monitor.
     ///
     /// If empty or not present, all units are accepted.
@@ -144,6 +166,32 @@ pub struct JournaldConfig {
     #[configurable(metadata(docs::examples = "matches_examples()"))]
     pub exclude_matches: Matches,
 
+    /// A list of journal fields to keep in events.
+    ///
+    /// If empty or not present, all fields are kept. `MESSAGE`, `_HOSTNAME`, and the timestamp
+    /// fields are always kept, as they are used for the message, host, and timestamp of events.
+    /// `__CURSOR` and `PRIORITY` are kept as well, even if they are excluded.
+    ///
+    /// Entries are matched against `include_matches` and `exclude_matches` before their fields
+    /// are selected.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "_SYSTEMD_UNIT", docs::examples = "_PID"))]
+    pub include_fields: Vec<String>,
+
+    /// A list of journal fields to remove from events.
+    ///
+    /// `__CURSOR` and `PRIORITY` are never removed.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "_CMDLINE", docs::examples = "_CAP_EFFECTIVE"))]
+    pub exclude_fields: Vec<String>,
+
+    /// Decode the trusted fields that hold integers, such as `_PID`, `_UID`, and `_GID`, into
+    /// integers rather than strings.
+    ///
+    /// Other fields, such as `_SYSTEMD_UNIT`, are kept as strings.
+    #[serde(default)]
+    pub decode_trusted_fields: bool,
+
     /// The directory used to persist file checkpoint positions.
     ///
     /// By default, the [global `data_dir` option][global_data_dir] is used.
@@ -253,6 +301,13 @@ impl JournaldConfig {
 
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
     fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        // for metadata that is added to the events dynamically through the Record
+        let field_kind = if self.decode_trusted_fields {
+            Kind::bytes().or_integer()
+        } else {
+            Kind::bytes()
+        };
+
         let schema_definition = match log_namespace {
             LogNamespace::Vector => Definition::new_with_default_metadata(
                 Kind::bytes().or_null(),
@@ -266,12 +321,11 @@ impl JournaldConfig {
 
         let mut schema_definition = schema_definition
             .with_standard_vector_source_metadata()
-            // for metadata that is added to the events dynamically through the Record
             .with_source_metadata(
                 JournaldConfig::NAME,
                 None,
                 &owned_value_path!("metadata"),
-                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                Kind::object(Collection::empty().with_unknown(field_kind.clone())).or_undefined(),
                 None,
             )
             .with_source_metadata(
@@ -289,9 +343,8 @@ impl JournaldConfig {
                 Some("host"),
             );
 
-        // for metadata that is added to the events dynamically through the Record
         if log_namespace == LogNamespace::Legacy {
-            schema_definition = schema_definition.unknown_fields(Kind::bytes());
+            schema_definition = schema_definition.unknown_fields(field_kind);
         }
 
         schema_definition
@@ -303,10 +356,14 @@ impl Default for JournaldConfig {
         Self {
             since_now: false,
             current_boot_only: true,
+            resume_across_boots: false,
             include_units: vec![],
             exclude_units: vec![],
             include_matches: Default::default(),
             exclude_matches: Default::default(),
+            include_fields: vec![],
+            exclude_fields: vec![],
+            decode_trusted_fields: false,
             data_dir: None,
             batch_size: default_batch_size(),
             journalctl_path: None,
@@ -367,6 +424,7 @@ impl SourceConfig for JournaldConfig {
             self.journal_directory.clone(),
             self.journal_namespace.clone(),
             self.current_boot_only,
+            self.resume_across_boots,
             self.since_now,
             self.extra_args.clone(),
         );
@@ -379,6 +437,9 @@ impl SourceConfig for JournaldConfig {
             JournaldSource {
                 include_matches,
                 exclude_matches,
+                include_fields: self.include_fields.iter().cloned().collect(),
+                exclude_fields: self.exclude_fields.iter().cloned().collect(),
+                decode_trusted_fields: self.decode_trusted_fields,
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
@@ -410,6 +471,9 @@ impl SourceConfig for JournaldConfig {
 struct JournaldSource {
     include_matches: Matches,
     exclude_matches: Matches,
+    include_fields: HashSet<String>,
+    exclude_fields: HashSet<String>,
+    decode_trusted_fields: bool,
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
@@ -583,10 +647,17 @@ impl<'a> Batch<'a> {
                         ) {
                             self.record_size += bytes.len();
 
+                            select_fields(
+                                &mut record,
+                                &self.source.include_fields,
+                                &self.source.exclude_fields,
+                            );
+
                             let mut event = create_log_event_from_record(
                                 record,
                                 &self.batch,
                                 self.source.log_namespace,
+                                self.source.decode_trusted_fields,
                             );
 
                             enrich_log_event(&mut event, self.source.log_namespace);
@@ -647,6 +718,7 @@ struct StartJournalctl {
     journal_dir: Option<PathBuf>,
     journal_namespace: Option<String>,
     current_boot_only: bool,
+    resume_across_boots: bool,
     since_now: bool,
     extra_args: Vec<String>,
 }
@@ -657,6 +729,7 @@ impl StartJournalctl {
         journal_dir: Option<PathBuf>,
         journal_namespace: Option<String>,
         current_boot_only: bool,
+        resume_across_boots: bool,
         since_now: bool,
         extra_args: Vec<String>,
     ) -> Self {
@@ -665,6 +738,7 @@ impl StartJournalctl {
             journal_dir,
             journal_namespace,
             current_boot_only,
+            resume_across_boots,
             since_now,
             extra_args,
         }
@@ -686,7 +760,8 @@ impl StartJournalctl {
             command.arg(format!("--namespace={}", namespace));
         }
 
-        if self.current_boot_only {
+        // A checkpoint from a previous boot is resumed from when replaying across boots.
+        if self.current_boot_only && !(self.resume_across_boots && checkpoint.is_some()) {
             command.arg("--boot");
         }
 
@@ -809,10 +884,38 @@ fn enrich_log_event(log: &mut LogEvent, log_namespace: LogNamespace) {
     );
 }
 
+/// Keep only the selected fields of the record.
+///
+/// The cursor and the priority are always kept, as they are needed to resume reading the journal
+/// and to remap the priority of the entry.
+fn select_fields(record: &mut Record, includes: &HashSet<String>, excludes: &HashSet<String>) {
+    if includes.is_empty() && excludes.is_empty() {
+        return;
+    }
+    record.retain(|field, _| {
+        let included = includes.is_empty()
+            || includes.contains(field)
+            || [MESSAGE, HOSTNAME, SOURCE_TIMESTAMP, RECEIVED_TIMESTAMP].contains(&field.as_str());
+        [CURSOR, PRIORITY].contains(&field.as_str()) || (included && !excludes.contains(field))
+    });
+}
+
+/// The value of a field of the record, as an integer if it's a trusted field that holds one and
+/// trusted fields are decoded.
+fn field_value(field: &str, value: String, decode_trusted_fields: bool) -> Value {
+    if decode_trusted_fields && TRUSTED_INTEGER_FIELDS.contains(&field) {
+        if let Ok(value) = value.parse::<i64>() {
+            return Value::Integer(value);
+        }
+    }
+    Value::Bytes(Bytes::from(value))
+}
+
 fn create_log_event_from_record(
     mut record: Record,
     batch: &Option<BatchNotifier>,
     log_namespace: LogNamespace,
+    decode_trusted_fields: bool,
 ) -> LogEvent {
     match log_namespace {
         LogNamespace::Vector => {
@@ -824,16 +927,21 @@ fn create_log_event_from_record(
             let mut log = LogEvent::from(message_value).with_batch_notifier_option(batch);
 
             // Add the remaining fields from the Record to the log event into an object to avoid collisions.
-            record.iter().for_each(|(key, value)| {
+            record.into_iter().for_each(|(key, value)| {
+                let value = field_value(&key, value, decode_trusted_fields);
                 log.metadata_mut()
                     .value_mut()
-                    .insert(path!(JournaldConfig::NAME, "metadata", key), value.as_str());
+                    .insert(path!(JournaldConfig::NAME, "metadata", &key), value);
             });
 
             log
         }
         LogNamespace::Legacy => {
-            let mut log = LogEvent::from_iter(record).with_batch_notifier_option(batch);
+            let mut log = LogEvent::from_iter(record.into_iter().map(|(key, value)| {
+                let value = field_value(&key, value, decode_trusted_fields);
+                (key, value)
+            }))
+            .with_batch_notifier_option(batch);
 
             if let Some(message) = log.remove(event_path!(MESSAGE)) {
                 log.maybe_insert(log_schema().message_key_target_path(), message);
@@ -864,7 +972,7 @@ fn decode_record(line: &[u8], remap: bool) -> Result<Record, JsonError> {
         }
     }
     if remap {
-        record.get_mut("PRIORITY").map(remap_priority);
+        record.get_mut(PRIORITY).map(remap_priority);
     }
     serde_json::from_value(record)
 }
@@ -1538,6 +1646,63 @@ mod tests {
         assert!(cmd_line.contains("--merge"));
     }
 
+    #[test]
+    fn command_resume_across_boots() {
+        let starter = |resume_across_boots| {
+            StartJournalctl::new(
+                "journalctl".into(),
+                None,
+                None,
+                true,
+                resume_across_boots,
+                false,
+                vec![],
+            )
+        };
+
+        let cmd_line = format!("{:?}", starter(true).make_command(Some("2")));
+        assert!(!cmd_line.contains("--boot"));
+        assert!(cmd_line.contains("--after-cursor=2"));
+
+        let cmd_line = format!("{:?}", starter(true).make_command(None));
+        assert!(cmd_line.contains("--boot"));
+
+        let cmd_line = format!("{:?}", starter(false).make_command(Some("2")));
+        assert!(cmd_line.contains("--boot"));
+    }
+
+    #[test]
+    fn selects_and_decodes_fields() {
+        let mut record = decode_record(
+            br#"{"MESSAGE":"hello","_PID":"2156","_UID":"0","_SYSTEMD_UNIT":"ntpd.service","_CMDLINE":"ntpd","_COMM":"ntpd","PRIORITY":"6","__CURSOR":"1","__REALTIME_TIMESTAMP":"1564173027000443"}"#,
+            false,
+        )
+        .unwrap();
+        select_fields(
+            &mut record,
+            &HashSet::from([
+                "_PID".to_owned(),
+                "_SYSTEMD_UNIT".to_owned(),
+                "_CMDLINE".to_owned(),
+            ]),
+            &HashSet::from(["_CMDLINE".to_owned(), PRIORITY.to_owned()]),
+        );
+        assert_eq!(record.get(CURSOR).map(String::as_str), Some("1"));
+        assert_eq!(record.get(PRIORITY).map(String::as_str), Some("6"));
+
+        let log = create_log_event_from_record(record, &None, LogNamespace::Legacy, true);
+        assert_eq!(
+            log[log_schema().message_key().unwrap().to_string()],
+            "hello".into()
+        );
+        assert_eq!(log["_PID"], Value::Integer(2156));
+        assert_eq!(log["_SYSTEMD_UNIT"], "ntpd.service".into());
+        assert_eq!(log[RECEIVED_TIMESTAMP], "1564173027000443".into());
+        assert!(!log.contains(event_path!("_UID")));
+        assert!(!log.contains(event_path!("_CMDLINE")));
+        assert!(!log.contains(event_path!("_COMM")));
+    }
+
     fn create_command(
         path: &Path,
         journal_dir: Option<PathBuf>,
@@ -1552,6 +1717,7 @@ mod tests {
             journal_dir,
             journal_namespace,
             current_boot_only,
+            false,
             since_now,
             extra_args,
         )
@@ -1,11 +1,14 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Debug,
+    future::Future,
     io::Cursor,
     pin::Pin,
     sync::{
         mpsc::{sync_channel, SyncSender},
-        Arc, OnceLock, Weak,
+        Arc, Mutex, OnceLock, Weak,
     },
+    thread,
     time::Duration,
 };
 
@@ -20,9 +23,9 @@ use rdkafka::{
         StreamConsumer,
     },
     error::KafkaError,
-    message::{BorrowedMessage, Headers as _, Message},
+    message::{BorrowedMessage, Headers, Message},
     types::RDKafkaErrorCode,
-    ClientConfig, ClientContext, Statistics, TopicPartitionList,
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
@@ -59,8 +62,8 @@ use crate::{
     },
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
-        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaBytesReceived, KafkaCheckpointError, KafkaEventsReceived, KafkaMessageFiltered,
+        KafkaOffsetUpdateError, KafkaReadError, StreamClosedError,
     },
     kafka,
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -79,6 +82,8 @@ enum BuildError {
     CreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     SubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    AssignError { source: rdkafka::error::KafkaError },
 }
 
 /// Metrics (beta) configuration.
@@ -89,6 +94,74 @@ struct Metrics {
     pub topic_lag_metric: bool,
 }
 
+/// A partition to consume, and the offset to start consuming it from.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct PartitionAssignment {
+    /// The topic of the partition.
+    #[configurable(metadata(docs::examples = "topic-1"))]
+    topic: String,
+
+    /// The partition number.
+    #[configurable(metadata(docs::examples = 0))]
+    partition: i32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    start_offset: StartOffset,
+}
+
+/// The offset to start consuming a partition from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(
+    docs::enum_tag_description = "The offset to start consuming the partition from."
+))]
+enum StartOffset {
+    /// The offset committed for the consumer group.
+    ///
+    /// If there is no committed offset, the `auto_offset_reset` strategy is used.
+    #[default]
+    Committed,
+
+    /// The first offset of the partition.
+    Earliest,
+
+    /// The offset after the last message of the partition, so that only new messages are consumed.
+    Latest,
+
+    /// The first offset whose message has a timestamp at or after the given timestamp.
+    ///
+    /// If there is no such message, only new messages are consumed.
+    Timestamp {
+        /// The timestamp, in milliseconds since the Unix epoch.
+        #[configurable(metadata(docs::examples = 1714564800000_i64))]
+        timestamp_ms: i64,
+    },
+
+    /// The given offset.
+    Absolute {
+        /// The offset of the first message to consume.
+        #[configurable(metadata(docs::examples = 42))]
+        offset: i64,
+    },
+}
+
+impl StartOffset {
+    const fn offset(self) -> Offset {
+        match self {
+            Self::Committed => Offset::Stored,
+            Self::Earliest => Offset::Beginning,
+            Self::Latest => Offset::End,
+            // Resolved into an offset with `offsets_for_times`.
+            Self::Timestamp { timestamp_ms } => Offset::Offset(timestamp_ms),
+            Self::Absolute { offset } => Offset::Offset(offset),
+        }
+    }
+}
+
 /// Configuration for the `kafka` source.
 #[serde_as]
 #[configurable_component(source("kafka", "Collect logs from Apache Kafka."))]
@@ -103,7 +176,7 @@ pub struct KafkaSourceConfig {
     ///
     /// Must be in the form of `host:port`, and comma-separated.
     #[configurable(metadata(docs::examples = "10.14.22.123:9092,10.14.23.332:9092"))]
-    bootstrap_servers: String,
+    pub(crate) bootstrap_servers: String,
 
     /// The Kafka topics names to read events from.
     ///
@@ -113,24 +186,37 @@ pub struct KafkaSourceConfig {
         docs::examples = "topic-1",
         docs::examples = "topic-2"
     ))]
-    topics: Vec<String>,
+    pub(crate) topics: Vec<String>,
+
+    /// The partitions to consume, instead of subscribing to `topics`.
+    ///
+    /// The partitions are assigned to this consumer statically, so they are never rebalanced to
+    /// other consumers of the consumer group, and each partition is consumed from its
+    /// `start_offset`. The consumer group is still used to commit offsets.
+    ///
+    /// When this is set, `topics` is ignored.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    partitions: Vec<PartitionAssignment>,
 
     /// The consumer group name to be used to consume events from Kafka.
     #[configurable(metadata(docs::examples = "consumer-group-name"))]
-    group_id: String,
+    pub(crate) group_id: String,
 
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
     #[serde(default = "default_auto_offset_reset")]
+    #[derivative(Default(value = "default_auto_offset_reset()"))]
     #[configurable(metadata(docs::examples = "example_auto_offset_reset_values()"))]
-    auto_offset_reset: String,
+    pub(crate) auto_offset_reset: String,
 
     /// The Kafka session timeout.
     #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
     #[configurable(metadata(docs::examples = 5000, docs::examples = 10000))]
     #[configurable(metadata(docs::advanced))]
     #[serde(default = "default_session_timeout_ms")]
+    #[derivative(Default(value = "default_session_timeout_ms()"))]
     #[configurable(metadata(docs::human_name = "Session Timeout"))]
     session_timeout_ms: Duration,
 
@@ -154,6 +240,7 @@ pub struct KafkaSourceConfig {
     #[configurable(metadata(docs::examples = 30000, docs::examples = 60000))]
     #[configurable(metadata(docs::advanced))]
     #[serde(default = "default_socket_timeout_ms")]
+    #[derivative(Default(value = "default_socket_timeout_ms()"))]
     #[configurable(metadata(docs::human_name = "Socket Timeout"))]
     socket_timeout_ms: Duration,
 
@@ -162,15 +249,17 @@ pub struct KafkaSourceConfig {
     #[configurable(metadata(docs::examples = 50, docs::examples = 100))]
     #[configurable(metadata(docs::advanced))]
     #[serde(default = "default_fetch_wait_max_ms")]
+    #[derivative(Default(value = "default_fetch_wait_max_ms()"))]
     #[configurable(metadata(docs::human_name = "Max Fetch Wait Time"))]
     fetch_wait_max_ms: Duration,
 
     /// The frequency that the consumer offsets are committed (written) to offset storage.
     #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
     #[serde(default = "default_commit_interval_ms")]
+    #[derivative(Default(value = "default_commit_interval_ms()"))]
     #[configurable(metadata(docs::examples = 5000, docs::examples = 10000))]
     #[configurable(metadata(docs::human_name = "Commit Interval"))]
-    commit_interval_ms: Duration,
+    pub(crate) commit_interval_ms: Duration,
 
     /// Overrides the name of the log field used to add the message key to each event.
     ///
@@ -178,6 +267,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"message_key"` is used.
     #[serde(default = "default_key_field")]
+    #[derivative(Default(value = "default_key_field()"))]
     #[configurable(metadata(docs::examples = "message_key"))]
     key_field: OptionalValuePath,
 
@@ -187,6 +277,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"topic"` is used.
     #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
     #[configurable(metadata(docs::examples = "topic"))]
     topic_key: OptionalValuePath,
 
@@ -196,6 +287,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"partition"` is used.
     #[serde(default = "default_partition_key")]
+    #[derivative(Default(value = "default_partition_key()"))]
     #[configurable(metadata(docs::examples = "partition"))]
     partition_key: OptionalValuePath,
 
@@ -205,6 +297,7 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"offset"` is used.
     #[serde(default = "default_offset_key")]
+    #[derivative(Default(value = "default_offset_key()"))]
     #[configurable(metadata(docs::examples = "offset"))]
     offset_key: OptionalValuePath,
 
@@ -214,9 +307,25 @@ pub struct KafkaSourceConfig {
     ///
     /// By default, `"headers"` is used.
     #[serde(default = "default_headers_key")]
+    #[derivative(Default(value = "default_headers_key()"))]
     #[configurable(metadata(docs::examples = "headers"))]
     headers_key: OptionalValuePath,
 
+    /// Only consume messages whose headers have one of the given values.
+    ///
+    /// A message must match every header in this map, and it matches a header when it has that
+    /// header with any of the listed values. Messages that don't match are skipped without being
+    /// decoded, and their offsets are committed as if they had been delivered.
+    ///
+    /// This allows topics shared by multiple tenants to be split between sources without a
+    /// downstream `filter` transform.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "example_header_filter()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "The values allowed for a message header."
+    ))]
+    header_filter: HashMap<String, Vec<String>>,
+
     /// Advanced options set directly on the underlying `librdkafka` client.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
@@ -225,34 +334,63 @@ pub struct KafkaSourceConfig {
     #[configurable(metadata(
         docs::additional_props_description = "A librdkafka configuration option."
     ))]
-    librdkafka_options: Option<HashMap<String, String>>,
+    pub(crate) librdkafka_options: Option<HashMap<String, String>>,
 
     #[serde(flatten)]
-    auth: kafka::KafkaAuthConfig,
+    pub(crate) auth: kafka::KafkaAuthConfig,
 
     #[configurable(derived)]
     #[configurable(metadata(docs::advanced))]
     #[serde(default = "default_framing_message_based")]
     #[derivative(Default(value = "default_framing_message_based()"))]
-    framing: FramingConfig,
+    pub(crate) framing: FramingConfig,
 
     #[configurable(derived)]
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
-    decoding: DeserializerConfig,
+    pub(crate) decoding: DeserializerConfig,
 
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
-    acknowledgements: SourceAcknowledgementsConfig,
+    pub(crate) acknowledgements: SourceAcknowledgementsConfig,
 
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
-    log_namespace: Option<bool>,
+    pub(crate) log_namespace: Option<bool>,
 
     #[configurable(derived)]
     #[serde(default)]
     metrics: Metrics,
+
+    /// The source type stamped on events, which sources built on top of this one override.
+    #[serde(skip, default = "default_source_type")]
+    #[derivative(Default(value = "default_source_type()"))]
+    pub(crate) source_type: &'static str,
+
+    /// Where checkpoints are kept in addition to the consumer group offsets, which sources built
+    /// on top of this one set.
+    #[serde(skip)]
+    pub(crate) checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+}
+
+/// A store for the offsets of delivered messages, kept in addition to the offsets committed to
+/// the consumer group.
+///
+/// Assigned partitions resume after their checkpoint, and from the committed offset when they
+/// have none. Checkpoints are written every `commit_interval_ms`, and before partitions are
+/// revoked or the source shuts down.
+#[async_trait::async_trait]
+pub(crate) trait CheckpointStore: Debug + Send + Sync {
+    /// Loads the offsets of the last delivered messages of the given partitions, leaving out the
+    /// partitions without a checkpoint.
+    async fn load(
+        &self,
+        partitions: &[TopicPartition],
+    ) -> crate::Result<HashMap<TopicPartition, i64>>;
+
+    /// Stores the offsets of the last delivered messages of the given partitions.
+    async fn store(&self, offsets: &HashMap<TopicPartition, i64>) -> crate::Result<()>;
 }
 
 impl KafkaSourceConfig {
@@ -261,6 +399,10 @@ impl KafkaSourceConfig {
     }
 }
 
+const fn default_source_type() -> &'static str {
+    KafkaSourceConfig::NAME
+}
+
 const fn default_session_timeout_ms() -> Duration {
     Duration::from_millis(10000) // default in librdkafka
 }
@@ -313,6 +455,13 @@ const fn example_auto_offset_reset_values() -> [&'static str; 7] {
     ]
 }
 
+fn example_header_filter() -> HashMap<String, Vec<String>> {
+    HashMap::<_, _>::from_iter([(
+        "tenant".to_string(),
+        vec!["tenant-a".to_string(), "tenant-b".to_string()],
+    )])
+}
+
 fn example_librdkafka_options() -> HashMap<String, String> {
     HashMap::<_, _>::from_iter([
         ("client.id".to_string(), "${ENV_VAR}".to_string()),
@@ -367,42 +516,42 @@ impl SourceConfig for KafkaSourceConfig {
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata()
             .with_source_metadata(
-                Self::NAME,
+                self.source_type,
                 keys.timestamp.map(LegacyKey::Overwrite),
                 &owned_value_path!("timestamp"),
                 Kind::timestamp(),
                 Some("timestamp"),
             )
             .with_source_metadata(
-                Self::NAME,
+                self.source_type,
                 keys.topic.clone().map(LegacyKey::Overwrite),
                 &owned_value_path!("topic"),
                 Kind::bytes(),
                 None,
             )
             .with_source_metadata(
-                Self::NAME,
+                self.source_type,
                 keys.partition.clone().map(LegacyKey::Overwrite),
                 &owned_value_path!("partition"),
                 Kind::bytes(),
                 None,
             )
             .with_source_metadata(
-                Self::NAME,
+                self.source_type,
                 keys.offset.clone().map(LegacyKey::Overwrite),
                 &owned_value_path!("offset"),
                 Kind::bytes(),
                 None,
             )
             .with_source_metadata(
-                Self::NAME,
+                self.source_type,
                 keys.headers.clone().map(LegacyKey::Overwrite),
                 &owned_value_path!("headers"),
                 Kind::object(Collection::empty().with_unknown(Kind::bytes())),
                 None,
             )
             .with_source_metadata(
-                Self::NAME,
+                self.source_type,
                 keys.key_field.clone().map(LegacyKey::Overwrite),
                 &owned_value_path!("message_key"),
                 Kind::bytes(),
@@ -443,11 +592,39 @@ async fn kafka_source(
     // EOF signal allowing the coordination task to tell the kafka client task when all partitions have reached EOF
     let (eof_tx, eof_rx) = eof.then(oneshot::channel::<()>).unzip();
 
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    if let Err(e) = consumer.subscribe(&topics).context(SubscribeSnafu) {
-        error!("{}", e);
-        return Err(());
-    }
+    let assignment = if config.partitions.is_empty() {
+        let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+        if let Err(e) = consumer.subscribe(&topics).context(SubscribeSnafu) {
+            error!("{}", e);
+            return Err(());
+        }
+        None
+    } else {
+        let consumer = Arc::clone(&consumer);
+        let partitions = config.partitions.clone();
+        let timeout = config.socket_timeout_ms;
+        let assignment =
+            tokio::task::spawn_blocking(move || static_assignment(&consumer, &partitions, timeout))
+                .await
+                .expect("Resolving the partition assignment panicked.");
+        match assignment.context(AssignSnafu) {
+            Ok(assignment) => Some(assignment),
+            Err(e) => {
+                error!("{}", e);
+                return Err(());
+            }
+        }
+    };
+
+    let checkpoint_task = consumer.context().checkpoints.as_ref().map(|checkpoints| {
+        let (stop, stopped) = oneshot::channel();
+        let task = tokio::spawn(write_checkpoints(
+            Arc::clone(checkpoints),
+            config.commit_interval_ms,
+            stopped,
+        ));
+        (stop, task)
+    });
 
     let coordination_task = {
         let span = span.clone();
@@ -473,16 +650,79 @@ async fn kafka_source(
         let consumer = Arc::clone(&consumer);
         tokio::task::spawn_blocking(move || {
             let _enter = span.enter();
+            if let Some(assignment) = assignment {
+                // The partition queues are set up before the partitions are assigned, just like
+                // during a rebalance, so that no message is received by the main consumer.
+                consumer.context().consume_partitions(&assignment);
+                if let Err(e) = consumer.assign(&assignment).context(AssignSnafu) {
+                    error!("{}", e);
+                    consumer.context().shutdown();
+                    return;
+                }
+            }
             drive_kafka_consumer(consumer, shutdown, eof_rx);
         })
     };
 
     _ = tokio::join!(client_task, coordination_task);
+    if let Some((stop, task)) = checkpoint_task {
+        _ = stop.send(());
+        _ = task.await;
+    }
     consumer.context().commit_consumer_state();
 
     Ok(())
 }
 
+/// Writes the checkpoints of delivered messages every `interval`, until it is stopped.
+async fn write_checkpoints(
+    checkpoints: Arc<Checkpoints>,
+    interval: Duration,
+    mut stopped: oneshot::Receiver<()>,
+) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = &mut stopped => break,
+            _ = interval.tick() => checkpoints.write().await,
+        }
+    }
+}
+
+/// Builds the list of statically assigned partitions, with the offset to start consuming each
+/// of them from. The offsets of partitions that start from a timestamp are looked up from the
+/// brokers.
+fn static_assignment(
+    consumer: &StreamConsumer<KafkaSourceContext>,
+    partitions: &[PartitionAssignment],
+    timeout: Duration,
+) -> Result<TopicPartitionList, KafkaError> {
+    let mut assignment = TopicPartitionList::new();
+    let mut timestamps = TopicPartitionList::new();
+    for partition in partitions {
+        let list = match partition.start_offset {
+            StartOffset::Timestamp { .. } => &mut timestamps,
+            _ => &mut assignment,
+        };
+        list.add_partition_offset(
+            &partition.topic,
+            partition.partition,
+            partition.start_offset.offset(),
+        )?;
+    }
+
+    if timestamps.count() > 0 {
+        for elem in consumer.offsets_for_times(timestamps, timeout)?.elements() {
+            elem.error()?;
+            // There is no message at or after the timestamp when the offset is `End`, so only new
+            // messages are consumed.
+            assignment.add_partition_offset(elem.topic(), elem.partition(), elem.offset())?;
+        }
+    }
+
+    Ok(assignment)
+}
+
 /// ConsumerStateInner implements a small struct/enum-based state machine.
 ///
 /// With a ConsumerStateInner<Consuming>, the client is able to spawn new tasks
@@ -591,6 +831,7 @@ impl ConsumerStateInner<Consuming> {
         exit_eof: bool,
     ) -> (oneshot::Sender<()>, tokio::task::AbortHandle) {
         let keys = self.config.keys();
+        let header_filter = self.config.header_filter.clone();
         let decoder = self.decoder.clone();
         let log_namespace = self.log_namespace;
         let mut out = self.out.clone();
@@ -626,6 +867,9 @@ impl ConsumerStateInner<Consuming> {
                                 if let Err(error) =  consumer.store_offset(&entry.topic, entry.partition, entry.offset) {
                                     emit!(KafkaOffsetUpdateError { error });
                                 }
+                                if let Some(checkpoints) = &consumer.context().checkpoints {
+                                    checkpoints.delivered(&entry);
+                                }
                             }
                         }
                         None if finalizer.is_none() => {
@@ -654,7 +898,11 @@ impl ConsumerStateInner<Consuming> {
                                 topic: msg.topic(),
                                 partition: msg.partition(),
                             });
-                            parse_message(msg, decoder.clone(), &keys, &mut out, acknowledgements, &finalizer, log_namespace).await;
+                            if headers_match(&header_filter, msg.headers()) {
+                                parse_message(msg, decoder.clone(), &keys, &mut out, acknowledgements, &finalizer, log_namespace).await;
+                            } else {
+                                skip_message(msg, &finalizer);
+                            }
                         }
                     },
                 )
@@ -988,6 +1236,35 @@ async fn parse_message(
     }
 }
 
+/// Whether the headers of the message have one of the allowed values for every header in the filter.
+fn headers_match<H: Headers>(filter: &HashMap<String, Vec<String>>, headers: Option<&H>) -> bool {
+    filter.iter().all(|(key, allowed)| {
+        headers.is_some_and(|headers| {
+            headers.iter().any(|header| {
+                header.key == key.as_str()
+                    && header.value.is_some_and(|value| {
+                        allowed.iter().any(|allowed| allowed.as_bytes() == value)
+                    })
+            })
+        })
+    })
+}
+
+/// Skip a message that didn't match the header filter. The message still goes through the
+/// finalizer, already delivered, so that its offset is stored in order with the messages around it.
+fn skip_message(msg: BorrowedMessage<'_>, finalizer: &Option<OrderedFinalizer<FinalizerEntry>>) {
+    emit!(KafkaMessageFiltered {
+        topic: msg.topic(),
+        partition: msg.partition(),
+        offset: msg.offset(),
+    });
+    if let Some(f) = finalizer.as_ref() {
+        // A batch without any events is delivered as soon as it is dropped.
+        let (_, receiver) = BatchNotifier::new_with_receiver();
+        f.add(msg.into(), receiver)
+    }
+}
+
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
@@ -1040,6 +1317,7 @@ struct Keys {
     partition: Option<OwnedValuePath>,
     offset: Option<OwnedValuePath>,
     headers: Option<OwnedValuePath>,
+    source_type: &'static str,
 }
 
 impl Keys {
@@ -1051,6 +1329,7 @@ impl Keys {
             partition: config.partition_key.path.clone(),
             offset: config.offset_key.path.clone(),
             headers: config.headers_key.path.clone(),
+            source_type: config.source_type,
         }
     }
 }
@@ -1109,19 +1388,19 @@ impl ReceivedMessage {
                     // timestamp when the event was processed.
                     log_namespace.insert_standard_vector_source_metadata(
                         log,
-                        KafkaSourceConfig::NAME,
+                        keys.source_type,
                         Utc::now(),
                     );
                 }
                 LogNamespace::Legacy => {
                     if let Some(source_type_key) = log_schema().source_type_key_target_path() {
-                        log.insert(source_type_key, KafkaSourceConfig::NAME);
+                        log.insert(source_type_key, keys.source_type);
                     }
                 }
             }
 
             log_namespace.insert_source_metadata(
-                KafkaSourceConfig::NAME,
+                keys.source_type,
                 log,
                 keys.key_field.as_ref().map(LegacyKey::Overwrite),
                 path!("message_key"),
@@ -1129,7 +1408,7 @@ impl ReceivedMessage {
             );
 
             log_namespace.insert_source_metadata(
-                KafkaSourceConfig::NAME,
+                keys.source_type,
                 log,
                 keys.timestamp.as_ref().map(LegacyKey::Overwrite),
                 path!("timestamp"),
@@ -1137,7 +1416,7 @@ impl ReceivedMessage {
             );
 
             log_namespace.insert_source_metadata(
-                KafkaSourceConfig::NAME,
+                keys.source_type,
                 log,
                 keys.topic.as_ref().map(LegacyKey::Overwrite),
                 path!("topic"),
@@ -1145,7 +1424,7 @@ impl ReceivedMessage {
             );
 
             log_namespace.insert_source_metadata(
-                KafkaSourceConfig::NAME,
+                keys.source_type,
                 log,
                 keys.partition.as_ref().map(LegacyKey::Overwrite),
                 path!("partition"),
@@ -1153,7 +1432,7 @@ impl ReceivedMessage {
             );
 
             log_namespace.insert_source_metadata(
-                KafkaSourceConfig::NAME,
+                keys.source_type,
                 log,
                 keys.offset.as_ref().map(LegacyKey::Overwrite),
                 path!("offset"),
@@ -1161,7 +1440,7 @@ impl ReceivedMessage {
             );
 
             log_namespace.insert_source_metadata(
-                KafkaSourceConfig::NAME,
+                keys.source_type,
                 log,
                 keys.headers.as_ref().map(LegacyKey::Overwrite),
                 path!("headers"),
@@ -1236,6 +1515,7 @@ fn create_consumer(
             config.metrics.topic_lag_metric,
             acknowledgements,
             callbacks,
+            config.checkpoint_store.clone().map(Checkpoints::new),
             Span::current(),
         ))
         .context(CreateSnafu)?;
@@ -1243,7 +1523,7 @@ fn create_consumer(
     Ok((consumer, callback_rx))
 }
 
-type TopicPartition = (String, i32);
+pub(crate) type TopicPartition = (String, i32);
 
 /// Status returned by partition consumer tasks, allowing the coordination task
 /// to differentiate between a consumer exiting normally (after receiving an end
@@ -1260,10 +1540,98 @@ enum KafkaCallback {
     ShuttingDown(SyncSender<()>),
 }
 
+/// The checkpoints of delivered messages, and the store they are written to.
+#[derive(Debug)]
+struct Checkpoints {
+    store: Arc<dyn CheckpointStore>,
+
+    /// The offsets of delivered messages that are not written to the store yet.
+    pending: Mutex<HashMap<TopicPartition, i64>>,
+
+    /// Held while writing, so that a checkpoint is never overwritten by an older one.
+    writing: tokio::sync::Mutex<()>,
+
+    /// The runtime that the store is called on from the consumer callbacks.
+    runtime: Handle,
+}
+
+impl Checkpoints {
+    fn new(store: Arc<dyn CheckpointStore>) -> Arc<Self> {
+        Arc::new(Self {
+            store,
+            pending: Mutex::default(),
+            writing: tokio::sync::Mutex::default(),
+            runtime: Handle::current(),
+        })
+    }
+
+    fn delivered(&self, entry: &FinalizerEntry) {
+        self.pending
+            .lock()
+            .expect("Checkpoint lock was poisoned.")
+            .insert((entry.topic.clone(), entry.partition), entry.offset);
+    }
+
+    async fn write(&self) {
+        let _writing = self.writing.lock().await;
+        let offsets =
+            std::mem::take(&mut *self.pending.lock().expect("Checkpoint lock was poisoned."));
+        if offsets.is_empty() {
+            return;
+        }
+        if let Err(error) = self.store.store(&offsets).await {
+            emit!(KafkaCheckpointError { error });
+            // Retry on the next write, unless newer messages have been delivered since.
+            let mut pending = self.pending.lock().expect("Checkpoint lock was poisoned.");
+            for (tp, offset) in offsets {
+                pending.entry(tp).or_insert(offset);
+            }
+        }
+    }
+
+    /// Sets the offsets of the partitions being assigned to resume after their checkpoints. The
+    /// partitions without a checkpoint resume from their committed offsets.
+    fn resume(&self, tpl: &TopicPartitionList) {
+        let partitions: Vec<TopicPartition> = tpl
+            .elements()
+            .iter()
+            .map(|tp| (tp.topic().into(), tp.partition()))
+            .collect();
+        let checkpoints = match self.block_on(self.store.load(&partitions)) {
+            Ok(checkpoints) => checkpoints,
+            Err(error) => {
+                emit!(KafkaCheckpointError { error });
+                return;
+            }
+        };
+        for mut elem in tpl.elements() {
+            if let Some(offset) = checkpoints.get(&(elem.topic().into(), elem.partition())) {
+                if let Err(error) = elem.set_offset(Offset::Offset(offset + 1)) {
+                    emit!(KafkaOffsetUpdateError { error });
+                }
+            }
+        }
+    }
+
+    /// Runs a call to the store from a consumer callback. The call runs on a thread of its own,
+    /// since the callbacks are served from within the runtime.
+    fn block_on<T: Send>(&self, future: impl Future<Output = T> + Send) -> T {
+        thread::scope(|scope| {
+            scope
+                .spawn(move || self.runtime.block_on(future))
+                .join()
+                .expect("Checkpoint store call panicked.")
+        })
+    }
+}
+
 struct KafkaSourceContext {
     acknowledgements: bool,
     stats: kafka::KafkaStatisticsContext,
 
+    /// The checkpoints written in addition to the consumer group offsets, if any
+    checkpoints: Option<Arc<Checkpoints>>,
+
     /// A callback channel used to coordinate between the main consumer task and the acknowledgement task
     callbacks: UnboundedSender<KafkaCallback>,
 
@@ -1276,6 +1644,7 @@ impl KafkaSourceContext {
         expose_lag_metrics: bool,
         acknowledgements: bool,
         callbacks: UnboundedSender<KafkaCallback>,
+        checkpoints: Option<Arc<Checkpoints>>,
         span: Span,
     ) -> Self {
         Self {
@@ -1284,6 +1653,7 @@ impl KafkaSourceContext {
                 span,
             },
             acknowledgements,
+            checkpoints,
             consumer: OnceLock::default(),
             callbacks,
         }
@@ -1359,6 +1729,9 @@ impl KafkaSourceContext {
                 Err(error) => emit!(KafkaOffsetUpdateError { error }),
             }
         }
+        if let Some(checkpoints) = &self.checkpoints {
+            checkpoints.block_on(checkpoints.write());
+        }
     }
 }
 
@@ -1371,7 +1744,12 @@ impl ClientContext for KafkaSourceContext {
 impl ConsumerContext for KafkaSourceContext {
     fn pre_rebalance(&self, rebalance: &Rebalance) {
         match rebalance {
-            Rebalance::Assign(tpl) => self.consume_partitions(tpl),
+            Rebalance::Assign(tpl) => {
+                if let Some(checkpoints) = &self.checkpoints {
+                    checkpoints.resume(tpl);
+                }
+                self.consume_partitions(tpl)
+            }
 
             Rebalance::Revoke(tpl) => {
                 // TODO  workaround for https://github.com/fede1024/rust-rdkafka/issues/681
@@ -1523,6 +1901,90 @@ mod test {
         )
     }
 
+    #[test]
+    fn header_filter_matches() {
+        use rdkafka::message::{Header, OwnedHeaders};
+
+        let filter = HashMap::from([(
+            "tenant".to_string(),
+            vec!["tenant-a".to_string(), "tenant-b".to_string()],
+        )]);
+        let headers = |tenant: &str| {
+            OwnedHeaders::new()
+                .insert(Header {
+                    key: "region",
+                    value: Some("eu"),
+                })
+                .insert(Header {
+                    key: "tenant",
+                    value: Some(tenant),
+                })
+        };
+
+        assert!(headers_match(&filter, Some(&headers("tenant-a"))));
+        assert!(headers_match(&filter, Some(&headers("tenant-b"))));
+        assert!(!headers_match(&filter, Some(&headers("tenant-c"))));
+        assert!(!headers_match(&filter, None::<&OwnedHeaders>));
+        assert!(headers_match(&HashMap::new(), None::<&OwnedHeaders>));
+    }
+
+    #[test]
+    fn parse_static_partitions() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topics = []
+            group_id = "replay"
+
+            [[partitions]]
+            topic = "logs"
+            partition = 0
+
+            [[partitions]]
+            topic = "logs"
+            partition = 1
+            start_offset.type = "earliest"
+
+            [[partitions]]
+            topic = "logs"
+            partition = 2
+            start_offset = { type = "timestamp", timestamp_ms = 1714564800000 }
+
+            [[partitions]]
+            topic = "logs"
+            partition = 3
+            start_offset = { type = "absolute", offset = 42 }
+            "#,
+        )
+        .unwrap();
+
+        let offsets = config
+            .partitions
+            .iter()
+            .map(|p| (p.partition, p.start_offset))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            vec![
+                (0, StartOffset::Committed),
+                (1, StartOffset::Earliest),
+                (
+                    2,
+                    StartOffset::Timestamp {
+                        timestamp_ms: 1714564800000
+                    }
+                ),
+                (3, StartOffset::Absolute { offset: 42 }),
+            ]
+        );
+        assert_eq!(StartOffset::Committed.offset(), Offset::Stored);
+        assert_eq!(StartOffset::Latest.offset(), Offset::End);
+        assert_eq!(
+            StartOffset::Absolute { offset: 42 }.offset(),
+            Offset::Offset(42)
+        );
+    }
+
     #[tokio::test]
     async fn consumer_create_ok() {
         let config = make_config("topic", "group", LogNamespace::Legacy, None);
@@ -1775,6 +2237,72 @@ mod integration_test {
         }
     }
 
+    /// Keeps checkpoints in memory.
+    #[derive(Debug, Default)]
+    struct MemoryCheckpointStore(Mutex<HashMap<TopicPartition, i64>>);
+
+    #[async_trait::async_trait]
+    impl CheckpointStore for MemoryCheckpointStore {
+        async fn load(
+            &self,
+            partitions: &[TopicPartition],
+        ) -> crate::Result<HashMap<TopicPartition, i64>> {
+            let checkpoints = self.0.lock().unwrap();
+            Ok(partitions
+                .iter()
+                .filter_map(|tp| checkpoints.get(tp).map(|offset| (tp.clone(), *offset)))
+                .collect())
+        }
+
+        async fn store(&self, offsets: &HashMap<TopicPartition, i64>) -> crate::Result<()> {
+            self.0.lock().unwrap().extend(offsets.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn resumes_from_checkpoints() {
+        const SEND_COUNT: usize = 10;
+        const CHECKPOINT: usize = 4;
+
+        let topic = format!("test-topic-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+        send_events(topic.clone(), 1, SEND_COUNT).await;
+
+        // The consumer group has no committed offset, so it would start from the beginning.
+        let store = Arc::new(MemoryCheckpointStore::default());
+        store
+            .0
+            .lock()
+            .unwrap()
+            .insert((topic.clone(), 0), CHECKPOINT as i64);
+        let config = KafkaSourceConfig {
+            checkpoint_store: Some(Arc::clone(&store) as _),
+            ..make_config(&topic, &group_id, LogNamespace::Legacy, None)
+        };
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let (trigger_shutdown, shutdown_done) =
+            spawn_kafka(tx, config, true, false, LogNamespace::Legacy);
+        let events = collect_n(rx, SEND_COUNT - CHECKPOINT - 1).await;
+        tokio::task::yield_now().await;
+        drop(trigger_shutdown);
+        shutdown_done.await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            format!("{} {:03}", TEXT, CHECKPOINT + 1).into()
+        );
+        assert_eq!(
+            store.0.lock().unwrap()[&(topic.clone(), 0)],
+            SEND_COUNT as i64 - 1
+        );
+        assert_eq!(
+            fetch_tpl_offset(&group_id, &topic, 0),
+            Offset::from_raw(SEND_COUNT as i64)
+        );
+    }
+
     fn make_rand_config() -> (String, String, KafkaSourceConfig) {
         let topic = format!("test-topic-{}", random_string(10));
         let group_id = format!("test-group-{}", random_string(10));
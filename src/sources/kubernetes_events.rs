@@ -0,0 +1,472 @@
+//! This mod implements the `kubernetes_events` source.
+//! The source watches the Kubernetes Events API and emits every cluster event
+//! as a structured log event, so that they can be shipped alongside the Pod
+//! logs collected by the `kubernetes_logs` source.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::Event as KubeEvent;
+use kube::{
+    api::Api,
+    config::{self, KubeConfigOptions},
+    runtime::{watcher, WatchStreamExt},
+    Client, Config as ClientConfig,
+};
+use serde_with::serde_as;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{DataType, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, LogEvent, Value},
+    internal_events::StreamClosedError,
+    shutdown::ShutdownSignal,
+    sources, SourceSender,
+};
+
+/// Configuration for the `kubernetes_events` source.
+#[serde_as]
+#[configurable_component(source(
+    "kubernetes_events",
+    "Collect events from the Kubernetes Events API."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct KubernetesEventsConfig {
+    /// The [namespace][namespace] to watch the events of.
+    ///
+    /// If not set, the events of all namespaces are watched.
+    ///
+    /// [namespace]: https://kubernetes.io/docs/concepts/overview/working-with-objects/namespaces/
+    #[configurable(metadata(docs::examples = "default", docs::examples = "kube-system"))]
+    namespace: Option<String>,
+
+    /// Specifies the [field selector][field_selector] to filter the events with.
+    ///
+    /// [field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+    #[configurable(metadata(docs::examples = "type=Warning"))]
+    #[configurable(metadata(docs::examples = "involvedObject.kind=Pod,type!=Normal"))]
+    field_selector: String,
+
+    /// Specifies the [label selector][label_selector] to filter the events with.
+    ///
+    /// [label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+    #[configurable(metadata(docs::examples = "my_custom_label!=my_value"))]
+    label_selector: String,
+
+    /// The interval at which the events are listed again from the Kubernetes API.
+    ///
+    /// Events that were missed while the watch was interrupted are picked up by the resync. Events
+    /// that were already emitted are deduplicated by their involved object and reason, and are
+    /// only emitted again if they occurred again since.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Resync Interval"))]
+    resync_interval_secs: Duration,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+
+    /// Determines if requests to the kube-apiserver can be served by a cache.
+    use_apiserver_cache: bool,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+const fn default_resync_interval() -> Duration {
+    Duration::from_secs(600)
+}
+
+impl Default for KubernetesEventsConfig {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            field_selector: "".to_string(),
+            label_selector: "".to_string(),
+            resync_interval_secs: default_resync_interval(),
+            kube_config_file: None,
+            use_apiserver_cache: false,
+            log_namespace: None,
+        }
+    }
+}
+
+impl GenerateConfig for KubernetesEventsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::default()).unwrap()
+    }
+}
+
+impl KubernetesEventsConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(
+            Kind::object(Collection::empty().with_unknown(Kind::any())),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .timestamp_key()
+                .cloned()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("timestamp"),
+            Kind::timestamp().or_undefined(),
+            Some("timestamp"),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for KubernetesEventsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubeconfig, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let api = match &self.namespace {
+            Some(namespace) => Api::<KubeEvent>::namespaced(client, namespace),
+            None => Api::<KubeEvent>::all(client),
+        };
+
+        let watcher_config = watcher::Config {
+            field_selector: Some(self.field_selector.clone()).filter(|s| !s.is_empty()),
+            label_selector: Some(self.label_selector.clone()).filter(|s| !s.is_empty()),
+            list_semantic: if self.use_apiserver_cache {
+                watcher::ListSemantic::Any
+            } else {
+                watcher::ListSemantic::MostRecent
+            },
+            ..Default::default()
+        };
+
+        Ok(Box::pin(run(
+            api,
+            watcher_config,
+            self.resync_interval_secs,
+            cx.out,
+            cx.shutdown,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    api: Api<KubeEvent>,
+    watcher_config: watcher::Config,
+    resync_interval: Duration,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let mut deduplicator = Deduplicator::default();
+    let bytes_received = register!(BytesReceived::from(Protocol::HTTP));
+    let events_received = register!(EventsReceived);
+
+    loop {
+        // The watcher is recreated at every resync, which lists all of the events again.
+        let mut stream = watcher(api.clone(), watcher_config.clone())
+            .backoff(watcher::default_backoff())
+            .take_until(tokio::time::sleep(resync_interval))
+            .boxed();
+
+        loop {
+            let kube_events = tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                item = stream.next() => match item {
+                    Some(Ok(watcher::Event::Applied(kube_event))) => vec![kube_event],
+                    Some(Ok(watcher::Event::Deleted(_))) => continue,
+                    Some(Ok(watcher::Event::Restarted(kube_events))) => {
+                        deduplicator.retain(&kube_events);
+                        kube_events
+                    }
+                    Some(Err(error)) => {
+                        warn!(message = "Watcher Stream received an error. Retrying.", ?error);
+                        continue;
+                    }
+                    // Time to resync.
+                    None => break,
+                },
+            };
+
+            for kube_event in kube_events {
+                if !deduplicator.observe(&kube_event) {
+                    continue;
+                }
+
+                let Some(log) = create_log_event(&kube_event, log_namespace) else {
+                    continue;
+                };
+
+                bytes_received.emit(ByteSize(log.estimated_json_encoded_size_of().get()));
+                events_received.emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+
+                if out.send_event(Event::from(log)).await.is_err() {
+                    emit!(StreamClosedError { count: 1 });
+                    return Err(());
+                }
+            }
+        }
+    }
+}
+
+/// Identifies the events that are deduplicated against each other.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct DedupKey {
+    kind: Option<String>,
+    namespace: Option<String>,
+    name: Option<String>,
+    uid: Option<String>,
+    reason: Option<String>,
+}
+
+impl From<&KubeEvent> for DedupKey {
+    fn from(kube_event: &KubeEvent) -> Self {
+        let involved_object = &kube_event.involved_object;
+        Self {
+            kind: involved_object.kind.clone(),
+            namespace: involved_object.namespace.clone(),
+            name: involved_object.name.clone(),
+            uid: involved_object.uid.clone(),
+            reason: kube_event.reason.clone(),
+        }
+    }
+}
+
+/// Keeps track of the last occurrence of the events that were emitted, so that the events
+/// listed again by a resync are only emitted if they occurred again since.
+#[derive(Debug, Default)]
+struct Deduplicator {
+    seen: HashMap<DedupKey, (Option<DateTime<Utc>>, i32)>,
+}
+
+impl Deduplicator {
+    /// Records the event, returning whether it occurred since the last one with the same key.
+    fn observe(&mut self, kube_event: &KubeEvent) -> bool {
+        let occurrence = (event_timestamp(kube_event), kube_event.count.unwrap_or(1));
+        match self.seen.get(&DedupKey::from(kube_event)) {
+            Some(last) if *last >= occurrence => false,
+            _ => {
+                self.seen.insert(DedupKey::from(kube_event), occurrence);
+                true
+            }
+        }
+    }
+
+    /// Forgets the events that are no longer listed, such as the ones that expired.
+    fn retain(&mut self, kube_events: &[KubeEvent]) {
+        let keys = kube_events
+            .iter()
+            .map(DedupKey::from)
+            .collect::<HashSet<_>>();
+        self.seen.retain(|key, _| keys.contains(key));
+    }
+}
+
+/// The time of the last occurrence of the event.
+fn event_timestamp(kube_event: &KubeEvent) -> Option<DateTime<Utc>> {
+    kube_event
+        .last_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or_else(|| kube_event.event_time.as_ref().map(|time| time.0))
+        .or_else(|| kube_event.first_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| {
+            kube_event
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .map(|time| time.0)
+        })
+}
+
+fn create_log_event(kube_event: &KubeEvent, log_namespace: LogNamespace) -> Option<LogEvent> {
+    let value = match serde_json::to_value(kube_event) {
+        Ok(value) => Value::from(value),
+        Err(error) => {
+            warn!(message = "Failed to serialize Kubernetes event.", %error);
+            return None;
+        }
+    };
+
+    let mut log = LogEvent::from(value);
+
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        KubernetesEventsConfig::NAME,
+        Utc::now(),
+    );
+
+    if let Some(timestamp) = event_timestamp(kube_event) {
+        log_namespace.insert_source_metadata(
+            KubernetesEventsConfig::NAME,
+            &mut log,
+            log_schema().timestamp_key().map(LegacyKey::Overwrite),
+            path!("timestamp"),
+            timestamp,
+        );
+    }
+
+    Some(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::{
+        api::core::v1::ObjectReference,
+        apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time},
+    };
+    use vector_lib::lookup::{event_path, metadata_path};
+    use vrl::value;
+
+    use super::*;
+
+    fn kube_event(name: &str, reason: &str, count: i32, last_timestamp: i64) -> KubeEvent {
+        KubeEvent {
+            metadata: ObjectMeta {
+                name: Some(format!("{name}.{reason}")),
+                namespace: Some("default".to_owned()),
+                ..ObjectMeta::default()
+            },
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_owned()),
+                namespace: Some("default".to_owned()),
+                name: Some(name.to_owned()),
+                ..ObjectReference::default()
+            },
+            reason: Some(reason.to_owned()),
+            message: Some(format!("{reason} {name}")),
+            count: Some(count),
+            last_timestamp: DateTime::from_timestamp(last_timestamp, 0).map(Time),
+            ..KubeEvent::default()
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesEventsConfig>();
+    }
+
+    #[test]
+    fn deduplicates_on_involved_object_and_reason() {
+        let mut deduplicator = Deduplicator::default();
+
+        assert!(deduplicator.observe(&kube_event("pod-a", "BackOff", 1, 100)));
+        assert!(!deduplicator.observe(&kube_event("pod-a", "BackOff", 1, 100)));
+        assert!(deduplicator.observe(&kube_event("pod-a", "Pulled", 1, 100)));
+        assert!(deduplicator.observe(&kube_event("pod-b", "BackOff", 1, 100)));
+
+        // The event occurred again.
+        assert!(deduplicator.observe(&kube_event("pod-a", "BackOff", 2, 100)));
+        assert!(deduplicator.observe(&kube_event("pod-a", "BackOff", 3, 200)));
+        assert!(!deduplicator.observe(&kube_event("pod-a", "BackOff", 2, 100)));
+    }
+
+    #[test]
+    fn forgets_events_no_longer_listed() {
+        let mut deduplicator = Deduplicator::default();
+        assert!(deduplicator.observe(&kube_event("pod-a", "BackOff", 1, 100)));
+        assert!(deduplicator.observe(&kube_event("pod-b", "BackOff", 1, 100)));
+
+        deduplicator.retain(&[kube_event("pod-b", "BackOff", 1, 100)]);
+
+        assert!(deduplicator.observe(&kube_event("pod-a", "BackOff", 1, 100)));
+        assert!(!deduplicator.observe(&kube_event("pod-b", "BackOff", 1, 100)));
+    }
+
+    #[test]
+    fn creates_legacy_log_event() {
+        let log = create_log_event(
+            &kube_event("pod-a", "BackOff", 2, 100),
+            LogNamespace::Legacy,
+        )
+        .unwrap();
+
+        assert_eq!(log["message"], "BackOff pod-a".into());
+        assert_eq!(log["reason"], "BackOff".into());
+        assert_eq!(log["count"], 2.into());
+        assert_eq!(
+            log.get(event_path!("involvedObject", "name")),
+            Some(&value!("pod-a"))
+        );
+        assert_eq!(
+            log[log_schema().timestamp_key().unwrap().to_string()],
+            Value::Timestamp(DateTime::from_timestamp(100, 0).unwrap())
+        );
+        assert_eq!(log["source_type"], "kubernetes_events".into());
+    }
+
+    #[test]
+    fn creates_vector_log_event() {
+        let log = create_log_event(
+            &kube_event("pod-a", "BackOff", 2, 100),
+            LogNamespace::Vector,
+        )
+        .unwrap();
+
+        assert_eq!(
+            log.get(event_path!("message")),
+            Some(&value!("BackOff pod-a"))
+        );
+        assert_eq!(
+            log.get(metadata_path!("kubernetes_events", "timestamp")),
+            Some(&Value::Timestamp(DateTime::from_timestamp(100, 0).unwrap()))
+        );
+        assert_eq!(
+            log.get(metadata_path!("vector", "source_type")),
+            Some(&value!("kubernetes_events"))
+        );
+    }
+}
@@ -2,13 +2,16 @@
 
 #![deny(missing_docs)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use k8s_openapi::api::core::v1::{Namespace, Pod};
 use kube::runtime::reflector::{store::Store, ObjectRef};
 use vector_lib::file_source::paths_provider::PathsProvider;
 
-use super::path_helpers::build_pod_logs_directory;
+use super::{
+    path_helpers::{build_pod_logs_directory, parse_log_file_path},
+    pod_metadata_annotator::find_container_status,
+};
 use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
 
 /// A paths provider implementation that uses the state obtained from the
@@ -18,6 +21,7 @@ pub struct K8sPathsProvider {
     namespace_state: Store<Namespace>,
     include_paths: Vec<glob::Pattern>,
     exclude_paths: Vec<glob::Pattern>,
+    container_selection: ContainerSelection,
 }
 
 impl K8sPathsProvider {
@@ -27,12 +31,35 @@ impl K8sPathsProvider {
         namespace_state: Store<Namespace>,
         include_paths: Vec<glob::Pattern>,
         exclude_paths: Vec<glob::Pattern>,
+        container_selection: ContainerSelection,
     ) -> Self {
         Self {
             pod_state,
             namespace_state,
             include_paths,
             exclude_paths,
+            container_selection,
+        }
+    }
+}
+
+/// The containers of a `Pod` to provide the log paths of, besides its regular containers.
+#[derive(Clone, Copy, Debug)]
+pub struct ContainerSelection {
+    /// Whether to provide the log paths of the init containers.
+    pub init_containers: bool,
+    /// Whether to provide the log paths of the ephemeral containers.
+    pub ephemeral_containers: bool,
+    /// Whether to provide the log paths of the previous instances of the restarted containers.
+    pub previous_containers: bool,
+}
+
+impl Default for ContainerSelection {
+    fn default() -> Self {
+        Self {
+            init_containers: true,
+            ephemeral_containers: true,
+            previous_containers: true,
         }
     }
 }
@@ -59,7 +86,8 @@ impl PathsProvider for K8sPathsProvider {
             })
             .flat_map(|pod| {
                 trace!(message = "Providing log paths for pod.", pod = ?pod.metadata.name);
-                let paths_iter = list_pod_log_paths(real_glob, pod.as_ref());
+                let paths_iter =
+                    list_pod_log_paths(real_glob, pod.as_ref(), self.container_selection);
                 filter_paths(
                     filter_paths(paths_iter, &self.include_paths, true),
                     &self.exclude_paths,
@@ -124,6 +152,51 @@ fn extract_excluded_containers_for_pod(pod: &Pod) -> impl Iterator<Item = &str>
     })
 }
 
+/// Lists the names of the containers of the `Pod` that are excluded by the [`ContainerSelection`].
+fn extract_unselected_containers_for_pod(
+    pod: &Pod,
+    selection: ContainerSelection,
+) -> impl Iterator<Item = &str> {
+    let spec = pod.spec.as_ref();
+    let init_containers = spec
+        .filter(|_| !selection.init_containers)
+        .and_then(|spec| spec.init_containers.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|container| container.name.as_str());
+    let ephemeral_containers = spec
+        .filter(|_| !selection.ephemeral_containers)
+        .and_then(|spec| spec.ephemeral_containers.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|container| container.name.as_str());
+    init_containers.chain(ephemeral_containers)
+}
+
+/// Whether the path is the log file of a previous instance of a restarted container.
+///
+/// `kubelet` names the log files after the restart count of the container, as in
+/// `<pod_logs_dir>/<container_name>/<restart_count>.log`, and keeps the log file of the
+/// previous instance around after a restart.
+fn is_previous_container_log(pod: &Pod, path: &Path) -> bool {
+    let Some(file_info) = path.to_str().and_then(parse_log_file_path) else {
+        return false;
+    };
+    let Some(instance) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .and_then(|restart_count| restart_count.parse::<i32>().ok())
+    else {
+        return false;
+    };
+
+    pod.status
+        .as_ref()
+        .and_then(|status| find_container_status(status, file_info.container_name))
+        .is_some_and(|status| instance < status.restart_count)
+}
+
 fn build_container_exclusion_patterns<'a>(
     pod_logs_dir: &'a str,
     containers: impl Iterator<Item = &'a str> + 'a,
@@ -137,6 +210,7 @@ fn build_container_exclusion_patterns<'a>(
 fn list_pod_log_paths<'a, G, GI>(
     mut glob_impl: G,
     pod: &'a Pod,
+    selection: ContainerSelection,
 ) -> impl Iterator<Item = PathBuf> + 'a
 where
     G: FnMut(&str) -> GI + 'a,
@@ -162,13 +236,15 @@ where
 
             // Extract the containers to exclude, then build patterns from them
             // and cache the results into a Vec.
-            let excluded_containers = extract_excluded_containers_for_pod(pod);
+            let excluded_containers = extract_excluded_containers_for_pod(pod)
+                .chain(extract_unselected_containers_for_pod(pod, selection));
             let exclusion_patterns: Vec<_> =
                 build_container_exclusion_patterns(dir, excluded_containers).collect();
 
             // Return paths filtered with container exclusion.
             filter_paths(path_iter, exclusion_patterns, false)
         })
+        .filter(move |path| selection.previous_containers || !is_previous_container_log(pod, path))
 }
 
 fn real_glob(pattern: &str) -> impl Iterator<Item = PathBuf> {
@@ -210,11 +286,14 @@ fn filter_paths<'a>(
 mod tests {
     use std::path::PathBuf;
 
-    use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::apis::meta::v1::ObjectMeta};
+    use k8s_openapi::{
+        api::core::v1::{Container, ContainerStatus, EphemeralContainer, Pod, PodSpec, PodStatus},
+        apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    };
 
     use super::{
         build_container_exclusion_patterns, extract_excluded_containers_for_pod,
-        extract_pod_logs_directory, filter_paths, list_pod_log_paths,
+        extract_pod_logs_directory, filter_paths, list_pod_log_paths, ContainerSelection,
     };
 
     #[test]
@@ -453,12 +532,75 @@ mod tests {
                 paths_to_return.into_iter().map(PathBuf::from)
             };
 
-            let actual_paths: Vec<_> = list_pod_log_paths(mock_glob, &pod).collect();
+            let actual_paths: Vec<_> =
+                list_pod_log_paths(mock_glob, &pod, ContainerSelection::default()).collect();
             let expected_paths: Vec<_> = expected_paths.into_iter().map(PathBuf::from).collect();
             assert_eq!(actual_paths, expected_paths)
         }
     }
 
+    #[test]
+    fn test_list_pod_log_paths_container_selection() {
+        let pod = Pod {
+            metadata: ObjectMeta {
+                namespace: Some("sandbox0-ns".to_owned()),
+                name: Some("sandbox0-name".to_owned()),
+                uid: Some("sandbox0-uid".to_owned()),
+                ..ObjectMeta::default()
+            },
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "app".to_owned(),
+                    ..Container::default()
+                }],
+                init_containers: Some(vec![Container {
+                    name: "setup".to_owned(),
+                    ..Container::default()
+                }]),
+                ephemeral_containers: Some(vec![EphemeralContainer {
+                    name: "debugger".to_owned(),
+                    ..EphemeralContainer::default()
+                }]),
+                ..PodSpec::default()
+            }),
+            status: Some(PodStatus {
+                container_statuses: Some(vec![ContainerStatus {
+                    name: "app".to_owned(),
+                    restart_count: 2,
+                    ..ContainerStatus::default()
+                }]),
+                ..PodStatus::default()
+            }),
+        };
+        let paths = vec![
+            "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/app/1.log",
+            "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/app/2.log",
+            "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/setup/0.log",
+            "/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/debugger/0.log",
+        ];
+
+        let cases = vec![
+            (ContainerSelection::default(), paths.clone()),
+            (
+                ContainerSelection {
+                    init_containers: false,
+                    ephemeral_containers: false,
+                    previous_containers: false,
+                },
+                vec!["/var/log/pods/sandbox0-ns_sandbox0-name_sandbox0-uid/app/2.log"],
+            ),
+        ];
+
+        for (selection, expected_paths) in cases {
+            let paths = paths.clone();
+            let mock_glob = move |_: &str| paths.clone().into_iter().map(PathBuf::from);
+
+            let actual_paths: Vec<_> = list_pod_log_paths(mock_glob, &pod, selection).collect();
+            let expected_paths: Vec<_> = expected_paths.into_iter().map(PathBuf::from).collect();
+            assert_eq!(actual_paths, expected_paths, "failed for {:?}", selection)
+        }
+    }
+
     #[test]
     fn test_exclude_paths() {
         let cases = vec![
@@ -11,7 +11,7 @@ use chrono::Utc;
 use futures::{future::FutureExt, stream::StreamExt};
 use futures_util::Stream;
 use k8s_openapi::api::core::v1::{Namespace, Node, Pod};
-use k8s_paths_provider::K8sPathsProvider;
+use k8s_paths_provider::{ContainerSelection, K8sPathsProvider};
 use kube::{
     api::Api,
     config::{self, KubeConfigOptions},
@@ -23,8 +23,8 @@ use serde_with::serde_as;
 use vector_lib::codecs::{BytesDeserializer, BytesDeserializerConfig};
 use vector_lib::configurable::configurable_component;
 use vector_lib::file_source::{
-    calculate_ignore_before, Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy,
-    Fingerprinter, Line, ReadFrom, ReadFromConfig,
+    calculate_ignore_before, Checkpointer, DiscoveryMode, FileServer, FileServerShutdown,
+    FingerprintStrategy, Fingerprinter, Line, ReadFrom, ReadFromConfig,
 };
 use vector_lib::lookup::{lookup_v2::OptionalTargetPath, owned_value_path, path, OwnedTargetPath};
 use vector_lib::{config::LegacyKey, config::LogNamespace, EstimatedJsonEncodedSizeOf};
@@ -152,6 +152,25 @@ pub struct Config {
     #[configurable(derived)]
     node_annotation_fields: node_metadata_annotator::FieldsSpec,
 
+    /// Whether or not to collect the logs of the [init containers][init_containers] of Pods.
+    ///
+    /// [init_containers]: https://kubernetes.io/docs/concepts/workloads/pods/init-containers/
+    include_init_containers: bool,
+
+    /// Whether or not to collect the logs of the [ephemeral containers][ephemeral_containers] of
+    /// Pods, such as the ones added by `kubectl debug`.
+    ///
+    /// [ephemeral_containers]: https://kubernetes.io/docs/concepts/workloads/pods/ephemeral-containers/
+    include_ephemeral_containers: bool,
+
+    /// Whether or not to collect the logs of the previous instances of restarted containers.
+    ///
+    /// The `kubelet` keeps the log file of the previous instance of a container after it restarts,
+    /// such as when it is crash looping. When enabled, the rest of that log file is collected as
+    /// well, so that the last logs before a crash are available for post-mortem analysis. When
+    /// disabled, only the log files of the current instances of containers are collected.
+    include_previous_container_logs: bool,
+
     /// A list of glob patterns to include while reading the files.
     #[configurable(metadata(docs::examples = "**/include/**"))]
     include_paths_glob_patterns: Vec<PathBuf>,
@@ -283,6 +302,9 @@ impl Default for Config {
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
             node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
+            include_init_containers: true,
+            include_ephemeral_containers: true,
+            include_previous_container_logs: true,
             include_paths_glob_patterns: default_path_inclusion(),
             exclude_paths_glob_patterns: default_path_exclusion(),
             read_from: default_read_from(),
@@ -369,6 +391,18 @@ impl SourceConfig for Config {
                 Kind::bytes().or_undefined(),
                 None,
             )
+            .with_source_metadata(
+                Self::NAME,
+                self.pod_annotation_fields
+                    .container_type
+                    .path
+                    .clone()
+                    .map(|k| k.path)
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("container_type"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
             .with_source_metadata(
                 Self::NAME,
                 self.namespace_annotation_fields
@@ -544,6 +578,7 @@ struct Source {
     self_node_name: String,
     include_paths: Vec<glob::Pattern>,
     exclude_paths: Vec<glob::Pattern>,
+    container_selection: ContainerSelection,
     read_from: ReadFrom,
     ignore_older_secs: Option<u64>,
     max_read_bytes: usize,
@@ -627,6 +662,11 @@ impl Source {
             self_node_name,
             include_paths,
             exclude_paths,
+            container_selection: ContainerSelection {
+                init_containers: config.include_init_containers,
+                ephemeral_containers: config.include_ephemeral_containers,
+                previous_containers: config.include_previous_container_logs,
+            },
             read_from: ReadFrom::from(config.read_from),
             ignore_older_secs: config.ignore_older_secs,
             max_read_bytes: config.max_read_bytes,
@@ -662,6 +702,7 @@ impl Source {
             self_node_name,
             include_paths,
             exclude_paths,
+            container_selection,
             read_from,
             ignore_older_secs,
             max_read_bytes,
@@ -758,6 +799,7 @@ impl Source {
             ns_state.clone(),
             include_paths,
             exclude_paths,
+            container_selection,
         );
         let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec, log_namespace);
         let ns_annotator =
@@ -798,6 +840,11 @@ impl Source {
             // This value specifies not exactly the globbing, but interval
             // between the polling the files to watch from the `paths_provider`.
             glob_minimum_cooldown,
+            // The pod log files are found through the Kubernetes API rather
+            // than by searching the file system, so we keep polling them.
+            discovery_mode: DiscoveryMode::Poll,
+            // `kubelet` doesn't compress the rotated log files.
+            read_archives: false,
             // The shape of the log files is well-known in the Kubernetes
             // environment, so we pick the a specially crafted fingerprinter
             // for the log files.
@@ -810,6 +857,7 @@ impl Source {
                 },
                 max_line_length: max_line_bytes,
                 ignore_not_found: true,
+                decompress_gzip: false,
             },
             oldest_first,
             // We do not remove the log files, `kubelet` is responsible for it.
@@ -3,7 +3,7 @@
 #![deny(missing_docs)]
 
 use k8s_openapi::{
-    api::core::v1::{Container, ContainerStatus, Pod, PodSpec, PodStatus},
+    api::core::v1::{ContainerStatus, Pod, PodSpec, PodStatus},
     apimachinery::pkg::apis::meta::v1::ObjectMeta,
 };
 use kube::runtime::reflector::{store::Store, ObjectRef};
@@ -128,6 +128,16 @@ pub struct FieldsSpec {
     #[configurable(metadata(docs::examples = "k8s.container_image_id"))]
     #[configurable(metadata(docs::examples = ""))]
     pub container_image_id: OptionalTargetPath,
+
+    /// Event field for the Container's type.
+    ///
+    /// The type is one of `container`, `init`, or `ephemeral`.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.container_type"))]
+    #[configurable(metadata(docs::examples = "k8s.container_type"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub container_type: OptionalTargetPath,
 }
 
 impl Default for FieldsSpec {
@@ -166,10 +176,81 @@ impl Default for FieldsSpec {
                 "container_image_id"
             ))
             .into(),
+            container_type: OwnedTargetPath::event(owned_value_path!(
+                "kubernetes",
+                "container_type"
+            ))
+            .into(),
         }
     }
 }
 
+/// The type of a container of a `Pod`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerType {
+    /// A regular application container.
+    Container,
+    /// An init container, run to completion before the regular containers start.
+    Init,
+    /// An ephemeral container, added to a running `Pod` for debugging.
+    Ephemeral,
+}
+
+impl ContainerType {
+    /// The name of the container type, as added to events.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Container => "container",
+            Self::Init => "init",
+            Self::Ephemeral => "ephemeral",
+        }
+    }
+}
+
+/// Finds the container with the given name in the [`PodSpec`], returning its type and image.
+pub fn find_container<'a>(
+    pod_spec: &'a PodSpec,
+    container_name: &str,
+) -> Option<(ContainerType, Option<&'a String>)> {
+    let containers = pod_spec
+        .containers
+        .iter()
+        .map(|c| (ContainerType::Container, &c.name, c.image.as_ref()));
+    let init_containers = pod_spec
+        .init_containers
+        .iter()
+        .flatten()
+        .map(|c| (ContainerType::Init, &c.name, c.image.as_ref()));
+    let ephemeral_containers = pod_spec
+        .ephemeral_containers
+        .iter()
+        .flatten()
+        .map(|c| (ContainerType::Ephemeral, &c.name, c.image.as_ref()));
+
+    containers
+        .chain(init_containers)
+        .chain(ephemeral_containers)
+        .find(|(_, name, _)| *name == container_name)
+        .map(|(container_type, _, image)| (container_type, image))
+}
+
+/// Finds the status of the container with the given name in the [`PodStatus`], whatever the type
+/// of the container.
+pub fn find_container_status<'a>(
+    pod_status: &'a PodStatus,
+    container_name: &str,
+) -> Option<&'a ContainerStatus> {
+    [
+        &pod_status.container_statuses,
+        &pod_status.init_container_statuses,
+        &pod_status.ephemeral_container_statuses,
+    ]
+    .into_iter()
+    .flatten()
+    .flatten()
+    .find(|c| c.name == container_name)
+}
+
 /// Annotate the event with pod metadata.
 pub struct PodMetadataAnnotator {
     pods_state_reader: Store<Pod>,
@@ -204,33 +285,33 @@ impl PodMetadataAnnotator {
         annotate_from_file_info(log, &self.fields_spec, &file_info, self.log_namespace);
         annotate_from_metadata(log, &self.fields_spec, &pod.metadata, self.log_namespace);
 
-        let container;
         if let Some(ref pod_spec) = pod.spec {
             annotate_from_pod_spec(log, &self.fields_spec, pod_spec, self.log_namespace);
 
-            container = pod_spec
-                .containers
-                .iter()
-                .find(|c| c.name == file_info.container_name);
-            if let Some(container) = container {
-                annotate_from_container(log, &self.fields_spec, container, self.log_namespace);
+            if let Some((container_type, image)) =
+                find_container(pod_spec, file_info.container_name)
+            {
+                annotate_from_container(
+                    log,
+                    &self.fields_spec,
+                    container_type,
+                    image,
+                    self.log_namespace,
+                );
             }
         }
 
         if let Some(ref pod_status) = pod.status {
             annotate_from_pod_status(log, &self.fields_spec, pod_status, self.log_namespace);
-            if let Some(ref container_statuses) = pod_status.container_statuses {
-                let container_status = container_statuses
-                    .iter()
-                    .find(|c| c.name == file_info.container_name);
-                if let Some(container_status) = container_status {
-                    annotate_from_container_status(
-                        log,
-                        &self.fields_spec,
-                        container_status,
-                        self.log_namespace,
-                    )
-                }
+            if let Some(container_status) =
+                find_container_status(pod_status, file_info.container_name)
+            {
+                annotate_from_container_status(
+                    log,
+                    &self.fields_spec,
+                    container_status,
+                    self.log_namespace,
+                )
             }
         }
         Some(file_info)
@@ -455,10 +536,26 @@ fn annotate_from_container_status(
 fn annotate_from_container(
     log: &mut LogEvent,
     fields_spec: &FieldsSpec,
-    container: &Container,
+    container_type: ContainerType,
+    image: Option<&String>,
     log_namespace: LogNamespace,
 ) {
-    if let Some(value) = &container.image {
+    let legacy_key = fields_spec
+        .container_type
+        .path
+        .as_ref()
+        .map(|k| &k.path)
+        .map(LegacyKey::Overwrite);
+
+    log_namespace.insert_source_metadata(
+        Config::NAME,
+        log,
+        legacy_key,
+        path!("container_type"),
+        container_type.as_str(),
+    );
+
+    if let Some(value) = image {
         let legacy_key = fields_spec
             .container_image
             .path
@@ -478,7 +575,7 @@ fn annotate_from_container(
 
 #[cfg(test)]
 mod tests {
-    use k8s_openapi::api::core::v1::PodIP;
+    use k8s_openapi::api::core::v1::{Container, EphemeralContainer, PodIP};
     use similar_asserts::assert_eq;
     use vector_lib::lookup::{event_path, metadata_path};
 
@@ -995,18 +1092,25 @@ mod tests {
         let cases = vec![
             (
                 FieldsSpec::default(),
+                ContainerType::Container,
                 Container::default(),
-                LogEvent::default(),
+                {
+                    let mut log = LogEvent::default();
+                    log.insert(event_path!("kubernetes", "container_type"), "container");
+                    log
+                },
                 LogNamespace::Legacy,
             ),
             (
                 FieldsSpec::default(),
+                ContainerType::Container,
                 Container {
                     image: Some("sandbox0-container-image".to_owned()),
                     ..Default::default()
                 },
                 {
                     let mut log = LogEvent::default();
+                    log.insert(event_path!("kubernetes", "container_type"), "container");
                     log.insert(
                         event_path!("kubernetes", "container_image"),
                         "sandbox0-container-image",
@@ -1019,8 +1123,10 @@ mod tests {
                 FieldsSpec {
                     container_image: OwnedTargetPath::event(owned_value_path!("container_image"))
                         .into(),
+                    container_type: OptionalTargetPath::none(),
                     ..Default::default()
                 },
+                ContainerType::Init,
                 Container {
                     image: Some("sandbox0-container-image".to_owned()),
                     ..Default::default()
@@ -1032,12 +1138,68 @@ mod tests {
                 },
                 LogNamespace::Legacy,
             ),
+            (
+                FieldsSpec::default(),
+                ContainerType::Ephemeral,
+                Container::default(),
+                {
+                    let mut log = LogEvent::default();
+                    log.insert(
+                        metadata_path!("kubernetes_logs", "container_type"),
+                        "ephemeral",
+                    );
+                    log
+                },
+                LogNamespace::Vector,
+            ),
         ];
 
-        for (fields_spec, container, expected, log_namespace) in cases.into_iter() {
+        for (fields_spec, container_type, container, expected, log_namespace) in cases.into_iter() {
             let mut log = LogEvent::default();
-            annotate_from_container(&mut log, &fields_spec, &container, log_namespace);
+            annotate_from_container(
+                &mut log,
+                &fields_spec,
+                container_type,
+                container.image.as_ref(),
+                log_namespace,
+            );
             assert_eq!(log, expected);
         }
     }
+
+    #[test]
+    fn test_find_container() {
+        let pod_spec = PodSpec {
+            containers: vec![Container {
+                name: "app".to_owned(),
+                image: Some("app-image".to_owned()),
+                ..Default::default()
+            }],
+            init_containers: Some(vec![Container {
+                name: "setup".to_owned(),
+                image: Some("setup-image".to_owned()),
+                ..Default::default()
+            }]),
+            ephemeral_containers: Some(vec![EphemeralContainer {
+                name: "debugger".to_owned(),
+                image: Some("debugger-image".to_owned()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            find_container(&pod_spec, "app"),
+            Some((ContainerType::Container, Some(&"app-image".to_owned())))
+        );
+        assert_eq!(
+            find_container(&pod_spec, "setup"),
+            Some((ContainerType::Init, Some(&"setup-image".to_owned())))
+        );
+        assert_eq!(
+            find_container(&pod_spec, "debugger"),
+            Some((ContainerType::Ephemeral, Some(&"debugger-image".to_owned())))
+        );
+        assert_eq!(find_container(&pod_spec, "missing"), None);
+    }
 }
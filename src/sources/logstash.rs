@@ -110,7 +110,12 @@ impl LogstashConfig {
                 Self::NAME,
                 tls_client_metadata_path,
                 &owned_value_path!("tls_client_metadata"),
-                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                Kind::object(
+                    Collection::empty().with_unknown(
+                        Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                    ),
+                )
+                .or_undefined(),
                 None,
             )
     }
@@ -873,7 +878,10 @@ mod test {
                 )
                 .with_metadata_field(
                     &owned_value_path!(LogstashConfig::NAME, "tls_client_metadata"),
-                    Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                    Kind::object(Collection::empty().with_unknown(
+                        Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                    ))
+                    .or_undefined(),
                     None,
                 );
 
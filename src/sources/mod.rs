@@ -13,6 +13,10 @@ pub mod aws_kinesis_firehose;
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub mod azure_event_hubs;
+#[cfg(feature = "sources-database")]
+pub mod database;
 #[cfg(feature = "sources-datadog_agent")]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -34,8 +38,12 @@ pub mod file;
 pub mod file_descriptors;
 #[cfg(feature = "sources-fluent")]
 pub mod fluent;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+pub mod gcp_cloud_storage;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-grpc")]
+pub mod grpc;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -52,16 +60,24 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+pub mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(feature = "sources-logstash")]
 pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-mqtt")]
+pub mod mqtt;
 #[cfg(feature = "sources-nats")]
 pub mod nats;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-object_store")]
+pub mod object_store;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
@@ -76,6 +92,8 @@ pub mod prometheus;
 pub mod pulsar;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-sflow")]
+pub mod sflow;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -88,6 +106,10 @@ pub mod statsd;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-webhook")]
+pub mod webhook;
+#[cfg(all(windows, feature = "sources-windows_eventlog"))]
+pub mod windows_eventlog;
 
 pub mod util;
 
@@ -0,0 +1,773 @@
+//! `MQTT` source.
+//! Subscribes to topic filters on an MQTT 3.1.1 or MQTT 5 broker.
+use std::{borrow::Cow, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{FutureExt, StreamExt};
+use rand::Rng;
+use rumqttc::{v5, MqttOptions, QoS, SubscribeFilter, TlsConfiguration, Transport};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc;
+use tokio_util::codec::FramedRead;
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
+    finalizer::OrderedFinalizer,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{SourceConfig, SourceContext, SourceOutput},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        MqttAckError, MqttSourceConnectionError, MqttSubscribeError, StreamClosedError,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsEnableableConfig, TlsError},
+    SourceSender,
+};
+
+/// The capacity of the channels between the client, the event loop and the source.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// The delay before polling the event loop again, which reconnects to the broker, after a
+/// connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("TLS error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("Client ID is not allowed to be empty."))]
+    EmptyClientId,
+    #[snafu(display("At least one topic filter must be provided."))]
+    NoTopics,
+    #[snafu(display("Username and password must be either both provided or both missing."))]
+    InvalidCredentials,
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source(
+    "mqtt",
+    "Collect observability data published to topics on an MQTT broker."
+))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    /// MQTT server address (The broker’s domain name or IP address).
+    #[configurable(metadata(docs::examples = "mqtt.example.com", docs::examples = "127.0.0.1"))]
+    #[derivative(Default(value = "\"localhost\".into()"))]
+    pub host: String,
+
+    /// TCP port of the MQTT server to connect to.
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    pub port: u16,
+
+    /// MQTT username.
+    pub user: Option<String>,
+
+    /// MQTT password.
+    pub password: Option<String>,
+
+    /// MQTT client ID.
+    ///
+    /// A stable client ID is required for the broker to keep the session, and the messages that
+    /// are not yet acknowledged, across reconnections.
+    pub client_id: Option<String>,
+
+    /// Connection keep-alive interval.
+    #[serde(default = "default_keep_alive")]
+    #[derivative(Default(value = "default_keep_alive()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub keep_alive: u16,
+
+    /// If set to true, the MQTT session is cleaned on login.
+    ///
+    /// With MQTT 5, this sets the `Clean Start` flag.
+    #[serde(default)]
+    pub clean_session: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol_version: MqttProtocolVersion,
+
+    /// The MQTT topic filters to subscribe to.
+    ///
+    /// The `+` wildcard matches a single topic level and the `#` wildcard matches any number of
+    /// trailing topic levels.
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "devices/#"))]
+    pub topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub quality_of_service: MqttQoS,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the topic the message was published to.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    pub topic_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+/// The version of the MQTT protocol used to connect to the broker.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MqttProtocolVersion {
+    /// MQTT 3.1.1.
+    #[default]
+    #[serde(rename = "3.1.1")]
+    V3_1_1,
+
+    /// MQTT 5.
+    #[serde(rename = "5")]
+    V5,
+}
+
+/// Supported Quality of Service types for MQTT.
+///
+/// This is the maximum quality of service the broker uses to deliver the messages. With
+/// `atleastonce` and `exactlyonce`, a message is only acknowledged to the broker once it has been
+/// processed, and when end-to-end acknowledgements are enabled, once it has been delivered by the
+/// sinks.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::enum_variant_names)]
+pub enum MqttQoS {
+    /// AtLeastOnce.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// AtMostOnce.
+    AtMostOnce,
+
+    /// ExactlyOnce.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+impl From<MqttQoS> for v5::mqttbytes::QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => v5::mqttbytes::QoS::AtLeastOnce,
+            MqttQoS::AtMostOnce => v5::mqttbytes::QoS::AtMostOnce,
+            MqttQoS::ExactlyOnce => v5::mqttbytes::QoS::ExactlyOnce,
+        }
+    }
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive() -> u16 {
+    60
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl_generate_config_from_default!(MqttSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+        let (client, event_loop) = self.connect()?;
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            client,
+            event_loop,
+            decoder,
+            log_namespace,
+            acknowledgements,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl MqttSourceConfig {
+    fn connect(&self) -> Result<(MqttClient, MqttEventLoop), BuildError> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics);
+        }
+
+        let client_id = self.client_id.clone().unwrap_or_else(|| {
+            let hash = rand::thread_rng()
+                .sample_iter(&rand_distr::Alphanumeric)
+                .take(6)
+                .map(char::from)
+                .collect::<String>();
+            format!("vectorSource{hash}")
+        });
+        if client_id.is_empty() {
+            return Err(BuildError::EmptyClientId);
+        }
+
+        let credentials = match (&self.user, &self.password) {
+            (Some(user), Some(password)) => Some((user, password)),
+            (None, None) => None,
+            _ => return Err(BuildError::InvalidCredentials),
+        };
+
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        let transport = tls.tls().map(|tls| {
+            Transport::Tls(TlsConfiguration::Simple {
+                ca: tls.authorities_pem().flatten().collect(),
+                client_auth: None,
+                alpn: Some(vec!["mqtt".into()]),
+            })
+        });
+
+        let keep_alive = Duration::from_secs(self.keep_alive.into());
+        Ok(match self.protocol_version {
+            MqttProtocolVersion::V3_1_1 => {
+                let mut options = MqttOptions::new(&client_id, &self.host, self.port);
+                options.set_keep_alive(keep_alive);
+                options.set_clean_session(self.clean_session);
+                options.set_manual_acks(true);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                if let Some(transport) = transport {
+                    options.set_transport(transport);
+                }
+                let (client, event_loop) = rumqttc::AsyncClient::new(options, CHANNEL_CAPACITY);
+                (MqttClient::V3(client), MqttEventLoop::V3(event_loop))
+            }
+            MqttProtocolVersion::V5 => {
+                let mut options = v5::MqttOptions::new(&client_id, &self.host, self.port);
+                options.set_keep_alive(keep_alive);
+                options.set_clean_start(self.clean_session);
+                options.set_manual_acks(true);
+                if let Some((user, password)) = credentials {
+                    options.set_credentials(user, password);
+                }
+                if let Some(transport) = transport {
+                    options.set_transport(transport);
+                }
+                let (client, event_loop) = v5::AsyncClient::new(options, CHANNEL_CAPACITY);
+                (MqttClient::V5(client), MqttEventLoop::V5(event_loop))
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+enum MqttClient {
+    V3(rumqttc::AsyncClient),
+    V5(v5::AsyncClient),
+}
+
+impl MqttClient {
+    /// Subscribes to the topic filters, without waiting on the event loop.
+    fn subscribe(&self, topics: &[String], qos: MqttQoS) -> crate::Result<()> {
+        match self {
+            Self::V3(client) => client.try_subscribe_many(
+                topics
+                    .iter()
+                    .map(|topic| SubscribeFilter::new(topic.clone(), qos.into())),
+            )?,
+            Self::V5(client) => client.try_subscribe_many(
+                topics
+                    .iter()
+                    .map(|topic| v5::mqttbytes::v5::Filter::new(topic.clone(), qos.into())),
+            )?,
+        }
+        Ok(())
+    }
+
+    async fn ack(&self, publish: &MqttPublish) -> crate::Result<()> {
+        match (self, publish) {
+            (Self::V3(client), MqttPublish::V3(publish)) => client.ack(publish).await?,
+            (Self::V5(client), MqttPublish::V5(publish)) => client.ack(publish).await?,
+            _ => unreachable!("the publish is received with the same protocol version"),
+        }
+        Ok(())
+    }
+
+    fn disconnect(&self) {
+        // The event loop is stopped right after, so a failure to send the disconnection is
+        // not worth reporting.
+        _ = match self {
+            Self::V3(client) => client.try_disconnect().map_err(crate::Error::from),
+            Self::V5(client) => client.try_disconnect().map_err(crate::Error::from),
+        };
+    }
+}
+
+enum MqttEventLoop {
+    V3(rumqttc::EventLoop),
+    V5(v5::EventLoop),
+}
+
+enum MqttIncoming {
+    ConnAck,
+    Publish(MqttPublish),
+    Other,
+}
+
+impl MqttEventLoop {
+    async fn poll(&mut self) -> crate::Result<MqttIncoming> {
+        Ok(match self {
+            Self::V3(event_loop) => match event_loop.poll().await? {
+                rumqttc::Event::Incoming(rumqttc::Packet::ConnAck(_)) => MqttIncoming::ConnAck,
+                rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish)) => {
+                    MqttIncoming::Publish(MqttPublish::V3(publish))
+                }
+                _ => MqttIncoming::Other,
+            },
+            Self::V5(event_loop) => match event_loop.poll().await? {
+                v5::Event::Incoming(v5::mqttbytes::v5::Packet::ConnAck(_)) => MqttIncoming::ConnAck,
+                v5::Event::Incoming(v5::mqttbytes::v5::Packet::Publish(publish)) => {
+                    MqttIncoming::Publish(MqttPublish::V5(publish))
+                }
+                _ => MqttIncoming::Other,
+            },
+        })
+    }
+}
+
+#[derive(Debug)]
+enum MqttPublish {
+    V3(rumqttc::Publish),
+    V5(v5::mqttbytes::v5::Publish),
+}
+
+impl MqttPublish {
+    fn topic(&self) -> Cow<'_, str> {
+        match self {
+            Self::V3(publish) => Cow::Borrowed(&publish.topic),
+            Self::V5(publish) => String::from_utf8_lossy(&publish.topic),
+        }
+    }
+
+    /// Takes the payload out of the message, leaving what is needed to acknowledge it.
+    fn take_payload(&mut self) -> Bytes {
+        match self {
+            Self::V3(publish) => std::mem::take(&mut publish.payload),
+            Self::V5(publish) => std::mem::take(&mut publish.payload),
+        }
+    }
+}
+
+/// Polls the event loop, subscribing to the topic filters on every (re)connection, and forwards
+/// the received messages to the source.
+async fn run_event_loop(
+    mut event_loop: MqttEventLoop,
+    client: MqttClient,
+    topics: Vec<String>,
+    qos: MqttQoS,
+    messages: mpsc::Sender<MqttPublish>,
+) {
+    loop {
+        match event_loop.poll().await {
+            Ok(MqttIncoming::ConnAck) => {
+                if let Err(error) = client.subscribe(&topics, qos) {
+                    emit!(MqttSubscribeError { error });
+                }
+            }
+            Ok(MqttIncoming::Publish(publish)) => {
+                if messages.send(publish).await.is_err() {
+                    break;
+                }
+            }
+            Ok(MqttIncoming::Other) => {}
+            Err(error) => {
+                emit!(MqttSourceConnectionError { error });
+                if messages.is_closed() {
+                    break;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    client: MqttClient,
+    event_loop: MqttEventLoop,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        OrderedFinalizer::<MqttPublish>::maybe_new(acknowledgements, Some(shutdown.clone()));
+
+    let (tx, mut messages) = mpsc::channel(CHANNEL_CAPACITY);
+    let event_loop = tokio::spawn(run_event_loop(
+        event_loop,
+        client.clone(),
+        config.topics.clone(),
+        config.quality_of_service,
+        tx,
+    ));
+
+    let bytes_received = register!(BytesReceived::from(Protocol::from("mqtt")));
+    let events_received = register!(EventsReceived);
+    let mut shutdown = shutdown.fuse();
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, publish)) = entry {
+                    handle_ack(&client, status, publish).await;
+                }
+            },
+            publish = messages.recv() => {
+                let Some(mut publish) = publish else { break };
+                let payload = publish.take_payload();
+                bytes_received.emit(ByteSize(payload.len()));
+
+                let topic = publish.topic().into_owned();
+                let now = Utc::now();
+                let mut events = Vec::new();
+                let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
+                while let Some(next) = stream.next().await {
+                    match next {
+                        Ok((decoded, _byte_size)) => events.extend(decoded),
+                        Err(error) => {
+                            // Error is logged by `crate::codecs`, no further
+                            // handling is needed here.
+                            if !error.can_continue() {
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                for event in &mut events {
+                    if let Event::Log(log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            MqttSourceConfig::NAME,
+                            now,
+                        );
+                        log_namespace.insert_source_metadata(
+                            MqttSourceConfig::NAME,
+                            log,
+                            config.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                            path!("topic"),
+                            topic.as_str(),
+                        );
+                    }
+                }
+
+                match &finalizer {
+                    Some(finalizer) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        let events = events
+                            .into_iter()
+                            .map(|event| event.with_batch_notifier(&batch));
+                        if out.send_batch(events).await.is_err() {
+                            emit!(StreamClosedError { count });
+                            break;
+                        }
+                        finalizer.add(publish, receiver);
+                    }
+                    None => {
+                        if out.send_batch(events).await.is_err() {
+                            emit!(StreamClosedError { count });
+                            break;
+                        }
+                        if let Err(error) = client.ack(&publish).await {
+                            emit!(MqttAckError { error });
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    client.disconnect();
+    event_loop.abort();
+    Ok(())
+}
+
+async fn handle_ack(client: &MqttClient, status: BatchStatus, publish: MqttPublish) {
+    match status {
+        // Rejected events are dropped by the sinks, so the message is not worth redelivering.
+        BatchStatus::Delivered | BatchStatus::Rejected => {
+            if let Err(error) = client.ack(&publish).await {
+                emit!(MqttAckError { error });
+            }
+        }
+        // Leaving the message unacknowledged lets the broker redeliver it when the session is
+        // resumed.
+        BatchStatus::Errored => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use vector_lib::lookup::OwnedTargetPath;
+    use vector_lib::schema::Definition;
+    use vrl::value::kind::Collection;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "mqtt.example.com"
+            topics = ["sensors/+/temperature", "devices/#"]
+            protocol_version = "5"
+            quality_of_service = "exactlyonce"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.protocol_version, MqttProtocolVersion::V5);
+        assert_eq!(config.topics, ["sensors/+/temperature", "devices/#"]);
+        assert!(matches!(config.quality_of_service, MqttQoS::ExactlyOnce));
+        assert_eq!(config.port, 1883);
+    }
+
+    #[test]
+    fn invalid_configs() {
+        let config = MqttSourceConfig::default();
+        assert!(matches!(config.connect(), Err(BuildError::NoTopics)));
+
+        let config = MqttSourceConfig {
+            topics: vec!["test".into()],
+            user: Some("user".into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.connect(),
+            Err(BuildError::InvalidCredentials)
+        ));
+
+        let config = MqttSourceConfig {
+            topics: vec!["test".into()],
+            client_id: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(matches!(config.connect(), Err(BuildError::EmptyClientId)));
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = MqttSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definitions = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(&owned_value_path!("mqtt", "topic"), Kind::bytes(), None);
+
+        assert_eq!(definitions, Some(expected_definition));
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = MqttSourceConfig::default();
+
+        let definitions = config
+            .outputs(LogNamespace::Legacy)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes(),
+            Some("message"),
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("topic"), Kind::bytes(), None);
+
+        assert_eq!(definitions, Some(expected_definition));
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use rumqttc::AsyncClient;
+    use vector_lib::config::log_schema;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        random_string,
+    };
+
+    fn mqtt_broker_address() -> String {
+        std::env::var("MQTT_BROKER_ADDRESS").unwrap_or_else(|_| "emqx".into())
+    }
+
+    fn mqtt_broker_port() -> u16 {
+        std::env::var("MQTT_BROKER_PORT")
+            .unwrap_or_else(|_| "1883".into())
+            .parse::<u16>()
+            .expect("Cannot parse as u16")
+    }
+
+    #[tokio::test]
+    async fn mqtt_source_receives_messages() {
+        let topic = format!("test-{}", random_string(10));
+        let published_topic = format!("{topic}/device");
+        let config = MqttSourceConfig {
+            host: mqtt_broker_address(),
+            port: mqtt_broker_port(),
+            topics: vec![format!("{topic}/+")],
+            ..Default::default()
+        };
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            let options = MqttOptions::new(
+                "integration-test-producer",
+                mqtt_broker_address(),
+                mqtt_broker_port(),
+            );
+            let (client, mut event_loop) = AsyncClient::new(options, 10);
+            tokio::spawn(async move { while event_loop.poll().await.is_ok() {} });
+
+            // Give the source time to subscribe.
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            client
+                .publish(
+                    published_topic.clone(),
+                    QoS::AtLeastOnce,
+                    false,
+                    "my message",
+                )
+                .await
+                .unwrap();
+
+            collect_n(rx, 1).await
+        })
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().message_key().unwrap().to_string()],
+            "my message".into()
+        );
+        assert_eq!(log["topic"], published_topic.into());
+    }
+}
@@ -1,5 +1,6 @@
+use async_nats::jetstream::{self, consumer::pull, AckKind};
 use chrono::Utc;
-use futures::{pin_mut, StreamExt};
+use futures::{pin_mut, FutureExt, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
@@ -9,7 +10,8 @@ use vector_lib::internal_event::{
 };
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
 use vector_lib::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
+    finalizer::UnorderedFinalizer,
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -17,10 +19,10 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
-    internal_events::StreamClosedError,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsJetStreamAckError, NatsJetStreamReceiveError, StreamClosedError},
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -34,6 +36,8 @@ enum BuildError {
     Connect { source: async_nats::ConnectError },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: async_nats::SubscribeError },
+    #[snafu(display("NATS JetStream Error: {}", source))]
+    JetStream { source: async_nats::Error },
 }
 
 /// Configuration for the `nats` source.
@@ -74,8 +78,13 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// The NATS queue group to join.
+    ///
+    /// This is ignored when consuming from JetStream.
     queue: Option<String>,
 
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -112,6 +121,44 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_subscription_capacity")]
     #[derivative(Default(value = "default_subscription_capacity()"))]
     subscriber_capacity: usize,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Configuration for consuming from a [JetStream][jetstream] stream with a durable pull consumer.
+///
+/// When set, the `subject` is used as the filter subject of the consumer, and the instances of
+/// the source sharing the same durable consumer share the messages of the stream.
+///
+/// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume from.
+    #[configurable(metadata(docs::examples = "events"))]
+    stream: String,
+
+    /// The name of the durable consumer.
+    ///
+    /// The consumer is created with explicit acknowledgements if it does not exist. A message is
+    /// acknowledged once it has been processed, or, when end-to-end acknowledgements are enabled,
+    /// once it has been delivered by the sinks. Messages that fail to be delivered are redelivered,
+    /// and messages rejected by the sinks are terminated.
+    #[configurable(metadata(docs::examples = "vector"))]
+    durable_name: String,
+
+    /// The maximum number of messages that are delivered but not yet acknowledged.
+    ///
+    /// The server stops delivering messages to the consumer once this limit is reached.
+    #[serde(default = "default_max_in_flight")]
+    max_in_flight: usize,
+}
+
+const fn default_max_in_flight() -> usize {
+    1000
 }
 
 fn default_subject_key_field() -> OptionalValuePath {
@@ -139,11 +186,26 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
+        if let Some(jetstream) = &self.jetstream {
+            let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+            let (connection, messages) = create_jetstream_consumer(self, jetstream).await?;
+            return Ok(Box::pin(jetstream_source(
+                self.clone(),
+                connection,
+                messages,
+                decoder,
+                log_namespace,
+                acknowledgements,
+                cx.shutdown,
+                cx.out,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
         Ok(Box::pin(nats_source(
             self.clone(),
             connection,
@@ -181,7 +243,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -231,57 +293,161 @@ async fn nats_source(
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
         bytes_received.emit(ByteSize(msg.payload.len()));
-        let mut stream = FramedRead::new(msg.payload.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-
-                            let legacy_subject_key_field = config
-                                .subject_key_field
-                                .path
-                                .as_ref()
-                                .map(LegacyKey::InsertIfEmpty);
-                            log_namespace.insert_source_metadata(
-                                NatsSourceConfig::NAME,
-                                log,
-                                legacy_subject_key_field,
-                                &owned_value_path!("subject"),
-                                msg.subject.as_str(),
-                            )
-                        }
-                        event
-                    });
+        let events = decode_message(
+            &config,
+            &decoder,
+            log_namespace,
+            msg.subject.as_str(),
+            &msg.payload,
+        )
+        .await;
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })?;
+    }
+    Ok(())
+}
 
-                    out.send_batch(events).await.map_err(|_| {
-                        emit!(StreamClosedError { count });
-                    })?;
+#[allow(clippy::too_many_arguments)]
+async fn jetstream_source(
+    config: NatsSourceConfig,
+    // Take ownership of the connection so it doesn't get dropped.
+    _connection: async_nats::Client,
+    mut messages: pull::Stream,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) = UnorderedFinalizer::<jetstream::Message>::maybe_new(
+        acknowledgements,
+        Some(shutdown.clone()),
+    );
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+    let mut shutdown = shutdown.fuse();
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, msg)) = entry {
+                    handle_ack(status, msg).await;
                 }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+            },
+            msg = messages.next() => {
+                let msg = match msg {
+                    None => break,
+                    Some(Err(error)) => {
+                        emit!(NatsJetStreamReceiveError { error: error.into() });
+                        continue;
+                    }
+                    Some(Ok(msg)) => msg,
+                };
+
+                bytes_received.emit(ByteSize(msg.payload.len()));
+                let events = decode_message(
+                    &config,
+                    &decoder,
+                    log_namespace,
+                    msg.subject.as_str(),
+                    &msg.payload,
+                )
+                .await;
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                match &finalizer {
+                    Some(finalizer) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        let events = events
+                            .into_iter()
+                            .map(|event| event.with_batch_notifier(&batch));
+                        out.send_batch(events).await.map_err(|_| {
+                            emit!(StreamClosedError { count });
+                        })?;
+                        finalizer.add(msg, receiver);
+                    }
+                    None => {
+                        out.send_batch(events).await.map_err(|_| {
+                            emit!(StreamClosedError { count });
+                        })?;
+                        if let Err(error) = msg.ack().await {
+                            emit!(NatsJetStreamAckError { error });
+                        }
                     }
                 }
-            }
+            },
         }
     }
     Ok(())
 }
 
+async fn handle_ack(status: BatchStatus, msg: jetstream::Message) {
+    let result = match status {
+        BatchStatus::Delivered => msg.ack().await,
+        BatchStatus::Errored => msg.ack_with(AckKind::Nak(None)).await,
+        // Sinks are responsible for emitting ComponentEventsDropped.
+        BatchStatus::Rejected => msg.ack_with(AckKind::Term).await,
+    };
+    if let Err(error) = result {
+        emit!(NatsJetStreamAckError { error });
+    }
+}
+
+/// Decodes the payload of a message into events, adding the source metadata.
+async fn decode_message(
+    config: &NatsSourceConfig,
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    subject: &str,
+    payload: &[u8],
+) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut stream = FramedRead::new(payload, decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((decoded, _byte_size)) => events.extend(decoded),
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    let now = Utc::now();
+    for event in &mut events {
+        if let Event::Log(ref mut log) = event {
+            log_namespace.insert_standard_vector_source_metadata(log, NatsSourceConfig::NAME, now);
+
+            let legacy_subject_key_field = config
+                .subject_key_field
+                .path
+                .as_ref()
+                .map(LegacyKey::InsertIfEmpty);
+            log_namespace.insert_source_metadata(
+                NatsSourceConfig::NAME,
+                log,
+                legacy_subject_key_field,
+                &owned_value_path!("subject"),
+                subject,
+            )
+        }
+    }
+    events
+}
+
 async fn create_subscription(
     config: &NatsSourceConfig,
 ) -> Result<(async_nats::Client, async_nats::Subscriber), BuildError> {
@@ -300,6 +466,44 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+async fn create_jetstream_consumer(
+    config: &NatsSourceConfig,
+    jetstream: &NatsJetStreamConfig,
+) -> Result<(async_nats::Client, pull::Stream), BuildError> {
+    let nc = config.connect().await?;
+    let context = jetstream::new(nc.clone());
+
+    let stream = context
+        .get_stream(&jetstream.stream)
+        .await
+        .map_err(|error| BuildError::JetStream {
+            source: error.into(),
+        })?;
+    let consumer = stream
+        .get_or_create_consumer(
+            &jetstream.durable_name,
+            pull::Config {
+                durable_name: Some(jetstream.durable_name.clone()),
+                filter_subject: config.subject.clone(),
+                ack_policy: jetstream::consumer::AckPolicy::Explicit,
+                max_ack_pending: jetstream.max_in_flight as i64,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|error| BuildError::JetStream {
+            source: error.into(),
+        })?;
+    let messages = consumer
+        .messages()
+        .await
+        .map_err(|error| BuildError::JetStream {
+            source: error.into(),
+        })?;
+
+    Ok((nc, messages))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -315,6 +519,29 @@ mod tests {
         crate::test_util::test_generate_config::<NatsSourceConfig>();
     }
 
+    #[test]
+    fn parse_jetstream_config() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            connection_name = "vector"
+            subject = "events.>"
+            url = "nats://127.0.0.1:4222"
+
+            [jetstream]
+            stream = "events"
+            durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.as_ref().unwrap();
+        assert_eq!(jetstream.stream, "events");
+        assert_eq!(jetstream.durable_name, "vector");
+        assert_eq!(jetstream.max_in_flight, default_max_in_flight());
+        assert!(config.can_acknowledge());
+        assert!(!NatsSourceConfig::default().can_acknowledge());
+    }
+
     #[test]
     fn output_schema_definition_vector_namespace() {
         let config = NatsSourceConfig {
@@ -432,6 +659,77 @@ mod integration_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn nats_jetstream() {
+        let subject = format!("test-{}", random_string(10));
+        let url =
+            std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject: subject.clone(),
+            url,
+            jetstream: Some(NatsJetStreamConfig {
+                stream: subject.clone(),
+                durable_name: "vector".to_owned(),
+                max_in_flight: default_max_in_flight(),
+            }),
+            subject_key_field: default_subject_key_field(),
+            ..Default::default()
+        };
+
+        let nc = conf.connect().await.unwrap();
+        let context = jetstream::new(nc);
+        context
+            .create_stream(jetstream::stream::Config {
+                name: subject.clone(),
+                subjects: vec![subject.clone()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let (nc, messages) = create_jetstream_consumer(&conf, conf.jetstream.as_ref().unwrap())
+            .await
+            .unwrap();
+        let msg = "my message";
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            let decoder = DecodingConfig::new(
+                conf.framing.clone(),
+                conf.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build()
+            .unwrap();
+            tokio::spawn(jetstream_source(
+                conf.clone(),
+                nc,
+                messages,
+                decoder,
+                LogNamespace::Legacy,
+                true,
+                ShutdownSignal::noop(),
+                tx,
+            ));
+            context
+                .publish(subject, Bytes::from_static(msg.as_bytes()))
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+
+            collect_n(rx, 1).await
+        })
+        .await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            msg.into()
+        );
+    }
+
     #[tokio::test]
     async fn nats_no_auth() {
         let subject = format!("test-{}", random_string(10));
@@ -0,0 +1,839 @@
+//! Decoding of NetFlow v5, NetFlow v9, and IPFIX packets.
+//!
+//! See <https://www.cisco.com/c/en/us/td/docs/net_mgmt/netflow_collection_engine/3-6/user/guide/format.html>,
+//! [RFC 3954](https://www.rfc-editor.org/rfc/rfc3954) and
+//! [RFC 7011](https://www.rfc-editor.org/rfc/rfc7011).
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use snafu::Snafu;
+use vrl::value::{ObjectMap, Value};
+
+use super::fields::{self, FieldType};
+
+const NETFLOW_V5: u16 = 5;
+const NETFLOW_V9: u16 = 9;
+const IPFIX: u16 = 10;
+
+const NETFLOW_V9_TEMPLATE_SET: u16 = 0;
+const NETFLOW_V9_OPTIONS_TEMPLATE_SET: u16 = 1;
+const IPFIX_TEMPLATE_SET: u16 = 2;
+const IPFIX_OPTIONS_TEMPLATE_SET: u16 = 3;
+const MIN_DATA_SET_ID: u16 = 256;
+
+const NETFLOW_V5_RECORD_LENGTH: usize = 48;
+
+/// The `paddingOctets` information element, used to skip over NetFlow v9 option scopes.
+const PADDING_OCTETS: u16 = 210;
+
+/// The length of variable-length IPFIX fields in templates.
+const VARIABLE_LENGTH: u16 = u16::MAX;
+
+/// The offset between the NTP and Unix epochs, in seconds.
+const NTP_EPOCH_OFFSET: i64 = 2_208_988_800;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("Packet is truncated"))]
+    Truncated,
+    #[snafu(display("Unsupported version {}", version))]
+    UnsupportedVersion { version: u16 },
+    #[snafu(display("Invalid length {} of set {}", length, set_id))]
+    InvalidSetLength { set_id: u16, length: u16 },
+    #[snafu(display("Template {} has a field with an invalid length", template_id))]
+    InvalidTemplate { template_id: u16 },
+}
+
+/// The records decoded from a packet.
+#[derive(Debug, Default)]
+pub struct Packet {
+    pub export_time: Option<DateTime<Utc>>,
+    pub records: Vec<ObjectMap>,
+    /// The identifiers of the templates of the data sets that couldn't be decoded.
+    pub missing_templates: Vec<u16>,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct TemplateKey {
+    exporter: SocketAddr,
+    version: u16,
+    observation_domain_id: u32,
+    template_id: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FieldSpec {
+    id: u16,
+    enterprise_number: Option<u32>,
+    length: u16,
+}
+
+#[derive(Debug)]
+struct Template {
+    fields: Vec<FieldSpec>,
+    options: bool,
+    received: Instant,
+}
+
+impl Template {
+    /// The minimum length of a record, which is used to detect the padding at the end of sets.
+    fn min_record_length(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => length as usize,
+            })
+            .sum()
+    }
+}
+
+/// The templates received from each exporter, which are needed to decode NetFlow v9 and IPFIX
+/// data records.
+///
+/// Templates are scoped to the address of the exporter and to its observation domain, or source
+/// ID for NetFlow v9.
+#[derive(Debug)]
+pub struct TemplateCache {
+    templates: HashMap<TemplateKey, Template>,
+    timeout: Duration,
+}
+
+impl TemplateCache {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            templates: HashMap::new(),
+            timeout,
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Removes the templates that weren't refreshed by their exporter within the timeout.
+    pub fn expire(&mut self, now: Instant) {
+        let timeout = self.timeout;
+        self.templates
+            .retain(|_, template| now.duration_since(template.received) < timeout);
+    }
+
+    /// Decodes a packet received from `exporter`, updating the templates it holds.
+    pub fn decode(
+        &mut self,
+        exporter: SocketAddr,
+        data: &[u8],
+        now: Instant,
+    ) -> Result<Packet, DecodeError> {
+        let mut reader = Reader::new(data);
+        let version = reader.u16()?;
+        match version {
+            NETFLOW_V5 => decode_v5(reader),
+            NETFLOW_V9 => self.decode_v9(exporter, reader, now),
+            IPFIX => self.decode_ipfix(exporter, reader, now),
+            version => Err(DecodeError::UnsupportedVersion { version }),
+        }
+    }
+
+    fn decode_v9(
+        &mut self,
+        exporter: SocketAddr,
+        mut reader: Reader,
+        now: Instant,
+    ) -> Result<Packet, DecodeError> {
+        let _count = reader.u16()?;
+        let _sys_up_time = reader.u32()?;
+        let unix_secs = reader.u32()?;
+        let sequence_number = reader.u32()?;
+        let source_id = reader.u32()?;
+
+        let mut header = ObjectMap::new();
+        header.insert("version".into(), NETFLOW_V9.into());
+        header.insert("sequence_number".into(), sequence_number.into());
+        header.insert("source_id".into(), source_id.into());
+
+        let mut packet = Packet {
+            export_time: DateTime::from_timestamp(unix_secs.into(), 0),
+            ..Default::default()
+        };
+        let key = |template_id| TemplateKey {
+            exporter,
+            version: NETFLOW_V9,
+            observation_domain_id: source_id,
+            template_id,
+        };
+
+        while !reader.is_empty() {
+            let (set_id, mut set) = reader.set()?;
+            match set_id {
+                NETFLOW_V9_TEMPLATE_SET => {
+                    while set.remaining() >= 4 {
+                        let template_id = set.u16()?;
+                        let field_count = set.u16()?;
+                        let fields = (0..field_count)
+                            .map(|_| set.v9_field())
+                            .collect::<Result<_, _>>()?;
+                        self.insert(key(template_id), fields, false, now)?;
+                    }
+                }
+                NETFLOW_V9_OPTIONS_TEMPLATE_SET => {
+                    while set.remaining() >= 6 {
+                        let template_id = set.u16()?;
+                        let scope_length = set.u16()? as usize;
+                        let option_length = set.u16()? as usize;
+                        // The scope fields have their own type space, so only their lengths are
+                        // kept, to skip over them.
+                        let mut fields = Vec::new();
+                        for _ in 0..scope_length / 4 {
+                            let _scope_type = set.u16()?;
+                            fields.push(FieldSpec {
+                                id: PADDING_OCTETS,
+                                enterprise_number: None,
+                                length: set.u16()?,
+                            });
+                        }
+                        for _ in 0..option_length / 4 {
+                            fields.push(set.v9_field()?);
+                        }
+                        self.insert(key(template_id), fields, true, now)?;
+                    }
+                }
+                set_id if set_id >= MIN_DATA_SET_ID => {
+                    self.decode_data_set(key(set_id), &header, set, &mut packet)?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(packet)
+    }
+
+    fn decode_ipfix(
+        &mut self,
+        exporter: SocketAddr,
+        mut reader: Reader,
+        now: Instant,
+    ) -> Result<Packet, DecodeError> {
+        let length = reader.u16()? as usize;
+        let export_time = reader.u32()?;
+        let sequence_number = reader.u32()?;
+        let observation_domain_id = reader.u32()?;
+        // The message length includes the 16 bytes of the header.
+        reader.truncate(length.saturating_sub(16))?;
+
+        let mut header = ObjectMap::new();
+        header.insert("version".into(), IPFIX.into());
+        header.insert("sequence_number".into(), sequence_number.into());
+        header.insert("observation_domain_id".into(), observation_domain_id.into());
+
+        let mut packet = Packet {
+            export_time: DateTime::from_timestamp(export_time.into(), 0),
+            ..Default::default()
+        };
+        let key = |template_id| TemplateKey {
+            exporter,
+            version: IPFIX,
+            observation_domain_id,
+            template_id,
+        };
+
+        while !reader.is_empty() {
+            let (set_id, mut set) = reader.set()?;
+            match set_id {
+                IPFIX_TEMPLATE_SET | IPFIX_OPTIONS_TEMPLATE_SET => {
+                    let options = set_id == IPFIX_OPTIONS_TEMPLATE_SET;
+                    while set.remaining() >= 4 {
+                        let template_id = set.u16()?;
+                        let field_count = set.u16()?;
+                        if field_count == 0 {
+                            // A template withdrawal.
+                            self.templates.remove(&key(template_id));
+                            continue;
+                        }
+                        if options {
+                            let _scope_field_count = set.u16()?;
+                        }
+                        let fields = (0..field_count)
+                            .map(|_| set.ipfix_field())
+                            .collect::<Result<_, _>>()?;
+                        self.insert(key(template_id), fields, options, now)?;
+                    }
+                }
+                set_id if set_id >= MIN_DATA_SET_ID => {
+                    self.decode_data_set(key(set_id), &header, set, &mut packet)?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(packet)
+    }
+
+    fn insert(
+        &mut self,
+        key: TemplateKey,
+        fields: Vec<FieldSpec>,
+        options: bool,
+        now: Instant,
+    ) -> Result<(), DecodeError> {
+        let invalid_length = |field: &FieldSpec| {
+            field.length == 0 || (key.version == NETFLOW_V9 && field.length == VARIABLE_LENGTH)
+        };
+        if fields.iter().any(invalid_length) {
+            return Err(DecodeError::InvalidTemplate {
+                template_id: key.template_id,
+            });
+        }
+        self.templates.insert(
+            key,
+            Template {
+                fields,
+                options,
+                received: now,
+            },
+        );
+        Ok(())
+    }
+
+    fn decode_data_set(
+        &self,
+        key: TemplateKey,
+        header: &ObjectMap,
+        mut set: Reader,
+        packet: &mut Packet,
+    ) -> Result<(), DecodeError> {
+        let Some(template) = self.templates.get(&key) else {
+            packet.missing_templates.push(key.template_id);
+            return Ok(());
+        };
+
+        let min_record_length = template.min_record_length();
+        while set.remaining() >= min_record_length.max(1) {
+            let mut record = header.clone();
+            record.insert("template_id".into(), key.template_id.into());
+            record.insert(
+                "record_type".into(),
+                if template.options { "options" } else { "flow" }.into(),
+            );
+            for field in &template.fields {
+                let length = match field.length {
+                    VARIABLE_LENGTH => set.variable_length()?,
+                    length => length as usize,
+                };
+                let data = set.take(length)?;
+                if let Some((name, value)) = decode_field(field, data) {
+                    record.insert(name.into(), value);
+                }
+            }
+            packet.records.push(record);
+        }
+
+        Ok(())
+    }
+}
+
+fn decode_v5(mut reader: Reader) -> Result<Packet, DecodeError> {
+    let count = reader.u16()?;
+    let sys_up_time = reader.u32()?;
+    let unix_secs = reader.u32()?;
+    let unix_nsecs = reader.u32()?;
+    let flow_sequence = reader.u32()?;
+    let engine_type = reader.u8()?;
+    let engine_id = reader.u8()?;
+    let sampling = reader.u16()?;
+
+    let mut header = ObjectMap::new();
+    header.insert("version".into(), NETFLOW_V5.into());
+    header.insert("sequence_number".into(), flow_sequence.into());
+    header.insert("engine_type".into(), engine_type.into());
+    header.insert("engine_id".into(), engine_id.into());
+    // The two top bits hold the sampling mode, and the others the sampling interval.
+    header.insert("sampling_interval".into(), (sampling & 0x3fff).into());
+    header.insert("system_up_time".into(), sys_up_time.into());
+    header.insert("record_type".into(), "flow".into());
+
+    let mut packet = Packet {
+        export_time: DateTime::from_timestamp(unix_secs.into(), unix_nsecs),
+        ..Default::default()
+    };
+
+    for _ in 0..count {
+        let mut data = Reader::new(reader.take(NETFLOW_V5_RECORD_LENGTH)?);
+        let mut record = header.clone();
+        let mut insert = |name: &str, value: Value| {
+            record.insert(name.into(), value);
+        };
+
+        insert("source_ipv4_address", ipv4(data.u32()?));
+        insert("destination_ipv4_address", ipv4(data.u32()?));
+        insert("ip_next_hop_ipv4_address", ipv4(data.u32()?));
+        insert("ingress_interface", data.u16()?.into());
+        insert("egress_interface", data.u16()?.into());
+        insert("packet_delta_count", data.u32()?.into());
+        insert("octet_delta_count", data.u32()?.into());
+        insert("flow_start_sys_up_time", data.u32()?.into());
+        insert("flow_end_sys_up_time", data.u32()?.into());
+        insert("source_transport_port", data.u16()?.into());
+        insert("destination_transport_port", data.u16()?.into());
+        let _pad = data.u8()?;
+        insert("tcp_control_bits", data.u8()?.into());
+        insert("protocol_identifier", data.u8()?.into());
+        insert("ip_class_of_service", data.u8()?.into());
+        insert("bgp_source_as_number", data.u16()?.into());
+        insert("bgp_destination_as_number", data.u16()?.into());
+        insert("source_ipv4_prefix_length", data.u8()?.into());
+        insert("destination_ipv4_prefix_length", data.u8()?.into());
+
+        packet.records.push(record);
+    }
+
+    Ok(packet)
+}
+
+/// Decodes the value of a field, returning `None` for padding.
+fn decode_field(field: &FieldSpec, data: &[u8]) -> Option<(String, Value)> {
+    if let Some(enterprise_number) = field.enterprise_number {
+        return Some((
+            format!("enterprise_{}_{}", enterprise_number, field.id),
+            hex::encode(data).into(),
+        ));
+    }
+
+    let Some((name, field_type)) = fields::lookup(field.id) else {
+        return Some((format!("field_{}", field.id), hex::encode(data).into()));
+    };
+
+    let value = match field_type {
+        FieldType::Padding => return None,
+        FieldType::Unsigned if data.len() <= 8 => unsigned(data),
+        FieldType::Ipv4 if data.len() == 4 => Ipv4Addr::from(<[u8; 4]>::try_from(data).ok()?)
+            .to_string()
+            .into(),
+        FieldType::Ipv6 if data.len() == 16 => Ipv6Addr::from(<[u8; 16]>::try_from(data).ok()?)
+            .to_string()
+            .into(),
+        FieldType::Mac if data.len() == 6 => data
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(":")
+            .into(),
+        FieldType::String => String::from_utf8_lossy(data)
+            .trim_end_matches('\0')
+            .to_owned()
+            .into(),
+        FieldType::DateTimeSeconds if data.len() == 4 => {
+            timestamp(DateTime::from_timestamp(be_u64(data) as i64, 0))
+        }
+        FieldType::DateTimeMilliseconds if data.len() == 8 => {
+            timestamp(DateTime::from_timestamp_millis(be_u64(data) as i64))
+        }
+        FieldType::DateTimeNtp if data.len() == 8 => {
+            let seconds = be_u64(&data[..4]) as i64 - NTP_EPOCH_OFFSET;
+            let fraction = be_u64(&data[4..]);
+            let nanoseconds = (fraction * 1_000_000_000) >> 32;
+            timestamp(DateTime::from_timestamp(seconds, nanoseconds as u32))
+        }
+        _ => hex::encode(data).into(),
+    };
+
+    Some((name.to_owned(), value))
+}
+
+fn unsigned(data: &[u8]) -> Value {
+    // Counters are unlikely to ever overflow a signed 64-bit integer.
+    i64::try_from(be_u64(data)).unwrap_or(i64::MAX).into()
+}
+
+fn be_u64(data: &[u8]) -> u64 {
+    data.iter()
+        .fold(0, |value, &byte| (value << 8) | u64::from(byte))
+}
+
+fn ipv4(address: u32) -> Value {
+    Ipv4Addr::from(address).to_string().into()
+}
+
+fn timestamp(timestamp: Option<DateTime<Utc>>) -> Value {
+    timestamp.map(Value::Timestamp).unwrap_or(Value::Null)
+}
+
+/// A cursor over the big-endian fields of a packet.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    fn truncate(&mut self, length: usize) -> Result<(), DecodeError> {
+        self.data = self.data.get(..length).ok_or(DecodeError::Truncated)?;
+        Ok(())
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() < length {
+            return Err(DecodeError::Truncated);
+        }
+        let (head, tail) = self.data.split_at(length);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(be_u64(self.take(2)?) as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(be_u64(self.take(4)?) as u32)
+    }
+
+    /// Reads the header of a set, or flowset, returning its identifier and contents.
+    fn set(&mut self) -> Result<(u16, Reader<'a>), DecodeError> {
+        let set_id = self.u16()?;
+        let length = self.u16()?;
+        if length < 4 {
+            return Err(DecodeError::InvalidSetLength { set_id, length });
+        }
+        Ok((set_id, Reader::new(self.take(length as usize - 4)?)))
+    }
+
+    fn v9_field(&mut self) -> Result<FieldSpec, DecodeError> {
+        Ok(FieldSpec {
+            id: self.u16()?,
+            enterprise_number: None,
+            length: self.u16()?,
+        })
+    }
+
+    fn ipfix_field(&mut self) -> Result<FieldSpec, DecodeError> {
+        let id = self.u16()?;
+        let length = self.u16()?;
+        // The enterprise bit is set for the information elements that aren't registered with IANA.
+        let enterprise_number = if id & 0x8000 != 0 {
+            Some(self.u32()?)
+        } else {
+            None
+        };
+        Ok(FieldSpec {
+            id: id & 0x7fff,
+            enterprise_number,
+            length,
+        })
+    }
+
+    /// Reads the length of a variable-length IPFIX field.
+    fn variable_length(&mut self) -> Result<usize, DecodeError> {
+        match self.u8()? {
+            255 => Ok(self.u16()? as usize),
+            length => Ok(length as usize),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn exporter() -> SocketAddr {
+        "192.0.2.1:40000".parse().unwrap()
+    }
+
+    fn v5_packet() -> Vec<u8> {
+        let mut packet = vec![];
+        packet.extend(5u16.to_be_bytes());
+        packet.extend(1u16.to_be_bytes());
+        packet.extend(360_000u32.to_be_bytes());
+        packet.extend(1_700_000_000u32.to_be_bytes());
+        packet.extend(500u32.to_be_bytes());
+        packet.extend(42u32.to_be_bytes());
+        packet.extend([0, 1]);
+        packet.extend((0x4000u16 | 100).to_be_bytes());
+
+        packet.extend([10, 0, 0, 1]);
+        packet.extend([10, 0, 0, 2]);
+        packet.extend([0, 0, 0, 0]);
+        packet.extend(3u16.to_be_bytes());
+        packet.extend(4u16.to_be_bytes());
+        packet.extend(10u32.to_be_bytes());
+        packet.extend(1500u32.to_be_bytes());
+        packet.extend(350_000u32.to_be_bytes());
+        packet.extend(359_000u32.to_be_bytes());
+        packet.extend(51234u16.to_be_bytes());
+        packet.extend(443u16.to_be_bytes());
+        packet.extend([0, 0x12, 6, 0]);
+        packet.extend(64512u16.to_be_bytes());
+        packet.extend(64513u16.to_be_bytes());
+        packet.extend([24, 16, 0, 0]);
+        packet
+    }
+
+    #[test]
+    fn decodes_v5() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let packet = cache
+            .decode(exporter(), &v5_packet(), Instant::now())
+            .unwrap();
+
+        assert_eq!(
+            packet.export_time,
+            Some(Utc.timestamp_opt(1_700_000_000, 500).unwrap())
+        );
+        assert_eq!(packet.records.len(), 1);
+        let record = &packet.records[0];
+        assert_eq!(record["version"], 5.into());
+        assert_eq!(record["sequence_number"], 42.into());
+        assert_eq!(record["engine_id"], 1.into());
+        assert_eq!(record["sampling_interval"], 100.into());
+        assert_eq!(record["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(record["destination_ipv4_address"], "10.0.0.2".into());
+        assert_eq!(record["ingress_interface"], 3.into());
+        assert_eq!(record["packet_delta_count"], 10.into());
+        assert_eq!(record["octet_delta_count"], 1500.into());
+        assert_eq!(record["source_transport_port"], 51234.into());
+        assert_eq!(record["destination_transport_port"], 443.into());
+        assert_eq!(record["tcp_control_bits"], 0x12.into());
+        assert_eq!(record["protocol_identifier"], 6.into());
+        assert_eq!(record["bgp_destination_as_number"], 64513.into());
+        assert_eq!(record["source_ipv4_prefix_length"], 24.into());
+    }
+
+    #[test]
+    fn rejects_truncated_v5() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let packet = v5_packet();
+        assert_eq!(
+            cache
+                .decode(exporter(), &packet[..packet.len() - 1], Instant::now())
+                .unwrap_err(),
+            DecodeError::Truncated
+        );
+    }
+
+    fn v9_packet(sets: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut packet = vec![];
+        packet.extend(9u16.to_be_bytes());
+        packet.extend((sets.len() as u16).to_be_bytes());
+        packet.extend(1000u32.to_be_bytes());
+        packet.extend(1_700_000_000u32.to_be_bytes());
+        packet.extend(7u32.to_be_bytes());
+        packet.extend(3u32.to_be_bytes());
+        for (set_id, contents) in sets {
+            packet.extend(set_id.to_be_bytes());
+            packet.extend((contents.len() as u16 + 4).to_be_bytes());
+            packet.extend(contents);
+        }
+        packet
+    }
+
+    fn v9_template() -> Vec<u8> {
+        let mut template = vec![];
+        template.extend(256u16.to_be_bytes());
+        template.extend(4u16.to_be_bytes());
+        for (id, length) in [(8u16, 4u16), (12, 4), (2, 4), (4, 1)] {
+            template.extend(id.to_be_bytes());
+            template.extend(length.to_be_bytes());
+        }
+        template
+    }
+
+    fn v9_data() -> Vec<u8> {
+        let mut data = vec![];
+        data.extend([10, 0, 0, 1, 10, 0, 0, 2]);
+        data.extend(5u32.to_be_bytes());
+        data.push(17);
+        data.extend([192, 168, 0, 1, 192, 168, 0, 2]);
+        data.extend(6u32.to_be_bytes());
+        data.push(6);
+        // Padding to a 32-bit boundary.
+        data.extend([0, 0]);
+        data
+    }
+
+    #[test]
+    fn decodes_v9_with_templates() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let packet = cache
+            .decode(
+                exporter(),
+                &v9_packet(&[(0, v9_template()), (256, v9_data())]),
+                Instant::now(),
+            )
+            .unwrap();
+
+        assert!(packet.missing_templates.is_empty());
+        assert_eq!(packet.records.len(), 2);
+        let record = &packet.records[1];
+        assert_eq!(record["version"], 9.into());
+        assert_eq!(record["source_id"], 3.into());
+        assert_eq!(record["template_id"], 256.into());
+        assert_eq!(record["record_type"], "flow".into());
+        assert_eq!(record["source_ipv4_address"], "192.168.0.1".into());
+        assert_eq!(record["destination_ipv4_address"], "192.168.0.2".into());
+        assert_eq!(record["packet_delta_count"], 6.into());
+        assert_eq!(record["protocol_identifier"], 6.into());
+
+        // The template is cached for the following packets of the exporter.
+        let packet = cache
+            .decode(exporter(), &v9_packet(&[(256, v9_data())]), Instant::now())
+            .unwrap();
+        assert_eq!(packet.records.len(), 2);
+
+        // But not for other exporters.
+        let packet = cache
+            .decode(
+                "192.0.2.2:40000".parse().unwrap(),
+                &v9_packet(&[(256, v9_data())]),
+                Instant::now(),
+            )
+            .unwrap();
+        assert!(packet.records.is_empty());
+        assert_eq!(packet.missing_templates, vec![256]);
+    }
+
+    #[test]
+    fn expires_templates() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        cache
+            .decode(exporter(), &v9_packet(&[(0, v9_template())]), now)
+            .unwrap();
+        assert_eq!(cache.len(), 1);
+
+        cache.expire(now + Duration::from_secs(30));
+        assert_eq!(cache.len(), 1);
+        cache.expire(now + Duration::from_secs(60));
+        assert_eq!(cache.len(), 0);
+    }
+
+    fn ipfix_packet(sets: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let contents = sets
+            .iter()
+            .flat_map(|(set_id, contents)| {
+                let mut set = vec![];
+                set.extend(set_id.to_be_bytes());
+                set.extend((contents.len() as u16 + 4).to_be_bytes());
+                set.extend(contents);
+                set
+            })
+            .collect::<Vec<_>>();
+
+        let mut packet = vec![];
+        packet.extend(10u16.to_be_bytes());
+        packet.extend((contents.len() as u16 + 16).to_be_bytes());
+        packet.extend(1_700_000_000u32.to_be_bytes());
+        packet.extend(11u32.to_be_bytes());
+        packet.extend(99u32.to_be_bytes());
+        packet.extend(contents);
+        packet
+    }
+
+    #[test]
+    fn decodes_ipfix() {
+        let mut template = vec![];
+        template.extend(300u16.to_be_bytes());
+        template.extend(5u16.to_be_bytes());
+        for (id, length) in [(27u16, 16u16), (7, 2), (152, 8), (82, u16::MAX)] {
+            template.extend(id.to_be_bytes());
+            template.extend(length.to_be_bytes());
+        }
+        // An enterprise-specific element.
+        template.extend((0x8000u16 | 1).to_be_bytes());
+        template.extend(2u16.to_be_bytes());
+        template.extend(29305u32.to_be_bytes());
+
+        let mut data = vec![];
+        data.extend("2001:db8::1".parse::<Ipv6Addr>().unwrap().octets());
+        data.extend(8080u16.to_be_bytes());
+        data.extend(1_700_000_000_123u64.to_be_bytes());
+        data.push(4);
+        data.extend(b"eth0");
+        data.extend([0xab, 0xcd]);
+
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        let packet = cache
+            .decode(
+                exporter(),
+                &ipfix_packet(&[(2, template), (300, data)]),
+                Instant::now(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            packet.export_time,
+            Some(Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+        );
+        assert_eq!(packet.records.len(), 1);
+        let record = &packet.records[0];
+        assert_eq!(record["version"], 10.into());
+        assert_eq!(record["observation_domain_id"], 99.into());
+        assert_eq!(record["sequence_number"], 11.into());
+        assert_eq!(record["source_ipv6_address"], "2001:db8::1".into());
+        assert_eq!(record["source_transport_port"], 8080.into());
+        assert_eq!(
+            record["flow_start_milliseconds"],
+            Value::Timestamp(Utc.timestamp_millis_opt(1_700_000_000_123).unwrap())
+        );
+        assert_eq!(record["interface_name"], "eth0".into());
+        assert_eq!(record["enterprise_29305_1"], "abcd".into());
+    }
+
+    #[test]
+    fn withdraws_ipfix_templates() {
+        let mut template = vec![];
+        template.extend(300u16.to_be_bytes());
+        template.extend(1u16.to_be_bytes());
+        template.extend(4u16.to_be_bytes());
+        template.extend(1u16.to_be_bytes());
+
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        cache
+            .decode(exporter(), &ipfix_packet(&[(2, template)]), Instant::now())
+            .unwrap();
+        assert_eq!(cache.len(), 1);
+
+        let mut withdrawal = vec![];
+        withdrawal.extend(300u16.to_be_bytes());
+        withdrawal.extend(0u16.to_be_bytes());
+        cache
+            .decode(
+                exporter(),
+                &ipfix_packet(&[(2, withdrawal)]),
+                Instant::now(),
+            )
+            .unwrap();
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        assert_eq!(
+            cache
+                .decode(exporter(), &[0, 7, 0, 0], Instant::now())
+                .unwrap_err(),
+            DecodeError::UnsupportedVersion { version: 7 }
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! The information elements that are decoded into named fields.
+//!
+//! The identifiers below 128 are shared by NetFlow v9 and IPFIX. See
+//! <https://www.iana.org/assignments/ipfix/ipfix.xhtml> for the registry.
+
+/// How the value of an information element is decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    /// An unsigned integer, possibly with a reduced-size encoding.
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    String,
+    /// An opaque value, rendered as a hex string.
+    Bytes,
+    DateTimeSeconds,
+    DateTimeMilliseconds,
+    /// A NTP timestamp, used for both microseconds and nanoseconds precision.
+    DateTimeNtp,
+    /// Padding, which isn't decoded.
+    Padding,
+}
+
+/// Looks up the name and type of an IANA information element.
+pub fn lookup(id: u16) -> Option<(&'static str, FieldType)> {
+    use FieldType::*;
+
+    Some(match id {
+        1 => ("octet_delta_count", Unsigned),
+        2 => ("packet_delta_count", Unsigned),
+        3 => ("delta_flow_count", Unsigned),
+        4 => ("protocol_identifier", Unsigned),
+        5 => ("ip_class_of_service", Unsigned),
+        6 => ("tcp_control_bits", Unsigned),
+        7 => ("source_transport_port", Unsigned),
+        8 => ("source_ipv4_address", Ipv4),
+        9 => ("source_ipv4_prefix_length", Unsigned),
+        10 => ("ingress_interface", Unsigned),
+        11 => ("destination_transport_port", Unsigned),
+        12 => ("destination_ipv4_address", Ipv4),
+        13 => ("destination_ipv4_prefix_length", Unsigned),
+        14 => ("egress_interface", Unsigned),
+        15 => ("ip_next_hop_ipv4_address", Ipv4),
+        16 => ("bgp_source_as_number", Unsigned),
+        17 => ("bgp_destination_as_number", Unsigned),
+        18 => ("bgp_next_hop_ipv4_address", Ipv4),
+        19 => ("post_mcast_packet_delta_count", Unsigned),
+        20 => ("post_mcast_octet_delta_count", Unsigned),
+        21 => ("flow_end_sys_up_time", Unsigned),
+        22 => ("flow_start_sys_up_time", Unsigned),
+        23 => ("post_octet_delta_count", Unsigned),
+        24 => ("post_packet_delta_count", Unsigned),
+        25 => ("minimum_ip_total_length", Unsigned),
+        26 => ("maximum_ip_total_length", Unsigned),
+        27 => ("source_ipv6_address", Ipv6),
+        28 => ("destination_ipv6_address", Ipv6),
+        29 => ("source_ipv6_prefix_length", Unsigned),
+        30 => ("destination_ipv6_prefix_length", Unsigned),
+        31 => ("flow_label_ipv6", Unsigned),
+        32 => ("icmp_type_code_ipv4", Unsigned),
+        33 => ("igmp_type", Unsigned),
+        34 => ("sampling_interval", Unsigned),
+        35 => ("sampling_algorithm", Unsigned),
+        36 => ("flow_active_timeout", Unsigned),
+        37 => ("flow_idle_timeout", Unsigned),
+        40 => ("exported_octet_total_count", Unsigned),
+        41 => ("exported_message_total_count", Unsigned),
+        42 => ("exported_flow_record_total_count", Unsigned),
+        44 => ("source_ipv4_prefix", Ipv4),
+        45 => ("destination_ipv4_prefix", Ipv4),
+        46 => ("mpls_top_label_type", Unsigned),
+        47 => ("mpls_top_label_ipv4_address", Ipv4),
+        52 => ("minimum_ttl", Unsigned),
+        53 => ("maximum_ttl", Unsigned),
+        54 => ("fragment_identification", Unsigned),
+        55 => ("post_ip_class_of_service", Unsigned),
+        56 => ("source_mac_address", Mac),
+        57 => ("post_destination_mac_address", Mac),
+        58 => ("vlan_id", Unsigned),
+        59 => ("post_vlan_id", Unsigned),
+        60 => ("ip_version", Unsigned),
+        61 => ("flow_direction", Unsigned),
+        62 => ("ip_next_hop_ipv6_address", Ipv6),
+        63 => ("bgp_next_hop_ipv6_address", Ipv6),
+        64 => ("ipv6_extension_headers", Unsigned),
+        80 => ("destination_mac_address", Mac),
+        81 => ("post_source_mac_address", Mac),
+        82 => ("interface_name", String),
+        83 => ("interface_description", String),
+        85 => ("octet_total_count", Unsigned),
+        86 => ("packet_total_count", Unsigned),
+        88 => ("fragment_offset", Unsigned),
+        89 => ("forwarding_status", Unsigned),
+        94 => ("application_description", String),
+        95 => ("application_id", Bytes),
+        96 => ("application_name", String),
+        128 => ("bgp_next_adjacent_as_number", Unsigned),
+        129 => ("bgp_prev_adjacent_as_number", Unsigned),
+        130 => ("exporter_ipv4_address", Ipv4),
+        131 => ("exporter_ipv6_address", Ipv6),
+        132 => ("dropped_octet_delta_count", Unsigned),
+        133 => ("dropped_packet_delta_count", Unsigned),
+        136 => ("flow_end_reason", Unsigned),
+        139 => ("icmp_type_code_ipv6", Unsigned),
+        144 => ("exporting_process_id", Unsigned),
+        148 => ("flow_id", Unsigned),
+        149 => ("observation_domain_id", Unsigned),
+        150 => ("flow_start_seconds", DateTimeSeconds),
+        151 => ("flow_end_seconds", DateTimeSeconds),
+        152 => ("flow_start_milliseconds", DateTimeMilliseconds),
+        153 => ("flow_end_milliseconds", DateTimeMilliseconds),
+        154 => ("flow_start_microseconds", DateTimeNtp),
+        155 => ("flow_end_microseconds", DateTimeNtp),
+        156 => ("flow_start_nanoseconds", DateTimeNtp),
+        157 => ("flow_end_nanoseconds", DateTimeNtp),
+        160 => ("system_init_time_milliseconds", DateTimeMilliseconds),
+        161 => ("flow_duration_milliseconds", Unsigned),
+        176 => ("icmp_type_ipv4", Unsigned),
+        177 => ("icmp_code_ipv4", Unsigned),
+        178 => ("icmp_type_ipv6", Unsigned),
+        179 => ("icmp_code_ipv6", Unsigned),
+        180 => ("udp_source_port", Unsigned),
+        181 => ("udp_destination_port", Unsigned),
+        182 => ("tcp_source_port", Unsigned),
+        183 => ("tcp_destination_port", Unsigned),
+        192 => ("ip_ttl", Unsigned),
+        195 => ("ip_diff_serv_code_point", Unsigned),
+        197 => ("fragment_flags", Unsigned),
+        205 => ("udp_message_length", Unsigned),
+        206 => ("is_multicast", Unsigned),
+        210 => ("padding_octets", Padding),
+        224 => ("ip_total_length", Unsigned),
+        225 => ("post_nat_source_ipv4_address", Ipv4),
+        226 => ("post_nat_destination_ipv4_address", Ipv4),
+        227 => ("post_napt_source_transport_port", Unsigned),
+        228 => ("post_napt_destination_transport_port", Unsigned),
+        230 => ("nat_event", Unsigned),
+        231 => ("initiator_octets", Unsigned),
+        232 => ("responder_octets", Unsigned),
+        233 => ("firewall_event", Unsigned),
+        234 => ("ingress_vrf_id", Unsigned),
+        235 => ("egress_vrf_id", Unsigned),
+        239 => ("biflow_direction", Unsigned),
+        243 => ("dot1q_vlan_id", Unsigned),
+        256 => ("ethernet_type", Unsigned),
+        281 => ("post_nat_source_ipv6_address", Ipv6),
+        282 => ("post_nat_destination_ipv6_address", Ipv6),
+        298 => ("initiator_packets", Unsigned),
+        299 => ("responder_packets", Unsigned),
+        322 => ("observation_time_seconds", DateTimeSeconds),
+        323 => ("observation_time_milliseconds", DateTimeMilliseconds),
+        324 => ("observation_time_microseconds", DateTimeNtp),
+        325 => ("observation_time_nanoseconds", DateTimeNtp),
+        _ => return None,
+    })
+}
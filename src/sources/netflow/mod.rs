@@ -0,0 +1,381 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use listenfd::ListenFd;
+use serde_with::serde_as;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::{
+        EventsReceived, NetflowDecodeError, NetflowTemplateMissing, SocketBindError, SocketMode,
+        SocketReceiveError, StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    SourceSender,
+};
+
+mod decoder;
+mod fields;
+
+pub use self::decoder::DecodeError;
+use self::decoder::{Packet, TemplateCache};
+
+/// The maximum size of a UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// How often the templates that timed out are removed.
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Configuration for the `netflow` source.
+#[serde_as]
+#[configurable_component(source(
+    "netflow",
+    "Collect flow records from NetFlow v5, NetFlow v9, and IPFIX exporters."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// How long to keep the templates of an exporter that are not refreshed.
+    ///
+    /// NetFlow v9 and IPFIX exporters periodically resend their templates, which are needed to
+    /// decode their flow records. Records whose template has not been received yet, or timed out,
+    /// are dropped.
+    #[serde(default = "default_template_timeout")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Template Timeout"))]
+    template_timeout_secs: Duration,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+const fn default_template_timeout() -> Duration {
+    Duration::from_secs(1800)
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:2055".parse().unwrap()),
+            receive_buffer_bytes: None,
+            template_timeout_secs: default_template_timeout(),
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+impl NetflowConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(
+            Kind::object(
+                Collection::empty()
+                    .with_known("version", Kind::integer())
+                    .with_known("sequence_number", Kind::integer())
+                    .with_known("record_type", Kind::bytes())
+                    .with_unknown(Kind::any()),
+            ),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .timestamp_key()
+                .cloned()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("timestamp"),
+            Kind::timestamp().or_undefined(),
+            Some("timestamp"),
+        )
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .host_key()
+                .cloned()
+                .map(LegacyKey::InsertIfEmpty),
+            &owned_value_path!("host"),
+            Kind::bytes(),
+            Some("host"),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "netflow")]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        Ok(Box::pin(netflow(
+            self.clone(),
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn netflow(
+    config: NetflowConfig,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .await
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error,
+            })
+        })?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(message = "Listening.", address = %config.address);
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+    let events_received = register!(EventsReceived);
+
+    let mut templates = TemplateCache::new(config.template_timeout_secs);
+    let mut expire = tokio::time::interval(EXPIRE_INTERVAL);
+    let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+
+    loop {
+        tokio::select! {
+            recv = socket.recv_from(&mut buf) => {
+                let (byte_size, exporter) = recv.map_err(|error| {
+                    emit!(SocketReceiveError {
+                        mode: SocketMode::Udp,
+                        error,
+                    })
+                })?;
+                bytes_received.emit(ByteSize(byte_size));
+
+                let data = &buf[..byte_size];
+                let packet = match templates.decode(exporter, data, Instant::now()) {
+                    Ok(packet) => packet,
+                    Err(error) => {
+                        emit!(NetflowDecodeError { error, exporter });
+                        continue;
+                    }
+                };
+                for &template_id in &packet.missing_templates {
+                    emit!(NetflowTemplateMissing {
+                        template_id,
+                        exporter,
+                    });
+                }
+
+                let events = create_events(packet, exporter.ip().to_string(), log_namespace);
+                if events.is_empty() {
+                    continue;
+                }
+
+                let count = events.len();
+                events_received.emit(CountByteSize(
+                    count,
+                    events.estimated_json_encoded_size_of(),
+                ));
+
+                tokio::select! {
+                    result = out.send_batch(events) => {
+                        if result.is_err() {
+                            emit!(StreamClosedError { count });
+                            return Ok(());
+                        }
+                    }
+                    _ = &mut shutdown => return Ok(()),
+                }
+            }
+            _ = expire.tick() => templates.expire(Instant::now()),
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+fn create_events(packet: Packet, exporter: String, log_namespace: LogNamespace) -> Vec<Event> {
+    let now = Utc::now();
+    packet
+        .records
+        .into_iter()
+        .map(|record| {
+            let mut log = LogEvent::from(Value::Object(record));
+
+            log_namespace.insert_standard_vector_source_metadata(
+                &mut log,
+                NetflowConfig::NAME,
+                now,
+            );
+            if let Some(export_time) = packet.export_time {
+                log_namespace.insert_source_metadata(
+                    NetflowConfig::NAME,
+                    &mut log,
+                    log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                    path!("timestamp"),
+                    export_time,
+                );
+            }
+            log_namespace.insert_source_metadata(
+                NetflowConfig::NAME,
+                &mut log,
+                log_schema().host_key().map(LegacyKey::InsertIfEmpty),
+                path!("host"),
+                exporter.clone(),
+            );
+
+            log.into()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    #[test]
+    fn config_template_timeout() {
+        let config: NetflowConfig = toml::from_str(
+            r#"
+            address = "0.0.0.0:2055"
+            template_timeout_secs = 60
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.template_timeout_secs, Duration::from_secs(60));
+    }
+
+    fn v5_packet() -> Vec<u8> {
+        let mut packet = vec![];
+        packet.extend(5u16.to_be_bytes());
+        packet.extend(1u16.to_be_bytes());
+        packet.extend(360_000u32.to_be_bytes());
+        packet.extend(1_700_000_000u32.to_be_bytes());
+        packet.extend(0u32.to_be_bytes());
+        packet.extend(1u32.to_be_bytes());
+        packet.extend([0; 4]);
+        packet.extend([10, 0, 0, 1, 10, 0, 0, 2]);
+        packet.extend([0; 40]);
+        packet
+    }
+
+    #[tokio::test]
+    async fn receives_flow_records() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let address = next_addr();
+            let config = NetflowConfig {
+                address: SocketListenAddr::SocketAddr(address),
+                receive_buffer_bytes: None,
+                template_timeout_secs: default_template_timeout(),
+                log_namespace: None,
+            };
+
+            let (tx, rx) = SourceSender::new_test();
+            let cx = SourceContext::new_test(tx, None);
+            tokio::spawn(config.build(cx).await.unwrap());
+            // Wait for the socket to be bound.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            socket.send_to(&v5_packet(), address).await.unwrap();
+
+            let events = collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+            assert_eq!(log["destination_ipv4_address"], "10.0.0.2".into());
+            assert_eq!(log["version"], 5.into());
+            assert_eq!(
+                log[log_schema().host_key().unwrap().to_string()],
+                "127.0.0.1".into()
+            );
+            assert_eq!(log["source_type"], "netflow".into());
+        })
+        .await;
+    }
+
+    #[test]
+    fn creates_events_in_vector_namespace() {
+        let mut templates = TemplateCache::new(default_template_timeout());
+        let exporter: SocketAddr = "192.0.2.1:2055".parse().unwrap();
+        let packet = templates
+            .decode(exporter, &v5_packet(), Instant::now())
+            .unwrap();
+
+        let events = create_events(packet, "192.0.2.1".to_owned(), LogNamespace::Vector);
+        let log = events[0].as_log();
+        assert_eq!(log.value().get("version"), Some(&5.into()));
+        assert_eq!(
+            log.metadata().value().get(path!("netflow", "host")),
+            Some(&"192.0.2.1".into())
+        );
+        assert!(log
+            .metadata()
+            .value()
+            .get(path!("netflow", "timestamp"))
+            .is_some());
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+const TMP_FILE_NAME: &str = "checkpoints.new.json";
+const CHECKPOINT_FILE_NAME: &str = "checkpoints.json";
+
+/// This enum represents the file format of checkpoints persisted to disk. Right
+/// now there is only one variant, but any incompatible changes will require an
+/// additional variant to be added here and handled anywhere that we transit
+/// this format.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "version", rename_all = "snake_case")]
+enum State {
+    #[serde(rename = "1")]
+    V1 { objects: BTreeMap<String, String> },
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::V1 {
+            objects: BTreeMap::new(),
+        }
+    }
+}
+
+/// The objects of a bucket that have been read, by key, with the entity tag that they had when
+/// they were read.
+///
+/// An object is read again when its entity tag changes, which happens whenever it is overwritten.
+pub(super) struct Checkpointer {
+    tmp_file_path: PathBuf,
+    stable_file_path: PathBuf,
+    objects: BTreeMap<String, String>,
+    dirty: bool,
+}
+
+impl Checkpointer {
+    /// Loads the checkpoints persisted in `data_dir`, if there are any.
+    pub(super) fn new(data_dir: &Path) -> Result<Self, io::Error> {
+        let tmp_file_path = data_dir.join(TMP_FILE_NAME);
+        let stable_file_path = data_dir.join(CHECKPOINT_FILE_NAME);
+
+        let State::V1 { objects } = match fs::read(&stable_file_path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => State::default(),
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            tmp_file_path,
+            stable_file_path,
+            objects,
+            dirty: false,
+        })
+    }
+
+    /// Whether the object has been read since it was last written.
+    pub(super) fn contains(&self, key: &str, etag: &str) -> bool {
+        self.objects.get(key).is_some_and(|read| read == etag)
+    }
+
+    pub(super) fn insert(&mut self, key: String, etag: String) {
+        self.objects.insert(key, etag);
+        self.dirty = true;
+    }
+
+    /// Drops the checkpoints of objects that are no longer in the bucket, so that the checkpoints
+    /// don't grow past the objects that are listed.
+    pub(super) fn retain_listed(&mut self, listed: &HashSet<String>) {
+        let count = self.objects.len();
+        self.objects.retain(|key, _| listed.contains(key));
+        self.dirty |= self.objects.len() != count;
+    }
+
+    /// Persist the checkpoints to disk if they have changed since they were last written.
+    ///
+    /// The checkpoints are written to a tmp file that is then renamed over the previous file, so
+    /// that there is always one complete file to recover from.
+    pub(super) fn write(&mut self) -> Result<(), io::Error> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let state = State::V1 {
+            objects: self.objects.clone(),
+        };
+        let mut file = BufWriter::new(fs::File::create(&self.tmp_file_path)?);
+        serde_json::to_writer(&mut file, &state)?;
+        file.into_inner()?.sync_all()?;
+        fs::rename(&self.tmp_file_path, &self.stable_file_path)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.stable_file_path
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn checkpoints_round_trip() {
+        let data_dir = tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::new(data_dir.path()).unwrap();
+        assert!(!checkpointer.contains("logs/a.log", "\"etag-a\""));
+
+        checkpointer.insert("logs/a.log".to_string(), "\"etag-a\"".to_string());
+        checkpointer.insert("logs/b.log".to_string(), "\"etag-b\"".to_string());
+        checkpointer.write().unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path()).unwrap();
+        assert!(checkpointer.contains("logs/a.log", "\"etag-a\""));
+        assert!(checkpointer.contains("logs/b.log", "\"etag-b\""));
+        assert!(!checkpointer.contains("logs/a.log", "\"etag-overwritten\""));
+        assert!(!data_dir.path().join(TMP_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn checkpoints_retain_listed() {
+        let data_dir = tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::new(data_dir.path()).unwrap();
+        checkpointer.insert("logs/a.log".to_string(), "a".to_string());
+        checkpointer.insert("logs/b.log".to_string(), "b".to_string());
+        checkpointer.write().unwrap();
+
+        checkpointer.retain_listed(&HashSet::from(["logs/b.log".to_string()]));
+        checkpointer.write().unwrap();
+
+        let checkpointer = Checkpointer::new(data_dir.path()).unwrap();
+        assert!(!checkpointer.contains("logs/a.log", "a"));
+        assert!(checkpointer.contains("logs/b.log", "b"));
+    }
+
+    #[test]
+    fn checkpoints_file_format() {
+        let data_dir = tempdir().unwrap();
+
+        let mut checkpointer = Checkpointer::new(data_dir.path()).unwrap();
+        checkpointer.insert("logs/a.log".to_string(), "a".to_string());
+        checkpointer.write().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(checkpointer.path()).unwrap(),
+            r#"{"version":"1","objects":{"logs/a.log":"a"}}"#
+        );
+    }
+}
@@ -0,0 +1,585 @@
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use aws_sdk_s3::operation::{get_object::GetObjectError, list_objects_v2::ListObjectsV2Error};
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::FutureExt;
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use tokio::{pin, select};
+use vector_lib::codecs::decoding::{
+    DeserializerConfig, FramingConfig, FramingError, NewlineDelimitedDecoderOptions,
+};
+use vector_lib::codecs::NewlineDelimitedDecoderConfig;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
+use vector_lib::lookup::owned_value_path;
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, RegionOrEndpoint},
+    codecs::{Decoder, DecodingConfig},
+    common::s3::S3ClientBuilder,
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{
+        EventsReceived, ObjectStoreCheckpointWriteError, ObjectStoreListError,
+        ObjectStoreObjectProcessingError,
+    },
+    serde::{bool_or_struct, default_decoding},
+    shutdown::ShutdownSignal,
+    sources::{
+        aws_s3::s3_object_decoder,
+        util::object_decoding::{
+            send_object_events, Compression, ObjectEventsError, ObjectMetadata,
+        },
+    },
+    tls::TlsConfig,
+    SourceSender,
+};
+
+mod checkpoint;
+
+use checkpoint::Checkpointer;
+
+/// The number of objects that are read between writes of the checkpoints, on top of the write at
+/// the end of each listing.
+const CHECKPOINT_WRITE_INTERVAL: usize = 100;
+
+/// Configuration for the `object_store` source.
+#[serde_as]
+#[configurable_component(source(
+    "object_store",
+    "Collect logs from S3-compatible object stores, such as MinIO or Ceph RGW."
+))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObjectStoreConfig {
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+
+    /// The bucket to read objects from.
+    #[configurable(metadata(docs::examples = "my-logs"))]
+    bucket: String,
+
+    /// Only objects whose keys start with this prefix are read.
+    #[configurable(metadata(docs::examples = "app/"))]
+    #[configurable(metadata(docs::examples = "date=2024-05-01/"))]
+    prefix: Option<String>,
+
+    /// The interval between listings of the bucket, in seconds.
+    ///
+    /// Objects that are new, or that have been overwritten, since the previous listing are read.
+    #[serde(default = "default_poll_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_poll_interval_secs()"))]
+    #[configurable(metadata(docs::human_name = "Poll Interval"))]
+    poll_interval_secs: Duration,
+
+    /// The compression scheme used for decompressing objects retrieved from the object store.
+    compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    #[configurable(derived)]
+    tls_options: Option<TlsConfig>,
+
+    /// The directory used to persist the checkpoints of the objects that have been read.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing")]
+    #[derivative(Default(value = "default_framing()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+}
+
+const fn default_poll_interval_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+const fn default_framing() -> FramingConfig {
+    FramingConfig::NewlineDelimited(NewlineDelimitedDecoderConfig {
+        newline_delimited: NewlineDelimitedDecoderOptions { max_length: None },
+    })
+}
+
+impl_generate_config_from_default!(ObjectStoreConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "object_store")]
+impl SourceConfig for ObjectStoreConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpointer = Checkpointer::new(&data_dir)?;
+
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint(),
+            &cx.proxy,
+            &self.tls_options,
+            &None,
+        )
+        .await?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let poller = ObjectStorePoller {
+            client,
+            bucket: self.bucket.clone(),
+            prefix: self.prefix.clone(),
+            poll_interval: self.poll_interval_secs,
+            compression: self.compression,
+            checkpointer,
+            decoder,
+            out: cx.out,
+            shutdown: cx.shutdown,
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        };
+
+        Ok(Box::pin(poller.run()))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("bucket"))),
+                &owned_value_path!("bucket"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("object"))),
+                &owned_value_path!("object"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+            // for metadata that is added to the events dynamically from the metadata
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+
+        // for metadata that is added to the events dynamically from the metadata
+        if log_namespace == LogNamespace::Legacy {
+            schema_definition = schema_definition.unknown_fields(Kind::bytes());
+        }
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ProcessingError {
+    #[snafu(display("Failed to fetch s3://{}/{}: {}", bucket, key, source))]
+    GetObject {
+        source: SdkError<GetObjectError, HttpResponse>,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Failed to read all of s3://{}/{}: {}", bucket, key, source))]
+    ReadObject {
+        source: Box<dyn FramingError>,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Failed to flush all of s3://{}/{}: {}", bucket, key, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Sink reported an error sending events"))]
+    ErrorAcknowledgement,
+}
+
+/// An object found by listing the bucket.
+#[derive(Clone, Debug)]
+struct ListedObject {
+    key: String,
+    etag: String,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+struct ObjectStorePoller {
+    client: S3Client,
+    bucket: String,
+    prefix: Option<String>,
+    poll_interval: Duration,
+    compression: Compression,
+    checkpointer: Checkpointer,
+    decoder: Decoder,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl ObjectStorePoller {
+    async fn run(mut self) -> Result<(), ()> {
+        let shutdown = self.shutdown.clone().fuse();
+        pin!(shutdown);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once() => {},
+            }
+            select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(self.poll_interval) => {},
+            }
+        }
+
+        // Objects read since the last write are not read again after a restart.
+        self.write_checkpoints();
+
+        Ok(())
+    }
+
+    async fn run_once(&mut self) {
+        let objects = match self.list_objects().await {
+            Ok(objects) => objects,
+            Err(error) => {
+                emit!(ObjectStoreListError {
+                    bucket: &self.bucket,
+                    error,
+                });
+                return;
+            }
+        };
+
+        let listed = objects
+            .iter()
+            .map(|object| object.key.clone())
+            .collect::<HashSet<_>>();
+
+        let mut read = 0;
+        for object in objects {
+            if self.checkpointer.contains(&object.key, &object.etag) {
+                continue;
+            }
+
+            match self.handle_object(&object).await {
+                Ok(()) => {
+                    self.checkpointer.insert(object.key, object.etag);
+                    read += 1;
+                    if read % CHECKPOINT_WRITE_INTERVAL == 0 {
+                        self.write_checkpoints();
+                    }
+                }
+                Err(error) => {
+                    emit!(ObjectStoreObjectProcessingError {
+                        bucket: &self.bucket,
+                        key: &object.key,
+                        error: &error,
+                    });
+                }
+            }
+        }
+
+        self.checkpointer.retain_listed(&listed);
+        self.write_checkpoints();
+    }
+
+    /// Lists the objects of the bucket under the prefix, oldest first, so that events are sent in
+    /// about the order that they were written in.
+    async fn list_objects(
+        &self,
+    ) -> Result<Vec<ListedObject>, SdkError<ListObjectsV2Error, HttpResponse>> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(self.bucket.clone())
+                .set_prefix(self.prefix.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+
+            objects.extend(
+                page.contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|object| {
+                        Some(ListedObject {
+                            key: object.key?,
+                            etag: object.e_tag.unwrap_or_default(),
+                            last_modified: object.last_modified.and_then(|ts| {
+                                Utc.timestamp_opt(ts.secs(), ts.subsec_nanos()).single()
+                            }),
+                        })
+                    }),
+            );
+
+            continuation_token = page.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        objects.sort_by(|a, b| a.last_modified.cmp(&b.last_modified));
+        Ok(objects)
+    }
+
+    async fn handle_object(&mut self, listed: &ListedObject) -> Result<(), ProcessingError> {
+        // The object is only read if it hasn't been overwritten since it was listed, as the
+        // checkpoint would otherwise be set for content that was never read.
+        let object = self
+            .client
+            .get_object()
+            .bucket(self.bucket.clone())
+            .key(listed.key.clone())
+            .set_if_match((!listed.etag.is_empty()).then(|| listed.etag.clone()))
+            .send()
+            .await
+            .context(GetObjectSnafu {
+                bucket: self.bucket.clone(),
+                key: listed.key.clone(),
+            })?;
+
+        let metadata = object.metadata;
+        let timestamp = object
+            .last_modified
+            .and_then(|ts| Utc.timestamp_opt(ts.secs(), ts.subsec_nanos()).single())
+            .or(listed.last_modified);
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let object_reader = s3_object_decoder(
+            self.compression,
+            &listed.key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+
+        let object_metadata = ObjectMetadata {
+            bucket: &self.bucket,
+            key: &listed.key,
+            attributes: &[],
+            metadata: metadata.as_ref(),
+            timestamp,
+        };
+        let log_namespace = self.log_namespace;
+        let result = send_object_events(
+            object_reader,
+            &self.decoder,
+            None,
+            batch,
+            &self.bytes_received,
+            &self.events_received,
+            &mut self.out,
+            |log| object_metadata.insert(ObjectStoreConfig::NAME, log, log_namespace),
+        )
+        .await;
+
+        match result {
+            Err(ObjectEventsError::Read(error)) => Err(ProcessingError::ReadObject {
+                source: error,
+                bucket: self.bucket.clone(),
+                key: listed.key.clone(),
+            }),
+            Err(ObjectEventsError::Send(error)) => Err(ProcessingError::PipelineSend {
+                source: error,
+                bucket: self.bucket.clone(),
+                key: listed.key.clone(),
+            }),
+            Ok(()) => match receiver {
+                None => Ok(()),
+                Some(receiver) => match receiver.await {
+                    // Rejected events are not retryable, so reading the object again would not
+                    // deliver them.
+                    BatchStatus::Delivered | BatchStatus::Rejected => Ok(()),
+                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                },
+            },
+        }
+    }
+
+    fn write_checkpoints(&mut self) {
+        if let Err(error) = self.checkpointer.write() {
+            emit!(ObjectStoreCheckpointWriteError {
+                error,
+                path: self.checkpointer.path(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::TimeZone;
+    use vector_lib::lookup::{path, OwnedTargetPath};
+    use vector_lib::schema::Definition;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ObjectStoreConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config: ObjectStoreConfig = toml::from_str(
+            r#"
+            endpoint = "http://minio:9000"
+            region = "us-east-1"
+            bucket = "logs"
+            prefix = "app/"
+            poll_interval_secs = 30
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.region.endpoint().as_deref(),
+            Some("http://minio:9000")
+        );
+        assert_eq!(config.bucket, "logs");
+        assert_eq!(config.prefix.as_deref(), Some("app/"));
+        assert_eq!(config.poll_interval_secs, Duration::from_secs(30));
+        assert_eq!(config.compression, Compression::Auto);
+    }
+
+    #[test]
+    fn single_log_vector_namespace() {
+        let mut log = LogEvent::default();
+        let timestamp = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let metadata = HashMap::from([("owner".to_string(), "team".to_string())]);
+        ObjectMetadata {
+            bucket: "logs",
+            key: "app/out.log",
+            attributes: &[],
+            metadata: Some(&metadata),
+            timestamp: Some(timestamp),
+        }
+        .insert(ObjectStoreConfig::NAME, &mut log, LogNamespace::Vector);
+
+        let metadata = log.metadata().value();
+        assert_eq!(
+            metadata.get(path!("object_store", "bucket")).unwrap(),
+            &vrl::value::Value::from("logs")
+        );
+        assert_eq!(
+            metadata.get(path!("object_store", "object")).unwrap(),
+            &vrl::value::Value::from("app/out.log")
+        );
+        assert_eq!(
+            metadata
+                .get(path!("object_store", "metadata", "owner"))
+                .unwrap(),
+            &vrl::value::Value::from("team")
+        );
+        assert_eq!(
+            metadata.get(path!("object_store", "timestamp")).unwrap(),
+            &vrl::value::Value::from(timestamp)
+        );
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = ObjectStoreConfig::default();
+
+        let definition = config
+            .outputs(LogNamespace::Legacy)
+            .remove(0)
+            .schema_definition(true)
+            .unwrap();
+
+        assert!(definition
+            .event_kind()
+            .at_path(&OwnedTargetPath::event(owned_value_path!("bucket")).path)
+            .contains_bytes());
+        assert_eq!(
+            definition,
+            Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [LogNamespace::Legacy]
+            )
+            .with_event_field(
+                &owned_value_path!("message"),
+                Kind::bytes(),
+                Some("message")
+            )
+            .with_event_field(&owned_value_path!("bucket"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("object"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp")
+            )
+            .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+            .unknown_fields(Kind::bytes())
+        );
+    }
+}
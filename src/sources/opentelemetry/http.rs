@@ -15,6 +15,7 @@ use vector_lib::internal_event::{
 };
 use vector_lib::opentelemetry::proto::collector::{
     logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+    metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
     trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
 };
 use vector_lib::tls::MaybeTlsIncomingStream;
@@ -97,10 +98,17 @@ pub(crate) fn build_warp_filter(
     let trace_filters = build_warp_trace_filter(
         acknowledgements,
         out.clone(),
-        bytes_received,
-        events_received,
+        bytes_received.clone(),
+        events_received.clone(),
     );
-    log_filters.or(trace_filters).unify().boxed()
+    let metric_filters =
+        build_warp_metric_filter(acknowledgements, out, bytes_received, events_received);
+    log_filters
+        .or(trace_filters)
+        .unify()
+        .or(metric_filters)
+        .unify()
+        .boxed()
 }
 
 fn build_warp_log_filter(
@@ -166,6 +174,37 @@ fn build_warp_trace_filter(
         .boxed()
 }
 
+fn build_warp_metric_filter(
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+) -> BoxedFilter<(Response,)> {
+    warp::post()
+        .and(warp::path!("v1" / "metrics"))
+        .and(warp::header::exact_ignore_case(
+            "content-type",
+            "application/x-protobuf",
+        ))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+        .and_then(move |encoding_header: Option<String>, body: Bytes| {
+            let events = decode(encoding_header.as_deref(), body).and_then(|body| {
+                bytes_received.emit(ByteSize(body.len()));
+                decode_metric_body(body, &events_received)
+            });
+
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                super::METRICS,
+                ExportMetricsServiceResponse::default(),
+            )
+        })
+        .boxed()
+}
+
 fn decode_trace_body(
     body: Bytes,
     events_received: &Registered<EventsReceived>,
@@ -191,6 +230,31 @@ fn decode_trace_body(
     Ok(events)
 }
 
+fn decode_metric_body(
+    body: Bytes,
+    events_received: &Registered<EventsReceived>,
+) -> Result<Vec<Event>, ErrorMessage> {
+    let request = ExportMetricsServiceRequest::decode(body).map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })?;
+
+    let events: Vec<Event> = request
+        .resource_metrics
+        .into_iter()
+        .flat_map(|v| v.into_event_iter())
+        .collect();
+
+    events_received.emit(CountByteSize(
+        events.len(),
+        events.estimated_json_encoded_size_of(),
+    ));
+
+    Ok(events)
+}
+
 fn decode_log_body(
     body: Bytes,
     log_namespace: LogNamespace,
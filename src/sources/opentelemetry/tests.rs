@@ -8,9 +8,16 @@ use tonic::Request;
 use vector_lib::config::LogNamespace;
 use vector_lib::lookup::path;
 use vector_lib::opentelemetry::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    },
     common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        metric::Data, number_data_point, AggregationTemporality, Histogram, HistogramDataPoint,
+        Metric as OtelMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+    },
     resource::v1::Resource as OtelResource,
 };
 use vrl::value;
@@ -18,8 +25,10 @@ use vrl::value;
 use crate::config::OutputId;
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, ObjectMap, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{
+        into_event_stream, Event, EventStatus, LogEvent, MetricKind, MetricValue, ObjectMap, Value,
+    },
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -334,6 +343,119 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+                keepalive: Default::default(),
+            },
+            acknowledgements: Default::default(),
+            log_namespace: Default::default(),
+        };
+
+        let (sender, metrics_output, _) = new_source(EventStatus::Delivered, METRICS.to_string());
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let attribute = |key: &str, value: &str| KeyValue {
+            key: key.into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.into())),
+            }),
+        };
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(OtelResource {
+                    attributes: vec![attribute("service.name", "checkout")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "some.scope.name".into(),
+                        ..Default::default()
+                    }),
+                    metrics: vec![
+                        OtelMetric {
+                            name: "requests".into(),
+                            data: Some(Data::Sum(Sum {
+                                data_points: vec![NumberDataPoint {
+                                    attributes: vec![attribute("method", "GET")],
+                                    time_unix_nano: 1,
+                                    value: Some(number_data_point::Value::AsInt(42)),
+                                    ..Default::default()
+                                }],
+                                aggregation_temporality: AggregationTemporality::Delta as i32,
+                                is_monotonic: true,
+                            })),
+                            ..Default::default()
+                        },
+                        OtelMetric {
+                            name: "latency".into(),
+                            data: Some(Data::Histogram(Histogram {
+                                data_points: vec![HistogramDataPoint {
+                                    time_unix_nano: 2,
+                                    count: 6,
+                                    sum: Some(9.5),
+                                    bucket_counts: vec![1, 3, 2],
+                                    explicit_bounds: vec![1.0, 2.0],
+                                    ..Default::default()
+                                }],
+                                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                            })),
+                            ..Default::default()
+                        },
+                    ],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        _ = client.export(req).await;
+        let output = test_util::collect_ready(metrics_output).await;
+        assert_eq!(output.len(), 2);
+
+        let counter = output[0].as_metric();
+        assert_eq!(counter.name(), "requests");
+        assert_eq!(counter.kind(), MetricKind::Incremental);
+        assert_eq!(counter.value(), &MetricValue::Counter { value: 42.0 });
+        assert_eq!(counter.timestamp(), Some(Utc.timestamp_nanos(1)));
+        let tags = counter.tags().unwrap();
+        assert_eq!(tags.get("method"), Some("GET"));
+        assert_eq!(tags.get("resource.service.name"), Some("checkout"));
+        assert_eq!(tags.get("scope.name"), Some("some.scope.name"));
+
+        let histogram = output[1].as_metric();
+        assert_eq!(histogram.name(), "latency");
+        assert_eq!(histogram.kind(), MetricKind::Absolute);
+        assert_eq!(
+            histogram.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![1.0 => 1, 2.0 => 3],
+                count: 6,
+                sum: 9.5,
+            }
+        );
+    })
+    .await;
+}
+
 pub(super) fn new_source(
     status: EventStatus,
     event_name: String,
@@ -0,0 +1,125 @@
+//! Discovery of targets listed in files, following Prometheus' `file_sd_configs`.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+
+use super::TargetGroup;
+
+#[derive(Debug, Snafu)]
+pub enum FileSdError {
+    #[snafu(display("Could not read targets file {:?}: {}", path, source))]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not parse JSON targets file {:?}: {}", path, source))]
+    ParseJson {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Could not parse YAML targets file {:?}: {}", path, source))]
+    ParseYaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+    #[snafu(display(
+        "Targets file {:?} must have a `.json`, `.yml`, or `.yaml` extension",
+        path
+    ))]
+    UnknownFormat { path: PathBuf },
+}
+
+/// Files listing the targets to scrape.
+///
+/// Each file contains a list of target groups in the format of Prometheus' `file_sd_configs`,
+/// as JSON or YAML depending on its extension. A target group has a list of `targets`, as
+/// `host:port`, and the `labels` set on all of them.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FileSdConfig {
+    /// The paths of the files listing the targets.
+    #[configurable(metadata(docs::examples = "/etc/vector/targets.json"))]
+    files: Vec<PathBuf>,
+
+    /// The interval between reads of the files.
+    #[serde(default = "default_refresh_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Refresh Interval"))]
+    pub refresh_interval_secs: Duration,
+}
+
+const fn default_refresh_interval() -> Duration {
+    Duration::from_secs(300)
+}
+
+impl FileSdConfig {
+    /// Reads the target groups of all the files.
+    pub fn discover(&self) -> Result<Vec<TargetGroup>, FileSdError> {
+        let mut groups = Vec::new();
+        for path in &self.files {
+            groups.extend(read_file(path)?);
+        }
+        Ok(groups)
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<TargetGroup>, FileSdError> {
+    let contents = std::fs::read(path).context(ReadFileSnafu { path })?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_slice(&contents).context(ParseJsonSnafu { path }),
+        Some("yml" | "yaml") => serde_yaml::from_slice(&contents).context(ParseYamlSnafu { path }),
+        _ => UnknownFormatSnafu { path }.fail(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn reads_json_and_yaml_files() {
+        let dir = temp_dir();
+        let json = dir.join("targets.json");
+        std::fs::write(
+            &json,
+            r#"[{"targets": ["10.0.0.1:9100", "10.0.0.2:9100"], "labels": {"job": "node"}}]"#,
+        )
+        .unwrap();
+        let yaml = dir.join("targets.yaml");
+        std::fs::write(&yaml, "- targets: ['10.0.0.3:8080']\n").unwrap();
+
+        let config = FileSdConfig {
+            files: vec![json, yaml],
+            refresh_interval_secs: default_refresh_interval(),
+        };
+        let groups = config.discover().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].targets, vec!["10.0.0.1:9100", "10.0.0.2:9100"]);
+        assert_eq!(groups[0].labels["job"], "node");
+        assert_eq!(groups[1].targets, vec!["10.0.0.3:8080"]);
+        assert!(groups[1].labels.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        let dir = temp_dir();
+        let path = dir.join("targets.txt");
+        std::fs::write(&path, "10.0.0.1:9100").unwrap();
+
+        let config = FileSdConfig {
+            files: vec![path],
+            refresh_interval_secs: default_refresh_interval(),
+        };
+        assert!(matches!(
+            config.discover(),
+            Err(FileSdError::UnknownFormat { .. })
+        ));
+    }
+}
@@ -0,0 +1,452 @@
+//! Discovery of targets from the pods and services of a Kubernetes cluster, following
+//! Prometheus' `kubernetes_sd_configs`.
+
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use k8s_openapi::api::core::v1::{Pod, Service};
+use kube::{
+    api::{Api, ListParams},
+    config::{self, KubeConfigOptions},
+    Client, Config as ClientConfig,
+};
+use serde_with::serde_as;
+use vector_lib::configurable::configurable_component;
+
+use super::{relabel::Labels, TargetGroup, METRICS_PATH_LABEL, SCHEME_LABEL};
+
+const SCRAPE_ANNOTATION: &str = "prometheus.io/scrape";
+const PORT_ANNOTATION: &str = "prometheus.io/port";
+const PATH_ANNOTATION: &str = "prometheus.io/path";
+const SCHEME_ANNOTATION: &str = "prometheus.io/scheme";
+
+/// The kind of Kubernetes objects discovered as targets.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KubernetesRole {
+    /// Discovers a target for each declared container port of the running pods.
+    #[default]
+    Pod,
+
+    /// Discovers a target for each port of the services, addressed by their DNS name.
+    Service,
+}
+
+/// The Kubernetes objects to discover the targets to scrape from.
+///
+/// The objects have `__meta_kubernetes_*` labels, such as `__meta_kubernetes_namespace` or
+/// `__meta_kubernetes_pod_label_<label>`, that can be used in `relabel_configs`.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesSdConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    role: KubernetesRole,
+
+    /// The namespaces to discover the objects in.
+    ///
+    /// The objects of all namespaces are discovered if this is empty.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "default"))]
+    namespaces: Vec<String>,
+
+    /// The label selector restricting the discovered objects.
+    #[configurable(metadata(docs::examples = "app=web"))]
+    label_selector: Option<String>,
+
+    /// Whether the `prometheus.io/*` annotations of the objects are honored.
+    ///
+    /// If `true`, only the objects annotated with `prometheus.io/scrape: "true"` are discovered,
+    /// and the `prometheus.io/port`, `prometheus.io/path`, and `prometheus.io/scheme` annotations
+    /// override the port, path, and scheme of their target. If `false`, all the objects are
+    /// discovered, and can be filtered with `relabel_configs`.
+    #[serde(default = "crate::serde::default_true")]
+    use_annotations: bool,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+
+    /// The interval between listings of the objects.
+    #[serde(default = "default_refresh_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Refresh Interval"))]
+    pub refresh_interval_secs: Duration,
+}
+
+const fn default_refresh_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+impl KubernetesSdConfig {
+    pub async fn build(&self) -> crate::Result<KubernetesDiscoverer> {
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubeconfig, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        Ok(KubernetesDiscoverer {
+            client: Client::try_from(client_config)?,
+            config: self.clone(),
+        })
+    }
+}
+
+/// Lists the objects of a cluster to discover their targets.
+pub struct KubernetesDiscoverer {
+    client: Client,
+    config: KubernetesSdConfig,
+}
+
+impl KubernetesDiscoverer {
+    pub const fn refresh_interval(&self) -> Duration {
+        self.config.refresh_interval_secs
+    }
+
+    pub async fn discover(&self) -> crate::Result<Vec<TargetGroup>> {
+        let mut params = ListParams::default();
+        if let Some(label_selector) = &self.config.label_selector {
+            params = params.labels(label_selector);
+        }
+
+        let mut groups = Vec::new();
+        match self.config.role {
+            KubernetesRole::Pod => {
+                for api in self.apis::<Pod>() {
+                    let pods = api.list(&params).await?;
+                    groups.extend(
+                        pods.items
+                            .iter()
+                            .filter_map(|pod| pod_groups(pod, self.config.use_annotations))
+                            .flatten(),
+                    );
+                }
+            }
+            KubernetesRole::Service => {
+                for api in self.apis::<Service>() {
+                    let services = api.list(&params).await?;
+                    groups.extend(
+                        services
+                            .items
+                            .iter()
+                            .filter_map(|service| {
+                                service_groups(service, self.config.use_annotations)
+                            })
+                            .flatten(),
+                    );
+                }
+            }
+        }
+        Ok(groups)
+    }
+
+    fn apis<K>(&self) -> Vec<Api<K>>
+    where
+        K: kube::Resource<Scope = k8s_openapi::NamespaceResourceScope>,
+        <K as kube::Resource>::DynamicType: Default,
+    {
+        if self.config.namespaces.is_empty() {
+            vec![Api::all(self.client.clone())]
+        } else {
+            self.config
+                .namespaces
+                .iter()
+                .map(|namespace| Api::namespaced(self.client.clone(), namespace))
+                .collect()
+        }
+    }
+}
+
+/// Replaces the characters that are invalid in label names with underscores.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// The labels shared by the targets of an object.
+fn object_labels(
+    kind: &str,
+    namespace: Option<&String>,
+    name: Option<&String>,
+    labels: Option<&BTreeMap<String, String>>,
+    annotations: Option<&BTreeMap<String, String>>,
+) -> Labels {
+    let mut result = Labels::new();
+    if let Some(namespace) = namespace {
+        result.insert("__meta_kubernetes_namespace".to_owned(), namespace.clone());
+    }
+    if let Some(name) = name {
+        result.insert(format!("__meta_kubernetes_{}_name", kind), name.clone());
+    }
+    for (label, value) in labels.into_iter().flatten() {
+        result.insert(
+            format!("__meta_kubernetes_{}_label_{}", kind, sanitize(label)),
+            value.clone(),
+        );
+    }
+    for (annotation, value) in annotations.into_iter().flatten() {
+        result.insert(
+            format!(
+                "__meta_kubernetes_{}_annotation_{}",
+                kind,
+                sanitize(annotation)
+            ),
+            value.clone(),
+        );
+    }
+    result
+}
+
+/// Applies the `prometheus.io/*` annotations, returning `None` if the object isn't scraped.
+///
+/// Returns the annotated port, if any, otherwise the declared ports are used.
+fn apply_annotations(
+    labels: &mut Labels,
+    annotations: Option<&BTreeMap<String, String>>,
+) -> Option<Option<String>> {
+    let annotation = |name: &str| annotations.and_then(|annotations| annotations.get(name));
+    if annotation(SCRAPE_ANNOTATION).map(String::as_str) != Some("true") {
+        return None;
+    }
+    if let Some(path) = annotation(PATH_ANNOTATION) {
+        labels.insert(METRICS_PATH_LABEL.to_owned(), path.clone());
+    }
+    if let Some(scheme) = annotation(SCHEME_ANNOTATION) {
+        labels.insert(SCHEME_LABEL.to_owned(), scheme.clone());
+    }
+    Some(annotation(PORT_ANNOTATION).cloned())
+}
+
+fn pod_groups(pod: &Pod, use_annotations: bool) -> Option<Vec<TargetGroup>> {
+    let status = pod.status.as_ref()?;
+    if status.phase.as_deref() != Some("Running") {
+        return None;
+    }
+    let ip = status.pod_ip.as_ref()?;
+    let metadata = &pod.metadata;
+
+    let mut labels = object_labels(
+        "pod",
+        metadata.namespace.as_ref(),
+        metadata.name.as_ref(),
+        metadata.labels.as_ref(),
+        metadata.annotations.as_ref(),
+    );
+    labels.insert("__meta_kubernetes_pod_ip".to_owned(), ip.clone());
+    if let Some(node_name) = pod.spec.as_ref().and_then(|spec| spec.node_name.as_ref()) {
+        labels.insert(
+            "__meta_kubernetes_pod_node_name".to_owned(),
+            node_name.clone(),
+        );
+    }
+
+    let annotated_port = if use_annotations {
+        apply_annotations(&mut labels, metadata.annotations.as_ref())?
+    } else {
+        None
+    };
+    if let Some(port) = annotated_port {
+        return Some(vec![TargetGroup {
+            targets: vec![format!("{}:{}", ip, port)],
+            labels,
+        }]);
+    }
+
+    // Each declared port is a target, with the labels of its container.
+    let targets = pod
+        .spec
+        .iter()
+        .flat_map(|spec| &spec.containers)
+        .flat_map(|container| {
+            container.ports.iter().flatten().map(|port| {
+                let mut labels = Labels::new();
+                labels.insert(
+                    "__meta_kubernetes_pod_container_name".to_owned(),
+                    container.name.clone(),
+                );
+                labels.insert(
+                    "__meta_kubernetes_pod_container_port_number".to_owned(),
+                    port.container_port.to_string(),
+                );
+                if let Some(name) = &port.name {
+                    labels.insert(
+                        "__meta_kubernetes_pod_container_port_name".to_owned(),
+                        name.clone(),
+                    );
+                }
+                (format!("{}:{}", ip, port.container_port), labels)
+            })
+        })
+        .collect::<Vec<_>>();
+    Some(split_groups(ip.clone(), targets, labels))
+}
+
+fn service_groups(service: &Service, use_annotations: bool) -> Option<Vec<TargetGroup>> {
+    let metadata = &service.metadata;
+    let host = format!(
+        "{}.{}.svc",
+        metadata.name.as_ref()?,
+        metadata.namespace.as_deref().unwrap_or("default")
+    );
+
+    let mut labels = object_labels(
+        "service",
+        metadata.namespace.as_ref(),
+        metadata.name.as_ref(),
+        metadata.labels.as_ref(),
+        metadata.annotations.as_ref(),
+    );
+
+    let annotated_port = if use_annotations {
+        apply_annotations(&mut labels, metadata.annotations.as_ref())?
+    } else {
+        None
+    };
+    if let Some(port) = annotated_port {
+        return Some(vec![TargetGroup {
+            targets: vec![format!("{}:{}", host, port)],
+            labels,
+        }]);
+    }
+
+    let targets = service
+        .spec
+        .iter()
+        .flat_map(|spec| spec.ports.iter().flatten())
+        .map(|port| {
+            let mut labels = Labels::new();
+            labels.insert(
+                "__meta_kubernetes_service_port_number".to_owned(),
+                port.port.to_string(),
+            );
+            if let Some(name) = &port.name {
+                labels.insert(
+                    "__meta_kubernetes_service_port_name".to_owned(),
+                    name.clone(),
+                );
+            }
+            (format!("{}:{}", host, port.port), labels)
+        })
+        .collect::<Vec<_>>();
+    Some(split_groups(host, targets, labels))
+}
+
+/// Splits the targets of an object into a group each, with the labels of the object and their
+/// own, or a single group of the host alone if there are none.
+fn split_groups(host: String, targets: Vec<(String, Labels)>, labels: Labels) -> Vec<TargetGroup> {
+    if targets.is_empty() {
+        return vec![TargetGroup {
+            targets: vec![host],
+            labels,
+        }];
+    }
+    targets
+        .into_iter()
+        .map(|(address, mut target_labels)| {
+            target_labels.extend(labels.clone());
+            TargetGroup {
+                targets: vec![address],
+                labels: target_labels,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::{
+        api::core::v1::{Container, ContainerPort, PodSpec, PodStatus},
+        apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    };
+
+    use super::*;
+
+    fn pod(annotations: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("web-0".to_owned()),
+                namespace: Some("prod".to_owned()),
+                labels: Some([("app.kubernetes.io/name".to_owned(), "web".to_owned())].into()),
+                annotations: Some(
+                    annotations
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "web".to_owned(),
+                    ports: Some(vec![ContainerPort {
+                        container_port: 8080,
+                        name: Some("http".to_owned()),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                phase: Some("Running".to_owned()),
+                pod_ip: Some("10.1.2.3".to_owned()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn skips_pods_without_scrape_annotation() {
+        assert!(pod_groups(&pod(&[]), true).is_none());
+        assert!(pod_groups(&pod(&[]), false).is_some());
+    }
+
+    #[test]
+    fn uses_annotated_port_and_path() {
+        let groups = pod_groups(
+            &pod(&[
+                (SCRAPE_ANNOTATION, "true"),
+                (PORT_ANNOTATION, "9102"),
+                (PATH_ANNOTATION, "/stats"),
+            ]),
+            true,
+        )
+        .unwrap();
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.targets, vec!["10.1.2.3:9102"]);
+        assert_eq!(group.labels[METRICS_PATH_LABEL], "/stats");
+        assert_eq!(group.labels["__meta_kubernetes_namespace"], "prod");
+        assert_eq!(
+            group.labels["__meta_kubernetes_pod_label_app_kubernetes_io_name"],
+            "web"
+        );
+    }
+
+    #[test]
+    fn uses_declared_container_ports() {
+        let groups = pod_groups(&pod(&[(SCRAPE_ANNOTATION, "true")]), true).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].targets, vec!["10.1.2.3:8080"]);
+        assert_eq!(
+            groups[0].labels["__meta_kubernetes_pod_container_port_name"],
+            "http"
+        );
+        assert_eq!(groups[0].labels["__meta_kubernetes_pod_name"], "web-0");
+    }
+}
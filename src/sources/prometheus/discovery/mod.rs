@@ -0,0 +1,373 @@
+//! Dynamic discovery of the targets scraped by the `prometheus_scrape` source.
+//!
+//! Targets are discovered as groups of addresses sharing labels, as in Prometheus. The labels of
+//! each target are rewritten by the relabeling rules, which can set its scheme, path, query
+//! parameters, and scrape interval through the `__`-prefixed labels, or drop it altogether. The
+//! remaining labels are added as tags to the scraped metrics.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use http::Uri;
+use serde::Deserialize;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    internal_events::PrometheusDiscoveryError, shutdown::ShutdownSignal,
+    sources::util::http_client::build_url,
+};
+
+mod file_sd;
+#[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+mod kubernetes;
+mod relabel;
+
+pub use self::file_sd::FileSdConfig;
+#[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+pub use self::kubernetes::KubernetesSdConfig;
+pub use self::relabel::{Labels, RelabelConfig};
+use self::relabel::{RelabelError, Relabeler};
+
+pub const ADDRESS_LABEL: &str = "__address__";
+pub const SCHEME_LABEL: &str = "__scheme__";
+pub const METRICS_PATH_LABEL: &str = "__metrics_path__";
+pub const SCRAPE_INTERVAL_LABEL: &str = "__scrape_interval__";
+pub const PARAM_LABEL_PREFIX: &str = "__param_";
+
+/// A group of targets sharing the same labels.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+pub struct TargetGroup {
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub labels: Labels,
+}
+
+impl TargetGroup {
+    /// The group of a statically configured endpoint.
+    ///
+    /// The query string of the endpoint is kept in its path.
+    pub fn from_endpoint(uri: &Uri) -> Self {
+        let mut labels = Labels::new();
+        if let Some(scheme) = uri.scheme_str() {
+            labels.insert(SCHEME_LABEL.to_owned(), scheme.to_owned());
+        }
+        if let Some(path) = uri.path_and_query() {
+            labels.insert(METRICS_PATH_LABEL.to_owned(), path.to_string());
+        }
+        Self {
+            targets: uri
+                .authority()
+                .map(ToString::to_string)
+                .into_iter()
+                .collect(),
+            labels,
+        }
+    }
+}
+
+/// A target to scrape.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Target {
+    pub url: Uri,
+    pub interval: Duration,
+    /// The labels added as tags to the scraped metrics.
+    pub labels: Labels,
+}
+
+/// Builds the targets of the discovered groups.
+pub struct TargetBuilder {
+    relabeler: Relabeler,
+    query: HashMap<String, Vec<String>>,
+    interval: Duration,
+}
+
+impl TargetBuilder {
+    pub fn new(
+        relabel_configs: &[RelabelConfig],
+        query: HashMap<String, Vec<String>>,
+        interval: Duration,
+    ) -> Result<Self, RelabelError> {
+        Ok(Self {
+            relabeler: Relabeler::new(relabel_configs)?,
+            query,
+            interval,
+        })
+    }
+
+    pub fn build(&self, group: &TargetGroup) -> Vec<Target> {
+        group
+            .targets
+            .iter()
+            .filter_map(|address| {
+                let mut labels = Labels::from([
+                    (ADDRESS_LABEL.to_owned(), address.clone()),
+                    (SCHEME_LABEL.to_owned(), "http".to_owned()),
+                    (METRICS_PATH_LABEL.to_owned(), "/metrics".to_owned()),
+                    (
+                        SCRAPE_INTERVAL_LABEL.to_owned(),
+                        format!("{}ms", self.interval.as_millis()),
+                    ),
+                ]);
+                labels.extend(group.labels.clone());
+
+                let labels = self.relabeler.apply(labels)?;
+                self.target(labels)
+                    .map_err(|error| emit!(PrometheusDiscoveryError { error }))
+                    .ok()
+            })
+            .collect()
+    }
+
+    fn target(&self, mut labels: Labels) -> crate::Result<Target> {
+        let label = |name: &str| labels.get(name).map(String::as_str).unwrap_or_default();
+        if label(ADDRESS_LABEL).is_empty() {
+            return Err(format!("Target has no {} label: {:?}", ADDRESS_LABEL, labels).into());
+        }
+        let url = format!(
+            "{}://{}{}",
+            label(SCHEME_LABEL),
+            label(ADDRESS_LABEL),
+            label(METRICS_PATH_LABEL)
+        );
+        let uri = url
+            .parse::<Uri>()
+            .map_err(|error| format!("Invalid target URL {:?}: {}", url, error))?;
+
+        let mut query = self.query.clone();
+        for (name, value) in &labels {
+            if let Some(param) = name.strip_prefix(PARAM_LABEL_PREFIX) {
+                query.insert(param.to_owned(), vec![value.clone()]);
+            }
+        }
+
+        let interval = label(SCRAPE_INTERVAL_LABEL);
+        let interval = parse_duration(interval)
+            .ok_or_else(|| format!("Invalid scrape interval {:?} for {:?}", interval, url))?;
+
+        labels.retain(|name, _| !name.starts_with("__"));
+        Ok(Target {
+            url: build_url(&uri, &query),
+            interval,
+            labels,
+        })
+    }
+}
+
+/// Parses a duration in the format of Prometheus, such as `1m30s`.
+fn parse_duration(input: &str) -> Option<Duration> {
+    const UNITS: [(&str, u64); 7] = [
+        ("ms", 1),
+        ("s", 1_000),
+        ("m", 60_000),
+        ("h", 3_600_000),
+        ("d", 86_400_000),
+        ("w", 604_800_000),
+        ("y", 31_536_000_000),
+    ];
+
+    let mut rest = input;
+    let mut millis = 0u64;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let value = rest[..digits].parse::<u64>().ok()?;
+        rest = &rest[digits..];
+        let (unit, factor) = UNITS.iter().find(|(unit, _)| rest.starts_with(unit))?;
+        rest = &rest[unit.len()..];
+        millis = millis.checked_add(value.checked_mul(*factor)?)?;
+    }
+    (millis > 0).then(|| Duration::from_millis(millis))
+}
+
+/// A source of target groups.
+pub enum Provider {
+    /// The statically configured endpoints.
+    Static(Vec<TargetGroup>),
+    File(FileSdConfig),
+    #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+    Kubernetes(kubernetes::KubernetesDiscoverer),
+}
+
+impl Provider {
+    /// The interval between discoveries, if the target groups can change.
+    fn refresh_interval(&self) -> Option<Duration> {
+        match self {
+            Self::Static(_) => None,
+            Self::File(config) => Some(config.refresh_interval_secs),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            Self::Kubernetes(discoverer) => Some(discoverer.refresh_interval()),
+        }
+    }
+
+    async fn discover(&self) -> crate::Result<Vec<TargetGroup>> {
+        match self {
+            Self::Static(groups) => Ok(groups.clone()),
+            Self::File(config) => Ok(config.discover()?),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            Self::Kubernetes(discoverer) => discoverer.discover().await,
+        }
+    }
+
+    /// Discovers the target groups periodically, until the receiver is dropped.
+    async fn run(self, index: usize, tx: mpsc::Sender<(usize, Vec<TargetGroup>)>) {
+        loop {
+            match self.discover().await {
+                Ok(groups) => {
+                    if tx.send((index, groups)).await.is_err() {
+                        return;
+                    }
+                }
+                // The previously discovered targets are kept until the next refresh.
+                Err(error) => emit!(PrometheusDiscoveryError { error }),
+            }
+
+            let Some(refresh_interval) = self.refresh_interval() else {
+                return;
+            };
+            tokio::time::sleep(refresh_interval).await;
+        }
+    }
+}
+
+/// Scrapes the targets discovered by the providers, each on its own interval.
+///
+/// A scrape task is started for every new target, and stopped once the target is no longer
+/// discovered.
+pub async fn run<F>(
+    providers: Vec<Provider>,
+    builder: TargetBuilder,
+    mut scrape: F,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()>
+where
+    F: FnMut(&Target) -> BoxFuture<'static, Result<(), ()>>,
+{
+    let (tx, mut rx) = mpsc::channel(providers.len().max(1));
+    let mut groups = vec![Vec::new(); providers.len()];
+    let discovery = providers
+        .into_iter()
+        .enumerate()
+        .map(|(index, provider)| tokio::spawn(provider.run(index, tx.clone())))
+        .collect::<Vec<_>>();
+
+    let mut scrapes = HashMap::<Target, JoinHandle<Result<(), ()>>>::new();
+    loop {
+        tokio::select! {
+            update = rx.recv() => {
+                // The sender is kept, so that the static targets are scraped until shutdown.
+                let Some((index, update)) = update else { break };
+                groups[index] = update;
+
+                let targets = groups
+                    .iter()
+                    .flatten()
+                    .flat_map(|group| builder.build(group))
+                    .collect::<HashSet<_>>();
+                scrapes.retain(|target, handle| {
+                    let keep = targets.contains(target);
+                    if !keep {
+                        debug!(message = "Stopped scraping target.", url = %target.url);
+                        handle.abort();
+                    }
+                    keep
+                });
+                for target in targets {
+                    if !scrapes.contains_key(&target) {
+                        debug!(message = "Started scraping target.", url = %target.url);
+                        let handle = tokio::spawn(scrape(&target));
+                        scrapes.insert(target, handle);
+                    }
+                }
+            }
+            _ = &mut shutdown => break,
+        }
+    }
+
+    for handle in discovery {
+        handle.abort();
+    }
+    // The scrapes stop on their own on shutdown.
+    scrapes
+        .into_values()
+        .collect::<FuturesUnordered<_>>()
+        .for_each(|_| futures::future::ready(()))
+        .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("15s"), Some(Duration::from_secs(15)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("15"), None);
+        assert_eq!(parse_duration("0s"), None);
+        assert_eq!(parse_duration("1x"), None);
+    }
+
+    #[test]
+    fn builds_targets_from_labels() {
+        let relabel_configs: Vec<RelabelConfig> = serde_json::from_value(serde_json::json!([
+            {
+                "source_labels": ["job"],
+                "regex": "federate",
+                "target_label": "__metrics_path__",
+                "replacement": "/federate",
+            },
+            {
+                "source_labels": ["__meta_interval"],
+                "regex": "(.+)",
+                "target_label": "__scrape_interval__",
+            },
+        ]))
+        .unwrap();
+        let builder = TargetBuilder::new(
+            &relabel_configs,
+            HashMap::from([("format".to_owned(), vec!["text".to_owned()])]),
+            Duration::from_secs(15),
+        )
+        .unwrap();
+
+        let group = TargetGroup {
+            targets: vec!["10.0.0.1:9090".to_owned(), "10.0.0.2:9090".to_owned()],
+            labels: Labels::from([
+                ("job".to_owned(), "federate".to_owned()),
+                ("__meta_interval".to_owned(), "1m".to_owned()),
+                ("__param_match[]".to_owned(), "up".to_owned()),
+            ]),
+        };
+        let targets = builder.build(&group);
+        assert_eq!(targets.len(), 2);
+        let target = &targets[0];
+        assert_eq!(target.url.host(), Some("10.0.0.1"));
+        assert_eq!(target.url.path(), "/federate");
+        let query = target.url.query().unwrap();
+        assert!(query.contains("format=text"));
+        assert!(query.contains("match%5B%5D=up"));
+        assert_eq!(target.interval, Duration::from_secs(60));
+        assert_eq!(
+            target.labels,
+            Labels::from([("job".to_owned(), "federate".to_owned())])
+        );
+    }
+
+    #[test]
+    fn keeps_the_query_of_static_endpoints() {
+        let builder = TargetBuilder::new(&[], HashMap::new(), Duration::from_secs(15)).unwrap();
+        let uri = "https://localhost:9090/federate?match%5B%5D=up"
+            .parse()
+            .unwrap();
+        let targets = builder.build(&TargetGroup::from_endpoint(&uri));
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].url.scheme_str(), Some("https"));
+        assert_eq!(targets[0].url.path(), "/federate");
+        assert!(targets[0].url.query().unwrap().contains("match%5B%5D=up"));
+        assert_eq!(targets[0].interval, Duration::from_secs(15));
+    }
+}
@@ -0,0 +1,290 @@
+//! Relabeling of discovered targets, following Prometheus' `relabel_configs`.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+
+#[derive(Debug, Snafu)]
+pub enum RelabelError {
+    #[snafu(display("Relabeling regex {:?} is invalid: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("Relabeling action `replace` requires a `target_label`"))]
+    MissingTargetLabel,
+}
+
+/// The action performed by a relabeling rule.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Sets `target_label` to `replacement` if `regex` matches the concatenated `source_labels`.
+    ///
+    /// The capture groups of `regex` can be referenced in `replacement`, such as `$1`.
+    #[default]
+    Replace,
+
+    /// Drops the targets whose concatenated `source_labels` don't match `regex`.
+    Keep,
+
+    /// Drops the targets whose concatenated `source_labels` match `regex`.
+    Drop,
+
+    /// Copies the values of the labels whose names match `regex` to the labels named by
+    /// `replacement`.
+    Labelmap,
+
+    /// Removes the labels whose names match `regex`.
+    Labeldrop,
+
+    /// Removes the labels whose names don't match `regex`.
+    Labelkeep,
+}
+
+/// A rule rewriting the labels of the discovered targets before they are scraped.
+///
+/// The rules are applied in order, and a target is not scraped if it is dropped by any of them.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The labels whose values are concatenated, with `separator`, and matched against `regex`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "__meta_kubernetes_namespace"))]
+    source_labels: Vec<String>,
+
+    /// The separator placed between the values of the `source_labels`.
+    #[serde(default = "default_separator")]
+    separator: String,
+
+    /// The label set by the `replace` action.
+    #[configurable(metadata(docs::examples = "namespace"))]
+    target_label: Option<String>,
+
+    /// The regular expression matched against the concatenated `source_labels`, or against the
+    /// label names for the `labelmap`, `labeldrop`, and `labelkeep` actions.
+    ///
+    /// The expression is anchored at both ends.
+    #[serde(default = "default_regex")]
+    #[configurable(metadata(docs::examples = "__meta_kubernetes_pod_label_(.+)"))]
+    regex: String,
+
+    /// The value the `replace` and `labelmap` actions set, in which the capture groups of
+    /// `regex` can be referenced.
+    #[serde(default = "default_replacement")]
+    replacement: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+/// The labels of a target, keyed by name.
+pub type Labels = BTreeMap<String, String>;
+
+/// A relabeling rule whose regular expression is compiled.
+#[derive(Clone, Debug)]
+struct Rule {
+    source_labels: Vec<String>,
+    separator: String,
+    target_label: Option<String>,
+    regex: Regex,
+    replacement: String,
+    action: RelabelAction,
+}
+
+/// Applies a list of relabeling rules to the labels of targets.
+#[derive(Clone, Debug, Default)]
+pub struct Relabeler {
+    rules: Vec<Rule>,
+}
+
+impl Relabeler {
+    pub fn new(configs: &[RelabelConfig]) -> Result<Self, RelabelError> {
+        let rules = configs
+            .iter()
+            .map(|config| {
+                if config.action == RelabelAction::Replace && config.target_label.is_none() {
+                    return Err(RelabelError::MissingTargetLabel);
+                }
+                let regex =
+                    Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu {
+                        regex: &config.regex,
+                    })?;
+                Ok(Rule {
+                    source_labels: config.source_labels.clone(),
+                    separator: config.separator.clone(),
+                    target_label: config.target_label.clone(),
+                    regex,
+                    replacement: config.replacement.clone(),
+                    action: config.action,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Applies the rules to the labels, returning `None` if the target is dropped.
+    pub fn apply(&self, mut labels: Labels) -> Option<Labels> {
+        for rule in &self.rules {
+            let value = rule
+                .source_labels
+                .iter()
+                .map(|name| labels.get(name).map(String::as_str).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(&rule.separator);
+
+            match rule.action {
+                RelabelAction::Replace => {
+                    let Some(captures) = rule.regex.captures(&value) else {
+                        continue;
+                    };
+                    let target = rule.target_label.as_deref().unwrap_or_default();
+                    let mut name = String::new();
+                    captures.expand(target, &mut name);
+                    let mut replacement = String::new();
+                    captures.expand(&rule.replacement, &mut replacement);
+                    if replacement.is_empty() {
+                        labels.remove(&name);
+                    } else {
+                        labels.insert(name, replacement);
+                    }
+                }
+                RelabelAction::Keep if !rule.regex.is_match(&value) => return None,
+                RelabelAction::Drop if rule.regex.is_match(&value) => return None,
+                RelabelAction::Keep | RelabelAction::Drop => {}
+                RelabelAction::Labelmap => {
+                    let mapped = labels
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            let captures = rule.regex.captures(name)?;
+                            let mut target = String::new();
+                            captures.expand(&rule.replacement, &mut target);
+                            Some((target, value.clone()))
+                        })
+                        .collect::<Vec<_>>();
+                    labels.extend(mapped);
+                }
+                RelabelAction::Labeldrop => labels.retain(|name, _| !rule.regex.is_match(name)),
+                RelabelAction::Labelkeep => labels.retain(|name, _| rule.regex.is_match(name)),
+            }
+        }
+        Some(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn relabeler(config: &str) -> Relabeler {
+        #[derive(serde::Deserialize)]
+        struct Rules {
+            rules: Vec<RelabelConfig>,
+        }
+        let rules: Rules = toml::from_str(config).unwrap();
+        Relabeler::new(&rules.rules).unwrap()
+    }
+
+    #[test]
+    fn replaces_with_captures() {
+        let relabeler = relabeler(
+            r#"
+            [[rules]]
+            source_labels = ["__address__", "__meta_port"]
+            regex = "([^:]+)(?::\\d+)?;(\\d+)"
+            replacement = "$1:$2"
+            target_label = "__address__"
+            "#,
+        );
+        let labels = relabeler
+            .apply(labels(&[
+                ("__address__", "10.0.0.1:80"),
+                ("__meta_port", "9100"),
+            ]))
+            .unwrap();
+        assert_eq!(labels["__address__"], "10.0.0.1:9100");
+    }
+
+    #[test]
+    fn keeps_and_drops_targets() {
+        let relabeler = relabeler(
+            r#"
+            [[rules]]
+            source_labels = ["__meta_scrape"]
+            regex = "true"
+            action = "keep"
+
+            [[rules]]
+            source_labels = ["env"]
+            regex = "dev"
+            action = "drop"
+            "#,
+        );
+        assert!(relabeler
+            .apply(labels(&[("__meta_scrape", "true"), ("env", "prod")]))
+            .is_some());
+        assert!(relabeler
+            .apply(labels(&[("__meta_scrape", "false"), ("env", "prod")]))
+            .is_none());
+        assert!(relabeler
+            .apply(labels(&[("__meta_scrape", "true"), ("env", "dev")]))
+            .is_none());
+    }
+
+    #[test]
+    fn maps_and_filters_label_names() {
+        let relabeler = relabeler(
+            r#"
+            [[rules]]
+            regex = "__meta_label_(.+)"
+            action = "labelmap"
+
+            [[rules]]
+            regex = "tmp_.*"
+            action = "labeldrop"
+            "#,
+        );
+        let labels = relabeler
+            .apply(labels(&[("__meta_label_app", "web"), ("tmp_id", "1")]))
+            .unwrap();
+        assert_eq!(
+            labels,
+            self::labels(&[("__meta_label_app", "web"), ("app", "web")])
+        );
+    }
+
+    #[test]
+    fn requires_target_label_for_replace() {
+        let error = Relabeler::new(&[RelabelConfig {
+            source_labels: vec![],
+            separator: default_separator(),
+            target_label: None,
+            regex: default_regex(),
+            replacement: default_replacement(),
+            action: RelabelAction::Replace,
+        }])
+        .unwrap_err();
+        assert!(matches!(error, RelabelError::MissingTargetLabel));
+    }
+}
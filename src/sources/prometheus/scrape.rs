@@ -9,7 +9,14 @@ use snafu::ResultExt;
 use vector_lib::configurable::configurable_component;
 use vector_lib::{config::LogNamespace, event::Event};
 
-use super::parser;
+#[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+use super::discovery::KubernetesSdConfig;
+use super::{
+    discovery::{
+        self, FileSdConfig, Labels, Provider, RelabelConfig, Target, TargetBuilder, TargetGroup,
+    },
+    parser,
+};
 use crate::sources::util::http::HttpMethod;
 use crate::sources::util::http_client::{default_timeout, warn_if_interval_too_low};
 use crate::{
@@ -44,10 +51,34 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
 #[derive(Clone, Debug)]
 pub struct PrometheusScrapeConfig {
     /// Endpoints to scrape metrics from.
+    ///
+    /// The endpoints are scraped along with the targets discovered by `file_sd_configs` and
+    /// `kubernetes_sd_configs`.
     #[configurable(metadata(docs::examples = "http://localhost:9090/metrics"))]
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
 
+    /// Files listing targets to scrape, in the format of Prometheus' `file_sd_configs`.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    file_sd_configs: Vec<FileSdConfig>,
+
+    /// Kubernetes objects to scrape, in the manner of Prometheus' `kubernetes_sd_configs`.
+    #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    kubernetes_sd_configs: Vec<KubernetesSdConfig>,
+
+    /// Rules rewriting the labels of the targets before they are scraped, in the format of
+    /// Prometheus' `relabel_configs`.
+    ///
+    /// The `__scheme__`, `__address__`, `__metrics_path__`, `__scrape_interval__`, and
+    /// `__param_<name>` labels of a target set the URL and interval of its scrapes. The labels not
+    /// prefixed with `__` are added as tags to the scraped metrics.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    relabel_configs: Vec<RelabelConfig>,
+
     /// The interval between scrapes. Requests are run concurrently so if a scrape takes longer
     /// than the interval a new scrape will be started. This can take extra resources, set the timeout
     /// to a value lower than the scrape interval to prevent this from happening.
@@ -117,6 +148,10 @@ impl GenerateConfig for PrometheusScrapeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: default_interval(),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -134,11 +169,10 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[typetag::serde(name = "prometheus_scrape")]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> Result<sources::Source> {
-        let urls = self
+        let endpoints = self
             .endpoints
             .iter()
             .map(|s| s.parse::<Uri>().context(sources::UriParseSnafu))
-            .map(|r| r.map(|uri| build_url(&uri, &self.query)))
             .collect::<std::result::Result<Vec<Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
 
@@ -146,23 +180,66 @@ impl SourceConfig for PrometheusScrapeConfig {
             honor_labels: self.honor_labels,
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
+            target_labels: Labels::new(),
         };
 
         warn_if_interval_too_low(self.timeout, self.interval);
 
-        let inputs = GenericHttpClientInputs {
-            urls,
-            interval: self.interval,
-            timeout: self.timeout,
-            headers: HashMap::new(),
-            content_type: "text/plain".to_string(),
-            auth: self.auth.clone(),
-            tls,
-            proxy: cx.proxy.clone(),
-            shutdown: cx.shutdown,
+        if !self.uses_discovery() {
+            let inputs = GenericHttpClientInputs {
+                urls: endpoints
+                    .iter()
+                    .map(|uri| build_url(uri, &self.query))
+                    .collect(),
+                interval: self.interval,
+                timeout: self.timeout,
+                headers: HashMap::new(),
+                content_type: "text/plain".to_string(),
+                auth: self.auth.clone(),
+                tls,
+                proxy: cx.proxy.clone(),
+                shutdown: cx.shutdown,
+            };
+
+            return Ok(call(inputs, builder, cx.out, HttpMethod::Get).boxed());
+        }
+
+        let mut providers = vec![Provider::Static(
+            endpoints.iter().map(TargetGroup::from_endpoint).collect(),
+        )];
+        providers.extend(self.file_sd_configs.iter().cloned().map(Provider::File));
+        #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+        for config in &self.kubernetes_sd_configs {
+            providers.push(Provider::Kubernetes(config.build().await?));
+        }
+        let targets = TargetBuilder::new(&self.relabel_configs, self.query.clone(), self.interval)?;
+
+        // Each target is scraped by its own client, on its own interval.
+        let timeout = self.timeout;
+        let auth = self.auth.clone();
+        let proxy = cx.proxy.clone();
+        let shutdown = cx.shutdown.clone();
+        let out = cx.out;
+        let scrape = move |target: &Target| {
+            let inputs = GenericHttpClientInputs {
+                urls: vec![target.url.clone()],
+                interval: target.interval,
+                timeout,
+                headers: HashMap::new(),
+                content_type: "text/plain".to_string(),
+                auth: auth.clone(),
+                tls: tls.clone(),
+                proxy: proxy.clone(),
+                shutdown: shutdown.clone(),
+            };
+            let builder = PrometheusScrapeBuilder {
+                target_labels: target.labels.clone(),
+                ..builder.clone()
+            };
+            call(inputs, builder, out.clone(), HttpMethod::Get).boxed()
         };
 
-        Ok(call(inputs, builder, cx.out, HttpMethod::Get).boxed())
+        Ok(discovery::run(providers, targets, scrape, cx.shutdown).boxed())
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
@@ -174,6 +251,17 @@ impl SourceConfig for PrometheusScrapeConfig {
     }
 }
 
+impl PrometheusScrapeConfig {
+    /// Whether the targets are discovered dynamically, rather than only the static endpoints.
+    fn uses_discovery(&self) -> bool {
+        #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+        if !self.kubernetes_sd_configs.is_empty() {
+            return true;
+        }
+        !self.file_sd_configs.is_empty() || !self.relabel_configs.is_empty()
+    }
+}
+
 // InstanceInfo stores the scraped instance info and the tag to insert into the log event with. It
 // is used to join these two pieces of info to avoid storing the instance if instance_tag is not
 // configured
@@ -200,6 +288,8 @@ struct PrometheusScrapeBuilder {
     honor_labels: bool,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
+    /// The labels of the discovered target, added as tags.
+    target_labels: Labels,
 }
 
 impl HttpClientBuilder for PrometheusScrapeBuilder {
@@ -231,6 +321,8 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
         PrometheusScrapeContext {
             instance_info,
             endpoint_info,
+            honor_labels: self.honor_labels,
+            target_labels: self.target_labels.clone(),
         }
     }
 }
@@ -239,6 +331,8 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
 struct PrometheusScrapeContext {
     instance_info: Option<InstanceInfo>,
     endpoint_info: Option<EndpointInfo>,
+    honor_labels: bool,
+    target_labels: Labels,
 }
 
 impl HttpClientContext for PrometheusScrapeContext {
@@ -279,6 +373,18 @@ impl HttpClientContext for PrometheusScrapeContext {
                     }
                 }
             }
+            for (tag, value) in &self.target_labels {
+                match (self.honor_labels, metric.tag_value(tag)) {
+                    (false, Some(old_value)) => {
+                        metric.replace_tag(format!("exported_{}", tag), old_value);
+                        metric.replace_tag(tag.clone(), value.clone());
+                    }
+                    (true, Some(_)) => {}
+                    (_, None) => {
+                        metric.replace_tag(tag.clone(), value.clone());
+                    }
+                }
+            }
         }
     }
 
@@ -364,6 +470,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -398,6 +508,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -450,6 +564,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -491,6 +609,59 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_prometheus_file_sd_with_relabeling() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("stats").map(|| {
+            r#"
+                    promhttp_metric_handler_requests_total{code="200"} 100 1612411516789
+            "#
+        });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let dir = crate::test_util::temp_dir();
+        let targets_file = dir.join("targets.json");
+        let targets = serde_json::json!([{
+            "targets": [in_addr.to_string()],
+            "labels": {"job": "node", "__meta_path": "/stats"},
+        }]);
+        std::fs::write(&targets_file, targets.to_string()).unwrap();
+
+        let config: PrometheusScrapeConfig = toml::from_str(&format!(
+            r#"
+            scrape_interval_secs = 1
+            instance_tag = "instance"
+
+            [[file_sd_configs]]
+            files = [{:?}]
+
+            [[relabel_configs]]
+            source_labels = ["__meta_path"]
+            target_label = "__metrics_path__"
+            "#,
+            targets_file
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        assert!(!events.is_empty());
+
+        for event in events {
+            let metric = event.into_metric();
+            assert_eq!(metric.tag_value("job"), Some(String::from("node")));
+            assert_eq!(metric.tag_value("instance"), Some(in_addr.to_string()));
+            assert_eq!(metric.tag_value("__meta_path"), None);
+        }
+    }
+
     /// According to the [spec](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md?plain=1#L115)
     /// > Label names MUST be unique within a LabelSet.
     /// Prometheus itself will reject the metric with an error. Largely to remain backward compatible with older versions of Vector,
@@ -516,6 +687,10 @@ mod test {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: HashMap::new(),
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -578,6 +753,10 @@ mod test {
                     vec!["val1".to_string(), "val2".to_string()],
                 ),
             ]),
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
@@ -678,6 +857,10 @@ mod test {
                 endpoint_tag: None,
                 honor_labels: false,
                 query: HashMap::new(),
+                file_sd_configs: Vec::new(),
+                #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+                kubernetes_sd_configs: Vec::new(),
+                relabel_configs: Vec::new(),
                 interval: Duration::from_secs(1),
                 timeout: default_timeout(),
                 tls: None,
@@ -770,6 +953,10 @@ mod integration_tests {
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: HashMap::new(),
+            file_sd_configs: Vec::new(),
+            #[cfg(feature = "sources-prometheus-scrape-kubernetes")]
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             auth: None,
             tls: None,
         };
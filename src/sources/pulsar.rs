@@ -2,6 +2,8 @@
 //! Accepts log events streamed from [`Apache Pulsar`][pulsar].
 //!
 //! [pulsar]: https://pulsar.apache.org/
+use std::time::Duration;
+
 use chrono::TimeZone;
 use futures_util::StreamExt;
 use pulsar::{
@@ -10,6 +12,8 @@ use pulsar::{
     message::proto::MessageIdData,
     Authentication, Consumer, Pulsar, SubType, TokioExecutor,
 };
+use regex::Regex;
+use serde_with::serde_as;
 use tokio_util::codec::FramedRead;
 
 use vector_lib::{
@@ -30,7 +34,10 @@ use vector_lib::{
     shutdown::ShutdownSignal,
     EstimatedJsonEncodedSizeOf,
 };
-use vrl::{owned_value_path, path, value::Kind};
+use vrl::{
+    owned_value_path, path,
+    value::{kind::Collection, Kind, ObjectMap, Value},
+};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
@@ -44,6 +51,7 @@ use crate::{
 };
 
 /// Configuration for the `pulsar` source.
+#[serde_as]
 #[configurable_component(source("pulsar", "Collect logs from Apache Pulsar."))]
 #[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
@@ -55,9 +63,30 @@ pub struct PulsarSourceConfig {
     endpoint: String,
 
     /// The Pulsar topic names to read events from.
+    ///
+    /// Either `topics` or `topics_pattern` must be set.
     #[configurable(metadata(docs::examples = "[persistent://public/default/my-topic]"))]
+    #[serde(default)]
     topics: Vec<String>,
 
+    /// A regular expression matching the names of the topics to read events from.
+    ///
+    /// The topics are discovered in the namespace of the pattern, such as `public/default` for
+    /// `persistent://public/default/logs-.*`, which is also the namespace of patterns that don't
+    /// start with a namespace. Topics that are created later are subscribed to as they are
+    /// discovered.
+    ///
+    /// Either `topics` or `topics_pattern` must be set.
+    #[configurable(metadata(docs::examples = "persistent://public/default/logs-.*"))]
+    topics_pattern: Option<String>,
+
+    /// The interval between discoveries of the topics matching `topics_pattern`, in seconds.
+    #[serde(default = "default_topics_refresh_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_topics_refresh_secs()"))]
+    #[configurable(metadata(docs::human_name = "Topics Refresh Interval"))]
+    topics_refresh_secs: Duration,
+
     /// The Pulsar consumer name.
     #[configurable(metadata(docs::examples = "consumer-name"))]
     consumer_name: Option<String>,
@@ -66,6 +95,10 @@ pub struct PulsarSourceConfig {
     #[configurable(metadata(docs::examples = "subscription_name"))]
     subscription_name: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    subscription_type: SubscriptionType,
+
     /// The consumer's priority level.
     ///
     /// The broker follows descending priorities. For example, 0=max-priority, 1, 2,...
@@ -102,6 +135,41 @@ pub struct PulsarSourceConfig {
     log_namespace: Option<bool>,
 }
 
+const fn default_topics_refresh_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// The type of the Pulsar subscription.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum SubscriptionType {
+    /// Only one consumer of the subscription is allowed.
+    Exclusive,
+
+    /// Messages are distributed among the consumers of the subscription.
+    #[default]
+    Shared,
+
+    /// Messages are delivered to one consumer of the subscription, and to another one if it fails.
+    Failover,
+
+    /// Messages are distributed among the consumers of the subscription, with all of the messages
+    /// of a key delivered to the same consumer, in order.
+    KeyShared,
+}
+
+impl From<SubscriptionType> for SubType {
+    fn from(subscription_type: SubscriptionType) -> Self {
+        match subscription_type {
+            SubscriptionType::Exclusive => SubType::Exclusive,
+            SubscriptionType::Shared => SubType::Shared,
+            SubscriptionType::Failover => SubType::Failover,
+            SubscriptionType::KeyShared => SubType::KeyShared,
+        }
+    }
+}
+
 /// Authentication configuration.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -229,6 +297,13 @@ impl SourceConfig for PulsarSourceConfig {
                 &owned_value_path!("producer_name"),
                 Kind::bytes(),
                 Some("producer_name"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::InsertIfEmpty(owned_value_path!("properties"))),
+                &owned_value_path!("properties"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())),
+                None,
             );
         vec![SourceOutput::new_maybe_logs(
             self.decoding.output_type(),
@@ -268,13 +343,21 @@ impl PulsarSourceConfig {
 
         let mut consumer_builder = pulsar
             .consumer()
-            .with_topics(&self.topics)
-            .with_subscription_type(SubType::Shared)
+            .with_subscription_type(self.subscription_type.into())
             .with_options(pulsar::consumer::ConsumerOptions {
                 priority_level: self.priority_level,
                 ..Default::default()
             });
 
+        consumer_builder = match (&self.topics_pattern, self.topics.is_empty()) {
+            (Some(pattern), true) => consumer_builder
+                .with_topic_regex(Regex::new(pattern)?)
+                .with_lookup_namespace(pattern_namespace(pattern))
+                .with_topic_refresh(self.topics_refresh_secs),
+            (None, false) => consumer_builder.with_topics(&self.topics),
+            _ => return Err("Exactly one of `topics` or `topics_pattern` must be set.".into()),
+        };
+
         if let Some(dead_letter_queue_policy) = &self.dead_letter_queue_policy {
             consumer_builder =
                 consumer_builder.with_dead_letter_policy(pulsar::consumer::DeadLetterPolicy {
@@ -299,6 +382,21 @@ impl PulsarSourceConfig {
     }
 }
 
+/// Returns the namespace in which to discover the topics matching a pattern, such as
+/// `public/default` for `persistent://public/default/logs-.*`.
+fn pattern_namespace(pattern: &str) -> String {
+    pattern
+        .split_once("://")
+        .and_then(|(_, name)| {
+            let mut parts = name.splitn(3, '/');
+            let (tenant, namespace) = (parts.next()?, parts.next()?);
+            // The topic part of the name may be missing, but the namespace must be literal.
+            let literal = |part: &str| !part.is_empty() && !part.contains(['.', '*', '(', '[']);
+            (literal(tenant) && literal(namespace)).then(|| format!("{}/{}", tenant, namespace))
+        })
+        .unwrap_or_else(|| "public/default".to_owned())
+}
+
 async fn pulsar_source(
     mut consumer: Consumer<String, TokioExecutor>,
     decoder: Decoder,
@@ -358,6 +456,18 @@ async fn parse_message(
         .and_then(|millis| chrono::Utc.timestamp_millis_opt(millis).latest());
     let topic = msg.topic.clone();
     let producer_name = msg.payload.metadata.producer_name.clone();
+    let properties = msg
+        .payload
+        .metadata
+        .properties
+        .iter()
+        .map(|property| {
+            (
+                property.key.as_str().into(),
+                Value::from(property.value.as_str()),
+            )
+        })
+        .collect::<ObjectMap>();
 
     let mut stream = FramedRead::new(msg.payload.data.as_ref(), decoder.clone());
     let stream = async_stream::stream! {
@@ -402,6 +512,14 @@ async fn parse_message(
                                 path!("producer_name"),
                                 producer_name.clone(),
                             );
+
+                            log_namespace.insert_source_metadata(
+                                PulsarSourceConfig::NAME,
+                                log,
+                                Some(LegacyKey::InsertIfEmpty(path!("properties"))),
+                                path!("properties"),
+                                properties.clone(),
+                            );
                         }
                         event
                     });
@@ -452,6 +570,7 @@ async fn finalize_event_stream(
             match out.send_event_stream(&mut stream).await {
                 Err(_error) => {
                     emit!(StreamClosedError { count: 1 });
+                    nack(consumer, &topic, message_id, pulsar_error_events).await;
                 }
                 Ok(_) => {
                     finalizer.add(FinalizerEntry { topic, message_id }, receiver);
@@ -461,6 +580,7 @@ async fn finalize_event_stream(
         None => match out.send_event_stream(&mut stream).await {
             Err(_error) => {
                 emit!(StreamClosedError { count: 1 });
+                nack(consumer, &topic, message_id, pulsar_error_events).await;
             }
             Ok(_) => {
                 if let Err(error) = consumer.ack_with_id(topic.as_str(), message_id).await {
@@ -492,28 +612,59 @@ async fn handle_ack(
                 });
             }
         }
+        // The message is redelivered, to this or another consumer of the subscription, and sent to
+        // the dead letter topic once it has been redelivered too many times, if there is one.
         BatchStatus::Errored | BatchStatus::Rejected => {
-            if let Err(error) = consumer
-                .nack_with_id(entry.topic.as_str(), entry.message_id)
-                .await
-            {
-                pulsar_error_events.emit(PulsarErrorEventData {
-                    msg: error.to_string(),
-                    error_type: PulsarErrorEventType::NAck,
-                });
-            }
+            nack(
+                consumer,
+                &entry.topic,
+                entry.message_id,
+                pulsar_error_events,
+            )
+            .await
         }
     }
 }
 
+async fn nack(
+    consumer: &mut Consumer<String, TokioExecutor>,
+    topic: &str,
+    message_id: MessageIdData,
+    pulsar_error_events: &Registered<PulsarErrorEvent>,
+) {
+    if let Err(error) = consumer.nack_with_id(topic, message_id).await {
+        pulsar_error_events.emit(PulsarErrorEventData {
+            msg: error.to_string(),
+            error_type: PulsarErrorEventType::NAck,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::sources::pulsar::PulsarSourceConfig;
+    use crate::sources::pulsar::{pattern_namespace, PulsarSourceConfig};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSourceConfig>();
     }
+
+    #[test]
+    fn pattern_namespaces() {
+        assert_eq!(
+            pattern_namespace("persistent://tenant/namespace/logs-.*"),
+            "tenant/namespace"
+        );
+        assert_eq!(
+            pattern_namespace("non-persistent://tenant/namespace"),
+            "tenant/namespace"
+        );
+        assert_eq!(
+            pattern_namespace("persistent://tenant/name.*/logs"),
+            "public/default"
+        );
+        assert_eq!(pattern_namespace("logs-.*"), "public/default");
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -555,8 +706,11 @@ mod integration_tests {
         let cnf = PulsarSourceConfig {
             endpoint: pulsar_address(),
             topics: vec![topic.clone()],
+            topics_pattern: None,
+            topics_refresh_secs: default_topics_refresh_secs(),
             consumer_name: None,
             subscription_name: None,
+            subscription_type: SubscriptionType::Shared,
             priority_level: None,
             batch_size: None,
             auth: None,
@@ -606,4 +760,57 @@ mod integration_tests {
             msg.into()
         );
     }
+
+    #[tokio::test]
+    async fn consumes_topics_pattern_with_properties() {
+        trace_init();
+
+        let prefix = format!("test-{}", random_string(10));
+        let topic = format!("persistent://public/default/{}-a", prefix);
+        let cnf = PulsarSourceConfig {
+            endpoint: pulsar_address(),
+            topics_pattern: Some(format!("persistent://public/default/{}-.*", prefix)),
+            subscription_type: SubscriptionType::KeyShared,
+            ..Default::default()
+        };
+
+        let pulsar = Pulsar::<TokioExecutor>::builder(&cnf.endpoint, TokioExecutor)
+            .build()
+            .await
+            .unwrap();
+        // The topic must exist for the pattern to discover it.
+        let mut producer = pulsar.producer().with_topic(topic).build().await.unwrap();
+
+        let consumer = cnf.create_consumer().await.unwrap();
+        let decoder = DecodingConfig::new(
+            cnf.framing.clone(),
+            cnf.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build()
+        .unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        tokio::spawn(pulsar_source(
+            consumer,
+            decoder,
+            ShutdownSignal::noop(),
+            tx,
+            false,
+            LogNamespace::Legacy,
+        ));
+        producer
+            .create_message()
+            .with_content("test message")
+            .with_key("key")
+            .with_properties([("origin", "test")])
+            .send_non_blocking()
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "test message".into());
+        assert_eq!(log["properties.origin"], "test".into());
+    }
 }
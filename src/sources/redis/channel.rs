@@ -43,7 +43,7 @@ impl InputHandler {
             while let Some(msg) = pubsub_stream.next().await {
                 match msg.get_payload::<String>() {
                     Ok(line) => {
-                        if let Err(()) = self.handle_line(line).await {
+                        if let Err(()) = self.handle_line(line, None, &None).await {
                             break;
                         }
                     }
@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use chrono::Utc;
 use futures::StreamExt;
+use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_lib::codecs::{
@@ -20,14 +23,18 @@ use vrl::value::Kind;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
+    config::{
+        log_schema, GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        SourceOutput,
+    },
+    event::{BatchNotifier, Event},
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +56,15 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// This is based on Redis Streams, read with a consumer group. Entries are acknowledged once
+    /// their events have been delivered, so that entries are read again, by this consumer or
+    /// another one, if Vector stops before delivering them.
+    ///
+    /// Requires Redis 6.2 or later.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -74,6 +90,83 @@ pub enum Method {
     Rpop,
 }
 
+/// Options for the Redis `stream` data type.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read the stream with.
+    ///
+    /// The group, and the stream, are created if they don't exist.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of the consumer in the consumer group.
+    ///
+    /// Each instance of Vector reading the stream with the same group must use a different name.
+    ///
+    /// By default, the hostname is used.
+    #[configurable(metadata(docs::examples = "vector-0"))]
+    consumer: Option<String>,
+
+    /// The ID of the entry after which the consumer group starts reading the stream, when the group
+    /// is created.
+    ///
+    /// `$` reads only the entries added after the group is created, and `0` reads all of the
+    /// entries of the stream.
+    #[serde(default = "default_start_id")]
+    #[derivative(Default(value = "default_start_id()"))]
+    #[configurable(metadata(docs::examples = "$", docs::examples = "0"))]
+    start_id: String,
+
+    /// The field of the stream entries that holds the message to decode.
+    ///
+    /// Entries without this field are logged and acknowledged.
+    #[serde(default = "default_field")]
+    #[derivative(Default(value = "default_field()"))]
+    field: String,
+
+    /// The maximum number of entries to read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    batch_size: usize,
+
+    /// The time after which the entries read by another consumer of the group, but never
+    /// acknowledged, are claimed by this consumer, in seconds.
+    ///
+    /// This is also the interval between checks for entries to claim, and between reads of the
+    /// entries of this consumer whose events failed to be delivered.
+    #[serde(default = "default_min_idle_time_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_min_idle_time_secs()"))]
+    #[configurable(metadata(docs::human_name = "Minimum Idle Time"))]
+    min_idle_time_secs: Duration,
+}
+
+fn default_group() -> String {
+    "vector".to_owned()
+}
+
+fn default_start_id() -> String {
+    "$".to_owned()
+}
+
+fn default_field() -> String {
+    "message".to_owned()
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+const fn default_min_idle_time_secs() -> Duration {
+    Duration::from_secs(300)
+}
+
 pub struct ConnectionInfo {
     protocol: &'static str,
     endpoint: String,
@@ -98,13 +191,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel`, or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
@@ -112,6 +208,8 @@ pub struct RedisSourceConfig {
     url: String,
 
     /// The Redis key to read messages from.
+    ///
+    /// This is the list to pop from, the channel to subscribe to, or the stream to read.
     #[configurable(metadata(docs::examples = "vector"))]
     key: String,
 
@@ -133,6 +231,10 @@ pub struct RedisSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -160,7 +262,7 @@ impl SourceConfig for RedisSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        // A key must be specified to actually query i.e. the list to pop from, or the channel to subscribe to.
+        // A key must be specified to actually query i.e. the list to pop from, the channel to subscribe to, or the stream to read.
         if self.key.is_empty() {
             return Err("`key` cannot be empty.".into());
         }
@@ -176,6 +278,7 @@ impl SourceConfig for RedisSourceConfig {
             connection_info.protocol
         )));
         let events_received = register!(EventsReceived);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let handler = InputHandler {
             client,
             bytes_received: bytes_received.clone(),
@@ -193,6 +296,14 @@ impl SourceConfig for RedisSourceConfig {
                 handler.watch(method).await
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let options = self.stream.clone().unwrap_or_default();
+                let consumer = match &options.consumer {
+                    Some(consumer) => consumer.clone(),
+                    None => crate::get_hostname()?,
+                };
+                handler.consume(options, consumer, acknowledgements).await
+            }
         }
     }
 
@@ -205,7 +316,7 @@ impl SourceConfig for RedisSourceConfig {
             .and_then(|k| k.path)
             .map(LegacyKey::InsertIfEmpty);
 
-        let schema_definition = self
+        let mut schema_definition = self
             .decoding
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -216,6 +327,15 @@ impl SourceConfig for RedisSourceConfig {
                 None,
             )
             .with_standard_vector_source_metadata();
+        if matches!(self.data_type, DataTypeConfig::Stream) {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("id"),
+                Kind::bytes(),
+                None,
+            );
+        }
 
         vec![SourceOutput::new_maybe_logs(
             self.decoding.output_type(),
@@ -224,7 +344,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -240,7 +360,16 @@ struct InputHandler {
 }
 
 impl InputHandler {
-    async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+    /// Decodes a message into events, and sends them.
+    ///
+    /// Messages read from a stream also carry the ID of their entry, and the batch that is
+    /// notified once their events have been delivered.
+    async fn handle_line(
+        &mut self,
+        line: String,
+        id: Option<&str>,
+        batch: &Option<BatchNotifier>,
+    ) -> Result<(), ()> {
         let now = Utc::now();
 
         self.bytes_received.emit(ByteSize(line.len()));
@@ -275,9 +404,18 @@ impl InputHandler {
                                 path!("key"),
                                 self.key.as_str(),
                             );
+                            if let Some(id) = id {
+                                self.log_namespace.insert_source_metadata(
+                                    RedisSourceConfig::NAME,
+                                    log,
+                                    None::<LegacyKey<&str>>,
+                                    path!("id"),
+                                    id,
+                                );
+                            }
                         };
 
-                        event
+                        event.with_batch_notifier_option(batch)
                     });
 
                     if (self.cx.out.send_batch(events).await).is_err() {
@@ -306,6 +444,29 @@ mod test {
     fn generate_config() {
         crate::test_util::test_generate_config::<RedisSourceConfig>();
     }
+
+    #[test]
+    fn parse_stream_config() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            url = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "stream"
+            stream.group = "ingest"
+            stream.min_idle_time_secs = 60
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.can_acknowledge());
+        let stream = config.stream.unwrap();
+        assert_eq!(stream.group, "ingest");
+        assert_eq!(stream.consumer, None);
+        assert_eq!(stream.start_id, "$");
+        assert_eq!(stream.field, "message");
+        assert_eq!(stream.batch_size, 100);
+        assert_eq!(stream.min_idle_time_secs, Duration::from_secs(60));
+    }
 }
 
 #[cfg(all(test, feature = "redis-integration-tests"))]
@@ -322,10 +483,66 @@ mod integration_test {
         },
         SourceSender,
     };
+    use vector_lib::event::EventStatus;
     use vrl::value;
 
     const REDIS_SERVER: &str = "redis://redis:6379/0";
 
+    #[tokio::test]
+    async fn redis_source_stream_consumer_group() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                consumer: Some("vector-test".to_owned()),
+                start_id: "0".to_owned(),
+                ..Default::default()
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: true.into(),
+            log_namespace: Some(true),
+        };
+
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let context = SourceContext::new_test(tx, None);
+        let source = config
+            .build(context)
+            .await
+            .expect("source should not fail to build");
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        for (event, message) in events.iter().zip(["1", "2", "3"]) {
+            let log = event.as_log();
+            assert_eq!(log.value(), &message.into());
+            assert!(log
+                .metadata()
+                .value()
+                .get(path!(RedisSourceConfig::NAME, "id"))
+                .is_some());
+        }
+
+        // The entries are acknowledged once their events have been delivered.
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        let pending: redis::streams::StreamPendingReply =
+            conn.xpending(&key, "vector").await.unwrap();
+        assert_eq!(pending.count(), 0);
+    }
+
     #[tokio::test]
     async fn redis_source_list_rpop() {
         // Push some test data into a list object which we'll read from.
@@ -345,11 +562,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -386,11 +605,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(true),
         };
 
@@ -427,11 +648,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -460,11 +683,13 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -0,0 +1,432 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use redis::{
+    aio::ConnectionManager,
+    from_redis_value,
+    streams::{StreamClaimReply, StreamId, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, RedisError, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::time::Instant;
+use tracing_futures::Instrument;
+use vector_lib::finalizer::UnorderedFinalizer;
+
+use super::{list::backoff_exponential, InputHandler, StreamOption};
+use crate::{
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{RedisReceiveEventError, RedisStreamAckError},
+    sources::Source,
+};
+
+/// The IDs of the entries whose events are awaiting delivery.
+type Finalizer = UnorderedFinalizer<Vec<String>>;
+
+/// The ID from which a scan of the pending entries starts, and at which it ends.
+const SCAN_START_ID: &str = "0-0";
+
+/// The IDs of the entries that were read and are not acknowledged yet.
+///
+/// These entries are still pending in the consumer group, so they are skipped when the pending
+/// entries are read again.
+#[derive(Clone, Default)]
+struct InFlight(Arc<Mutex<HashSet<String>>>);
+
+impl InFlight {
+    /// Keeps the entries that are not in flight already, and marks them as in flight.
+    fn start(&self, entries: Vec<StreamId>) -> Vec<StreamId> {
+        let mut in_flight = self.0.lock().expect("in-flight lock poisoned");
+        entries
+            .into_iter()
+            .filter(|entry| in_flight.insert(entry.id.clone()))
+            .collect()
+    }
+
+    /// Marks the entries as no longer in flight, once they are acknowledged or failed.
+    fn finish(&self, ids: &[String]) {
+        let mut in_flight = self.0.lock().expect("in-flight lock poisoned");
+        for id in ids {
+            in_flight.remove(id);
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: RedisError },
+}
+
+impl InputHandler {
+    pub(super) async fn consume(
+        mut self,
+        options: StreamOption,
+        consumer: String,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        create_group(&mut conn, &self.key, &options)
+            .await
+            .context(CreateGroupSnafu {})?;
+
+        Ok(Box::pin(async move {
+            let in_flight = InFlight::default();
+            let finalizer = acknowledgements.then(|| {
+                let (finalizer, mut ack_stream) = Finalizer::new(Some(self.cx.shutdown.clone()));
+                let mut conn = conn.clone();
+                let key = self.key.clone();
+                let group = options.group.clone();
+                let in_flight = in_flight.clone();
+                tokio::spawn(
+                    async move {
+                        while let Some((status, ids)) = ack_stream.next().await {
+                            // The entries whose events were not delivered stay pending, and are
+                            // read again later.
+                            if status == BatchStatus::Delivered {
+                                ack(&mut conn, &key, &group, &ids).await;
+                            }
+                            in_flight.finish(&ids);
+                        }
+                    }
+                    .in_current_span(),
+                );
+                finalizer
+            });
+
+            let mut reader = StreamReader::new(
+                conn.clone(),
+                self.key.clone(),
+                &options,
+                consumer,
+                in_flight.clone(),
+            );
+            let mut shutdown = self.cx.shutdown.clone();
+            let mut retry: u32 = 0;
+            loop {
+                let res = tokio::select! {
+                    res = reader.read() => res,
+                    _ = &mut shutdown => break
+                };
+
+                match res {
+                    Err(error) => {
+                        emit!(RedisReceiveEventError::from(error));
+                        retry += 1;
+                        backoff_exponential(retry).await
+                    }
+                    Ok(entries) => {
+                        retry = 0;
+                        if entries.is_empty() {
+                            continue;
+                        }
+                        let result = self
+                            .handle_entries(entries, &options.field, &finalizer)
+                            .await;
+                        match result {
+                            // Without acknowledgements, entries are acknowledged once they are
+                            // sent.
+                            Ok(Some(ids)) => {
+                                ack(&mut conn, &self.key, &options.group, &ids).await;
+                                in_flight.finish(&ids);
+                            }
+                            Ok(None) => {}
+                            Err(()) => break,
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    /// Sends the events of the entries, and returns the IDs of the entries to acknowledge now, if
+    /// they are not acknowledged once their events have been delivered.
+    async fn handle_entries(
+        &mut self,
+        entries: Vec<StreamId>,
+        field: &str,
+        finalizer: &Option<Finalizer>,
+    ) -> Result<Option<Vec<String>>, ()> {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+        let mut ids = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match entry.get::<String>(field) {
+                Some(line) => self.handle_line(line, Some(&entry.id), &batch).await?,
+                None => emit!(RedisReceiveEventError::from(RedisError::from((
+                    ErrorKind::TypeError,
+                    "Stream entry has no message field",
+                    format!("entry {}, field {:?}", entry.id, field),
+                )))),
+            }
+            ids.push(entry.id);
+        }
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        match (finalizer, receiver) {
+            (Some(finalizer), Some(receiver)) => {
+                finalizer.add(ids, receiver);
+                Ok(None)
+            }
+            _ => Ok(Some(ids)),
+        }
+    }
+}
+
+/// Creates the consumer group, and the stream, unless the group already exists.
+async fn create_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+) -> RedisResult<()> {
+    match conn
+        .xgroup_create_mkstream::<_, _, _, ()>(key, &options.group, &options.start_id)
+        .await
+    {
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        result => result,
+    }
+}
+
+async fn ack(conn: &mut ConnectionManager, key: &str, group: &str, ids: &[String]) {
+    if let Err(error) = conn.xack::<_, _, _, ()>(key, group, ids).await {
+        emit!(RedisStreamAckError {
+            error,
+            count: ids.len(),
+        });
+    }
+}
+
+/// Reads the entries of the stream for a consumer of the group.
+///
+/// The entries that were delivered to this consumer, but never acknowledged, are read first, on
+/// startup and then every `min_idle_time_secs`, so that the entries whose events failed to be
+/// delivered are read again. The entries that have been pending for longer than
+/// `min_idle_time_secs` are also claimed, as the consumer that read them has presumably stopped.
+/// Then new entries are read.
+struct StreamReader {
+    conn: ConnectionManager,
+    key: String,
+    group: String,
+    consumer: String,
+    batch_size: usize,
+    min_idle_time: Duration,
+    in_flight: InFlight,
+    /// The ID after which to read the pending entries of this consumer, until they have all been
+    /// read.
+    pending_cursor: Option<String>,
+    /// The ID from which to claim the pending entries of the group, until they have all been
+    /// scanned.
+    claim_cursor: String,
+    next_claim: Instant,
+}
+
+impl StreamReader {
+    fn new(
+        conn: ConnectionManager,
+        key: String,
+        options: &StreamOption,
+        consumer: String,
+        in_flight: InFlight,
+    ) -> Self {
+        Self {
+            conn,
+            key,
+            group: options.group.clone(),
+            consumer,
+            batch_size: options.batch_size,
+            min_idle_time: options.min_idle_time_secs,
+            in_flight,
+            pending_cursor: Some(SCAN_START_ID.to_owned()),
+            claim_cursor: SCAN_START_ID.to_owned(),
+            next_claim: Instant::now(),
+        }
+    }
+
+    async fn read(&mut self) -> RedisResult<Vec<StreamId>> {
+        while let Some(cursor) = self.pending_cursor.take() {
+            let entries = self.read_group(&cursor, None).await?;
+            self.pending_cursor = entries.last().map(|entry| entry.id.clone());
+            let entries = self.in_flight.start(entries);
+            if !entries.is_empty() {
+                return Ok(entries);
+            }
+        }
+
+        let now = Instant::now();
+        if now >= self.next_claim {
+            let (next_cursor, entries) = self.claim().await?;
+            // The pending entries are scanned in pages, until the cursor wraps around.
+            if next_cursor == SCAN_START_ID {
+                self.next_claim = now + self.min_idle_time;
+                self.pending_cursor = Some(SCAN_START_ID.to_owned());
+            }
+            self.claim_cursor = next_cursor;
+            let entries = self.in_flight.start(entries);
+            if !entries.is_empty() {
+                return Ok(entries);
+            }
+            if self.claim_cursor != SCAN_START_ID {
+                return Ok(Vec::new());
+            }
+        }
+
+        // A block of zero waits forever, so wait at least a millisecond until the next claim.
+        let block = self
+            .next_claim
+            .saturating_duration_since(Instant::now())
+            .max(Duration::from_millis(1));
+        let entries = self.read_group(">", Some(block)).await?;
+        Ok(self.in_flight.start(entries))
+    }
+
+    async fn read_group(
+        &mut self,
+        id: &str,
+        block: Option<Duration>,
+    ) -> RedisResult<Vec<StreamId>> {
+        let mut options = StreamReadOptions::default()
+            .group(&self.group, &self.consumer)
+            .count(self.batch_size);
+        if let Some(block) = block {
+            options = options.block(block.as_millis() as usize);
+        }
+
+        // A read that times out while blocking returns nothing.
+        let reply: Option<StreamReadReply> = self
+            .conn
+            .xread_options(&[&self.key], &[id], &options)
+            .await?;
+        Ok(reply
+            .into_iter()
+            .flat_map(|reply| reply.keys)
+            .flat_map(|key| key.ids)
+            .collect())
+    }
+
+    /// Claims a page of the entries that have been pending for longer than the minimum idle time,
+    /// and returns them with the ID from which to claim the next page.
+    async fn claim(&mut self) -> RedisResult<(String, Vec<StreamId>)> {
+        let reply: Value = redis::cmd("XAUTOCLAIM")
+            .arg(&self.key)
+            .arg(&self.group)
+            .arg(&self.consumer)
+            .arg(self.min_idle_time.as_millis() as usize)
+            .arg(&self.claim_cursor)
+            .arg("COUNT")
+            .arg(self.batch_size)
+            .query_async(&mut self.conn)
+            .await?;
+        parse_autoclaim_reply(&reply)
+    }
+}
+
+/// Parses the reply of `XAUTOCLAIM`, which holds the ID from which to claim the next page and the
+/// claimed entries, followed by the IDs of the deleted entries since Redis 7.
+///
+/// Deleted entries are returned as nil by Redis 6.2, and skipped.
+fn parse_autoclaim_reply(reply: &Value) -> RedisResult<(String, Vec<StreamId>)> {
+    match reply {
+        Value::Bulk(values) if values.len() >= 2 => {
+            let next_cursor = from_redis_value(&values[0])?;
+            let claimed: StreamClaimReply = from_redis_value(&values[1])?;
+            Ok((next_cursor, claimed.ids))
+        }
+        _ => Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Unexpected XAUTOCLAIM reply",
+            format!("{reply:?}"),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(value: &str) -> Value {
+        Value::Data(value.as_bytes().to_vec())
+    }
+
+    fn entry(id: &str, message: &str) -> Value {
+        Value::Bulk(vec![
+            data(id),
+            Value::Bulk(vec![data("message"), data(message)]),
+        ])
+    }
+
+    #[test]
+    fn parses_autoclaim_reply() {
+        // Redis 7 also returns the IDs of the deleted entries.
+        let reply = Value::Bulk(vec![
+            data("1-5"),
+            Value::Bulk(vec![entry("1-1", "first"), entry("1-2", "second")]),
+            Value::Bulk(vec![data("1-3")]),
+        ]);
+        let (next_cursor, entries) = parse_autoclaim_reply(&reply).unwrap();
+        assert_eq!(next_cursor, "1-5");
+        let ids = entries
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["1-1", "1-2"]);
+        assert_eq!(
+            entries[1].get::<String>("message").as_deref(),
+            Some("second")
+        );
+    }
+
+    #[test]
+    fn parses_autoclaim_reply_with_deleted_entries() {
+        // Redis 6.2 returns deleted entries as nil.
+        let reply = Value::Bulk(vec![
+            data(SCAN_START_ID),
+            Value::Bulk(vec![Value::Nil, entry("1-2", "second")]),
+        ]);
+        let (next_cursor, entries) = parse_autoclaim_reply(&reply).unwrap();
+        assert_eq!(next_cursor, SCAN_START_ID);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "1-2");
+    }
+
+    #[test]
+    fn rejects_unexpected_autoclaim_reply() {
+        assert!(parse_autoclaim_reply(&Value::Nil).is_err());
+        assert!(parse_autoclaim_reply(&Value::Bulk(vec![data(SCAN_START_ID)])).is_err());
+    }
+
+    #[test]
+    fn skips_entries_in_flight() {
+        let in_flight = InFlight::default();
+        let ids = |entries: Vec<StreamId>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.id)
+                .collect::<Vec<_>>()
+        };
+        let read = |ids: &[&str]| {
+            ids.iter()
+                .map(|id| StreamId {
+                    id: id.to_string(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(in_flight.start(read(&["1-1", "1-2"]))), ["1-1", "1-2"]);
+        assert_eq!(ids(in_flight.start(read(&["1-1", "1-3"]))), ["1-3"]);
+
+        in_flight.finish(&["1-1".to_owned()]);
+        assert_eq!(ids(in_flight.start(read(&["1-1", "1-2"]))), ["1-1"]);
+    }
+}
@@ -0,0 +1,516 @@
+//! Decoding of sFlow v5 datagrams.
+//!
+//! See <https://sflow.org/sflow_version_5.txt> for the format of datagrams, which are encoded with
+//! XDR. Only the flow and counter records defined by the specification are decoded, the others
+//! are skipped.
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use snafu::Snafu;
+use vrl::value::{ObjectMap, Value};
+
+const SFLOW_V5: u32 = 5;
+
+const FLOW_SAMPLE: u32 = 1;
+const COUNTERS_SAMPLE: u32 = 2;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const EXPANDED_COUNTERS_SAMPLE: u32 = 4;
+
+const RAW_PACKET_HEADER: u32 = 1;
+const ETHERNET_FRAME_DATA: u32 = 2;
+const IPV4_DATA: u32 = 3;
+const IPV6_DATA: u32 = 4;
+const EXTENDED_SWITCH: u32 = 1001;
+const EXTENDED_ROUTER: u32 = 1002;
+
+const GENERIC_INTERFACE_COUNTERS: u32 = 1;
+const ETHERNET_INTERFACE_COUNTERS: u32 = 2;
+const PROCESSOR_COUNTERS: u32 = 1001;
+
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("Datagram is truncated"))]
+    Truncated,
+    #[snafu(display("Unsupported version {}", version))]
+    UnsupportedVersion { version: u32 },
+    #[snafu(display("Unsupported agent address type {}", address_type))]
+    UnsupportedAddressType { address_type: u32 },
+}
+
+/// Decodes a datagram into one record per flow or counter sample.
+pub fn decode(data: &[u8]) -> Result<Vec<ObjectMap>, DecodeError> {
+    let mut reader = Reader::new(data);
+    let version = reader.u32()?;
+    if version != SFLOW_V5 {
+        return Err(DecodeError::UnsupportedVersion { version });
+    }
+
+    let mut header = ObjectMap::new();
+    header.insert("agent_address".into(), reader.address()?.to_string().into());
+    header.insert("sub_agent_id".into(), reader.u32()?.into());
+    header.insert("datagram_sequence_number".into(), reader.u32()?.into());
+    header.insert("agent_uptime".into(), reader.u32()?.into());
+
+    let sample_count = reader.u32()?;
+    let mut samples = Vec::new();
+    for _ in 0..sample_count {
+        let (format, mut data) = reader.opaque_with_format()?;
+        let mut sample = header.clone();
+        match format {
+            FLOW_SAMPLE | EXPANDED_FLOW_SAMPLE => {
+                decode_flow_sample(&mut data, format == EXPANDED_FLOW_SAMPLE, &mut sample)?
+            }
+            COUNTERS_SAMPLE | EXPANDED_COUNTERS_SAMPLE => {
+                decode_counters_sample(&mut data, format == EXPANDED_COUNTERS_SAMPLE, &mut sample)?
+            }
+            _ => continue,
+        }
+        samples.push(sample);
+    }
+
+    Ok(samples)
+}
+
+fn insert(sample: &mut ObjectMap, name: &str, value: impl Into<Value>) {
+    sample.insert(name.into(), value.into());
+}
+
+/// Decodes the identifier of the source of a sample, which is an interface for most agents.
+fn decode_source_id(
+    reader: &mut Reader,
+    expanded: bool,
+    sample: &mut ObjectMap,
+) -> Result<(), DecodeError> {
+    let (source_id_type, source_id_index) = if expanded {
+        (reader.u32()?, reader.u32()?)
+    } else {
+        let source_id = reader.u32()?;
+        (source_id >> 24, source_id & 0x00ff_ffff)
+    };
+    insert(sample, "source_id_type", source_id_type);
+    insert(sample, "source_id_index", source_id_index);
+    Ok(())
+}
+
+fn decode_flow_sample(
+    reader: &mut Reader,
+    expanded: bool,
+    sample: &mut ObjectMap,
+) -> Result<(), DecodeError> {
+    insert(sample, "sample_type", "flow");
+    insert(sample, "sequence_number", reader.u32()?);
+    decode_source_id(reader, expanded, sample)?;
+    insert(sample, "sampling_rate", reader.u32()?);
+    insert(sample, "sample_pool", reader.u32()?);
+    insert(sample, "drops", reader.u32()?);
+    if expanded {
+        let _input_format = reader.u32()?;
+        insert(sample, "input_interface", reader.u32()?);
+        let _output_format = reader.u32()?;
+        insert(sample, "output_interface", reader.u32()?);
+    } else {
+        // The two top bits hold the format, which is `0` for a single interface.
+        insert(sample, "input_interface", reader.u32()? & 0x3fff_ffff);
+        insert(sample, "output_interface", reader.u32()? & 0x3fff_ffff);
+    }
+
+    let record_count = reader.u32()?;
+    for _ in 0..record_count {
+        let (format, mut data) = reader.opaque_with_format()?;
+        match format {
+            RAW_PACKET_HEADER => {
+                let protocol = data.u32()?;
+                insert(sample, "frame_length", data.u32()?);
+                insert(sample, "stripped", data.u32()?);
+                let header = data.opaque()?;
+                insert(sample, "header_length", header.len() as u32);
+                if protocol == HEADER_PROTOCOL_ETHERNET {
+                    decode_ethernet_header(header, sample);
+                }
+            }
+            ETHERNET_FRAME_DATA => {
+                insert(sample, "frame_length", data.u32()?);
+                insert(sample, "source_mac", mac(data.take(8)?));
+                insert(sample, "destination_mac", mac(data.take(8)?));
+                insert(sample, "ethernet_type", data.u32()?);
+            }
+            IPV4_DATA | IPV6_DATA => {
+                let addresses = if format == IPV4_DATA { 4 } else { 16 };
+                insert(sample, "ip_length", data.u32()?);
+                insert(sample, "ip_protocol", data.u32()?);
+                insert(sample, "source_ip", ip(data.take(addresses)?));
+                insert(sample, "destination_ip", ip(data.take(addresses)?));
+                insert(sample, "source_port", data.u32()?);
+                insert(sample, "destination_port", data.u32()?);
+                insert(sample, "tcp_flags", data.u32()?);
+                insert(sample, "ip_tos", data.u32()?);
+            }
+            EXTENDED_SWITCH => {
+                insert(sample, "source_vlan", data.u32()?);
+                insert(sample, "source_priority", data.u32()?);
+                insert(sample, "destination_vlan", data.u32()?);
+                insert(sample, "destination_priority", data.u32()?);
+            }
+            EXTENDED_ROUTER => {
+                insert(sample, "next_hop", data.address()?.to_string());
+                insert(sample, "source_mask_length", data.u32()?);
+                insert(sample, "destination_mask_length", data.u32()?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn decode_counters_sample(
+    reader: &mut Reader,
+    expanded: bool,
+    sample: &mut ObjectMap,
+) -> Result<(), DecodeError> {
+    insert(sample, "sample_type", "counters");
+    insert(sample, "sequence_number", reader.u32()?);
+    decode_source_id(reader, expanded, sample)?;
+
+    let record_count = reader.u32()?;
+    for _ in 0..record_count {
+        let (format, mut data) = reader.opaque_with_format()?;
+        let mut counters = ObjectMap::new();
+        let name = match format {
+            GENERIC_INTERFACE_COUNTERS => {
+                insert(&mut counters, "index", data.u32()?);
+                insert(&mut counters, "type", data.u32()?);
+                insert(&mut counters, "speed", data.u64()?);
+                insert(&mut counters, "direction", data.u32()?);
+                insert(&mut counters, "status", data.u32()?);
+                insert(&mut counters, "in_octets", data.u64()?);
+                for name in [
+                    "in_unicast_packets",
+                    "in_multicast_packets",
+                    "in_broadcast_packets",
+                    "in_discards",
+                    "in_errors",
+                    "in_unknown_protocols",
+                ] {
+                    insert(&mut counters, name, data.u32()?);
+                }
+                insert(&mut counters, "out_octets", data.u64()?);
+                for name in [
+                    "out_unicast_packets",
+                    "out_multicast_packets",
+                    "out_broadcast_packets",
+                    "out_discards",
+                    "out_errors",
+                    "promiscuous_mode",
+                ] {
+                    insert(&mut counters, name, data.u32()?);
+                }
+                "interface"
+            }
+            ETHERNET_INTERFACE_COUNTERS => {
+                for name in [
+                    "alignment_errors",
+                    "fcs_errors",
+                    "single_collision_frames",
+                    "multiple_collision_frames",
+                    "sqe_test_errors",
+                    "deferred_transmissions",
+                    "late_collisions",
+                    "excessive_collisions",
+                    "internal_mac_transmit_errors",
+                    "carrier_sense_errors",
+                    "frame_too_longs",
+                    "internal_mac_receive_errors",
+                    "symbol_errors",
+                ] {
+                    insert(&mut counters, name, data.u32()?);
+                }
+                "ethernet"
+            }
+            PROCESSOR_COUNTERS => {
+                // The CPU utilizations are in hundredths of a percent.
+                insert(&mut counters, "cpu_5s", data.u32()?);
+                insert(&mut counters, "cpu_1m", data.u32()?);
+                insert(&mut counters, "cpu_5m", data.u32()?);
+                insert(&mut counters, "total_memory", data.u64()?);
+                insert(&mut counters, "free_memory", data.u64()?);
+                "processor"
+            }
+            _ => continue,
+        };
+        insert(sample, name, counters);
+    }
+
+    Ok(())
+}
+
+/// Decodes the fields of the sampled packet, as far as its header was captured.
+fn decode_ethernet_header(header: &[u8], sample: &mut ObjectMap) {
+    let mut reader = Reader::new(header);
+    let (Ok(destination), Ok(source), Ok(mut ethernet_type)) =
+        (reader.take(6), reader.take(6), reader.u16())
+    else {
+        return;
+    };
+    insert(sample, "destination_mac", mac(destination));
+    insert(sample, "source_mac", mac(source));
+    if ethernet_type == ETHERTYPE_VLAN {
+        let (Ok(tag), Ok(inner_type)) = (reader.u16(), reader.u16()) else {
+            return;
+        };
+        insert(sample, "vlan_id", tag & 0x0fff);
+        ethernet_type = inner_type;
+    }
+    insert(sample, "ethernet_type", ethernet_type);
+
+    let packet = reader.data;
+    let (protocol, transport) = match ethernet_type {
+        ETHERTYPE_IPV4 if packet.len() >= 20 => {
+            let header_length = usize::from(packet[0] & 0x0f) * 4;
+            insert(sample, "ip_tos", packet[1]);
+            insert(sample, "ip_ttl", packet[8]);
+            insert(sample, "source_ip", ip(&packet[12..16]));
+            insert(sample, "destination_ip", ip(&packet[16..20]));
+            (packet[9], packet.get(header_length..).unwrap_or_default())
+        }
+        ETHERTYPE_IPV6 if packet.len() >= 40 => {
+            insert(sample, "ip_ttl", packet[7]);
+            insert(sample, "source_ip", ip(&packet[8..24]));
+            insert(sample, "destination_ip", ip(&packet[24..40]));
+            (packet[6], &packet[40..])
+        }
+        _ => return,
+    };
+    insert(sample, "ip_protocol", protocol);
+
+    if matches!(protocol, IP_PROTOCOL_TCP | IP_PROTOCOL_UDP) && transport.len() >= 4 {
+        let source_port = u16::from_be_bytes([transport[0], transport[1]]);
+        let destination_port = u16::from_be_bytes([transport[2], transport[3]]);
+        insert(sample, "source_port", source_port);
+        insert(sample, "destination_port", destination_port);
+    }
+    if protocol == IP_PROTOCOL_TCP && transport.len() >= 14 {
+        insert(sample, "tcp_flags", transport[13]);
+    }
+}
+
+fn mac(data: &[u8]) -> String {
+    data.iter()
+        .take(6)
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn ip(data: &[u8]) -> String {
+    match data.len() {
+        4 => Ipv4Addr::new(data[0], data[1], data[2], data[3]).to_string(),
+        _ => Ipv6Addr::from(<[u8; 16]>::try_from(data).unwrap_or_default()).to_string(),
+    }
+}
+
+/// A cursor over the XDR encoded fields of a datagram.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], DecodeError> {
+        if self.data.len() < length {
+            return Err(DecodeError::Truncated);
+        }
+        let (head, tail) = self.data.split_at(length);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> Result<u16, DecodeError> {
+        let data = self.take(2)?;
+        Ok(u16::from_be_bytes([data[0], data[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        let data = self.take(4)?;
+        Ok(u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+    }
+
+    /// Reads a 64-bit counter, which is unlikely to ever overflow a signed 64-bit integer.
+    fn u64(&mut self) -> Result<i64, DecodeError> {
+        let value = (u64::from(self.u32()?) << 32) | u64::from(self.u32()?);
+        Ok(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+
+    /// Reads variable-length opaque data, which is padded to a multiple of 4 bytes.
+    fn opaque(&mut self) -> Result<&'a [u8], DecodeError> {
+        let length = self.u32()? as usize;
+        let data = self.take(length.next_multiple_of(4))?;
+        Ok(&data[..length])
+    }
+
+    /// Reads a sample or record, returning its format and contents.
+    ///
+    /// The formats of the enterprises other than sFlow.org, which is `0`, are never decoded, so
+    /// they are returned as is.
+    fn opaque_with_format(&mut self) -> Result<(u32, Reader<'a>), DecodeError> {
+        let format = self.u32()?;
+        Ok((format, Reader::new(self.opaque()?)))
+    }
+
+    fn address(&mut self) -> Result<IpAddr, DecodeError> {
+        match self.u32()? {
+            1 => Ok(Ipv4Addr::from(self.u32()?).into()),
+            2 => {
+                let data = self.take(16)?;
+                Ok(Ipv6Addr::from(<[u8; 16]>::try_from(data).unwrap_or_default()).into())
+            }
+            address_type => Err(DecodeError::UnsupportedAddressType { address_type }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opaque(format: u32, data: Vec<u8>) -> Vec<u8> {
+        let mut opaque = vec![];
+        opaque.extend(format.to_be_bytes());
+        opaque.extend((data.len() as u32).to_be_bytes());
+        opaque.extend(data);
+        opaque
+    }
+
+    fn u32s(values: &[u32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    fn datagram(samples: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut datagram = u32s(&[5, 1]);
+        datagram.extend([192, 0, 2, 1]);
+        datagram.extend(u32s(&[0, 17, 123_456, samples.len() as u32]));
+        datagram.extend(samples.into_iter().flatten());
+        datagram
+    }
+
+    fn packet_header() -> Vec<u8> {
+        let mut header = vec![];
+        header.extend([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        header.extend([0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb]);
+        header.extend([0x81, 0x00, 0x00, 0x0a, 0x08, 0x00]);
+        // IPv4, with a 20 bytes header.
+        header.extend([0x45, 0x00, 0x00, 0x3c, 0, 0, 0, 0, 64, 6, 0, 0]);
+        header.extend([10, 0, 0, 1, 10, 0, 0, 2]);
+        // TCP, with the SYN flag.
+        header.extend([0xc0, 0x00, 0x01, 0xbb, 0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x02]);
+        header
+    }
+
+    #[test]
+    fn decodes_flow_samples() {
+        let header = packet_header();
+        let mut raw_header = u32s(&[1, 1518, 4, header.len() as u32]);
+        raw_header.extend(&header);
+        raw_header.resize(raw_header.len().next_multiple_of(4), 0);
+
+        let mut flow_sample = u32s(&[3, 5, 512, 1_000_000, 0, 7, 8, 2]);
+        flow_sample.extend(opaque(RAW_PACKET_HEADER, raw_header));
+        flow_sample.extend(opaque(EXTENDED_SWITCH, u32s(&[10, 0, 20, 0])));
+
+        let samples = decode(&datagram(vec![opaque(FLOW_SAMPLE, flow_sample)])).unwrap();
+        assert_eq!(samples.len(), 1);
+        let sample = &samples[0];
+        assert_eq!(sample["agent_address"], "192.0.2.1".into());
+        assert_eq!(sample["datagram_sequence_number"], 17.into());
+        assert_eq!(sample["sample_type"], "flow".into());
+        assert_eq!(sample["sequence_number"], 3.into());
+        assert_eq!(sample["source_id_type"], 0.into());
+        assert_eq!(sample["source_id_index"], 5.into());
+        assert_eq!(sample["sampling_rate"], 512.into());
+        assert_eq!(sample["input_interface"], 7.into());
+        assert_eq!(sample["output_interface"], 8.into());
+        assert_eq!(sample["frame_length"], 1518.into());
+        assert_eq!(sample["destination_mac"], "00:11:22:33:44:55".into());
+        assert_eq!(sample["source_mac"], "66:77:88:99:aa:bb".into());
+        assert_eq!(sample["vlan_id"], 10.into());
+        assert_eq!(sample["ethernet_type"], 0x0800.into());
+        assert_eq!(sample["source_ip"], "10.0.0.1".into());
+        assert_eq!(sample["destination_ip"], "10.0.0.2".into());
+        assert_eq!(sample["ip_protocol"], 6.into());
+        assert_eq!(sample["ip_ttl"], 64.into());
+        assert_eq!(sample["source_port"], 49152.into());
+        assert_eq!(sample["destination_port"], 443.into());
+        assert_eq!(sample["tcp_flags"], 2.into());
+        assert_eq!(sample["destination_vlan"], 20.into());
+    }
+
+    #[test]
+    fn decodes_counters_samples() {
+        let mut interface = u32s(&[5, 6, 0, 1_000_000_000, 1, 3, 0, 1000]);
+        interface.extend(u32s(&[10, 1, 2, 0, 0, 0, 0, 2000, 20, 3, 4, 0, 1, 0]));
+
+        let mut counters_sample = u32s(&[9, 3, 5, 2]);
+        counters_sample.extend(opaque(GENERIC_INTERFACE_COUNTERS, interface));
+        counters_sample.extend(opaque(
+            PROCESSOR_COUNTERS,
+            u32s(&[150, 200, 250, 1, 0, 0, 1024]),
+        ));
+
+        let samples = decode(&datagram(vec![opaque(
+            EXPANDED_COUNTERS_SAMPLE,
+            counters_sample,
+        )]))
+        .unwrap();
+        assert_eq!(samples.len(), 1);
+        let sample = &samples[0];
+        assert_eq!(sample["sample_type"], "counters".into());
+        assert_eq!(sample["source_id_type"], 3.into());
+        assert_eq!(sample["source_id_index"], 5.into());
+
+        let Value::Object(interface) = &sample["interface"] else {
+            panic!("interface counters are missing");
+        };
+        assert_eq!(interface["index"], 5.into());
+        assert_eq!(interface["speed"], 1_000_000_000.into());
+        assert_eq!(interface["in_octets"], 1000.into());
+        assert_eq!(interface["out_octets"], 2000.into());
+        assert_eq!(interface["out_errors"], 1.into());
+
+        let Value::Object(processor) = &sample["processor"] else {
+            panic!("processor counters are missing");
+        };
+        assert_eq!(processor["cpu_5m"], 250.into());
+        assert_eq!(processor["total_memory"], (1i64 << 32).into());
+        assert_eq!(processor["free_memory"], 1024.into());
+    }
+
+    #[test]
+    fn skips_unknown_samples() {
+        let samples = decode(&datagram(vec![opaque((4413 << 12) | 1, vec![0; 8])])).unwrap();
+        assert!(samples.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_datagrams() {
+        assert_eq!(
+            decode(&u32s(&[4, 1])).unwrap_err(),
+            DecodeError::UnsupportedVersion { version: 4 }
+        );
+        let datagram = datagram(vec![opaque(FLOW_SAMPLE, u32s(&[1, 2]))]);
+        assert_eq!(decode(&datagram).unwrap_err(), DecodeError::Truncated);
+    }
+}
@@ -0,0 +1,304 @@
+use chrono::Utc;
+use listenfd::ListenFd;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::{
+        EventsReceived, SflowDecodeError, SocketBindError, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    sources::util::net::{try_bind_udp_socket, SocketListenAddr},
+    SourceSender,
+};
+
+mod decoder;
+
+pub use self::decoder::DecodeError;
+
+/// The maximum size of a UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// Configuration for the `sflow` source.
+#[configurable_component(source(
+    "sflow",
+    "Collect flow and counter samples from sFlow v5 agents."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SflowConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for SflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:6343".parse().unwrap()),
+            receive_buffer_bytes: None,
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+impl SflowConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        Definition::new_with_default_metadata(
+            Kind::object(
+                Collection::empty()
+                    .with_known("agent_address", Kind::bytes())
+                    .with_known("sample_type", Kind::bytes())
+                    .with_known("sequence_number", Kind::integer())
+                    .with_known("source_id_type", Kind::integer())
+                    .with_known("source_id_index", Kind::integer())
+                    .with_unknown(Kind::any()),
+            ),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .host_key()
+                .cloned()
+                .map(LegacyKey::InsertIfEmpty),
+            &owned_value_path!("host"),
+            Kind::bytes(),
+            Some("host"),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sflow")]
+impl SourceConfig for SflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        Ok(Box::pin(sflow(
+            self.clone(),
+            cx.shutdown,
+            cx.out,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn sflow(
+    config: SflowConfig,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .await
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error,
+            })
+        })?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(message = "Listening.", address = %config.address);
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+    let events_received = register!(EventsReceived);
+
+    let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+
+    loop {
+        tokio::select! {
+            recv = socket.recv_from(&mut buf) => {
+                let (byte_size, agent) = recv.map_err(|error| {
+                    emit!(SocketReceiveError {
+                        mode: SocketMode::Udp,
+                        error,
+                    })
+                })?;
+                bytes_received.emit(ByteSize(byte_size));
+
+                let samples = match decoder::decode(&buf[..byte_size]) {
+                    Ok(samples) => samples,
+                    Err(error) => {
+                        emit!(SflowDecodeError { error, agent });
+                        continue;
+                    }
+                };
+
+                let events = create_events(samples, agent.ip().to_string(), log_namespace);
+                if events.is_empty() {
+                    continue;
+                }
+
+                let count = events.len();
+                events_received.emit(CountByteSize(
+                    count,
+                    events.estimated_json_encoded_size_of(),
+                ));
+
+                tokio::select! {
+                    result = out.send_batch(events) => {
+                        if result.is_err() {
+                            emit!(StreamClosedError { count });
+                            return Ok(());
+                        }
+                    }
+                    _ = &mut shutdown => return Ok(()),
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+fn create_events(
+    samples: Vec<ObjectMap>,
+    agent: String,
+    log_namespace: LogNamespace,
+) -> Vec<Event> {
+    let now = Utc::now();
+    samples
+        .into_iter()
+        .map(|sample| {
+            let mut log = LogEvent::from(Value::Object(sample));
+
+            log_namespace.insert_standard_vector_source_metadata(&mut log, SflowConfig::NAME, now);
+            log_namespace.insert_source_metadata(
+                SflowConfig::NAME,
+                &mut log,
+                log_schema().host_key().map(LegacyKey::InsertIfEmpty),
+                path!("host"),
+                agent.clone(),
+            );
+
+            log.into()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SflowConfig>();
+    }
+
+    /// A datagram with a counters sample, whose only record has a format that is skipped.
+    fn counters_datagram() -> Vec<u8> {
+        [
+            5,
+            1,
+            0xc000_0201,
+            0,
+            1,
+            1000,
+            1,
+            2,
+            28,
+            7,
+            5,
+            1,
+            2000,
+            8,
+            100,
+            200,
+        ]
+        .into_iter()
+        .flat_map(u32::to_be_bytes)
+        .collect::<Vec<_>>()
+    }
+
+    #[tokio::test]
+    async fn receives_samples() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let address = next_addr();
+            let config = SflowConfig {
+                address: SocketListenAddr::SocketAddr(address),
+                receive_buffer_bytes: None,
+                log_namespace: None,
+            };
+
+            let (tx, rx) = SourceSender::new_test();
+            let cx = SourceContext::new_test(tx, None);
+            tokio::spawn(config.build(cx).await.unwrap());
+            // Wait for the socket to be bound.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            socket.send_to(&counters_datagram(), address).await.unwrap();
+
+            let events = collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log["agent_address"], "192.0.2.1".into());
+            assert_eq!(log["sample_type"], "counters".into());
+            assert_eq!(log["source_id_index"], 5.into());
+            assert_eq!(
+                log[log_schema().host_key().unwrap().to_string()],
+                "127.0.0.1".into()
+            );
+            assert_eq!(log["source_type"], "sflow".into());
+        })
+        .await;
+    }
+}
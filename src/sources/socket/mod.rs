@@ -241,8 +241,10 @@ impl SourceConfig for SocketConfig {
                         Self::NAME,
                         tls_client_metadata_path,
                         &owned_value_path!("tls_client_metadata"),
-                        Kind::object(Collection::empty().with_unknown(Kind::bytes()))
-                            .or_undefined(),
+                        Kind::object(Collection::empty().with_unknown(
+                            Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                        ))
+                        .or_undefined(),
                         None,
                     )
             }
@@ -601,7 +603,8 @@ mod test {
             );
 
             let tls_meta: ObjectMap = btreemap!(
-                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"
+                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US",
+                "fingerprint_sha256" => "0a224c50633ce7e72ea9688c8c7332c9ac0718ca63ab1829681bd76b1a49d01c"
             );
 
             assert_eq!(event.as_log()["tls_peer"], tls_meta.clone().into(),);
@@ -666,7 +669,8 @@ mod test {
             assert_eq!(log.value(), &"one line".into());
 
             let tls_meta: ObjectMap = btreemap!(
-                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"
+                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US",
+                "fingerprint_sha256" => "0a224c50633ce7e72ea9688c8c7332c9ac0718ca63ab1829681bd76b1a49d01c"
             );
 
             assert_eq!(
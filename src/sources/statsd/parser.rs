@@ -5,10 +5,11 @@ use std::{
     sync::LazyLock,
 };
 
+use chrono::{DateTime, Utc};
 use regex::Regex;
 
 use crate::{
-    event::metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue, Sample, StatisticKind},
     sources::util::extract_tag_key_and_value,
 };
 
@@ -47,80 +48,84 @@ impl Parser {
         let name = sanitize_key(key, self.sanitize);
         let metric_type = parts[1];
 
-        // sampling part is optional and comes after metric type part
-        let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-        let sample_rate = if let Some(s) = sampling {
-            1.0 / sanitize_sampling(parse_sampling(s)?)
-        } else {
-            1.0
-        };
-
-        // tags are optional and could be found either after sampling of after metric type part
-        let tags = if sampling.is_none() {
-            parts.get(2)
-        } else {
-            parts.get(3)
-        };
-        let tags = tags.filter(|s| s.starts_with('#'));
-        let tags = tags.map(parse_tags).transpose()?;
+        // The optional fields after the metric type part are told apart by their prefix, and
+        // unknown fields, such as those of newer versions of the DogStatsD protocol, are ignored.
+        let mut sample_rate = 1.0;
+        let mut tags = None;
+        let mut container_id = None;
+        let mut timestamp = None;
+        for field in &parts[2..] {
+            if field.starts_with('@') {
+                sample_rate = 1.0 / sanitize_sampling(parse_sampling(field)?);
+            } else if field.starts_with('#') {
+                tags = Some(parse_tags(field)?);
+            } else if let Some(id) = field.strip_prefix("c:") {
+                container_id = Some(id);
+            } else if let Some(seconds) = field.strip_prefix('T') {
+                timestamp = Some(parse_timestamp(seconds)?);
+            }
+        }
+        if let Some(container_id) = container_id {
+            tags.get_or_insert_with(MetricTags::default)
+                .replace("container_id".to_owned(), container_id.to_owned());
+        }
 
+        // DogStatsD packs multiple values of a metric in the same message, such as `foo:1:2|d`.
+        let mut values = parts[0].split(':');
         let metric = match metric_type {
             "c" => {
-                let val: f64 = parts[0].parse()?;
+                let value = values.map(str::parse::<f64>).sum::<Result<f64, _>>()?;
                 Metric::new(
                     name,
                     MetricKind::Incremental,
                     MetricValue::Counter {
-                        value: val * sample_rate,
+                        value: value * sample_rate,
                     },
                 )
-                .with_tags(tags)
             }
             unit @ "h" | unit @ "ms" | unit @ "d" => {
-                let val: f64 = parts[0].parse()?;
+                let samples = values
+                    .map(|value| {
+                        Ok(Sample {
+                            value: convert_to_base_units(unit, value.parse()?),
+                            rate: expand_sample_rate(sample_rate),
+                        })
+                    })
+                    .collect::<Result<_, ParseError>>()?;
                 Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
-                    samples: vector_lib::samples![convert_to_base_units(unit, val) => sample_rate as u32],
-                    statistic: convert_to_statistic(unit),
-                },
-            ).with_tags(tags)
+                    name,
+                    MetricKind::Incremental,
+                    MetricValue::Distribution {
+                        samples,
+                        statistic: convert_to_statistic(unit),
+                    },
+                )
             }
             "g" => {
-                let value = if parts[0]
-                    .chars()
-                    .next()
-                    .map(|c| c.is_ascii_digit())
-                    .ok_or(ParseError::Malformed("empty first body component"))?
-                {
-                    parts[0].parse()?
-                } else {
-                    parts[0][1..].parse()?
+                // An absolute value resets the gauge, while signed values add up.
+                let (mut kind, mut value) = match parse_gauge(values.next().unwrap_or_default())? {
+                    (None, value) => (MetricKind::Absolute, value),
+                    (Some(sign), value) => (MetricKind::Incremental, value * sign),
                 };
-
-                match parse_direction(parts[0])? {
-                    None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                        .with_tags(tags),
-                    Some(sign) => Metric::new(
-                        name,
-                        MetricKind::Incremental,
-                        MetricValue::Gauge {
-                            value: value * sign,
-                        },
-                    )
-                    .with_tags(tags),
+                for next in values {
+                    match parse_gauge(next)? {
+                        (None, next) => (kind, value) = (MetricKind::Absolute, next),
+                        (Some(sign), next) => value += next * sign,
+                    }
                 }
+                Metric::new(name, kind, MetricValue::Gauge { value })
             }
+            // Set values may contain `:`, so they are never packed.
             "s" => Metric::new(
                 name,
                 MetricKind::Incremental,
                 MetricValue::Set {
                     values: vec![parts[0].into()].into_iter().collect(),
                 },
-            )
-            .with_tags(tags),
+            ),
             other => return Err(ParseError::UnknownMetricType(other.into())),
         };
-        Ok(metric)
+        Ok(metric.with_tags(tags).with_timestamp(timestamp))
     }
 }
 
@@ -153,6 +158,18 @@ fn parse_tags(input: &&str) -> Result<MetricTags, ParseError> {
         .collect())
 }
 
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    DateTime::from_timestamp(input.parse()?, 0).ok_or(ParseError::Malformed("invalid timestamp"))
+}
+
+/// Parses a gauge value, along with its sign if it is relative.
+fn parse_gauge(input: &str) -> Result<(Option<f64>, f64), ParseError> {
+    match parse_direction(input)? {
+        None => Ok((None, input.parse()?)),
+        Some(sign) => Ok((Some(sign), input[1..].parse()?)),
+    }
+}
+
 fn parse_direction(input: &str) -> Result<Option<f64>, ParseError> {
     match input
         .chars()
@@ -185,6 +202,11 @@ fn sanitize_sampling(sampling: f64) -> f64 {
     }
 }
 
+/// Converts the inverse of a sample rate into the number of observations that a sample stands for.
+fn expand_sample_rate(sample_rate: f64) -> u32 {
+    (sample_rate.round() as u32).max(1)
+}
+
 fn convert_to_base_units(unit: &str, val: f64) -> f64 {
     match unit {
         "ms" => val / 1000.0,
@@ -437,6 +459,85 @@ mod test {
         );
     }
 
+    #[test]
+    fn packed_values() {
+        assert_event_data_eq!(
+            parse("foo:1:2.5|c|@0.5"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 7.0 },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("glork:320:100|ms|@0.3"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_lib::samples![0.320 => 3, 0.1 => 3],
+                    statistic: StatisticKind::Histogram
+                },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("gaugor:+1:10:-4|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 6.0 },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("gaugor:+1:-4|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Incremental,
+                MetricValue::Gauge { value: -3.0 },
+            )),
+        );
+        assert_event_data_eq!(
+            unsanitized_parse("uniques:a:b|s"),
+            Ok(Metric::new(
+                "uniques",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["a:b".into()].into_iter().collect()
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn dogstatsd_fields() {
+        assert_event_data_eq!(
+            parse("foo:1|d|#env:prod|c:83c0a99c0a54|T1656581400|e:unknown|@0.5"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_lib::samples![1.0 => 2],
+                    statistic: StatisticKind::Summary
+                },
+            )
+            .with_tags(Some(metric_tags!(
+                "env" => "prod",
+                "container_id" => "83c0a99c0a54",
+            )))
+            .with_timestamp(chrono::DateTime::from_timestamp(1_656_581_400, 0))),
+        );
+        assert_event_data_eq!(
+            parse("foo:1|c|c:83c0a99c0a54"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(metric_tags!("container_id" => "83c0a99c0a54")))),
+        );
+        assert!(parse("foo:1|c|Tnow").is_err());
+    }
+
     #[test]
     fn sanitizing_keys() {
         assert_eq!("foo-bar-baz", sanitize_key("foo/bar/baz", true));
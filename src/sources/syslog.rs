@@ -60,12 +60,41 @@ pub struct SyslogConfig {
     /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
     host_key: Option<OptionalValuePath>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    parsing: ParsingMode,
+
+    /// Whether to decode the structured data parameters into typed values.
+    ///
+    /// Parameters that are repeated within an SD-ELEMENT are decoded into arrays, and the parameters
+    /// of the SD-IDs registered by RFC 5424 (`timeQuality` and `meta`) are decoded into booleans and
+    /// integers. Otherwise, every parameter is decoded as a string, and only the last value of a
+    /// repeated parameter is kept.
+    #[serde(default)]
+    typed_structured_data: bool,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     pub log_namespace: Option<bool>,
 }
 
+/// How strictly messages are parsed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParsingMode {
+    /// Accept both RFC 5424 and RFC 3164 messages, and recover from malformed messages where
+    /// possible.
+    #[default]
+    Lenient,
+
+    /// Only accept RFC 5424 messages with well-formed structured data.
+    ///
+    /// Messages with invalid or duplicated SD-IDs or invalid parameter names are rejected.
+    Strict,
+}
+
 /// Listener mode for the `syslog` source.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -129,12 +158,20 @@ pub enum Mode {
 }
 
 impl SyslogConfig {
+    fn deserializer_config(&self) -> SyslogDeserializerConfig {
+        SyslogDeserializerConfig::from_source(SyslogConfig::NAME)
+            .with_strict(self.parsing == ParsingMode::Strict)
+            .with_typed_structured_data(self.typed_structured_data)
+    }
+
     #[cfg(test)]
     pub fn from_mode(mode: Mode) -> Self {
         Self {
             mode,
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            parsing: ParsingMode::default(),
+            typed_structured_data: false,
             log_namespace: None,
         }
     }
@@ -153,6 +190,8 @@ impl Default for SyslogConfig {
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            parsing: ParsingMode::default(),
+            typed_structured_data: false,
             log_namespace: None,
         }
     }
@@ -186,6 +225,7 @@ impl SourceConfig for SyslogConfig {
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
+                    deserializer: self.deserializer_config(),
                     host_key,
                     log_namespace,
                 };
@@ -218,6 +258,7 @@ impl SourceConfig for SyslogConfig {
             } => Ok(udp(
                 address,
                 self.max_length,
+                self.deserializer_config(),
                 host_key,
                 receive_buffer_bytes,
                 cx.shutdown,
@@ -233,9 +274,7 @@ impl SourceConfig for SyslogConfig {
                     Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(
                         self.max_length,
                     )),
-                    Deserializer::Syslog(
-                        SyslogDeserializerConfig::from_source(SyslogConfig::NAME).build(),
-                    ),
+                    Deserializer::Syslog(self.deserializer_config().build()),
                 );
 
                 build_unix_stream_source(
@@ -252,7 +291,8 @@ impl SourceConfig for SyslogConfig {
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
-        let schema_definition = SyslogDeserializerConfig::from_source(SyslogConfig::NAME)
+        let schema_definition = self
+            .deserializer_config()
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
@@ -279,6 +319,7 @@ impl SourceConfig for SyslogConfig {
 #[derive(Debug, Clone)]
 struct SyslogTcpSource {
     max_length: usize,
+    deserializer: SyslogDeserializerConfig,
     host_key: Option<OwnedValuePath>,
     log_namespace: LogNamespace,
 }
@@ -292,7 +333,7 @@ impl TcpSource for SyslogTcpSource {
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
             Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
-            Deserializer::Syslog(SyslogDeserializerConfig::from_source(SyslogConfig::NAME).build()),
+            Deserializer::Syslog(self.deserializer.build()),
         )
     }
 
@@ -313,6 +354,7 @@ impl TcpSource for SyslogTcpSource {
 pub fn udp(
     addr: SocketListenAddr,
     _max_length: usize,
+    deserializer: SyslogDeserializerConfig,
     host_key: Option<OwnedValuePath>,
     receive_buffer_bytes: Option<usize>,
     shutdown: ShutdownSignal,
@@ -344,9 +386,7 @@ pub fn udp(
             socket,
             Decoder::new(
                 Framer::Bytes(BytesDecoder::new()),
-                Deserializer::Syslog(
-                    SyslogDeserializerConfig::from_source(SyslogConfig::NAME).build(),
-                ),
+                Deserializer::Syslog(deserializer.build()),
             ),
         )
         .take_until(shutdown)
@@ -574,7 +614,10 @@ mod test {
                 )
                 .with_metadata_field(
                     &owned_value_path!("syslog", "tls_client_metadata"),
-                    Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                    Kind::object(Collection::empty().with_unknown(
+                        Kind::bytes().or_array(Collection::from_unknown(Kind::bytes())),
+                    ))
+                    .or_undefined(),
                     None,
                 );
 
@@ -736,6 +779,21 @@ mod test {
         assert!(matches!(config.mode, Mode::Udp { .. }));
     }
 
+    #[test]
+    fn config_strict_typed_structured_data() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            parsing = "strict"
+            typed_structured_data = true
+          "#,
+        )
+        .unwrap();
+        assert_eq!(config.parsing, ParsingMode::Strict);
+        assert!(config.typed_structured_data);
+    }
+
     #[test]
     fn config_udp_with_receive_buffer_size() {
         let config: SyslogConfig = toml::from_str(
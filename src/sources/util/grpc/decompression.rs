@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    io::Write,
+    io::{self, Write},
     mem,
     pin::Pin,
     task::{Context, Poll},
@@ -31,8 +31,10 @@ const GRPC_MESSAGE_HEADER_LEN: usize = mem::size_of::<u8>() + mem::size_of::<u32
 const GRPC_ENCODING_HEADER: &str = "grpc-encoding";
 const GRPC_ACCEPT_ENCODING_HEADER: &str = "grpc-accept-encoding";
 
+#[derive(Clone, Copy)]
 enum CompressionScheme {
     Gzip,
+    Zstd,
 }
 
 impl CompressionScheme {
@@ -52,6 +54,7 @@ impl CompressionScheme {
                 None => Ok(None),
                 Some(scheme) => match scheme.as_str() {
                     "gzip" => Ok(Some(CompressionScheme::Gzip)),
+                    "zstd" => Ok(Some(CompressionScheme::Zstd)),
                     other => Err(Status::unimplemented(format!(
                         "compression scheme `{}` is not supported",
                         other
@@ -61,7 +64,7 @@ impl CompressionScheme {
             .map_err(|mut status| {
                 status.metadata_mut().insert(
                     GRPC_ACCEPT_ENCODING_HEADER,
-                    AsciiMetadataValue::from_static("gzip,identity"),
+                    AsciiMetadataValue::from_static("zstd,gzip,identity"),
                 );
                 status
             })
@@ -80,17 +83,45 @@ impl Default for State {
     }
 }
 
-fn new_decompressor() -> GzDecoder<Vec<u8>> {
-    // Create the backing buffer for the decompressor and set the compression flag to false (0) and pre-allocate
-    // the space for the length prefix, which we'll fill out once we've finalized the decompressor.
-    let buf = vec![0; GRPC_MESSAGE_HEADER_LEN];
+enum Decompressor {
+    Gzip(GzDecoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+}
+
+impl Decompressor {
+    fn new(scheme: CompressionScheme) -> io::Result<Self> {
+        // Create the backing buffer for the decompressor and set the compression flag to false (0) and pre-allocate
+        // the space for the length prefix, which we'll fill out once we've finalized the decompressor.
+        let buf = vec![0; GRPC_MESSAGE_HEADER_LEN];
+
+        match scheme {
+            CompressionScheme::Gzip => Ok(Self::Gzip(GzDecoder::new(buf))),
+            CompressionScheme::Zstd => zstd::stream::write::Decoder::new(buf).map(Self::Zstd),
+        }
+    }
 
-    GzDecoder::new(buf)
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Gzip(decoder) => decoder.write_all(buf),
+            Self::Zstd(decoder) => decoder.write_all(buf),
+        }
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip(decoder) => decoder.finish(),
+            Self::Zstd(mut decoder) => {
+                decoder.flush()?;
+                Ok(decoder.into_inner())
+            }
+        }
+    }
 }
 
 async fn drive_body_decompression(
     mut source: Body,
     mut destination: Sender,
+    scheme: CompressionScheme,
 ) -> Result<usize, Status> {
     let mut state = State::default();
     let mut buf = BytesMut::new();
@@ -137,6 +168,10 @@ async fn drive_body_decompression(
                         // recreate it anyways.
                         buf.advance(GRPC_MESSAGE_HEADER_LEN);
 
+                        decompressor = Some(
+                            Decompressor::new(scheme)
+                                .map_err(|_| Status::internal("failed to create decompressor"))?,
+                        );
                         state = State::Decompress {
                             remaining: message_len,
                         };
@@ -172,7 +207,9 @@ async fn drive_body_decompression(
                             // the decompressor. This is _technically_ synchronous but there's really no way to do it
                             // asynchronously since we already have the data, and that's the only asynchronous part.
                             let to_take = cmp::min(available, *remaining);
-                            let decompressor = decompressor.get_or_insert_with(new_decompressor);
+                            let decompressor = decompressor
+                                .as_mut()
+                                .expect("decompressor is created along with the decompress state");
                             if decompressor.write_all(&buf[..to_take]).is_err() {
                                 return Err(Status::internal("failed to write to decompressor"));
                             }
@@ -243,6 +280,7 @@ async fn drive_body_decompression(
 async fn drive_request<F, E>(
     source: Body,
     destination: Sender,
+    scheme: CompressionScheme,
     inner: F,
     bytes_received: Registered<BytesReceived>,
 ) -> Result<Response<BoxBody>, E>
@@ -250,7 +288,7 @@ where
     F: Future<Output = Result<Response<BoxBody>, E>>,
     E: std::fmt::Display,
 {
-    let body_decompression = drive_body_decompression(source, destination);
+    let body_decompression = drive_body_decompression(source, destination, scheme);
 
     pin!(inner);
     pin!(body_decompression);
@@ -328,15 +366,23 @@ where
             // can support decompression based on the indicated compression scheme... so wrap the body to decompress, if
             // need be, and then track the bytes that flowed through.
             //
-            // TODO: Actually use the scheme given back to us to support other compression schemes.
-            Ok(_) => {
+            // Messages that are flagged as compressed without a compression scheme are assumed to be compressed with
+            // gzip, as they always were.
+            Ok(scheme) => {
                 let (destination, decompressed_body) = Body::channel();
                 let (req_parts, req_body) = req.into_parts();
                 let mapped_req = Request::from_parts(req_parts, decompressed_body);
 
                 let inner = self.inner.call(mapped_req);
 
-                drive_request(req_body, destination, inner, self.bytes_received.clone()).boxed()
+                drive_request(
+                    req_body,
+                    destination,
+                    scheme.unwrap_or(CompressionScheme::Gzip),
+                    inner,
+                    self.bytes_received.clone(),
+                )
+                .boxed()
             }
         }
     }
@@ -361,7 +407,8 @@ where
 /// request was valid, and was processed -- we can now report the number of bytes (after decompression) that were
 /// received _and_ processed correctly.
 ///
-/// The only supported compression scheme is gzip, which is also the only supported compression scheme in `tonic` itself.
+/// The supported compression schemes are gzip and zstd, which are also the compression schemes supported by `tonic`
+/// itself.
 #[derive(Clone, Default)]
 pub struct DecompressionAndMetricsLayer;
 
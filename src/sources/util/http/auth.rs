@@ -1,20 +1,41 @@
-use std::convert::TryFrom;
-
 use headers::{Authorization, HeaderMapExt};
+use serde::{de, Deserialize, Deserializer};
 use vector_lib::configurable::configurable_component;
 use vector_lib::sensitive_string::SensitiveString;
 use warp::http::HeaderMap;
 
+use crate::config::ProxyConfig;
+
+use super::oidc::{OidcConfig, OidcValidator};
+
 #[cfg(any(
     feature = "sources-utils-http-prelude",
     feature = "sources-utils-http-auth"
 ))]
 use super::error::ErrorMessage;
 
+/// HTTP authentication configuration.
+#[configurable_component(no_deser)]
+#[derive(Clone, Debug)]
+#[serde(rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The authentication strategy to use. Defaults to `basic` when unset."
+))]
+pub enum HttpSourceAuthConfig {
+    /// HTTP Basic authentication.
+    Basic(BasicAuthConfig),
+
+    /// OpenID Connect bearer token authentication.
+    ///
+    /// Requests must carry a JSON Web Token (JWT) in the `Authorization: Bearer` header, which is
+    /// validated against the keys published by the issuer.
+    Oidc(OidcConfig),
+}
+
 /// HTTP Basic authentication configuration.
 #[configurable_component]
 #[derive(Clone, Debug)]
-pub struct HttpSourceAuthConfig {
+pub struct BasicAuthConfig {
     /// The username for basic authentication.
     #[configurable(metadata(docs::examples = "AzureDiamond"))]
     #[configurable(metadata(docs::examples = "admin"))]
@@ -26,12 +47,39 @@ pub struct HttpSourceAuthConfig {
     pub password: SensitiveString,
 }
 
-impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
-    type Error = String;
+// Configurations written before `strategy` was introduced only have the basic authentication
+// credentials, so `strategy` defaults to `basic` when it is missing.
+impl<'de> Deserialize<'de> for HttpSourceAuthConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case", tag = "strategy")]
+        enum Tagged {
+            Basic(BasicAuthConfig),
+            Oidc(OidcConfig),
+        }
+
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        if let Some(object) = value.as_object_mut() {
+            object
+                .entry("strategy")
+                .or_insert_with(|| serde_json::Value::from("basic"));
+        }
 
-    fn try_from(auth: Option<&HttpSourceAuthConfig>) -> Result<Self, Self::Error> {
+        let tagged = Tagged::deserialize(value).map_err(de::Error::custom)?;
+        Ok(match tagged {
+            Tagged::Basic(config) => Self::Basic(config),
+            Tagged::Oidc(config) => Self::Oidc(config),
+        })
+    }
+}
+
+impl HttpSourceAuth {
+    pub fn build(auth: Option<&HttpSourceAuthConfig>, proxy: &ProxyConfig) -> crate::Result<Self> {
         match auth {
-            Some(auth) => {
+            Some(HttpSourceAuthConfig::Basic(auth)) => {
                 let mut headers = HeaderMap::new();
                 headers.typed_insert(Authorization::basic(
                     auth.username.as_str(),
@@ -43,12 +91,22 @@ impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
                             .to_str()
                             .map_err(|error| format!("Failed stringify HeaderValue: {:?}", error))?
                             .to_owned();
-                        Ok(HttpSourceAuth { token: Some(token) })
+                        Ok(HttpSourceAuth {
+                            token: Some(token),
+                            oidc: None,
+                        })
                     }
-                    None => Err("Authorization headers wasn't generated".to_owned()),
+                    None => Err("Authorization headers wasn't generated".into()),
                 }
             }
-            None => Ok(HttpSourceAuth { token: None }),
+            Some(HttpSourceAuthConfig::Oidc(config)) => Ok(HttpSourceAuth {
+                token: None,
+                oidc: Some(OidcValidator::new(config, proxy)?),
+            }),
+            None => Ok(HttpSourceAuth {
+                token: None,
+                oidc: None,
+            }),
         }
     }
 }
@@ -57,6 +115,8 @@ impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
 pub struct HttpSourceAuth {
     #[allow(unused)] // triggered by check-component-features
     pub(self) token: Option<String>,
+    #[allow(unused)] // triggered by check-component-features
+    pub(self) oidc: Option<OidcValidator>,
 }
 
 impl HttpSourceAuth {
@@ -64,6 +124,10 @@ impl HttpSourceAuth {
     pub fn is_valid(&self, header: &Option<String>) -> Result<(), ErrorMessage> {
         use warp::http::StatusCode;
 
+        if let Some(oidc) = &self.oidc {
+            return oidc.is_valid(header);
+        }
+
         match (&self.token, header) {
             (Some(token1), Some(token2)) => {
                 if token1 == token2 {
@@ -83,3 +147,36 @@ impl HttpSourceAuth {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_auth_strategy() {
+        let config: HttpSourceAuthConfig = toml::from_str(
+            r#"
+            username = "admin"
+            password = "hunter2"
+            "#,
+        )
+        .unwrap();
+        assert!(
+            matches!(config, HttpSourceAuthConfig::Basic(ref basic) if basic.username == "admin")
+        );
+
+        let config: HttpSourceAuthConfig = toml::from_str(
+            r#"
+            strategy = "oidc"
+            issuer = "https://accounts.example.com"
+            audiences = ["vector"]
+            "#,
+        )
+        .unwrap();
+        let HttpSourceAuthConfig::Oidc(oidc) = config else {
+            panic!("expected oidc strategy");
+        };
+        assert_eq!(oidc.issuer, "https://accounts.example.com");
+        assert_eq!(oidc.jwks_refresh_interval_secs, 3600);
+    }
+}
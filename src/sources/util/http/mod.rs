@@ -5,6 +5,8 @@ mod encoding;
 #[cfg(feature = "sources-utils-http-error")]
 mod error;
 mod method;
+#[cfg(feature = "sources-utils-http-auth")]
+mod oidc;
 #[cfg(feature = "sources-utils-http-prelude")]
 mod prelude;
 #[cfg(any(
@@ -15,13 +17,13 @@ mod prelude;
 mod query;
 
 #[cfg(feature = "sources-utils-http-auth")]
-pub use auth::{HttpSourceAuth, HttpSourceAuthConfig};
+pub use auth::{BasicAuthConfig, HttpSourceAuth, HttpSourceAuthConfig};
 #[cfg(feature = "sources-utils-http-encoding")]
 pub use encoding::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use error::ErrorMessage;
 pub use method::HttpMethod;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use prelude::HttpSource;
+pub use prelude::{run_routes, HttpRoute, HttpSource};
 #[cfg(feature = "sources-utils-http-query")]
 pub use query::add_query_parameters;
@@ -0,0 +1,680 @@
+use std::{
+    sync::{Arc, RwLock, Weak},
+    time::{Duration, Instant},
+};
+
+use base64::prelude::{Engine as _, BASE64_URL_SAFE_NO_PAD};
+use chrono::Utc;
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use openssl::{
+    bn::BigNum,
+    ec::{EcGroup, EcKey},
+    ecdsa::EcdsaSig,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Public},
+    rsa::Rsa,
+    sign::Verifier,
+};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Notify;
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    config::ProxyConfig,
+    http::{HttpClient, HttpError},
+    internal_events::HttpSourceJwksRefreshError,
+    tls::{TlsConfig, TlsSettings},
+};
+
+use super::error::ErrorMessage;
+
+/// The shortest time between two refreshes of the keys that are caused by tokens signed with an
+/// unknown key, so that such tokens can't be used to flood the provider with requests.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// OpenID Connect bearer token authentication configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OidcConfig {
+    /// The issuer of the tokens.
+    ///
+    /// The `iss` claim of tokens must be equal to it.
+    #[configurable(metadata(docs::examples = "https://accounts.example.com"))]
+    pub issuer: String,
+
+    /// The audiences that tokens are accepted for.
+    ///
+    /// The `aud` claim of tokens must contain at least one of them.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub audiences: Vec<String>,
+
+    /// The URL of the JSON Web Key Set (JWKS) with the keys that tokens are signed with.
+    ///
+    /// If not set, the URL is discovered from the `jwks_uri` of the OpenID Connect configuration
+    /// of the issuer, at `<issuer>/.well-known/openid-configuration`.
+    #[configurable(metadata(
+        docs::examples = "https://accounts.example.com/.well-known/jwks.json"
+    ))]
+    pub jwks_url: Option<String>,
+
+    /// The interval between refreshes of the keys, in seconds.
+    ///
+    /// The keys are also refreshed when a token is signed with a key that isn't known yet.
+    #[serde(default = "default_jwks_refresh_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "JWKS Refresh Interval"))]
+    pub jwks_refresh_interval_secs: u64,
+
+    /// The leeway allowed when checking the `exp` and `nbf` claims of tokens, in seconds.
+    ///
+    /// This accounts for the clock skew between Vector and the issuer.
+    #[serde(default = "default_leeway_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub leeway_secs: u64,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_jwks_refresh_interval_secs() -> u64 {
+    3600
+}
+
+const fn default_leeway_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, Snafu)]
+pub enum JwksError {
+    #[snafu(display("Invalid URL {:?}: {}", url, source))]
+    InvalidUrl {
+        url: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Failed to send request: {}", source))]
+    Send { source: HttpError },
+    #[snafu(display("Unexpected status: {}", status))]
+    Status { status: StatusCode },
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadBody { source: hyper::Error },
+    #[snafu(display("Failed to parse response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+}
+
+#[derive(Debug, Snafu)]
+enum TokenError {
+    #[snafu(display("Malformed token"))]
+    Malformed,
+    #[snafu(display("Unsupported signing algorithm {:?}", alg))]
+    UnsupportedAlgorithm { alg: String },
+    #[snafu(display("Token is signed with an unknown key"))]
+    UnknownKey,
+    #[snafu(display("Invalid token signature"))]
+    InvalidSignature,
+    #[snafu(display("Token has expired"))]
+    Expired,
+    #[snafu(display("Token is not valid yet"))]
+    NotYetValid,
+    #[snafu(display("Token has an invalid issuer"))]
+    InvalidIssuer,
+    #[snafu(display("Token has an invalid audience"))]
+    InvalidAudience,
+}
+
+/// Validates bearer tokens against the keys of an OpenID Connect provider.
+///
+/// The keys are cached, and refreshed in the background for as long as the validator is alive.
+#[derive(Clone, Debug)]
+pub struct OidcValidator {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    issuer: String,
+    audiences: Vec<String>,
+    leeway: i64,
+    keys: RwLock<Vec<VerifyingKey>>,
+    last_refresh: RwLock<Option<Instant>>,
+    refresh: Arc<Notify>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Wakes up the refresh task so that it ends.
+        self.refresh.notify_one();
+    }
+}
+
+impl OidcValidator {
+    pub fn new(config: &OidcConfig, proxy: &ProxyConfig) -> crate::Result<Self> {
+        let tls = TlsSettings::from_options(&config.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+
+        let inner = Arc::new(Inner {
+            issuer: config.issuer.clone(),
+            audiences: config.audiences.clone(),
+            leeway: config.leeway_secs as i64,
+            keys: RwLock::default(),
+            last_refresh: RwLock::default(),
+            refresh: Arc::new(Notify::new()),
+        });
+
+        tokio::spawn(refresh_keys(
+            Arc::downgrade(&inner),
+            Arc::clone(&inner.refresh),
+            client,
+            config.issuer.clone(),
+            config.jwks_url.clone(),
+            Duration::from_secs(config.jwks_refresh_interval_secs),
+        ));
+
+        Ok(Self { inner })
+    }
+
+    pub fn is_valid(&self, header: &Option<String>) -> Result<(), ErrorMessage> {
+        let token = header
+            .as_deref()
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .ok_or_else(|| {
+                ErrorMessage::new(StatusCode::UNAUTHORIZED, "No bearer token".to_owned())
+            })?;
+
+        self.validate(token.trim()).map_err(|error| {
+            if let TokenError::UnknownKey = error {
+                self.request_refresh();
+            }
+            ErrorMessage::new(StatusCode::UNAUTHORIZED, error.to_string())
+        })
+    }
+
+    fn validate(&self, token: &str) -> Result<(), TokenError> {
+        let (signed, signature) = token.rsplit_once('.').ok_or(TokenError::Malformed)?;
+        let (header, claims) = signed.split_once('.').ok_or(TokenError::Malformed)?;
+
+        let header: Header = decode_part(header)?;
+        let signature = BASE64_URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| TokenError::Malformed)?;
+
+        let algorithm = Algorithm::from_name(&header.alg)?;
+        {
+            // A token without a `kid` can be signed by any key of its algorithm, such as both the
+            // old and the new key while they are rotated, so it's checked against all of them.
+            let keys = self.inner.keys.read().expect("poisoned lock");
+            let mut candidates = keys
+                .iter()
+                .filter(|key| {
+                    key.algorithm.matches(algorithm)
+                        && (header.kid.is_none() || key.kid == header.kid)
+                })
+                .peekable();
+            if candidates.peek().is_none() {
+                return Err(TokenError::UnknownKey);
+            }
+            if !candidates.any(|key| key.verify(algorithm, signed.as_bytes(), &signature).is_ok()) {
+                return Err(TokenError::InvalidSignature);
+            }
+        }
+
+        let claims: Claims = decode_part(claims)?;
+        let now = Utc::now().timestamp();
+        if claims.exp.ok_or(TokenError::Expired)? + self.inner.leeway < now {
+            return Err(TokenError::Expired);
+        }
+        if claims.nbf.is_some_and(|nbf| nbf - self.inner.leeway > now) {
+            return Err(TokenError::NotYetValid);
+        }
+        if claims.iss.as_deref() != Some(self.inner.issuer.as_str()) {
+            return Err(TokenError::InvalidIssuer);
+        }
+        if !claims
+            .aud
+            .iter()
+            .any(|aud| self.inner.audiences.contains(aud))
+        {
+            return Err(TokenError::InvalidAudience);
+        }
+
+        Ok(())
+    }
+
+    /// Requests a refresh of the keys, unless they have been refreshed recently.
+    fn request_refresh(&self) {
+        let last_refresh = *self.inner.last_refresh.read().expect("poisoned lock");
+        if last_refresh.map_or(true, |last| last.elapsed() >= MIN_REFRESH_INTERVAL) {
+            self.inner.refresh.notify_one();
+        }
+    }
+}
+
+fn decode_part<T: for<'de> Deserialize<'de>>(part: &str) -> Result<T, TokenError> {
+    let bytes = BASE64_URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| TokenError::Malformed)?;
+    serde_json::from_slice(&bytes).map_err(|_| TokenError::Malformed)
+}
+
+async fn refresh_keys(
+    inner: Weak<Inner>,
+    refresh: Arc<Notify>,
+    client: HttpClient,
+    issuer: String,
+    jwks_url: Option<String>,
+    interval: Duration,
+) {
+    loop {
+        match fetch_keys(&client, &issuer, jwks_url.as_deref()).await {
+            Ok(keys) => {
+                let Some(inner) = inner.upgrade() else { break };
+                *inner.keys.write().expect("poisoned lock") = keys;
+                *inner.last_refresh.write().expect("poisoned lock") = Some(Instant::now());
+            }
+            Err(error) => emit!(HttpSourceJwksRefreshError { error: &error }),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {},
+            _ = refresh.notified() => {},
+        }
+        if inner.strong_count() == 0 {
+            break;
+        }
+    }
+}
+
+async fn fetch_keys(
+    client: &HttpClient,
+    issuer: &str,
+    jwks_url: Option<&str>,
+) -> Result<Vec<VerifyingKey>, JwksError> {
+    let jwks_url = match jwks_url {
+        Some(jwks_url) => jwks_url.to_owned(),
+        None => {
+            let url = format!(
+                "{}/.well-known/openid-configuration",
+                issuer.trim_end_matches('/')
+            );
+            let discovery: Discovery = get_json(client, &url).await?;
+            discovery.jwks_uri
+        }
+    };
+
+    let jwks: Jwks = get_json(client, &jwks_url).await?;
+    Ok(jwks
+        .keys
+        .iter()
+        .filter_map(|jwk| {
+            VerifyingKey::from_jwk(jwk)
+                .map_err(|error| {
+                    debug!(message = "Ignoring unsupported JSON Web Key.", kid = ?jwk.kid, %error);
+                })
+                .ok()
+        })
+        .collect())
+}
+
+async fn get_json<T: for<'de> Deserialize<'de>>(
+    client: &HttpClient,
+    url: &str,
+) -> Result<T, JwksError> {
+    let uri: Uri = url.parse().context(InvalidUrlSnafu { url })?;
+    let request = Request::get(uri)
+        .body(Body::empty())
+        .expect("building request can't fail");
+
+    let response = client.send(request).await.context(SendSnafu)?;
+    if !response.status().is_success() {
+        return Err(JwksError::Status {
+            status: response.status(),
+        });
+    }
+
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(ReadBodySnafu)?;
+    serde_json::from_slice(&body).context(ParseResponseSnafu)
+}
+
+#[derive(Deserialize)]
+struct Discovery {
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    #[serde(rename = "use")]
+    usage: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    iss: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    aud: Vec<String>,
+    exp: Option<i64>,
+    nbf: Option<i64>,
+}
+
+/// The `aud` claim is either a single audience or a list of them.
+fn one_or_many<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(aud) => vec![aud],
+        OneOrMany::Many(aud) => aud,
+    })
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Algorithm {
+    Rs256,
+    Rs384,
+    Rs512,
+    Es256,
+    Es384,
+}
+
+impl Algorithm {
+    fn from_name(alg: &str) -> Result<Self, TokenError> {
+        match alg {
+            "RS256" => Ok(Self::Rs256),
+            "RS384" => Ok(Self::Rs384),
+            "RS512" => Ok(Self::Rs512),
+            "ES256" => Ok(Self::Es256),
+            "ES384" => Ok(Self::Es384),
+            _ => Err(TokenError::UnsupportedAlgorithm {
+                alg: alg.to_owned(),
+            }),
+        }
+    }
+
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Rs256 | Self::Es256 => MessageDigest::sha256(),
+            Self::Rs384 | Self::Es384 => MessageDigest::sha384(),
+            Self::Rs512 => MessageDigest::sha512(),
+        }
+    }
+}
+
+/// The algorithms that a key can verify signatures of.
+#[derive(Debug)]
+enum KeyAlgorithm {
+    Rsa,
+    Ec(Algorithm),
+}
+
+impl KeyAlgorithm {
+    fn matches(&self, algorithm: Algorithm) -> bool {
+        match self {
+            Self::Rsa => matches!(
+                algorithm,
+                Algorithm::Rs256 | Algorithm::Rs384 | Algorithm::Rs512
+            ),
+            Self::Ec(curve) => *curve == algorithm,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct VerifyingKey {
+    kid: Option<String>,
+    algorithm: KeyAlgorithm,
+    key: PKey<Public>,
+}
+
+impl VerifyingKey {
+    fn from_jwk(jwk: &Jwk) -> crate::Result<Self> {
+        if jwk.usage.as_deref().is_some_and(|usage| usage != "sig") {
+            return Err("key is not used for signatures".into());
+        }
+
+        let param = |value: &Option<String>, name: &str| -> crate::Result<BigNum> {
+            let value = value
+                .as_deref()
+                .ok_or_else(|| format!("missing `{}` parameter", name))?;
+            Ok(BigNum::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(value)?)?)
+        };
+
+        let (algorithm, key) = match jwk.kty.as_str() {
+            "RSA" => {
+                let rsa = Rsa::from_public_components(param(&jwk.n, "n")?, param(&jwk.e, "e")?)?;
+                (KeyAlgorithm::Rsa, PKey::from_rsa(rsa)?)
+            }
+            "EC" => {
+                let (algorithm, nid) = match jwk.crv.as_deref() {
+                    Some("P-256") => (Algorithm::Es256, Nid::X9_62_PRIME256V1),
+                    Some("P-384") => (Algorithm::Es384, Nid::SECP384R1),
+                    crv => return Err(format!("unsupported curve {:?}", crv).into()),
+                };
+                let group = EcGroup::from_curve_name(nid)?;
+                let ec = EcKey::from_public_key_affine_coordinates(
+                    &group,
+                    &param(&jwk.x, "x")?,
+                    &param(&jwk.y, "y")?,
+                )?;
+                (KeyAlgorithm::Ec(algorithm), PKey::from_ec_key(ec)?)
+            }
+            kty => return Err(format!("unsupported key type {:?}", kty).into()),
+        };
+
+        Ok(Self {
+            kid: jwk.kid.clone(),
+            algorithm,
+            key,
+        })
+    }
+
+    fn verify(
+        &self,
+        algorithm: Algorithm,
+        signed: &[u8],
+        signature: &[u8],
+    ) -> Result<(), TokenError> {
+        let signature = match algorithm {
+            // JWS encodes ECDSA signatures as the concatenation of `r` and `s`, while OpenSSL
+            // expects them DER encoded.
+            Algorithm::Es256 | Algorithm::Es384 => {
+                let (r, s) = signature.split_at(signature.len() / 2);
+                BigNum::from_slice(r)
+                    .and_then(|r| Ok((r, BigNum::from_slice(s)?)))
+                    .and_then(|(r, s)| EcdsaSig::from_private_components(r, s))
+                    .and_then(|signature| signature.to_der())
+                    .map_err(|_| TokenError::InvalidSignature)?
+            }
+            _ => signature.to_vec(),
+        };
+
+        let verified = Verifier::new(algorithm.digest(), &self.key)
+            .and_then(|mut verifier| verifier.verify_oneshot(&signature, signed))
+            .unwrap_or(false);
+        if verified {
+            Ok(())
+        } else {
+            Err(TokenError::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{ec::EcKey, pkey::Private, sign::Signer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn validator(keys: Vec<VerifyingKey>) -> OidcValidator {
+        OidcValidator {
+            inner: Arc::new(Inner {
+                issuer: "https://issuer.example.com".to_owned(),
+                audiences: vec!["vector".to_owned()],
+                leeway: 60,
+                keys: RwLock::new(keys),
+                last_refresh: RwLock::new(Some(Instant::now())),
+                refresh: Arc::new(Notify::new()),
+            }),
+        }
+    }
+
+    fn sign(key: &PKey<Private>, header: serde_json::Value, claims: serde_json::Value) -> String {
+        let signed = format!(
+            "{}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(header.to_string()),
+            BASE64_URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let mut signer = Signer::new(MessageDigest::sha256(), key).unwrap();
+        let der = signer.sign_oneshot_to_vec(signed.as_bytes()).unwrap();
+        let signature = EcdsaSig::from_der(&der).unwrap();
+        let mut raw = signature.r().to_vec_padded(32).unwrap();
+        raw.extend(signature.s().to_vec_padded(32).unwrap());
+        format!("{}.{}", signed, BASE64_URL_SAFE_NO_PAD.encode(raw))
+    }
+
+    fn key_pair(kid: &str) -> (PKey<Private>, VerifyingKey) {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let private = EcKey::generate(&group).unwrap();
+        let public = EcKey::from_public_key(&group, private.public_key()).unwrap();
+        (
+            PKey::from_ec_key(private).unwrap(),
+            VerifyingKey {
+                kid: Some(kid.to_owned()),
+                algorithm: KeyAlgorithm::Ec(Algorithm::Es256),
+                key: PKey::from_ec_key(public).unwrap(),
+            },
+        )
+    }
+
+    fn claims(aud: serde_json::Value) -> serde_json::Value {
+        let now = Utc::now().timestamp();
+        json!({
+            "iss": "https://issuer.example.com",
+            "aud": aud,
+            "exp": now + 300,
+            "nbf": now - 10,
+        })
+    }
+
+    #[test]
+    fn accepts_valid_token() {
+        let (private, public) = key_pair("key-1");
+        let validator = validator(vec![public]);
+        let token = sign(
+            &private,
+            json!({"alg": "ES256", "kid": "key-1"}),
+            claims(json!(["other", "vector"])),
+        );
+
+        assert!(validator.is_valid(&Some(format!("Bearer {token}"))).is_ok());
+    }
+
+    #[test]
+    fn accepts_token_without_kid_signed_by_any_key() {
+        let (old_private, old_public) = key_pair("key-1");
+        let (new_private, new_public) = key_pair("key-2");
+        let validator = validator(vec![old_public, new_public]);
+
+        for private in [old_private, new_private] {
+            let token = sign(&private, json!({"alg": "ES256"}), claims(json!("vector")));
+            assert!(validator.is_valid(&Some(format!("Bearer {token}"))).is_ok());
+        }
+
+        let (other, _) = key_pair("key-3");
+        let token = sign(&other, json!({"alg": "ES256"}), claims(json!("vector")));
+        assert_eq!(
+            validator
+                .is_valid(&Some(format!("Bearer {token}")))
+                .unwrap_err()
+                .to_string(),
+            "401: Invalid token signature"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        let (private, public) = key_pair("key-1");
+        let (other, _) = key_pair("key-1");
+        let validator = validator(vec![public]);
+        let header = json!({"alg": "ES256", "kid": "key-1"});
+        let now = Utc::now().timestamp();
+
+        let cases = [
+            (
+                sign(&other, header.clone(), claims(json!("vector"))),
+                "Invalid token signature",
+            ),
+            (
+                sign(
+                    &private,
+                    json!({"alg": "ES256", "kid": "key-2"}),
+                    claims(json!("vector")),
+                ),
+                "Token is signed with an unknown key",
+            ),
+            (
+                sign(&private, header.clone(), claims(json!("other"))),
+                "Token has an invalid audience",
+            ),
+            (
+                sign(
+                    &private,
+                    header.clone(),
+                    json!({"iss": "https://issuer.example.com", "aud": "vector", "exp": now - 120}),
+                ),
+                "Token has expired",
+            ),
+            (
+                sign(
+                    &private,
+                    header.clone(),
+                    json!({"iss": "https://other.example.com", "aud": "vector", "exp": now + 300}),
+                ),
+                "Token has an invalid issuer",
+            ),
+            (
+                sign(&private, json!({"alg": "HS256"}), claims(json!("vector"))),
+                "Unsupported signing algorithm \"HS256\"",
+            ),
+            ("not-a-token".to_owned(), "Malformed token"),
+        ];
+
+        for (token, message) in cases {
+            let error = validator
+                .is_valid(&Some(format!("Bearer {token}")))
+                .unwrap_err();
+            assert_eq!(error.to_string(), format!("401: {message}"));
+        }
+
+        assert_eq!(
+            validator.is_valid(&None).unwrap_err().to_string(),
+            "401: No bearer token"
+        );
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 };
 
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::FutureExt;
 use hyper::{service::make_service_fn, Server};
 use tokio::net::TcpStream;
 use tower::ServiceBuilder;
@@ -70,6 +70,12 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         decode(encoding_header, body)
     }
 
+    // This function can be defined to send the events of a request to a named
+    // output that depends on its headers, rather than to the output of its route.
+    fn request_output(&self, _header_map: &HeaderMap) -> Option<String> {
+        None
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -84,158 +90,241 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         acknowledgements: SourceAcknowledgementsConfig,
         keepalive_settings: KeepaliveConfig,
     ) -> crate::Result<crate::sources::Source> {
-        let tls = MaybeTlsSettings::from_config(tls, true)?;
-        let protocol = tls.http_protocol_name();
-        let auth = HttpSourceAuth::try_from(auth.as_ref())?;
-        let path = path.to_owned();
-        let acknowledgements = cx.do_acknowledgements(acknowledgements);
-        let enable_source_ip = self.enable_source_ip();
-
-        Ok(Box::pin(async move {
-            let mut filter: BoxedFilter<()> = match method {
-                HttpMethod::Head => warp::head().boxed(),
-                HttpMethod::Get => warp::get().boxed(),
-                HttpMethod::Put => warp::put().boxed(),
-                HttpMethod::Post => warp::post().boxed(),
-                HttpMethod::Patch => warp::patch().boxed(),
-                HttpMethod::Delete => warp::delete().boxed(),
-                HttpMethod::Options => warp::options().boxed(),
-            };
-
-            // https://github.com/rust-lang/rust-clippy/issues/8148
-            #[allow(clippy::unnecessary_to_owned)]
-            for s in path.split('/').filter(|&x| !x.is_empty()) {
-                filter = filter.and(warp::path(s.to_string())).boxed()
+        run_routes(
+            vec![HttpRoute {
+                path: path.to_owned(),
+                method,
+                output: None,
+                source: self,
+            }],
+            address,
+            response_code,
+            strict_path,
+            tls,
+            auth,
+            cx,
+            acknowledgements,
+            keepalive_settings,
+        )
+    }
+
+    fn enable_source_ip(&self) -> bool {
+        false
+    }
+}
+
+/// A URL path served by an HTTP source, along with the source that builds the events of the
+/// requests sent to it.
+pub struct HttpRoute<S> {
+    pub path: String,
+    pub method: HttpMethod,
+    /// The named output that the events are sent to, or the default output if `None`.
+    pub output: Option<String>,
+    pub source: S,
+}
+
+/// Serves each of the routes on the same listener.
+///
+/// A request is handled by the first route whose path and method it matches.
+#[allow(clippy::too_many_arguments)]
+pub fn run_routes<S: HttpSource>(
+    routes: Vec<HttpRoute<S>>,
+    address: SocketAddr,
+    response_code: StatusCode,
+    strict_path: bool,
+    tls: &Option<TlsEnableableConfig>,
+    auth: &Option<HttpSourceAuthConfig>,
+    cx: SourceContext,
+    acknowledgements: SourceAcknowledgementsConfig,
+    keepalive_settings: KeepaliveConfig,
+) -> crate::Result<crate::sources::Source> {
+    let tls = MaybeTlsSettings::from_config(tls, true)?;
+    let protocol = tls.http_protocol_name();
+    let auth = HttpSourceAuth::build(auth.as_ref(), &cx.proxy)?;
+    let acknowledgements = cx.do_acknowledgements(acknowledgements);
+    let enable_source_ip = routes.iter().any(|route| route.source.enable_source_ip());
+
+    Ok(Box::pin(async move {
+        let svc = routes
+            .into_iter()
+            .map(|route| {
+                route_filter(
+                    route,
+                    strict_path,
+                    auth.clone(),
+                    protocol,
+                    acknowledgements,
+                    response_code,
+                    cx.out.clone(),
+                )
+            })
+            .reduce(|svc, route| svc.or(route).unify().boxed())
+            .expect("HTTP sources serve at least one route");
+
+        let ping = warp::get().and(warp::path("ping")).map(|| "pong");
+        let routes = svc.or(ping).recover(|r: Rejection| async move {
+            if let Some(e_msg) = r.find::<ErrorMessage>() {
+                let json = warp::reply::json(e_msg);
+                Ok(warp::reply::with_status(json, e_msg.status_code()))
+            } else {
+                //other internal error - will return 500 internal server error
+                emit!(HttpInternalError {
+                    message: &format!("Internal error: {:?}", r)
+                });
+                Err(r)
             }
-            let svc = filter
-                .and(warp::path::tail())
-                .and_then(move |tail: Tail| async move {
-                    if !strict_path || tail.as_str().is_empty() {
-                        Ok(())
-                    } else {
-                        emit!(HttpInternalError {
-                            message: "Path not found."
-                        });
-                        Err(warp::reject::custom(ErrorMessage::new(
-                            StatusCode::NOT_FOUND,
-                            "Not found".to_string(),
-                        )))
+        });
+
+        let span = Span::current();
+        let make_svc = make_service_fn(move |conn: &MaybeTlsIncomingStream<TcpStream>| {
+            let remote_addr = conn.peer_addr();
+            let remote_addr_ref = enable_source_ip.then_some(remote_addr);
+            let svc = ServiceBuilder::new()
+                .layer(build_http_trace_layer(span.clone()))
+                .option_layer(keepalive_settings.max_connection_age_secs.map(|secs| {
+                    MaxConnectionAgeLayer::new(
+                        Duration::from_secs(secs),
+                        keepalive_settings.max_connection_age_jitter_factor,
+                        remote_addr,
+                    )
+                }))
+                .map_request(move |mut request: hyper::Request<_>| {
+                    if let Some(remote_addr_inner) = remote_addr_ref.as_ref() {
+                        request
+                            .extensions_mut()
+                            .insert(PeerAddr::new(*remote_addr_inner));
                     }
+
+                    request
                 })
-                .untuple_one()
-                .and(warp::path::full())
-                .and(warp::header::optional::<String>("authorization"))
-                .and(warp::header::optional::<String>("content-encoding"))
-                .and(warp::header::headers_cloned())
-                .and(warp::body::bytes())
-                .and(warp::query::<HashMap<String, String>>())
-                .and(warp::filters::ext::optional())
-                .and_then(
-                    move |path: FullPath,
-                          auth_header,
-                          encoding_header: Option<String>,
-                          headers: HeaderMap,
-                          body: Bytes,
-                          query_parameters: HashMap<String, String>,
-                          addr: Option<PeerAddr>| {
-                        debug!(message = "Handling HTTP request.", headers = ?headers);
-                        let http_path = path.as_str();
-
-                        let events = auth
-                            .is_valid(&auth_header)
-                            .and_then(|()| self.decode(encoding_header.as_deref(), body))
-                            .and_then(|body| {
-                                emit!(HttpBytesReceived {
-                                    byte_size: body.len(),
-                                    http_path,
-                                    protocol,
-                                });
-                                self.build_events(body, &headers, &query_parameters, path.as_str())
-                            })
-                            .map(|mut events| {
-                                emit!(HttpEventsReceived {
-                                    count: events.len(),
-                                    byte_size: events.estimated_json_encoded_size_of(),
-                                    http_path,
-                                    protocol,
-                                });
-
-                                self.enrich_events(
-                                    &mut events,
-                                    path.as_str(),
-                                    &headers,
-                                    &query_parameters,
-                                    addr.map(|PeerAddr(inner_addr)| inner_addr).as_ref(),
-                                );
-
-                                events
-                            });
-
-                        handle_request(events, acknowledgements, response_code, cx.out.clone())
-                    },
-                );
-
-            let ping = warp::get().and(warp::path("ping")).map(|| "pong");
-            let routes = svc.or(ping).recover(|r: Rejection| async move {
-                if let Some(e_msg) = r.find::<ErrorMessage>() {
-                    let json = warp::reply::json(e_msg);
-                    Ok(warp::reply::with_status(json, e_msg.status_code()))
-                } else {
-                    //other internal error - will return 500 internal server error
-                    emit!(HttpInternalError {
-                        message: &format!("Internal error: {:?}", r)
-                    });
-                    Err(r)
-                }
-            });
-
-            let span = Span::current();
-            let make_svc = make_service_fn(move |conn: &MaybeTlsIncomingStream<TcpStream>| {
-                let remote_addr = conn.peer_addr();
-                let remote_addr_ref = enable_source_ip.then_some(remote_addr);
-                let svc = ServiceBuilder::new()
-                    .layer(build_http_trace_layer(span.clone()))
-                    .option_layer(keepalive_settings.max_connection_age_secs.map(|secs| {
-                        MaxConnectionAgeLayer::new(
-                            Duration::from_secs(secs),
-                            keepalive_settings.max_connection_age_jitter_factor,
-                            remote_addr,
-                        )
-                    }))
-                    .map_request(move |mut request: hyper::Request<_>| {
-                        if let Some(remote_addr_inner) = remote_addr_ref.as_ref() {
-                            request
-                                .extensions_mut()
-                                .insert(PeerAddr::new(*remote_addr_inner));
-                        }
+                .service(warp::service(routes.clone()));
+            futures_util::future::ok::<_, Infallible>(svc)
+        });
 
-                        request
-                    })
-                    .service(warp::service(routes.clone()));
-                futures_util::future::ok::<_, Infallible>(svc)
-            });
+        info!(message = "Building HTTP server.", address = %address);
 
-            info!(message = "Building HTTP server.", address = %address);
+        let listener = tls.bind(&address).await.map_err(|err| {
+            error!("An error occurred: {:?}.", err);
+        })?;
 
-            let listener = tls.bind(&address).await.map_err(|err| {
+        Server::builder(hyper::server::accept::from_stream(listener.accept_stream()))
+            .serve(make_svc)
+            .with_graceful_shutdown(cx.shutdown.map(|_| ()))
+            .await
+            .map_err(|err| {
                 error!("An error occurred: {:?}.", err);
             })?;
 
-            Server::builder(hyper::server::accept::from_stream(listener.accept_stream()))
-                .serve(make_svc)
-                .with_graceful_shutdown(cx.shutdown.map(|_| ()))
-                .await
-                .map_err(|err| {
-                    error!("An error occurred: {:?}.", err);
-                })?;
+        Ok(())
+    }))
+}
 
-            Ok(())
-        }))
-    }
+fn route_filter<S: HttpSource>(
+    route: HttpRoute<S>,
+    strict_path: bool,
+    auth: HttpSourceAuth,
+    protocol: &'static str,
+    acknowledgements: bool,
+    response_code: StatusCode,
+    out: SourceSender,
+) -> BoxedFilter<(Box<dyn warp::Reply>,)> {
+    let HttpRoute {
+        path,
+        method,
+        output,
+        source,
+    } = route;
 
-    fn enable_source_ip(&self) -> bool {
-        false
+    let mut filter: BoxedFilter<()> = match method {
+        HttpMethod::Head => warp::head().boxed(),
+        HttpMethod::Get => warp::get().boxed(),
+        HttpMethod::Put => warp::put().boxed(),
+        HttpMethod::Post => warp::post().boxed(),
+        HttpMethod::Patch => warp::patch().boxed(),
+        HttpMethod::Delete => warp::delete().boxed(),
+        HttpMethod::Options => warp::options().boxed(),
+    };
+
+    // https://github.com/rust-lang/rust-clippy/issues/8148
+    #[allow(clippy::unnecessary_to_owned)]
+    for s in path.split('/').filter(|&x| !x.is_empty()) {
+        filter = filter.and(warp::path(s.to_string())).boxed()
     }
+    filter
+        .and(warp::path::tail())
+        .and_then(move |tail: Tail| async move {
+            if !strict_path || tail.as_str().is_empty() {
+                Ok(())
+            } else {
+                emit!(HttpInternalError {
+                    message: "Path not found."
+                });
+                Err(warp::reject::custom(ErrorMessage::new(
+                    StatusCode::NOT_FOUND,
+                    "Not found".to_string(),
+                )))
+            }
+        })
+        .untuple_one()
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::header::headers_cloned())
+        .and(warp::body::bytes())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::filters::ext::optional())
+        .and_then(
+            move |path: FullPath,
+                  auth_header,
+                  encoding_header: Option<String>,
+                  headers: HeaderMap,
+                  body: Bytes,
+                  query_parameters: HashMap<String, String>,
+                  addr: Option<PeerAddr>| {
+                debug!(message = "Handling HTTP request.", headers = ?headers);
+                let http_path = path.as_str();
+
+                let events = auth
+                    .is_valid(&auth_header)
+                    .and_then(|()| source.decode(encoding_header.as_deref(), body))
+                    .and_then(|body| {
+                        emit!(HttpBytesReceived {
+                            byte_size: body.len(),
+                            http_path,
+                            protocol,
+                        });
+                        source.build_events(body, &headers, &query_parameters, path.as_str())
+                    })
+                    .map(|mut events| {
+                        emit!(HttpEventsReceived {
+                            count: events.len(),
+                            byte_size: events.estimated_json_encoded_size_of(),
+                            http_path,
+                            protocol,
+                        });
+
+                        source.enrich_events(
+                            &mut events,
+                            path.as_str(),
+                            &headers,
+                            &query_parameters,
+                            addr.map(|PeerAddr(inner_addr)| inner_addr).as_ref(),
+                        );
+
+                        events
+                    });
+
+                handle_request(
+                    events,
+                    acknowledgements,
+                    response_code,
+                    out.clone(),
+                    source.request_output(&headers).or_else(|| output.clone()),
+                )
+            },
+        )
+        .map(|reply| Box::new(reply) as Box<dyn warp::Reply>)
+        .boxed()
 }
 
 #[derive(Clone)]
@@ -263,21 +352,25 @@ async fn handle_request(
     acknowledgements: bool,
     response_code: StatusCode,
     mut out: SourceSender,
+    output: Option<String>,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
         Ok(mut events) => {
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
 
             let count = events.len();
-            out.send_batch(events)
-                .map_err(|_| {
-                    // can only fail if receiving end disconnected, so we are shutting down,
-                    // probably not gracefully.
-                    emit!(StreamClosedError { count });
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(response_code, receiver))
-                .await
+            match output {
+                Some(output) => out.send_batch_named(&output, events).await,
+                None => out.send_batch(events).await,
+            }
+            .map_err(|_| {
+                // can only fail if receiving end disconnected, so we are shutting down,
+                // probably not gracefully.
+                emit!(StreamClosedError { count });
+                warp::reject::custom(RejectShuttingDown)
+            })?;
+
+            handle_batch_status(response_code, receiver).await
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
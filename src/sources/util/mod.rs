@@ -4,7 +4,11 @@ mod body_decoding;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 pub mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
@@ -21,6 +25,8 @@ mod message_decoding;
 pub mod multiline_config;
 #[cfg(any(feature = "sources-utils-net-tcp", feature = "sources-utils-net-udp"))]
 pub mod net;
+#[cfg(any(feature = "sources-aws_s3", feature = "sources-gcp_cloud_storage"))]
+pub mod object_decoding;
 #[cfg(all(
     unix,
     any(feature = "sources-socket", feature = "sources-utils-net-unix",)
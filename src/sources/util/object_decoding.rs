@@ -0,0 +1,399 @@
+use std::{collections::HashMap, future::ready, io::ErrorKind};
+
+use async_compression::tokio::bufread;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use smallvec::SmallVec;
+use tokio_util::{codec::FramedRead, io::StreamReader};
+use vector_lib::codecs::decoding::FramingError;
+use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::event::MaybeAsLogMut;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Registered,
+};
+use vector_lib::lookup::{metadata_path, path, PathPrefix};
+
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, EstimatedJsonEncodedSizeOf, Event, LogEvent},
+    internal_events::{EventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
+    source_sender::ClosedError,
+    SourceSender,
+};
+
+/// Compression scheme for objects retrieved from object storage.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum Compression {
+    /// Automatically attempt to determine the compression scheme.
+    ///
+    /// The compression scheme of the object is determined from its `Content-Encoding` and
+    /// `Content-Type` metadata, as well as the key suffix (for example, `.gz`).
+    ///
+    /// It is set to `none` if the compression scheme cannot be determined.
+    #[derivative(Default)]
+    Auto,
+
+    /// Uncompressed.
+    None,
+
+    /// GZIP.
+    Gzip,
+
+    /// ZSTD.
+    Zstd,
+}
+
+/// Turns the body of an object into a reader of its decompressed contents.
+pub async fn object_decoder<S, E>(
+    compression: Compression,
+    key: &str,
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    mut body: S,
+) -> Box<dyn tokio::io::AsyncRead + Send + Unpin>
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + Unpin + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let first = if let Some(first) = body.next().await {
+        first
+    } else {
+        return Box::new(tokio::io::empty());
+    };
+
+    let r = tokio::io::BufReader::new(StreamReader::new(
+        stream::iter(Some(first))
+            .chain(body)
+            .map_err(|e| std::io::Error::new(ErrorKind::Other, e)),
+    ));
+
+    let compression = match compression {
+        Auto => determine_compression(content_encoding, content_type, key).unwrap_or(None),
+        _ => compression,
+    };
+
+    use Compression::*;
+    match compression {
+        Auto => unreachable!(), // is mapped above
+        None => Box::new(r),
+        Gzip => Box::new({
+            let mut decoder = bufread::GzipDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+        Zstd => Box::new({
+            let mut decoder = bufread::ZstdDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+    }
+}
+
+// try to determine the compression given the:
+// * content-encoding
+// * content-type
+// * key name (for file extension)
+//
+// It will use this information in this order
+fn determine_compression(
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    key: &str,
+) -> Option<Compression> {
+    content_encoding
+        .and_then(content_encoding_to_compression)
+        .or_else(|| content_type.and_then(content_type_to_compression))
+        .or_else(|| object_key_to_compression(key))
+}
+
+fn content_encoding_to_compression(content_encoding: &str) -> Option<Compression> {
+    match content_encoding {
+        "gzip" => Some(Compression::Gzip),
+        "zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn content_type_to_compression(content_type: &str) -> Option<Compression> {
+    match content_type {
+        "application/gzip" | "application/x-gzip" => Some(Compression::Gzip),
+        "application/zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn object_key_to_compression(key: &str) -> Option<Compression> {
+    let extension = std::path::Path::new(key)
+        .extension()
+        .and_then(std::ffi::OsStr::to_str);
+
+    use Compression::*;
+    extension.and_then(|extension| match extension {
+        "gz" => Some(Gzip),
+        "zst" => Some(Zstd),
+        _ => Option::None,
+    })
+}
+
+/// Why the events of an object could not all be sent.
+pub enum ObjectEventsError {
+    /// The object could not be read to its end.
+    Read(Box<dyn FramingError>),
+
+    /// The events could not be sent downstream.
+    Send(ClosedError),
+}
+
+/// Frames and decodes the contents of an object, and sends the resulting events.
+///
+/// Each log event is passed to `handle_log` to add the metadata of the object before being sent.
+/// The batch notifier is dropped once all events have been sent, so that its status can be
+/// awaited by the caller.
+#[allow(clippy::too_many_arguments)]
+pub async fn send_object_events(
+    object_reader: Box<dyn tokio::io::AsyncRead + Send + Unpin>,
+    decoder: &Decoder,
+    multiline: Option<&line_agg::Config>,
+    batch: Option<BatchNotifier>,
+    bytes_received: &Registered<BytesReceived>,
+    events_received: &Registered<EventsReceived>,
+    out: &mut SourceSender,
+    mut handle_log: impl FnMut(&mut LogEvent) + Send,
+) -> Result<(), ObjectEventsError> {
+    // Record the read error seen to propagate up later so we avoid acknowledging the object.
+    //
+    // FramedRead likely stops when it gets an i/o error but I found it more clear to
+    // show that we `take_while` there hasn't been an error
+    //
+    // This can result in objects being partially processed before an error, but we
+    // prefer duplicate lines over message loss. Future work could include recording
+    // the offset of the object that has been read, but this would only be relevant in
+    // the case that the same vector instance processes the same object.
+    let mut read_error = None;
+    let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+        FramedRead::new(object_reader, decoder.framer.clone())
+            .map(|res| {
+                res.inspect(|bytes| {
+                    bytes_received.emit(ByteSize(bytes.len()));
+                })
+                .map_err(|err| {
+                    read_error = Some(err);
+                })
+                .ok()
+            })
+            .take_while(|res| ready(res.is_some()))
+            .map(|r| r.expect("validated by take_while")),
+    );
+
+    let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match multiline {
+        Some(config) => Box::new(
+            LineAgg::new(
+                lines.map(|line| ((), line, ())),
+                line_agg::Logic::new(config.clone()),
+            )
+            .map(|(_src, line, _context, _lastline_context)| line),
+        ),
+        None => lines,
+    };
+
+    let mut stream = lines.flat_map(|line| {
+        let events = match decoder.deserializer_parse(line) {
+            Ok((events, _events_size)) => events,
+            Err(_error) => {
+                // Error is handled by `codecs::Decoder`, no further handling
+                // is needed here.
+                SmallVec::new()
+            }
+        };
+
+        let events = events
+            .into_iter()
+            .map(|mut event: Event| {
+                event = event.with_batch_notifier_option(&batch);
+                if let Some(log_event) = event.maybe_as_log_mut() {
+                    handle_log(log_event);
+                }
+                events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+                event
+            })
+            .collect::<Vec<Event>>();
+        futures::stream::iter(events)
+    });
+
+    let send_error = match out.send_event_stream(&mut stream).await {
+        Ok(_) => None,
+        Err(_) => {
+            let (count, _) = stream.size_hint();
+            emit!(StreamClosedError { count });
+            Some(ClosedError)
+        }
+    };
+
+    // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+    // so we explicitly drop it so that we can again utilize `read_error` below.
+    drop(stream);
+
+    // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
+    // reference must be dropped before the status of the batch is sent to the channel.
+    drop(batch);
+
+    match (read_error, send_error) {
+        (Some(error), _) => Err(ObjectEventsError::Read(error)),
+        (None, Some(error)) => Err(ObjectEventsError::Send(error)),
+        (None, None) => Ok(()),
+    }
+}
+
+/// The metadata of an object that is added to each log event read from it.
+pub struct ObjectMetadata<'a> {
+    pub bucket: &'a str,
+    pub key: &'a str,
+    /// Further attributes of the object that are specific to the source, such as its region.
+    pub attributes: &'a [(&'static str, &'a str)],
+    /// The user-defined metadata of the object.
+    pub metadata: Option<&'a HashMap<String, String>>,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl ObjectMetadata<'_> {
+    pub fn insert(
+        &self,
+        source_type: &'static str,
+        log: &mut LogEvent,
+        log_namespace: LogNamespace,
+    ) {
+        log_namespace.insert_source_metadata(
+            source_type,
+            log,
+            Some(LegacyKey::Overwrite(path!("bucket"))),
+            path!("bucket"),
+            Bytes::from(self.bucket.as_bytes().to_vec()),
+        );
+
+        log_namespace.insert_source_metadata(
+            source_type,
+            log,
+            Some(LegacyKey::Overwrite(path!("object"))),
+            path!("object"),
+            Bytes::from(self.key.as_bytes().to_vec()),
+        );
+
+        for &(name, value) in self.attributes {
+            log_namespace.insert_source_metadata(
+                source_type,
+                log,
+                Some(LegacyKey::Overwrite(path!(name))),
+                path!(name),
+                Bytes::from(value.as_bytes().to_vec()),
+            );
+        }
+
+        if let Some(metadata) = self.metadata {
+            for (key, value) in metadata {
+                log_namespace.insert_source_metadata(
+                    source_type,
+                    log,
+                    Some(LegacyKey::Overwrite(path!(key))),
+                    path!("metadata", key.as_str()),
+                    value.clone(),
+                );
+            }
+        }
+
+        log_namespace.insert_vector_metadata(
+            log,
+            log_schema().source_type_key(),
+            path!("source_type"),
+            Bytes::from_static(source_type.as_bytes()),
+        );
+
+        // This handles the transition from the original timestamp logic. Originally the
+        // `timestamp_key` was populated by the `last_modified` time on the object, falling
+        // back to calling `now()`.
+        match log_namespace {
+            LogNamespace::Vector => {
+                if let Some(timestamp) = self.timestamp {
+                    log.insert(metadata_path!(source_type, "timestamp"), timestamp);
+                }
+
+                log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+            }
+            LogNamespace::Legacy => {
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    log.try_insert(
+                        (PathPrefix::Event, timestamp_key),
+                        self.timestamp.unwrap_or_else(Utc::now),
+                    );
+                }
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[test]
+    fn determine_compression() {
+        use super::Compression;
+
+        let cases = vec![
+            ("out.log", Some("gzip"), None, Some(Compression::Gzip)),
+            (
+                "out.log",
+                None,
+                Some("application/gzip"),
+                Some(Compression::Gzip),
+            ),
+            (
+                "out.log",
+                None,
+                Some("application/zstd"),
+                Some(Compression::Zstd),
+            ),
+            ("out.log.gz", None, None, Some(Compression::Gzip)),
+            ("out.txt", None, None, None),
+        ];
+        for case in cases {
+            let (key, content_encoding, content_type, expected) = case;
+            assert_eq!(
+                super::determine_compression(content_encoding, content_type, key),
+                expected,
+                "key={:?} content_encoding={:?} content_type={:?}",
+                key,
+                content_encoding,
+                content_type,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn decode_gzip_object_by_key() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(b"first\nsecond\n").unwrap();
+        let body = stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from(
+            encoder.finish().unwrap(),
+        ))]);
+
+        let mut data = Vec::new();
+        object_decoder(Compression::Auto, "logs/out.log.gz", None, None, body)
+            .await
+            .read_to_end(&mut data)
+            .await
+            .unwrap();
+
+        assert_eq!(data, b"first\nsecond\n");
+    }
+}
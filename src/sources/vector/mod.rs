@@ -1,8 +1,9 @@
 //! The `vector` source. See [VectorConfig].
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 use chrono::Utc;
 use futures::TryFutureExt;
+use serde_with::serde_as;
 use tonic::{Request, Response, Status};
 use vector_lib::codecs::NativeDeserializerConfig;
 use vector_lib::configurable::configurable_component;
@@ -114,6 +115,7 @@ async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<()
 }
 
 /// Configuration for the `vector` source.
+#[serde_as]
 #[configurable_component(source("vector", "Collect observability data from a Vector instance."))]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
@@ -130,6 +132,20 @@ pub struct VectorConfig {
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
 
+    /// The interval at which the TLS certificate, key, and CA files are checked for changes, in
+    /// seconds.
+    ///
+    /// When the files change, they are reloaded, so that certificates can be rotated without
+    /// restarting Vector. Connections that are already established are kept, and new connections
+    /// are accepted with the reloaded certificates.
+    ///
+    /// If not set, the files are only read when the source is built.
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = 60))]
+    #[configurable(metadata(docs::human_name = "TLS Reload Interval"))]
+    tls_reload_interval_secs: Option<Duration>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -156,6 +172,7 @@ impl Default for VectorConfig {
             version: None,
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
+            tls_reload_interval_secs: None,
             acknowledgements: Default::default(),
             log_namespace: None,
         }
@@ -172,7 +189,10 @@ impl GenerateConfig for VectorConfig {
 #[typetag::serde(name = "vector")]
 impl SourceConfig for VectorConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
-        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let mut tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        if let Some(interval) = self.tls_reload_interval_secs {
+            tls_settings = tls_settings.reload_every(&self.tls, true, interval)?;
+        }
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let log_namespace = cx.log_namespace(self.log_namespace);
 
@@ -182,6 +202,7 @@ impl SourceConfig for VectorConfig {
             log_namespace,
         })
         .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+        .accept_compressed(tonic::codec::CompressionEncoding::Zstd)
         // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
         .max_decoding_message_size(usize::MAX);
 
@@ -339,4 +360,16 @@ mod tests {
         );
         run_test(&config, addr).await;
     }
+
+    #[tokio::test]
+    async fn receive_zstd_compressed_message() {
+        let addr = test_util::next_addr();
+
+        let config = format!(
+            r#"address = "{}"
+            compression = "zstd""#,
+            addr
+        );
+        run_test(&config, addr).await;
+    }
 }
@@ -0,0 +1,371 @@
+//! This mod implements the `webhook` source.
+//! The source receives the webhooks of SaaS providers, such as GitHub, Stripe, and PagerDuty. The
+//! signature of each webhook is verified with the secret shared with the provider, replayed
+//! deliveries are dropped, and the payload is normalized into an event with the fields that are
+//! common to every provider, so that audit events can be ingested without a VRL program for each
+//! provider.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use serde_with::serde_as;
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::schema::Definition;
+use vector_lib::sensitive_string::SensitiveString;
+use vrl::value::{kind::Collection, Kind, ObjectMap};
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
+    http::KeepaliveConfig,
+    serde::bool_or_struct,
+    sources::util::{http::HttpMethod, ErrorMessage, HttpSource},
+    tls::TlsEnableableConfig,
+};
+
+mod provider;
+mod replay;
+
+use provider::Delivery;
+pub use provider::Provider;
+use replay::ReplayCache;
+
+/// Configuration for the `webhook` source.
+#[serde_as]
+#[configurable_component(source(
+    "webhook",
+    "Receive the webhooks of SaaS providers, such as GitHub, Stripe, and PagerDuty."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// The socket address to listen for connections on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:443"))]
+    #[configurable(metadata(docs::examples = "localhost:8080"))]
+    address: SocketAddr,
+
+    /// The URL path on which webhooks are received.
+    #[serde(default = "default_path")]
+    #[configurable(metadata(docs::examples = "/webhooks/github"))]
+    path: String,
+
+    #[configurable(derived)]
+    provider: Provider,
+
+    /// The secret that the provider signs the webhooks with.
+    ///
+    /// Webhooks whose signature can't be verified with this secret are rejected.
+    #[configurable(metadata(docs::examples = "${WEBHOOK_SECRET}"))]
+    secret: SensitiveString,
+
+    /// The time during which deliveries are protected against replays, in seconds.
+    ///
+    /// Deliveries whose ID was already received within this time are dropped. For providers that
+    /// sign a timestamp along with the payload, deliveries signed further in the past, or in the
+    /// future, than this time are rejected.
+    #[serde(default = "default_replay_window_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Replay Window"))]
+    replay_window_secs: Duration,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    keepalive: KeepaliveConfig,
+}
+
+fn default_path() -> String {
+    "/".to_owned()
+}
+
+const fn default_replay_window_secs() -> Duration {
+    Duration::from_secs(300)
+}
+
+impl GenerateConfig for WebhookConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "0.0.0.0:443"
+            provider = "github"
+            secret = "${WEBHOOK_SECRET}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "webhook")]
+impl SourceConfig for WebhookConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let source = WebhookSource {
+            provider: self.provider,
+            secret: Bytes::copy_from_slice(self.secret.inner().as_bytes()),
+            replay_window: self.replay_window_secs,
+            replay_cache: Arc::new(ReplayCache::new(self.replay_window_secs)),
+            log_namespace: cx.log_namespace(self.log_namespace),
+        };
+
+        source.run(
+            self.address,
+            &self.path,
+            HttpMethod::Post,
+            StatusCode::OK,
+            true,
+            &self.tls,
+            &None,
+            cx,
+            self.acknowledgements,
+            self.keepalive.clone(),
+        )
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::from(BTreeMap::from([
+                ("provider".into(), Kind::bytes()),
+                ("id".into(), Kind::bytes().or_undefined()),
+                ("event_type".into(), Kind::bytes().or_undefined()),
+                ("actor".into(), Kind::bytes().or_undefined()),
+                ("occurred_at".into(), Kind::timestamp().or_undefined()),
+                ("payload".into(), Kind::json()),
+            ]))),
+            [log_namespace],
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct WebhookSource {
+    provider: Provider,
+    secret: Bytes,
+    replay_window: Duration,
+    replay_cache: Arc<ReplayCache>,
+    log_namespace: LogNamespace,
+}
+
+impl HttpSource for WebhookSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: &HeaderMap,
+        _query_parameters: &HashMap<String, String>,
+        _path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        self.provider.verify(
+            &self.secret,
+            &body,
+            header_map,
+            self.replay_window,
+            Utc::now(),
+        )?;
+
+        let payload = serde_json::from_slice(&body).map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid JSON payload: {}", error),
+            )
+        })?;
+        let delivery = self.provider.normalize(payload, header_map);
+
+        let log = create_log_event(self.provider, &delivery, self.log_namespace);
+        let Some(id) = delivery.id else {
+            return Ok(vec![log.into()]);
+        };
+        if !self.replay_cache.insert(&id) {
+            debug!(message = "Dropping replayed webhook delivery.", %id);
+            return Ok(Vec::new());
+        }
+
+        // A delivery that fails is retried by the provider, so its ID is forgotten, so that the
+        // retry isn't dropped as a replay.
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let replay_cache = Arc::clone(&self.replay_cache);
+        tokio::spawn(async move {
+            if receiver.await != BatchStatus::Delivered {
+                replay_cache.remove(&id);
+            }
+        });
+
+        Ok(vec![Event::from(log).with_batch_notifier(&batch)])
+    }
+}
+
+fn create_log_event(
+    provider: Provider,
+    delivery: &Delivery,
+    log_namespace: LogNamespace,
+) -> LogEvent {
+    let mut object = ObjectMap::new();
+    object.insert("provider".into(), provider.as_str().into());
+    if let Some(id) = &delivery.id {
+        object.insert("id".into(), id.as_str().into());
+    }
+    if let Some(event_type) = &delivery.event_type {
+        object.insert("event_type".into(), event_type.as_str().into());
+    }
+    if let Some(actor) = &delivery.actor {
+        object.insert("actor".into(), actor.as_str().into());
+    }
+    if let Some(occurred_at) = delivery.occurred_at {
+        object.insert("occurred_at".into(), occurred_at.into());
+    }
+    object.insert("payload".into(), Value::from(delivery.payload.clone()));
+
+    let mut log = LogEvent::from(Value::Object(object));
+    log_namespace.insert_standard_vector_source_metadata(&mut log, WebhookConfig::NAME, Utc::now());
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use futures::Stream;
+    use vector_lib::event::EventStatus;
+    use vrl::value;
+
+    use super::{provider::hmac_sha256, *};
+    use crate::{
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
+            next_addr, spawn_collect_n, wait_for_tcp,
+        },
+        SourceSender,
+    };
+
+    const SECRET: &str = "It's a Secret to Everybody";
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebhookConfig>();
+    }
+
+    async fn source(status: EventStatus) -> (impl Stream<Item = Event> + Unpin, SocketAddr) {
+        let (sender, recv) = SourceSender::new_test_finalize(status);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            WebhookConfig {
+                address,
+                path: "/webhooks/github".to_owned(),
+                provider: Provider::Github,
+                secret: SECRET.to_owned().into(),
+                replay_window_secs: default_replay_window_secs(),
+                tls: None,
+                acknowledgements: true.into(),
+                log_namespace: Some(true),
+                keepalive: Default::default(),
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, delivery: &str, body: &str, secret: &str) -> u16 {
+        let signature = hex::encode(hmac_sha256(secret.as_bytes(), &[body.as_bytes()]).unwrap());
+        reqwest::Client::new()
+            .post(format!("http://{}/webhooks/github", address))
+            .header("X-GitHub-Event", "issues")
+            .header("X-GitHub-Delivery", delivery)
+            .header("X-Hub-Signature-256", format!("sha256={}", signature))
+            .body(body.to_owned())
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    const BODY: &str = r#"{"action": "opened", "sender": {"login": "octocat"}}"#;
+
+    #[tokio::test]
+    async fn receives_verified_webhooks() {
+        assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+            let (rx, address) = source(EventStatus::Delivered).await;
+
+            let events = spawn_collect_n(
+                async move { assert_eq!(200, send(address, "72d3162e", BODY, SECRET).await) },
+                rx,
+                1,
+            )
+            .await;
+
+            let log = events[0].as_log();
+            assert_eq!(
+                log.value(),
+                &value!({
+                    "provider": "github",
+                    "id": "72d3162e",
+                    "event_type": "issues.opened",
+                    "actor": "octocat",
+                    "payload": {"action": "opened", "sender": {"login": "octocat"}},
+                })
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_signatures_and_drops_replays() {
+        let (mut rx, address) = source(EventStatus::Delivered).await;
+
+        assert_eq!(401, send(address, "72d3162e", BODY, "not the secret").await);
+        assert_eq!(200, send(address, "72d3162e", BODY, SECRET).await);
+        assert_eq!(200, send(address, "72d3162e", BODY, SECRET).await);
+        assert_eq!(collect_ready(&mut rx).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn accepts_retries_of_failed_deliveries() {
+        let (mut rx, address) = source(EventStatus::Errored).await;
+
+        assert_eq!(500, send(address, "72d3162e", BODY, SECRET).await);
+        // The ID of the failed delivery is forgotten once the finalizer of its event has run.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(500, send(address, "72d3162e", BODY, SECRET).await);
+        assert_eq!(collect_ready(&mut rx).await.len(), 2);
+    }
+}
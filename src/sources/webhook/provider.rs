@@ -0,0 +1,329 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use openssl::{error::ErrorStack, hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
+use serde_json::Value as JsonValue;
+use vector_lib::configurable::configurable_component;
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::sources::util::ErrorMessage;
+
+/// The SaaS provider that sends the webhooks.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// GitHub webhooks.
+    ///
+    /// The `X-Hub-Signature-256` header is verified, and the `X-GitHub-Delivery` header is the ID
+    /// of the delivery.
+    Github,
+
+    /// Stripe webhooks.
+    ///
+    /// The `Stripe-Signature` header is verified, along with the timestamp that it signs, and the
+    /// `id` of the event is the ID of the delivery.
+    Stripe,
+
+    /// PagerDuty V3 webhooks.
+    ///
+    /// The `X-PagerDuty-Signature` header is verified, and the `event.id` of the payload is the ID
+    /// of the delivery.
+    Pagerduty,
+}
+
+/// The fields that are common to the webhooks of every provider.
+#[derive(Debug, PartialEq)]
+pub(super) struct Delivery {
+    pub(super) id: Option<String>,
+    pub(super) event_type: Option<String>,
+    pub(super) actor: Option<String>,
+    pub(super) occurred_at: Option<DateTime<Utc>>,
+    pub(super) payload: JsonValue,
+}
+
+impl Provider {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::Stripe => "stripe",
+            Self::Pagerduty => "pagerduty",
+        }
+    }
+
+    /// Verifies the signature of a webhook with the secret shared with the provider.
+    ///
+    /// Signatures of a timestamp, which only Stripe sends, are rejected if the timestamp is
+    /// further from `now` than `tolerance`.
+    pub(super) fn verify(
+        self,
+        secret: &[u8],
+        body: &[u8],
+        headers: &HeaderMap,
+        tolerance: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<(), ErrorMessage> {
+        match self {
+            Self::Github => {
+                let header = signature_header(headers, "X-Hub-Signature-256")?;
+                verify_any(secret, &[body], header.strip_prefix("sha256="))
+            }
+            Self::Stripe => {
+                let header = signature_header(headers, "Stripe-Signature")?;
+                let mut timestamp = None;
+                let mut signatures = Vec::new();
+                let items = header
+                    .split(',')
+                    .filter_map(|item| item.trim().split_once('='));
+                for (key, value) in items {
+                    match key {
+                        "t" => timestamp = Some(value),
+                        "v1" => signatures.push(value),
+                        _ => {}
+                    }
+                }
+
+                let timestamp =
+                    timestamp.ok_or_else(|| unauthorized("Missing signature timestamp"))?;
+                let signed_at = timestamp
+                    .parse::<i64>()
+                    .map_err(|_| unauthorized("Invalid signature timestamp"))?;
+                if now.timestamp().abs_diff(signed_at) > tolerance.as_secs() {
+                    return Err(unauthorized(
+                        "Signature timestamp is outside of the replay window",
+                    ));
+                }
+                verify_any(secret, &[timestamp.as_bytes(), b".", body], signatures)
+            }
+            Self::Pagerduty => {
+                // A signature is sent for each of the secrets of the subscription while they are
+                // rotated.
+                let header = signature_header(headers, "X-PagerDuty-Signature")?;
+                verify_any(
+                    secret,
+                    &[body],
+                    header
+                        .split(',')
+                        .filter_map(|signature| signature.trim().strip_prefix("v1=")),
+                )
+            }
+        }
+    }
+
+    /// Extracts the fields that are common to the webhooks of every provider.
+    pub(super) fn normalize(self, payload: JsonValue, headers: &HeaderMap) -> Delivery {
+        match self {
+            Self::Github => {
+                // The type of the event is qualified by its action, such as `pull_request.opened`.
+                let event = header_value(headers, "X-GitHub-Event");
+                let event_type = match (event, payload.get("action").and_then(JsonValue::as_str)) {
+                    (Some(event), Some(action)) => Some(format!("{}.{}", event, action)),
+                    (event, _) => event.map(str::to_owned),
+                };
+                Delivery {
+                    id: header_value(headers, "X-GitHub-Delivery").map(str::to_owned),
+                    event_type,
+                    actor: string_at(&payload, "/sender/login"),
+                    occurred_at: None,
+                    payload,
+                }
+            }
+            Self::Stripe => Delivery {
+                id: string_at(&payload, "/id"),
+                event_type: string_at(&payload, "/type"),
+                actor: None,
+                occurred_at: payload
+                    .get("created")
+                    .and_then(JsonValue::as_i64)
+                    .and_then(|created| DateTime::from_timestamp(created, 0)),
+                payload,
+            },
+            Self::Pagerduty => Delivery {
+                id: string_at(&payload, "/event/id"),
+                event_type: string_at(&payload, "/event/event_type"),
+                actor: string_at(&payload, "/event/agent/summary"),
+                occurred_at: payload
+                    .pointer("/event/occurred_at")
+                    .and_then(JsonValue::as_str)
+                    .and_then(|occurred_at| DateTime::parse_from_rfc3339(occurred_at).ok())
+                    .map(|occurred_at| occurred_at.with_timezone(&Utc)),
+                payload,
+            },
+        }
+    }
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn signature_header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, ErrorMessage> {
+    header_value(headers, name)
+        .ok_or_else(|| unauthorized(&format!("Missing signature header {:?}", name)))
+}
+
+fn string_at(payload: &JsonValue, pointer: &str) -> Option<String> {
+    payload.pointer(pointer)?.as_str().map(str::to_owned)
+}
+
+fn unauthorized(message: &str) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::UNAUTHORIZED, message.to_owned())
+}
+
+pub(super) fn hmac_sha256(secret: &[u8], message: &[&[u8]]) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(secret)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    for part in message {
+        signer.update(part)?;
+    }
+    signer.sign_to_vec()
+}
+
+/// Verifies that one of the hex encoded signatures is the signature of the message.
+fn verify_any<'a>(
+    secret: &[u8],
+    message: &[&[u8]],
+    signatures: impl IntoIterator<Item = &'a str>,
+) -> Result<(), ErrorMessage> {
+    let expected = hmac_sha256(secret, message).map_err(|error| {
+        ErrorMessage::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to compute signature: {}", error),
+        )
+    })?;
+    let valid = signatures
+        .into_iter()
+        .filter_map(|signature| hex::decode(signature).ok())
+        // The comparison takes the same time wherever the signatures differ.
+        .any(|signature| signature.len() == expected.len() && memcmp::eq(&signature, &expected));
+    if valid {
+        Ok(())
+    } else {
+        Err(unauthorized("Invalid signature"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use warp::http::HeaderValue;
+
+    use super::*;
+
+    const SECRET: &[u8] = b"It's a Secret to Everybody";
+    const BODY: &[u8] = b"Hello, World!";
+    const TOLERANCE: Duration = Duration::from_secs(300);
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn sign(message: &[&[u8]]) -> String {
+        hex::encode(hmac_sha256(SECRET, message).unwrap())
+    }
+
+    #[test]
+    fn verifies_github_signature() {
+        // The example of the GitHub documentation.
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+        let now = Utc::now();
+        let verify =
+            |headers: &HeaderMap| Provider::Github.verify(SECRET, BODY, headers, TOLERANCE, now);
+
+        assert!(verify(&headers("X-Hub-Signature-256", signature)).is_ok());
+        assert!(verify(&headers(
+            "X-Hub-Signature-256",
+            &signature.replace('7', "8")
+        ))
+        .is_err());
+        assert!(verify(&headers("X-Hub-Signature-256", &signature[7..])).is_err());
+        assert!(verify(&HeaderMap::new()).is_err());
+    }
+
+    #[test]
+    fn verifies_stripe_signature_and_timestamp() {
+        let now = DateTime::from_timestamp(1_714_564_800, 0).unwrap();
+        let signature = sign(&[b"1714564800", b".", BODY]);
+        let verify = |header: String| {
+            let headers = headers("Stripe-Signature", &header);
+            Provider::Stripe.verify(SECRET, BODY, &headers, TOLERANCE, now)
+        };
+
+        assert!(verify(format!("t=1714564800,v1={}", signature)).is_ok());
+        assert!(verify(format!("t=1714564800,v1=00,v1={},v0=00", signature)).is_ok());
+        assert!(verify(format!("t=1714564801,v1={}", signature)).is_err());
+        assert!(verify(format!("v1={}", signature)).is_err());
+
+        let stale = now + chrono::Duration::seconds(301);
+        let headers = headers(
+            "Stripe-Signature",
+            &format!("t=1714564800,v1={}", signature),
+        );
+        let error = Provider::Stripe
+            .verify(SECRET, BODY, &headers, TOLERANCE, stale)
+            .unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn verifies_any_pagerduty_signature() {
+        let signature = sign(&[BODY]);
+        let now = Utc::now();
+        let verify = |header: String| {
+            let headers = headers("X-PagerDuty-Signature", &header);
+            Provider::Pagerduty.verify(SECRET, BODY, &headers, TOLERANCE, now)
+        };
+
+        assert!(verify(format!("v1={}", signature)).is_ok());
+        assert!(verify(format!("v1=00ff, v1={}", signature)).is_ok());
+        assert!(verify("v1=00ff".to_owned()).is_err());
+    }
+
+    #[test]
+    fn normalizes_payloads() {
+        let mut github_headers = headers("X-GitHub-Event", "pull_request");
+        github_headers.insert("X-GitHub-Delivery", HeaderValue::from_static("72d3162e"));
+        let payload = json!({"action": "opened", "sender": {"login": "octocat"}});
+        assert_eq!(
+            Provider::Github.normalize(payload.clone(), &github_headers),
+            Delivery {
+                id: Some("72d3162e".to_owned()),
+                event_type: Some("pull_request.opened".to_owned()),
+                actor: Some("octocat".to_owned()),
+                occurred_at: None,
+                payload,
+            }
+        );
+
+        let payload = json!({"id": "evt_1", "type": "charge.succeeded", "created": 1714564800});
+        assert_eq!(
+            Provider::Stripe.normalize(payload.clone(), &HeaderMap::new()),
+            Delivery {
+                id: Some("evt_1".to_owned()),
+                event_type: Some("charge.succeeded".to_owned()),
+                actor: None,
+                occurred_at: DateTime::from_timestamp(1_714_564_800, 0),
+                payload,
+            }
+        );
+
+        let payload = json!({"event": {
+            "id": "01DEN4HPBQAAAG05V5QQYBRZMF",
+            "event_type": "incident.triggered",
+            "occurred_at": "2024-05-01T12:00:00.000Z",
+            "agent": {"summary": "Tenex Engineer"},
+        }});
+        assert_eq!(
+            Provider::Pagerduty.normalize(payload.clone(), &HeaderMap::new()),
+            Delivery {
+                id: Some("01DEN4HPBQAAAG05V5QQYBRZMF".to_owned()),
+                event_type: Some("incident.triggered".to_owned()),
+                actor: Some("Tenex Engineer".to_owned()),
+                occurred_at: DateTime::from_timestamp(1_714_564_800, 0),
+                payload,
+            }
+        );
+    }
+}
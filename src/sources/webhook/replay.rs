@@ -0,0 +1,91 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// The IDs of the deliveries received within the replay window.
+pub(super) struct ReplayCache {
+    window: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    received: HashMap<String, Instant>,
+    /// The IDs in the order they were received, so that they are forgotten in that order.
+    order: VecDeque<(Instant, String)>,
+}
+
+impl ReplayCache {
+    pub(super) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Records the ID of a delivery, and returns `false` if it was already received within the
+    /// replay window.
+    pub(super) fn insert(&self, id: &str) -> bool {
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("mutex poisoned");
+        state.expire(now, self.window);
+
+        if state.received.contains_key(id) {
+            return false;
+        }
+        state.received.insert(id.to_owned(), now);
+        state.order.push_back((now, id.to_owned()));
+        true
+    }
+
+    /// Forgets the ID of a delivery, so that it can be received again.
+    pub(super) fn remove(&self, id: &str) {
+        self.state
+            .lock()
+            .expect("mutex poisoned")
+            .received
+            .remove(id);
+    }
+}
+
+impl State {
+    fn expire(&mut self, now: Instant, window: Duration) {
+        while let Some((received_at, _)) = self.order.front() {
+            if now.duration_since(*received_at) < window {
+                break;
+            }
+            let (received_at, id) = self.order.pop_front().expect("front exists");
+            // The ID may have been removed, and received again since.
+            if self.received.get(&id) == Some(&received_at) {
+                self.received.remove(&id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_replays_within_window() {
+        let cache = ReplayCache::new(Duration::from_secs(300));
+        assert!(cache.insert("a"));
+        assert!(cache.insert("b"));
+        assert!(!cache.insert("a"));
+
+        cache.remove("a");
+        assert!(cache.insert("a"));
+        assert!(!cache.insert("a"));
+    }
+
+    #[test]
+    fn forgets_ids_after_window() {
+        let cache = ReplayCache::new(Duration::ZERO);
+        assert!(cache.insert("a"));
+        assert!(cache.insert("a"));
+        assert_eq!(cache.state.lock().unwrap().order.len(), 1);
+    }
+}
@@ -0,0 +1,517 @@
+use std::{io, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio::sync::{mpsc, oneshot};
+use vector_lib::configurable::configurable_component;
+use vector_lib::finalizer::OrderedFinalizer;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_lib::lookup::{owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use crate::{
+    config::{
+        log_schema, DataType, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, LogEvent},
+    internal_events::{
+        EventsReceived, StreamClosedError, WindowsEventLogBookmarkError, WindowsEventLogReadError,
+        WindowsEventLogSubscribeError,
+    },
+    serde::{bool_or_struct, default_true},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+pub mod parser;
+mod subscription;
+
+use self::subscription::{Batch, Subscription};
+
+const BOOKMARK_FILENAME: &str = "bookmark.xml";
+
+/// How long to wait for new events before checking whether the source is shutting down.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const BACKOFF_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one channel must be specified"))]
+    NoChannels,
+}
+
+/// Configuration for the `windows_eventlog` source.
+#[configurable_component(source("windows_eventlog", "Collect logs from the Windows Event Log."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsEventLogConfig {
+    /// The channels to read events from.
+    ///
+    /// The available channels can be listed with `wevtutil enum-logs`.
+    #[configurable(metadata(
+        docs::examples = "System",
+        docs::examples = "Application",
+        docs::examples = "Security",
+        docs::examples = "Microsoft-Windows-Sysmon/Operational"
+    ))]
+    pub channels: Vec<String>,
+
+    /// An [XPath query][xpath] to select the events to read from each channel.
+    ///
+    /// By default, all events are read.
+    ///
+    /// [xpath]: https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations
+    #[serde(default = "default_query")]
+    #[configurable(metadata(
+        docs::examples = "*[System[(Level=1 or Level=2 or Level=3)]]",
+        docs::examples = "*[System[EventID=4624]]"
+    ))]
+    pub query: String,
+
+    /// Read the events that were logged before Vector started.
+    ///
+    /// By default, only the events that are logged from now on are read. Once a bookmark has been
+    /// persisted, the source always resumes after it.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    /// Render the message of events, using the message tables of their providers.
+    ///
+    /// Events whose provider is not installed on this host have no message.
+    #[serde(default = "default_true")]
+    pub render_message: bool,
+
+    /// Include the XML rendering of events in the `xml` field.
+    #[serde(default)]
+    pub include_xml: bool,
+
+    /// The directory used to persist the bookmark of the last event read.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "C:\\ProgramData\\vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
+    /// The event log is read in batches, and the bookmark is persisted at the end of each batch.
+    ///
+    /// This option limits the size of the batch.
+    #[serde(default = "default_batch_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub batch_size: usize,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_query() -> String {
+    "*".to_owned()
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
+impl Default for WindowsEventLogConfig {
+    fn default() -> Self {
+        Self {
+            channels: vec!["System".to_owned(), "Application".to_owned()],
+            query: default_query(),
+            read_existing_events: false,
+            render_message: true,
+            include_xml: false,
+            data_dir: None,
+            batch_size: default_batch_size(),
+            acknowledgements: Default::default(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(WindowsEventLogConfig);
+
+impl WindowsEventLogConfig {
+    /// Builds the [structured XML query][query] selecting the events of all channels.
+    ///
+    /// [query]: https://learn.microsoft.com/en-us/windows/win32/wes/queryschema-schema
+    fn structured_query(&self) -> String {
+        let query = escape_xml(&self.query);
+        let selects = self
+            .channels
+            .iter()
+            .map(|channel| {
+                let channel = escape_xml(channel);
+                format!(r#"<Select Path="{channel}">{query}</Select>"#)
+            })
+            .collect::<String>();
+        format!(r#"<QueryList><Query Id="0">{selects}</Query></QueryList>"#)
+    }
+
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let mut fields = Collection::empty()
+            .with_known("message", Kind::bytes().or_undefined())
+            .with_known("provider_name", Kind::bytes().or_undefined())
+            .with_known("event_id", Kind::integer().or_undefined())
+            .with_known("level", Kind::bytes().or_undefined())
+            .with_known("channel", Kind::bytes().or_undefined())
+            .with_known("computer", Kind::bytes().or_undefined())
+            .with_known("record_id", Kind::integer().or_undefined())
+            .with_known("task", Kind::integer().or_undefined())
+            .with_known("opcode", Kind::integer().or_undefined())
+            .with_known("keywords", Kind::bytes().or_undefined())
+            .with_known("process_id", Kind::integer().or_undefined())
+            .with_known("thread_id", Kind::integer().or_undefined())
+            .with_known("user_id", Kind::bytes().or_undefined())
+            .with_known("event_data", Kind::object(Collection::any()));
+        if self.include_xml {
+            fields = fields.with_known("xml", Kind::bytes());
+        }
+
+        Definition::new_with_default_metadata(Kind::object(fields), [log_namespace])
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                log_schema().host_key().cloned().map(LegacyKey::Overwrite),
+                &owned_value_path!("host"),
+                Kind::bytes().or_undefined(),
+                Some("host"),
+            )
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "windows_eventlog")]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+
+        let data_dir = cx
+            .globals
+            // source are only global, name can be used for subdir
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        let source = WindowsEventLogSource {
+            query: self.structured_query(),
+            read_existing_events: self.read_existing_events,
+            render_message: self.render_message,
+            include_xml: self.include_xml,
+            bookmark_path: data_dir.join(BOOKMARK_FILENAME),
+            batch_size: self.batch_size.max(1),
+            acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            log_namespace: cx.log_namespace(self.log_namespace),
+            out: cx.out,
+        };
+
+        Ok(Box::pin(source.run(cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::Log,
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+struct WindowsEventLogSource {
+    query: String,
+    read_existing_events: bool,
+    render_message: bool,
+    include_xml: bool,
+    bookmark_path: PathBuf,
+    batch_size: usize,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+}
+
+impl WindowsEventLogSource {
+    async fn run(mut self, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let bookmark = match tokio::fs::read_to_string(&self.bookmark_path).await {
+            Ok(bookmark) => Some(bookmark),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => {
+                emit!(WindowsEventLogBookmarkError {
+                    error,
+                    path: self.bookmark_path.display().to_string(),
+                });
+                return Err(());
+            }
+        };
+
+        // The subscription is driven from a blocking thread, as the Event Log API is synchronous.
+        let (tx, mut rx) = mpsc::channel(1);
+        let (started_tx, started_rx) = oneshot::channel();
+        let query = self.query.clone();
+        let (read_existing_events, render_message, batch_size) = (
+            self.read_existing_events,
+            self.render_message,
+            self.batch_size,
+        );
+        tokio::task::spawn_blocking(move || {
+            match Subscription::new(
+                &query,
+                bookmark.as_deref(),
+                read_existing_events,
+                render_message,
+            ) {
+                Ok(subscription) => {
+                    _ = started_tx.send(Ok(()));
+                    read_subscription(subscription, batch_size, tx);
+                }
+                Err(error) => _ = started_tx.send(Err(error)),
+            }
+        });
+        match started_rx.await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                emit!(WindowsEventLogSubscribeError { error });
+                return Err(());
+            }
+            Err(_) => return Err(()),
+        }
+
+        let (finalizer, mut ack_stream) =
+            OrderedFinalizer::<String>::maybe_new(self.acknowledgements, Some(shutdown.clone()));
+
+        let bytes_received = register!(BytesReceived::from(Protocol::from("windows_eventlog")));
+        let events_received = register!(EventsReceived);
+        let mut shutdown = shutdown.fuse();
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                entry = ack_stream.next() => if let Some((status, bookmark)) = entry {
+                    if status == BatchStatus::Delivered {
+                        self.persist_bookmark(&bookmark).await;
+                    }
+                },
+                batch = rx.recv() => {
+                    let Some(Batch { records, bookmark }) = batch else {
+                        break;
+                    };
+
+                    let (batch, receiver) =
+                        BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+                    let mut byte_size = 0;
+                    let events = records
+                        .into_iter()
+                        .map(|record| {
+                            byte_size += record.xml.len();
+                            let log = self.create_log_event(record);
+                            match &batch {
+                                Some(batch) => log.with_batch_notifier(batch),
+                                None => log,
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    drop(batch);
+
+                    bytes_received.emit(ByteSize(byte_size));
+                    events_received.emit(CountByteSize(
+                        events.len(),
+                        events.estimated_json_encoded_size_of(),
+                    ));
+
+                    let count = events.len();
+                    if self.out.send_batch(events).await.is_err() {
+                        emit!(StreamClosedError { count });
+                        break;
+                    }
+
+                    match (&finalizer, receiver) {
+                        (Some(finalizer), Some(receiver)) => finalizer.add(bookmark, receiver),
+                        _ => self.persist_bookmark(&bookmark).await,
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create_log_event(&self, record: subscription::Record) -> LogEvent {
+        let event = record.event;
+        let level = event.level_name();
+        let mut fields = ObjectMap::new();
+        let mut insert = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                fields.insert(key.into(), value);
+            }
+        };
+        insert("message", record.message.map(Into::into));
+        insert("provider_name", event.provider_name.map(Into::into));
+        insert("event_id", event.event_id.map(Into::into));
+        insert("level", level.map(Into::into));
+        insert("channel", event.channel.map(Into::into));
+        insert("computer", event.computer.clone().map(Into::into));
+        insert("record_id", event.record_id.map(Into::into));
+        insert("task", event.task.map(Into::into));
+        insert("opcode", event.opcode.map(Into::into));
+        insert("keywords", event.keywords.map(Into::into));
+        insert("process_id", event.process_id.map(Into::into));
+        insert("thread_id", event.thread_id.map(Into::into));
+        insert("user_id", event.user_id.map(Into::into));
+        insert("event_data", Some(Value::Object(event.event_data)));
+        if self.include_xml {
+            insert("xml", Some(record.xml.into()));
+        }
+
+        let mut log = LogEvent::from(Value::Object(fields));
+
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            WindowsEventLogConfig::NAME,
+            Utc::now(),
+        );
+        if let Some(timestamp) = event.timestamp {
+            self.log_namespace.insert_source_metadata(
+                WindowsEventLogConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                timestamp,
+            );
+        }
+        if let Some(computer) = event.computer {
+            self.log_namespace.insert_source_metadata(
+                WindowsEventLogConfig::NAME,
+                &mut log,
+                log_schema().host_key().map(LegacyKey::Overwrite),
+                path!("host"),
+                computer,
+            );
+        }
+
+        log
+    }
+
+    /// Persists the bookmark, replacing the previous one atomically.
+    async fn persist_bookmark(&self, bookmark: &str) {
+        let temp_path = self.bookmark_path.with_extension("xml.tmp");
+        let result = match tokio::fs::write(&temp_path, bookmark).await {
+            Ok(()) => tokio::fs::rename(&temp_path, &self.bookmark_path).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            emit!(WindowsEventLogBookmarkError {
+                error,
+                path: self.bookmark_path.display().to_string(),
+            });
+        }
+    }
+}
+
+/// Reads batches of events from the subscription until the receiver is dropped.
+fn read_subscription(mut subscription: Subscription, batch_size: usize, tx: mpsc::Sender<Batch>) {
+    while !tx.is_closed() {
+        match subscription.next_batch(batch_size) {
+            Ok(Some(batch)) => {
+                if tx.blocking_send(batch).is_err() {
+                    break;
+                }
+            }
+            Ok(None) => {
+                subscription.wait(POLL_INTERVAL);
+            }
+            Err(error) => {
+                emit!(WindowsEventLogReadError { error });
+                std::thread::sleep(BACKOFF_DURATION);
+            }
+        }
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    #[test]
+    fn builds_structured_query() {
+        let config: WindowsEventLogConfig = toml::from_str(
+            r#"
+            channels = ["System", "Microsoft-Windows-Sysmon/Operational"]
+            query = "*[System[(Level=1 or Level=2)]]"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.structured_query(),
+            concat!(
+                r#"<QueryList><Query Id="0">"#,
+                r#"<Select Path="System">*[System[(Level=1 or Level=2)]]</Select>"#,
+                r#"<Select Path="Microsoft-Windows-Sysmon/Operational">*[System[(Level=1 or Level=2)]]</Select>"#,
+                r#"</Query></QueryList>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn escapes_structured_query() {
+        let config: WindowsEventLogConfig = toml::from_str(
+            r#"
+            channels = ["Application"]
+            query = "*[EventData[Data[@Name='Path']='C:\\a&b']]"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config
+            .structured_query()
+            .contains(r#"*[EventData[Data[@Name='Path']='C:\a&amp;b']]"#));
+    }
+}
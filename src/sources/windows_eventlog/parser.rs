@@ -0,0 +1,220 @@
+//! Parsing of the XML rendering of Windows events.
+//!
+//! See <https://learn.microsoft.com/en-us/windows/win32/wes/eventschema-schema> for the schema.
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+use snafu::{ResultExt, Snafu};
+use vrl::value::{ObjectMap, Value};
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Invalid event XML: {}", source))]
+    Xml { source: roxmltree::Error },
+    #[snafu(display("The event XML has no `System` element"))]
+    MissingSystem,
+}
+
+/// A Windows event, parsed from its XML rendering.
+#[derive(Debug, Default, PartialEq)]
+pub struct WindowsEvent {
+    pub provider_name: Option<String>,
+    pub event_id: Option<i64>,
+    pub level: Option<i64>,
+    pub task: Option<i64>,
+    pub opcode: Option<i64>,
+    pub keywords: Option<String>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub record_id: Option<i64>,
+    pub process_id: Option<i64>,
+    pub thread_id: Option<i64>,
+    pub channel: Option<String>,
+    pub computer: Option<String>,
+    pub user_id: Option<String>,
+    /// The `EventData` or `UserData` of the event.
+    pub event_data: ObjectMap,
+}
+
+impl WindowsEvent {
+    pub fn parse(xml: &str) -> Result<Self, ParseError> {
+        let document = Document::parse(xml).context(XmlSnafu)?;
+        let root = document.root_element();
+        let system = child(root, "System").ok_or(ParseError::MissingSystem)?;
+
+        let text = |name| {
+            child(system, name)
+                .and_then(|node| node.text())
+                .map(str::trim)
+        };
+        let int = |name| text(name).and_then(|text| text.parse().ok());
+        let attribute =
+            |name, attribute| child(system, name).and_then(|node| node.attribute(attribute));
+
+        let mut event = Self {
+            provider_name: attribute("Provider", "Name").map(Into::into),
+            event_id: int("EventID"),
+            level: int("Level"),
+            task: int("Task"),
+            opcode: int("Opcode"),
+            keywords: text("Keywords").map(Into::into),
+            timestamp: attribute("TimeCreated", "SystemTime")
+                .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| time.with_timezone(&Utc)),
+            record_id: int("EventRecordID"),
+            process_id: attribute("Execution", "ProcessID").and_then(|id| id.parse().ok()),
+            thread_id: attribute("Execution", "ThreadID").and_then(|id| id.parse().ok()),
+            channel: text("Channel").map(Into::into),
+            computer: text("Computer").map(Into::into),
+            user_id: attribute("Security", "UserID").map(Into::into),
+            event_data: ObjectMap::new(),
+        };
+
+        if let Some(data) = child(root, "EventData") {
+            event.event_data = event_data(data);
+        } else if let Some(data) = child(root, "UserData").and_then(first_element) {
+            event.event_data = user_data(data);
+        }
+
+        Ok(event)
+    }
+
+    /// The name of the level of the event.
+    ///
+    /// See <https://learn.microsoft.com/en-us/windows/win32/wes/eventmanifestschema-leveltype-complextype>.
+    pub fn level_name(&self) -> Option<&'static str> {
+        Some(match self.level? {
+            0 | 4 => "information",
+            1 => "critical",
+            2 => "error",
+            3 => "warning",
+            5 => "verbose",
+            _ => return None,
+        })
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn first_element<'a, 'input>(node: Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    node.children().find(Node::is_element)
+}
+
+/// The `Data` elements of `EventData`, keyed by their `Name`, or by their position when unnamed.
+fn event_data(node: Node) -> ObjectMap {
+    node.children()
+        .filter(|child| child.is_element() && child.tag_name().name() == "Data")
+        .enumerate()
+        .map(|(index, data)| {
+            let name = data
+                .attribute("Name")
+                .map(Into::into)
+                .unwrap_or_else(|| index.to_string());
+            (name.into(), data.text().unwrap_or_default().into())
+        })
+        .collect()
+}
+
+/// The elements of `UserData`, which are defined by the provider, as nested maps.
+fn user_data(node: Node) -> ObjectMap {
+    node.children()
+        .filter(Node::is_element)
+        .map(|child| {
+            let value = if first_element(child).is_some() {
+                Value::Object(user_data(child))
+            } else {
+                child.text().unwrap_or_default().into()
+            };
+            (child.tag_name().name().into(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const EVENT: &str = r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-a5ba-3e3b0328c30d}" />
+    <EventID>4624</EventID>
+    <Version>2</Version>
+    <Level>0</Level>
+    <Task>12544</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8020000000000000</Keywords>
+    <TimeCreated SystemTime="2024-05-01T12:34:56.7890123Z" />
+    <EventRecordID>123456</EventRecordID>
+    <Correlation />
+    <Execution ProcessID="812" ThreadID="4732" />
+    <Channel>Security</Channel>
+    <Computer>host.example.com</Computer>
+    <Security UserID="S-1-5-18" />
+  </System>
+  <EventData>
+    <Data Name="SubjectUserName">HOST$</Data>
+    <Data Name="LogonType">5</Data>
+  </EventData>
+</Event>"#;
+
+    #[test]
+    fn parses_system_and_event_data() {
+        let event = WindowsEvent::parse(EVENT).unwrap();
+
+        assert_eq!(
+            event.provider_name.as_deref(),
+            Some("Microsoft-Windows-Security-Auditing")
+        );
+        assert_eq!(event.event_id, Some(4624));
+        assert_eq!(event.level_name(), Some("information"));
+        assert_eq!(event.task, Some(12544));
+        assert_eq!(event.keywords.as_deref(), Some("0x8020000000000000"));
+        assert_eq!(
+            event.timestamp,
+            Some(
+                Utc.with_ymd_and_hms(2024, 5, 1, 12, 34, 56).unwrap()
+                    + chrono::Duration::nanoseconds(789_012_300)
+            )
+        );
+        assert_eq!(event.record_id, Some(123456));
+        assert_eq!(event.process_id, Some(812));
+        assert_eq!(event.thread_id, Some(4732));
+        assert_eq!(event.channel.as_deref(), Some("Security"));
+        assert_eq!(event.computer.as_deref(), Some("host.example.com"));
+        assert_eq!(event.user_id.as_deref(), Some("S-1-5-18"));
+        assert_eq!(event.event_data["SubjectUserName"], "HOST$".into());
+        assert_eq!(event.event_data["LogonType"], "5".into());
+    }
+
+    #[test]
+    fn parses_unnamed_data_and_user_data() {
+        let event = WindowsEvent::parse(
+            r#"<Event><System><Level>2</Level></System><EventData><Data>first</Data><Data>second</Data></EventData></Event>"#,
+        )
+        .unwrap();
+        assert_eq!(event.level_name(), Some("error"));
+        assert_eq!(event.event_data["0"], "first".into());
+        assert_eq!(event.event_data["1"], "second".into());
+
+        let event = WindowsEvent::parse(
+            r#"<Event><System /><UserData><LogFileCleared><SubjectUserName>admin</SubjectUserName></LogFileCleared></UserData></Event>"#,
+        )
+        .unwrap();
+        assert_eq!(event.event_data["SubjectUserName"], "admin".into());
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        assert!(matches!(
+            WindowsEvent::parse("<Event>"),
+            Err(ParseError::Xml { .. })
+        ));
+        assert!(matches!(
+            WindowsEvent::parse("<Event />"),
+            Err(ParseError::MissingSystem)
+        ));
+    }
+}
@@ -0,0 +1,275 @@
+//! A pull subscription to the Windows Event Log, using the `EvtSubscribe` API.
+//!
+//! See <https://learn.microsoft.com/en-us/windows/win32/wes/subscribing-to-events>.
+use std::{collections::HashMap, time::Duration};
+
+use windows::{
+    core::{Error, Result, HSTRING, PCWSTR},
+    Win32::{
+        Foundation::{
+            CloseHandle, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE, WAIT_OBJECT_0,
+        },
+        System::{
+            EventLog::{
+                EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+                EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+                EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+                EvtSubscribeToFutureEvents, EvtUpdateBookmark, EVT_HANDLE, EVT_RENDER_FLAGS,
+            },
+            Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+        },
+    },
+};
+
+use super::parser::WindowsEvent;
+use crate::internal_events::WindowsEventLogParseError;
+
+/// An event read from the event log.
+pub struct Record {
+    pub event: WindowsEvent,
+    pub xml: String,
+    pub message: Option<String>,
+}
+
+/// A batch of events, with the bookmark pointing at the last one.
+pub struct Batch {
+    pub records: Vec<Record>,
+    pub bookmark: String,
+}
+
+/// A handle that is closed with `EvtClose` when dropped.
+struct Handle(EVT_HANDLE);
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        if !self.0.is_invalid() {
+            // SAFETY: The handle was returned by the Event Log API and is only closed once.
+            _ = unsafe { EvtClose(self.0) };
+        }
+    }
+}
+
+pub struct Subscription {
+    signal: HANDLE,
+    subscription: Handle,
+    bookmark: Handle,
+    render_message: bool,
+    publishers: HashMap<String, Option<Handle>>,
+}
+
+impl Subscription {
+    /// Subscribes to the events matching `query`, a structured XML query.
+    ///
+    /// The subscription starts after `bookmark` when one is given, or else at the oldest event if
+    /// `read_existing_events` is set, or else with the events that are logged from now on.
+    pub fn new(
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        render_message: bool,
+    ) -> Result<Self> {
+        // SAFETY: The arguments are valid for the duration of the calls, and the returned handles
+        // are owned by the subscription.
+        unsafe {
+            let signal = CreateEventW(None, true, true, PCWSTR::null())?;
+
+            let bookmark = match bookmark {
+                Some(bookmark) => Handle(EvtCreateBookmark(&HSTRING::from(bookmark))?),
+                None => Handle(EvtCreateBookmark(PCWSTR::null())?),
+            };
+            let flags = if bookmark_is_set(bookmark.0)? {
+                EvtSubscribeStartAfterBookmark
+            } else if read_existing_events {
+                EvtSubscribeStartAtOldestRecord
+            } else {
+                EvtSubscribeToFutureEvents
+            };
+
+            let subscription = EvtSubscribe(
+                EVT_HANDLE::default(),
+                signal,
+                PCWSTR::null(),
+                &HSTRING::from(query),
+                bookmark.0,
+                None,
+                None,
+                flags.0,
+            )
+            .map(Handle)
+            .inspect_err(|_| {
+                _ = CloseHandle(signal);
+            })?;
+
+            Ok(Self {
+                signal,
+                subscription,
+                bookmark,
+                render_message,
+                publishers: HashMap::new(),
+            })
+        }
+    }
+
+    /// Waits until new events may be available, or until the timeout expires.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        // SAFETY: The signal event is owned by the subscription.
+        unsafe { WaitForSingleObject(self.signal, timeout.as_millis() as u32) == WAIT_OBJECT_0 }
+    }
+
+    /// Reads up to `batch_size` events, returning `None` once no events are available.
+    pub fn next_batch(&mut self, batch_size: usize) -> Result<Option<Batch>> {
+        let mut handles = vec![0isize; batch_size];
+        let mut returned = 0;
+
+        // SAFETY: `handles` holds `batch_size` entries, and the first `returned` are owned handles.
+        let handles = unsafe {
+            if let Err(error) = EvtNext(self.subscription.0, &mut handles, 0, 0, &mut returned) {
+                if error.code() == ERROR_NO_MORE_ITEMS.to_hresult() {
+                    // The signal is set whenever events are available, so it is only reset once
+                    // all of them have been read.
+                    _ = ResetEvent(self.signal);
+                    return Ok(None);
+                }
+                return Err(error);
+            }
+            handles
+                .into_iter()
+                .take(returned as usize)
+                .map(|handle| Handle(EVT_HANDLE(handle)))
+                .collect::<Vec<_>>()
+        };
+
+        let mut records = Vec::with_capacity(handles.len());
+        for handle in &handles {
+            let xml = render(handle.0, EvtRenderEventXml)?;
+            match WindowsEvent::parse(&xml) {
+                Ok(event) => {
+                    let message = self
+                        .render_message
+                        .then(|| self.format_message(handle.0, event.provider_name.as_deref()))
+                        .flatten();
+                    records.push(Record {
+                        event,
+                        xml,
+                        message,
+                    });
+                }
+                Err(error) => emit!(WindowsEventLogParseError { error }),
+            }
+        }
+
+        let Some(last) = handles.last() else {
+            return Ok(None);
+        };
+        // SAFETY: Both handles are valid.
+        unsafe { EvtUpdateBookmark(self.bookmark.0, last.0)? };
+        let bookmark = render(self.bookmark.0, EvtRenderBookmark)?;
+
+        Ok(Some(Batch { records, bookmark }))
+    }
+
+    /// Formats the message of the event, using the message table of its provider.
+    ///
+    /// Returns `None` if the provider isn't installed on this host or has no message for the event.
+    fn format_message(&mut self, event: EVT_HANDLE, provider: Option<&str>) -> Option<String> {
+        let provider = provider?;
+        let publisher = self
+            .publishers
+            .entry(provider.to_owned())
+            .or_insert_with(|| {
+                // SAFETY: The provider name is valid for the duration of the call.
+                unsafe {
+                    EvtOpenPublisherMetadata(
+                        EVT_HANDLE::default(),
+                        &HSTRING::from(provider),
+                        PCWSTR::null(),
+                        0,
+                        0,
+                    )
+                }
+                .ok()
+                .map(Handle)
+            })
+            .as_ref()?;
+
+        let mut used = 0;
+        // SAFETY: The buffer is sized according to the length returned by the first call.
+        unsafe {
+            match EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                None,
+                EvtFormatMessageEvent.0,
+                None,
+                &mut used,
+            ) {
+                Err(error) if error.code() == ERROR_INSUFFICIENT_BUFFER.to_hresult() => {}
+                _ => return None,
+            }
+            let mut buffer = vec![0u16; used as usize];
+            EvtFormatMessage(
+                publisher.0,
+                event,
+                0,
+                None,
+                EvtFormatMessageEvent.0,
+                Some(&mut buffer),
+                &mut used,
+            )
+            .ok()?;
+            Some(from_wide(&buffer)).filter(|message| !message.is_empty())
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // SAFETY: The signal event is owned by the subscription.
+        _ = unsafe { CloseHandle(self.signal) };
+    }
+}
+
+/// Whether the bookmark points at an event, rather than being newly created.
+fn bookmark_is_set(bookmark: EVT_HANDLE) -> Result<bool> {
+    Ok(render(bookmark, EvtRenderBookmark)?.contains("<Bookmark "))
+}
+
+/// Renders the event or bookmark as XML.
+fn render(fragment: EVT_HANDLE, flags: EVT_RENDER_FLAGS) -> Result<String> {
+    let mut used = 0;
+    let mut count = 0;
+    // SAFETY: The buffer is sized according to the size in bytes returned by the first call.
+    unsafe {
+        match EvtRender(
+            EVT_HANDLE::default(),
+            fragment,
+            flags.0,
+            0,
+            None,
+            &mut used,
+            &mut count,
+        ) {
+            Err(error) if error.code() == ERROR_INSUFFICIENT_BUFFER.to_hresult() => {}
+            Err(error) => return Err(error),
+            Ok(()) => return Err(Error::empty()),
+        }
+        let mut buffer = vec![0u16; (used as usize).div_ceil(2)];
+        EvtRender(
+            EVT_HANDLE::default(),
+            fragment,
+            flags.0,
+            used,
+            Some(buffer.as_mut_ptr().cast()),
+            &mut used,
+            &mut count,
+        )?;
+        Ok(from_wide(&buffer))
+    }
+}
+
+/// Converts a NUL-terminated wide string.
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
@@ -4,8 +4,8 @@ use std::{
     marker::PhantomData,
     str::FromStr,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Mutex, MutexGuard, OnceLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock, RwLock,
     },
 };
 
@@ -16,11 +16,11 @@ use tokio::sync::{
     oneshot,
 };
 use tokio_stream::wrappers::BroadcastStream;
-use tracing::{Event, Subscriber};
+use tracing::{subscriber::Interest, Event, Level, Metadata, Subscriber};
 use tracing_limit::RateLimitedLayer;
 use tracing_subscriber::{
-    filter::LevelFilter,
-    layer::{Context, SubscriberExt},
+    filter::{FilterExt, LevelFilter, Targets},
+    layer::{Context, Filter, SubscriberExt},
     registry::LookupSpan,
     util::SubscriberInitExt,
     Layer,
@@ -52,6 +52,16 @@ static SUBSCRIBERS: Mutex<Option<Vec<oneshot::Sender<Vec<LogEvent>>>>> =
 /// has been initialized.
 static SENDER: OnceLock<Sender<LogEvent>> = OnceLock::new();
 
+/// TARGETS holds the log levels that `tracing` was initialized with, so that subscribers can tell apart the internal
+/// log events that are only broadcast because of an elevation.
+static TARGETS: OnceLock<Targets> = OnceLock::new();
+
+/// ELEVATIONS contains the elevations registered by subscribers, which broadcast internal log events regardless of the
+/// log levels that `tracing` was initialized with.
+static ELEVATIONS: RwLock<Vec<Elevation>> = RwLock::new(Vec::new());
+
+static NEXT_ELEVATION_ID: AtomicUsize = AtomicUsize::new(0);
+
 fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
@@ -64,9 +74,11 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
     let metrics_layer =
         metrics_layer_enabled().then(|| MetricsLayer::new().with_filter(LevelFilter::INFO));
 
+    _ = TARGETS.set(fmt_filter.clone());
+
     let broadcast_layer = RateLimitedLayer::new(BroadcastLayer::new())
         .with_default_limit(internal_log_rate_limit)
-        .with_filter(fmt_filter.clone());
+        .with_filter(fmt_filter.clone().or(ElevationFilter));
 
     let subscriber = tracing_subscriber::registry()
         .with(metrics_layer)
@@ -285,6 +297,112 @@ impl TraceSubscription {
     }
 }
 
+/// A predicate selecting the internal log events to broadcast regardless of the log levels that `tracing` was
+/// initialized with, given the metadata of an event and the ID of the component that it was emitted by, if any.
+pub type ElevationFn = dyn Fn(&Metadata<'_>, Option<&str>) -> bool + Send + Sync;
+
+struct Elevation {
+    id: usize,
+    max_level: LevelFilter,
+    predicate: Arc<ElevationFn>,
+}
+
+/// Registers an elevation, which broadcasts the internal log events selected by `predicate` regardless of the log
+/// levels that `tracing` was initialized with, as long as they are no more verbose than `max_level`.
+///
+/// The elevation is removed when the returned guard is dropped. Callers must still filter the events that they receive,
+/// as the events selected by the elevations of other callers are broadcast to every subscriber, and can be told apart
+/// with [`is_enabled_by_log_levels`].
+pub fn elevate(max_level: LevelFilter, predicate: Arc<ElevationFn>) -> ElevationGuard {
+    let id = NEXT_ELEVATION_ID.fetch_add(1, Ordering::Relaxed);
+    ELEVATIONS
+        .write()
+        .expect("Couldn't acquire lock on elevations")
+        .push(Elevation {
+            id,
+            max_level,
+            predicate,
+        });
+    // The interest of callsites that are more verbose than the log levels is cached as never.
+    tracing_core::callsite::rebuild_interest_cache();
+    ElevationGuard { id }
+}
+
+/// Removes an elevation when dropped.
+pub struct ElevationGuard {
+    id: usize,
+}
+
+impl Drop for ElevationGuard {
+    fn drop(&mut self) {
+        ELEVATIONS
+            .write()
+            .expect("Couldn't acquire lock on elevations")
+            .retain(|elevation| elevation.id != self.id);
+        tracing_core::callsite::rebuild_interest_cache();
+    }
+}
+
+/// Returns whether an internal log event is enabled by the log levels that `tracing` was initialized with, rather than
+/// only being broadcast because of an elevation.
+pub fn is_enabled_by_log_levels(target: &str, level: &Level) -> bool {
+    TARGETS
+        .get()
+        .map_or(true, |targets| targets.would_enable(target, level))
+}
+
+fn max_elevated_level() -> LevelFilter {
+    ELEVATIONS
+        .read()
+        .expect("Couldn't acquire lock on elevations")
+        .iter()
+        .map(|elevation| elevation.max_level)
+        .max()
+        .unwrap_or(LevelFilter::OFF)
+}
+
+/// Enables the internal log events selected by the registered elevations.
+struct ElevationFilter;
+
+impl<S> Filter<S> for ElevationFilter
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        let elevations = ELEVATIONS
+            .read()
+            .expect("Couldn't acquire lock on elevations");
+        let mut elevations = elevations
+            .iter()
+            .filter(|elevation| elevation.max_level >= *meta.level())
+            .peekable();
+        if elevations.peek().is_none() {
+            return false;
+        }
+
+        let component_id = cx.lookup_current().and_then(|span| {
+            span.scope().find_map(|span| {
+                let extensions = span.extensions();
+                let component_id = extensions.get::<SpanFields>()?.0.get("component_id")?;
+                Some(component_id.to_string_lossy().into_owned())
+            })
+        });
+        elevations.any(|elevation| (elevation.predicate)(meta, component_id.as_deref()))
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        if max_elevated_level() >= *meta.level() {
+            Interest::sometimes()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(max_elevated_level())
+    }
+}
+
 struct BroadcastLayer<S> {
     _subscriber: PhantomData<S>,
 }
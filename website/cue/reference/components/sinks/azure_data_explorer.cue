@@ -0,0 +1,95 @@
+package metadata
+
+components: sinks: azure_data_explorer: {
+	title: "Azure Data Explorer"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    10_000_000
+				timeout_secs: 30.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.azure_data_explorer
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Data Explorer queued ingestion"
+							url:   urls.azure_data_explorer_queued_ingestion
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The identity that Vector runs as must have the `Database Ingestor` or `Table Ingestor`
+				[role](\(urls.azure_data_explorer_permissions)) on the target database or table.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_data_explorer.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				Vector authenticates with Microsoft Entra ID as the service principal configured in
+				`client_credentials`, using either a client secret or a client certificate. Otherwise, it
+				tries in order the credentials set in environment variables, the managed identity of the
+				host, and the `az` CLI.
+				"""
+		}
+		queued_ingestion: {
+			title: "Queued ingestion"
+			body:  """
+				Each batch is written as a gzipped blob of newline delimited JSON objects to the
+				temporary storage of the cluster, and then queued for
+				[ingestion](\(urls.azure_data_explorer_queued_ingestion)). Events are acknowledged
+				once they are queued, and the cluster ingests them according to the ingestion
+				batching policy of the table.
+				"""
+		}
+	}
+}
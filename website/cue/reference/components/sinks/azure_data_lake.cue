@@ -0,0 +1,99 @@
+package metadata
+
+components: sinks: azure_data_lake: {
+	title: "Azure Data Lake Storage"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    10_000_000
+				timeout_secs: 300.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip", "snappy", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			request: {
+				enabled:        true
+				rate_limit_num: 250
+				headers:        false
+			}
+			tls: enabled: false
+			to: {
+				service: services.azure_data_lake_storage
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Data Lake Storage Gen2 REST API"
+							url:   urls.azure_data_lake_storage_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The storage account must have a [hierarchical namespace](\(urls.azure_data_lake_storage_hns))
+				enabled.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_data_lake.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		directory_partitioning: {
+			title: "Directory partitioning"
+			body:  """
+				Events are batched by the rendered `path_prefix`, and each batch is written as a file
+				in the directory that the prefix names. Directories are created as needed, so a prefix
+				such as `year=%Y/month=%m/day=%d/` lays out files in the Hive-style partitions that
+				query engines such as Azure Synapse and Databricks prune on.
+
+				A batch is written once it reaches `batch.max_bytes` or `batch.max_events`, or after
+				`batch.timeout_secs`, whichever comes first.
+				"""
+		}
+		file_formats: {
+			title: "File formats"
+			body:  """
+				Files are newline-delimited JSON by default, compressed according to `compression`.
+				With `encoding.codec` set to `parquet`, each batch is written as a Parquet file whose
+				columns are described by `encoding.schema` and filled from the top-level fields of the
+				events of the same name.
+				"""
+		}
+	}
+}
@@ -0,0 +1,119 @@
+package metadata
+
+components: sinks: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "dynamic"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_events:   null
+				max_bytes:    null
+				timeout_secs: null
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "lz4", "snappy", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: services.azure_event_hubs
+				interface: {
+					socket: {
+						api: {
+							title: "Kafka protocol"
+							url:   urls.azure_event_hubs_kafka
+						}
+						direction: "outgoing"
+						port:      9093
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The Event Hubs namespace must use the Standard tier or above, since the
+				[Basic tier](\(urls.azure_event_hubs_tiers)) does not expose the Kafka endpoint.
+				""",
+		]
+		notices: []
+		warnings: []
+	}
+
+	configuration: base.components.sinks.azure_event_hubs.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		kafka_endpoint: {
+			title: "Kafka endpoint"
+			body:  """
+				The sink writes to the [Kafka-compatible endpoint](\(urls.azure_event_hubs_kafka))
+				of the Event Hubs namespace, authenticating with the configured connection string or
+				with Azure Active Directory. Events that share a rendered `partition_key` are written to
+				the same partition of the Event Hub.
+				"""
+		}
+		azure_ad: {
+			title: "Azure Active Directory authentication"
+			body:  """
+				Without a connection string, the sink authenticates with Azure Active Directory, and
+				requires the `namespace` and `event_hub_name` options. It signs in as the service
+				principal set in `client_credentials`, using either a client secret or a client
+				certificate. Otherwise, it tries in order the credentials set in environment variables,
+				the managed identity of the host, and the `az` CLI. OAuth bearer tokens for the
+				namespace are refreshed before they expire.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
+		kafka_queue_messages_bytes:          components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
+		kafka_requests_total:                components.sources.internal_metrics.output.metrics.kafka_requests_total
+		kafka_requests_bytes_total:          components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
+		kafka_responses_total:               components.sources.internal_metrics.output.metrics.kafka_responses_total
+		kafka_responses_bytes_total:         components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
+		kafka_produced_messages_total:       components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
+		kafka_produced_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
+	}
+}
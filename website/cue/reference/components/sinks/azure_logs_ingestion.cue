@@ -0,0 +1,96 @@
+package metadata
+
+components: sinks: azure_logs_ingestion: {
+	title: "Azure Monitor Logs Ingestion"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled:   true
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_monitor_logs
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Monitor Logs Ingestion API"
+							url:   urls.azure_logs_ingestion_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The identity that Vector runs as must have the `Monitoring Metrics Publisher` role on
+				the data collection rule.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_logs_ingestion.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				Vector authenticates with Microsoft Entra ID as the service principal configured in
+				`client_credentials`, using either a client secret or a client certificate. Otherwise, it
+				tries in order the credentials set in environment variables, the managed identity of the
+				host, and the `az` CLI.
+				"""
+		}
+		batching: {
+			title: "Batching"
+			body:  """
+				Batches are sent gzipped as a JSON array of records. The
+				[Logs Ingestion API](\(urls.azure_logs_ingestion_api)) rejects calls larger than 1 MB,
+				so batches are capped at that size.
+				"""
+		}
+	}
+}
@@ -57,7 +57,14 @@ components: sinks: azure_monitor_logs: {
 
 	support: {
 		requirements: []
-		warnings: []
+		warnings: [
+			"""
+				The HTTP Data Collector API that this sink uses is
+				[deprecated](\(urls.azure_monitor_logs_migration)). Use the
+				[`azure_logs_ingestion` sink](\(urls.vector_sinks)/azure_logs_ingestion/) for new
+				deployments.
+				""",
+		]
 		notices: []
 	}
 
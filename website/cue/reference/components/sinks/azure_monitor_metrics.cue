@@ -0,0 +1,120 @@
+package metadata
+
+components: sinks: azure_monitor_metrics: {
+	title: "Azure Monitor Metrics"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_monitor_metrics
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Monitor custom metrics REST API"
+							url:   urls.azure_monitor_custom_metrics_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The identity that Vector runs as must have the `Monitoring Metrics Publisher` role on
+				the resource that metrics are published for.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_monitor_metrics.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    false
+			set:          true
+			summary:      false
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				Vector authenticates with Microsoft Entra ID as the service principal configured in
+				`client_credentials`, using either a client secret or a client certificate. Otherwise, it
+				tries in order the credentials set in environment variables, the managed identity of the
+				host, and the `az` CLI.
+				"""
+		}
+		aggregation: {
+			title: "Aggregation"
+			body:  """
+				Custom metrics are stored per minute, as the minimum, maximum, sum, and count of the
+				values of each series. Vector aggregates the metrics of a batch that fall within the
+				same minute into a single document per metric name and namespace. Counters are sent
+				as increments, gauges as their latest value, sets as the number of their values, and
+				distributions as the summary of their samples.
+
+				Aggregated histograms, aggregated summaries, and sketches can't be summarized this
+				way, so they are dropped and counted in the `component_discarded_events_total`
+				internal metric.
+				"""
+		}
+		dimensions: {
+			title: "Dimensions"
+			body:  """
+				Metric tags are sent as dimensions. A custom metric can have at most 10 dimensions,
+				so further tags are left out, in the order of their names. Tags with several values
+				are sent with their last value.
+				"""
+		}
+	}
+}
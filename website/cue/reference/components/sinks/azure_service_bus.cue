@@ -0,0 +1,97 @@
+package metadata
+
+components: sinks: azure_service_bus: {
+	title: "Azure Service Bus"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    262_144
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: true
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.azure_service_bus
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Service Bus REST API"
+							url:   urls.azure_service_bus_batch
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_service_bus.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		batching: {
+			title: "Batching"
+			body:  """
+				Events are sent with the [batch send](\(urls.azure_service_bus_batch)) operation, one
+				message per event. Batches are capped at 256 KiB, the largest batch accepted by the
+				Standard tier, counting the JSON envelope of each message.
+				"""
+		}
+		message_bodies: {
+			title: "Message bodies"
+			body:  """
+				The batch format only carries text message bodies. Bodies produced by binary codecs
+				(`avro`, `native` and `protobuf`), or that are not valid UTF-8, are sent base64
+				encoded, and their messages have the `ContentEncoding` user property set to `base64`
+				so that receivers can decode them. Other bodies are sent as they are, without the
+				property.
+				"""
+		}
+		sessions: {
+			title: "Sessions"
+			body:  """
+				Queues and subscriptions that require [sessions](\(urls.azure_service_bus_sessions))
+				reject messages without a session ID. Set `session_id` to a template that renders the
+				session of each event; events for which the template cannot be rendered are dropped.
+				"""
+		}
+	}
+}
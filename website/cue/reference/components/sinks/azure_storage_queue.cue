@@ -0,0 +1,82 @@
+package metadata
+
+components: sinks: azure_storage_queue: {
+	title: "Azure Queue Storage"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: true
+			}
+			proxy: enabled: false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.azure_storage_queue
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Queue Storage REST API"
+							url:   urls.azure_storage_queue_put_message
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_storage_queue.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body:  """
+				Each event is encoded and [put on the queue](\(urls.azure_storage_queue_put_message))
+				as a message of its own. Queue messages can be at most 64 KiB, including the Base64
+				encoding, so events that encode to larger messages are rejected by the service.
+				"""
+		}
+		azure_functions: {
+			title: "Triggering Azure Functions"
+			body:  """
+				[Queue triggers](\(urls.azure_functions_queue_trigger)) of Azure Functions expect
+				Base64-encoded messages by default, which is what this sink writes unless
+				`base64_encode` is disabled. Use `visibility_timeout_secs` to delay processing of
+				messages and `message_ttl_secs` to discard messages that were not processed in time.
+				"""
+		}
+	}
+}
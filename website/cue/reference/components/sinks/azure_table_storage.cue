@@ -0,0 +1,105 @@
+package metadata
+
+components: sinks: azure_table_storage: {
+	title: "Azure Table Storage"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_194_304
+				max_events:   100
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.azure_table_storage
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Table Storage REST API"
+							url:   urls.azure_table_storage_batch
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_table_storage.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		entities: {
+			title: "Entities"
+			body:  """
+				Each event is written as an entity, with its `PartitionKey` and `RowKey` rendered
+				from the `partition_key` and `row_key` templates. Events whose keys can't be
+				rendered, or contain [characters that are not allowed](\(urls.azure_table_storage_data_model)),
+				are dropped.
+
+				The top-level fields of the event become the properties of the entity. Integers and
+				timestamps are written as `Edm.Int64` and `Edm.DateTime` properties, objects and
+				arrays are written as JSON strings, and null fields are left out.
+				Property names must be C# identifiers of up to 255 characters, so events with fields
+				such as `@timestamp` or `host.name` are rejected. They can be renamed, for example
+				with a `remap` transform, before reaching the sink.
+				"""
+		}
+		batch_transactions: {
+			title: "Batch transactions"
+			body:  """
+				Entities are written in [batch transactions](\(urls.azure_table_storage_batch)) of
+				up to 100 entities, each of which only holds entities of a single partition. A
+				transaction succeeds or fails as a whole. As a transaction can't hold more than one
+				operation on the same entity, events of a batch with the same keys are folded into one
+				entity, as writing them one after the other would: with `insert_or_replace` the last
+				event is written, and with `insert_or_merge` the properties of the events are merged
+				in order. With `insert`, only the first event is written, and the others are rejected.
+
+				When the service fails a transaction because of one of its entities, such as an entity
+				that already exists with the `insert` write mode, the event of that entity is rejected
+				and the other entities of the transaction are written again without it. With the
+				default `insert_or_replace` write mode, retried transactions are idempotent.
+				"""
+		}
+	}
+}
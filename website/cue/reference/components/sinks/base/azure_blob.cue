@@ -111,6 +111,92 @@ base: components: sinks: azure_blob: configuration: {
 		required: false
 		type: string: syntax: "strftime"
 	}
+	block_upload: {
+		description: """
+			Staged block upload options.
+
+			Blobs larger than `block_size` are uploaded as [staged blocks][put_block], which are committed
+			with a [block list][put_block_list] once all of them are uploaded. A block that fails to upload
+			is retried on its own, so large blobs don't have to be sent again in full.
+
+			[put_block]: https://learn.microsoft.com/en-us/rest/api/storageservices/put-block
+			[put_block_list]: https://learn.microsoft.com/en-us/rest/api/storageservices/put-block-list
+			"""
+		required: false
+		type: object: options: {
+			block_size: {
+				description: """
+					The size of the blocks that blobs are staged in.
+
+					Blobs up to this size are uploaded with a single request. Blocks can be at most 4000 MiB.
+					"""
+				required: false
+				type: uint: {
+					default: 4194304
+					unit:    "bytes"
+				}
+			}
+			max_concurrent_blocks: {
+				description: "The maximum number of blocks of a blob that are uploaded concurrently."
+				required:    false
+				type: uint: default: 4
+			}
+		}
+	}
+	client_credentials: {
+		description: """
+			The credentials of an Azure Active Directory service principal.
+
+			The service principal signs in with either a client secret or a client certificate. If unset,
+			the [default credential chain][default_chain] is used, which tries environment variables,
+			workload identity, managed identity, and the Azure CLI in turn.
+
+			[default_chain]: https://learn.microsoft.com/en-us/azure/developer/intro/passwordless-overview#use-defaultazurecredential
+			"""
+		required: false
+		type: object: options: {
+			client_certificate_password: {
+				description: """
+					The password of the client certificate.
+
+					This is the password of the PKCS#12 archive, or of the encrypted private key in a PEM file.
+					"""
+				required: false
+				type: string: examples: ["${AZURE_CLIENT_CERTIFICATE_PASSWORD}"]
+			}
+			client_certificate_path: {
+				description: """
+					The path to a client certificate of the service principal.
+
+					The certificate is either a PKCS#12 (PFX) archive, or a PEM file with the certificate, its
+					private key, and optionally the rest of its chain.
+
+					Either this or `client_secret` must be set.
+					"""
+				required: false
+				type: string: examples: ["/etc/vector/service-principal.pfx", "/etc/vector/service-principal.pem"]
+			}
+			client_id: {
+				description: "The client ID of the service principal."
+				required:    true
+				type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+			}
+			client_secret: {
+				description: """
+					A client secret of the service principal.
+
+					Either this or `client_certificate_path` must be set.
+					"""
+				required: false
+				type: string: examples: ["${AZURE_CLIENT_SECRET}"]
+			}
+			tenant_id: {
+				description: "The ID of the Azure Active Directory tenant of the service principal."
+				required:    true
+				type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+			}
+		}
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -679,7 +765,8 @@ base: components: sinks: azure_blob: configuration: {
 		description: """
 			The Azure Blob Storage Account name.
 
-			Attempts to load credentials for the account in the following ways, in order:
+			Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+			for the account in the following ways, in order:
 
 			- read from environment variables ([more information][env_cred_docs])
 			- looks for a [Managed Identity][managed_ident_docs]
@@ -0,0 +1,487 @@
+package metadata
+
+base: components: sinks: azure_data_lake: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, waits for events to be acknowledged by **all
+				connected** sinks before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	}
+	batch: {
+		description: "Event batching behavior."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: """
+					The maximum size of a batch that is processed by a sink.
+
+					This is based on the uncompressed size of the batched events, before they are
+					serialized/compressed.
+					"""
+				required: false
+				type: uint: {
+					default: 10000000
+					unit:    "bytes"
+				}
+			}
+			max_events: {
+				description: "The maximum size of a batch before it is flushed."
+				required:    false
+				type: uint: unit: "events"
+			}
+			timeout_secs: {
+				description: "The maximum age of a batch before it is flushed."
+				required:    false
+				type: float: {
+					default: 300.0
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	client_credentials: {
+		description: """
+			The credentials of an Azure Active Directory service principal.
+
+			The service principal signs in with either a client secret or a client certificate. If unset,
+			the [default credential chain][default_chain] is used, which tries environment variables,
+			workload identity, managed identity, and the Azure CLI in turn.
+
+			[default_chain]: https://learn.microsoft.com/en-us/azure/developer/intro/passwordless-overview#use-defaultazurecredential
+			"""
+		required: false
+		type: object: options: {
+			client_certificate_password: {
+				description: """
+					The password of the client certificate.
+
+					This is the password of the PKCS#12 archive, or of the encrypted private key in a PEM file.
+					"""
+				required: false
+				type: string: examples: ["${AZURE_CLIENT_CERTIFICATE_PASSWORD}"]
+			}
+			client_certificate_path: {
+				description: """
+					The path to a client certificate of the service principal.
+
+					The certificate is either a PKCS#12 (PFX) archive, or a PEM file with the certificate, its
+					private key, and optionally the rest of its chain.
+
+					Either this or `client_secret` must be set.
+					"""
+				required: false
+				type: string: examples: ["/etc/vector/service-principal.pfx", "/etc/vector/service-principal.pem"]
+			}
+			client_id: {
+				description: "The client ID of the service principal."
+				required:    true
+				type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+			}
+			client_secret: {
+				description: """
+					A client secret of the service principal.
+
+					Either this or `client_certificate_path` must be set.
+					"""
+				required: false
+				type: string: examples: ["${AZURE_CLIENT_SECRET}"]
+			}
+			tenant_id: {
+				description: "The ID of the Azure Active Directory tenant of the service principal."
+				required:    true
+				type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+			}
+		}
+	}
+	compression: {
+		description: """
+			Compression to apply to newline-delimited JSON files.
+
+			This is ignored for Parquet files, whose pages are always compressed with Snappy.
+			"""
+		required: false
+		type: string: {
+			default: "gzip"
+			enum: {
+				gzip: """
+					[Gzip][gzip] compression.
+
+					[gzip]: https://www.gzip.org/
+					"""
+				none: "No compression."
+				snappy: """
+					[Snappy][snappy] compression.
+
+					[snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+					"""
+				zlib: """
+					[Zlib][zlib] compression.
+
+					[zlib]: https://zlib.net/
+					"""
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
+			}
+		}
+	}
+	connection_string: {
+		description: """
+			The Azure Storage Account connection string.
+
+			Authentication with access key is the only supported authentication method.
+
+			Either `storage_account`, or this field, must be specified.
+			"""
+		required: false
+		type: string: examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+	}
+	encoding: {
+		description: "Configures how events are encoded into files."
+		required:    false
+		type: object: options: {
+			codec: {
+				description: "The format of the written files."
+				required:    false
+				type: string: {
+					default: "ndjson"
+					enum: {
+						ndjson: """
+															Writes files of [newline-delimited JSON][ndjson], with one event per line.
+
+															[ndjson]: https://github.com/ndjson/ndjson-spec
+															"""
+						parquet: """
+															Writes [Apache Parquet][parquet] files, with one row per event.
+
+															[parquet]: https://parquet.apache.org/
+															"""
+					}
+				}
+			}
+			except_fields: {
+				description: "List of fields that are excluded from the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			only_fields: {
+				description: "List of fields that are included in the encoded event."
+				required:    false
+				type: array: items: type: string: {}
+			}
+			schema: {
+				description: """
+					The schema of the Parquet files, in the Parquet message type syntax.
+
+					Each column is filled from the top-level event field of the same name. Only flat schemas of
+					`BOOLEAN`, `INT32`, `INT64`, `FLOAT`, `DOUBLE`, and `BINARY` columns are supported, and
+					`INT64` columns annotated as timestamps are filled from timestamp fields.
+
+					Events without a value of the right type for a `REQUIRED` column are dropped, so columns for
+					fields that are not always present must be `OPTIONAL`.
+					"""
+				relevant_when: "codec = \"parquet\""
+				required:      true
+				type: string: examples: ["message log { REQUIRED INT64 timestamp (TIMESTAMP_MILLIS); OPTIONAL BINARY message (UTF8); }"]
+			}
+			timestamp_format: {
+				description: "Format used for timestamp fields."
+				required:    false
+				type: string: enum: {
+					rfc3339:    "Represent the timestamp as a RFC 3339 timestamp."
+					unix:       "Represent the timestamp as a Unix timestamp."
+					unix_float: "Represent the timestamp as a Unix timestamp in floating point."
+					unix_ms:    "Represent the timestamp as a Unix timestamp in milliseconds."
+					unix_ns:    "Represent the timestamp as a Unix timestamp in nanoseconds."
+					unix_us:    "Represent the timestamp as a Unix timestamp in microseconds"
+				}
+			}
+		}
+	}
+	endpoint: {
+		description: """
+			The Data Lake Storage (DFS) endpoint to use instead of the one derived from the account name.
+
+			This may only be used with `storage_account` and is ignored when used with
+			`connection_string`.
+			"""
+		required: false
+		type: string: examples: ["https://mylogstorage.dfs.core.usgovcloudapi.net/", "https://mylogstorage.dfs.core.windows.net/"]
+	}
+	file_append_uuid: {
+		description: """
+			Whether or not to append a UUID v4 token to the file name.
+
+			This ensures there are no name collisions between files written at the same time.
+			"""
+		required: false
+		type: bool: {}
+	}
+	file_system: {
+		description: "The name of the file system (container) to write files to."
+		required:    true
+		type: string: examples: ["my-logs"]
+	}
+	file_time_format: {
+		description: """
+			The timestamp format for the time component of the file name.
+
+			By default, file names are the time at which the file is written to Azure Data Lake
+			Storage, in seconds since the Unix epoch.
+
+			Supports the common [`strftime`][chrono_strftime_specifiers] specifiers found in most
+			languages.
+
+			[chrono_strftime_specifiers]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+			"""
+		required: false
+		type: string: syntax: "strftime"
+	}
+	path_prefix: {
+		description: """
+			A prefix to apply to the path of all files.
+
+			The prefix is rendered for each event, so it can partition files into a hierarchy of
+			directories built from event fields and from the event timestamp, such as the Hive-style
+			partitions read by most query engines. It must end in `/` to act as a directory path. A
+			trailing `/` is **not** automatically added.
+			"""
+		required: false
+		type: string: {
+			default: "year=%Y/month=%m/day=%d/"
+			examples: ["year=%Y/month=%m/day=%d/", "application={{ application_name }}/date=%F/hour=%H/"]
+			syntax: "template"
+		}
+	}
+	request: {
+		description: """
+			Middleware settings for outbound requests.
+
+			Various settings can be configured, such as concurrency and rate limits, timeouts, retry behavior, etc.
+
+			Note that the retry backoff policy follows the Fibonacci sequence.
+			"""
+		required: false
+		type: object: options: {
+			adaptive_concurrency: {
+				description: """
+					Configuration of adaptive concurrency parameters.
+
+					These parameters typically do not require changes from the default, and incorrect values can lead to meta-stable or
+					unstable performance and sink behavior. Proceed with caution.
+					"""
+				required: false
+				type: object: options: {
+					decrease_ratio: {
+						description: """
+																The fraction of the current value to set the new concurrency limit when decreasing the limit.
+
+																Valid values are greater than `0` and less than `1`. Smaller values cause the algorithm to scale back rapidly
+																when latency increases.
+
+																Note that the new limit is rounded down after applying this ratio.
+																"""
+						required: false
+						type: float: default: 0.9
+					}
+					ewma_alpha: {
+						description: """
+																The weighting of new measurements compared to older measurements.
+
+																Valid values are greater than `0` and less than `1`.
+
+																ARC uses an exponentially weighted moving average (EWMA) of past RTT measurements as a reference to compare with
+																the current RTT. Smaller values cause this reference to adjust more slowly, which may be useful if a service has
+																unusually high response variability.
+																"""
+						required: false
+						type: float: default: 0.4
+					}
+					initial_concurrency: {
+						description: """
+																The initial concurrency limit to use. If not specified, the initial limit will be 1 (no concurrency).
+
+																It is recommended to set this value to your service's average limit if you're seeing that it takes a
+																long time to ramp up adaptive concurrency after a restart. You can find this value by looking at the
+																`adaptive_concurrency_limit` metric.
+																"""
+						required: false
+						type: uint: default: 1
+					}
+					max_concurrency_limit: {
+						description: """
+																The maximum concurrency limit.
+
+																The adaptive request concurrency limit will not go above this bound. This is put in place as a safeguard.
+																"""
+						required: false
+						type: uint: default: 200
+					}
+					rtt_deviation_scale: {
+						description: """
+																Scale of RTT deviations which are not considered anomalous.
+
+																Valid values are greater than or equal to `0`, and we expect reasonable values to range from `1.0` to `3.0`.
+
+																When calculating the past RTT average, we also compute a secondary “deviation” value that indicates how variable
+																those values are. We use that deviation when comparing the past RTT average to the current measurements, so we
+																can ignore increases in RTT that are within an expected range. This factor is used to scale up the deviation to
+																an appropriate range.  Larger values cause the algorithm to ignore larger increases in the RTT.
+																"""
+						required: false
+						type: float: default: 2.5
+					}
+				}
+			}
+			concurrency: {
+				description: """
+					Configuration for outbound request concurrency.
+
+					This can be set either to one of the below enum values or to a positive integer, which denotes
+					a fixed concurrency limit.
+					"""
+				required: false
+				type: {
+					string: {
+						default: "adaptive"
+						enum: {
+							adaptive: """
+															Concurrency will be managed by Vector's [Adaptive Request Concurrency][arc] feature.
+
+															[arc]: https://vector.dev/docs/about/under-the-hood/networking/arc/
+															"""
+							none: """
+															A fixed concurrency of 1.
+
+															Only one request can be outstanding at any given time.
+															"""
+						}
+					}
+					uint: {}
+				}
+			}
+			rate_limit_duration_secs: {
+				description: "The time window used for the `rate_limit_num` option."
+				required:    false
+				type: uint: {
+					default: 1
+					unit:    "seconds"
+				}
+			}
+			rate_limit_num: {
+				description: "The maximum number of requests allowed within the `rate_limit_duration_secs` time window."
+				required:    false
+				type: uint: {
+					default: 250
+					unit:    "requests"
+				}
+			}
+			retry_attempts: {
+				description: "The maximum number of retries to make for failed requests."
+				required:    false
+				type: uint: {
+					default: 9223372036854775807
+					unit:    "retries"
+				}
+			}
+			retry_initial_backoff_secs: {
+				description: """
+					The amount of time to wait before attempting the first retry for a failed request.
+
+					After the first retry has failed, the fibonacci sequence is used to select future backoffs.
+					"""
+				required: false
+				type: uint: {
+					default: 1
+					unit:    "seconds"
+				}
+			}
+			retry_jitter_mode: {
+				description: "The jitter mode to use for retry backoff behavior."
+				required:    false
+				type: string: {
+					default: "Full"
+					enum: {
+						Full: """
+															Full jitter.
+
+															The random delay is anywhere from 0 up to the maximum current delay calculated by the backoff
+															strategy.
+
+															Incorporating full jitter into your backoff strategy can greatly reduce the likelihood
+															of creating accidental denial of service (DoS) conditions against your own systems when
+															many clients are recovering from a failure state.
+															"""
+						None: "No jitter."
+					}
+				}
+			}
+			retry_max_duration_secs: {
+				description: "The maximum amount of time to wait between retries."
+				required:    false
+				type: uint: {
+					default: 30
+					unit:    "seconds"
+				}
+			}
+			timeout_secs: {
+				description: """
+					The time a request can take before being aborted.
+
+					Datadog highly recommends that you do not lower this value below the service's internal timeout, as this could
+					create orphaned requests, pile on retries, and result in duplicate data downstream.
+					"""
+				required: false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	storage_account: {
+		description: """
+			The Azure Storage Account name.
+
+			The account must have a [hierarchical namespace][hns] enabled.
+
+			Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+			for the account in the following ways, in order:
+
+			- read from environment variables ([more information][env_cred_docs])
+			- looks for a [Managed Identity][managed_ident_docs]
+			- uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+
+			Either `connection_string`, or this field, must be specified.
+
+			[hns]: https://learn.microsoft.com/en-us/azure/storage/blobs/data-lake-storage-namespace
+			[env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+			[managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+			[az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+			"""
+		required: false
+		type: string: examples: ["mylogstorage"]
+	}
+}
@@ -207,6 +207,29 @@ base: components: sinks: clickhouse: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	protocol: {
+		description: "The interface of ClickHouse that events are inserted through."
+		required:    false
+		type: string: {
+			default: "http"
+			enum: {
+				http: """
+					The [HTTP interface][http], which inserts events as rows of JSON in the configured `format`.
+
+					[http]: https://clickhouse.com/docs/en/interfaces/http
+					"""
+				native: """
+					The [native TCP interface][native], which inserts events as blocks of columns.
+
+					The endpoint is that of the TCP interface, such as `tcp://localhost:9000`, which is connected to over TLS when `tls` is set. As the schema of the table is fetched before the first insert, and again whenever it changes, `database` and `table` can't be templated.
+
+					Each column of the table is filled from the field of the same name, which may be a path to a nested field such as `host.name`. Columns that no event of a batch has a field for are left out of its insert, so that ClickHouse fills them with their `DEFAULT` expression. Other missing fields are written as the default value of their column, and events with fields that can't be converted to the type of their column are dropped. The `compression`, `format`, `skip_unknown_fields`, and `date_time_best_effort` options only apply to the HTTP interface.
+
+					[native]: https://clickhouse.com/docs/en/interfaces/tcp
+					"""
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
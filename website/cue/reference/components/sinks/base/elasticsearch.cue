@@ -328,6 +328,22 @@ base: components: sinks: elasticsearch: configuration: {
 		description: "Elasticsearch data stream mode configuration."
 		required:    false
 		type: object: options: {
+			auto_create: {
+				description: """
+					Whether or not to create the data streams that events are routed to if they don't exist.
+
+					Elasticsearch creates a data stream when events are first written to it, unless the creation
+					of indices is restricted with the `action.auto_create_index` cluster setting, in which case
+					the events are rejected. If enabled, the data streams that events are rejected for are
+					created, and the rejected events are written to them again, at least once even if
+					`index_retry_attempts` is `0`.
+
+					A data stream can only be created if an index template with a data stream matches its name,
+					such as the `index_template` of this configuration.
+					"""
+				required: false
+				type: bool: default: false
+			}
 			auto_routing: {
 				description: """
 					Automatically routes events by deriving the data stream name using specific event fields.
@@ -351,6 +367,90 @@ base: components: sinks: elasticsearch: configuration: {
 					syntax: "template"
 				}
 			}
+			index_retry_attempts: {
+				description: """
+					The number of times that documents are written again when the data stream that they're
+					written to is unavailable.
+
+					Documents that are rejected because the write index of their data stream is closed, or is
+					blocked for writes while [index lifecycle management][ilm] rolls it over, are written again
+					on their own, without the documents of the request that were accepted, so that they aren't
+					duplicated.
+
+					Documents are not written again by default, as the documents of each request are then kept
+					in memory until it completes.
+
+					[ilm]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index-lifecycle-management.html
+					"""
+				required: false
+				type: uint: default: 0
+			}
+			index_template: {
+				description: """
+					The index template that data streams are created with.
+
+					The template is installed, or updated, before events are first written, so that the data streams
+					that match its patterns are created with its settings. If it can't be installed, it's installed
+					again before the next request.
+					"""
+				required: false
+				type: object: options: {
+					composed_of: {
+						description: """
+							The component templates that the index template is composed of, such as the mappings of the
+							data streams.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["logs-mappings"]
+						}
+					}
+					ilm_policy: {
+						description: """
+							The [index lifecycle management][ilm] policy of the backing indices of the data streams.
+
+							[ilm]: https://www.elastic.co/guide/en/elasticsearch/reference/current/index-lifecycle-management.html
+							"""
+						required: false
+						type: string: examples: ["logs"]
+					}
+					index_patterns: {
+						description: "The patterns of the names of the data streams that the index template applies to."
+						required:    true
+						type: array: items: type: string: examples: ["logs-vector-*"]
+					}
+					name: {
+						description: "The name of the index template."
+						required:    true
+						type: string: examples: ["vector-logs"]
+					}
+					priority: {
+						description: """
+							The priority of the index template.
+
+							The index template with the highest priority applies to a data stream that several index
+							templates match. The built-in index templates of Elasticsearch have a priority of `100`.
+							"""
+						required: false
+						type: uint: default: 200
+					}
+					settings: {
+						description: "The index settings of the backing indices of the data streams."
+						required:    false
+						type: object: {
+							examples: [{
+								"index.number_of_shards": "1"
+							}]
+							options: "*": {
+								description: "An index setting."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			namespace: {
 				description: "The data stream namespace used to construct the data stream at index time."
 				required:    false
@@ -402,6 +502,22 @@ base: components: sinks: elasticsearch: configuration: {
 			}
 		}
 	}
+	document_id: {
+		description: """
+			The [`_id`][es_id] of each document, rendered from its event.
+
+			Unlike `id_key`, the fields that the ID is rendered from are kept in the document. This
+			option can't be used along with `id_key`. If the ID can't be rendered, or is empty, it's not
+			set, which allows Elasticsearch to set it automatically.
+
+			[es_id]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-id-field.html
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ tenant }}-{{ request_id }}"]
+			syntax: "template"
+		}
+	}
 	doc_type: {
 		description: """
 			The [`doc_type`][doc_type] for your index data.
@@ -551,9 +667,18 @@ base: components: sinks: elasticsearch: configuration: {
 		}
 	}
 	pipeline: {
-		description: "The name of the pipeline to apply."
-		required:    false
-		type: string: examples: ["pipeline-name"]
+		description: """
+			The name of the pipeline to apply.
+
+			If the name is rendered from the event, the pipeline is set for each document, so that
+			events can be dispatched to different ingest pipelines. If it can't be rendered, or is
+			empty, it's not set, which applies the default pipeline of the index.
+			"""
+		required: false
+		type: string: {
+			examples: ["pipeline-name", "{{ service }}-pipeline"]
+			syntax: "template"
+		}
 	}
 	query: {
 		description: "Custom parameters to add to the query string for each HTTP request sent to Elasticsearch."
@@ -773,6 +898,22 @@ base: components: sinks: elasticsearch: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	routing: {
+		description: """
+			The [routing][es_routing] value of each document, rendered from its event.
+
+			The routing value determines the shard that a document is written to, such as to write the
+			documents of each tenant to the same shard. If it can't be rendered, or is empty, it's not
+			set, which routes the document by its ID.
+
+			[es_routing]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-routing-field.html
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ tenant }}"]
+			syntax: "template"
+		}
+	}
 	suppress_type_name: {
 		deprecated:         true
 		deprecated_message: "This option has been deprecated, the `api_version` option should be used instead."
@@ -82,6 +82,77 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	delivery: {
+		description: "How events written to Kafka are protected against duplication."
+		required:    false
+		type: object: options: {
+			mode: {
+				description: "The delivery mode of the producer."
+				required:    false
+				type: string: {
+					default: "at_least_once"
+					enum: {
+						at_least_once: """
+							Events are written at least once.
+
+							Records that the producer retries, such as after losing the connection to a broker, may be
+							written more than once.
+							"""
+						idempotent: """
+							Events are written by an [idempotent producer][idempotence].
+
+							Kafka deduplicates the records that the producer retries, so that each record is written
+							once while the sink runs. Events that are sent again after Vector restarts can still be
+							written twice.
+
+							[idempotence]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#idempotent-producer
+							"""
+						transactional: """
+							Events are written in [transactions][transactions], one per batch of events.
+
+							A transaction is committed once all of its events are written, and only then are its events
+							acknowledged. If any of its events can't be written, the transaction is aborted and none of
+							its events are acknowledged, so that they are sent again by sources with end-to-end
+							acknowledgements or from disk buffers. Consumers that read with `isolation.level` set to
+							`read_committed` never see the events of aborted transactions, so restarting Vector
+							doesn't duplicate events in downstream topics.
+
+							Batches are bounded by the `batch` options, and transactions are written one at a time.
+
+							[transactions]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#transactional-producer
+							"""
+					}
+				}
+			}
+			transaction_timeout_ms: {
+				description: """
+					The maximum time that a transaction can stay open, in milliseconds, before the brokers
+					abort it.
+
+					It can't be lower than `message_timeout_ms`, and can't be higher than the
+					`transaction.max.timeout.ms` of the brokers.
+					"""
+				relevant_when: "mode = \"transactional\""
+				required:      false
+				type: uint: {
+					default: 300000
+					unit:    "milliseconds"
+				}
+			}
+			transactional_id: {
+				description: """
+					The transactional ID of the producer.
+
+					It must be unique to this sink, and must stay the same across restarts of Vector, so
+					that the transactions left open by a previous run are aborted when the sink starts
+					writing.
+					"""
+				relevant_when: "mode = \"transactional\""
+				required:      true
+				type: string: examples: ["vector-aggregator-0"]
+			}
+		}
+	}
 	encoding: {
 		description: "Configures how events are encoded into raw bytes."
 		required:    true
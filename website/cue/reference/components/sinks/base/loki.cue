@@ -716,6 +716,137 @@ base: components: sinks: loki: configuration: {
 			syntax: "template"
 		}
 	}
+	tenant_limits: {
+		description: """
+			Per-tenant limits of the pushes to Loki.
+
+			When events are pushed on behalf of several tenants, these mirror the [limits][limits] that Loki
+			enforces for each tenant, so that the events of one tenant don't get the pushes of the others
+			throttled or rejected.
+
+			[limits]: https://grafana.com/docs/loki/latest/configure/#limits_config
+			"""
+		required: false
+		type: object: options: {
+			default: {
+				description: """
+					The limits of the tenants that aren't listed in `tenants`, including events without a
+					tenant ID.
+					"""
+				required: false
+				type: object: options: {
+					out_of_order_window_secs: {
+						description: """
+							How far behind the latest event of the tenant an event can be while still being sent
+							unmodified, regardless of `out_of_order_action`.
+
+							Loki 2.4.0 and newer accept out-of-order events within a window of half of their
+							`max_chunk_age`, one hour by default. Setting this to that window sends the events that Loki
+							accepts as they are, and only applies `out_of_order_action` to the others.
+							"""
+						required: false
+						type: uint: {
+							default: 0
+							unit:    "seconds"
+						}
+					}
+					rate_limit_duration_secs: {
+						description: "The time window used for the `rate_limit_num` option."
+						required:    false
+						type: uint: {
+							default: 1
+							unit:    "seconds"
+						}
+					}
+					rate_limit_num: {
+						description: """
+							The maximum number of requests pushing events of the tenant allowed within the
+							`rate_limit_duration_secs` time window.
+
+							Requests beyond this rate are delayed until the tenant is allowed to push again, before they
+							are sent, so the delay doesn't count towards the request timeout. The requests of other
+							tenants are sent as usual while up to 64 requests are delayed across tenants. Beyond that,
+							no more requests are built until delayed requests are sent.
+
+							By default, the requests of the tenant are only limited by the `request` options.
+							"""
+						required: false
+						type: uint: unit: "requests"
+					}
+					structured_metadata: {
+						description: """
+							Whether or not the tenant accepts structured metadata.
+
+							Loki rejects pushes with structured metadata for tenants whose `allow_structured_metadata`
+							limit is disabled. If set to false, the structured metadata of the events of the tenant is
+							not sent, and the fields used in it are kept in the events.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+			tenants: {
+				description: "The limits of specific tenants, keyed by tenant ID."
+				required:    false
+				type: object: options: "*": {
+					description: "The limits of a tenant."
+					required:    true
+					type: object: options: {
+						out_of_order_window_secs: {
+							description: """
+								How far behind the latest event of the tenant an event can be while still being sent
+								unmodified, regardless of `out_of_order_action`.
+
+								Loki 2.4.0 and newer accept out-of-order events within a window of half of their
+								`max_chunk_age`, one hour by default. Setting this to that window sends the events that Loki
+								accepts as they are, and only applies `out_of_order_action` to the others.
+								"""
+							required: false
+							type: uint: {
+								default: 0
+								unit:    "seconds"
+							}
+						}
+						rate_limit_duration_secs: {
+							description: "The time window used for the `rate_limit_num` option."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    "seconds"
+							}
+						}
+						rate_limit_num: {
+							description: """
+								The maximum number of requests pushing events of the tenant allowed within the
+								`rate_limit_duration_secs` time window.
+
+								Requests beyond this rate are delayed until the tenant is allowed to push again, before they
+								are sent, so the delay doesn't count towards the request timeout. The requests of other
+								tenants are sent as usual while up to 64 requests are delayed across tenants. Beyond that,
+								no more requests are built until delayed requests are sent.
+
+								By default, the requests of the tenant are only limited by the `request` options.
+								"""
+							required: false
+							type: uint: unit: "requests"
+						}
+						structured_metadata: {
+							description: """
+								Whether or not the tenant accepts structured metadata.
+
+								Loki rejects pushes with structured metadata for tenants whose `allow_structured_metadata`
+								limit is disabled. If set to false, the structured metadata of the events of the tenant is
+								not sent, and the fields used in it are kept in the events.
+								"""
+							required: false
+							type: bool: default: true
+						}
+					}
+				}
+			}
+		}
+	}
 	tls: {
 		description: "TLS configuration."
 		required:    false
@@ -72,14 +72,30 @@ base: components: sinks: vector: configuration: {
 	}
 	compression: {
 		description: """
-			Whether or not to compress requests.
+			The compression of requests.
 
-			If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+			If the downstream Vector doesn't accept this compression, such as a version of Vector that
+			predates `zstd` support, requests fall back to a compression that it accepts.
 
-			[gzip_docs]: https://www.gzip.org/
+			For backward compatibility, `true` is accepted as `gzip`, and `false` as `none`.
 			"""
 		required: false
-		type: bool: default: false
+		type: string: {
+			default: "none"
+			enum: {
+				gzip: """
+					[Gzip][gzip] compression.
+
+					[gzip]: https://www.gzip.org/
+					"""
+				none: "No compression."
+				zstd: """
+					[Zstandard][zstd] compression.
+
+					[zstd]: https://facebook.github.io/zstd/
+					"""
+			}
+		}
 	}
 	request: {
 		description: """
@@ -371,4 +387,21 @@ base: components: sinks: vector: configuration: {
 			}
 		}
 	}
+	tls_reload_interval_secs: {
+		description: """
+			The interval at which the TLS certificate, key, and CA files are checked for changes, in
+			seconds.
+
+			When the files change, they are reloaded, so that the client certificate can be rotated
+			without restarting Vector. The connection to the downstream Vector is kept, and new
+			connections are established with the reloaded certificates.
+
+			If not set, the files are only read when the sink is built.
+			"""
+		required: false
+		type: uint: {
+			examples: [60]
+			unit: "seconds"
+		}
+	}
 }
@@ -0,0 +1,138 @@
+package metadata
+
+components: sources: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			tls: enabled:        false
+			from: {
+				service: services.azure_event_hubs
+				interface: {
+					socket: {
+						api: {
+							title: "Kafka protocol"
+							url:   urls.azure_event_hubs_kafka
+						}
+						direction: "outgoing"
+						port:      9093
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The Event Hubs namespace must use the Standard tier or above, since the
+				[Basic tier](\(urls.azure_event_hubs_tiers)) does not expose the Kafka endpoint.
+				""",
+		]
+		notices: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.azure_event_hubs.configuration
+
+	output: logs: record: {
+		description: "An individual Event Hubs event."
+		fields: {
+			message: {
+				description: "The raw body of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			offset: {
+				description: "The offset of the event in its partition."
+				required:    true
+				type: uint: {
+					examples: [100]
+					unit: null
+				}
+			}
+			partition: {
+				description: "The Event Hub partition that the event came from."
+				required:    true
+				type: string: {
+					examples: ["0"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["azure_event_hubs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the event was enqueued in the Event Hub."
+			}
+			topic: {
+				description: "The Event Hub that the event came from."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		kafka_endpoint: {
+			title: "Kafka endpoint"
+			body:  """
+				The source reads from the [Kafka-compatible endpoint](\(urls.azure_event_hubs_kafka))
+				of the Event Hubs namespace, authenticating with the configured connection string.
+				The Kafka endpoint is available on every tier except Basic. Partitions are balanced
+				across every Vector instance in the same consumer group, so each partition is owned by
+				one instance at a time, and move between instances as they join or leave the group.
+				"""
+		}
+		checkpoints: {
+			title: "Checkpoints"
+			body:  """
+				The offsets of processed events are committed to the consumer group, which Event Hubs
+				stores itself. When `checkpoint_store` is set, they are also checkpointed to Azure Blob
+				Storage, in a blob per partition, every `checkpoint_interval_ms` and whenever a
+				partition moves to another instance or Vector shuts down. A partition that is assigned
+				to an instance resumes after its checkpoint, or from the committed offset when it has
+				no checkpoint yet.
+
+				The checkpoints hold Kafka offsets, so they can't be shared with Event Hubs clients that
+				read over AMQP.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		kafka_consumed_messages_total:       components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
+		kafka_consumed_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		kafka_consumer_lag:                  components.sources.internal_metrics.output.metrics.kafka_consumer_lag
+	}
+}
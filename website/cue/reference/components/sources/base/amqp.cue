@@ -90,6 +90,19 @@ base: components: sources: amqp: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -134,6 +147,14 @@ base: components: sources: amqp: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -409,11 +430,35 @@ base: components: sources: amqp: configuration: {
 			}
 		}
 	}
+	max_requeues: {
+		description: """
+			The maximum number of times a message is requeued when its events fail to be delivered.
+
+			Once this limit is reached, or when its events are rejected by the sinks, the message is
+			rejected without being requeued, so that the broker dead-letters it when the queue has a
+			dead letter exchange.
+
+			The number of previous deliveries of a message is read from the `x-delivery-count` header
+			set by quorum queues. For other queues, a redelivered message counts as delivered once
+			before.
+			"""
+		required: false
+		type: uint: default: 0
+	}
 	offset_key: {
 		description: "The `AMQP` offset key."
 		required:    false
 		type: string: default: "offset"
 	}
+	prefetch_count: {
+		description: """
+			The maximum number of unacknowledged messages the broker delivers to the consumer.
+
+			By default, the number of unacknowledged messages is not limited by the consumer.
+			"""
+		required: false
+		type: uint: examples: [100]
+	}
 	queue: {
 		description: "The name of the queue to consume."
 		required:    false
@@ -0,0 +1,594 @@
+package metadata
+
+base: components: sources: azure_event_hubs: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	checkpoint_interval_ms: {
+		description: "The frequency that the offsets of processed events are checkpointed."
+		required:    false
+		type: uint: {
+			default: 5000
+			examples: [5000, 10000]
+			unit: "milliseconds"
+		}
+	}
+	checkpoint_store: {
+		description: """
+			Azure Blob Storage settings for storing checkpoints.
+
+			Each partition is checkpointed to a blob named
+			`<namespace host>/<event hub>/<consumer group>/<partition>`, which holds the offset of the last
+			processed event of the partition. When a partition is assigned to a Vector instance, it resumes
+			after its checkpoint, and from the offset committed to the consumer group when it has none.
+
+			Offsets are still committed to the consumer group as well.
+			"""
+		required: false
+		type: object: options: {
+			client_credentials: {
+				description: """
+					The credentials of an Azure Active Directory service principal.
+
+					The service principal signs in with either a client secret or a client certificate. If unset,
+					the [default credential chain][default_chain] is used, which tries environment variables,
+					workload identity, managed identity, and the Azure CLI in turn.
+
+					[default_chain]: https://learn.microsoft.com/en-us/azure/developer/intro/passwordless-overview#use-defaultazurecredential
+					"""
+				required: false
+				type: object: options: {
+					client_certificate_password: {
+						description: """
+							The password of the client certificate.
+
+							This is the password of the PKCS#12 archive, or of the encrypted private key in a PEM file.
+							"""
+						required: false
+						type: string: examples: ["${AZURE_CLIENT_CERTIFICATE_PASSWORD}"]
+					}
+					client_certificate_path: {
+						description: """
+							The path to a client certificate of the service principal.
+
+							The certificate is either a PKCS#12 (PFX) archive, or a PEM file with the certificate, its
+							private key, and optionally the rest of its chain.
+
+							Either this or `client_secret` must be set.
+							"""
+						required: false
+						type: string: examples: ["/etc/vector/service-principal.pfx", "/etc/vector/service-principal.pem"]
+					}
+					client_id: {
+						description: "The client ID of the service principal."
+						required:    true
+						type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+					}
+					client_secret: {
+						description: """
+							A client secret of the service principal.
+
+							Either this or `client_certificate_path` must be set.
+							"""
+						required: false
+						type: string: examples: ["${AZURE_CLIENT_SECRET}"]
+					}
+					tenant_id: {
+						description: "The ID of the Azure Active Directory tenant of the service principal."
+						required:    true
+						type: string: examples: ["00000000-0000-0000-0000-000000000000"]
+					}
+				}
+			}
+			connection_string: {
+				description: """
+					The Azure Blob Storage Account connection string.
+
+					Authentication with access key is the only supported authentication method.
+
+					Either `storage_account`, or this field, must be specified.
+					"""
+				required: false
+				type: string: examples: ["DefaultEndpointsProtocol=https;AccountName=mycheckpoints;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+			}
+			container_name: {
+				description: """
+					The name of the container that checkpoints are stored in.
+
+					The container must already exist.
+					"""
+				required: true
+				type: string: examples: ["event-hubs-checkpoints"]
+			}
+			endpoint: {
+				description: """
+					The Azure Blob Storage Endpoint URL.
+
+					This is used to override the default blob storage endpoint URL when authenticating with
+					`storage_account`.
+
+					This may only be used with `storage_account` and is ignored when used with
+					`connection_string`.
+					"""
+				required: false
+				type: string: examples: ["https://test.blob.core.usgovcloudapi.net/", "https://test.blob.core.windows.net/"]
+			}
+			storage_account: {
+				description: """
+					The Azure Blob Storage Account name.
+
+					Authenticates with `client_credentials` if set, and otherwise attempts to load credentials
+					for the account in the following ways, in order:
+
+					- read from environment variables ([more information][env_cred_docs])
+					- looks for a [Managed Identity][managed_ident_docs]
+					- uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+
+					Either `connection_string`, or this field, must be specified.
+
+					[env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+					[managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+					[az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+					"""
+				required: false
+				type: string: examples: ["mycheckpoints"]
+			}
+		}
+	}
+	connection_string: {
+		description: """
+			The connection string of the Event Hubs namespace or of the Event Hub itself.
+
+			The namespace host is taken from its `Endpoint` property, and the Event Hub name from its
+			`EntityPath` property when present.
+			"""
+		required: true
+		type: string: examples: ["Endpoint=sb://mynamespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=${EVENT_HUBS_KEY};EntityPath=logs"]
+	}
+	consumer_group: {
+		description: """
+			The consumer group to read events as.
+
+			Partitions of the Event Hub are balanced across all Vector instances that share a consumer
+			group, so that each partition is read by only one of them at a time. The offsets of
+			processed events are checkpointed against that group.
+			"""
+		required: false
+		type: string: {
+			default: "$Default"
+			examples: ["$Default", "vector"]
+		}
+	}
+	decoding: {
+		description: "Configures how events are decoded from raw bytes."
+		required:    false
+		type: object: options: {
+			avro: {
+				description:   "Apache Avro-specific encoder options."
+				relevant_when: "codec = \"avro\""
+				required:      true
+				type: object: options: {
+					schema: {
+						description: """
+																The Avro schema definition.
+																Please note that the following [`apache_avro::types::Value`] variants are currently *not* supported:
+																* `Date`
+																* `Decimal`
+																* `Duration`
+																* `Fixed`
+																* `TimeMillis`
+																"""
+						required: true
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					strip_schema_id_prefix: {
+						description: """
+																For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to true to strip the schema ID prefix.
+																According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
+																"""
+						required: true
+						type: bool: {}
+					}
+				}
+			}
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						avro: """
+															Decodes the raw bytes as as an [Apache Avro][apache_avro] message.
+
+															[apache_avro]: https://avro.apache.org/
+															"""
+						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
+						gelf: """
+															Decodes the raw bytes as a [GELF][gelf] message.
+
+															This codec is experimental for the following reason:
+
+															The GELF specification is more strict than the actual Graylog receiver.
+															Vector's decoder currently adheres more strictly to the GELF spec, with
+															the exception that some characters such as `@`  are allowed in field names.
+
+															Other GELF codecs such as Loki's, use a [Go SDK][implementation] that is maintained
+															by Graylog, and is much more relaxed than the GELF spec.
+
+															Going forward, Vector will use that [Go SDK][implementation] as the reference implementation, which means
+															the codec may continue to relax the enforcement of specification.
+
+															[gelf]: https://docs.graylog.org/docs/gelf
+															[implementation]: https://github.com/Graylog2/go-gelf/blob/v2/gelf/reader.go
+															"""
+						influxdb: """
+															Decodes the raw bytes as an [Influxdb Line Protocol][influxdb] message.
+
+															[influxdb]: https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol
+															"""
+						json: """
+															Decodes the raw bytes as [JSON][json].
+
+															[json]: https://www.json.org/
+															"""
+						native: """
+															Decodes the raw bytes as [native Protocol Buffers format][vector_native_protobuf].
+
+															This codec is **[experimental][experimental]**.
+
+															[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						native_json: """
+															Decodes the raw bytes as [native JSON format][vector_native_json].
+
+															This codec is **[experimental][experimental]**.
+
+															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
+						protobuf: """
+															Decodes the raw bytes as [protobuf][protobuf].
+
+															[protobuf]: https://protobuf.dev/
+															"""
+						syslog: """
+															Decodes the raw bytes as a Syslog message.
+
+															Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+															[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+															[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+															[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+															"""
+						vrl: """
+															Decodes the raw bytes as a string and passes them as input to a [VRL][vrl] program.
+
+															[vrl]: https://vector.dev/docs/reference/vrl
+															"""
+					}
+				}
+			}
+			gelf: {
+				description:   "GELF-specific decoding options."
+				relevant_when: "codec = \"gelf\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			influxdb: {
+				description:   "Influxdb-specific decoding options."
+				relevant_when: "codec = \"influxdb\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			native_json: {
+				description:   "Vector's native JSON-specific decoding options."
+				relevant_when: "codec = \"native_json\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			protobuf: {
+				description:   "Protobuf-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      false
+				type: object: options: {
+					desc_file: {
+						description: "Path to desc file"
+						required:    false
+						type: string: default: ""
+					}
+					message_type: {
+						description: "message type. e.g package.message"
+						required:    false
+						type: string: default: ""
+					}
+				}
+			}
+			syslog: {
+				description:   "Syslog-specific decoding options."
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			vrl: {
+				description:   "VRL-specific decoding options."
+				relevant_when: "codec = \"vrl\""
+				required:      true
+				type: object: options: {
+					source: {
+						description: """
+																The [Vector Remap Language][vrl] (VRL) program to execute for each event.
+																Note that the final contents of the `.` target will be used as the decoding result.
+																Compilation error or use of 'abort' in a program will result in a decoding error.
+
+																[vrl]: https://vector.dev/docs/reference/vrl
+																"""
+						required: true
+						type: string: {}
+					}
+					timezone: {
+						description: """
+																The name of the timezone to apply to timestamp conversions that do not contain an explicit
+																time zone. The time zone name may be any name in the [TZ database][tz_database], or `local`
+																to indicate system local time.
+
+																If not set, `local` will be used.
+
+																[tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+																"""
+						required: false
+						type: string: examples: ["local", "America/New_York", "EST5EDT"]
+					}
+				}
+			}
+		}
+	}
+	event_hub_name: {
+		description: """
+			The name of the Event Hub to read events from.
+
+			Required if the connection string does not contain an `EntityPath`.
+			"""
+		required: false
+		type: string: examples: ["logs"]
+	}
+	framing: {
+		description: """
+			Framing configuration.
+
+			Framing handles how events are separated when encoded in a raw byte form, where each event is
+			a frame that must be prefixed, or delimited, in a way that marks where an event begins and
+			ends within the byte stream.
+			"""
+		required: false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: ascii_char: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+
+																By default, there is no maximum length enforced. If events are malformed, this can lead to
+																additional resource usage as events continue to be buffered in memory, and can potentially
+																lead to memory exhaustion in extreme cases.
+
+																If there is a risk of processing malformed data, such as logs with user-controlled input,
+																consider setting the maximum length to a reasonably large value as a safety net. This
+																ensures that processing is not actually unbounded.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			length_delimited: {
+				description:   "Options for the length delimited decoder."
+				relevant_when: "method = \"length_delimited\""
+				required:      true
+				type: object: options: {
+					length_field_is_big_endian: {
+						description: "Length field byte order (little or big endian)"
+						required:    false
+						type: bool: default: true
+					}
+					length_field_length: {
+						description: "Number of bytes representing the field length"
+						required:    false
+						type: uint: default: 4
+					}
+					length_field_offset: {
+						description: "Number of bytes in the header before the length field"
+						required:    false
+						type: uint: default: 0
+					}
+					max_frame_length: {
+						description: "Maximum frame length"
+						required:    false
+						type: uint: default: 8388608
+					}
+				}
+			}
+			method: {
+				description: "The framing method."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+						character_delimited: "Byte frames which are delimited by a chosen character."
+						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						newline_delimited:   "Byte frames which are delimited by a newline character."
+						octet_counting: """
+															Byte frames according to the [octet counting][octet_counting] format.
+
+															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+															"""
+					}
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+
+						By default, there is no maximum length enforced. If events are malformed, this can lead to
+						additional resource usage as events continue to be buffered in memory, and can potentially
+						lead to memory exhaustion in extreme cases.
+
+						If there is a risk of processing malformed data, such as logs with user-controlled input,
+						consider setting the maximum length to a reasonably large value as a safety net. This
+						ensures that processing is not actually unbounded.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+		}
+	}
+	librdkafka_options: {
+		description: """
+			Advanced options set directly on the underlying `librdkafka` client.
+
+			See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for details.
+			"""
+		required: false
+		type: object: {
+			options: "*": {
+				description: "A librdkafka configuration option."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	start_position: {
+		description: "Where to start reading a partition when the consumer group has no committed offset for it."
+		required:    false
+		type: string: {
+			default: "latest"
+			enum: {
+				earliest: "Start from the oldest event still retained by the Event Hub."
+				latest:   "Start from events enqueued after the consumer joined."
+			}
+		}
+	}
+}
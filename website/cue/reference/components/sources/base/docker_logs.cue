@@ -168,6 +168,32 @@ base: components: sources: docker_logs: configuration: {
 			unit:    "seconds"
 		}
 	}
+	routes: {
+		description: """
+			A table of output names to container labels, used to route the logs of containers to named
+			outputs.
+
+			The logs of a container are sent to the first output whose label the container has, and to
+			the default output if the container has none of them. Labels follow the same syntax as
+			`include_labels`, either `key=value` to match the value of the label, or `key` to match any
+			value.
+
+			Each output can then be referenced as an input by other components with the name
+			`<source_id>.<output>`.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				nginx: "vector.pipeline=nginx"
+				redis: "vector.pipeline=redis"
+			}]
+			options: "*": {
+				description: "The container label of the output."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
 	tls: {
 		description: """
 			Configuration of TLS when connecting to the Docker daemon.
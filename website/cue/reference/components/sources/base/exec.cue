@@ -55,6 +55,19 @@ base: components: sources: exec: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -99,6 +112,14 @@ base: components: sources: exec: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -255,6 +276,11 @@ base: components: sources: exec: configuration: {
 		description: """
 			Custom environment variables to set or update when running the command.
 			If a variable name already exists in the environment, its value is replaced.
+
+			Values can contain placeholders, rendered each time the command is run:
+
+			- `{{ hostname }}`: the hostname of the host running Vector.
+			- `{{ restart_count }}`: the number of times a streaming command has been restarted.
 			"""
 		required: false
 		type: object: {
@@ -416,22 +442,72 @@ base: components: sources: exec: configuration: {
 			type: uint: default: 60
 		}
 	}
+	stderr_output: {
+		description: """
+			Whether to send the events generated from stderr to a separate output.
+
+			If `true`, these events can be referenced as an input by other components with the name
+			`<source_id>.stderr`, and are not sent to the default output.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	streaming: {
 		description: "Configuration options for streaming commands."
 		required:    false
 		type: object: options: {
+			max_respawn_interval_secs: {
+				description: """
+					The maximum amount of time, in seconds, before rerunning a streaming command that keeps
+					exiting.
+
+					If set, the time before rerunning the command doubles each time it exits, starting from
+					`respawn_interval_secs`, up to this value. It is reset once the command has run for longer
+					than this value.
+					"""
+				required: false
+				type: uint: {}
+			}
 			respawn_interval_secs: {
 				description: "The amount of time, in seconds, before rerunning a streaming command that exited."
 				required:    false
 				type: uint: default: 5
 			}
 			respawn_on_exit: {
-				description: "Whether or not the command should be rerun if the command exits."
-				required:    false
+				description: """
+					Whether or not the command should be rerun if the command exits.
+
+					Ignored if `restart_policy` is set.
+					"""
+				required: false
 				type: bool: default: true
 			}
+			restart_policy: {
+				description: """
+					The policy for rerunning a streaming command that exited.
+
+					Defaults to `always` if `respawn_on_exit` is `true`, and to `never` otherwise.
+					"""
+				required: false
+				type: string: enum: {
+					always: "The command is always rerun."
+					never:  "The command is never rerun."
+					on_failure: """
+						The command is rerun only if it failed, exiting with a non-zero status or killed by a
+						signal.
+						"""
+				}
+			}
 		}
 	}
+	termination_grace_period_secs: {
+		description: """
+			The amount of time, in seconds, that the command has to exit once it is sent a `SIGTERM`
+			on shutdown, before it is killed.
+			"""
+		required: false
+		type: uint: default: 10
+	}
 	working_directory: {
 		description: "The directory in which to run the command."
 		required:    false
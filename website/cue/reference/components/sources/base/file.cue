@@ -36,6 +36,27 @@ base: components: sources: file: configuration: {
 		required: false
 		type: string: examples: ["/var/local/lib/vector/"]
 	}
+	discovery_mode: {
+		description: "How files are discovered."
+		required:    false
+		type: string: {
+			default: "poll"
+			enum: {
+				native: """
+					Also subscribe to the change notifications of the operating system, to search for files
+					soon after they are created, removed, or renamed in the directories that they are in.
+
+					The notifications come from `inotify` on Linux, `kqueue` on the BSDs, `FSEvents` on macOS,
+					and `ReadDirectoryChangesW` on Windows. This reduces the chance of missing files that are
+					rotated quickly. Searches triggered by notifications are at least a tenth of
+					`glob_minimum_cooldown_ms` apart, so files can be searched for up to ten times as often
+					as when polling. Files are still searched for every `glob_minimum_cooldown_ms`, in case
+					notifications are missed.
+					"""
+				poll: "Search for files every `glob_minimum_cooldown_ms`."
+			}
+		}
+	}
 	encoding: {
 		description: "Character set encoding."
 		required:    false
@@ -342,6 +363,22 @@ base: components: sources: file: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	read_archives: {
+		description: """
+			Whether to read gzipped files that are matched by `include` as rotated archives.
+
+			When enabled, gzipped files, such as `*.log.1.gz`, are read from the beginning once, even
+			if `read_from` is `end`, and their position is checkpointed. Archives that have been read
+			to their end are marked as completed in the checkpoints, so that they aren't decompressed
+			again after a restart. They are fingerprinted by their decompressed content, so a file that
+			is compressed after it has been rotated is resumed where it was left off rather than read
+			again.
+
+			This allows catching up on the rotated history after Vector has been stopped.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	read_from: {
 		description: "File position to use when reading a new file."
 		required:    false
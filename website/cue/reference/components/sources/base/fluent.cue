@@ -89,8 +89,13 @@ base: components: sources: fluent: configuration: {
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
 			client_metadata_key: {
-				description: "Event field for client certificate metadata."
-				required:    false
+				description: """
+					Event field for client certificate metadata.
+
+					The metadata contains the `subject` of the client certificate, and when present, its
+					`subject_alt_names` and its SHA-256 fingerprint (`fingerprint_sha256`).
+					"""
+				required: false
 				type: string: {}
 			}
 			crt_file: {
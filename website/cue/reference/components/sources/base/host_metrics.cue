@@ -68,7 +68,7 @@ base: components: sources: host_metrics: configuration: {
 		description: """
 			The list of host metric collector services to use.
 
-			Defaults to all collectors.
+			Defaults to all collectors, except `gpu` and `process`.
 			"""
 		required: false
 		type: array: {
@@ -83,12 +83,22 @@ base: components: sources: host_metrics: configuration: {
 					cpu:        "Metrics related to CPU utilization."
 					disk:       "Metrics related to disk I/O utilization."
 					filesystem: "Metrics related to filesystem space utilization."
+					gpu: """
+						Metrics related to NVIDIA GPU utilization.
+
+						Not collected by default, as it requires the NVIDIA Management Library.
+						"""
 					host:       "Metrics related to the host."
 					load:       "Metrics related to the system load average."
 					memory:     "Metrics related to memory utilization."
 					network:    "Metrics related to network utilization."
+					process: """
+						Metrics related to the resource usage of individual processes.
+
+						Not collected by default, as the number of processes can be large.
+						"""
 				}
-				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				examples: ["cgroups", "cpu", "disk", "filesystem", "gpu", "load", "host", "memory", "network", "process"]
 			}
 		}
 	}
@@ -246,6 +256,53 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	gpu: {
+		description: """
+			Options for the GPU metrics collector.
+
+			This collector reports the metrics of NVIDIA GPUs, through the NVIDIA Management Library (NVML)
+			installed with their drivers.
+
+			This collector is only available if Vector is built with the `sources-host_metrics-gpu` feature.
+			"""
+		required: false
+		type: object: options: devices: {
+			description: """
+				Lists of GPU index patterns, such as `0`, to include or exclude in gathering
+				GPU metrics.
+				"""
+			required: false
+			type: object: {
+				examples: [{
+					excludes: ["7"]
+					includes: ["0"]
+				}]
+				options: {
+					excludes: {
+						description: """
+																Any patterns which should be excluded.
+
+																The patterns are matched using globbing.
+																"""
+						required: false
+						type: array: items: type: string: {}
+					}
+					includes: {
+						description: """
+																Any patterns which should be included.
+
+																The patterns are matched using globbing.
+																"""
+						required: false
+						type: array: {
+							default: ["*"]
+							items: type: string: {}
+						}
+					}
+				}
+			}
+		}
+	}
 	namespace: {
 		description: "Overrides the default namespace for the metrics emitted by the source."
 		required:    false
@@ -291,6 +348,107 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	process: {
+		description: "Options for the process metrics collector."
+		required:    false
+		type: object: options: {
+			cmdlines: {
+				description: """
+					Lists of command line patterns to include or exclude in gathering
+					process metrics.
+
+					The command line of a process is its arguments separated by spaces.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						includes: ["*--config /etc/app/*"]
+					}]
+					options: {
+						excludes: {
+							description: """
+																	Any patterns which should be excluded.
+
+																	The patterns are matched using globbing.
+																	"""
+							required: false
+							type: array: items: type: string: {}
+						}
+						includes: {
+							description: """
+																	Any patterns which should be included.
+
+																	The patterns are matched using globbing.
+																	"""
+							required: false
+							type: array: {
+								default: ["*"]
+								items: type: string: {}
+							}
+						}
+					}
+				}
+			}
+			group_by_name: {
+				description: """
+					Whether to aggregate the metrics of the processes with the same name.
+
+					If `true`, the metrics are not tagged with the `pid` of the processes, which keeps their
+					cardinality bounded when processes are restarted, and the number of processes is reported
+					as `process_count`.
+
+					The CPU time and I/O totals of a group drop when one of its processes exits, so they are
+					reported as gauges rather than counters.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			max_processes: {
+				description: """
+					The maximum number of processes to report metrics for.
+
+					If more processes match the filters, the ones that used the most CPU time are reported.
+					"""
+				required: false
+				type: uint: default: 100
+			}
+			names: {
+				description: """
+					Lists of process name patterns to include or exclude in gathering
+					process metrics.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						includes: ["nginx"]
+					}]
+					options: {
+						excludes: {
+							description: """
+																	Any patterns which should be excluded.
+
+																	The patterns are matched using globbing.
+																	"""
+							required: false
+							type: array: items: type: string: {}
+						}
+						includes: {
+							description: """
+																	Any patterns which should be included.
+
+																	The patterns are matched using globbing.
+																	"""
+							required: false
+							type: array: {
+								default: ["*"]
+								items: type: string: {}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between metric gathering, in seconds."
 		required:    false
@@ -32,17 +32,184 @@ base: components: sources: http_server: configuration: {
 		type: string: examples: ["0.0.0.0:80", "localhost:80"]
 	}
 	auth: {
-		description: "HTTP Basic authentication configuration."
+		description: "HTTP authentication configuration."
 		required:    false
 		type: object: options: {
+			audiences: {
+				description: """
+					The audiences that tokens are accepted for.
+
+					The `aud` claim of tokens must contain at least one of them.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      true
+				type: array: items: type: string: examples: ["vector"]
+			}
+			issuer: {
+				description: """
+					The issuer of the tokens.
+
+					The `iss` claim of tokens must be equal to it.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      true
+				type: string: examples: ["https://accounts.example.com"]
+			}
+			jwks_refresh_interval_secs: {
+				description: """
+					The interval between refreshes of the keys, in seconds.
+
+					The keys are also refreshed when a token is signed with a key that isn't known yet.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: uint: {
+					default: 3600
+					unit:    "seconds"
+				}
+			}
+			jwks_url: {
+				description: """
+					The URL of the JSON Web Key Set (JWKS) with the keys that tokens are signed with.
+
+					If not set, the URL is discovered from the `jwks_uri` of the OpenID Connect configuration
+					of the issuer, at `<issuer>/.well-known/openid-configuration`.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: string: examples: ["https://accounts.example.com/.well-known/jwks.json"]
+			}
+			leeway_secs: {
+				description: """
+					The leeway allowed when checking the `exp` and `nbf` claims of tokens, in seconds.
+
+					This accounts for the clock skew between Vector and the issuer.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
 			password: {
-				description: "The password for basic authentication."
-				required:    true
+				description:   "The password for basic authentication."
+				relevant_when: "strategy = \"basic\""
+				required:      true
 				type: string: examples: ["hunter2", "${PASSWORD}"]
 			}
-			username: {
-				description: "The username for basic authentication."
+			strategy: {
+				description: "The authentication strategy to use. Defaults to `basic` when unset."
 				required:    true
+				type: string: enum: {
+					basic: "HTTP Basic authentication."
+					oidc: """
+						OpenID Connect bearer token authentication.
+
+						Requests must carry a JSON Web Token (JWT) in the `Authorization: Bearer` header, which is
+						validated against the keys published by the issuer.
+						"""
+				}
+			}
+			tls: {
+				description:   "TLS configuration."
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+		
+							Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+							that they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+		
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+		
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+		
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+		
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+		
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					server_name: {
+						description: """
+							Server name to use when using Server Name Indication (SNI).
+		
+							Only relevant for outgoing connections.
+							"""
+						required: false
+						type: string: examples: ["www.example.com"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification. For components that create a server, this requires that the
+							client connections have a valid client certificate. For components that initiate requests,
+							this validates that the upstream has a valid certificate.
+		
+							If enabled, certificates must not be expired and must be issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+							certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+							so on until the verification process reaches a root certificate.
+		
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+		
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+		
+							Only relevant for outgoing connections.
+		
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			username: {
+				description:   "The username for basic authentication."
+				relevant_when: "strategy = \"basic\""
+				required:      true
 				type: string: examples: ["AzureDiamond", "admin"]
 			}
 		}
@@ -89,6 +256,19 @@ base: components: sources: http_server: configuration: {
 						[apache_avro]: https://avro.apache.org/
 						"""
 					bytes: "Uses the raw bytes as-is."
+					event_hub_capture: """
+						Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+						event per captured event.
+
+						The body of each captured event is decoded as the message, and the time that it was enqueued
+						at as the timestamp of the event. Its sequence number, offset, and properties are added as
+						fields of the event.
+
+						Each byte frame must contain a complete Avro file, so this codec is typically combined with
+						`bytes` framing.
+
+						[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+						"""
 					gelf: """
 						Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -133,6 +313,14 @@ base: components: sources: http_server: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					parquet: """
+						Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+						Each byte frame must contain a complete Parquet file, so this codec is typically combined
+						with `bytes` framing.
+
+						[apache_parquet]: https://parquet.apache.org/
+						"""
 					protobuf: """
 						Decodes the raw bytes as [protobuf][protobuf].
 
@@ -529,6 +717,423 @@ base: components: sources: http_server: configuration: {
 			]
 		}
 	}
+	routes: {
+		description: """
+			A list of routes to serve on the same address, each with its own path, method, decoding
+			and output.
+
+			When routes are set, requests are only accepted on the routes, and not on `path`. The
+			`method`, `framing`, and `decoding` options are used for the routes that don't set them.
+			"""
+		required: false
+		type: array: items: type: object: options: {
+			decoding: {
+				description: "Configures how events are decoded from raw bytes."
+				required:    false
+				type: object: options: {
+					avro: {
+						description:   "Apache Avro-specific encoder options."
+						relevant_when: "codec = \"avro\""
+						required:      true
+						type: object: options: {
+							schema: {
+								description: """
+																		The Avro schema definition.
+																		Please note that the following [`apache_avro::types::Value`] variants are currently *not* supported:
+																		* `Date`
+																		* `Decimal`
+																		* `Duration`
+																		* `Fixed`
+																		* `TimeMillis`
+																		"""
+								required: true
+								type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+							}
+							strip_schema_id_prefix: {
+								description: """
+																		For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to true to strip the schema ID prefix.
+																		According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
+																		"""
+								required: true
+								type: bool: {}
+							}
+						}
+					}
+					codec: {
+						description: "The codec to use for decoding events."
+						required:    true
+						type: string: enum: {
+							avro: """
+								Decodes the raw bytes as as an [Apache Avro][apache_avro] message.
+
+								[apache_avro]: https://avro.apache.org/
+								"""
+							bytes: "Uses the raw bytes as-is."
+							event_hub_capture: """
+								Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+								event per captured event.
+
+								The body of each captured event is decoded as the message, and the time that it was enqueued
+								at as the timestamp of the event. Its sequence number, offset, and properties are added as
+								fields of the event.
+
+								Each byte frame must contain a complete Avro file, so this codec is typically combined with
+								`bytes` framing.
+
+								[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+								"""
+							gelf: """
+								Decodes the raw bytes as a [GELF][gelf] message.
+
+								This codec is experimental for the following reason:
+
+								The GELF specification is more strict than the actual Graylog receiver.
+								Vector's decoder currently adheres more strictly to the GELF spec, with
+								the exception that some characters such as `@`  are allowed in field names.
+
+								Other GELF codecs such as Loki's, use a [Go SDK][implementation] that is maintained
+								by Graylog, and is much more relaxed than the GELF spec.
+
+								Going forward, Vector will use that [Go SDK][implementation] as the reference implementation, which means
+								the codec may continue to relax the enforcement of specification.
+
+								[gelf]: https://docs.graylog.org/docs/gelf
+								[implementation]: https://github.com/Graylog2/go-gelf/blob/v2/gelf/reader.go
+								"""
+							influxdb: """
+								Decodes the raw bytes as an [Influxdb Line Protocol][influxdb] message.
+
+								[influxdb]: https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol
+								"""
+							json: """
+								Decodes the raw bytes as [JSON][json].
+
+								[json]: https://www.json.org/
+								"""
+							native: """
+								Decodes the raw bytes as [native Protocol Buffers format][vector_native_protobuf].
+
+								This codec is **[experimental][experimental]**.
+
+								[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+								[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+								"""
+							native_json: """
+								Decodes the raw bytes as [native JSON format][vector_native_json].
+
+								This codec is **[experimental][experimental]**.
+
+								[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+								[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+								"""
+							parquet: """
+								Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+								Each byte frame must contain a complete Parquet file, so this codec is typically combined
+								with `bytes` framing.
+
+								[apache_parquet]: https://parquet.apache.org/
+								"""
+							protobuf: """
+								Decodes the raw bytes as [protobuf][protobuf].
+
+								[protobuf]: https://protobuf.dev/
+								"""
+							syslog: """
+								Decodes the raw bytes as a Syslog message.
+
+								Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+								[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+								[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+								[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+								"""
+							vrl: """
+								Decodes the raw bytes as a string and passes them as input to a [VRL][vrl] program.
+
+								[vrl]: https://vector.dev/docs/reference/vrl
+								"""
+						}
+					}
+					gelf: {
+						description:   "GELF-specific decoding options."
+						relevant_when: "codec = \"gelf\""
+						required:      false
+						type: object: options: lossy: {
+							description: """
+								Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+								When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+								[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+								"""
+							required: false
+							type: bool: default: true
+						}
+					}
+					influxdb: {
+						description:   "Influxdb-specific decoding options."
+						relevant_when: "codec = \"influxdb\""
+						required:      false
+						type: object: options: lossy: {
+							description: """
+								Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+								When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+								[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+								"""
+							required: false
+							type: bool: default: true
+						}
+					}
+					json: {
+						description:   "JSON-specific decoding options."
+						relevant_when: "codec = \"json\""
+						required:      false
+						type: object: options: lossy: {
+							description: """
+								Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+								When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+								[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+								"""
+							required: false
+							type: bool: default: true
+						}
+					}
+					native_json: {
+						description:   "Vector's native JSON-specific decoding options."
+						relevant_when: "codec = \"native_json\""
+						required:      false
+						type: object: options: lossy: {
+							description: """
+								Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+								When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+								[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+								"""
+							required: false
+							type: bool: default: true
+						}
+					}
+					protobuf: {
+						description:   "Protobuf-specific decoding options."
+						relevant_when: "codec = \"protobuf\""
+						required:      false
+						type: object: options: {
+							desc_file: {
+								description: "Path to desc file"
+								required:    false
+								type: string: default: ""
+							}
+							message_type: {
+								description: "message type. e.g package.message"
+								required:    false
+								type: string: default: ""
+							}
+						}
+					}
+					syslog: {
+						description:   "Syslog-specific decoding options."
+						relevant_when: "codec = \"syslog\""
+						required:      false
+						type: object: options: lossy: {
+							description: """
+								Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+								When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+								[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+								"""
+							required: false
+							type: bool: default: true
+						}
+					}
+					vrl: {
+						description:   "VRL-specific decoding options."
+						relevant_when: "codec = \"vrl\""
+						required:      true
+						type: object: options: {
+							source: {
+								description: """
+																		The [Vector Remap Language][vrl] (VRL) program to execute for each event.
+																		Note that the final contents of the `.` target will be used as the decoding result.
+																		Compilation error or use of 'abort' in a program will result in a decoding error.
+
+																		[vrl]: https://vector.dev/docs/reference/vrl
+																		"""
+								required: true
+								type: string: {}
+							}
+							timezone: {
+								description: """
+																		The name of the timezone to apply to timestamp conversions that do not contain an explicit
+																		time zone. The time zone name may be any name in the [TZ database][tz_database], or `local`
+																		to indicate system local time.
+
+																		If not set, `local` will be used.
+
+																		[tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+																		"""
+								required: false
+								type: string: examples: ["local", "America/New_York", "EST5EDT"]
+							}
+						}
+					}
+				}
+			}
+			framing: {
+				description: """
+					Framing configuration.
+
+					Framing handles how events are separated when encoded in a raw byte form, where each event is
+					a frame that must be prefixed, or delimited, in a way that marks where an event begins and
+					ends within the byte stream.
+					"""
+				required: false
+				type: object: options: {
+					character_delimited: {
+						description:   "Options for the character delimited decoder."
+						relevant_when: "method = \"character_delimited\""
+						required:      true
+						type: object: options: {
+							delimiter: {
+								description: "The character that delimits byte sequences."
+								required:    true
+								type: ascii_char: {}
+							}
+							max_length: {
+								description: """
+																		The maximum length of the byte buffer.
+
+																		This length does *not* include the trailing delimiter.
+
+																		By default, there is no maximum length enforced. If events are malformed, this can lead to
+																		additional resource usage as events continue to be buffered in memory, and can potentially
+																		lead to memory exhaustion in extreme cases.
+
+																		If there is a risk of processing malformed data, such as logs with user-controlled input,
+																		consider setting the maximum length to a reasonably large value as a safety net. This
+																		ensures that processing is not actually unbounded.
+																		"""
+								required: false
+								type: uint: {}
+							}
+						}
+					}
+					length_delimited: {
+						description:   "Options for the length delimited decoder."
+						relevant_when: "method = \"length_delimited\""
+						required:      true
+						type: object: options: {
+							length_field_is_big_endian: {
+								description: "Length field byte order (little or big endian)"
+								required:    false
+								type: bool: default: true
+							}
+							length_field_length: {
+								description: "Number of bytes representing the field length"
+								required:    false
+								type: uint: default: 4
+							}
+							length_field_offset: {
+								description: "Number of bytes in the header before the length field"
+								required:    false
+								type: uint: default: 0
+							}
+							max_frame_length: {
+								description: "Maximum frame length"
+								required:    false
+								type: uint: default: 8388608
+							}
+						}
+					}
+					method: {
+						description: "The framing method."
+						required:    true
+						type: string: enum: {
+							bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+							character_delimited: "Byte frames which are delimited by a chosen character."
+							length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+							newline_delimited:   "Byte frames which are delimited by a newline character."
+							octet_counting: """
+								Byte frames according to the [octet counting][octet_counting] format.
+
+								[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+								"""
+						}
+					}
+					newline_delimited: {
+						description:   "Options for the newline delimited decoder."
+						relevant_when: "method = \"newline_delimited\""
+						required:      false
+						type: object: options: max_length: {
+							description: """
+								The maximum length of the byte buffer.
+
+								This length does *not* include the trailing delimiter.
+
+								By default, there is no maximum length enforced. If events are malformed, this can lead to
+								additional resource usage as events continue to be buffered in memory, and can potentially
+								lead to memory exhaustion in extreme cases.
+
+								If there is a risk of processing malformed data, such as logs with user-controlled input,
+								consider setting the maximum length to a reasonably large value as a safety net. This
+								ensures that processing is not actually unbounded.
+								"""
+							required: false
+							type: uint: {}
+						}
+					}
+					octet_counting: {
+						description:   "Options for the octet counting decoder."
+						relevant_when: "method = \"octet_counting\""
+						required:      false
+						type: object: options: max_length: {
+							description: "The maximum length of the byte buffer."
+							required:    false
+							type: uint: {}
+						}
+					}
+				}
+			}
+			method: {
+				description: """
+					The HTTP method of requests for this route.
+
+					The `method` of the source is used if this is not set.
+					"""
+				required: false
+				type: string: enum: {
+					DELETE:  "HTTP DELETE method."
+					GET:     "HTTP GET method."
+					HEAD:    "HTTP HEAD method."
+					OPTIONS: "HTTP OPTIONS method."
+					PATCH:   "HTTP PATCH method."
+					POST:    "HTTP POST method."
+					PUT:     "HTTP Put method."
+				}
+			}
+			output: {
+				description: """
+					The name of the output that the events received on this route are sent to.
+
+					Events are sent to the default output of the source if this is not set. Otherwise, they can
+					be consumed by using `<source_id>.<output>` as an input.
+					"""
+				required: false
+				type: string: examples: ["logs"]
+			}
+			path: {
+				description: "The URL path on which requests for this route are sent."
+				required:    true
+				type: string: examples: ["/logs", "/metrics"]
+			}
+		}
+	}
 	strict_path: {
 		description: """
 			Whether or not to treat the configured `path` as an absolute path.
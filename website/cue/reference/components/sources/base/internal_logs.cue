@@ -25,4 +25,53 @@ base: components: sources: internal_logs: configuration: {
 		required: false
 		type: string: default: "pid"
 	}
+	severity_overrides: {
+		description: """
+			Overrides of the severity of the internal log events captured from some components or
+			modules.
+
+			The first override that matches an event decides whether it is captured: events of a
+			matching component or module are captured if they are at least as severe as the level of
+			the override, even if they are more verbose than the log level of Vector. Events that no
+			override matches are captured as per the log level of Vector.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				component_id: {
+					description: """
+						A pattern matching the IDs of the components whose events are overridden.
+
+						Wildcards (`*`) are supported. If not set, events are matched regardless of their
+						component.
+						"""
+					required: false
+					type: string: examples: ["azure_blob", "kafka_*"]
+				}
+				level: {
+					description: "The least severe level at which the matching events are captured."
+					required:    true
+					type: string: enum: {
+						debug: "The `DEBUG` level."
+						error: "The `ERROR` level."
+						info:  "The `INFO` level."
+						trace: "The `TRACE` level."
+						warn:  "The `WARN` level."
+					}
+				}
+				module_path: {
+					description: """
+						A pattern matching the module paths whose events are overridden.
+
+						The events of submodules are matched as well, and module paths are matched with or without
+						the name of their crate, so `sinks` matches the events of `vector::sinks::http`. Wildcards
+						(`*`) are supported. If not set, events are matched regardless of their module.
+						"""
+					required: false
+					type: string: examples: ["sinks", "sources::azure_blob"]
+				}
+			}
+		}
+	}
 }
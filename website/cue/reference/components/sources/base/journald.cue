@@ -53,6 +53,16 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: string: examples: ["/var/lib/vector"]
 	}
+	decode_trusted_fields: {
+		description: """
+			Decode the trusted fields that hold integers, such as `_PID`, `_UID`, and `_GID`, into
+			integers rather than strings.
+
+			Other fields, such as `_SYSTEMD_UNIT`, are kept as strings.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	emit_cursor: {
 		description: """
 			Whether to emit the [__CURSOR field][cursor]. See also [sd_journal_get_cursor][get_cursor].
@@ -63,6 +73,18 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	exclude_fields: {
+		description: """
+			A list of journal fields to remove from events.
+
+			`__CURSOR` and `PRIORITY` are never removed.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["_CMDLINE", "_CAP_EFFECTIVE"]
+		}
+	}
 	exclude_matches: {
 		description: """
 			A list of sets of field/value pairs that, if any are present in a journal entry,
@@ -108,6 +130,23 @@ base: components: sources: journald: configuration: {
 			items: type: string: examples: ["--merge"]
 		}
 	}
+	include_fields: {
+		description: """
+			A list of journal fields to keep in events.
+
+			If empty or not present, all fields are kept. `MESSAGE`, `_HOSTNAME`, and the timestamp
+			fields are always kept, as they are used for the message, host, and timestamp of events.
+			`__CURSOR` and `PRIORITY` are kept as well, even if they are excluded.
+
+			Entries are matched against `include_matches` and `exclude_matches` before their fields
+			are selected.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["_SYSTEMD_UNIT", "_PID"]
+		}
+	}
 	include_matches: {
 		description: """
 			A list of sets of field/value pairs to monitor.
@@ -185,6 +224,18 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	resume_across_boots: {
+		description: """
+			Resume from the checkpoint even if it is from a previous boot of the system.
+
+			By default, with `current_boot_only`, the entries that are appended to the journal between
+			the checkpoint and a reboot are never read. When enabled, the source resumes from the
+			checkpoint, replaying the rest of the previous boots before following the current boot, and
+			`current_boot_only` only applies when there is no checkpoint yet.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	since_now: {
 		description: "Only include entries that appended to the journal after the entries have been read."
 		required:    false
@@ -99,6 +99,19 @@ base: components: sources: kafka: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -143,6 +156,14 @@ base: components: sources: kafka: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -442,6 +463,29 @@ base: components: sources: kafka: configuration: {
 		required:    true
 		type: string: examples: ["consumer-group-name"]
 	}
+	header_filter: {
+		description: """
+			Only consume messages whose headers have one of the given values.
+
+			A message must match every header in this map, and it matches a header when it has that
+			header with any of the listed values. Messages that don't match are skipped without being
+			decoded, and their offsets are committed as if they had been delivered.
+
+			This allows topics shared by multiple tenants to be split between sources without a
+			downstream `filter` transform.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				tenant: ["tenant-a", "tenant-b"]
+			}]
+			options: "*": {
+				description: "The values allowed for a message header."
+				required:    true
+				type: array: items: type: string: {}
+			}
+		}
+	}
 	headers_key: {
 		description: """
 			Overrides the name of the log field used to add the headers to each event.
@@ -529,6 +573,70 @@ base: components: sources: kafka: configuration: {
 			examples: ["partition"]
 		}
 	}
+	partitions: {
+		description: """
+			The partitions to consume, instead of subscribing to `topics`.
+
+			The partitions are assigned to this consumer statically, so they are never rebalanced to
+			other consumers of the consumer group, and each partition is consumed from its
+			`start_offset`. The consumer group is still used to commit offsets.
+
+			When this is set, `topics` is ignored.
+			"""
+		required: false
+		type: array: items: type: object: options: {
+			partition: {
+				description: "The partition number."
+				required:    true
+				type: int: examples: [0]
+			}
+			start_offset: {
+				description: "The offset to start consuming a partition from."
+				required:    false
+				type: object: options: {
+					offset: {
+						description:   "The offset of the first message to consume."
+						relevant_when: "type = \"absolute\""
+						required:      true
+						type: int: examples: [42]
+					}
+					timestamp_ms: {
+						description:   "The timestamp, in milliseconds since the Unix epoch."
+						relevant_when: "type = \"timestamp\""
+						required:      true
+						type: int: examples: [1714564800000]
+					}
+					type: {
+						description: "The offset to start consuming the partition from."
+						required:    false
+						type: string: {
+							default: "committed"
+							enum: {
+								absolute: "The given offset."
+								committed: """
+									The offset committed for the consumer group.
+
+									If there is no committed offset, the `auto_offset_reset` strategy is used.
+									"""
+								earliest: "The first offset of the partition."
+								latest:   "The offset after the last message of the partition, so that only new messages are consumed."
+								timestamp: """
+									The first offset whose message has a timestamp at or after the given timestamp.
+
+									If there is no such message, only new messages are consumed.
+									"""
+							}
+						}
+					}
+				}
+			}
+			topic: {
+				description: "The topic of the partition."
+				required:    true
+				type: string: examples: ["topic-1"]
+			}
+		}
+	}
 	sasl: {
 		description: "Configuration for SASL authentication when interacting with Kafka."
 		required:    false
@@ -0,0 +1,69 @@
+package metadata
+
+base: components: sources: kubernetes_events: configuration: {
+	field_selector: {
+		description: """
+			Specifies the [field selector][field_selector] to filter the events with.
+
+			[field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["type=Warning", "involvedObject.kind=Pod,type!=Normal"]
+		}
+	}
+	kube_config_file: {
+		description: """
+			Optional path to a readable [kubeconfig][kubeconfig] file.
+
+			If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+			[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+			"""
+		required: false
+		type: string: examples: ["/path/to/.kube/config"]
+	}
+	label_selector: {
+		description: """
+			Specifies the [label selector][label_selector] to filter the events with.
+
+			[label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+			"""
+		required: false
+		type: string: {
+			default: ""
+			examples: ["my_custom_label!=my_value"]
+		}
+	}
+	namespace: {
+		description: """
+			The [namespace][namespace] to watch the events of.
+
+			If not set, the events of all namespaces are watched.
+
+			[namespace]: https://kubernetes.io/docs/concepts/overview/working-with-objects/namespaces/
+			"""
+		required: false
+		type: string: examples: ["default", "kube-system"]
+	}
+	resync_interval_secs: {
+		description: """
+			The interval at which the events are listed again from the Kubernetes API.
+
+			Events that were missed while the watch was interrupted are picked up by the resync. Events
+			that were already emitted are deduplicated by their involved object and reason, and are
+			only emitted again if they occurred again since.
+			"""
+		required: false
+		type: uint: {
+			default: 600
+			unit:    "seconds"
+		}
+	}
+	use_apiserver_cache: {
+		description: "Determines if requests to the kube-apiserver can be served by a cache."
+		required:    false
+		type: bool: default: false
+	}
+}
@@ -133,6 +133,25 @@ base: components: sources: kubernetes_logs: configuration: {
 			unit: "seconds"
 		}
 	}
+	include_ephemeral_containers: {
+		description: """
+			Whether or not to collect the logs of the [ephemeral containers][ephemeral_containers] of
+			Pods, such as the ones added by `kubectl debug`.
+
+			[ephemeral_containers]: https://kubernetes.io/docs/concepts/workloads/pods/ephemeral-containers/
+			"""
+		required: false
+		type: bool: default: true
+	}
+	include_init_containers: {
+		description: """
+			Whether or not to collect the logs of the [init containers][init_containers] of Pods.
+
+			[init_containers]: https://kubernetes.io/docs/concepts/workloads/pods/init-containers/
+			"""
+		required: false
+		type: bool: default: true
+	}
 	include_paths_glob_patterns: {
 		description: "A list of glob patterns to include while reading the files."
 		required:    false
@@ -143,6 +162,18 @@ base: components: sources: kubernetes_logs: configuration: {
 			items: type: string: examples: ["**/include/**"]
 		}
 	}
+	include_previous_container_logs: {
+		description: """
+			Whether or not to collect the logs of the previous instances of restarted containers.
+
+			The `kubelet` keeps the log file of the previous instance of a container after it restarts,
+			such as when it is crash looping. When enabled, the rest of that log file is collected as
+			well, so that the last logs before a crash are available for post-mortem analysis. When
+			disabled, only the log files of the current instances of containers are collected.
+			"""
+		required: false
+		type: bool: default: true
+	}
 	ingestion_timestamp_field: {
 		description: """
 			Overrides the name of the log field used to add the ingestion timestamp to each event.
@@ -294,6 +325,20 @@ base: components: sources: kubernetes_logs: configuration: {
 					examples: [".k8s.container_name", "k8s.container_name", ""]
 				}
 			}
+			container_type: {
+				description: """
+					Event field for the Container's type.
+
+					The type is one of `container`, `init`, or `ephemeral`.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.container_type"
+					examples: [".k8s.container_type", "k8s.container_type", ""]
+				}
+			}
 			pod_annotations: {
 				description: """
 					Event field for the Pod's annotations.
@@ -1,6 +1,27 @@
 package metadata
 
 base: components: sources: nats: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	auth: {
 		description: "Configuration of the authentication strategy when interacting with NATS."
 		required:    false
@@ -142,6 +163,19 @@ base: components: sources: nats: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -186,6 +220,14 @@ base: components: sources: nats: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -456,9 +498,52 @@ base: components: sources: nats: configuration: {
 			}
 		}
 	}
+	jetstream: {
+		description: """
+			Configuration for consuming from a [JetStream][jetstream] stream with a durable pull consumer.
+
+			When set, the `subject` is used as the filter subject of the consumer, and the instances of
+			the source sharing the same durable consumer share the messages of the stream.
+
+			[jetstream]: https://docs.nats.io/nats-concepts/jetstream
+			"""
+		required: false
+		type: object: options: {
+			durable_name: {
+				description: """
+					The name of the durable consumer.
+
+					The consumer is created with explicit acknowledgements if it does not exist. A message is
+					acknowledged once it has been processed, or, when end-to-end acknowledgements are enabled,
+					once it has been delivered by the sinks. Messages that fail to be delivered are redelivered,
+					and messages rejected by the sinks are terminated.
+					"""
+				required: true
+				type: string: examples: ["vector"]
+			}
+			max_in_flight: {
+				description: """
+					The maximum number of messages that are delivered but not yet acknowledged.
+
+					The server stops delivering messages to the consumer once this limit is reached.
+					"""
+				required: false
+				type: uint: default: 1000
+			}
+			stream: {
+				description: "The name of the stream to consume from."
+				required:    true
+				type: string: examples: ["events"]
+			}
+		}
+	}
 	queue: {
-		description: "The NATS queue group to join."
-		required:    false
+		description: """
+			The NATS queue group to join.
+
+			This is ignored when consuming from JetStream.
+			"""
+		required: false
 		type: string: {}
 	}
 	subject: {
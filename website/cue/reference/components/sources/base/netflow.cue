@@ -0,0 +1,33 @@
+package metadata
+
+base: components: sources: netflow: configuration: {
+	address: {
+		description: """
+			The socket address to listen for connections on, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation.
+
+			If a socket address is used, it _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	template_timeout_secs: {
+		description: """
+			How long to keep the templates of an exporter that are not refreshed.
+
+			NetFlow v9 and IPFIX exporters periodically resend their templates, which are needed to
+			decode their flow records. Records whose template has not been received yet, or timed out,
+			are dropped.
+			"""
+		required: false
+		type: uint: {
+			default: 1800
+			unit:    "seconds"
+		}
+	}
+}
@@ -42,17 +42,184 @@ base: components: sources: prometheus_pushgateway: configuration: {
 		type: bool: default: false
 	}
 	auth: {
-		description: "HTTP Basic authentication configuration."
+		description: "HTTP authentication configuration."
 		required:    false
 		type: object: options: {
+			audiences: {
+				description: """
+					The audiences that tokens are accepted for.
+
+					The `aud` claim of tokens must contain at least one of them.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      true
+				type: array: items: type: string: examples: ["vector"]
+			}
+			issuer: {
+				description: """
+					The issuer of the tokens.
+
+					The `iss` claim of tokens must be equal to it.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      true
+				type: string: examples: ["https://accounts.example.com"]
+			}
+			jwks_refresh_interval_secs: {
+				description: """
+					The interval between refreshes of the keys, in seconds.
+
+					The keys are also refreshed when a token is signed with a key that isn't known yet.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: uint: {
+					default: 3600
+					unit:    "seconds"
+				}
+			}
+			jwks_url: {
+				description: """
+					The URL of the JSON Web Key Set (JWKS) with the keys that tokens are signed with.
+
+					If not set, the URL is discovered from the `jwks_uri` of the OpenID Connect configuration
+					of the issuer, at `<issuer>/.well-known/openid-configuration`.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: string: examples: ["https://accounts.example.com/.well-known/jwks.json"]
+			}
+			leeway_secs: {
+				description: """
+					The leeway allowed when checking the `exp` and `nbf` claims of tokens, in seconds.
+
+					This accounts for the clock skew between Vector and the issuer.
+					"""
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
 			password: {
-				description: "The password for basic authentication."
-				required:    true
+				description:   "The password for basic authentication."
+				relevant_when: "strategy = \"basic\""
+				required:      true
 				type: string: examples: ["hunter2", "${PASSWORD}"]
 			}
-			username: {
-				description: "The username for basic authentication."
+			strategy: {
+				description: "The authentication strategy to use. Defaults to `basic` when unset."
 				required:    true
+				type: string: enum: {
+					basic: "HTTP Basic authentication."
+					oidc: """
+						OpenID Connect bearer token authentication.
+
+						Requests must carry a JSON Web Token (JWT) in the `Authorization: Bearer` header, which is
+						validated against the keys published by the issuer.
+						"""
+				}
+			}
+			tls: {
+				description:   "TLS configuration."
+				relevant_when: "strategy = \"oidc\""
+				required:      false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+		
+							Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+							that they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+		
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+		
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+		
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+		
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+		
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					server_name: {
+						description: """
+							Server name to use when using Server Name Indication (SNI).
+		
+							Only relevant for outgoing connections.
+							"""
+						required: false
+						type: string: examples: ["www.example.com"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification. For components that create a server, this requires that the
+							client connections have a valid client certificate. For components that initiate requests,
+							this validates that the upstream has a valid certificate.
+		
+							If enabled, certificates must not be expired and must be issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+							certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+							so on until the verification process reaches a root certificate.
+		
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+		
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+		
+							Only relevant for outgoing connections.
+		
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			username: {
+				description:   "The username for basic authentication."
+				relevant_when: "strategy = \"basic\""
+				required:      true
 				type: string: examples: ["AzureDiamond", "admin"]
 			}
 		}
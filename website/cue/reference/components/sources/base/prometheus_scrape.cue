@@ -58,9 +58,39 @@ base: components: sources: prometheus_scrape: configuration: {
 		type: string: {}
 	}
 	endpoints: {
-		description: "Endpoints to scrape metrics from."
-		required:    true
-		type: array: items: type: string: examples: ["http://localhost:9090/metrics"]
+		description: """
+			Endpoints to scrape metrics from.
+
+			The endpoints are scraped along with the targets discovered by `file_sd_configs` and
+			`kubernetes_sd_configs`.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["http://localhost:9090/metrics"]
+		}
+	}
+	file_sd_configs: {
+		description: "Files listing targets to scrape, in the format of Prometheus' `file_sd_configs`."
+		required:    false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				files: {
+					description: "The paths of the files listing the targets."
+					required:    true
+					type: array: items: type: string: examples: ["/etc/vector/targets.json"]
+				}
+				refresh_interval_secs: {
+					description: "The interval between reads of the files."
+					required:    false
+					type: uint: {
+						default: 300
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 	}
 	honor_labels: {
 		description: """
@@ -83,6 +113,74 @@ base: components: sources: prometheus_scrape: configuration: {
 		required: false
 		type: string: {}
 	}
+	kubernetes_sd_configs: {
+		description: "Kubernetes objects to scrape, in the manner of Prometheus' `kubernetes_sd_configs`."
+		required:    false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				kube_config_file: {
+					description: """
+						Optional path to a readable [kubeconfig][kubeconfig] file.
+
+						If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+						[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+						"""
+					required: false
+					type: string: examples: ["/path/to/.kube/config"]
+				}
+				label_selector: {
+					description: "The label selector restricting the discovered objects."
+					required:    false
+					type: string: examples: ["app=web"]
+				}
+				namespaces: {
+					description: """
+						The namespaces to discover the objects in.
+
+						The objects of all namespaces are discovered if this is empty.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["default"]
+					}
+				}
+				refresh_interval_secs: {
+					description: "The interval between listings of the objects."
+					required:    false
+					type: uint: {
+						default: 30
+						unit:    "seconds"
+					}
+				}
+				role: {
+					description: "The kind of Kubernetes objects discovered as targets."
+					required:    false
+					type: string: {
+						default: "pod"
+						enum: {
+							pod:     "Discovers a target for each declared container port of the running pods."
+							service: "Discovers a target for each port of the services, addressed by their DNS name."
+						}
+					}
+				}
+				use_annotations: {
+					description: """
+						Whether the `prometheus.io/*` annotations of the objects are honored.
+
+						If `true`, only the objects annotated with `prometheus.io/scrape: "true"` are discovered,
+						and the `prometheus.io/port`, `prometheus.io/path`, and `prometheus.io/scheme` annotations
+						override the port, path, and scheme of their target. If `false`, all the objects are
+						discovered, and can be filtered with `relabel_configs`.
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+		}
+	}
 	query: {
 		description: """
 			Custom parameters for the scrape request query string.
@@ -103,6 +201,83 @@ base: components: sources: prometheus_scrape: configuration: {
 			}
 		}
 	}
+	relabel_configs: {
+		description: """
+			Rules rewriting the labels of the targets before they are scraped, in the format of
+			Prometheus' `relabel_configs`.
+
+			The `__scheme__`, `__address__`, `__metrics_path__`, `__scrape_interval__`, and
+			`__param_<name>` labels of a target set the URL and interval of its scrapes. The labels not
+			prefixed with `__` are added as tags to the scraped metrics.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				action: {
+					description: "The action performed by a relabeling rule."
+					required:    false
+					type: string: {
+						default: "replace"
+						enum: {
+							drop:      "Drops the targets whose concatenated `source_labels` match `regex`."
+							keep:      "Drops the targets whose concatenated `source_labels` don't match `regex`."
+							labeldrop: "Removes the labels whose names match `regex`."
+							labelkeep: "Removes the labels whose names don't match `regex`."
+							labelmap: """
+								Copies the values of the labels whose names match `regex` to the labels named by
+								`replacement`.
+								"""
+							replace: """
+								Sets `target_label` to `replacement` if `regex` matches the concatenated `source_labels`.
+
+								The capture groups of `regex` can be referenced in `replacement`, such as `$1`.
+								"""
+						}
+					}
+				}
+				regex: {
+					description: """
+						The regular expression matched against the concatenated `source_labels`, or against the
+						label names for the `labelmap`, `labeldrop`, and `labelkeep` actions.
+
+						The expression is anchored at both ends.
+						"""
+					required: false
+					type: string: {
+						default: "(.*)"
+						examples: ["__meta_kubernetes_pod_label_(.+)"]
+					}
+				}
+				replacement: {
+					description: """
+						The value the `replace` and `labelmap` actions set, in which the capture groups of
+						`regex` can be referenced.
+						"""
+					required: false
+					type: string: default: "$1"
+				}
+				separator: {
+					description: "The separator placed between the values of the `source_labels`."
+					required:    false
+					type: string: default: ";"
+				}
+				source_labels: {
+					description: "The labels whose values are concatenated, with `separator`, and matched against `regex`."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["__meta_kubernetes_namespace"]
+					}
+				}
+				target_label: {
+					description: "The label set by the `replace` action."
+					required:    false
+					type: string: examples: ["namespace"]
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: """
 			The interval between scrapes. Requests are run concurrently so if a scrape takes longer
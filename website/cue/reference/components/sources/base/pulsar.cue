@@ -148,6 +148,19 @@ base: components: sources: pulsar: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -192,6 +205,14 @@ base: components: sources: pulsar: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -483,9 +504,54 @@ base: components: sources: pulsar: configuration: {
 		required:    false
 		type: string: examples: ["subscription_name"]
 	}
+	subscription_type: {
+		description: "The type of the Pulsar subscription."
+		required:    false
+		type: string: {
+			default: "shared"
+			enum: {
+				exclusive: "Only one consumer of the subscription is allowed."
+				failover:  "Messages are delivered to one consumer of the subscription, and to another one if it fails."
+				key_shared: """
+					Messages are distributed among the consumers of the subscription, with all of the messages
+					of a key delivered to the same consumer, in order.
+					"""
+				shared: "Messages are distributed among the consumers of the subscription."
+			}
+		}
+	}
 	topics: {
-		description: "The Pulsar topic names to read events from."
-		required:    true
-		type: array: items: type: string: examples: ["[persistent://public/default/my-topic]"]
+		description: """
+			The Pulsar topic names to read events from.
+
+			Either `topics` or `topics_pattern` must be set.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["[persistent://public/default/my-topic]"]
+		}
+	}
+	topics_pattern: {
+		description: """
+			A regular expression matching the names of the topics to read events from.
+
+			The topics are discovered in the namespace of the pattern, such as `public/default` for
+			`persistent://public/default/logs-.*`, which is also the namespace of patterns that don't
+			start with a namespace. Topics that are created later are subscribed to as they are
+			discovered.
+
+			Either `topics` or `topics_pattern` must be set.
+			"""
+		required: false
+		type: string: examples: ["persistent://public/default/logs-.*"]
+	}
+	topics_refresh_secs: {
+		description: "The interval between discoveries of the topics matching `topics_pattern`, in seconds."
+		required:    false
+		type: uint: {
+			default: 60
+			unit:    "seconds"
+		}
 	}
 }
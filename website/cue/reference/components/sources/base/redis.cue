@@ -1,8 +1,29 @@
 package metadata
 
 base: components: sources: redis: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	data_type: {
-		description: "The Redis data type (`list` or `channel`) to use."
+		description: "The Redis data type (`list`, `channel`, or `stream`) to use."
 		required:    false
 		type: string: {
 			default: "list"
@@ -13,6 +34,15 @@ base: components: sources: redis: configuration: {
 					This is based on Redis' Pub/Sub capabilities.
 					"""
 				list: "The `list` data type."
+				stream: """
+					The `stream` data type.
+
+					This is based on Redis Streams, read with a consumer group. Entries are acknowledged once
+					their events have been delivered, so that entries are read again, by this consumer or
+					another one, if Vector stops before delivering them.
+
+					Requires Redis 6.2 or later.
+					"""
 			}
 		}
 	}
@@ -60,6 +90,19 @@ base: components: sources: redis: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -104,6 +147,14 @@ base: components: sources: redis: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -375,7 +426,11 @@ base: components: sources: redis: configuration: {
 		}
 	}
 	key: {
-		description: "The Redis key to read messages from."
+		description: """
+			The Redis key to read messages from.
+
+			This is the list to pop from, the channel to subscribe to, or the stream to read.
+			"""
 		required:    true
 		type: string: examples: [
 			"vector",
@@ -404,6 +459,77 @@ base: components: sources: redis: configuration: {
 		required: false
 		type: string: examples: ["redis_key"]
 	}
+	stream: {
+		description: "Options for the Redis `stream` data type."
+		required:    false
+		type: object: options: {
+			batch_size: {
+				description: "The maximum number of entries to read at once."
+				required:    false
+				type: uint: default: 100
+			}
+			consumer: {
+				description: """
+					The name of the consumer in the consumer group.
+
+					Each instance of Vector reading the stream with the same group must use a different name.
+
+					By default, the hostname is used.
+					"""
+				required: false
+				type: string: examples: ["vector-0"]
+			}
+			field: {
+				description: """
+					The field of the stream entries that holds the message to decode.
+
+					Entries without this field are logged and acknowledged.
+					"""
+				required: false
+				type: string: default: "message"
+			}
+			group: {
+				description: """
+					The consumer group to read the stream with.
+
+					The group, and the stream, are created if they don't exist.
+					"""
+				required: false
+				type: string: {
+					default: "vector"
+					examples: ["vector"]
+				}
+			}
+			min_idle_time_secs: {
+				description: """
+					The time after which the entries read by another consumer of the group, but never
+					acknowledged, are claimed by this consumer, in seconds.
+
+					This is also the interval between checks for entries to claim, and between reads of the
+					entries of this consumer whose events failed to be delivered.
+					"""
+				required: false
+				type: uint: {
+					default: 300
+					unit:    "seconds"
+				}
+			}
+			start_id: {
+				description: """
+					The ID of the entry after which the consumer group starts reading the stream, when the group
+					is created.
+
+					`$` reads only the entries added after the group is created, and `0` reads all of the
+					entries of the stream.
+					"""
+				required: false
+				type: string: {
+					default: "$"
+					examples: ["$", "0"]
+				}
+			}
+		}
+	}
 	url: {
 		description: """
 			The Redis URL to connect to.
@@ -0,0 +1,19 @@
+package metadata
+
+base: components: sources: sflow: configuration: {
+	address: {
+		description: """
+			The socket address to listen for connections on, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation.
+
+			If a socket address is used, it _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+}
@@ -62,6 +62,19 @@ base: components: sources: socket: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						event_hub_capture: """
+															Decodes the raw bytes as an [Event Hubs Capture][event_hub_capture] Avro file, emitting one
+															event per captured event.
+
+															The body of each captured event is decoded as the message, and the time that it was enqueued
+															at as the timestamp of the event. Its sequence number, offset, and properties are added as
+															fields of the event.
+
+															Each byte frame must contain a complete Avro file, so this codec is typically combined with
+															`bytes` framing.
+
+															[event_hub_capture]: https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-capture-overview
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -106,6 +119,14 @@ base: components: sources: socket: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						parquet: """
+															Decodes the raw bytes as an [Apache Parquet][apache_parquet] file, emitting one event per row.
+
+															Each byte frame must contain a complete Parquet file, so this codec is typically combined
+															with `bytes` framing.
+
+															[apache_parquet]: https://parquet.apache.org/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -512,8 +533,13 @@ base: components: sources: socket: configuration: {
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
 			client_metadata_key: {
-				description: "Event field for client certificate metadata."
-				required:    false
+				description: """
+					Event field for client certificate metadata.
+
+					The metadata contains the `subject` of the client certificate, and when present, its
+					`subject_alt_names` and its SHA-256 fingerprint (`fingerprint_sha256`).
+					"""
+				required: false
 				type: string: {}
 			}
 			crt_file: {
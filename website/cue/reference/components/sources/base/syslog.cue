@@ -67,6 +67,24 @@ base: components: sources: syslog: configuration: {
 				"""
 		}
 	}
+	parsing: {
+		description: "How strictly messages are parsed."
+		required:    false
+		type: string: {
+			default: "lenient"
+			enum: {
+				lenient: """
+					Accept both RFC 5424 and RFC 3164 messages, and recover from malformed messages where
+					possible.
+					"""
+				strict: """
+					Only accept RFC 5424 messages with well-formed structured data.
+
+					Messages with invalid or duplicated SD-IDs or invalid parameter names are rejected.
+					"""
+			}
+		}
+	}
 	path: {
 		description: """
 			The Unix socket path.
@@ -129,8 +147,13 @@ base: components: sources: syslog: configuration: {
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
 			client_metadata_key: {
-				description: "Event field for client certificate metadata."
-				required:    false
+				description: """
+					Event field for client certificate metadata.
+
+					The metadata contains the `subject` of the client certificate, and when present, its
+					`subject_alt_names` and its SHA-256 fingerprint (`fingerprint_sha256`).
+					"""
+				required: false
 				type: string: {}
 			}
 			crt_file: {
@@ -214,4 +237,16 @@ base: components: sources: syslog: configuration: {
 			}
 		}
 	}
+	typed_structured_data: {
+		description: """
+			Whether to decode the structured data parameters into typed values.
+
+			Parameters that are repeated within an SD-ELEMENT are decoded into arrays, and the parameters
+			of the SD-IDs registered by RFC 5424 (`timeQuality` and `meta`) are decoded into booleans and
+			integers. Otherwise, every parameter is decoded as a string, and only the last value of a
+			repeated parameter is kept.
+			"""
+		required: false
+		type: bool: default: false
+	}
 }
@@ -135,6 +135,23 @@ base: components: sources: vector: configuration: {
 			}
 		}
 	}
+	tls_reload_interval_secs: {
+		description: """
+			The interval at which the TLS certificate, key, and CA files are checked for changes, in
+			seconds.
+
+			When the files change, they are reloaded, so that certificates can be rotated without
+			restarting Vector. Connections that are already established are kept, and new connections
+			are accepted with the reloaded certificates.
+
+			If not set, the files are only read when the source is built.
+			"""
+		required: false
+		type: uint: {
+			examples: [60]
+			unit: "seconds"
+		}
+	}
 	version: {
 		description: "Version of the configuration."
 		required:    false
@@ -0,0 +1,98 @@
+package metadata
+
+base: components: sources: windows_eventlog: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	batch_size: {
+		description: """
+			The event log is read in batches, and the bookmark is persisted at the end of each batch.
+
+			This option limits the size of the batch.
+			"""
+		required: false
+		type: uint: {
+			default: 100
+			unit:    "events"
+		}
+	}
+	channels: {
+		description: """
+			The channels to read events from.
+
+			The available channels can be listed with `wevtutil enum-logs`.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["System", "Application", "Security", "Microsoft-Windows-Sysmon/Operational"]
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the bookmark of the last event read.
+
+			By default, the [global `data_dir` option][global_data_dir] is used.
+			Make sure the running user has write permissions to this directory.
+
+			If this directory is specified, then Vector will attempt to create it.
+
+			[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+			"""
+		required: false
+		type: string: examples: ["C:\\ProgramData\\vector"]
+	}
+	include_xml: {
+		description: "Include the XML rendering of events in the `xml` field."
+		required:    false
+		type: bool: default: false
+	}
+	query: {
+		description: """
+			An [XPath query][xpath] to select the events to read from each channel.
+
+			By default, all events are read.
+
+			[xpath]: https://learn.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations
+			"""
+		required: false
+		type: string: {
+			default: "*"
+			examples: ["*[System[(Level=1 or Level=2 or Level=3)]]", "*[System[EventID=4624]]"]
+		}
+	}
+	read_existing_events: {
+		description: """
+			Read the events that were logged before Vector started.
+
+			By default, only the events that are logged from now on are read. Once a bookmark has been
+			persisted, the source always resumes after it.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	render_message: {
+		description: """
+			Render the message of events, using the message tables of their providers.
+
+			Events whose provider is not installed on this host have no message.
+			"""
+		required: false
+		type: bool: default: true
+	}
+}
@@ -0,0 +1,125 @@
+package metadata
+
+components: sources: database: {
+	title: "Database"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			checkpoint: enabled: true
+			from: {
+				service: {
+					name:     "PostgreSQL or MySQL Server"
+					thing:    "a \(name)"
+					url:      urls.postgresql
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	support: {
+		requirements: [
+			"""
+				The user of the `endpoint` URL must be allowed to run the configured query.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.database.configuration
+
+	output: logs: row: {
+		description: "A row returned by the query."
+		fields: {
+			"*": {
+				description: "The columns of the row, named after the columns of the query."
+				required:    true
+				type: "*": {}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the row was read."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["database"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				The `database` source runs `query` against the [PostgreSQL](\(urls.postgresql)) or
+				[MySQL](\(urls.mysql)) database of the `endpoint` URL every `poll_interval_secs`, and emits
+				each row returned as a log event whose fields are the columns of the row. The database is
+				selected by the scheme of the URL. A failed query is logged and run again on the next poll.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				When `tracking_column` is set, the greatest value of that column among the rows read is kept
+				as the checkpoint, and is bound to each `{{ checkpoint }}` placeholder of the query as a
+				query parameter, so that the query only returns the rows past the checkpoint:
+
+				```sql
+				SELECT * FROM audit_log WHERE id > {{ checkpoint }} ORDER BY id LIMIT 1000
+				```
+
+				The tracking column must only ever increase as rows are inserted, such as an
+				auto-incremented id (`tracking_column_type = "integer"`) or an insertion timestamp
+				(`tracking_column_type = "timestamp"`). With PostgreSQL, the type of the parameter is
+				inferred from the query, and can be set with a cast such as `{{ checkpoint }}::bigint`.
+
+				The checkpoint is persisted under the `data_dir`, so that rows are not read again after
+				Vector restarts. When acknowledgements are enabled, the checkpoint only moves past rows once
+				their events have been delivered, so rows whose events could not be delivered are read again
+				on the next poll.
+				"""
+		}
+		column_types: {
+			title: "Column types"
+			body: """
+				Integer, floating point, boolean, text, binary, and JSON columns are emitted as the matching
+				event values, and timestamp columns as timestamps in UTC. PostgreSQL columns of other types,
+				such as `numeric` or `uuid`, are emitted as `null` with a warning, and can be cast to `text`
+				in the query. MySQL `DECIMAL` columns are emitted as strings.
+				"""
+		}
+	}
+}
@@ -109,6 +109,32 @@ components: sources: exec: {
 				[`maximum_buffer_size_bytes`](#maximum_buffer_size_bytes) is reached.
 				"""
 		}
+		restarts: {
+			title: "Restarting Streaming Commands"
+			body: """
+				In `streaming` mode, the [`restart_policy`](#streaming.restart_policy) decides whether a
+				command that exited is run again: `always`, only `on_failure`, when it exits with a
+				non-zero status or is killed by a signal, or `never`.
+
+				The command is rerun after [`respawn_interval_secs`](#streaming.respawn_interval_secs).
+				If [`max_respawn_interval_secs`](#streaming.max_respawn_interval_secs) is set, this delay
+				doubles each time the command exits, up to that value, so that a failing command is not
+				restarted in a tight loop. The delay is reset once the command stays up for longer than
+				`max_respawn_interval_secs`.
+
+				The number of restarts is available to the command through the `{{ restart_count }}`
+				placeholder of the [`environment`](#environment) variables.
+				"""
+		}
+		stderr_output: {
+			title: "Separate stderr Output"
+			body: """
+				By default, the events generated from the stdout and stderr of the command are sent to
+				the same output, and can be told apart by their `data_stream` field. If
+				[`stderr_output`](#stderr_output) is `true`, the events generated from stderr are sent
+				to a separate output instead, that can be referenced as `<source_id>.stderr`.
+				"""
+		}
 		shutdown: {
 			title: "Shutting Down"
 			body: """
@@ -117,8 +143,10 @@ components: sources: exec: {
 
 				On *nix platforms, Vector will issue a SIGTERM to the child process, allowing it to
 				gracefully shutdown, and the source will continue reading until the process exits or
-				Vector's shutdown grace period expires. The duration of the grace period can be
-				configured using `--graceful-shutdown-limit-secs`.
+				the [`termination_grace_period_secs`](#termination_grace_period_secs) expire, after
+				which the process is killed. Vector's own shutdown grace period, configured using
+				`--graceful-shutdown-limit-secs`, still applies, so the termination grace period
+				should be shorter.
 
 				On Windows, the subprocess will be issued a SIGKILL and terminate abruptly. In the
 				future we hope to support graceful shutdown of Windows processes as well.
@@ -0,0 +1,125 @@
+package metadata
+
+components: sources: gcp_cloud_storage: {
+	title: "GCP Cloud Storage"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: false
+			proxy: enabled:      true
+			from: service:       services.gcp_cloud_storage
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The GCP Cloud Storage source requires a Pub/Sub subscription that receives the
+				[notifications](\(urls.gcs_pubsub_notifications)) of the desired buckets.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.gcp_cloud_storage.configuration
+
+	output: logs: object: {
+		description: "A line from a GCS object."
+		fields: {
+			message: {
+				description: "A line from the GCS object."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The Last-Modified time of the object. Defaults the current timestamp if this information is missing."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["gcp_cloud_storage"]
+				}
+			}
+			bucket: {
+				description: "The bucket of the object the line came from."
+				required:    true
+				type: string: {
+					examples: ["my-bucket"]
+				}
+			}
+			object: {
+				description: "The object the line came from."
+				required:    true
+				type: string: {
+					examples: ["logs/2024/05/01/app.log.gz"]
+				}
+			}
+			generation: {
+				description: "The generation of the object the line came from."
+				required:    false
+				type: string: {
+					examples: ["1714564800000000"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		notifications: {
+			title: "Bucket notifications"
+			body: """
+				The `gcp_cloud_storage` source pulls [bucket notifications](\(urls.gcs_pubsub_notifications))
+				from a Pub/Sub subscription, and reads the object of each `OBJECT_FINALIZE` notification.
+				Notifications for other events, such as deletions, are acknowledged without reading anything.
+
+				The generation of the object named by the notification is read, so an object that is
+				overwritten before it is read is not read twice. The custom metadata of the object is added
+				to each event under `metadata`.
+				"""
+		}
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				A notification is only acknowledged once the events of its object have been delivered, or
+				immediately after they are sent when no sink has acknowledgements enabled. Notifications that
+				are not acknowledged, such as when an object cannot be read, are redelivered by Pub/Sub once
+				their `ack_deadline_secs` expires.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		gcp_cloud_storage_notification_ignored_total: components.sources.internal_metrics.output.metrics.gcp_cloud_storage_notification_ignored_total
+	}
+}
@@ -0,0 +1,92 @@
+package metadata
+
+components: sources: grpc: {
+	_port: 50051
+
+	title: "gRPC"
+
+	description: """
+		Receives messages from gRPC clients, for a service defined by a compiled Protobuf descriptor set.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.grpc
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.grpc.configuration
+
+	output: logs: message: {
+		description: "A request message."
+		fields: {
+			method: {
+				description: "The name of the method the message was sent to."
+				required:    true
+				type: string: examples: ["Push"]
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["grpc"]
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: "The fields of the request message, as defined by the descriptor set."
+				required:    false
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		streaming: {
+			title: "Unary and streaming methods"
+			body: """
+				Each request message is decoded into an event, whatever the kind of the method. Methods
+				with a single response, unary or client streaming, respond with an empty response message
+				once all the request messages are delivered. Methods with streamed responses, server
+				streaming or bidirectional, respond with an empty response message per delivered request
+				message.
+				"""
+		}
+	}
+}
@@ -74,6 +74,13 @@ components: sources: heroku_logs: {
 					examples: ["erlang"]
 				}
 			}
+			drain_name: {
+				description: "The name of the drain that sent the event, if `drains` are configured."
+				required:    false
+				type: string: {
+					examples: ["my-app"]
+				}
+			}
 			host: fields._local_host
 			message: {
 				description: "The message field, containing the plain text message."
@@ -100,6 +107,23 @@ components: sources: heroku_logs: {
 		}
 	}
 
+	how_it_works: {
+		drains: {
+			title: "Drains"
+			body: """
+				Logplex sends the token of the drain that logs are forwarded through in the
+				`Logplex-Drain-Token` header of each request. The token is added to the metadata of the
+				events as `drain_token` when the `vector` log namespace is used.
+
+				By configuring `drains`, one `heroku_logs` source can receive the logs of many Heroku
+				apps while keeping them apart: requests with an unknown drain token are rejected, the
+				name of the drain is added to the events as `drain_name`, and the events of each drain
+				are sent to the output of that name, such as `<source_id>.my-app`, so that each app can
+				be routed to its own transforms and sinks.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		http_server_handler_duration_seconds: components.sources.internal_metrics.output.metrics.http_server_handler_duration_seconds
 		http_server_requests_received_total:  components.sources.internal_metrics.output.metrics.http_server_requests_received_total
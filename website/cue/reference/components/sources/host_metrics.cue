@@ -128,6 +128,16 @@ components: sources: host_metrics: {
 		filesystem_used_bytes: _host & _filesystem_bytes & {description: "The number of bytes used on the named filesystem."}
 		filesystem_used_ratio: _host & _filesystem_bytes & {description: "The ratio between used and total bytes on the named filesystem."}
 
+		// Host GPUs
+		gpu_memory_free_bytes: _host & _gpu_gauge & {description: "The number of bytes of free memory on the GPU."}
+		gpu_memory_total_bytes: _host & _gpu_gauge & {description: "The total number of bytes of memory on the GPU."}
+		gpu_memory_used_bytes: _host & _gpu_gauge & {description: "The number of bytes of used memory on the GPU."}
+		gpu_memory_utilization_ratio: _host & _gpu_gauge & {description: "The ratio of time the GPU memory was read or written during the last sample period."}
+		gpu_power_limit_watts: _host & _gpu_gauge & {description: "The power limit enforced on the GPU, in watts."}
+		gpu_power_usage_watts: _host & _gpu_gauge & {description: "The power drawn by the GPU, in watts."}
+		gpu_temperature_celsius: _host & _gpu_gauge & {description: "The temperature of the GPU die, in degrees Celsius."}
+		gpu_utilization_ratio: _host & _gpu_gauge & {description: "The ratio of time one or more kernels were running on the GPU during the last sample period."}
+
 		// Host load
 		load1: _host & _loadavg & {description: "System load averaged over the last 1 minute."}
 		load5: _host & _loadavg & {description: "System load averaged over the last 5 minutes."}
@@ -167,6 +177,28 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total: _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_count: _host & _process_gauge & {
+			description:   "The number of running processes with this name."
+			relevant_when: "`process.group_by_name` is `true`"
+		}
+		process_cpu_seconds_total: _host & _process_counter & {
+			description: "The CPU time spent by the process, in seconds. Reported as a gauge if `process.group_by_name` is `true`."
+			tags: mode: {
+				description: "Whether the time was spent in user or system mode."
+				required:    true
+				examples: ["user", "system"]
+			}
+		}
+		process_io_read_bytes_total: _host & _process_linux_counter & {description: "The number of bytes read from storage by the process. Reported as a gauge if `process.group_by_name` is `true`."}
+		process_io_written_bytes_total: _host & _process_linux_counter & {description: "The number of bytes written to storage by the process. Reported as a gauge if `process.group_by_name` is `true`."}
+		process_memory_rss_bytes: _host & _process_gauge & {description: "The resident set size of the process, in bytes."}
+		process_memory_virtual_bytes: _host & _process_gauge & {description: "The virtual memory size of the process, in bytes."}
+		process_open_fds: _host & _process_gauge & {
+			description:   "The number of file descriptors opened by the process."
+			relevant_when: "OS is Linux"
+		}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -216,6 +248,27 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		_gpu_gauge: {
+			type: "gauge"
+			tags: _host_metrics_tags & {
+				collector: examples: ["gpu"]
+				device: {
+					description: "The index of the GPU."
+					required:    true
+					examples: ["0", "1"]
+				}
+				model: {
+					description: "The product name of the GPU."
+					required:    false
+					examples: ["NVIDIA A100-SXM4-40GB"]
+				}
+				uuid: {
+					description: "The globally unique identifier of the GPU."
+					required:    false
+					examples: ["GPU-5b8e6c2a-1f3d-4e7a-9c0b-2d4f6a8e1c3b"]
+				}
+			}
+		}
 		_loadavg: {
 			type: "gauge"
 			tags: _host_metrics_tags & {
@@ -256,5 +309,27 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_tags: _host_metrics_tags & {
+			collector: examples: ["process"]
+			process_name: {
+				description: "The name of the process."
+				required:    true
+				examples: ["nginx", "postgres"]
+			}
+			pid: {
+				description: "The process ID. Not present if `process.group_by_name` is `true`."
+				required:    false
+				examples: ["1234"]
+			}
+		}
+		_process_counter: {
+			type: "counter"
+			tags: _process_tags
+		}
+		_process_gauge: {
+			type: "gauge"
+			tags: _process_tags
+		}
+		_process_linux_counter: _process_counter & {relevant_when: "OS is Linux"}
 	}
 }
@@ -131,5 +131,29 @@ components: sources: internal_logs: {
 				command-line options. The `internal_logs` source only receives logs that are generated by these options.
 				"""
 		}
+		severity_overrides: {
+			title: "Severity overrides"
+			body: """
+				The `severity_overrides` option captures the logs of some components or modules at a
+				different level than the rest, so that a self-monitoring pipeline can, for example, only
+				receive the warnings of sinks, while receiving the debug logs of a single source:
+
+				```toml
+				[sources.vector_logs]
+				type = "internal_logs"
+
+				[[sources.vector_logs.severity_overrides]]
+				module_path = "sinks"
+				level = "warn"
+
+				[[sources.vector_logs.severity_overrides]]
+				component_id = "azure_blob_in"
+				level = "debug"
+				```
+
+				Overrides that are more verbose than the log level of Vector enable the matching logs for
+				the `internal_logs` sources only, so that they are not written to the console.
+				"""
+		}
 	}
 }
@@ -532,6 +532,21 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		kafka_filtered_messages_total: {
+			description:       "The total number of Kafka messages skipped because their headers did not match the `header_filter`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				topic: {
+					description: "The Kafka topic."
+					required:    true
+				}
+				partition: {
+					description: "The Kafka partition."
+					required:    true
+				}
+			}
+		}
 		files_added_total: {
 			description:       "The total number of files Vector has found to watch."
 			type:              "counter"
@@ -564,6 +579,21 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		gcp_cloud_storage_notification_ignored_total: {
+			description:       "The total number of times a bucket notification was ignored (for an event that was not `OBJECT_FINALIZE`)."
+			type:              "counter"
+			default_namespace: "vector"
+
+			tags: _component_tags & {
+				ignore_type: {
+					description: "The reason for ignoring the bucket notification"
+					required:    true
+					enum: {
+						"invalid_event_type": "The type of invalid event."
+					}
+				}
+			}
+		}
 		grpc_server_messages_received_total: {
 			description:       "The total number of gRPC messages received."
 			type:              "counter"
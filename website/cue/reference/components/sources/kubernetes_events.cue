@@ -0,0 +1,125 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the events of a Kubernetes cluster from the Kubernetes Events API, such as
+		scheduling decisions, image pulls, and container crashes.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.kubernetes
+
+				interface: {
+					socket: {
+						api: {
+							title: "Kubernetes API"
+							url:   urls.kubernetes_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+			"""
+				This source requires the permission to `list` and `watch` the `events` resources of
+				the watched namespaces.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: "kubernetes"
+	}
+
+	configuration: base.components.sources.kubernetes_events.configuration
+
+	output: logs: event: {
+		description: "A Kubernetes event, with the fields of the `Event` resource of the Kubernetes API."
+		fields: {
+			message: {
+				description: "The description of the event."
+				required:    false
+				type: string: {
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			reason: {
+				description: "The reason of the event."
+				required:    false
+				type: string: {
+					examples: ["BackOff"]
+				}
+			}
+			involvedObject: {
+				description: "The object that the event is about."
+				required:    true
+				type: object: {
+					examples: [{"kind": "Pod", "namespace": "default", "name": "my-pod"}]
+					options: {}
+				}
+			}
+			count: {
+				description: "The number of times the event occurred."
+				required:    false
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time of the last occurrence of the event."
+			}
+		}
+	}
+
+	how_it_works: {
+		deduplication: {
+			title: "Resync and deduplication"
+			body:  """
+				The source watches the events through the Kubernetes API, and lists all of the
+				events again every `resync_interval_secs`, which picks up the events that were
+				missed while the watch was interrupted. Events are deduplicated by their involved
+				object and reason, so an event is only emitted again when it occurred again since,
+				which is when its count or last timestamp changed.
+
+				The deduplication state is not persisted, so the events still retained by the
+				Kubernetes API are emitted again when Vector restarts.
+				"""
+		}
+	}
+}
@@ -105,6 +105,15 @@ components: sources: kubernetes_logs: {
 					examples: ["coredns"]
 				}
 			}
+			"kubernetes.container_type": {
+				description: "Container type, one of `container`, `init`, or `ephemeral`."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["container", "init"]
+				}
+			}
 			"kubernetes.namespace_labels": {
 				description: "Set of labels attached to the Namespace."
 				required:    false
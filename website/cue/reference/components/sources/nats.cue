@@ -5,7 +5,7 @@ components: sources: nats: {
 
 	features: {
 		auto_generated:   true
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -0,0 +1,126 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					api: {
+						title: "IPFIX"
+						url:   urls.ipfix
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled: false
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.netflow.configuration
+
+	output: logs: record: {
+		description: "A flow record."
+		fields: {
+			host: {
+				description: "The IP address of the exporter."
+				required:    true
+				type: string: examples: ["192.0.2.1"]
+			}
+			record_type: {
+				description: "The type of the record."
+				required:    true
+				type: string: enum: {
+					flow:    "A flow record."
+					options: "An options record, such as the sampling configuration of the exporter."
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the packet, or of the first flow of the packet for NetFlow v5."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["netflow"]
+			}
+			template_id: {
+				description: "The identifier of the template of the record, for NetFlow v9 and IPFIX."
+				required:    false
+				type: uint: {
+					default: null
+					examples: [256]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The export time of the packet."
+			}
+			version: {
+				description: "The version of the protocol: `5` or `9` for NetFlow, and `10` for IPFIX."
+				required:    true
+				type: uint: {
+					examples: [5, 9, 10]
+					unit: null
+				}
+			}
+			"*": {
+				description: """
+					The fields of the record, named after their [IPFIX information element](\(urls.ipfix_information_elements))
+					in snake case, such as `source_ipv4_address` or `octet_delta_count`. Information elements
+					that are not known are named `field_<id>`, or `enterprise_<enterprise number>_<id>`, and their
+					value is hex encoded.
+					"""
+				required: false
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		templates: {
+			title: "Templates"
+			body: """
+				NetFlow v9 and IPFIX records are described by templates, which exporters send periodically.
+				The templates are cached per exporter address and observation domain, or source ID for
+				NetFlow v9, and expire after `template_timeout_secs` unless they are refreshed. Records
+				received before their template are dropped.
+				"""
+		}
+	}
+}
@@ -0,0 +1,117 @@
+package metadata
+
+components: sources: object_store: {
+	title: "S3-Compatible Object Store"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.object_store
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	support: {
+		requirements: [
+			"""
+				The credentials used by the `object_store` source must be allowed to list the bucket and
+				to read its objects.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.object_store.configuration
+
+	output: logs: object: {
+		description: "A line from an object."
+		fields: {
+			message: {
+				description: "A line from the object."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The Last-Modified time of the object. Defaults the current timestamp if this information is missing."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["object_store"]
+				}
+			}
+			bucket: {
+				description: "The bucket of the object the line came from."
+				required:    true
+				type: string: {
+					examples: ["my-logs"]
+				}
+			}
+			object: {
+				description: "The key of the object the line came from."
+				required:    true
+				type: string: {
+					examples: ["app/2024/05/01/out.log.gz"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Instead of relying on bucket notifications, which are not supported by every S3-compatible
+				object store, the `object_store` source [lists](\(urls.s3_list_objects_v2)) the objects of
+				the bucket every `poll_interval_secs`, and reads the objects that are new or that have been
+				overwritten since the previous listing. The objects of a listing are read oldest first.
+
+				For object stores that are not AWS S3, set `endpoint` to the address of the object store.
+				Buckets are always addressed in the path of requests rather than in the host name.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The key and entity tag of each object that has been read are persisted under the `data_dir`,
+				so that objects are not read again after Vector restarts. An object that is overwritten gets
+				a new entity tag and is read again in full. Checkpoints of objects that are no longer listed
+				are dropped.
+
+				When acknowledgements are enabled, an object is only checkpointed once its events have been
+				delivered. Objects whose events could not be delivered are read again on the next poll.
+				"""
+		}
+	}
+}
@@ -39,11 +39,7 @@ components: sources: opentelemetry: {
 
 	support: {
 		requirements: []
-		warnings: [
-			"""
-				The `opentelemetry` source only supports log and trace events at this time.
-				""",
-		]
+		warnings: []
 		notices: []
 	}
 
@@ -66,6 +62,12 @@ components: sources: opentelemetry: {
 				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -238,6 +240,38 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		metrics: {
+			_extra_tags: {
+				"resource.*": {
+					description: "The attributes of the resource that produced the metric."
+					examples: ["checkout"]
+					required: false
+				}
+				"scope.name": {
+					description: "The name of the instrumentation scope of the metric."
+					examples: ["io.opentelemetry.runtime-telemetry-java8"]
+					required: false
+				}
+				"scope.version": {
+					description: "The version of the instrumentation scope of the metric."
+					examples: ["1.32.0"]
+					required: false
+				}
+			}
+
+			counter: output._passthrough_counter & {
+				tags: _extra_tags
+			}
+			gauge: output._passthrough_gauge & {
+				tags: _extra_tags
+			}
+			histogram: output._passthrough_histogram & {
+				tags: _extra_tags
+			}
+			summary: output._passthrough_summary & {
+				tags: _extra_tags
+			}
+		}
 	}
 
 	how_it_works: {
@@ -256,6 +290,18 @@ components: sources: opentelemetry: {
 				Trace support is experimental and subject to change as Vector has no strongly-typed structure for traces internally. Instead traces are stored as a key/value map similar to logs. This may change in the future to be a structured format.
 				"""
 		}
+		metrics: {
+			title: "Ingest OTLP metrics"
+			body: """
+				Each data point of a metric is converted into a metric event, tagged with the attributes of the
+				data point, the attributes of its resource prefixed with `resource.`, and the name and version
+				of its instrumentation scope.
+
+				Monotonic sums are converted into counters, and non-monotonic sums and gauges into gauges.
+				Exponential histograms are converted into histograms with explicit bucket boundaries. Metrics
+				with a delta aggregation temporality are incremental, and the others are absolute.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
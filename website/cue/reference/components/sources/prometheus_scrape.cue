@@ -66,6 +66,25 @@ components: sources: prometheus_scrape: {
 				but will only take the last value for each tag name specified.
 				"""
 		}
+
+		service_discovery: {
+			title: "Service discovery"
+			body: """
+				Besides the static `endpoints`, targets can be discovered from files listing them in the
+				format of Prometheus' `file_sd_configs`, and from the pods or services of a Kubernetes
+				cluster. Kubernetes objects are discovered if they are annotated with
+				`prometheus.io/scrape: "true"`, and the `prometheus.io/port`, `prometheus.io/path`, and
+				`prometheus.io/scheme` annotations override the address of their target. The targets are
+				refreshed periodically: new targets start being scraped, and the targets that are no longer
+				discovered stop being scraped.
+
+				Each target has labels, such as the `__meta_kubernetes_*` labels of the Kubernetes objects,
+				that are rewritten by the `relabel_configs` rules as in Prometheus. The `__scheme__`,
+				`__address__`, `__metrics_path__`, and `__param_<name>` labels set the URL of the target, and
+				the `__scrape_interval__` label, such as `30s`, its own scrape interval. The labels not
+				prefixed with `__` are added as tags to the scraped metrics, following `honor_labels`.
+				"""
+		}
 	}
 
 	output: metrics: {
@@ -157,6 +157,18 @@ components: sources: pulsar: {
 					examples: ["pulsar-client"]
 				}
 			}
+			properties: {
+				description: "The properties of the Pulsar message."
+				required:    true
+				type: object: {
+					examples: [
+						{
+							"origin": "checkout-service"
+						},
+					]
+					options: {}
+				}
+			}
 		}
 	}
 
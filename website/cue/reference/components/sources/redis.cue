@@ -5,7 +5,7 @@ components: sources: redis: {
 
 	features: {
 		auto_generated:   true
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -94,5 +94,20 @@ components: sources: redis: {
 				API.
 				"""
 		}
+		streams: {
+			title: "Redis Streams"
+			body: """
+				With `data_type = "stream"`, the `redis` source reads the [stream](\(urls.redis_streams)) at `key` as a
+				consumer of a consumer group, so that several instances of Vector can share the entries of a stream.
+				The message of each entry is read from its `stream.field` field, and the ID of the entry is
+				added to the source metadata of its events.
+
+				Entries are acknowledged with `XACK` once their events have been delivered, when acknowledgements
+				are enabled, or once their events have been sent otherwise. Entries that were read but never
+				acknowledged are read again: the entries of this consumer when Vector restarts, and the entries
+				of another consumer once they have been pending for `stream.min_idle_time_secs`, as that
+				consumer has presumably stopped. This gives at-least-once delivery, so events may be duplicated.
+				"""
+		}
 	}
 }
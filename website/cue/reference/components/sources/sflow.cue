@@ -0,0 +1,139 @@
+package metadata
+
+components: sources: sflow: {
+	_port: 6343
+
+	title: "sFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.sflow
+				interface: socket: {
+					api: {
+						title: "sFlow v5"
+						url:   urls.sflow_v5
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled: false
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.sflow.configuration
+
+	output: logs: sample: {
+		description: "A flow or counters sample."
+		fields: {
+			agent_address: {
+				description: "The address of the agent, as reported in the datagram."
+				required:    true
+				type: string: examples: ["192.0.2.1"]
+			}
+			agent_uptime: {
+				description: "The time since the agent booted."
+				required:    true
+				type: uint: {
+					examples: [360000]
+					unit: "milliseconds"
+				}
+			}
+			datagram_sequence_number: {
+				description: "The sequence number of the datagram."
+				required:    true
+				type: uint: {
+					examples: [17]
+					unit: null
+				}
+			}
+			host: {
+				description: "The IP address the datagram was received from."
+				required:    true
+				type: string: examples: ["192.0.2.1"]
+			}
+			sample_type: {
+				description: "The type of the sample."
+				required:    true
+				type: string: enum: {
+					flow:     "A sampled packet, with the decoded fields of its headers."
+					counters: "The counters of an interface or of the agent, in the `interface`, `ethernet`, and `processor` objects."
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the sample."
+				required:    true
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+			source_id_index: {
+				description: "The index of the source of the sample, usually the `ifIndex` of an interface."
+				required:    true
+				type: uint: {
+					examples: [5]
+					unit: null
+				}
+			}
+			source_id_type: {
+				description: "The type of the source of the sample: `0` for an interface."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: examples: ["sflow"]
+			}
+			sub_agent_id: {
+				description: "The identifier of the sub-agent that sent the datagram."
+				required:    true
+				type: uint: {
+					examples: [0]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: """
+					The decoded flow records of a flow sample, such as `sampling_rate`, `input_interface`,
+					`source_ip`, `destination_port`, or `vlan_id`. Records in formats that are not known
+					are skipped.
+					"""
+				required: false
+				type: "*": {}
+			}
+		}
+	}
+}
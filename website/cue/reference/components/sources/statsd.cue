@@ -81,6 +81,28 @@ components: sources: statsd: {
 				`null` timestamps are substituted with the current time by downstream sinks or
 				third-party services during sending/ingestion. See the
 				[metric data model](\(urls.vector_metric)) page for more info.
+
+				Metrics that carry a DogStatsD timestamp field, such as `|T1656581400`, are assigned
+				that timestamp instead.
+				"""
+		}
+		dogstatsd: {
+			title: "DogStatsD extensions"
+			body: """
+				The [DogStatsD](\(urls.dogstatsd)) extensions of the protocol are supported, so that
+				applications that emit metrics with a DogStatsD client can send them to Vector
+				unchanged:
+
+				* Tags (`|#env:prod,debug`) are added to the tags of the metric.
+				* The container ID field (`|c:<container_id>`) is added as the `container_id` tag.
+				* Multiple values packed in a message (`foo:1:2:3|d`) are parsed as a single metric,
+				  with a sample for each value of distributions, histograms, and timings.
+				* Distributions (`d`) and histograms (`h`) are emitted as distributions.
+				* Sample rates (`|@0.1`) scale counters, and set the number of observations that each
+				  sample of a distribution stands for.
+
+				The optional fields after the metric type may come in any order, and the fields that
+				are not supported are ignored.
 				"""
 		}
 	}
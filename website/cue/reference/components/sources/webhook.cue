@@ -0,0 +1,162 @@
+package metadata
+
+components: sources: webhook: {
+	_port: 443
+
+	title: "Webhook"
+
+	description: """
+		Receives the webhooks of SaaS providers, such as GitHub, Stripe, and PagerDuty, verifying
+		their signatures and normalizing their payloads.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: {
+					name:     "GitHub, Stripe, or PagerDuty"
+					thing:    "\(name) webhooks"
+					url:      urls.github_webhooks
+					versions: null
+				}
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The `secret` must be the secret that the webhooks are signed with, as configured for the
+				webhook at the provider.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.webhook.configuration
+
+	output: logs: delivery: {
+		description: "A webhook delivery received through an HTTP POST request."
+		fields: {
+			provider: {
+				description: "The provider that sent the webhook."
+				required:    true
+				type: string: {
+					examples: ["github", "stripe", "pagerduty"]
+				}
+			}
+			id: {
+				description: "The ID of the delivery."
+				required:    false
+				type: string: {
+					examples: ["72d3162e-cc78-11e3-81ab-4c9367dc0958", "evt_1NG8Du2eZvKYlo2CUI79vXWy"]
+				}
+			}
+			event_type: {
+				description: "The type of the event that triggered the webhook."
+				required:    false
+				type: string: {
+					examples: ["pull_request.opened", "charge.succeeded", "incident.triggered"]
+				}
+			}
+			actor: {
+				description: "The user or agent that caused the event, if the provider sends it."
+				required:    false
+				type: string: {
+					examples: ["octocat"]
+				}
+			}
+			occurred_at: {
+				description: "The time at which the event occurred, if the provider sends it."
+				required:    false
+				type: timestamp: {}
+			}
+			payload: {
+				description: "The JSON payload of the webhook, as sent by the provider."
+				required:    true
+				type: object: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["webhook"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		signature_verification: {
+			title: "Signature verification"
+			body: """
+				The signature of each webhook is verified with the `secret` shared with the provider, using
+				the HMAC-SHA256 scheme of the provider:
+
+				* [GitHub](\(urls.github_webhooks)) signs the body in the `X-Hub-Signature-256` header.
+				* [Stripe](\(urls.stripe_webhooks)) signs a timestamp and the body in the
+				  `Stripe-Signature` header. Webhooks signed further from the current time than
+				  `replay_window_secs` are rejected.
+				* [PagerDuty](\(urls.pagerduty_webhooks)) signs the body in the `X-PagerDuty-Signature`
+				  header, once for each of the secrets of the subscription while they are rotated.
+
+				Webhooks whose signature can't be verified are rejected with a `401 Unauthorized` response.
+				"""
+		}
+		replay_protection: {
+			title: "Replay protection"
+			body: """
+				The IDs of the deliveries received within the last `replay_window_secs` are kept in memory,
+				and deliveries whose ID was already received are acknowledged with a `200 OK` response, but
+				dropped. As providers retry the deliveries that fail, the ID of a delivery whose event could
+				not be delivered is forgotten, so that its retry is not dropped.
+				"""
+		}
+		normalization: {
+			title: "Normalization"
+			body: """
+				The fields that are common to the webhooks of every provider, such as the ID of the
+				delivery, the type of the event, and its actor, are extracted into the top-level fields of
+				the event, so that audit events can be processed alike regardless of their provider. The
+				JSON payload is kept as it was sent in the `payload` field.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		http_server_handler_duration_seconds: components.sources.internal_metrics.output.metrics.http_server_handler_duration_seconds
+		http_server_requests_received_total:  components.sources.internal_metrics.output.metrics.http_server_requests_received_total
+		http_server_responses_sent_total:     components.sources.internal_metrics.output.metrics.http_server_responses_sent_total
+	}
+}
@@ -0,0 +1,169 @@
+package metadata
+
+components: sources: windows_eventlog: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: true
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: true
+			from: service: services.windows_eventlog
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Reading the `Security` channel requires the running user to be a member of the
+				`Event Log Readers` group, or to run as an administrator or `LocalSystem`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.windows_eventlog.configuration
+
+	output: logs: event: {
+		description: "An event from the Windows Event Log."
+		fields: {
+			host: fields._local_host & {
+				description: "The name of the computer that logged the event."
+			}
+			message: {
+				description: "The rendered message of the event, if `render_message` is enabled and its provider is installed."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["The Windows Update service entered the running state."]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["windows_eventlog"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the event was logged."
+			}
+			provider_name: {
+				description: "The name of the provider that logged the event."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["Service Control Manager"]
+				}
+			}
+			event_id: {
+				description: "The identifier of the event, specific to its provider."
+				required:    false
+				type: uint: {
+					default: null
+					examples: [7036]
+					unit: null
+				}
+			}
+			level: {
+				description: "The level of the event."
+				required:    false
+				type: string: {
+					default: null
+					enum: {
+						critical:    "Critical"
+						error:       "Error"
+						warning:     "Warning"
+						information: "Information"
+						verbose:     "Verbose"
+					}
+				}
+			}
+			channel: {
+				description: "The channel the event was logged to."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["System"]
+				}
+			}
+			computer: {
+				description: "The name of the computer that logged the event."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["host.example.com"]
+				}
+			}
+			record_id: {
+				description: "The record number of the event in its channel."
+				required:    false
+				type: uint: {
+					default: null
+					examples: [123456]
+					unit: null
+				}
+			}
+			event_data: {
+				description: "The `EventData` of the event, keyed by the name of each item, or by its position when unnamed. For events with `UserData` instead, the elements defined by the provider."
+				required:    true
+				type: object: {
+					examples: [{"param1": "Windows Update", "param2": "running"}]
+					options: {}
+				}
+			}
+			xml: {
+				description: "The XML rendering of the event, if `include_xml` is enabled."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["<Event xmlns=\"http://schemas.microsoft.com/win/2004/08/events/event\">...</Event>"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		bookmarks: {
+			title: "Bookmarks"
+			body: """
+				The source persists a bookmark of the last event read in its data directory, and resumes
+				after it when Vector restarts. When acknowledgements are enabled, the bookmark is only
+				persisted once the events are delivered.
+				"""
+		}
+		queries: {
+			title: "Queries"
+			body: """
+				The `query` option is applied to each of the `channels`, and supports the subset of XPath 1.0
+				that the Windows Event Log supports, such as `*[System[(Level=1 or Level=2)]]` to only read
+				critical and error events.
+				"""
+		}
+	}
+}
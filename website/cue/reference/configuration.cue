@@ -559,6 +559,40 @@ configuration: {
 						}
 					}
 				}
+				azure_key_vault: {
+					required: true
+					description: """
+						Retrieve secrets from Azure Key Vault.
+
+						Each secret key refers to the Key Vault secret of the same name, with underscores
+						replaced by dashes as secret names may only contain alphanumeric characters and dashes.
+						For example, `SECRET[vault.connection_string]` is replaced by the value of the
+						`connection-string` secret.
+
+						Access tokens are loaded from the environment, from a managed identity, or from the
+						Azure CLI, in that order. The identity must be allowed to get secrets from the vault.
+
+						As secrets are retrieved before the global options are loaded, requests to the vault
+						are only proxied as set by the `proxy` option of the backend and the `HTTP_PROXY`,
+						`HTTPS_PROXY`, and `NO_PROXY` environment variables.
+
+						If an error occurred retrieving the secrets, Vector logs the error and exits.
+
+						Secrets are loaded when Vector starts or if Vector receives a `SIGHUP` signal triggering its
+						configuration reload process.
+						"""
+					type: object: options: {
+						vault_url: {
+							description: """
+								The URL of the key vault to retrieve secrets from.
+								"""
+							required: true
+							type: string: {
+								examples: ["https://my-vault.vault.azure.net"]
+							}
+						}
+					}
+				}
 			}
 		}
 
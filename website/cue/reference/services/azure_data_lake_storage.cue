@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_data_lake_storage: {
+	name:     "Azure Data Lake Storage"
+	thing:    "an \(name) file system"
+	url:      urls.azure_data_lake_storage
+	versions: null
+
+	description: "[Azure Data Lake Storage](\(urls.azure_data_lake_storage)) Gen2 is a set of big data analytics capabilities built on Azure Blob Storage, adding a hierarchical namespace of directories and files to storage accounts."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_event_hubs: {
+	name:     "Azure Event Hubs"
+	thing:    "an \(name) instance"
+	url:      urls.azure_event_hubs
+	versions: null
+
+	description: "[Azure Event Hubs](\(urls.azure_event_hubs)) is a fully managed, real-time data ingestion service on Azure. Event hubs are partitioned, and consumers read them as part of consumer groups that track their own position in each partition."
+}
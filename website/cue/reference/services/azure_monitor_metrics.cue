@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_monitor_metrics: {
+	name:     "Azure Monitor metrics"
+	thing:    "an \(name) resource"
+	url:      urls.azure_monitor_custom_metrics
+	versions: null
+
+	description: "[Azure Monitor](\(urls.azure_monitor)) is a service in Azure that provides performance and availability monitoring for applications and services in Azure, other cloud environments, or on-premises. [Custom metrics](\(urls.azure_monitor_custom_metrics)) are stored alongside the platform metrics of an Azure resource, where they can be charted and alerted on."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_service_bus: {
+	name:     "Azure Service Bus"
+	thing:    "an \(name) namespace"
+	url:      urls.azure_service_bus
+	versions: null
+
+	description: "[Azure Service Bus](\(urls.azure_service_bus)) is a fully managed enterprise message broker on Azure, with queues for point-to-point delivery and topics for publish-subscribe."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_storage_queue: {
+	name:     "Azure Queue Storage"
+	thing:    "an \(name) queue"
+	url:      urls.azure_storage_queue
+	versions: null
+
+	description: "[Azure Queue Storage](\(urls.azure_storage_queue)) is a service for storing large numbers of messages that can be accessed from anywhere over HTTP or HTTPS, commonly used to trigger Azure Functions."
+}
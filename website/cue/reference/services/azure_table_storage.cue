@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_table_storage: {
+	name:     "Azure Table Storage"
+	thing:    "an \(name) table"
+	url:      urls.azure_table_storage
+	versions: null
+
+	description: "[Azure Table Storage](\(urls.azure_table_storage)) is a service that stores large amounts of structured, non-relational data as entities in schemaless tables."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: grpc: {
+	name:     "gRPC"
+	thing:    "a \(name) client"
+	url:      urls.grpc
+	versions: null
+
+	description: "[gRPC](\(urls.grpc)) is a remote procedure call framework, whose services and messages are defined with [Protocol Buffers](\(urls.protobuf))."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow"
+	thing:    "a \(name) or IPFIX exporter"
+	url:      urls.netflow
+	versions: null
+
+	description: "[NetFlow](\(urls.netflow)) and its standardized successor [IPFIX](\(urls.ipfix)) are protocols for exporting IP flow records from routers, switches, and firewalls."
+}
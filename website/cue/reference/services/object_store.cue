@@ -0,0 +1,10 @@
+package metadata
+
+services: object_store: {
+	name:     "S3-compatible object store"
+	thing:    "an \(name) bucket"
+	url:      urls.aws_s3
+	versions: null
+
+	description: "Object stores such as [MinIO](\(urls.minio)) and [Ceph RGW](\(urls.ceph_rgw)) implement the [Amazon S3](\(urls.aws_s3)) API, so that they can be used in place of S3 for storing log data."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: sflow: {
+	name:     "sFlow"
+	thing:    "an \(name) agent"
+	url:      urls.sflow
+	versions: "= 5"
+
+	description: "[sFlow](\(urls.sflow)) is a sampling technology for monitoring switches and routers, which export packet samples and interface counters over UDP."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: windows_eventlog: {
+	name:     "Windows Event Log"
+	thing:    "the \(name)"
+	url:      urls.windows_eventlog
+	versions: null
+
+	description: "The [Windows Event Log](\(urls.windows_eventlog)) records the events of the operating system, its services, and applications, such as errors, audits, and state changes, in channels like `System`, `Application`, and `Security`."
+}
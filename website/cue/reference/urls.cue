@@ -3,6 +3,12 @@ package metadata
 urls: {
 	appsignal:                                  "https://www.appsignal.com/"
 	azure_blob_storage:                         "https://azure.microsoft.com/en-us/services/storage/blobs/"
+	azure_data_explorer:                        "https://learn.microsoft.com/en-us/azure/data-explorer/"
+	azure_data_explorer_queued_ingestion:       "https://learn.microsoft.com/en-us/azure/data-explorer/ingest-data-overview#queued-ingestion"
+	azure_data_explorer_permissions:            "https://learn.microsoft.com/en-us/azure/data-explorer/kusto/access-control/role-based-access-control"
+	azure_data_lake_storage:                    "https://learn.microsoft.com/en-us/azure/storage/blobs/data-lake-storage-introduction"
+	azure_data_lake_storage_api:                "https://learn.microsoft.com/en-us/rest/api/storageservices/data-lake-storage-gen2"
+	azure_data_lake_storage_hns:                "https://learn.microsoft.com/en-us/azure/storage/blobs/data-lake-storage-namespace"
 	azure_event_hubs:                           "https://learn.microsoft.com/en-us/azure/event-hubs/"
 	azure_event_hubs_kafka:                     "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-for-kafka-ecosystem-overview"
 	azure_event_hubs_connection_string:         "https://learn.microsoft.com/en-us/azure/event-hubs/event-hubs-get-connection-string"
@@ -93,8 +99,21 @@ urls: {
 	axiom_cloud:                                "https://cloud.axiom.co"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_functions_queue_trigger:              "https://learn.microsoft.com/en-us/azure/azure-functions/functions-bindings-storage-queue-trigger"
+	azure_logs_ingestion_api:                   "https://learn.microsoft.com/en-us/azure/azure-monitor/logs/logs-ingestion-api-overview"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
+	azure_monitor_custom_metrics:               "https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/metrics-custom-overview"
+	azure_monitor_custom_metrics_api:           "https://learn.microsoft.com/en-us/azure/azure-monitor/essentials/metrics-store-custom-rest-api"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
+	azure_monitor_logs_migration:               "https://learn.microsoft.com/en-us/azure/azure-monitor/logs/custom-logs-migrate"
+	azure_service_bus:                          "https://learn.microsoft.com/en-us/azure/service-bus-messaging/"
+	azure_service_bus_batch:                    "https://learn.microsoft.com/en-us/rest/api/servicebus/send-message-batch"
+	azure_service_bus_sessions:                 "https://learn.microsoft.com/en-us/azure/service-bus-messaging/message-sessions"
+	azure_storage_queue:                        "https://learn.microsoft.com/en-us/azure/storage/queues/storage-queues-introduction"
+	azure_storage_queue_put_message:            "https://learn.microsoft.com/en-us/rest/api/storageservices/put-message"
+	azure_table_storage:                        "https://learn.microsoft.com/en-us/azure/storage/tables/table-storage-overview"
+	azure_table_storage_batch:                  "https://learn.microsoft.com/en-us/rest/api/storageservices/performing-entity-group-transactions"
+	azure_table_storage_data_model:             "https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-the-table-service-data-model"
 	base16:                                     "\(wikipedia)/wiki/Hexadecimal"
 	base64:                                     "\(wikipedia)/wiki/Base64"
 	base64_padding:                             "\(wikipedia)/wiki/Base64#Output_padding"
@@ -106,6 +125,7 @@ urls: {
 	b_tree_map:                                 "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
 	cargo_audit:                                "\(github)/RustSec/cargo-audit"
 	centos:                                     "https://www.centos.org/"
+	ceph_rgw:                                   "https://docs.ceph.com/en/latest/radosgw/"
 	chrono_time_formats:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                 "https://clickhouse.com/"
@@ -226,10 +246,12 @@ urls: {
 	gcs_predefined_acl:                         "\(gcp)/storage/docs/access-control/lists#predefined-acl"
 	gcs_storage_classes:                        "\(gcp)/storage/docs/storage-classes"
 	gcs_custom_metadata:                        "\(gcp)/storage/docs/metadata#custom-metadata"
+	gcs_pubsub_notifications:                   "\(gcp)/storage/docs/pubsub-notifications"
 	git:                                        "https://git-scm.com/"
 	github:                                     "https://github.com"
 	github_protected_branches:                  "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
+	github_webhooks:                            "https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                       "\(github)/google/glog"
 	graphql:                                    "https://graphql.org"
@@ -238,6 +260,7 @@ urls: {
 	greptimecloud:                              "https://greptime.cloud"
 	greptimedb:                                 "https://github.com/greptimeteam/greptimedb"
 	greptimedb_docs:                            "https://docs.greptime.com/"
+	grpc:                                       "https://grpc.io"
 	grpc_status_code:                           "https://grpc.github.io/grpc/core/md_doc_statuscodes.html"
 	grok:                                       "https://github.com/daschl/grok/tree/master/patterns"
 	grok_debugger:                              "https://grokdebug.herokuapp.com/"
@@ -275,6 +298,8 @@ urls: {
 	influxdb_authentication_token:              "https://v2.docs.influxdata.com/v2.0/security/tokens/"
 	influxdb_line_protocol:                     "https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/"
 	inode:                                      "\(wikipedia)/wiki/Inode"
+	ipfix:                                      "https://www.rfc-editor.org/rfc/rfc7011"
+	ipfix_information_elements:                 "https://www.iana.org/assignments/ipfix/ipfix.xhtml"
 	ip_aton:                                    "https://linux.die.net/man/3/inet_aton"
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
@@ -358,16 +383,20 @@ urls: {
 	memory_safety:                              "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                         "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
 	metric_event_source:                        "\(vector_repo)/blob/master/src/event/metric.rs"
+	minio:                                      "https://min.io/"
 	mlua:                                       "\(github)/khvzak/mlua"
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
 	mqtt:                                       "https://mqtt.org/"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
+	mysql:                                      "https://www.mysql.com/"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	netflow:                                    "https://www.cisco.com/c/en/us/products/ios-nx-os-software/ios-netflow/index.html"
+	netflow_v9:                                 "https://www.rfc-editor.org/rfc/rfc3954"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -391,6 +420,7 @@ urls: {
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	pagerduty_webhooks:                         "https://developer.pagerduty.com/docs/webhooks-overview"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -462,6 +492,8 @@ urls: {
 	rustup:                                     "https://rustup.rs"
 	redis:                                      "https://redis.io"
 	redis_rs:                                   "https://github.com/mitsuhiko/redis-rs"
+	redis_streams:                              "https://redis.io/docs/latest/develop/data-types/streams/"
+	s3_list_objects_v2:                         "https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectsV2.html"
 	seahash:                                    "https://docs.rs/seahash/latest/seahash/"
 	sematext:                                   "https://sematext.com"
 	sematext_create_logs_app:                   "https://apps.sematext.com/ui/integrations"
@@ -470,6 +502,8 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sflow:                                      "https://sflow.org"
+	sflow_v5:                                   "https://sflow.org/sflow_version_5.txt"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
@@ -498,6 +532,7 @@ urls: {
 	exec:                                       "\(wikipedia)/wiki/Exec_(system_call)"
 	stdout:                                     "\(wikipedia)/wiki/Standard_streams#Standard_output_(stdout)"
 	stripe_blog_canonical_log_lines:            "https://stripe.com/blog/canonical-log-lines"
+	stripe_webhooks:                            "https://docs.stripe.com/webhooks#verify-events"
 	strptime_specifiers:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	sysfs:                                      "https://www.kernel.org/doc/html/latest/filesystems/sysfs.html"
 	syslog:                                     "\(wikipedia)/wiki/Syslog"
@@ -658,6 +693,7 @@ urls: {
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_eventlog:                           "https://learn.microsoft.com/en-us/windows/win32/wes/windows-event-log"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"